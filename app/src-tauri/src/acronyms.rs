@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::diff::added_lines;
+
+/// Acronyms readers are expected to know; style guides do not ask for these to be
+/// spelled out.
+const WELL_KNOWN: &[&str] = &[
+    "AM", "API", "CPU", "CSS", "CSV", "FAQ", "FIXME", "GIF", "HTML", "HTTP", "HTTPS", "ID",
+    "JPEG", "JPG", "JSON", "OK", "OS", "PDF", "PM", "PNG", "README", "SQL", "SVG", "TBD",
+    "TODO", "UI", "UK", "URL", "US", "USB", "UTC", "XML", "YAML",
+];
+
+/// Uppercase words docs use for emphasis and admonitions (`[!NOTE]`, RFC 2119 keywords)
+/// rather than as abbreviations.
+const SHOUTED_WORDS: &[&str] = &[
+    "CAUTION", "DANGER", "DEPRECATED", "ERROR", "IMPORTANT", "INFO", "MAY", "MUST", "NOT",
+    "NOTE", "OPTIONAL", "RECOMMENDED", "REQUIRED", "SHALL", "SHOULD", "TIP", "WARNING",
+];
+
+/// An acronym whose first use in the file is on a changed line and is not spelled out.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AcronymFinding {
+    pub path: String,
+    pub acronym: String,
+    /// 1-based head line of the first use.
+    pub line: u64,
+    /// Line of a later spelled-out use, when the definition exists but comes too late.
+    pub defined_at: Option<u64>,
+}
+
+fn acronym_regex() -> &'static Regex {
+    static ACRONYM: OnceLock<Regex> = OnceLock::new();
+    ACRONYM.get_or_init(|| {
+        Regex::new(r"\b[A-Z][A-Z0-9&]*[A-Z]s?\b").expect("acronym pattern is valid")
+    })
+}
+
+/// Text of one line with inline code spans removed.
+fn prose(line: &str) -> String {
+    line.split('`').step_by(2).collect::<Vec<_>>().join(" ")
+}
+
+/// Whether `text` spells out `acronym`, as `Long Form (ACR)` or `ACR (Long form)`.
+fn defines(text: &str, acronym: &str) -> bool {
+    let first = acronym.chars().next().map(|c| c.to_ascii_lowercase());
+    if let Some(index) = text.find(&format!("({acronym})")) {
+        let before = text[..index].trim_end();
+        if before.ends_with(|c: char| c.is_alphabetic()) {
+            return true;
+        }
+    }
+    text.match_indices(acronym).any(|(index, _)| {
+        let after = text[index + acronym.len()..].trim_start();
+        after
+            .strip_prefix('(')
+            .and_then(|inner| inner.chars().next())
+            .is_some_and(|c| Some(c.to_ascii_lowercase()) == first)
+    })
+}
+
+/// Check `content` (the head version of `path`). `changed` holds the 1-based lines the
+/// PR adds; `None` treats the whole file as new. Fenced code blocks and inline code are
+/// ignored, and a plural `s` is folded into the acronym.
+pub fn check_content(
+    path: &str,
+    content: &str,
+    changed: Option<&HashSet<u64>>,
+) -> Vec<AcronymFinding> {
+    let mut first_use: HashMap<String, u64> = HashMap::new();
+    let mut defined_at: HashMap<String, u64> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let line_number = index as u64 + 1;
+        let text = prose(line);
+        for found in acronym_regex().find_iter(&text) {
+            let raw = found.as_str();
+            let acronym = raw.strip_suffix('s').unwrap_or(raw);
+            if acronym.len() < 2
+                || WELL_KNOWN.contains(&acronym)
+                || SHOUTED_WORDS.contains(&raw)
+            {
+                continue;
+            }
+            if !first_use.contains_key(acronym) {
+                first_use.insert(acronym.to_string(), line_number);
+                order.push(acronym.to_string());
+            }
+            if !defined_at.contains_key(acronym) && defines(&text, acronym) {
+                defined_at.insert(acronym.to_string(), line_number);
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|acronym| {
+            let line = first_use[&acronym];
+            let definition = defined_at.get(&acronym).copied();
+            let introduced = changed.is_none_or(|lines| lines.contains(&line));
+            (introduced && definition != Some(line)).then(|| AcronymFinding {
+                path: path.to_string(),
+                line,
+                defined_at: definition,
+                acronym,
+            })
+        })
+        .collect()
+}
+
+/// Check a changed file given its head content and the PR patch, if GitHub sent one.
+pub fn check_file(path: &str, head_content: &str, patch: Option<&str>) -> Vec<AcronymFinding> {
+    let changed: Option<HashSet<u64>> =
+        patch.map(|patch| added_lines(patch).into_iter().map(|(line, _)| line).collect());
+    check_content(path, head_content, changed.as_ref())
+}
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dialogs;
+use crate::error::{AppError, AppResult};
+use crate::i18n::{t, tf, Msg};
+use crate::review_storage::{self, ReviewStorage};
+use crate::settings;
+
+pub const HOOKS_ALLOW_LIST_KEY: &str = "hooks_allow_list";
+pub const SNIPPET_CHECKS_ALLOW_LIST_KEY: &str = "snippet_checks_allow_list";
+
+/// Programs and hosts the user approved in a native dialog. Stored under its own key,
+/// apart from the config that uses it, and never accepted from the webview, so saving a
+/// config cannot also grant what it runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AllowList {
+    /// Absolute program paths.
+    pub programs: Vec<String>,
+    pub hosts: Vec<String>,
+}
+
+impl AllowList {
+    /// Reject `program` unless it is an absolute path on the list. Bare names are refused
+    /// outright, since they resolve through `PATH` to whatever is found first.
+    pub fn check_program(&self, program: &str, feature: &str) -> AppResult<()> {
+        ensure_absolute_program(program)?;
+        if !self.programs.iter().any(|p| p == program) {
+            return Err(AppError::InvalidInput(format!(
+                "program `{program}` is not in the {feature} allow-list"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn check_host(&self, host: &str, feature: &str) -> AppResult<()> {
+        if !self.hosts.iter().any(|h| h.eq_ignore_ascii_case(host)) {
+            return Err(AppError::InvalidInput(format!(
+                "host `{host}` is not in the {feature} allow-list"
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub fn ensure_absolute_program(program: &str) -> AppResult<()> {
+    if !Path::new(program).is_absolute() {
+        return Err(AppError::InvalidInput(format!(
+            "program `{program}` must be an absolute path"
+        )));
+    }
+    Ok(())
+}
+
+pub fn load(storage: &ReviewStorage, key: &str) -> AppResult<AllowList> {
+    settings::load(storage, key)
+}
+
+pub fn save(storage: &ReviewStorage, key: &str, list: &AllowList) -> AppResult<()> {
+    for program in &list.programs {
+        ensure_absolute_program(program)?;
+    }
+    settings::save(storage, key, list)
+}
+
+/// Ask in a native dialog for each of `programs` and `hosts` the list under `key` does
+/// not hold yet, and add the ones the user approves. `feature` names what is asking in
+/// the dialog. Declined entries stay off the list, so the save that needed them fails
+/// its own allow-list check.
+pub async fn request(
+    app: &tauri::AppHandle,
+    key: &str,
+    feature: Msg,
+    programs: &[String],
+    hosts: &[String],
+) -> AppResult<AllowList> {
+    let storage = review_storage::get_storage()?;
+    let mut list = load(storage, key)?;
+    let mut changed = false;
+
+    for program in programs {
+        ensure_absolute_program(program)?;
+        if list.programs.contains(program) {
+            continue;
+        }
+        if dialogs::confirm(app, tf(Msg::AllowProgramPrompt, &[&t(feature), program])).await? {
+            list.programs.push(program.clone());
+            changed = true;
+        }
+    }
+    for host in hosts {
+        if list.hosts.iter().any(|h| h.eq_ignore_ascii_case(host)) {
+            continue;
+        }
+        if dialogs::confirm(app, tf(Msg::AllowHostPrompt, &[&t(feature), host])).await? {
+            list.hosts.push(host.to_ascii_lowercase());
+            changed = true;
+        }
+    }
+
+    if changed {
+        save(storage, key, &list)?;
+    }
+    Ok(list)
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::diff::added_lines;
+use crate::i18n::{tf, Msg};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AltTextIssue {
+    /// `![](...)` or `alt=""`.
+    Empty,
+    /// An `<img>` tag without an `alt` attribute.
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AltTextFinding {
+    pub path: String,
+    /// 1-based head line.
+    pub line: u64,
+    /// Image source as written, or the reference label for `![][label]`.
+    pub image: String,
+    pub issue: AltTextIssue,
+    pub suggested_comment: String,
+}
+
+fn markdown_image_regex() -> &'static Regex {
+    static IMAGE: OnceLock<Regex> = OnceLock::new();
+    IMAGE.get_or_init(|| {
+        Regex::new(r"!\[([^\]]*)\](?:\(\s*<?([^)\s>]*)|\[([^\]]*)\])")
+            .expect("markdown image pattern is valid")
+    })
+}
+
+fn html_image_regex() -> &'static Regex {
+    static IMG: OnceLock<Regex> = OnceLock::new();
+    IMG.get_or_init(|| Regex::new(r"(?i)<img\b[^>]*>").expect("img tag pattern is valid"))
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!(r#"(?i)\b{name}\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#);
+    let caps = Regex::new(&pattern).ok()?.captures(tag)?;
+    caps.iter()
+        .skip(1)
+        .flatten()
+        .next()
+        .map(|m| m.as_str().to_string())
+}
+
+fn finding(path: &str, line: u64, image: String, issue: AltTextIssue) -> AltTextFinding {
+    AltTextFinding {
+        suggested_comment: tf(Msg::AltTextComment, &[&image]),
+        path: path.to_string(),
+        line,
+        image,
+        issue,
+    }
+}
+
+/// Images on one line without usable alt text. Inline code is skipped.
+pub fn scan_line(path: &str, line_number: u64, text: &str) -> Vec<AltTextFinding> {
+    let prose: String = text.split('`').step_by(2).collect::<Vec<_>>().join(" ");
+    let mut findings = Vec::new();
+
+    for caps in markdown_image_regex().captures_iter(&prose) {
+        if caps[1].trim().is_empty() {
+            let image = caps.get(2).or(caps.get(3)).map_or("", |m| m.as_str());
+            findings.push(finding(path, line_number, image.to_string(), AltTextIssue::Empty));
+        }
+    }
+
+    for tag in html_image_regex().find_iter(&prose) {
+        let image = attribute(tag.as_str(), "src").unwrap_or_default();
+        match attribute(tag.as_str(), "alt") {
+            None => findings.push(finding(path, line_number, image, AltTextIssue::Missing)),
+            Some(alt) if alt.trim().is_empty() => {
+                findings.push(finding(path, line_number, image, AltTextIssue::Empty))
+            }
+            Some(_) => {}
+        }
+    }
+
+    findings
+}
+
+/// Check the head version of a markdown file. With a `patch`, only images on added
+/// lines are reported. Fenced code blocks are skipped.
+pub fn check_file(path: &str, head_content: &str, patch: Option<&str>) -> Vec<AltTextFinding> {
+    let changed: Option<HashSet<u64>> =
+        patch.map(|patch| added_lines(patch).into_iter().map(|(line, _)| line).collect());
+    let mut in_fence = false;
+    let mut findings = Vec::new();
+
+    for (index, line) in head_content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        let line_number = index as u64 + 1;
+        if in_fence || changed.as_ref().is_some_and(|c| !c.contains(&line_number)) {
+            continue;
+        }
+        findings.extend(scan_line(path, line_number, line));
+    }
+
+    findings
+}
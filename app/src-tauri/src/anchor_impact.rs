@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::link_index::{anchors, LinkIndex};
+
+/// A heading anchor the change removes, usually because the heading was renamed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedAnchor {
+    /// Base path of the file, which is what existing links point at.
+    pub path: String,
+    pub old_anchor: String,
+    /// The anchor that took its place, when headings were renamed one for one.
+    pub new_anchor: Option<String>,
+    /// 1-based head line of the new heading, when known.
+    pub line: Option<u64>,
+}
+
+/// A link that still points at an anchor the change removes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BrokenAnchorLink {
+    pub source_path: String,
+    /// 1-based line of the link in the head version of `source_path`.
+    pub line: u64,
+    pub target_path: String,
+    pub anchor: String,
+    pub suggested_anchor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AnchorImpactReport {
+    pub changed_anchors: Vec<ChangedAnchor>,
+    pub broken_links: Vec<BrokenAnchorLink>,
+    pub indexed_files: usize,
+    /// The link index covered only part of the repository's markdown files.
+    pub truncated: bool,
+}
+
+/// Anchors in `base` that are gone from `head`. When as many anchors were added as were
+/// removed, they are paired in order as renames.
+pub fn changed_anchors(path: &str, base: &str, head: &str) -> Vec<ChangedAnchor> {
+    let before = anchors(base);
+    let after = anchors(head);
+    let before_slugs: HashSet<&str> = before.iter().map(|a| a.slug.as_str()).collect();
+    let after_slugs: HashSet<&str> = after.iter().map(|a| a.slug.as_str()).collect();
+
+    let removed: Vec<_> = before
+        .iter()
+        .filter(|a| !after_slugs.contains(a.slug.as_str()))
+        .collect();
+    let added: Vec<_> = after
+        .iter()
+        .filter(|a| !before_slugs.contains(a.slug.as_str()))
+        .collect();
+    let renamed = removed.len() == added.len();
+
+    removed
+        .iter()
+        .enumerate()
+        .map(|(index, old)| {
+            let new = renamed.then(|| added[index]);
+            ChangedAnchor {
+                path: path.to_string(),
+                old_anchor: old.slug.clone(),
+                new_anchor: new.map(|a| a.slug.clone()),
+                line: new.map(|a| a.line),
+            }
+        })
+        .collect()
+}
+
+/// Links in `index` that point at one of `changes`.
+pub fn report(changed_anchors: Vec<ChangedAnchor>, index: &LinkIndex) -> AnchorImpactReport {
+    let broken_links = changed_anchors
+        .iter()
+        .flat_map(|change| {
+            index
+                .links_to(&change.path, &change.old_anchor)
+                .map(|link| BrokenAnchorLink {
+                    source_path: link.source_path.clone(),
+                    line: link.line,
+                    target_path: link.target_path.clone(),
+                    anchor: link.anchor.clone(),
+                    suggested_anchor: change.new_anchor.clone(),
+                })
+        })
+        .collect();
+
+    AnchorImpactReport {
+        changed_anchors,
+        broken_links,
+        indexed_files: index.indexed_files,
+        truncated: index.truncated,
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::events;
+
+pub const API_VERSION_HEADER: &str = "x-github-api-version";
+pub const API_VERSION: &str = "2022-11-28";
+/// GitHub echoes the version it actually served.
+const SELECTED_VERSION_HEADER: &str = "x-github-api-version-selected";
+
+/// Emitted with an `ApiDeprecation` payload the first time a warning is seen.
+pub const DEPRECATION_EVENT: &str = "api-deprecation";
+
+static SEEN: Mutex<Vec<ApiDeprecation>> = Mutex::new(Vec::new());
+
+/// A sign from GitHub that a request will stop working as sent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ApiDeprecation {
+    /// What the app was doing, e.g. `fetch pull request files`.
+    pub context: String,
+    /// Request path without the query string.
+    pub endpoint: String,
+    /// A `Deprecation` header was present.
+    pub deprecated: bool,
+    /// When the endpoint was deprecated, RFC 3339, if the header carried a date.
+    pub deprecated_at: Option<String>,
+    /// When the endpoint stops working, RFC 3339, from the `Sunset` header.
+    pub sunset_at: Option<String>,
+    /// Documentation from a `Link` with `rel="deprecation"` or `rel="sunset"`.
+    pub link: Option<String>,
+    /// The version GitHub served, when it differs from the one requested.
+    pub selected_version: Option<String>,
+    /// GitHub rejected the requested version outright.
+    pub unsupported_version: bool,
+    pub first_seen_at: String,
+}
+
+impl ApiDeprecation {
+    fn new(context: &str, endpoint: &str) -> Self {
+        Self {
+            context: context.to_string(),
+            endpoint: endpoint.to_string(),
+            deprecated: false,
+            deprecated_at: None,
+            sunset_at: None,
+            link: None,
+            selected_version: None,
+            unsupported_version: false,
+            first_seen_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+pub fn version_header() -> (HeaderName, HeaderValue) {
+    (
+        HeaderName::from_static(API_VERSION_HEADER),
+        HeaderValue::from_static(API_VERSION),
+    )
+}
+
+/// An HTTP date (`Sunset`) or `@<unix seconds>` (`Deprecation`) as RFC 3339 UTC.
+fn header_date(value: &str) -> Option<String> {
+    let value = value.trim();
+    let instant = match value.strip_prefix('@') {
+        Some(seconds) => DateTime::from_timestamp(seconds.parse().ok()?, 0)?,
+        None => DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc),
+    };
+    Some(instant.to_rfc3339())
+}
+
+/// Target of the first `Link` entry whose relation is `deprecation` or `sunset`.
+fn deprecation_link(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all("link")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|entry| {
+            let (target, params) = entry.split_once(';')?;
+            let relation = params.split(';').any(|param| {
+                let param = param.trim().to_ascii_lowercase();
+                param == "rel=\"deprecation\"" || param == "rel=\"sunset\""
+            });
+            relation.then(|| target.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+        })
+}
+
+/// Deprecation signals in a response's headers, if any.
+pub fn detect(headers: &HeaderMap, context: &str, endpoint: &str) -> Option<ApiDeprecation> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let mut warning = ApiDeprecation::new(context, endpoint);
+    if let Some(value) = header("deprecation") {
+        warning.deprecated = !value.trim().eq_ignore_ascii_case("false");
+        warning.deprecated_at = header_date(value);
+    }
+    warning.sunset_at = header("sunset").and_then(header_date);
+    warning.selected_version = header(SELECTED_VERSION_HEADER)
+        .map(str::trim)
+        .filter(|version| !version.is_empty() && *version != API_VERSION)
+        .map(str::to_string);
+
+    if !warning.deprecated && warning.sunset_at.is_none() && warning.selected_version.is_none() {
+        return None;
+    }
+    warning.link = deprecation_link(headers);
+    Some(warning)
+}
+
+/// A 400 GitHub returns when it no longer accepts the requested API version.
+pub fn is_unsupported_version(status: StatusCode, body: &str) -> bool {
+    status == StatusCode::BAD_REQUEST && body.to_ascii_lowercase().contains("api version")
+}
+
+pub fn unsupported_version(context: &str, endpoint: &str) -> ApiDeprecation {
+    ApiDeprecation {
+        unsupported_version: true,
+        ..ApiDeprecation::new(context, endpoint)
+    }
+}
+
+/// Keep a warning for `recorded`, once per request context, and announce it when new.
+pub fn record(warning: ApiDeprecation) {
+    let Ok(mut seen) = SEEN.lock() else {
+        return;
+    };
+    if seen.iter().any(|existing| {
+        existing.context == warning.context
+            && existing.unsupported_version == warning.unsupported_version
+    }) {
+        return;
+    }
+
+    warn!(
+        context = warning.context.as_str(),
+        endpoint = warning.endpoint.as_str(),
+        sunset_at = warning.sunset_at.as_deref().unwrap_or("unknown"),
+        "GitHub API deprecation reported"
+    );
+    events::emit(DEPRECATION_EVENT, &warning);
+    seen.push(warning);
+}
+
+pub fn recorded() -> Vec<ApiDeprecation> {
+    SEEN.lock().map(|seen| seen.clone()).unwrap_or_default()
+}
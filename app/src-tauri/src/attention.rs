@@ -0,0 +1,70 @@
+use crate::visits::is_after;
+
+/// The parts of a PR timeline that decide whose turn it is.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineActivity {
+    Reviewed { by: String, at: String },
+    Committed { at: String },
+    ReviewRequested { reviewer: String, at: String },
+    /// One review thread, as `(author, created_at)` per comment.
+    Thread { comments: Vec<(String, String)> },
+}
+
+fn latest<'a>(timestamps: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    timestamps.fold(None, |latest, at| match latest {
+        Some(current) if !is_after(at, current) => Some(current),
+        _ => Some(at),
+    })
+}
+
+/// Whether the ball is in `me`'s court on a PR by `author`: my review was (re-)requested
+/// after my last review, commits landed after my last review, or the author replied in a
+/// thread after my last comment there. Always `false` on my own PRs.
+pub fn needs_attention(me: &str, author: &str, activity: &[TimelineActivity]) -> bool {
+    if me.eq_ignore_ascii_case(author) {
+        return false;
+    }
+
+    let last_review = latest(activity.iter().filter_map(|item| match item {
+        TimelineActivity::Reviewed { by, at } if by.eq_ignore_ascii_case(me) => Some(at.as_str()),
+        _ => None,
+    }));
+    let last_request = latest(activity.iter().filter_map(|item| match item {
+        TimelineActivity::ReviewRequested { reviewer, at } if reviewer.eq_ignore_ascii_case(me) => {
+            Some(at.as_str())
+        }
+        _ => None,
+    }));
+
+    if let Some(requested) = last_request {
+        if last_review.is_none_or(|reviewed| is_after(requested, reviewed)) {
+            return true;
+        }
+    }
+
+    if let Some(reviewed) = last_review {
+        let new_commits = activity.iter().any(|item| {
+            matches!(item, TimelineActivity::Committed { at } if is_after(at, reviewed))
+        });
+        if new_commits {
+            return true;
+        }
+    }
+
+    activity.iter().any(|item| {
+        let TimelineActivity::Thread { comments } = item else {
+            return false;
+        };
+        let my_last = latest(
+            comments
+                .iter()
+                .filter(|(by, _)| by.eq_ignore_ascii_case(me))
+                .map(|(_, at)| at.as_str()),
+        );
+        my_last.is_some_and(|mine| {
+            comments
+                .iter()
+                .any(|(by, at)| by.eq_ignore_ascii_case(author) && is_after(at, mine))
+        })
+    })
+}
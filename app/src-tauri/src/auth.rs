@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::{env, io, time::Duration};
 
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
@@ -8,19 +10,92 @@ use tokio::{io::AsyncReadExt, io::AsyncWriteExt, net::TcpListener, net::TcpStrea
 use tracing::info;
 use url::Url;
 
+use crate::cache::TtlCache;
+use crate::emoji::EmojiSet;
 use crate::error::{AppError, AppResult};
+use crate::events;
 use crate::github::{
     create_pending_review, fetch_authenticated_user, get_file_contents, get_pull_request, 
-    list_pull_requests_with_login, submit_file_comment, submit_general_comment, 
-    submit_pending_review, CommentMode,
+    list_pull_request_files, list_pull_requests_with_login, submit_file_comment, submit_general_comment, 
+    submit_pending_review, CommentMode, ContentFormat,
 };
-use crate::models::{AuthStatus, PullRequestDetail, PullRequestReview, PullRequestSummary};
-use crate::storage::{delete_token, read_token, store_token, store_last_login, read_last_login, delete_last_login};
+use crate::models::{
+    AccountProfile, AuthStatus, OrganizationSummary, PullRequestDetail, PullRequestFile,
+    PullRequestReview, PullRequestSummary, RepositorySummary,
+};
+use crate::oauth_scopes;
+use crate::repo_config::{self, RepoReviewConfig};
+use crate::storage::{self, delete_token, read_token, store_token, store_last_login, read_last_login, delete_last_login};
 
 const AUTHORIZE_URL: &str = "https://github.com/login/oauth/authorize";
 const TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const DEVICE_CODE_URL: &str = "https://github.com/login/device/code";
+const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
 const SCOPES: &str = "repo pull_request:write";
 const OAUTH_TIMEOUT: Duration = Duration::from_secs(180);
+const REPOSITORY_CACHE_TTL: Duration = Duration::from_secs(300);
+const EMOJI_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Emitted once when GitHub stops accepting the stored token.
+pub const AUTH_EXPIRED_EVENT: &str = "auth-expired";
+/// Sent during device sign-in with the code the user enters on GitHub.
+pub const DEVICE_CODE_EVENT: &str = "oauth-device-code";
+
+/// How a sign-in gets its token. The browser flow receives GitHub's redirect on a local
+/// port; the device flow needs no listener, for machines where binding one is blocked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginMethod {
+    #[default]
+    Browser,
+    Device,
+}
+
+/// The code to enter at `verification_uri` to finish a device sign-in.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DeviceCodePrompt {
+    pub user_code: String,
+    pub verification_uri: String,
+    /// Seconds until the code expires.
+    pub expires_in: u64,
+}
+
+/// What one poll of the device flow's token endpoint said.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DevicePoll {
+    Token(String),
+    Pending,
+    /// Polling too often; wait `interval` seconds from now on.
+    SlowDown(u64),
+}
+
+static SESSION_EXPIRED: AtomicBool = AtomicBool::new(false);
+
+fn repository_cache() -> &'static TtlCache<Vec<RepositorySummary>> {
+    static CACHE: OnceLock<TtlCache<Vec<RepositorySummary>>> = OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(REPOSITORY_CACHE_TTL))
+}
+
+fn organization_cache() -> &'static TtlCache<Vec<OrganizationSummary>> {
+    static CACHE: OnceLock<TtlCache<Vec<OrganizationSummary>>> = OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(REPOSITORY_CACHE_TTL))
+}
+
+fn emoji_cache() -> &'static TtlCache<Arc<EmojiSet>> {
+    static CACHE: OnceLock<TtlCache<Arc<EmojiSet>>> = OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(EMOJI_CACHE_TTL))
+}
+
+/// Link indexes keyed by `owner/repo@sha`; a commit's files never change.
+fn link_index_cache() -> &'static TtlCache<Arc<crate::link_index::LinkIndex>> {
+    static CACHE: OnceLock<TtlCache<Arc<crate::link_index::LinkIndex>>> = OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(REPOSITORY_CACHE_TTL))
+}
+
+fn repo_config_cache() -> &'static TtlCache<Option<RepoReviewConfig>> {
+    static CACHE: OnceLock<TtlCache<Option<RepoReviewConfig>>> = OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(REPOSITORY_CACHE_TTL))
+}
 
 /// Helper function to detect network-related errors
 fn is_network_error(err: &AppError) -> bool {
@@ -58,6 +133,12 @@ pub async fn check_auth_status() -> AppResult<AuthStatus> {
                 })
             }
             Err(err) => match err {
+                AppError::AuthExpired => Ok(AuthStatus {
+                    is_authenticated: false,
+                    login: None,
+                    avatar_url: None,
+                    is_offline: false,
+                }),
                 AppError::Http(http_err) => {
                     if http_err.status() == Some(StatusCode::UNAUTHORIZED) {
                         // Token explicitly rejected - clear credentials
@@ -137,16 +218,155 @@ pub async fn check_auth_status() -> AppResult<AuthStatus> {
     }
 }
 
+/// Forget cached repository and organization listings, e.g. after the owner policy
+/// changes what they may contain.
+pub fn clear_listing_caches() {
+    repository_cache().clear();
+    organization_cache().clear();
+    crate::github::clear_discussion_cache();
+}
+
+/// GitHub answered 401: the token was revoked or expired. Forget it and tell the UI, once
+/// per session.
+pub fn expire_session(context: &str) {
+    delete_token().ok();
+    delete_last_login().ok();
+    clear_listing_caches();
+    if !SESSION_EXPIRED.swap(true, Ordering::SeqCst) {
+        tracing::warn!(context = context, "GitHub rejected the stored token; signed out");
+        events::emit(AUTH_EXPIRED_EVENT, serde_json::json!({ "context": context }));
+    }
+}
+
+/// Whether the token was rejected since the last sign-in.
+pub fn session_expired() -> bool {
+    SESSION_EXPIRED.load(Ordering::SeqCst)
+}
+
 pub async fn logout() -> AppResult<()> {
     delete_token()?;
+    clear_listing_caches();
     delete_last_login().ok(); // Best effort - don't fail logout if this fails
     Ok(())
 }
 
-pub async fn start_oauth_flow(_app: &tauri::AppHandle) -> AppResult<AuthStatus> {
+/// The saved accounts, in the order they were added.
+pub fn list_accounts() -> AppResult<Vec<AccountProfile>> {
+    let index = storage::read_profiles()?;
+    index
+        .profiles
+        .iter()
+        .map(|name| {
+            Ok(AccountProfile {
+                name: name.clone(),
+                login: storage::read_profile_login(name)?,
+                signed_in: storage::read_profile_token(name)?.is_some(),
+                active: *name == index.active,
+            })
+        })
+        .collect()
+}
+
+/// Make `name` the account requests use. Listings cached for the previous account are
+/// dropped so they do not show up under the new one.
+pub async fn switch_account(name: &str) -> AppResult<AuthStatus> {
+    let mut index = storage::read_profiles()?;
+    index.switch_to(name)?;
+    storage::store_profiles(&index)?;
+    clear_listing_caches();
+    SESSION_EXPIRED.store(false, Ordering::SeqCst);
+    check_auth_status().await
+}
+
+/// Add an account named `name`, switch to it and sign in. If sign-in fails the account is
+/// dropped again and the previous one stays active.
+pub async fn add_account(name: &str, method: LoginMethod) -> AppResult<AuthStatus> {
+    let mut index = storage::read_profiles()?;
+    let previous = index.active.clone();
+    index.add(name)?;
+    index.switch_to(name)?;
+    storage::store_profiles(&index)?;
+    clear_listing_caches();
+
+    match run_oauth_flow(SCOPES, method).await {
+        Ok(status) => Ok(status),
+        Err(err) => {
+            storage::delete_profile_credentials(name).ok();
+            index.remove(name)?;
+            index.switch_to(&previous)?;
+            storage::store_profiles(&index)?;
+            Err(err)
+        }
+    }
+}
+
+/// Forget an account and its token. Removing the active account switches to the next.
+pub fn remove_account(name: &str) -> AppResult<Vec<AccountProfile>> {
+    let mut index = storage::read_profiles()?;
+    let was_active = index.active == name;
+    index.remove(name)?;
+    storage::delete_profile_credentials(name)?;
+    storage::store_profiles(&index)?;
+    if was_active {
+        clear_listing_caches();
+        SESSION_EXPIRED.store(false, Ordering::SeqCst);
+    }
+    list_accounts()
+}
+
+/// Sign out of every account and forget them all.
+pub fn forget_all_accounts() -> AppResult<()> {
+    for name in storage::read_profiles()?.profiles {
+        storage::delete_profile_credentials(&name)?;
+    }
+    storage::store_profiles(&storage::ProfileIndex::default())?;
+    clear_listing_caches();
+    Ok(())
+}
+
+pub async fn start_oauth_flow(
+    _app: &tauri::AppHandle,
+    method: LoginMethod,
+) -> AppResult<AuthStatus> {
+    run_oauth_flow(SCOPES, method).await
+}
+
+/// Sign in again asking GitHub for `scope` on top of the usual ones, after a request was
+/// refused for lacking it.
+pub async fn upgrade_oauth_scope(
+    _app: &tauri::AppHandle,
+    scope: &str,
+    method: LoginMethod,
+) -> AppResult<AuthStatus> {
+    run_oauth_flow(&oauth_scopes::with_scope(SCOPES, scope), method).await
+}
+
+async fn run_oauth_flow(scopes: &str, method: LoginMethod) -> AppResult<AuthStatus> {
     dotenvy::dotenv().ok();
     let client_id =
         env::var("GITHUB_CLIENT_ID").map_err(|_| AppError::MissingConfig("GITHUB_CLIENT_ID"))?;
+
+    let token = match method {
+        LoginMethod::Browser => browser_flow_token(&client_id, scopes).await?,
+        LoginMethod::Device => device_flow_token(&client_id, scopes).await?,
+    };
+
+    store_token(&token)?;
+    SESSION_EXPIRED.store(false, Ordering::SeqCst);
+    let user = fetch_authenticated_user(&token).await?;
+    
+    // Store login for offline use
+    store_last_login(&user.login).ok();
+
+    Ok(AuthStatus {
+        is_authenticated: true,
+        login: Some(user.login.clone()),
+        avatar_url: user.avatar_url,
+        is_offline: false,
+    })
+}
+
+async fn browser_flow_token(client_id: &str, scopes: &str) -> AppResult<String> {
     let client_secret = env::var("GITHUB_CLIENT_SECRET")
         .map_err(|_| AppError::MissingConfig("GITHUB_CLIENT_SECRET"))?;
 
@@ -160,9 +380,9 @@ pub async fn start_oauth_flow(_app: &tauri::AppHandle) -> AppResult<AuthStatus>
 
     let mut url = Url::parse(AUTHORIZE_URL)?;
     url.query_pairs_mut()
-        .append_pair("client_id", &client_id)
+        .append_pair("client_id", client_id)
         .append_pair("redirect_uri", &redirect_uri)
-        .append_pair("scope", SCOPES)
+        .append_pair("scope", scopes)
         .append_pair("state", &state)
         .append_pair("code_challenge", &code_challenge)
         .append_pair("code_challenge_method", "S256");
@@ -176,27 +396,105 @@ pub async fn start_oauth_flow(_app: &tauri::AppHandle) -> AppResult<AuthStatus>
         return Err(AppError::InvalidOAuthCallback);
     }
 
-    let token = exchange_code(
-        &client_id,
+    exchange_code(
+        client_id,
         &client_secret,
         &code,
         &redirect_uri,
         &code_verifier,
     )
-    .await?;
+    .await
+}
 
-    store_token(&token)?;
-    let user = fetch_authenticated_user(&token).await?;
-    
-    // Store login for offline use
-    store_last_login(&user.login).ok();
+#[derive(serde::Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    expires_in: u64,
+    interval: u64,
+}
 
-    Ok(AuthStatus {
-        is_authenticated: true,
-        login: Some(user.login.clone()),
-        avatar_url: user.avatar_url,
-        is_offline: false,
-    })
+/// GitHub's device authorization flow: show the user a code to enter on GitHub, then poll
+/// until they have, without a client secret or a local listener.
+async fn device_flow_token(client_id: &str, scopes: &str) -> AppResult<String> {
+    let client = reqwest::Client::new();
+    let codes: DeviceCodeResponse = client
+        .post(DEVICE_CODE_URL)
+        .header(ACCEPT, "application/json")
+        .form(&[("client_id", client_id), ("scope", scopes)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    events::emit(
+        DEVICE_CODE_EVENT,
+        DeviceCodePrompt {
+            user_code: codes.user_code.clone(),
+            verification_uri: codes.verification_uri.clone(),
+            expires_in: codes.expires_in,
+        },
+    );
+    // Best effort: the code is shown in the app, so the page can be opened by hand
+    if let Err(err) = open::that(&codes.verification_uri) {
+        tracing::warn!("Could not open {}: {err}", codes.verification_uri);
+    }
+
+    let poll = async {
+        let mut interval = codes.interval.max(1);
+        loop {
+            time::sleep(Duration::from_secs(interval)).await;
+            let payload: serde_json::Value = client
+                .post(TOKEN_URL)
+                .header(ACCEPT, "application/json")
+                .form(&[
+                    ("client_id", client_id),
+                    ("device_code", codes.device_code.as_str()),
+                    ("grant_type", DEVICE_GRANT_TYPE),
+                ])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            match device_poll_outcome(&payload, interval)? {
+                DevicePoll::Token(token) => return Ok(token),
+                DevicePoll::Pending => {}
+                DevicePoll::SlowDown(slower) => interval = slower,
+            }
+        }
+    };
+    time::timeout(Duration::from_secs(codes.expires_in), poll).await?
+}
+
+/// Read a device flow token response. GitHub answers 200 with an `error` field while the
+/// user has not finished; `slow_down` adds five seconds to the interval unless it names
+/// a new one.
+pub fn device_poll_outcome(payload: &serde_json::Value, interval: u64) -> AppResult<DevicePoll> {
+    if let Some(token) = payload.get("access_token").and_then(|t| t.as_str()) {
+        return Ok(DevicePoll::Token(token.to_string()));
+    }
+    match payload.get("error").and_then(|e| e.as_str()) {
+        Some("authorization_pending") => Ok(DevicePoll::Pending),
+        Some("slow_down") => Ok(DevicePoll::SlowDown(
+            payload
+                .get("interval")
+                .and_then(|i| i.as_u64())
+                .unwrap_or(interval + 5),
+        )),
+        Some("access_denied") => Err(AppError::OAuthCancelled),
+        Some("expired_token") => Err(AppError::Timeout),
+        error => {
+            let description = payload
+                .get("error_description")
+                .and_then(|d| d.as_str())
+                .or(error)
+                .unwrap_or("no token in the response");
+            Err(AppError::Api(format!("GitHub sign-in failed: {description}")))
+        }
+    }
 }
 
 pub async fn list_repo_pull_requests(
@@ -225,6 +523,88 @@ pub async fn list_repo_pull_requests(
     Ok(pulls)
 }
 
+/// List the user's repositories, served from a short-lived cache unless `force_refresh`.
+pub async fn list_my_repositories(
+    filter: Option<&str>,
+    force_refresh: bool,
+) -> AppResult<Vec<RepositorySummary>> {
+    let cache_key = "@me";
+    let repos = match repository_cache().get(cache_key).filter(|_| !force_refresh) {
+        Some(repos) => repos,
+        None => {
+            let token = require_token()?;
+            let repos = crate::github::list_my_repositories(&token).await?;
+            repository_cache().insert(cache_key, repos.clone());
+            repos
+        }
+    };
+
+    Ok(filter_repositories(repos, filter))
+}
+
+pub async fn list_starred_repositories(force_refresh: bool) -> AppResult<Vec<RepositorySummary>> {
+    let cache_key = "@starred";
+    if let Some(repos) = repository_cache().get(cache_key).filter(|_| !force_refresh) {
+        return Ok(repos);
+    }
+
+    let affiliated = list_my_repositories(None, false).await?;
+    let token = require_token()?;
+    let repos = crate::github::list_starred_repositories(&token, &affiliated).await?;
+    repository_cache().insert(cache_key, repos.clone());
+    Ok(repos)
+}
+
+pub async fn list_org_repositories(
+    org: &str,
+    filter: Option<&str>,
+    force_refresh: bool,
+) -> AppResult<Vec<RepositorySummary>> {
+    let cache_key = org.to_ascii_lowercase();
+    let repos = match repository_cache().get(&cache_key).filter(|_| !force_refresh) {
+        Some(repos) => repos,
+        None => {
+            let token = require_token()?;
+            let repos = crate::github::list_org_repositories(&token, org).await?;
+            repository_cache().insert(&cache_key, repos.clone());
+            repos
+        }
+    };
+
+    Ok(filter_repositories(repos, filter))
+}
+
+pub async fn list_my_organizations(force_refresh: bool) -> AppResult<Vec<OrganizationSummary>> {
+    if let Some(orgs) = organization_cache().get("@me").filter(|_| !force_refresh) {
+        return Ok(orgs);
+    }
+    let token = require_token()?;
+    let orgs = crate::github::list_my_organizations(&token).await?;
+    organization_cache().insert("@me", orgs.clone());
+    Ok(orgs)
+}
+
+/// GitHub's emoji list, fetched once a day; it only changes with GitHub releases.
+pub async fn emoji_set() -> AppResult<Arc<EmojiSet>> {
+    if let Some(set) = emoji_cache().get("all") {
+        return Ok(set);
+    }
+    let token = require_token()?;
+    let set = Arc::new(EmojiSet::from_api(crate::github::list_emoji(&token).await?));
+    emoji_cache().insert("all", set.clone());
+    Ok(set)
+}
+
+fn filter_repositories(
+    repos: Vec<RepositorySummary>,
+    filter: Option<&str>,
+) -> Vec<RepositorySummary> {
+    match filter {
+        Some(filter) => repos.into_iter().filter(|repo| repo.matches(filter)).collect(),
+        None => repos,
+    }
+}
+
 pub async fn fetch_pull_request_details(
     owner: &str,
     repo: &str,
@@ -235,6 +615,205 @@ pub async fn fetch_pull_request_details(
     get_pull_request(&token, owner, repo, number, current_login).await
 }
 
+pub async fn fetch_pull_request_files(
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<Vec<PullRequestFile>> {
+    let token = require_token()?;
+    list_pull_request_files(&token, owner, repo, number).await
+}
+
+pub async fn check_pull_request_template(
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<crate::pr_template::TemplateCheck> {
+    let token = require_token()?;
+    crate::github::check_pull_request_template(&token, owner, repo, number).await
+}
+
+pub async fn get_comment_permalink(
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    comment_id: u64,
+) -> AppResult<String> {
+    let token = require_token()?;
+    crate::github::get_comment_permalink(&token, owner, repo, pr_number, comment_id).await
+}
+
+pub async fn get_comment_author_and_body(
+    owner: &str,
+    repo: &str,
+    comment_id: u64,
+) -> AppResult<(String, String)> {
+    let token = require_token()?;
+    crate::github::get_comment_author_and_body(&token, owner, repo, comment_id).await
+}
+
+pub async fn add_comment_reaction(
+    owner: &str,
+    repo: &str,
+    kind: crate::permalinks::CommentKind,
+    comment_id: u64,
+    content: &str,
+) -> AppResult<()> {
+    let token = require_token()?;
+    crate::github::add_comment_reaction(&token, owner, repo, kind, comment_id, content).await
+}
+
+pub async fn remove_comment_reaction(
+    owner: &str,
+    repo: &str,
+    kind: crate::permalinks::CommentKind,
+    comment_id: u64,
+    content: &str,
+) -> AppResult<bool> {
+    let token = require_token()?;
+    crate::github::remove_comment_reaction(&token, owner, repo, kind, comment_id, content).await
+}
+
+pub async fn check_base_drift(
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<crate::drift::BaseDrift> {
+    let token = require_token()?;
+    crate::github::check_base_drift(&token, owner, repo, number).await
+}
+
+pub async fn changes_since_my_review(
+    owner: &str,
+    repo: &str,
+    number: u64,
+    stored: Option<crate::second_pass::ReviewedCommit>,
+) -> AppResult<crate::second_pass::ChangesSinceReview> {
+    let token = require_token()?;
+    crate::github::changes_since_my_review(&token, owner, repo, number, stored).await
+}
+
+pub async fn get_issue(owner: &str, repo: &str, number: u64) -> AppResult<crate::models::IssueDetail> {
+    let token = require_token()?;
+    crate::github::get_issue(&token, owner, repo, number).await
+}
+
+/// Re-request the signed-in user's review on a PR, optionally posting a nudge comment.
+pub async fn request_review_followup(
+    owner: &str,
+    repo: &str,
+    number: u64,
+    followup: Option<&crate::settings::ReviewFollowup>,
+) -> AppResult<crate::models::ReviewFollowupResult> {
+    let token = require_token()?;
+    let user = fetch_authenticated_user(&token).await?;
+    crate::github::request_review_followup(&token, owner, repo, number, &user.login, followup).await
+}
+
+pub async fn review_requested_at(owner: &str, repo: &str, number: u64) -> AppResult<Option<String>> {
+    let token = require_token()?;
+    let user = fetch_authenticated_user(&token).await?;
+    crate::github::fetch_review_requested_at(&token, owner, repo, number, &user.login).await
+}
+
+/// Whether each `(owner, repo, number)` PR is waiting on the signed-in user, in order.
+/// PRs whose timeline cannot be read are `None`.
+pub async fn needs_my_attention(prs: &[(String, String, u64)]) -> AppResult<Vec<Option<bool>>> {
+    let token = require_token()?;
+    let user = fetch_authenticated_user(&token).await?;
+    crate::github::pull_requests_need_attention(&token, prs, &user.login).await
+}
+
+pub async fn pull_request_review_states(
+    prs: &[(String, String, u64)],
+) -> AppResult<Vec<Option<crate::models::PrReviewState>>> {
+    let token = require_token()?;
+    crate::github::pull_request_review_states(&token, prs).await
+}
+
+pub async fn post_conversation_comment(
+    owner: &str,
+    repo: &str,
+    number: u64,
+    body: &str,
+) -> AppResult<String> {
+    let token = require_token()?;
+    crate::github::post_conversation_comment(&token, owner, repo, number, body).await
+}
+
+pub async fn fetch_text_file(
+    owner: &str,
+    repo: &str,
+    path: &str,
+    reference: &str,
+) -> AppResult<String> {
+    let token = require_token()?;
+    crate::github::fetch_text_file(&token, owner, repo, path, reference).await
+}
+
+pub async fn fetch_localized_pair(
+    owner: &str,
+    repo: &str,
+    reference: &str,
+    translation_path: &str,
+    source_locale: &str,
+) -> AppResult<crate::localization::LocalizedPair> {
+    let token = require_token()?;
+    crate::github::fetch_localized_pair(&token, owner, repo, reference, translation_path, source_locale)
+        .await
+}
+
+/// The repository's review policy, cached like other repository listings. A file that
+/// fails to parse is reported rather than ignored so the team notices. When the file
+/// cannot be fetched the repo is treated as having none, and nothing is cached so the
+/// next call tries again.
+pub async fn repo_review_config(owner: &str, repo: &str) -> AppResult<Option<RepoReviewConfig>> {
+    let key = format!("{owner}/{repo}");
+    if let Some(config) = repo_config_cache().get(&key) {
+        return Ok(config);
+    }
+    let token = require_token()?;
+    let text = match crate::github::fetch_repo_review_config(&token, owner, repo).await {
+        Ok(text) => text,
+        Err(err) => {
+            tracing::warn!(error = %err, "could not fetch the review config of {key}");
+            return Ok(None);
+        }
+    };
+    let config = text.map(|text| repo_config::parse_repo_config(&text)).transpose()?;
+    repo_config_cache().insert(&key, config.clone());
+    Ok(config)
+}
+
+/// Links elsewhere in the repository that point at heading anchors the PR removes. The
+/// link index is only built when some anchor actually changed.
+pub async fn anchor_impact(
+    owner: &str,
+    repo: &str,
+    number: u64,
+    base_sha: &str,
+    head_sha: &str,
+) -> AppResult<crate::anchor_impact::AnchorImpactReport> {
+    let token = require_token()?;
+    let changed =
+        crate::github::list_changed_anchors(&token, owner, repo, number, base_sha, head_sha).await?;
+    if changed.is_empty() {
+        return Ok(crate::anchor_impact::report(changed, &Default::default()));
+    }
+
+    let key = format!("{owner}/{repo}@{head_sha}");
+    let index = match link_index_cache().get(&key) {
+        Some(index) => index,
+        None => {
+            let index =
+                Arc::new(crate::github::build_link_index(&token, owner, repo, head_sha).await?);
+            link_index_cache().insert(&key, index.clone());
+            index
+        }
+    };
+    Ok(crate::anchor_impact::report(changed, &index))
+}
+
 pub async fn fetch_pull_request_metadata(
     owner: &str,
     repo: &str,
@@ -244,6 +823,7 @@ pub async fn fetch_pull_request_metadata(
     crate::github::get_pull_request_metadata(&token, owner, repo, number).await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_file_contents_on_demand(
     owner: &str,
     repo: &str,
@@ -252,9 +832,50 @@ pub async fn fetch_file_contents_on_demand(
     head_sha: &str,
     status: &str,
     previous_filename: Option<&str>,
+    head_repository: Option<(&str, &str)>,
+) -> AppResult<(Option<String>, Option<String>)> {
+    let token = require_token()?;
+    get_file_contents(
+        &token,
+        owner,
+        repo,
+        file_path,
+        base_sha,
+        head_sha,
+        status,
+        previous_filename,
+        head_repository,
+        ContentFormat::Raw,
+    )
+    .await
+}
+
+/// GitHub's rendered HTML of both versions of a changed markdown file.
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_rendered_file_contents(
+    owner: &str,
+    repo: &str,
+    file_path: &str,
+    base_sha: &str,
+    head_sha: &str,
+    status: &str,
+    previous_filename: Option<&str>,
+    head_repository: Option<(&str, &str)>,
 ) -> AppResult<(Option<String>, Option<String>)> {
     let token = require_token()?;
-    get_file_contents(&token, owner, repo, file_path, base_sha, head_sha, status, previous_filename).await
+    get_file_contents(
+        &token,
+        owner,
+        repo,
+        file_path,
+        base_sha,
+        head_sha,
+        status,
+        previous_filename,
+        head_repository,
+        ContentFormat::Html,
+    )
+    .await
 }
 
 pub async fn publish_review_comment(
@@ -280,6 +901,7 @@ pub async fn publish_file_comment(
     mode: CommentMode,
     pending_review_id: Option<u64>,
     in_reply_to: Option<u64>,
+    suggestion: Option<&crate::models::Suggestion>,
 ) -> AppResult<()> {
     let token = require_token()?;
     submit_file_comment(
@@ -296,6 +918,7 @@ pub async fn publish_file_comment(
         mode,
         pending_review_id,
         in_reply_to,
+        suggestion,
     )
     .await
 }
@@ -328,7 +951,7 @@ pub async fn finalize_pending_review(
     review_id: u64,
     event: &str,
     body: Option<&str>,
-) -> AppResult<()> {
+) -> AppResult<Option<String>> {
     let token = require_token()?;
     submit_pending_review(&token, owner, repo, number, review_id, event, body).await
 }
@@ -342,7 +965,7 @@ pub async fn submit_review_with_comments(
     body: Option<&str>,
     event: Option<&str>,
     comments: &[crate::review_storage::ReviewComment],
-) -> AppResult<(Vec<i64>, Option<String>)> {
+) -> AppResult<crate::submission_report::CommentBatch> {
     use crate::github::create_review_with_comments;
     
     let token = require_token()?;
@@ -360,6 +983,47 @@ pub async fn submit_review_with_comments(
     .await
 }
 
+pub async fn create_review(
+    owner: &str,
+    repo: &str,
+    number: u64,
+    review: &crate::github::NewReview<'_>,
+) -> AppResult<u64> {
+    let token = require_token()?;
+    crate::github::create_review(&token, owner, repo, number, review).await
+}
+
+pub async fn list_review_requests() -> AppResult<Vec<crate::workload::AwaitingReview>> {
+    let token = require_token()?;
+    crate::github::list_review_requests(&token).await
+}
+
+pub async fn list_review_activity(
+    org: &str,
+    since: &str,
+) -> AppResult<Vec<crate::review_activity::ActivityRecord>> {
+    let token = require_token()?;
+    crate::github::list_review_activity(&token, org, since).await
+}
+
+pub async fn search_my_comments(
+    query: &str,
+    repo_filter: Option<&str>,
+) -> AppResult<Vec<crate::comment_search::CommentSearchHit>> {
+    let token = require_token()?;
+    crate::github::search_my_comments(&token, query, repo_filter).await
+}
+
+pub async fn refresh_rate_limits() -> AppResult<Vec<crate::rate_limit::RateLimitStatus>> {
+    let token = require_token()?;
+    crate::github::refresh_rate_limits(&token).await
+}
+
+pub async fn render_markdown(text: &str, context: Option<&str>) -> AppResult<String> {
+    let token = require_token()?;
+    crate::github::render_markdown(&token, text, context).await
+}
+
 pub fn require_token() -> AppResult<String> {
     read_token()?.ok_or(AppError::OAuthCancelled)
 }
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewComment;
+
+/// Categories a reviewer can assign to a local comment.
+pub const COMMENT_CATEGORIES: &[&str] = &["blocking", "suggestion", "question", "nit", "praise"];
+
+/// Mapping key used for comments without a category.
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// How comments of one category are sent to GitHub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionTarget {
+    /// Part of a REQUEST_CHANGES review.
+    RequestChanges,
+    /// Part of a separate COMMENT review.
+    Comment,
+    /// Posted one by one, outside any review.
+    Single,
+}
+
+pub fn validate_category(category: &str) -> AppResult<()> {
+    crate::settings::ensure_one_of("category", category, COMMENT_CATEGORIES)
+}
+
+/// Blocking comments request changes; everything else goes into a comment review.
+pub fn default_mapping() -> HashMap<String, SubmissionTarget> {
+    COMMENT_CATEGORIES
+        .iter()
+        .chain(std::iter::once(&UNCATEGORIZED))
+        .map(|category| {
+            let target = if *category == "blocking" {
+                SubmissionTarget::RequestChanges
+            } else {
+                SubmissionTarget::Comment
+            };
+            (category.to_string(), target)
+        })
+        .collect()
+}
+
+/// Comments grouped by where they will be submitted.
+#[derive(Debug, Default)]
+pub struct SubmissionPlan {
+    pub request_changes: Vec<ReviewComment>,
+    pub comment: Vec<ReviewComment>,
+    pub single: Vec<ReviewComment>,
+}
+
+/// Split comments by category. Categories missing from `overrides` use the default
+/// mapping. File-level comments (line 0) and replies cannot be attached to a review
+/// through the API, so they are always posted singly; replies keep `in_reply_to_id`.
+pub fn plan_submission(
+    comments: &[ReviewComment],
+    overrides: &HashMap<String, SubmissionTarget>,
+) -> AppResult<SubmissionPlan> {
+    for key in overrides.keys() {
+        if key != UNCATEGORIZED {
+            validate_category(key).map_err(|_| {
+                AppError::InvalidInput(format!("unknown comment category in mapping: {key}"))
+            })?;
+        }
+    }
+
+    let mut mapping = default_mapping();
+    mapping.extend(overrides.iter().map(|(k, v)| (k.clone(), *v)));
+
+    let mut plan = SubmissionPlan::default();
+    for comment in comments.iter().filter(|c| !c.deleted) {
+        let category = comment.category.as_deref().unwrap_or(UNCATEGORIZED);
+        let target = mapping
+            .get(category)
+            .copied()
+            .unwrap_or(SubmissionTarget::Comment);
+        let target = if comment.line_number == 0 || comment.in_reply_to_id.is_some() {
+            SubmissionTarget::Single
+        } else {
+            target
+        };
+        match target {
+            SubmissionTarget::RequestChanges => plan.request_changes.push(comment.clone()),
+            SubmissionTarget::Comment => plan.comment.push(comment.clone()),
+            SubmissionTarget::Single => plan.single.push(comment.clone()),
+        }
+    }
+
+    Ok(plan)
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CategorySubmissionResult {
+    pub request_changes_review_id: Option<u64>,
+    pub comment_review_id: Option<u64>,
+    pub single_comments_posted: usize,
+    /// Why a batch failed. The batches before it are posted and reported above; its
+    /// comments and those of later batches stay in the local review.
+    pub error: Option<String>,
+}
@@ -0,0 +1,134 @@
+use serde::Deserialize;
+
+/// GitHub caps logins at 39 characters and repository names at 100.
+const MAX_OWNER_LEN: usize = 39;
+const MAX_REPO_LEN: usize = 100;
+
+/// Issue and pull request numbers are 32-bit in GitHub's GraphQL schema.
+const MAX_PR_NUMBER: u64 = i32::MAX as u64;
+
+/// The owner used for local folder reviews, which is not a valid GitHub login.
+pub const LOCAL_OWNER: &str = "__local__";
+
+fn invalid(kind: &str, value: impl std::fmt::Debug, reason: &str) -> String {
+    format!("invalid {kind} {value:?}: {reason}")
+}
+
+/// A GitHub user or organization login, or `__local__` for local folder reviews.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Owner(pub String);
+
+impl TryFrom<String> for Owner {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        let valid = value == LOCAL_OWNER
+            || (!value.is_empty()
+                && value.len() <= MAX_OWNER_LEN
+                && !value.starts_with('-')
+                // Enterprise managed users have logins like `jdoe_acme`
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        if !valid {
+            return Err(invalid(
+                "owner",
+                &value,
+                "expected a GitHub login of letters, digits, '-' and '_'",
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
+/// A repository name without its owner.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Repo(pub String);
+
+impl TryFrom<String> for Repo {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        let valid = !value.is_empty()
+            && value.len() <= MAX_REPO_LEN
+            && value != "."
+            && value != ".."
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(invalid(
+                "repository",
+                &value,
+                "expected a name of letters, digits, '-', '_' and '.'",
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
+/// A pull request or issue number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "u64")]
+pub struct PrNumber(pub u64);
+
+impl TryFrom<u64> for PrNumber {
+    type Error = String;
+
+    fn try_from(value: u64) -> Result<Self, String> {
+        if !(1..=MAX_PR_NUMBER).contains(&value) {
+            return Err(invalid(
+                "pull request number",
+                value,
+                &format!("expected 1 to {MAX_PR_NUMBER}"),
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
+/// A reaction as GitHub's REST API names it, such as `+1` or `hooray`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Reaction(pub String);
+
+impl TryFrom<String> for Reaction {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        const REACTIONS: [&str; 8] =
+            ["+1", "-1", "laugh", "hooray", "confused", "heart", "rocket", "eyes"];
+        if !REACTIONS.contains(&value.as_str()) {
+            return Err(invalid("reaction", &value, &format!("expected one of {REACTIONS:?}")));
+        }
+        Ok(Self(value))
+    }
+}
+
+/// A file path inside a repository or local review folder: relative, with `/` or `\`
+/// separators and no `..` segments, so it cannot name a file outside the tree.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct RepoPath(pub String);
+
+impl TryFrom<String> for RepoPath {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        let reason = if value.is_empty() {
+            Some("path is empty")
+        } else if value.chars().any(char::is_control) {
+            Some("path contains control characters")
+        } else if value.starts_with(['/', '\\']) || value.get(1..2) == Some(":") {
+            Some("path must be relative")
+        } else if value.split(['/', '\\']).any(|segment| segment == "..") {
+            Some("path must not contain '..' segments")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => Err(invalid("path", &value, reason)),
+            None => Ok(Self(value)),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use serde::Serialize;
+
+/// Metadata for one backend command, as listed by `cmd_list_commands`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandInfo {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub requires_auth: bool,
+    pub params: Vec<ParamInfo>,
+}
+
+/// A field of a command's `args` object. Values injected by Tauri (app handle, window)
+/// are not listed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamInfo {
+    pub name: &'static str,
+    /// Key of the field in `args`; argument structs use camelCase names.
+    pub js_name: String,
+    pub rust_type: String,
+    /// JSON type of the value: "string", "integer", "number", "boolean", "array" or
+    /// "object".
+    pub kind: &'static str,
+    pub required: bool,
+}
+
+impl CommandInfo {
+    pub fn new(
+        name: &'static str,
+        title: &'static str,
+        requires_auth: bool,
+        params: Vec<ParamInfo>,
+    ) -> Self {
+        Self {
+            name,
+            title,
+            requires_auth,
+            params,
+        }
+    }
+}
+
+impl ParamInfo {
+    pub fn new(name: &'static str, rust_type: &str) -> Self {
+        // `stringify!` on a type spaces out generics ("Option < String >").
+        let rust_type: String = rust_type.split_whitespace().collect();
+        let inner = rust_type
+            .strip_prefix("Option<")
+            .and_then(|rest| rest.strip_suffix('>'));
+
+        Self {
+            name,
+            js_name: to_camel_case(name),
+            kind: json_kind(inner.unwrap_or(&rust_type)),
+            required: inner.is_none(),
+            rust_type,
+        }
+    }
+
+    /// Apply the field's attributes, as `stringify!` renders them: a field with
+    /// `#[serde(default)]` may be left out.
+    pub fn with_attributes(mut self, attributes: &[&str]) -> Self {
+        let defaulted = attributes.iter().any(|attribute| {
+            let attribute: String = attribute.split_whitespace().collect();
+            attribute.starts_with("serde(") && attribute.contains("default")
+        });
+        self.required &= !defaulted;
+        self
+    }
+}
+
+fn json_kind(rust_type: &str) -> &'static str {
+    match rust_type {
+        "String" | "&str" | "Owner" | "Repo" | "RepoPath" => "string",
+        "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize"
+        | "PrNumber" => "integer",
+        "f32" | "f64" => "number",
+        "bool" => "boolean",
+        _ if rust_type.starts_with("Vec<") => "array",
+        _ => "object",
+    }
+}
+
+pub fn to_camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper_next = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            upper_next = true;
+        } else if upper_next {
+            out.extend(ch.to_uppercase());
+            upper_next = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::command_args::LOCAL_OWNER;
+use crate::error::AppResult;
+use crate::github::is_image_path;
+use crate::markdown_render::git_blob_sha;
+use crate::review_storage::{CommentAnchor, ReviewComment, ReviewStorage};
+
+/// How long a capture waits for more comments on the same PR before fetching, so a burst
+/// of comments costs one round of fetches.
+const CAPTURE_DELAY: Duration = Duration::from_secs(2);
+
+/// PRs with a capture scheduled or running, and whether another was asked for meanwhile.
+static CAPTURES: Mutex<BTreeMap<(String, String, u64), bool>> = Mutex::new(BTreeMap::new());
+
+/// SHA-256 of one line's text, without its line ending.
+pub fn line_hash(line: &str) -> String {
+    Sha256::digest(line.trim_end_matches('\r').as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Anchor of a comment on line `line_number` (1-based, `0` for the whole file) of
+/// `content`. The line hash is left out when the line is past the end of the file.
+pub fn comment_anchor(content: &str, line_number: u64) -> CommentAnchor {
+    let line = usize::try_from(line_number)
+        .ok()
+        .and_then(|line| line.checked_sub(1))
+        .and_then(|index| content.lines().nth(index));
+    CommentAnchor {
+        blob_sha: git_blob_sha(content),
+        line_hash: line.map(line_hash),
+    }
+}
+
+/// Whether an anchor can be taken for `comment`. Left-side line numbers refer to the
+/// base version, which `commit_id` does not name, and image contents come back encoded.
+/// Local folder files cannot be force-pushed away, so they need none.
+pub fn can_anchor(comment: &ReviewComment) -> bool {
+    !comment.deleted
+        && !comment.general
+        && comment.owner != LOCAL_OWNER
+        && !comment.side.eq_ignore_ascii_case("LEFT")
+        && !is_image_path(&comment.file_path)
+}
+
+/// Take anchors for the comments of a PR that can have one and do not yet, reading each
+/// file once per commit. Files that cannot be fetched are skipped, to be tried next time.
+pub async fn capture_missing(
+    storage: &ReviewStorage,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+) -> AppResult<usize> {
+    let known = storage.get_comment_anchors(owner, repo, pr_number)?;
+    let comments = storage.get_comments(owner, repo, pr_number)?;
+    let mut missing: BTreeMap<(&str, &str), Vec<&ReviewComment>> = BTreeMap::new();
+    for comment in comments
+        .iter()
+        .filter(|c| can_anchor(c) && !known.contains_key(&c.id))
+    {
+        missing
+            .entry((comment.file_path.as_str(), comment.commit_id.as_str()))
+            .or_default()
+            .push(comment);
+    }
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    // A fork PR's head commits live in the fork; ask the PR where that is, once, the
+    // first time a file is not in the base repository
+    let mut fork: Option<Option<(String, String)>> = None;
+    let mut contents: HashMap<(&str, &str), String> = HashMap::new();
+    for (path, commit) in missing.keys().copied() {
+        let mut fetched = crate::auth::fetch_text_file(owner, repo, path, commit).await;
+        if fetched.is_err() {
+            if fork.is_none() {
+                fork = Some(
+                    crate::auth::fetch_pull_request_metadata(owner, repo, pr_number)
+                        .await
+                        .ok()
+                        .and_then(|pr| pr.head_owner.zip(pr.head_repo)),
+                );
+            }
+            if let Some(Some((head_owner, head_repo))) = &fork {
+                fetched = crate::auth::fetch_text_file(head_owner, head_repo, path, commit).await;
+            }
+        }
+        match fetched {
+            Ok(content) => {
+                contents.insert((path, commit), content);
+            }
+            Err(err) => tracing::warn!(path, commit, error = %err, "cannot anchor comments"),
+        }
+    }
+
+    let anchors: Vec<(i64, CommentAnchor)> = missing
+        .iter()
+        .filter_map(|(key, comments)| Some((contents.get(key)?, comments)))
+        .flat_map(|(content, comments)| {
+            comments
+                .iter()
+                .map(|comment| (comment.id, comment_anchor(content, comment.line_number)))
+        })
+        .collect();
+    if !anchors.is_empty() {
+        storage.set_comment_anchors(&anchors)?;
+    }
+    Ok(anchors.len())
+}
+
+/// Note that a capture is wanted for `key`. Returns `true` when the caller should start
+/// one; otherwise the capture already scheduled or running picks the request up.
+fn schedule_capture(key: &(String, String, u64)) -> bool {
+    let mut captures = CAPTURES.lock().unwrap_or_else(|e| e.into_inner());
+    match captures.get_mut(key) {
+        Some(again) => {
+            *again = true;
+            false
+        }
+        None => {
+            captures.insert(key.clone(), false);
+            true
+        }
+    }
+}
+
+/// Finish a capture of `key`. Returns `true` when another was asked for while it ran, in
+/// which case it stays scheduled and the caller runs again.
+fn finish_capture(key: &(String, String, u64)) -> bool {
+    let mut captures = CAPTURES.lock().unwrap_or_else(|e| e.into_inner());
+    match captures.get_mut(key) {
+        Some(again) if *again => {
+            *again = false;
+            true
+        }
+        _ => {
+            captures.remove(key);
+            false
+        }
+    }
+}
+
+/// Anchor new comments in the background, so the hashes are taken while the commit they
+/// were written on still exists. Calls for a PR that already has a capture coming are
+/// folded into it.
+pub fn spawn_capture(owner: String, repo: String, pr_number: u64) {
+    if owner == LOCAL_OWNER {
+        return;
+    }
+    let key = (owner, repo, pr_number);
+    if !schedule_capture(&key) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let (owner, repo, pr_number) = &key;
+        loop {
+            tokio::time::sleep(CAPTURE_DELAY).await;
+            let result = match crate::review_storage::get_storage() {
+                Ok(storage) => capture_missing(storage, owner, repo, *pr_number).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                tracing::warn!("Failed to anchor comments on {owner}/{repo}#{pr_number}: {err}");
+            }
+            if !finish_capture(&key) {
+                break;
+            }
+        }
+    });
+}
@@ -0,0 +1,384 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppResult;
+use crate::i18n::{t, tf, Msg};
+use crate::permalinks::line_url;
+use crate::review_storage::{CommentAnchor, ReviewComment, ReviewMetadata};
+
+/// Pending comments rendered as one markdown body.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TasklistExport {
+    pub body: String,
+    pub item_count: usize,
+    /// URL of the posted comment, when it was posted.
+    pub comment_url: Option<String>,
+}
+
+/// How `cmd_submit_local_review` sends comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionMode {
+    /// Each comment anchored to its line.
+    #[default]
+    Inline,
+    /// One review body listing every comment by file, with permalinks instead of anchors.
+    Consolidated,
+}
+
+impl SubmissionMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inline => "inline",
+            Self::Consolidated => "consolidated",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Inline, Self::Consolidated]
+            .into_iter()
+            .find(|mode| mode.as_str() == name)
+    }
+}
+
+/// Live comments grouped by file, each file's comments in line order.
+pub fn group_by_file(comments: &[ReviewComment]) -> BTreeMap<&str, Vec<&ReviewComment>> {
+    let mut by_file: BTreeMap<&str, Vec<&ReviewComment>> = BTreeMap::new();
+    for comment in comments.iter().filter(|c| !c.deleted) {
+        by_file.entry(comment.file_path.as_str()).or_default().push(comment);
+    }
+    for file_comments in by_file.values_mut() {
+        file_comments.sort_by(|a, b| {
+            a.line_number
+                .cmp(&b.line_number)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+    }
+    by_file
+}
+
+/// `L12` linked to the line at the comment's commit, or "whole file" for file-level
+/// comments. Falls back to plain text when no permalink can be built.
+pub fn line_reference(web_base: &str, owner: &str, repo: &str, comment: &ReviewComment) -> String {
+    if comment.line_number == 0 {
+        return t(Msg::TasklistFileComment).to_string();
+    }
+    let label = format!("L{}", comment.line_number);
+    match line_url(
+        web_base,
+        owner,
+        repo,
+        &comment.commit_id,
+        &comment.file_path,
+        comment.line_number,
+    ) {
+        Ok(url) => format!("[{label}]({url})"),
+        Err(_) => label,
+    }
+}
+
+/// Continuation lines indented under a list item, so multi-line bodies stay inside it.
+fn indent_continuation(body: &str) -> String {
+    body.trim()
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("  {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render comments as a GitHub task list, one unchecked item per comment under a heading
+/// per file. Categories lead each item in bold.
+pub fn render_tasklist(
+    comments: &[ReviewComment],
+    web_base: &str,
+    owner: &str,
+    repo: &str,
+) -> TasklistExport {
+    let by_file = group_by_file(comments);
+    let item_count = by_file.values().map(Vec::len).sum::<usize>();
+
+    let mut body = format!("## {}\n", tf(Msg::TasklistHeading, &[&item_count]));
+    for (path, file_comments) in &by_file {
+        body.push_str(&format!("\n### `{path}`\n\n"));
+        for comment in file_comments {
+            let category = comment
+                .category
+                .as_deref()
+                .map(|category| format!("**{category}**: "))
+                .unwrap_or_default();
+            body.push_str(&format!(
+                "- [ ] {} {category}{}\n",
+                line_reference(web_base, owner, repo, comment),
+                indent_continuation(&comment.body)
+            ));
+        }
+    }
+
+    TasklistExport {
+        body,
+        item_count,
+        comment_url: None,
+    }
+}
+
+/// One review body holding every comment, grouped by file, each linked to the line at the
+/// commit it was written against. For PRs force-pushed so often that inline comments no
+/// longer anchor.
+pub fn render_consolidated_review(
+    body: Option<&str>,
+    comments: &[ReviewComment],
+    web_base: &str,
+    owner: &str,
+    repo: &str,
+) -> String {
+    let mut review = body
+        .map(str::trim)
+        .filter(|body| !body.is_empty())
+        .map(|body| format!("{body}\n\n---\n"))
+        .unwrap_or_default();
+    let (general, anchored): (Vec<ReviewComment>, Vec<ReviewComment>) = comments
+        .iter()
+        .filter(|c| !c.deleted)
+        .cloned()
+        .partition(|c| c.general);
+    for comment in &general {
+        review.push_str(&format!("\n{}\n", comment.body.trim()));
+    }
+    for (path, file_comments) in &group_by_file(&anchored) {
+        review.push_str(&format!("\n### `{path}`\n\n"));
+        for comment in file_comments {
+            let category = comment
+                .category
+                .as_deref()
+                .map(|category| format!(" **{category}**"))
+                .unwrap_or_default();
+            review.push_str(&format!(
+                "- {}{category}: {}\n",
+                line_reference(web_base, owner, repo, comment),
+                indent_continuation(&comment.body)
+            ));
+        }
+    }
+    review.trim_start().to_string()
+}
+
+/// File formats `cmd_export_review` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewExportFormat {
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl ReviewExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// A local review as written by the JSON export.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewExport<'a> {
+    pub exported_at: String,
+    pub review: &'a ReviewMetadata,
+    pub comments: Vec<ExportedComment<'a>>,
+}
+
+/// A comment with the hashes that tie it to the content it was written on; both are
+/// `None` until the comment has been anchored.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedComment<'a> {
+    #[serde(flatten)]
+    pub comment: &'a ReviewComment,
+    pub blob_sha: Option<&'a str>,
+    pub line_hash: Option<&'a str>,
+}
+
+impl<'a> ExportedComment<'a> {
+    pub fn new(comment: &'a ReviewComment, anchors: &'a HashMap<i64, CommentAnchor>) -> Self {
+        let anchor = anchors.get(&comment.id);
+        Self {
+            comment,
+            blob_sha: anchor.map(|a| a.blob_sha.as_str()),
+            line_hash: anchor.and_then(|a| a.line_hash.as_deref()),
+        }
+    }
+}
+
+const CSV_COLUMNS: &[&str] = &[
+    "id",
+    "file_path",
+    "line_number",
+    "side",
+    "category",
+    "general",
+    "in_reply_to_id",
+    "commit_id",
+    "blob_sha",
+    "line_hash",
+    "created_at",
+    "updated_at",
+    "body",
+];
+
+/// A CSV field, quoted when needed. Fields a spreadsheet would read as a formula get a
+/// leading `'` so opening the export never runs one.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn review_name(metadata: &ReviewMetadata) -> String {
+    match &metadata.local_folder {
+        Some(folder) => folder.clone(),
+        None => format!("{}/{}#{}", metadata.owner, metadata.repo, metadata.pr_number),
+    }
+}
+
+/// The review as a markdown document: its details and body, general comments, then the
+/// rest by file. Local folder reviews get plain line numbers instead of permalinks, and
+/// anchored comments end in an HTML comment with their hashes.
+pub fn render_review_markdown(
+    metadata: &ReviewMetadata,
+    comments: &[ReviewComment],
+    anchors: &HashMap<i64, CommentAnchor>,
+    web_base: &str,
+) -> String {
+    let mut doc = format!("# {}\n\n", tf(Msg::ReviewExportHeading, &[&review_name(metadata)]));
+    doc.push_str(&format!("- {}: `{}`\n", t(Msg::LogCommit), metadata.commit_id));
+    doc.push_str(&format!("- {}: {}\n", t(Msg::ReviewExportStarted), metadata.created_at));
+    if let Some(due_at) = &metadata.due_at {
+        doc.push_str(&format!("- {}: {}\n", t(Msg::ReviewExportDue), due_at));
+    }
+    if let Some(body) = metadata.body.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+        doc.push_str(&format!("\n{body}\n"));
+    }
+
+    let (general, anchored): (Vec<ReviewComment>, Vec<ReviewComment>) = comments
+        .iter()
+        .filter(|c| !c.deleted)
+        .cloned()
+        .partition(|c| c.general);
+    for comment in &general {
+        doc.push_str(&format!("\n{}\n", comment.body.trim()));
+    }
+    for (path, file_comments) in &group_by_file(&anchored) {
+        doc.push_str(&format!("\n### `{path}`\n\n"));
+        for comment in file_comments {
+            let reference = if metadata.local_folder.is_some() {
+                match comment.line_number {
+                    0 => t(Msg::TasklistFileComment).to_string(),
+                    line => format!("L{line}"),
+                }
+            } else {
+                line_reference(web_base, &metadata.owner, &metadata.repo, comment)
+            };
+            let category = comment
+                .category
+                .as_deref()
+                .map(|category| format!(" **{category}**"))
+                .unwrap_or_default();
+            let hashes = match anchors.get(&comment.id) {
+                Some(CommentAnchor { blob_sha, line_hash: Some(line_hash) }) => {
+                    format!(" <!-- blob {blob_sha} line-sha256 {line_hash} -->")
+                }
+                Some(CommentAnchor { blob_sha, line_hash: None }) => {
+                    format!(" <!-- blob {blob_sha} -->")
+                }
+                None => String::new(),
+            };
+            doc.push_str(&format!(
+                "- {reference}{category}: {}{hashes}\n",
+                indent_continuation(&comment.body)
+            ));
+        }
+    }
+    doc
+}
+
+/// One row per live comment, with a header row.
+pub fn render_review_csv(
+    comments: &[ReviewComment],
+    anchors: &HashMap<i64, CommentAnchor>,
+) -> String {
+    let mut csv = CSV_COLUMNS.join(",");
+    csv.push_str("\r\n");
+    for comment in comments.iter().filter(|c| !c.deleted) {
+        let exported = ExportedComment::new(comment, anchors);
+        let row = [
+            comment.id.to_string(),
+            comment.file_path.clone(),
+            comment.line_number.to_string(),
+            comment.side.clone(),
+            comment.category.clone().unwrap_or_default(),
+            comment.general.to_string(),
+            comment.in_reply_to_id.map(|id| id.to_string()).unwrap_or_default(),
+            comment.commit_id.clone(),
+            exported.blob_sha.unwrap_or_default().to_string(),
+            exported.line_hash.unwrap_or_default().to_string(),
+            comment.created_at.clone(),
+            comment.updated_at.clone(),
+            comment.body.clone(),
+        ];
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+pub fn render_review_export(
+    format: ReviewExportFormat,
+    metadata: &ReviewMetadata,
+    comments: &[ReviewComment],
+    anchors: &HashMap<i64, CommentAnchor>,
+    web_base: &str,
+) -> AppResult<String> {
+    Ok(match format {
+        ReviewExportFormat::Markdown => {
+            render_review_markdown(metadata, comments, anchors, web_base)
+        }
+        ReviewExportFormat::Csv => render_review_csv(comments, anchors),
+        ReviewExportFormat::Json => serde_json::to_string_pretty(&ReviewExport {
+            exported_at: Utc::now().to_rfc3339(),
+            review: metadata,
+            comments: comments
+                .iter()
+                .filter(|c| !c.deleted)
+                .map(|comment| ExportedComment::new(comment, anchors))
+                .collect(),
+        })?,
+    })
+}
+
+/// File name offered in the save dialog for an export of the review.
+pub fn export_file_name(metadata: &ReviewMetadata, format: ReviewExportFormat) -> String {
+    let name = if metadata.local_folder.is_some() {
+        format!("local-{}", metadata.pr_number)
+    } else {
+        format!("{}-{}-{}", metadata.owner, metadata.repo, metadata.pr_number)
+    };
+    format!("{name}-review.{}", format.extension())
+}
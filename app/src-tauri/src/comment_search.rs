@@ -0,0 +1,99 @@
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+
+/// PRs whose comments are read for one search; GitHub only says which PRs match, not
+/// which comments.
+pub const MAX_SEARCHED_PRS: usize = 20;
+/// Characters of context kept on each side of the first match.
+const SNIPPET_CONTEXT: usize = 80;
+
+/// One of my comments that matches a search.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommentSearchHit {
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    pub pr_title: String,
+    pub comment_id: u64,
+    pub url: String,
+    /// `None` for comments in the PR conversation.
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub created_at: String,
+    /// The text around the first match, with `…` where it was cut.
+    pub snippet: String,
+}
+
+/// A search split into words to find in comment text and GitHub qualifiers such as
+/// `updated:>2024-05-01`, which only narrow the PRs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentQuery {
+    pub terms: Vec<String>,
+    pub qualifiers: Vec<String>,
+}
+
+pub fn parse_query(query: &str) -> CommentQuery {
+    let (qualifiers, terms): (Vec<&str>, Vec<&str>) =
+        query.split_whitespace().partition(|word| word.contains(':'));
+    CommentQuery {
+        terms: terms.into_iter().map(str::to_lowercase).collect(),
+        qualifiers: qualifiers.into_iter().map(str::to_string).collect(),
+    }
+}
+
+/// The GitHub search for PRs I commented on that mention every term. `repo_filter` is
+/// `owner/name`.
+pub fn search_query(query: &CommentQuery, repo_filter: Option<&str>) -> AppResult<String> {
+    if query.terms.is_empty() {
+        return Err(AppError::InvalidInput("search needs at least one word to look for".into()));
+    }
+    let mut parts = vec!["is:pr".to_string(), "commenter:@me".to_string()];
+    if let Some(repo) = repo_filter.map(str::trim).filter(|repo| !repo.is_empty()) {
+        match repo.split_once('/') {
+            Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+                parts.push(format!("repo:{repo}"))
+            }
+            _ => {
+                return Err(AppError::InvalidInput(format!(
+                    "repository filter must be owner/name, got {repo}"
+                )))
+            }
+        }
+    }
+    parts.extend(query.qualifiers.iter().cloned());
+    parts.extend(query.terms.iter().cloned());
+    Ok(parts.join(" "))
+}
+
+/// Every term appears in `body`, ignoring case.
+pub fn matches(body: &str, terms: &[String]) -> bool {
+    let body = body.to_lowercase();
+    terms.iter().all(|term| body.contains(term.as_str()))
+}
+
+/// The text around the first term found, on one line.
+pub fn snippet(body: &str, terms: &[String]) -> String {
+    let flat = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    let lower = flat.to_lowercase();
+    // Lowercasing can change byte lengths, so positions are taken in chars
+    let chars: Vec<char> = flat.chars().collect();
+    let start = terms
+        .iter()
+        .filter_map(|term| lower.find(term.as_str()))
+        .min()
+        .map(|byte| lower[..byte].chars().count())
+        .unwrap_or(0)
+        .min(chars.len());
+
+    let from = start.saturating_sub(SNIPPET_CONTEXT);
+    let to = (start + SNIPPET_CONTEXT).min(chars.len());
+    let mut text: String = chars[from..to].iter().collect();
+    if from > 0 {
+        text = format!("…{}", text.trim_start());
+    }
+    if to < chars.len() {
+        text = format!("{}…", text.trim_end());
+    }
+    text
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// After this long a PR's comments and reviews are fetched in full again, picking up
+/// edits the incremental path cannot see, such as dismissed reviews.
+pub const FULL_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// `since` overlaps the previous fetch by this much, so clock skew between the app and
+/// GitHub cannot drop an update. Overlapping items are merged by id.
+const SINCE_OVERLAP: chrono::Duration = chrono::Duration::seconds(60);
+
+/// The `since` query value for changes after a fetch started at `fetched_at`.
+pub fn since_param(fetched_at: DateTime<Utc>) -> String {
+    (fetched_at - SINCE_OVERLAP).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Replace cached items that were updated and append new ones, keeping id order.
+pub fn merge_by_id<T>(cached: &mut Vec<T>, updates: Vec<T>, id: impl Fn(&T) -> u64) {
+    for update in updates {
+        match cached.iter().position(|item| id(item) == id(&update)) {
+            Some(index) => cached[index] = update,
+            None => cached.push(update),
+        }
+    }
+    cached.sort_by_key(|item| id(item));
+}
+
+/// How many cached reviews can be kept without refetching. Reviews are listed oldest
+/// first and only a pending review still changes, so every full page before the first
+/// pending one is final.
+pub fn settled_reviews(pending: &[bool], per_page: usize) -> usize {
+    let unsettled = pending.iter().position(|is_pending| *is_pending).unwrap_or(pending.len());
+    unsettled / per_page * per_page
+}
+
+/// Whether a merged list disagrees with the count GitHub reports, which means something
+/// was deleted since the last fetch.
+pub fn count_mismatch(merged: usize, reported: Option<u64>) -> bool {
+    reported.is_some_and(|count| count != merged as u64)
+}
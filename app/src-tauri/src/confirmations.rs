@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::i18n::{tf, Msg};
+use crate::reset::ResetOptions;
+
+/// How long a confirmation stays valid: enough to read a dialog, too short to reuse
+/// a stale token later.
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(120);
+
+/// A destructive call, described precisely enough that a token issued for one cannot
+/// be spent on another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DestructiveAction {
+    DeleteReview {
+        owner: String,
+        repo: String,
+        pr_number: u64,
+        review_id: u64,
+    },
+    DeleteGithubComment {
+        owner: String,
+        repo: String,
+        comment_id: u64,
+    },
+    ClearLocalReview {
+        owner: String,
+        repo: String,
+        pr_number: u64,
+    },
+    ResetAppData {
+        options: ResetOptions,
+    },
+}
+
+impl DestructiveAction {
+    /// What will be deleted, for the confirmation dialog. `local_comments` is the number
+    /// of drafts a local review holds.
+    pub fn describe(&self, local_comments: Option<usize>) -> String {
+        match self {
+            Self::DeleteReview { owner, repo, pr_number, review_id } => {
+                tf(Msg::ConfirmDeleteReview, &[review_id, owner, repo, pr_number])
+            }
+            Self::DeleteGithubComment { owner, repo, comment_id } => {
+                tf(Msg::ConfirmDeleteGithubComment, &[comment_id, owner, repo])
+            }
+            Self::ClearLocalReview { owner, repo, pr_number } => match local_comments {
+                Some(count) => {
+                    tf(Msg::ConfirmClearLocalReviewDrafts, &[owner, repo, pr_number, &count])
+                }
+                None => tf(Msg::ConfirmClearLocalReview, &[owner, repo, pr_number]),
+            },
+            Self::ResetAppData { options } => options.describe(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfirmationToken {
+    pub token: String,
+    pub action: DestructiveAction,
+    pub description: String,
+    pub expires_at: String,
+}
+
+/// Outstanding confirmation tokens. Each is single-use and bound to one action.
+pub struct ConfirmationStore {
+    ttl: Duration,
+    pending: Mutex<HashMap<String, (Instant, DestructiveAction)>>,
+}
+
+impl ConfirmationStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn issue(&self, action: DestructiveAction, description: String) -> AppResult<ConfirmationToken> {
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        pending.retain(|_, (issued_at, _)| issued_at.elapsed() < self.ttl);
+        pending.insert(token.clone(), (Instant::now(), action.clone()));
+
+        let expires_at = Utc::now()
+            + chrono::Duration::from_std(self.ttl).unwrap_or_else(|_| chrono::Duration::zero());
+        Ok(ConfirmationToken {
+            token,
+            action,
+            description,
+            expires_at: expires_at.to_rfc3339(),
+        })
+    }
+
+    /// Spend `token` on `action`. The token is consumed even when it does not match, so a
+    /// mistaken call has to ask the user again.
+    pub fn redeem(&self, token: &str, action: &DestructiveAction) -> AppResult<()> {
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        match pending.remove(token) {
+            Some((issued_at, issued_for))
+                if issued_at.elapsed() < self.ttl && issued_for == *action =>
+            {
+                Ok(())
+            }
+            _ => Err(AppError::ConfirmationRequired(action.describe(None))),
+        }
+    }
+}
+
+pub fn confirmations() -> &'static ConfirmationStore {
+    static STORE: OnceLock<ConfirmationStore> = OnceLock::new();
+    STORE.get_or_init(|| ConfirmationStore::new(CONFIRMATION_TTL))
+}
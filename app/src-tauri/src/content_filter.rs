@@ -0,0 +1,246 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::{ReviewComment, ReviewStorage};
+use crate::secrets;
+use crate::settings;
+
+pub const CONTENT_FILTER_KEY: &str = "content_filter";
+
+/// Words flagged by the profanity check. Matched as whole words, case-insensitively.
+const PROFANITY: &[&str] = &[
+    "ass", "asshole", "bastard", "bitch", "bollocks", "bullshit", "crap", "damn", "dick",
+    "fuck", "fucking", "goddamn", "piss", "shit", "shitty", "wtf",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    Block,
+    Warn,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterCategory {
+    BannedPhrase,
+    InternalHost,
+    Profanity,
+    Secret,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterActions {
+    pub banned_phrase: FilterAction,
+    pub internal_host: FilterAction,
+    pub profanity: FilterAction,
+    pub secret: FilterAction,
+}
+
+impl Default for FilterActions {
+    fn default() -> Self {
+        Self {
+            banned_phrase: FilterAction::Block,
+            internal_host: FilterAction::Block,
+            profanity: FilterAction::Warn,
+            secret: FilterAction::Block,
+        }
+    }
+}
+
+impl FilterActions {
+    fn for_category(&self, category: FilterCategory) -> FilterAction {
+        match category {
+            FilterCategory::BannedPhrase => self.banned_phrase,
+            FilterCategory::InternalHost => self.internal_host,
+            FilterCategory::Profanity => self.profanity,
+            FilterCategory::Secret => self.secret,
+        }
+    }
+}
+
+/// Checks applied to review text before it leaves the machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentFilterConfig {
+    pub enabled: bool,
+    pub banned_phrases: Vec<String>,
+    /// Hostnames that must not appear in public comments. An entry also matches its
+    /// subdomains, so `corp.example.com` catches `wiki.corp.example.com`.
+    pub internal_hosts: Vec<String>,
+    pub actions: FilterActions,
+}
+
+impl Default for ContentFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            banned_phrases: Vec::new(),
+            internal_hosts: Vec::new(),
+            actions: FilterActions::default(),
+        }
+    }
+}
+
+impl ContentFilterConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.banned_phrases.iter().any(|phrase| phrase.trim().is_empty()) {
+            return Err(AppError::InvalidInput("banned phrases cannot be empty".into()));
+        }
+        for host in &self.internal_hosts {
+            let entry = host.trim().trim_start_matches('.');
+            let whole = hostname_regex().find(entry).is_some_and(|found| found.as_str() == entry);
+            if !whole {
+                return Err(AppError::InvalidInput(format!(
+                    "internal host `{host}` is not a hostname such as `corp.example.com`"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilterFinding {
+    /// Local comment id, or `None` for the review body.
+    pub comment_id: Option<i64>,
+    pub file_path: Option<String>,
+    pub category: FilterCategory,
+    pub action: FilterAction,
+    /// The offending text; secrets are redacted.
+    pub matched: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FilterReport {
+    pub blocked: Vec<FilterFinding>,
+    pub warnings: Vec<FilterFinding>,
+}
+
+impl FilterReport {
+    /// One-line list of findings for error messages, e.g. `docs/a.md: internal_host (wiki.corp)`.
+    pub fn describe(findings: &[FilterFinding]) -> String {
+        findings
+            .iter()
+            .map(|f| {
+                let category = serde_json::to_value(f.category)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                let location = f.file_path.as_deref().unwrap_or("review body");
+                format!("{location}: {category} ({})", f.matched)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<ContentFilterConfig> {
+    settings::load(storage, CONTENT_FILTER_KEY)
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &ContentFilterConfig) -> AppResult<()> {
+    config.validate()?;
+    settings::save(storage, CONTENT_FILTER_KEY, config)
+}
+
+fn hostname_regex() -> &'static Regex {
+    static HOSTNAME: OnceLock<Regex> = OnceLock::new();
+    HOSTNAME.get_or_init(|| {
+        Regex::new(r"(?i)\b[a-z0-9](?:[a-z0-9-]*[a-z0-9])?(?:\.[a-z0-9](?:[a-z0-9-]*[a-z0-9])?)+\b")
+            .expect("hostname pattern is valid")
+    })
+}
+
+fn profanity_regex() -> &'static Regex {
+    static PROFANITY_RE: OnceLock<Regex> = OnceLock::new();
+    PROFANITY_RE.get_or_init(|| {
+        Regex::new(&format!(r"(?i)\b({})\b", PROFANITY.join("|")))
+            .expect("profanity pattern is valid")
+    })
+}
+
+/// Find every filter match in one piece of text, tagged with its configured action.
+/// Categories whose action is `Off` are skipped.
+pub fn scan_text(config: &ContentFilterConfig, text: &str) -> Vec<(FilterCategory, FilterAction, String)> {
+    let mut matches = Vec::new();
+    if !config.enabled {
+        return matches;
+    }
+
+    let mut push = |category: FilterCategory, matched: String| {
+        let action = config.actions.for_category(category);
+        if action != FilterAction::Off {
+            matches.push((category, action, matched));
+        }
+    };
+
+    let lower = text.to_lowercase();
+    for phrase in &config.banned_phrases {
+        let phrase = phrase.trim();
+        if !phrase.is_empty() && lower.contains(&phrase.to_lowercase()) {
+            push(FilterCategory::BannedPhrase, phrase.to_string());
+        }
+    }
+
+    for found in hostname_regex().find_iter(text) {
+        let host = found.as_str().to_ascii_lowercase();
+        let internal = config.internal_hosts.iter().any(|entry| {
+            let entry = entry.trim().trim_start_matches('.').to_ascii_lowercase();
+            !entry.is_empty() && (host == entry || host.ends_with(&format!(".{entry}")))
+        });
+        if internal {
+            push(FilterCategory::InternalHost, found.as_str().to_string());
+        }
+    }
+
+    for found in profanity_regex().find_iter(text) {
+        push(FilterCategory::Profanity, found.as_str().to_string());
+    }
+
+    for finding in secrets::scan_content("", text) {
+        push(FilterCategory::Secret, finding.excerpt);
+    }
+
+    matches
+}
+
+/// Check the review body and every pending comment, splitting findings into blocking
+/// ones and warnings.
+pub fn check_review(
+    config: &ContentFilterConfig,
+    review_body: Option<&str>,
+    comments: &[ReviewComment],
+) -> FilterReport {
+    let mut report = FilterReport::default();
+    let texts = review_body
+        .map(|body| (None, None, body))
+        .into_iter()
+        .chain(comments.iter().filter(|c| !c.deleted).map(|c| {
+            (Some(c.id), Some(c.file_path.clone()), c.body.as_str())
+        }));
+
+    for (comment_id, file_path, text) in texts {
+        for (category, action, matched) in scan_text(config, text) {
+            let finding = FilterFinding {
+                comment_id,
+                file_path: file_path.clone(),
+                category,
+                action,
+                matched,
+            };
+            match action {
+                FilterAction::Block => report.blocked.push(finding),
+                FilterAction::Warn => report.warnings.push(finding),
+                FilterAction::Off => {}
+            }
+        }
+    }
+
+    report
+}
@@ -0,0 +1,283 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::markdown_structure::code_blocks;
+
+/// Diagram types mermaid accepts as the first statement.
+const MERMAID_TYPES: &[&str] = &[
+    "graph",
+    "flowchart",
+    "sequenceDiagram",
+    "classDiagram",
+    "classDiagram-v2",
+    "stateDiagram",
+    "stateDiagram-v2",
+    "erDiagram",
+    "gantt",
+    "pie",
+    "journey",
+    "gitGraph",
+    "mindmap",
+    "timeline",
+    "quadrantChart",
+    "requirementDiagram",
+    "C4Context",
+    "C4Container",
+    "C4Component",
+    "C4Dynamic",
+    "C4Deployment",
+    "sankey-beta",
+    "xychart-beta",
+    "block-beta",
+    "packet-beta",
+    "architecture-beta",
+    "kanban",
+];
+const FLOWCHART_DIRECTIONS: &[&str] = &["TB", "TD", "BT", "RL", "LR"];
+/// Sequence diagram statements closed by `end`.
+const SEQUENCE_BLOCKS: &[&str] = &["loop", "alt", "opt", "par", "critical", "break", "rect", "box"];
+const FLOWCHART_ARROWS: &[&str] = &["-->", "---", "-.->", "==>", "--x", "--o", "<-->"];
+
+/// A syntax problem in a diagram fence.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiagramIssue {
+    /// `mermaid` or `plantuml`.
+    pub language: String,
+    /// 1-based line in the markdown content.
+    pub line: u64,
+    pub detail: String,
+}
+
+/// Problems in the mermaid and PlantUML fences of markdown `content`. The checks are
+/// structural: an unknown diagram type, unbalanced brackets or blocks, and arrows
+/// without a target, which cover most diagrams that fail to render.
+pub fn validate(content: &str) -> Vec<DiagramIssue> {
+    let mut issues = Vec::new();
+    for block in code_blocks(content) {
+        let first_line = block.fence_line + 1;
+        let found = match block.language.as_str() {
+            "mermaid" => check_mermaid(&block.lines),
+            "plantuml" | "puml" => check_plantuml(&block.lines),
+            _ => continue,
+        };
+        let language = if block.language == "mermaid" { "mermaid" } else { "plantuml" };
+        issues.extend(found.into_iter().map(|(offset, detail)| DiagramIssue {
+            language: language.to_string(),
+            line: first_line + offset as u64,
+            detail,
+        }));
+    }
+    issues
+}
+
+/// Brackets opened and not yet closed, with the offset of the line each was opened on.
+/// Fed line by line, so a body like `class A {` ... `}` can span several lines.
+#[derive(Default)]
+struct Brackets {
+    open: Vec<(usize, char)>,
+}
+
+impl Brackets {
+    /// Track the brackets on `line` outside quoted text, which ends with the line. Returns
+    /// the first bracket that closes the wrong one, or an unclosed quote.
+    fn feed(&mut self, offset: usize, line: &str) -> Option<String> {
+        let mut in_quote = false;
+        for c in line.chars() {
+            match c {
+                '"' => in_quote = !in_quote,
+                _ if in_quote => {}
+                '(' | '[' | '{' => self.open.push((offset, c)),
+                ')' | ']' | '}' => {
+                    let expected = match c {
+                        ')' => '(',
+                        ']' => '[',
+                        _ => '{',
+                    };
+                    if self.open.pop().map(|(_, open)| open) != Some(expected) {
+                        return Some(format!("unexpected `{c}`"));
+                    }
+                }
+                _ => {}
+            }
+        }
+        in_quote.then(|| "unclosed `\"`".to_string())
+    }
+
+    /// The innermost bracket still open, with the offset it was opened on.
+    fn unclosed(&self) -> Option<(usize, String)> {
+        self.open.last().map(|(offset, c)| (*offset, format!("`{c}` is never closed")))
+    }
+}
+
+/// The first bracket on `line` that is never closed or closes the wrong one, outside
+/// quoted text.
+fn unbalanced_bracket(line: &str) -> Option<String> {
+    let mut brackets = Brackets::default();
+    brackets
+        .feed(0, line)
+        .or_else(|| brackets.unclosed().map(|(_, problem)| problem))
+}
+
+/// Offsets into `lines` with problems.
+fn check_mermaid(lines: &[String]) -> Vec<(usize, String)> {
+    let mut issues = Vec::new();
+    let mut statements = lines
+        .iter()
+        .enumerate()
+        .map(|(offset, line)| (offset, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("%%"))
+        .peekable();
+
+    // Optional front matter
+    if statements.peek().is_some_and(|(_, line)| *line == "---") {
+        statements.next();
+        for (_, line) in statements.by_ref() {
+            if line == "---" {
+                break;
+            }
+        }
+    }
+
+    let Some((header_offset, header)) = statements.next() else {
+        return vec![(0, "diagram is empty".to_string())];
+    };
+    let mut words = header.split_whitespace();
+    let kind = words.next().unwrap_or_default().trim_end_matches(':');
+    if !MERMAID_TYPES.contains(&kind) {
+        return vec![(header_offset, format!("unknown diagram type `{kind}`"))];
+    }
+    let is_flowchart = kind == "graph" || kind == "flowchart";
+    if is_flowchart {
+        if let Some(direction) = words.next().filter(|d| !FLOWCHART_DIRECTIONS.contains(d)) {
+            issues.push((
+                header_offset,
+                format!("unknown direction `{direction}`, expected one of TB, TD, BT, RL, LR"),
+            ));
+        }
+    }
+
+    // Open `subgraph`/sequence blocks with the offset they started at
+    let mut blocks: Vec<(usize, &str)> = Vec::new();
+    // Class, state and ER bodies open a `{` on one line and close it on another, so
+    // other diagram types balance brackets across the whole block
+    let mut brackets = Brackets::default();
+    for (offset, line) in statements {
+        let keyword = line.split_whitespace().next().unwrap_or_default();
+        if keyword == "end" {
+            if blocks.pop().is_none() {
+                issues.push((offset, "`end` without an open block".to_string()));
+            }
+            continue;
+        }
+
+        match kind {
+            "graph" | "flowchart" => {
+                if keyword == "subgraph" {
+                    blocks.push((offset, "subgraph"));
+                    continue;
+                }
+                // `id>text]` is the asymmetric node shape, not a stray bracket
+                let shapes = asymmetric_node_regex().replace_all(line, "");
+                if let Some(problem) = unbalanced_bracket(&shapes) {
+                    issues.push((offset, problem));
+                }
+                let without_label = line.split('|').next_back().unwrap_or(line).trim_end();
+                if let Some(arrow) = FLOWCHART_ARROWS.iter().find(|arrow| without_label.ends_with(*arrow)) {
+                    issues.push((offset, format!("`{arrow}` has no target node")));
+                }
+            }
+            "sequenceDiagram" => {
+                if SEQUENCE_BLOCKS.contains(&keyword) {
+                    blocks.push((offset, keyword));
+                } else if keyword == "else" && !blocks.iter().any(|(_, b)| *b == "alt" || *b == "critical") {
+                    issues.push((offset, "`else` outside an `alt` block".to_string()));
+                } else if keyword == "and" && !blocks.iter().any(|(_, b)| *b == "par") {
+                    issues.push((offset, "`and` outside a `par` block".to_string()));
+                } else if let Some(problem) = sequence_message_problem(line) {
+                    issues.push((offset, problem.to_string()));
+                }
+            }
+            _ => {
+                if let Some(problem) = brackets.feed(offset, line) {
+                    issues.push((offset, problem));
+                }
+            }
+        }
+    }
+    issues.extend(brackets.unclosed());
+    issues.extend(
+        blocks
+            .into_iter()
+            .map(|(offset, block)| (offset, format!("`{block}` is never closed with `end`"))),
+    );
+    issues
+}
+
+fn asymmetric_node_regex() -> &'static Regex {
+    static ASYMMETRIC: OnceLock<Regex> = OnceLock::new();
+    ASYMMETRIC.get_or_init(|| Regex::new(r"\w>[^\]]*\]").expect("asymmetric node pattern is valid"))
+}
+
+fn message_regex() -> &'static Regex {
+    static MESSAGE: OnceLock<Regex> = OnceLock::new();
+    MESSAGE.get_or_init(|| {
+        Regex::new(r"^[^-]*[^-\s][^-]*?\s*(<<)?-{1,2}(>>|>|x|\))\s*[+-]?\s*[^-+\s>)]")
+            .expect("message pattern is valid")
+    })
+}
+
+/// Why a sequence diagram line that looks like a message is not one.
+fn sequence_message_problem(line: &str) -> Option<&'static str> {
+    let (arrow, text) = match line.split_once(':') {
+        Some((arrow, text)) => (arrow, Some(text)),
+        None => (line, None),
+    };
+    if !arrow.contains("->") && !arrow.contains("--") {
+        return None;
+    }
+    if !message_regex().is_match(arrow.trim()) {
+        return Some("message arrow needs a sending and a receiving participant");
+    }
+    text.is_none().then_some("message needs `: text` after the participants")
+}
+
+fn check_plantuml(lines: &[String]) -> Vec<(usize, String)> {
+    let mut statements = lines
+        .iter()
+        .enumerate()
+        .map(|(offset, line)| (offset, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('\''));
+
+    let Some((start_offset, start)) = statements.next() else {
+        return vec![(0, "diagram is empty".to_string())];
+    };
+    let Some(kind) = start
+        .strip_prefix("@start")
+        .map(|rest| rest.split_whitespace().next().unwrap_or_default())
+    else {
+        return vec![(start_offset, "diagram must start with `@startuml`".to_string())];
+    };
+
+    let mut issues = Vec::new();
+    let mut ended = false;
+    for (offset, line) in statements {
+        if ended {
+            issues.push((offset, format!("text after `@end{kind}`")));
+            break;
+        }
+        if let Some(end) = line.strip_prefix("@end") {
+            if end.trim() != kind {
+                issues.push((offset, format!("`@end{}` does not match `@start{kind}`", end.trim())));
+            }
+            ended = true;
+        } else if line.starts_with("@start") {
+            issues.push((offset, format!("`@start{kind}` is still open")));
+        }
+    }
+    if !ended {
+        issues.push((start_offset, format!("`@start{kind}` is never closed with `@end{kind}`")));
+    }
+    issues
+}
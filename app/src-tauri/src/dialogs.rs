@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use tauri_plugin_dialog::{DialogExt, FilePath, MessageDialogButtons, MessageDialogKind};
+
+use crate::error::{AppError, AppResult};
+
+const DIALOG_TITLE: &str = "DocReviewer";
+
+/// Ask the user to confirm `message` with OK or Cancel in a native dialog. The answer
+/// comes from the user rather than the webview, so it can gate grants that a script in
+/// the webview must not be able to make for itself.
+pub async fn confirm(app: &tauri::AppHandle, message: String) -> AppResult<bool> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        app.dialog()
+            .message(message)
+            .title(DIALOG_TITLE)
+            .kind(MessageDialogKind::Warning)
+            .buttons(MessageDialogButtons::OkCancel)
+            .blocking_show()
+    })
+    .await
+    .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Let the user pick a folder; `None` when they cancel.
+pub async fn pick_folder(app: &tauri::AppHandle) -> AppResult<Option<PathBuf>> {
+    let app = app.clone();
+    let picked = tauri::async_runtime::spawn_blocking(move || {
+        app.dialog().file().set_title(DIALOG_TITLE).blocking_pick_folder()
+    })
+    .await
+    .map_err(|e| AppError::Internal(e.to_string()))?;
+    picked.map(into_path).transpose()
+}
+
+/// Let the user pick a file to read, offering `extensions` first; `None` when they cancel.
+pub async fn pick_file(
+    app: &tauri::AppHandle,
+    extensions: &'static [&'static str],
+) -> AppResult<Option<PathBuf>> {
+    let app = app.clone();
+    let picked = tauri::async_runtime::spawn_blocking(move || {
+        app.dialog()
+            .file()
+            .set_title(DIALOG_TITLE)
+            .add_filter(extensions.join(", "), extensions)
+            .blocking_pick_file()
+    })
+    .await
+    .map_err(|e| AppError::Internal(e.to_string()))?;
+    picked.map(into_path).transpose()
+}
+
+/// Let the user choose where to save a file, suggesting `file_name`; `None` when they
+/// cancel.
+async fn pick_save_path(app: &tauri::AppHandle, file_name: String) -> AppResult<Option<PathBuf>> {
+    let app = app.clone();
+    let picked = tauri::async_runtime::spawn_blocking(move || {
+        app.dialog()
+            .file()
+            .set_title(DIALOG_TITLE)
+            .set_file_name(file_name)
+            .blocking_save_file()
+    })
+    .await
+    .map_err(|e| AppError::Internal(e.to_string()))?;
+    picked.map(into_path).transpose()
+}
+
+/// Let the user choose where to save `contents`, suggesting `file_name`, and write it
+/// there. Returns the written path, or `None` when they cancel.
+pub async fn save_file(
+    app: &tauri::AppHandle,
+    file_name: String,
+    contents: String,
+) -> AppResult<Option<PathBuf>> {
+    let Some(path) = pick_save_path(app, file_name).await? else {
+        return Ok(None);
+    };
+    tokio::fs::write(&path, contents).await?;
+    Ok(Some(path))
+}
+
+fn into_path(path: FilePath) -> AppResult<PathBuf> {
+    path.into_path().map_err(|e| AppError::Internal(e.to_string()))
+}
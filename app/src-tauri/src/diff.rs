@@ -0,0 +1,269 @@
+use serde::Serialize;
+use similar::{capture_diff_slices, group_diff_ops, Algorithm, ChangeTag, DiffOp, TextDiff};
+
+/// Paired lines longer than this skip intraline highlighting; a char diff of two huge
+/// minified lines costs more than it helps.
+const MAX_INTRALINE_CHARS: usize = 2_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowKind {
+    Equal,
+    Added,
+    Removed,
+    Modified,
+    /// Unchanged lines hidden between hunks; `skipped` holds how many.
+    Skip,
+}
+
+/// Changed range within a line, in UTF-16 code units so the webview can slice the
+/// string directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffCell {
+    /// 1-based line number in the base (left) or head (right) file, as GitHub uses
+    /// for review comments.
+    pub line_number: usize,
+    pub text: String,
+    pub spans: Vec<Span>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffRow {
+    pub kind: RowKind,
+    pub left: Option<DiffCell>,
+    pub right: Option<DiffCell>,
+    pub skipped: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SideBySideDiff {
+    pub path: String,
+    pub rows: Vec<DiffRow>,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+fn normalize_whitespace(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+/// Character-level changed ranges for a modified line pair.
+fn intraline_spans(old: &str, new: &str) -> (Vec<Span>, Vec<Span>) {
+    if old.len() > MAX_INTRALINE_CHARS || new.len() > MAX_INTRALINE_CHARS {
+        return (Vec::new(), Vec::new());
+    }
+
+    let diff = TextDiff::from_chars(old, new);
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    let (mut left_pos, mut right_pos) = (0, 0);
+
+    for change in diff.iter_all_changes() {
+        let len = utf16_len(change.value());
+        match change.tag() {
+            ChangeTag::Equal => {
+                left_pos += len;
+                right_pos += len;
+            }
+            ChangeTag::Delete => {
+                push_span(&mut left, left_pos, left_pos + len);
+                left_pos += len;
+            }
+            ChangeTag::Insert => {
+                push_span(&mut right, right_pos, right_pos + len);
+                right_pos += len;
+            }
+        }
+    }
+
+    (left, right)
+}
+
+/// Append a span, merging it into the previous one when they touch.
+fn push_span(spans: &mut Vec<Span>, start: usize, end: usize) {
+    match spans.last_mut() {
+        Some(last) if last.end == start => last.end = end,
+        _ => spans.push(Span { start, end }),
+    }
+}
+
+fn cell(lines: &[&str], index: usize) -> DiffCell {
+    DiffCell {
+        line_number: index + 1,
+        text: lines[index].to_string(),
+        spans: Vec::new(),
+    }
+}
+
+/// Align base and head content into side-by-side rows.
+///
+/// `context` limits output to changed hunks with that many unchanged lines around them
+/// (hidden lines become `Skip` rows); `None` returns every line. With
+/// `ignore_whitespace`, lines that differ only in whitespace count as equal.
+pub fn side_by_side(
+    path: &str,
+    base: &str,
+    head: &str,
+    context: Option<usize>,
+    ignore_whitespace: bool,
+) -> SideBySideDiff {
+    let old_lines: Vec<&str> = base.lines().collect();
+    let new_lines: Vec<&str> = head.lines().collect();
+
+    let ops = if ignore_whitespace {
+        let old_norm: Vec<String> = old_lines.iter().map(|l| normalize_whitespace(l)).collect();
+        let new_norm: Vec<String> = new_lines.iter().map(|l| normalize_whitespace(l)).collect();
+        capture_diff_slices(Algorithm::Patience, &old_norm, &new_norm)
+    } else {
+        capture_diff_slices(Algorithm::Patience, &old_lines, &new_lines)
+    };
+
+    let groups = match context {
+        Some(n) => group_diff_ops(ops, n),
+        None => vec![ops],
+    };
+
+    let mut result = SideBySideDiff {
+        path: path.to_string(),
+        rows: Vec::new(),
+        additions: 0,
+        deletions: 0,
+    };
+    let mut next_old = 0;
+
+    for group in groups {
+        let Some(first) = group.first() else {
+            continue;
+        };
+        let start_old = first.old_range().start;
+        if start_old > next_old {
+            result.rows.push(skip_row(start_old - next_old));
+        }
+
+        for op in &group {
+            push_op_rows(&mut result, op, &old_lines, &new_lines);
+        }
+
+        if let Some(last) = group.last() {
+            next_old = last.old_range().end;
+        }
+    }
+
+    if context.is_some() && next_old < old_lines.len() && !result.rows.is_empty() {
+        result.rows.push(skip_row(old_lines.len() - next_old));
+    }
+
+    result
+}
+
+fn skip_row(count: usize) -> DiffRow {
+    DiffRow {
+        kind: RowKind::Skip,
+        left: None,
+        right: None,
+        skipped: Some(count),
+    }
+}
+
+fn push_op_rows(result: &mut SideBySideDiff, op: &DiffOp, old_lines: &[&str], new_lines: &[&str]) {
+    let old_range = op.old_range();
+    let new_range = op.new_range();
+
+    match op {
+        DiffOp::Equal { .. } => {
+            for (old, new) in old_range.zip(new_range) {
+                result.rows.push(DiffRow {
+                    kind: RowKind::Equal,
+                    left: Some(cell(old_lines, old)),
+                    right: Some(cell(new_lines, new)),
+                    skipped: None,
+                });
+            }
+        }
+        DiffOp::Delete { .. } => {
+            result.deletions += old_range.len();
+            for old in old_range {
+                result.rows.push(DiffRow {
+                    kind: RowKind::Removed,
+                    left: Some(cell(old_lines, old)),
+                    right: None,
+                    skipped: None,
+                });
+            }
+        }
+        DiffOp::Insert { .. } => {
+            result.additions += new_range.len();
+            for new in new_range {
+                result.rows.push(DiffRow {
+                    kind: RowKind::Added,
+                    left: None,
+                    right: Some(cell(new_lines, new)),
+                    skipped: None,
+                });
+            }
+        }
+        DiffOp::Replace { .. } => {
+            result.deletions += old_range.len();
+            result.additions += new_range.len();
+            let rows = old_range.len().max(new_range.len());
+            for offset in 0..rows {
+                let old = (offset < old_range.len()).then(|| old_range.start + offset);
+                let new = (offset < new_range.len()).then(|| new_range.start + offset);
+                let mut left = old.map(|i| cell(old_lines, i));
+                let mut right = new.map(|i| cell(new_lines, i));
+
+                let kind = match (&mut left, &mut right) {
+                    (Some(l), Some(r)) => {
+                        let (left_spans, right_spans) = intraline_spans(&l.text, &r.text);
+                        l.spans = left_spans;
+                        r.spans = right_spans;
+                        RowKind::Modified
+                    }
+                    (Some(_), None) => RowKind::Removed,
+                    _ => RowKind::Added,
+                };
+
+                result.rows.push(DiffRow {
+                    kind,
+                    left,
+                    right,
+                    skipped: None,
+                });
+            }
+        }
+    }
+}
+
+/// Lines a unified diff patch adds, with their 1-based line numbers in the head file.
+pub fn added_lines(patch: &str) -> Vec<(u64, &str)> {
+    let mut added = Vec::new();
+    let mut right_line = 0u64;
+
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            if let Some((_, right_start)) = crate::github::parse_hunk_header(line) {
+                right_line = right_start;
+            }
+            continue;
+        }
+
+        if let Some(text) = line.strip_prefix('+') {
+            added.push((right_line, text));
+            right_line += 1;
+        } else if !line.starts_with('-') && !line.starts_with('\\') {
+            right_line += 1;
+        }
+    }
+
+    added
+}
@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::i18n::{tf, Msg};
+use crate::pr_size::is_prose_path;
+
+/// How far a PR's branch has fallen behind its base.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BaseDrift {
+    pub base_ref: String,
+    pub merge_base_sha: String,
+    /// Commits on the base branch since the PR branched off.
+    pub behind_by: u64,
+    /// Commits on the PR branch not yet on the base branch.
+    pub ahead_by: u64,
+    /// Files changed on the base branch since the merge base.
+    pub base_changed_files: usize,
+    /// Files changed both by the PR and on the base branch, likely to conflict.
+    pub overlapping_files: Vec<String>,
+    /// One per overlapping documentation file.
+    pub warnings: Vec<String>,
+}
+
+/// PR paths that were also changed on the base branch, sorted and deduplicated.
+pub fn overlapping(base_files: &[String], pr_files: &[String]) -> Vec<String> {
+    let changed_on_base: HashSet<&str> = base_files.iter().map(String::as_str).collect();
+    let mut overlapping: Vec<String> = pr_files
+        .iter()
+        .filter(|path| changed_on_base.contains(path.as_str()))
+        .cloned()
+        .collect();
+    overlapping.sort();
+    overlapping.dedup();
+    overlapping
+}
+
+/// Combine the compare results. `base_files` are the paths changed on the base branch
+/// since the merge base; `pr_files` are the PR's paths, including old names of renames.
+pub fn build_drift(
+    base_ref: &str,
+    merge_base_sha: &str,
+    behind_by: u64,
+    ahead_by: u64,
+    base_files: &[String],
+    pr_files: &[String],
+) -> BaseDrift {
+    let overlapping_files = overlapping(base_files, pr_files);
+
+    let warnings = overlapping_files
+        .iter()
+        .filter(|path| is_prose_path(path))
+        .map(|path| tf(Msg::BaseDriftDocChanged, &[path, &base_ref]))
+        .collect();
+
+    BaseDrift {
+        base_ref: base_ref.to_string(),
+        merge_base_sha: merge_base_sha.to_string(),
+        behind_by,
+        ahead_by,
+        base_changed_files: base_files.iter().collect::<HashSet<_>>().len(),
+        overlapping_files,
+        warnings,
+    }
+}
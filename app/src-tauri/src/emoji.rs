@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EmojiEntry {
+    /// Name without colons, e.g. `shipit`.
+    pub shortcode: String,
+    pub image_url: String,
+    /// The character sequence for standard emoji; `None` for GitHub's custom ones such
+    /// as `:shipit:` and `:octocat:`, which only exist as images.
+    pub unicode: Option<String>,
+}
+
+/// Every emoji GitHub knows, sorted by shortcode.
+#[derive(Debug, Clone, Default)]
+pub struct EmojiSet {
+    entries: Vec<EmojiEntry>,
+    by_shortcode: HashMap<String, usize>,
+}
+
+/// Decode `.../unicode/1f1fa-1f1f8.png?v8` into the emoji it depicts.
+fn unicode_from_url(url: &str) -> Option<String> {
+    let file = url.split('?').next()?.rsplit_once("/unicode/")?.1;
+    let stem = file.strip_suffix(".png")?;
+    stem.split('-')
+        .map(|hex| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32))
+        .collect()
+}
+
+impl EmojiSet {
+    /// Build from the `GET /emojis` response, a map of shortcode to image URL.
+    pub fn from_api(map: HashMap<String, String>) -> Self {
+        let mut entries: Vec<EmojiEntry> = map
+            .into_iter()
+            .map(|(shortcode, image_url)| EmojiEntry {
+                unicode: unicode_from_url(&image_url),
+                shortcode,
+                image_url,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.shortcode.cmp(&b.shortcode));
+        let by_shortcode = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.shortcode.clone(), index))
+            .collect();
+        Self {
+            entries,
+            by_shortcode,
+        }
+    }
+
+    pub fn get(&self, shortcode: &str) -> Option<&EmojiEntry> {
+        self.by_shortcode.get(shortcode).map(|&index| &self.entries[index])
+    }
+
+    /// Autocomplete matches: shortcodes starting with `filter` first, then those that
+    /// contain it anywhere. An empty filter returns everything.
+    pub fn search(&self, filter: &str, limit: usize) -> Vec<EmojiEntry> {
+        let needle = filter.trim().trim_matches(':').to_lowercase();
+        let (prefix, contains): (Vec<&EmojiEntry>, Vec<&EmojiEntry>) = self
+            .entries
+            .iter()
+            .filter(|entry| entry.shortcode.contains(&needle))
+            .partition(|entry| entry.shortcode.starts_with(&needle));
+        prefix
+            .into_iter()
+            .chain(contains)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Replace known `:shortcode:`s with the emoji character, or with an inline image
+    /// for custom emoji. Unknown shortcodes and anything inside code spans or fenced
+    /// blocks are left as written.
+    pub fn expand(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut in_fence = false;
+
+        for line in text.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+                out.push_str(line);
+                continue;
+            }
+            if in_fence {
+                out.push_str(line);
+                continue;
+            }
+
+            // Odd-numbered pieces between backticks are inline code
+            for (index, piece) in line.split('`').enumerate() {
+                if index > 0 {
+                    out.push('`');
+                }
+                if index % 2 == 1 {
+                    out.push_str(piece);
+                } else {
+                    out.push_str(&self.expand_plain(piece));
+                }
+            }
+        }
+
+        out
+    }
+
+    fn expand_plain(&self, text: &str) -> String {
+        shortcode_regex()
+            .replace_all(text, |caps: &Captures| match self.get(&caps[1]) {
+                Some(EmojiEntry {
+                    unicode: Some(unicode),
+                    ..
+                }) => unicode.clone(),
+                Some(entry) => format!(
+                    r#"<img class="emoji" title=":{0}:" alt=":{0}:" src="{1}" height="20" width="20" align="absmiddle">"#,
+                    entry.shortcode, entry.image_url
+                ),
+                None => caps[0].to_string(),
+            })
+            .into_owned()
+    }
+}
+
+fn shortcode_regex() -> &'static Regex {
+    static SHORTCODE: OnceLock<Regex> = OnceLock::new();
+    SHORTCODE.get_or_init(|| {
+        Regex::new(r":([a-z0-9_+-]+):").expect("shortcode pattern is valid")
+    })
+}
@@ -2,36 +2,53 @@ use std::io;
 
 use thiserror::Error;
 
+use crate::i18n::{t, tf, Msg};
+
 pub type AppResult<T> = Result<T, AppError>;
 
 #[derive(Debug, Error)]
 pub enum AppError {
-    #[error("missing configuration value: {0}")]
+    #[error("{}", tf(Msg::MissingConfig, &[.0]))]
     MissingConfig(&'static str),
-    #[error("oauth flow was cancelled or timed out")]
+    #[error("{}", t(Msg::OAuthCancelled))]
     OAuthCancelled,
-    #[error("received an invalid oauth callback")]
+    #[error("{}", t(Msg::InvalidOAuthCallback))]
     InvalidOAuthCallback,
-    #[error("http error: {0}")]
+    #[error("{}", tf(Msg::Http, &[.0]))]
     Http(#[from] reqwest::Error),
-    #[error("io error: {0}")]
+    #[error("{}", tf(Msg::Io, &[.0]))]
     Io(#[from] io::Error),
-    #[error("url parse error: {0}")]
+    #[error("{}", tf(Msg::Url, &[.0]))]
     Url(#[from] url::ParseError),
-    #[error("serialization error: {0}")]
+    #[error("{}", tf(Msg::Serde, &[.0]))]
     Serde(#[from] serde_json::Error),
-    #[error("secure storage error: {0}")]
+    #[error("{}", tf(Msg::Keyring, &[.0]))]
     Keyring(#[from] keyring::Error),
-    #[error("database error: {0}")]
+    #[error("{}", tf(Msg::Database, &[.0]))]
     Database(#[from] rusqlite::Error),
-    #[error("operation timed out")]
+    #[error("{}", t(Msg::Timeout))]
     Timeout,
-    #[error("internal error: {0}")]
+    #[error("{}", tf(Msg::Internal, &[.0]))]
     Internal(String),
     #[error("{0}")]
     SsoAuthorizationRequired(String),
     #[error("{0}")]
     Api(String),
+    #[error("{}", tf(Msg::InvalidInput, &[.0]))]
+    InvalidInput(String),
+    #[error("{}", tf(Msg::OwnerNotAllowed, &[.0]))]
+    OwnerNotAllowed(String),
+    #[error("{}", tf(Msg::PathNotAllowed, &[.0]))]
+    PathNotAllowed(String),
+    #[error("{}", tf(Msg::ConfirmationRequired, &[.0]))]
+    ConfirmationRequired(String),
+    #[error("{}", t(Msg::AuthExpired))]
+    AuthExpired,
+    #[error("{}", tf(Msg::MissingScope, &[.0]))]
+    MissingScope(String),
+    /// GitHub refused a request for a rate limit; holds when requests can resume.
+    #[error("{}", tf(Msg::RateLimited, &[.0]))]
+    RateLimited(String),
 }
 
 impl From<tokio::time::error::Elapsed> for AppError {
@@ -0,0 +1,18 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use tauri::Emitter;
+
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// Lets code without an `AppHandle` at hand send events to the frontend.
+pub fn init(app: tauri::AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// Send `payload` to the frontend; a no-op before `init`, e.g. in tests.
+pub fn emit<S: Serialize + Clone>(event: &str, payload: S) {
+    if let Some(app) = APP.get() {
+        let _ = app.emit(event, payload);
+    }
+}
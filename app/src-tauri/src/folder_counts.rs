@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use crate::local_files::is_markdown;
+
+/// Markdown file count of a folder tree with the modification time of every directory in
+/// it. Adding, removing, or renaming a file changes the mtime of its directory, so the
+/// count holds as long as none of those times move.
+#[derive(Debug, Clone)]
+struct CachedCount {
+    count: usize,
+    directories: Vec<(PathBuf, SystemTime)>,
+}
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, CachedCount>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, CachedCount>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn modified(path: &Path) -> io::Result<SystemTime> {
+    fs::metadata(path)?.modified()
+}
+
+/// The mtime is read before the listing, so a change made mid-walk invalidates the count
+/// on the next call instead of being lost.
+fn walk(dir: &Path, directories: &mut Vec<(PathBuf, SystemTime)>) -> io::Result<usize> {
+    directories.push((dir.to_path_buf(), modified(dir)?));
+    let mut count = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            count += walk(&entry.path(), directories)?;
+        } else if file_type.is_file() && is_markdown(&entry.path()) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn is_fresh(cached: &CachedCount) -> bool {
+    cached
+        .directories
+        .iter()
+        .all(|(dir, at)| modified(dir).is_ok_and(|now| now == *at))
+}
+
+/// Number of markdown files under `dir`. Blocks on file I/O; a cached count is reused
+/// while no directory in the tree has changed, which costs one `stat` per directory
+/// instead of a full listing.
+pub fn markdown_file_count(dir: &Path) -> io::Result<usize> {
+    let cached = cache().lock().ok().and_then(|cache| cache.get(dir).cloned());
+    if let Some(cached) = cached.filter(is_fresh) {
+        return Ok(cached.count);
+    }
+
+    let mut directories = Vec::new();
+    let count = walk(dir, &mut directories)?;
+    if let Ok(mut cache) = cache().lock() {
+        cache.insert(dir.to_path_buf(), CachedCount { count, directories });
+    }
+    Ok(count)
+}
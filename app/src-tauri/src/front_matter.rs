@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::diff::added_lines;
+use crate::i18n::{tf, Msg};
+use crate::local_files::is_markdown_path;
+
+/// Front-matter keys that record when a doc was last checked, as used by Microsoft Learn
+/// (`ms.date`) and most docs-as-code pipelines (`last_reviewed`).
+pub const DEFAULT_DATE_FIELDS: &[&str] = &["ms.date", "last_reviewed"];
+
+/// Date formats seen in front matter, tried in order; an updated value keeps the format
+/// of the old one.
+const DATE_FORMATS: &[&str] = &["%m/%d/%Y", "%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y"];
+
+/// A top-level `key: value` line in a markdown file's front matter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatterField {
+    pub key: String,
+    /// Value with surrounding quotes removed.
+    pub value: String,
+    /// 1-based line in the file.
+    pub line: u64,
+}
+
+/// A changed doc whose date field was left as it was.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StaleDateFinding {
+    pub path: String,
+    /// 1-based head line of the date field.
+    pub line: u64,
+    pub field: String,
+    pub value: String,
+    /// Today's date in the format of `value`, when the format is recognized.
+    pub suggested_value: Option<String>,
+    /// Draft comment, with a suggestion block when `suggested_value` is known.
+    pub suggested_comment: String,
+}
+
+/// Top-level scalar fields of the `---` block at the start of `content`. Nested and
+/// list values are skipped; an unterminated block has no fields.
+pub fn fields(content: &str) -> Vec<FrontMatterField> {
+    let mut lines = content.lines().enumerate();
+    if lines.next().is_none_or(|(_, line)| line.trim_end() != "---") {
+        return Vec::new();
+    }
+
+    let mut fields = Vec::new();
+    for (index, line) in lines {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            return fields;
+        }
+        if line.starts_with(char::is_whitespace) || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let unquoted = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        fields.push(FrontMatterField {
+            key: key.trim().to_string(),
+            value: unquoted.to_string(),
+            line: index as u64 + 1,
+        });
+    }
+    Vec::new()
+}
+
+/// `today` written in the same format as `value`.
+pub fn updated_date(value: &str, today: NaiveDate) -> Option<String> {
+    DATE_FORMATS
+        .iter()
+        .find(|format| NaiveDate::parse_from_str(value, format).is_ok())
+        .map(|format| today.format(format).to_string())
+}
+
+/// Flag date fields a markdown change left untouched. Only files the `patch` changes are
+/// checked, and only fields that exist: a doc without `ms.date` is not part of the
+/// freshness requirement. A field already set to `today` is not flagged.
+pub fn check_freshness(
+    path: &str,
+    head_content: &str,
+    patch: &str,
+    date_fields: &[String],
+    today: NaiveDate,
+) -> Vec<StaleDateFinding> {
+    let changed: HashSet<u64> = added_lines(patch).into_iter().map(|(line, _)| line).collect();
+    if !is_markdown_path(path) || changed.is_empty() {
+        return Vec::new();
+    }
+
+    let lines: Vec<&str> = head_content.lines().collect();
+    fields(head_content)
+        .into_iter()
+        .filter(|field| date_fields.iter().any(|f| f.eq_ignore_ascii_case(&field.key)))
+        .filter(|field| !changed.contains(&field.line))
+        .filter_map(|field| {
+            let suggested_value = updated_date(&field.value, today);
+            if suggested_value.as_deref() == Some(field.value.as_str()) {
+                return None;
+            }
+            let mut suggested_comment = tf(Msg::StaleDateComment, &[&field.key, &field.value]);
+            if let Some(new_value) = &suggested_value {
+                let original = lines[field.line as usize - 1];
+                suggested_comment.push_str(&format!(
+                    "\n\n```suggestion\n{}\n```",
+                    original.replacen(&field.value, new_value, 1)
+                ));
+            }
+            Some(StaleDateFinding {
+                path: path.to_string(),
+                line: field.line,
+                field: field.key,
+                value: field.value,
+                suggested_value,
+                suggested_comment,
+            })
+        })
+        .collect()
+}
@@ -1,20 +1,38 @@
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
 use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::{json, Map, Value};
 use tauri::Emitter;
 use tracing::{debug, info, warn};
 
+use crate::api_version;
+use crate::attention::{needs_attention, TimelineActivity};
+use crate::auth::expire_session;
+use crate::cache::TtlCache;
+use crate::comment_search::{self, CommentSearchHit};
+use crate::comment_sync;
 use crate::error::{AppError, AppResult};
 use crate::models::{
-    FileLanguage, PullRequestComment, PullRequestDetail, PullRequestFile, PullRequestReview,
-    PullRequestMetadata, PullRequestSummary,
+    tally_participants, AuthorContext, CommentReactions, CommentThread, FileLanguage, IssueDetail, LinkedIssue, MergeStatus, OrganizationSummary, PullRequestComment, PullRequestDetail,
+    PrReviewState, PullRequestFile, PullRequestReview, PullRequestMetadata, PullRequestSummary,
+    ReactionSummary, RepositorySummary, ReviewFollowupResult, Suggestion,
 };
+use crate::oauth_scopes;
+use crate::permalinks::CommentKind;
+use crate::rate_limit::{self, RateLimitKind};
+use crate::recorder::RecordedSend;
+use crate::review_activity::{self, ActivityKind, ActivityRecord};
+use crate::second_pass::{self, ChangedFile, ChangesSinceReview, ReviewedCommit};
+use crate::settings::ReviewFollowup;
+use crate::submission_report::{self, CommentBatch, FailedComment};
+use crate::time_format::display_time;
+
+mod graphql;
 
 const API_BASE: &str = "https://api.github.com";
+const PAGE_SIZE: usize = 100;
 const USER_AGENT_VALUE: &str = "github-review-app/0.1";
-const API_VERSION_HEADER: &str = "x-github-api-version";
-const API_VERSION_VALUE: &str = "2022-11-28";
 
 // Prevent enormous HTML/JSON bodies from flooding the terminal.
 const LOG_BODY_SNIPPET_CHARS: usize = 800;
@@ -71,10 +89,20 @@ async fn ensure_success(
     context: &str,
 ) -> AppResult<reqwest::Response> {
     let status = response.status();
+    let endpoint = response.url().path().to_string();
+    if let Some(warning) = api_version::detect(response.headers(), context, &endpoint) {
+        api_version::record(warning);
+    }
+    rate_limit::observe(response.headers());
     if status.is_success() {
         return Ok(response);
     }
 
+    if status == StatusCode::UNAUTHORIZED {
+        expire_session(context);
+        return Err(AppError::AuthExpired);
+    }
+
     let headers = response.headers().clone();
 
     if status == StatusCode::FORBIDDEN {
@@ -98,11 +126,32 @@ async fn ensure_success(
                 return Err(AppError::SsoAuthorizationRequired(message));
             }
         }
+
+        if let Some(scope) = oauth_scopes::missing_scope(&headers) {
+            oauth_scopes::record(context, &scope);
+            return Err(AppError::MissingScope(scope));
+        }
     }
 
     let body = response.text().await.unwrap_or_default();
+    if api_version::is_unsupported_version(status, &body) {
+        api_version::record(api_version::unsupported_version(context, &endpoint));
+    }
     let log_snippet = body_snippet(&body, LOG_BODY_SNIPPET_CHARS);
 
+    if let Some(kind) = rate_limit::classify(status, &headers, &body) {
+        let now = chrono::Utc::now();
+        let pause = rate_limit::pause_for(kind, &headers, 0, now);
+        let resumes_at = now + chrono::Duration::from_std(pause).unwrap_or_default();
+        warn!(
+            context = context,
+            kind = kind.as_str(),
+            pause_secs = pause.as_secs(),
+            "GitHub API request refused for a rate limit"
+        );
+        return Err(AppError::RateLimited(display_time(&resumes_at.to_rfc3339())));
+    }
+
     // Log only a short snippet of the response to avoid dumping entire HTML pages.
     warn!(
         context = context,
@@ -184,10 +233,8 @@ fn build_client(token: &str) -> AppResult<reqwest::Client> {
             .map_err(|_| AppError::MissingConfig("invalid access token"))?,
     );
     headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
-    headers.insert(
-        HeaderName::from_static(API_VERSION_HEADER),
-        HeaderValue::from_static(API_VERSION_VALUE),
-    );
+    let (version_name, version_value) = api_version::version_header();
+    headers.insert(version_name, version_value);
 
     let client = reqwest::Client::builder()
         .default_headers(headers)
@@ -200,13 +247,25 @@ fn build_client(token: &str) -> AppResult<reqwest::Client> {
 
 pub async fn fetch_authenticated_user(token: &str) -> AppResult<GitHubUser> {
     let client = build_client(token)?;
-    let response = client.get(format!("{API_BASE}/user")).send().await?;
+    let response = client.get(format!("{API_BASE}/user")).send_recorded().await?;
 
     let response = ensure_success(response, "fetch authenticated user").await?;
 
     Ok(response.json::<GitHubUser>().await?)
 }
 
+/// Ask GitHub for every quota and keep them. `/rate_limit` does not count against any.
+pub async fn refresh_rate_limits(token: &str) -> AppResult<Vec<rate_limit::RateLimitStatus>> {
+    let client = build_client(token)?;
+    let response = client.get(format!("{API_BASE}/rate_limit")).send_recorded().await?;
+    let response = ensure_success(response, "fetch rate limits").await?;
+    let statuses = rate_limit::parse_rate_limit_body(&response.json::<Value>().await?);
+    for status in &statuses {
+        rate_limit::record(status.clone());
+    }
+    Ok(statuses)
+}
+
 pub async fn list_pull_requests_with_login(
     token: &str,
     owner: &str,
@@ -216,6 +275,12 @@ pub async fn list_pull_requests_with_login(
 ) -> AppResult<Vec<PullRequestSummary>> {
     let client = build_client(token)?;
     let state_value = state.unwrap_or("open");
+    let mut sizes = fetch_pull_request_sizes(&client, owner, repo, state_value)
+        .await
+        .unwrap_or_else(|err| {
+            warn!("Could not fetch pull request sizes for {owner}/{repo}: {err}");
+            std::collections::HashMap::new()
+        });
     let mut all_pulls = Vec::new();
     let mut page = 1;
     let per_page = 100;
@@ -228,7 +293,7 @@ pub async fn list_pull_requests_with_login(
                 ("per_page", &per_page.to_string()),
                 ("page", &page.to_string()),
             ])
-            .send()
+            .send_recorded()
             .await?;
 
         let pulls = ensure_success(pulls, &format!("list pull requests for {owner}/{repo}")).await?;
@@ -255,6 +320,10 @@ pub async fn list_pull_requests_with_login(
                 state: pr.state.clone(),
                 merged: pr.merged_at.is_some(),
                 locked: pr.locked.unwrap_or(false),
+                size: sizes.remove(&pr.number),
+                new_since_last_visit: false,
+                needs_my_attention: false,
+                pending_comment_count: 0,
             });
         }
 
@@ -266,9 +335,348 @@ pub async fn list_pull_requests_with_login(
         page += 1;
     }
 
+    if let Some(login) = current_login {
+        let open: Vec<(String, String, u64)> = all_pulls
+            .iter()
+            .filter(|pr| pr.state == "open")
+            .map(|pr| (owner.to_string(), repo.to_string(), pr.number))
+            .collect();
+        match attention_flags(&client, &open, login).await {
+            Ok(flags) => {
+                let mut flags = flags.into_iter();
+                for pr in all_pulls.iter_mut().filter(|pr| pr.state == "open") {
+                    pr.needs_my_attention = flags.next().flatten().unwrap_or(false);
+                }
+            }
+            Err(err) => warn!("Could not check which PRs of {owner}/{repo} need attention: {err}"),
+        }
+    }
+
     Ok(all_pulls)
 }
 
+const PULL_REQUEST_SIZES_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $states: [PullRequestState!], $after: String) {
+  repository(owner: $owner, name: $repo) {
+    pullRequests(states: $states, first: 50, after: $after, orderBy: { field: CREATED_AT, direction: DESC }) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        number
+        additions
+        deletions
+        changedFiles
+        files(first: 100) { nodes { path additions deletions } }
+      }
+    }
+  }
+}
+"#;
+
+/// Size class and review estimate for each PR in a listing, keyed by number. One
+/// GraphQL query per 50 PRs, since the REST list endpoint has no line counts.
+async fn fetch_pull_request_sizes(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    state: &str,
+) -> AppResult<std::collections::HashMap<u64, crate::pr_size::PrSize>> {
+    let states = match state {
+        "closed" => json!(["CLOSED", "MERGED"]),
+        "all" => Value::Null,
+        _ => json!(["OPEN"]),
+    };
+    let config = crate::review_storage::get_storage()
+        .and_then(crate::pr_size::load_config)
+        .unwrap_or_default();
+    let mut sizes = std::collections::HashMap::new();
+    let mut after: Option<String> = None;
+
+    loop {
+        let data: GraphQlPullRequestSizeData = graphql::query(
+            client,
+            PULL_REQUEST_SIZES_QUERY,
+            json!({ "owner": owner, "repo": repo, "states": states, "after": after }),
+            &format!("fetch pull request sizes for {owner}/{repo}"),
+        )
+        .await?;
+        let Some(repository) = data.repository else {
+            break;
+        };
+        let connection = repository.pull_requests;
+
+        for pr in connection.nodes.into_iter().flatten() {
+            let files: Vec<crate::pr_size::FileChange> = pr
+                .files
+                .into_iter()
+                .flat_map(|files| files.nodes.into_iter().flatten())
+                .map(|file| crate::pr_size::FileChange {
+                    path: file.path,
+                    additions: file.additions,
+                    deletions: file.deletions,
+                })
+                .collect();
+            let size = crate::pr_size::classify(
+                pr.additions,
+                pr.deletions,
+                &files,
+                pr.changed_files,
+                &config,
+            );
+            sizes.insert(pr.number, size);
+        }
+
+        match connection.page_info.end_cursor {
+            Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+            _ => break,
+        }
+    }
+
+    Ok(sizes)
+}
+
+/// GET every page of a list endpoint (100 items per page).
+async fn fetch_all_pages<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+    query: &[(&str, &str)],
+    context: &str,
+) -> AppResult<Vec<T>> {
+    let mut all_items = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let page_str = page.to_string();
+        let response = client
+            .get(url)
+            .query(query)
+            .query(&[("per_page", "100"), ("page", page_str.as_str())])
+            .send_recorded()
+            .await?;
+
+        let response = ensure_success(response, &format!("{context} (page {})", page)).await?;
+        let items = response.json::<Vec<T>>().await?;
+        let count = items.len();
+        all_items.extend(items);
+
+        // If we got less than 100, we've reached the last page
+        if count < 100 {
+            break;
+        }
+
+        page += 1;
+    }
+
+    Ok(all_items)
+}
+
+/// Affiliations queried separately so each repo can be tagged with how the user is
+/// connected to it. Earlier entries win when a repo appears under several.
+const AFFILIATIONS: &[&str] = &["owner", "collaborator", "organization_member"];
+
+/// Repositories the user owns, collaborates on, or can see through an organization,
+/// most recently updated first.
+pub async fn list_my_repositories(token: &str) -> AppResult<Vec<RepositorySummary>> {
+    let client = build_client(token)?;
+    let mut seen = std::collections::HashSet::new();
+    let mut summaries = Vec::new();
+
+    for affiliation in AFFILIATIONS {
+        let repos: Vec<GitHubRepository> = fetch_all_pages(
+            &client,
+            &format!("{API_BASE}/user/repos"),
+            &[("affiliation", affiliation), ("sort", "updated")],
+            &format!("list {affiliation} repositories for authenticated user"),
+        )
+        .await?;
+
+        for repo in repos {
+            if seen.insert(repo.full_name.to_ascii_lowercase()) {
+                let mut summary = map_repository(repo);
+                summary.affiliation = Some(affiliation.to_string());
+                summaries.push(summary);
+            }
+        }
+    }
+
+    summaries.retain(|repo| crate::owner_policy::is_allowed(&repo.owner));
+    summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    Ok(summaries)
+}
+
+/// Starred repositories, tagged with their affiliation and latest PR activity and
+/// sorted by that activity.
+pub async fn list_starred_repositories(
+    token: &str,
+    affiliated: &[RepositorySummary],
+) -> AppResult<Vec<RepositorySummary>> {
+    let client = build_client(token)?;
+    let repos: Vec<GitHubRepository> = fetch_all_pages(
+        &client,
+        &format!("{API_BASE}/user/starred"),
+        &[("sort", "updated")],
+        "list starred repositories for authenticated user",
+    )
+    .await?;
+
+    let mut summaries: Vec<RepositorySummary> = repos
+        .into_iter()
+        .filter(|repo| crate::owner_policy::is_allowed(&repo.owner.login))
+        .map(|repo| {
+            let mut summary = map_repository(repo);
+            summary.affiliation = affiliated
+                .iter()
+                .find(|r| r.full_name.eq_ignore_ascii_case(&summary.full_name))
+                .and_then(|r| r.affiliation.clone());
+            summary
+        })
+        .collect();
+
+    fill_last_pr_activity(&client, &mut summaries).await;
+    crate::models::sort_by_recent_activity(&mut summaries);
+    Ok(summaries)
+}
+
+/// Upper bound on per-repo PR lookups, so a user with hundreds of stars does not wait
+/// on hundreds of requests. The most recently pushed repos are looked up first.
+const MAX_ACTIVITY_LOOKUPS: usize = 50;
+
+async fn fill_last_pr_activity(client: &reqwest::Client, repos: &mut [RepositorySummary]) {
+    let mut order: Vec<usize> = (0..repos.len()).collect();
+    order.sort_by(|&a, &b| repos[b].pushed_at.cmp(&repos[a].pushed_at));
+    order.truncate(MAX_ACTIVITY_LOOKUPS);
+
+    let mut lookups = tokio::task::JoinSet::new();
+    for index in order {
+        let client = client.clone();
+        let owner = repos[index].owner.clone();
+        let name = repos[index].name.clone();
+        lookups.spawn(async move {
+            (index, latest_pull_request_activity(&client, &owner, &name).await)
+        });
+    }
+
+    while let Some(result) = lookups.join_next().await {
+        if let Ok((index, activity)) = result {
+            repos[index].last_pr_activity = activity;
+        }
+    }
+}
+
+/// `updated_at` of the most recently updated PR in any state; errors are treated as
+/// "no activity" since this only affects ordering.
+async fn latest_pull_request_activity(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+) -> Option<String> {
+    let response = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls"))
+        .query(&[
+            ("state", "all"),
+            ("sort", "updated"),
+            ("direction", "desc"),
+            ("per_page", "1"),
+        ])
+        .send_recorded()
+        .await
+        .ok()?;
+    let response = ensure_success(response, &format!("latest pull request for {owner}/{repo}"))
+        .await
+        .ok()?;
+    let pulls = response.json::<Vec<GitHubPullRequest>>().await.ok()?;
+    pulls.into_iter().next().map(|pr| pr.updated_at)
+}
+
+/// Repositories of an organization. Falls back to the user endpoint when `org` is a
+/// personal account, so either kind of owner can be browsed.
+pub async fn list_org_repositories(token: &str, org: &str) -> AppResult<Vec<RepositorySummary>> {
+    let client = build_client(token)?;
+    let probe = client
+        .get(format!("{API_BASE}/orgs/{org}"))
+        .send_recorded()
+        .await?;
+    let url = if probe.status() == StatusCode::NOT_FOUND {
+        format!("{API_BASE}/users/{org}/repos")
+    } else {
+        ensure_success(probe, &format!("get organization {org}")).await?;
+        format!("{API_BASE}/orgs/{org}/repos")
+    };
+
+    let repos: Vec<GitHubRepository> = fetch_all_pages(
+        &client,
+        &url,
+        &[("sort", "updated")],
+        &format!("list repositories for {org}"),
+    )
+    .await?;
+
+    Ok(repos.into_iter().map(map_repository).collect())
+}
+
+pub async fn list_my_organizations(token: &str) -> AppResult<Vec<OrganizationSummary>> {
+    let client = build_client(token)?;
+    let orgs: Vec<GitHubOrganization> = fetch_all_pages(
+        &client,
+        &format!("{API_BASE}/user/orgs"),
+        &[],
+        "list organizations for authenticated user",
+    )
+    .await?;
+
+    Ok(orgs
+        .into_iter()
+        .filter(|org| crate::owner_policy::is_allowed(&org.login))
+        .map(|org| OrganizationSummary {
+            login: org.login,
+            description: org.description,
+            avatar_url: org.avatar_url,
+        })
+        .collect())
+}
+
+/// Shortcode to image URL for every emoji GitHub renders, including custom ones.
+pub async fn list_emoji(token: &str) -> AppResult<std::collections::HashMap<String, String>> {
+    let client = build_client(token)?;
+    let response = client.get(format!("{API_BASE}/emojis")).send_recorded().await?;
+    let response = ensure_success(response, "list emoji").await?;
+    Ok(response.json().await?)
+}
+
+/// Render markdown to HTML the way GitHub shows it. With a `context` of `owner/repo`,
+/// references like `#123` link into that repository.
+pub async fn render_markdown(token: &str, text: &str, context: Option<&str>) -> AppResult<String> {
+    let client = build_client(token)?;
+    let mut payload = json!({ "text": text, "mode": "gfm" });
+    if let Some(context) = context {
+        payload["context"] = json!(context);
+    }
+    let response = client
+        .post(format!("{API_BASE}/markdown"))
+        .json(&payload)
+        .send_recorded()
+        .await?;
+    let response = ensure_success(response, "render markdown").await?;
+    Ok(response.text().await?)
+}
+
+fn map_repository(repo: GitHubRepository) -> RepositorySummary {
+    RepositorySummary {
+        owner: repo.owner.login,
+        name: repo.name,
+        full_name: repo.full_name,
+        description: repo.description,
+        private: repo.private,
+        archived: repo.archived,
+        fork: repo.fork,
+        default_branch: repo.default_branch,
+        updated_at: repo.updated_at,
+        pushed_at: repo.pushed_at,
+        can_push: repo.permissions.map(|p| p.push).unwrap_or(false),
+        affiliation: None,
+        last_pr_activity: None,
+    }
+}
+
 async fn check_has_pending_review(
     client: &reqwest::Client,
     owner: &str,
@@ -289,7 +697,7 @@ async fn check_has_pending_review(
         let files_response = client
             .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/files"))
             .query(&[("per_page", "1")]) // We only need the count, not the actual files
-            .send()
+            .send_recorded()
             .await?;
         
         if let Ok(_response) = ensure_success(files_response, "count pull request files").await {
@@ -298,7 +706,7 @@ async fn check_has_pending_review(
             let files_response = client
                 .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/files"))
                 .query(&[("per_page", "100")])
-                .send()
+                .send_recorded()
                 .await?;
             
             if let Ok(response) = ensure_success(files_response, "list pull request files").await {
@@ -320,6 +728,23 @@ async fn check_has_pending_review(
     Ok((has_pending, file_count))
 }
 
+/// A pull request with its discussion and what is shown beside it, before its files
+/// are joined in.
+struct PullRequestParts {
+    pr: GitHubPullRequest,
+    review_comments: Vec<GitHubReviewComment>,
+    issue_comments: Vec<GitHubIssueComment>,
+    reviews: Vec<GitHubPullRequestReview>,
+    /// Resolution of review threads keyed by root comment id; `None` when GitHub could
+    /// not be asked.
+    resolved: Option<std::collections::HashMap<u64, bool>>,
+    reactions: ReactionSummary,
+    participant_logins: Vec<String>,
+    /// Issues GitHub links as closed by the PR, before any named in the description.
+    linked_issues: Vec<LinkedIssue>,
+    mergeability: Option<GraphQlMergeability>,
+}
+
 pub async fn get_pull_request(
     token: &str,
     owner: &str,
@@ -328,75 +753,63 @@ pub async fn get_pull_request(
     current_login: Option<&str>,
 ) -> AppResult<PullRequestDetail> {
     let client = build_client(token)?;
-    let pr = client
-        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
-        .send()
-        .await?;
-    let pr = ensure_success(pr, &format!("get pull request {owner}/{repo}#{number}")).await?;
-    let pr = pr.json::<GitHubPullRequest>().await?;
-
-    // Fetch all files with pagination
-    let mut all_files = Vec::new();
-    let mut page = 1;
-    
-    loop {
-        let files_response = client
-            .get(format!(
-                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/files"
-            ))
-            .query(&[("per_page", "100"), ("page", &page.to_string())])
-            .send()
-            .await?;
-
-        let files_response = ensure_success(
-            files_response,
-            &format!("list pull request files {owner}/{repo}#{number} (page {})", page),
-        )
-        .await?;
 
-        let files = files_response.json::<Vec<GitHubPullRequestFile>>().await?;
-        let count = files.len();
-        all_files.extend(files);
-        
-        // If we got less than 100, we've reached the last page
-        if count < 100 {
-            break;
+    // GraphQL has no patches, so files always come from REST. Everything else is one
+    // GraphQL query, sent alongside; PRs with more discussion than it returns, or a
+    // failed query, fall back to the REST endpoints.
+    let (files, parts) = tokio::join!(
+        fetch_pull_request_files(&client, owner, repo, number),
+        graphql::fetch_pull_request(&client, owner, repo, number),
+    );
+    let files = files?;
+    let parts = match parts {
+        Ok(Some(parts)) => {
+            let key = format!("{owner}/{repo}#{number}");
+            discussion_cache().insert(
+                &key,
+                DiscussionSnapshot {
+                    fetched_at: chrono::Utc::now(),
+                    review_comments: parts.review_comments.clone(),
+                    issue_comments: parts.issue_comments.clone(),
+                    reviews: parts.reviews.clone(),
+                },
+            );
+            parts
         }
-        
-        page += 1;
-    }
-
-    // Return all files (frontend will filter if needed)
-    let non_removed: Vec<_> = all_files
-        .into_iter()
-        .filter(|file| file.status != "removed")
-        .collect();
+        Ok(None) => {
+            debug!(owner, repo, number, "pull request discussion spans pages; using REST");
+            fetch_pull_request_parts(&client, owner, repo, number).await?
+        }
+        Err(err) => {
+            warn!(error = %err, "GraphQL pull request fetch failed; fetching it over REST");
+            fetch_pull_request_parts(&client, owner, repo, number).await?
+        }
+    };
 
+    // Return all files, removed ones included: they have no head version, but their base
+    // content can be fetched and GitHub takes left-side and file-level comments on them
+    let mut collected: Vec<PullRequestFile> =
+        files.into_iter().map(map_pull_request_file).collect();
+    crate::localization::pair_files(&mut collected, crate::localization::DEFAULT_SOURCE_LOCALE);
+    let secret_findings = crate::secrets::scan_files(&collected);
+
+    let PullRequestParts {
+        pr,
+        review_comments,
+        issue_comments,
+        reviews,
+        resolved,
+        reactions,
+        participant_logins,
+        linked_issues,
+        mergeability,
+    } = parts;
     let base_sha = pr.base.sha.clone();
     let head_sha = pr.head.sha.clone();
-
-    let mut collected = Vec::with_capacity(non_removed.len());
-
-    for file in non_removed {
-        let filename = file.filename;
-        collected.push(PullRequestFile {
-            path: filename.clone(),
-            status: file.status.clone(),
-            additions: file.additions,
-            deletions: file.deletions,
-            patch: file.patch.clone(),
-            head_content: None,  // Will be loaded on demand
-            base_content: None,  // Will be loaded on demand
-            language: detect_language(&filename),
-            previous_filename: file.previous_filename,
-        });
-    }
-
-    let review_comments = fetch_review_comments(&client, owner, repo, number).await?;
-    let issue_comments = fetch_issue_comments(&client, owner, repo, number).await?;
-    let reviews = fetch_pull_request_reviews(&client, owner, repo, number).await?;
+    let (head_owner, head_repo) = head_fork(owner, repo, &pr.head).unzip();
 
     let comments = build_comments(current_login, &review_comments, &issue_comments, &reviews, &head_sha);
+    let threads = build_threads(&comments, resolved.as_ref());
     let mapped_reviews = build_reviews(current_login, &reviews);
     let my_comments = comments
         .iter()
@@ -404,6 +817,53 @@ pub async fn get_pull_request(
         .filter(|comment| comment.is_mine)
         .collect();
 
+    let comment_authors: Vec<&str> = review_comments
+        .iter()
+        .map(|c| c.user.login.as_str())
+        .chain(issue_comments.iter().map(|c| c.user.login.as_str()))
+        .chain(
+            reviews
+                .iter()
+                .filter(|r| r.body.as_deref().is_some_and(|b| !b.trim().is_empty()))
+                .map(|r| r.user.login.as_str()),
+        )
+        .collect();
+    let review_authors: Vec<&str> = reviews
+        .iter()
+        .filter(|r| r.state != "PENDING")
+        .map(|r| r.user.login.as_str())
+        .collect();
+    let participants = tally_participants(&participant_logins, &comment_authors, &review_authors);
+
+    // The author's history needs their login, so it is a second query either way
+    let author_context =
+        match fetch_author_context(&client, owner, repo, number, &pr.user.login).await {
+            Ok(context) => context,
+            Err(err) => {
+                warn!(error = %err, "failed to fetch PR author context");
+                None
+            }
+        };
+
+    let linked_issues = add_mentioned_issues(
+        &client,
+        owner,
+        repo,
+        linked_issues,
+        pr.body.as_deref().unwrap_or(""),
+    )
+    .await;
+    let merge_status = match mergeability {
+        Some(state) => match merge_status(&client, owner, repo, &pr, &collected, state).await {
+            Ok(status) => Some(status),
+            Err(err) => {
+                warn!(error = %err, "failed to fetch PR merge status");
+                None
+            }
+        },
+        None => None,
+    };
+
     Ok(PullRequestDetail {
         number: pr.number,
         title: pr.title,
@@ -411,1034 +871,3554 @@ pub async fn get_pull_request(
         author: pr.user.login,
         head_sha,
         base_sha,
+        head_owner,
+        head_repo,
         files: collected,
         comments,
+        threads,
         my_comments,
         reviews: mapped_reviews,
+        secret_findings,
+        reactions,
+        participants,
+        author_context,
+        linked_issues,
+        merge_status,
+        bookmarks: Vec::new(),
+        last_visited_at: None,
+        new_comment_count: 0,
+        skipped_files: Vec::new(),
+        stale: false,
+        cached_at: None,
     })
 }
 
-pub async fn get_pull_request_metadata(
-    token: &str,
+/// Whether a PR whose head lives in `head_owner/head_repo` comes from a fork of
+/// `owner/repo`. GitHub treats both names case-insensitively.
+pub fn is_fork(owner: &str, repo: &str, head_owner: &str, head_repo: &str) -> bool {
+    !(head_owner.eq_ignore_ascii_case(owner) && head_repo.eq_ignore_ascii_case(repo))
+}
+
+/// Owner and name of the fork a PR's head is on, or `None` for a branch of `owner/repo`.
+fn head_fork(owner: &str, repo: &str, head: &GitRef) -> Option<(String, String)> {
+    let fork = head.repo.as_ref()?;
+    is_fork(owner, repo, &fork.owner.login, &fork.name)
+        .then(|| (fork.owner.login.clone(), fork.name.clone()))
+}
+
+/// The REST way to get a PR's parts: the PR, its discussion (refreshed incrementally
+/// when cached), and a few small GraphQL queries for what REST does not have. Only the
+/// PR and its discussion are required; the rest is left out when it cannot be fetched.
+async fn fetch_pull_request_parts(
+    client: &reqwest::Client,
     owner: &str,
     repo: &str,
     number: u64,
-) -> AppResult<PullRequestMetadata> {
-    let client = build_client(token)?;
+) -> AppResult<PullRequestParts> {
     let pr = client
         .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
-        .send()
+        .send_recorded()
         .await?;
-    let pr = ensure_success(pr, &format!("get pull request metadata {owner}/{repo}#{number}")).await?;
+    let pr = ensure_success(pr, &format!("get pull request {owner}/{repo}#{number}")).await?;
     let pr = pr.json::<GitHubPullRequest>().await?;
 
-    Ok(PullRequestMetadata {
-        state: pr.state,
-        merged: pr.merged_at.is_some(),
-        locked: pr.locked.unwrap_or(false),
+    let (review_comments, issue_comments, reviews) =
+        fetch_discussion(client, owner, repo, &pr).await?;
+
+    let resolved = match fetch_thread_resolution(client, owner, repo, number).await {
+        Ok(resolved) => Some(resolved),
+        Err(err) => {
+            warn!(error = %err, "failed to fetch review thread resolution");
+            None
+        }
+    };
+
+    // Reactions and participants are a nice-to-have; don't fail the whole PR load over them
+    let (reactions, participant_logins) =
+        match fetch_reactions_and_participants(client, owner, repo, number).await {
+            Ok(result) => result,
+            Err(err) => {
+                warn!(error = %err, "failed to fetch PR reactions and participants");
+                (ReactionSummary::default(), Vec::new())
+            }
+        };
+
+    let linked_issues = fetch_linked_issues(client, owner, repo, number).await;
+    let mergeability = match fetch_mergeability(client, owner, repo, number).await {
+        Ok(state) => state,
+        Err(err) => {
+            warn!(error = %err, "failed to fetch PR merge status");
+            None
+        }
+    };
+
+    Ok(PullRequestParts {
+        pr,
+        review_comments,
+        issue_comments,
+        reviews,
+        resolved,
+        reactions,
+        participant_logins,
+        linked_issues,
+        mergeability,
     })
 }
 
-pub async fn get_file_contents(
-    token: &str,
+/// Comments and reviews of a PR as last fetched, so a refresh asks only for what changed.
+#[derive(Clone)]
+struct DiscussionSnapshot {
+    fetched_at: chrono::DateTime<chrono::Utc>,
+    review_comments: Vec<GitHubReviewComment>,
+    issue_comments: Vec<GitHubIssueComment>,
+    reviews: Vec<GitHubPullRequestReview>,
+}
+
+fn discussion_cache() -> &'static TtlCache<DiscussionSnapshot> {
+    static CACHE: std::sync::OnceLock<TtlCache<DiscussionSnapshot>> = std::sync::OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(comment_sync::FULL_REFRESH_INTERVAL))
+}
+
+pub fn clear_discussion_cache() {
+    discussion_cache().clear();
+}
+
+/// Review comments, conversation comments and reviews of `pr`. Within
+/// `FULL_REFRESH_INTERVAL` of a full fetch only changes are requested: comments updated
+/// since then, and reviews from the first page that can still change. A list whose merged
+/// size disagrees with GitHub's count had deletions and is fetched in full.
+async fn fetch_discussion(
+    client: &reqwest::Client,
     owner: &str,
     repo: &str,
-    file_path: &str,
-    base_sha: &str,
-    head_sha: &str,
-    status: &str,
-    previous_filename: Option<&str>,
-) -> AppResult<(Option<String>, Option<String>)> {
-    let client = build_client(token)?;
-    
-    let head_content = if status != "removed" {
-        Some(fetch_file_contents(&client, owner, repo, file_path, head_sha).await?)
-    } else {
-        None
-    };
+    pr: &GitHubPullRequest,
+) -> AppResult<(
+    Vec<GitHubReviewComment>,
+    Vec<GitHubIssueComment>,
+    Vec<GitHubPullRequestReview>,
+)> {
+    let number = pr.number;
+    let key = format!("{owner}/{repo}#{number}");
+    let fetched_at = chrono::Utc::now();
+
+    let snapshot = match discussion_cache().get(&key) {
+        Some(cached) => {
+            let since = comment_sync::since_param(cached.fetched_at);
+
+            let mut review_comments = cached.review_comments;
+            let updated = fetch_review_comments(client, owner, repo, number, Some(&since)).await?;
+            comment_sync::merge_by_id(&mut review_comments, updated, |c| c.id);
+            if comment_sync::count_mismatch(review_comments.len(), pr.review_comments) {
+                review_comments = fetch_review_comments(client, owner, repo, number, None).await?;
+            }
 
-    let base_content = if status != "added" {
-        // For renamed files, use the previous filename to fetch base content
-        let base_path = if status == "renamed" && previous_filename.is_some() {
-            previous_filename.unwrap()
-        } else {
-            file_path
-        };
-        Some(fetch_file_contents(&client, owner, repo, base_path, base_sha).await?)
-    } else {
-        None
+            let mut issue_comments = cached.issue_comments;
+            let updated = fetch_issue_comments(client, owner, repo, number, Some(&since)).await?;
+            comment_sync::merge_by_id(&mut issue_comments, updated, |c| c.id);
+            if comment_sync::count_mismatch(issue_comments.len(), pr.comments) {
+                issue_comments = fetch_issue_comments(client, owner, repo, number, None).await?;
+            }
+
+            let pending: Vec<bool> = cached.reviews.iter().map(|r| r.state == "PENDING").collect();
+            let settled = comment_sync::settled_reviews(&pending, PAGE_SIZE);
+            let mut reviews = cached.reviews;
+            reviews.truncate(settled);
+            let first_page = (settled / PAGE_SIZE) as u32 + 1;
+            reviews.extend(fetch_reviews_from_page(client, owner, repo, number, first_page).await?);
+
+            debug!(
+                owner,
+                repo,
+                number,
+                settled_reviews = settled,
+                "refreshed pull request comments incrementally"
+            );
+            DiscussionSnapshot {
+                fetched_at,
+                review_comments,
+                issue_comments,
+                reviews,
+            }
+        }
+        None => DiscussionSnapshot {
+            fetched_at,
+            review_comments: fetch_review_comments(client, owner, repo, number, None).await?,
+            issue_comments: fetch_issue_comments(client, owner, repo, number, None).await?,
+            reviews: fetch_pull_request_reviews(client, owner, repo, number).await?,
+        },
     };
 
-    Ok((head_content, base_content))
+    discussion_cache().insert(&key, snapshot.clone());
+    Ok((snapshot.review_comments, snapshot.issue_comments, snapshot.reviews))
 }
 
-pub async fn submit_general_comment(
+/// List every file of a pull request, including removed ones, without comments or reviews.
+pub async fn list_pull_request_files(
     token: &str,
     owner: &str,
     repo: &str,
     number: u64,
-    body: &str,
-) -> AppResult<()> {
+) -> AppResult<Vec<PullRequestFile>> {
     let client = build_client(token)?;
-    let response = client
-        .post(format!(
-            "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews"
-        ))
-        .json(&json!({
-            "body": body,
-            "event": "COMMENT",
-        }))
-        .send()
-        .await?;
-
-    ensure_success(
-        response,
-        &format!("submit general comment for {owner}/{repo}#{number}"),
-    )
-    .await?;
+    let all_files = fetch_pull_request_files(&client, owner, repo, number).await?;
+    Ok(all_files.into_iter().map(map_pull_request_file).collect())
+}
 
-    Ok(())
+fn map_pull_request_file(file: GitHubPullRequestFile) -> PullRequestFile {
+    PullRequestFile {
+        language: detect_language(&file.filename),
+        path: file.filename,
+        status: file.status,
+        additions: file.additions,
+        deletions: file.deletions,
+        patch: file.patch,
+        head_content: None,  // Will be loaded on demand
+        base_content: None,  // Will be loaded on demand
+        previous_filename: file.previous_filename,
+        localized_pair: None,
+    }
 }
 
-pub async fn create_pending_review(
+/// Compare a PR description with the repository's PR template(s) on the base branch.
+pub async fn check_pull_request_template(
     token: &str,
     owner: &str,
     repo: &str,
     number: u64,
-    commit_id: Option<&str>,
-    _body: Option<&str>,
-    _current_login: Option<&str>,
-) -> AppResult<PullRequestReview> {
+) -> AppResult<crate::pr_template::TemplateCheck> {
     let client = build_client(token)?;
-    
-    // Fetch the authenticated user to check review ownership
-    let user = fetch_authenticated_user(token).await?;
-    let normalized_login = user.login.to_ascii_lowercase();
+    let pr = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
+        .send_recorded()
+        .await?;
+    let pr = ensure_success(pr, &format!("get pull request {owner}/{repo}#{number}")).await?;
+    let pr = pr.json::<GitHubPullRequest>().await?;
 
-    // First check if there's already a pending review - you can only have one at a time
-    let existing_reviews = fetch_pull_request_reviews(&client, owner, repo, number).await?;
-    for review in existing_reviews {
-        let mapped = map_review(&review, Some(&normalized_login));
-        if mapped.is_mine && mapped.state.eq_ignore_ascii_case("pending") {
-            // Reuse existing pending review
-            return Ok(mapped);
+    let templates = fetch_pull_request_templates(&client, owner, repo, &pr.base.r#ref).await?;
+    Ok(crate::pr_template::check_against_templates(
+        &templates,
+        pr.body.as_deref().unwrap_or(""),
+    ))
+}
+
+/// Locations GitHub reads PR templates from, in priority order.
+const TEMPLATE_DIRS: &[&str] = &[".github", "", "docs"];
+
+/// Return `(path, content)` for the PR templates in the first location that has any.
+/// A `PULL_REQUEST_TEMPLATE/` folder contributes every markdown file inside it.
+async fn fetch_pull_request_templates(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    reference: &str,
+) -> AppResult<Vec<(String, String)>> {
+    for dir in TEMPLATE_DIRS {
+        let mut paths = Vec::new();
+        for entry in list_directory(client, owner, repo, dir, reference).await? {
+            let name = entry.name.to_ascii_lowercase();
+            if entry.kind == "file"
+                && (name == "pull_request_template.md" || name == "pull_request_template")
+            {
+                paths.push(entry.path);
+            } else if entry.kind == "dir" && name == "pull_request_template" {
+                for inner in list_directory(client, owner, repo, &entry.path, reference).await? {
+                    if inner.kind == "file" && inner.name.to_ascii_lowercase().ends_with(".md") {
+                        paths.push(inner.path);
+                    }
+                }
+            }
         }
-    }
 
-    // No existing pending review, create a new one
-    // Include commit_id if provided, otherwise GitHub uses the latest commit
-    let mut payload = Map::new();
-    if let Some(commit_id) = commit_id {
-        payload.insert("commit_id".into(), Value::String(commit_id.to_string()));
+        if !paths.is_empty() {
+            let mut templates = Vec::with_capacity(paths.len());
+            for path in paths {
+                let content = fetch_file_contents(client, owner, repo, &path, reference).await?;
+                templates.push((path, content));
+            }
+            return Ok(templates);
+        }
     }
 
+    Ok(Vec::new())
+}
+
+/// List a repository directory; a missing directory yields an empty list.
+async fn list_directory(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    path: &str,
+    reference: &str,
+) -> AppResult<Vec<GitHubContentEntry>> {
     let response = client
-        .post(format!(
-            "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews"
-        ))
-        .json(&Value::Object(payload))
-        .send()
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
+        .query(&[("ref", reference)])
+        .send_recorded()
         .await?;
 
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+
     let response = ensure_success(
         response,
-        &format!("create pending review for {owner}/{repo}#{number}"),
+        &format!("list directory {owner}/{repo}:{reference}:{path}"),
     )
     .await?;
 
-    let review = response.json::<GitHubPullRequestReview>().await?;
-    Ok(map_review(&review, Some(&normalized_login)))
+    // The contents API returns an object rather than an array when `path` is a file
+    let value: Value = response.json().await?;
+    if value.is_array() {
+        Ok(serde_json::from_value(value)?)
+    } else {
+        Ok(Vec::new())
+    }
 }
 
-pub async fn submit_pending_review(
-    token: &str,
+/// Web host matching `API_BASE`, for building links users open in a browser.
+pub fn web_base_url() -> String {
+    crate::permalinks::web_base(API_BASE)
+}
+
+/// Work out which kind of comment `comment_id` is, since review comments, issue comments
+/// and reviews share one id space in the UI but use different anchors.
+async fn resolve_comment_kind(
+    client: &reqwest::Client,
     owner: &str,
     repo: &str,
-    number: u64,
-    review_id: u64,
-    event: &str,
-    body: Option<&str>,
-) -> AppResult<()> {
-    let client = build_client(token)?;
-    let mut payload = Map::new();
-    payload.insert("event".into(), Value::String(event.to_string()));
-
-    if let Some(body) = body {
-        if !body.trim().is_empty() {
-            payload.insert("body".into(), Value::String(body.to_string()));
+    pr_number: u64,
+    comment_id: u64,
+) -> AppResult<CommentKind> {
+    let candidates = [
+        (
+            CommentKind::ReviewComment,
+            format!("{API_BASE}/repos/{owner}/{repo}/pulls/comments/{comment_id}"),
+        ),
+        (
+            CommentKind::IssueComment,
+            format!("{API_BASE}/repos/{owner}/{repo}/issues/comments/{comment_id}"),
+        ),
+        (
+            CommentKind::Review,
+            format!("{API_BASE}/repos/{owner}/{repo}/pulls/{pr_number}/reviews/{comment_id}"),
+        ),
+    ];
+
+    for (kind, url) in candidates {
+        let response = client.get(url).send_recorded().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            continue;
         }
+        ensure_success(response, &format!("look up comment {comment_id}")).await?;
+        return Ok(kind);
     }
 
-    let response = client
-        .post(format!(
-            "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}/events"
-        ))
-        .json(&Value::Object(payload))
-        .send()
+    Err(AppError::Api(format!(
+        "Comment {comment_id} was not found in {owner}/{repo}#{pr_number}."
+    )))
+}
+
+pub async fn get_comment_permalink(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    comment_id: u64,
+) -> AppResult<String> {
+    let client = build_client(token)?;
+    let kind = resolve_comment_kind(&client, owner, repo, pr_number, comment_id).await?;
+    crate::permalinks::comment_url(&web_base_url(), owner, repo, pr_number, kind, comment_id)
+}
+
+/// Compare a PR's branch with the current tip of its base branch and find files both
+/// sides changed since they diverged.
+pub async fn check_base_drift(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<crate::drift::BaseDrift> {
+    let client = build_client(token)?;
+    let pr = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
+        .send_recorded()
         .await?;
+    let pr = ensure_success(pr, &format!("get pull request {owner}/{repo}#{number}")).await?;
+    let pr = pr.json::<GitHubPullRequest>().await?;
+    let base_ref = pr.base.r#ref;
+    let comparison = compare_with_base(&client, owner, repo, &base_ref, &pr.head.sha).await?;
 
-    ensure_success(
-        response,
-        &format!("submit review {review_id} for {owner}/{repo}#{number}"),
-    )
-    .await?;
+    let pr_files: Vec<String> = fetch_pull_request_files(&client, owner, repo, number)
+        .await?
+        .into_iter()
+        .flat_map(|file| std::iter::once(file.filename).chain(file.previous_filename))
+        .collect();
 
-    Ok(())
+    Ok(crate::drift::build_drift(
+        &base_ref,
+        &comparison.merge_base,
+        comparison.behind_by,
+        comparison.ahead_by,
+        &comparison.base_files,
+        &pr_files,
+    ))
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum CommentMode {
-    Single,
-    Review,
+struct BaseComparison {
+    merge_base: String,
+    behind_by: u64,
+    ahead_by: u64,
+    /// Paths changed on the base branch since the merge base, old names included.
+    base_files: Vec<String>,
 }
 
-pub async fn submit_file_comment(
+async fn compare_with_base(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    base_ref: &str,
+    head_sha: &str,
+) -> AppResult<BaseComparison> {
+    // Compare base...head: ahead_by counts PR commits, behind_by base commits
+    let compare = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/compare/{base_ref}...{head_sha}"))
+        .query(&[("per_page", "1")])
+        .send_recorded()
+        .await?;
+    let compare = ensure_success(compare, &format!("compare {base_ref} with {head_sha}")).await?;
+    let compare = compare.json::<GitHubCompare>().await?;
+    let merge_base = compare.merge_base_commit.sha;
+
+    let base_files = if compare.behind_by == 0 {
+        Vec::new()
+    } else {
+        let changes = client
+            .get(format!("{API_BASE}/repos/{owner}/{repo}/compare/{merge_base}...{base_ref}"))
+            .send_recorded()
+            .await?;
+        let changes = ensure_success(changes, &format!("list changes on {base_ref} since {merge_base}"))
+            .await?;
+        changes
+            .json::<GitHubCompare>()
+            .await?
+            .files
+            .into_iter()
+            .flat_map(|file| std::iter::once(file.filename).chain(file.previous_filename))
+            .collect()
+    };
+
+    Ok(BaseComparison {
+        merge_base,
+        behind_by: compare.behind_by,
+        ahead_by: compare.ahead_by,
+        base_files,
+    })
+}
+
+/// What changed in the files I commented on since my last review of a PR. `stored` is
+/// the commit recorded when a review was submitted from this app; a later review
+/// submitted on GitHub takes its place.
+pub async fn changes_since_my_review(
     token: &str,
     owner: &str,
     repo: &str,
     number: u64,
-    path: &str,
-    body: &str,
-    commit_id: &str,
-    line: Option<u64>,
-    side: Option<&str>,
-    subject_type: Option<&str>,
-    mode: CommentMode,
-    pending_review_id: Option<u64>,
-    in_reply_to: Option<u64>,
-) -> AppResult<()> {
+    stored: Option<ReviewedCommit>,
+) -> AppResult<ChangesSinceReview> {
     let client = build_client(token)?;
+    let login = fetch_authenticated_user(token).await?.login;
+    let pr = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
+        .send_recorded()
+        .await?;
+    let pr = ensure_success(pr, &format!("get pull request {owner}/{repo}#{number}")).await?;
+    let head_sha = pr.json::<GitHubPullRequest>().await?.head.sha;
 
-    // If no line number provided, treat as file-level comment
-    let effective_subject_type = if subject_type.is_none() && line.is_none() {
-        Some("file")
+    let (reviews, comments) = tokio::try_join!(
+        fetch_pull_request_reviews(&client, owner, repo, number),
+        fetch_review_comments(&client, owner, repo, number, None),
+    )?;
+    let submitted_on_github = reviews
+        .into_iter()
+        .filter(|review| {
+            review.user.login.eq_ignore_ascii_case(&login) && review.state != "PENDING"
+        })
+        .filter_map(|review| {
+            Some(ReviewedCommit {
+                commit_id: review.commit_id?,
+                submitted_at: review.submitted_at?,
+            })
+        })
+        .fold(None, |last, review| second_pass::latest(last, Some(review)));
+    let reviewed = second_pass::latest(stored, submitted_on_github);
+    let commented_paths = comments
+        .into_iter()
+        .filter(|comment| comment.user.login.eq_ignore_ascii_case(&login))
+        .map(|comment| comment.path)
+        .collect();
+
+    let changed = match &reviewed {
+        Some(reviewed) if reviewed.commit_id != head_sha => {
+            let base = &reviewed.commit_id;
+            let compare = client
+                .get(format!("{API_BASE}/repos/{owner}/{repo}/compare/{base}...{head_sha}"))
+                .send_recorded()
+                .await?;
+            let compare = ensure_success(compare, &format!("compare {base} with {head_sha}"))
+                .await?;
+            compare
+                .json::<GitHubCompare>()
+                .await?
+                .files
+                .into_iter()
+                .map(|file| ChangedFile {
+                    path: file.filename,
+                    previous_path: file.previous_filename,
+                    status: file.status,
+                    additions: file.additions,
+                    deletions: file.deletions,
+                    patch: file.patch,
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    Ok(second_pass::build(
+        reviewed.map(|reviewed| reviewed.commit_id),
+        &head_sha,
+        changed,
+        &commented_paths,
+    ))
+}
+
+const MERGEABILITY_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) { mergeable mergeStateStatus }
+  }
+}
+"#;
+
+async fn fetch_mergeability(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<Option<GraphQlMergeability>> {
+    let data: GraphQlMergeabilityData = graphql::query(
+        client,
+        MERGEABILITY_QUERY,
+        json!({ "owner": owner, "repo": repo, "number": number }),
+        &format!("fetch mergeability for {owner}/{repo}#{number}"),
+    )
+    .await?;
+    Ok(data.repository.and_then(|r| r.pull_request))
+}
+
+/// Whether the PR merges cleanly and, when it does not, which of its files were also
+/// changed on the base branch. GitHub does not name the conflicting files, so those
+/// are the likely ones.
+async fn merge_status(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    pr: &GitHubPullRequest,
+    files: &[PullRequestFile],
+    state: GraphQlMergeability,
+) -> AppResult<MergeStatus> {
+    let conflicting_files = if state.mergeable == "CONFLICTING" {
+        let comparison =
+            compare_with_base(client, owner, repo, &pr.base.r#ref, &pr.head.sha).await?;
+        let pr_paths: Vec<String> = files
+            .iter()
+            .flat_map(|file| std::iter::once(file.path.clone()).chain(file.previous_filename.clone()))
+            .collect();
+        crate::drift::overlapping(&comparison.base_files, &pr_paths)
     } else {
-        subject_type
+        Vec::new()
     };
 
-    if matches!(mode, CommentMode::Review) && matches!(effective_subject_type, Some("file")) {
-        return Err(AppError::Api(
-            "GitHub does not allow starting a review with a file-level comment via the REST API. Choose a specific line or post this as a single comment.".into(),
-        ));
+    Ok(MergeStatus {
+        mergeable: state.mergeable.to_ascii_lowercase(),
+        merge_state: state.merge_state_status.to_ascii_lowercase(),
+        conflicting_files,
+    })
+}
+
+/// Fetch an issue (or PR, which GitHub also serves as an issue) for a quick look.
+pub async fn get_issue(token: &str, owner: &str, repo: &str, number: u64) -> AppResult<IssueDetail> {
+    let client = build_client(token)?;
+    fetch_issue(&client, owner, repo, number).await
+}
+
+async fn fetch_issue(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<IssueDetail> {
+    let response = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/issues/{number}"))
+        .send_recorded()
+        .await?;
+    let response = ensure_success(response, &format!("get issue {owner}/{repo}#{number}")).await?;
+    let issue = response.json::<GitHubIssue>().await?;
+
+    Ok(IssueDetail {
+        number: issue.number,
+        title: issue.title,
+        body: issue.body,
+        state: issue.state,
+        author: issue.user.login,
+        labels: issue.labels.into_iter().map(|label| label.name).collect(),
+        url: issue.html_url,
+        created_at: issue.created_at,
+        closed_at: issue.closed_at,
+        comments: issue.comments,
+    })
+}
+
+const THREAD_RESOLUTION_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes { isResolved comments(first: 1) { nodes { databaseId } } }
+      }
     }
+  }
+}
+"#;
 
-    let mut single_comment_fields = Map::new();
-    single_comment_fields.insert("body".into(), Value::String(body.to_string()));
-    single_comment_fields.insert("path".into(), Value::String(path.to_string()));
-    single_comment_fields.insert("commit_id".into(), Value::String(commit_id.to_string()));
+/// Whether each review thread is resolved, keyed by the id of its first comment. Only
+/// the first 100 threads are asked for; later ones read as unresolved.
+async fn fetch_thread_resolution(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<std::collections::HashMap<u64, bool>> {
+    let data: GraphQlReviewThreadsData = graphql::query(
+        client,
+        THREAD_RESOLUTION_QUERY,
+        json!({ "owner": owner, "repo": repo, "number": number }),
+        &format!("fetch review threads for {owner}/{repo}#{number}"),
+    )
+    .await?;
+    Ok(data
+        .repository
+        .and_then(|r| r.pull_request)
+        .map(|pr| pr.review_threads.nodes)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .filter_map(|thread| {
+            let root = thread.comments.nodes.into_iter().flatten().next()?;
+            Some((root.database_id?, thread.is_resolved))
+        })
+        .collect())
+}
 
-    if let Some(subject_type) = effective_subject_type {
-        single_comment_fields.insert(
-            "subject_type".into(),
-            Value::String(subject_type.to_string()),
-        );
-    } else if let Some(line_number) = line {
-        single_comment_fields.insert("line".into(), Value::Number(line_number.into()));
-        single_comment_fields.insert(
-            "side".into(),
-            Value::String(side.unwrap_or("RIGHT").to_string()),
-        );
+/// Most issues a description can name that are looked up individually.
+const MAX_BODY_ISSUE_LOOKUPS: usize = 10;
+
+const LINKED_ISSUES_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      closingIssuesReferences(first: 25) {
+        nodes { number title state url repository { name owner { login } } }
+      }
     }
+  }
+}
+"#;
 
-    // Add in_reply_to if provided
-    if let Some(reply_to_id) = in_reply_to {
-        single_comment_fields.insert("in_reply_to".into(), Value::Number(reply_to_id.into()));
+/// Issues GitHub links as closed by the PR. Failures leave issues out rather than
+/// failing the PR load.
+async fn fetch_linked_issues(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> Vec<LinkedIssue> {
+    let linked: AppResult<GraphQlLinkedIssuesData> = graphql::query(
+        client,
+        LINKED_ISSUES_QUERY,
+        json!({ "owner": owner, "repo": repo, "number": number }),
+        &format!("fetch linked issues for {owner}/{repo}#{number}"),
+    )
+    .await;
+    match linked {
+        Ok(data) => data
+            .repository
+            .and_then(|r| r.pull_request)
+            .map(|pr| pr.closing_issues_references.nodes)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(map_linked_issue)
+            .collect(),
+        Err(err) => {
+            warn!(error = %err, "failed to fetch linked issues");
+            Vec::new()
+        }
     }
+}
 
-    match mode {
-        CommentMode::Single => {
-            let payload = Value::Object(single_comment_fields);
-            let response = client
-                .post(format!(
-                    "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"
-                ))
-                .json(&payload)
-                .send()
-                .await?;
+fn map_linked_issue(issue: GraphQlLinkedIssue) -> LinkedIssue {
+    LinkedIssue {
+        owner: issue.repository.owner.login,
+        repo: issue.repository.name,
+        number: issue.number,
+        title: issue.title,
+        state: issue.state.to_ascii_lowercase(),
+        url: issue.url,
+    }
+}
 
-            ensure_success(
-                response,
-                &format!("submit single file comment for {owner}/{repo}#{number}"),
-            )
+/// Add issues named with closing keywords in the description to those GitHub linked.
+/// GitHub only links keywords for PRs into the default branch, so these are looked up
+/// one by one; failures leave them out.
+async fn add_mentioned_issues(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    mut issues: Vec<LinkedIssue>,
+    body: &str,
+) -> Vec<LinkedIssue> {
+    let mentioned = crate::linked_issues::closing_references(body);
+    for reference in mentioned.into_iter().take(MAX_BODY_ISSUE_LOOKUPS) {
+        let issue_owner = reference.owner.as_deref().unwrap_or(owner);
+        let issue_repo = reference.repo.as_deref().unwrap_or(repo);
+        let known = issues.iter().any(|issue| {
+            issue.number == reference.number
+                && issue.owner.eq_ignore_ascii_case(issue_owner)
+                && issue.repo.eq_ignore_ascii_case(issue_repo)
+        });
+        if known || !crate::owner_policy::is_allowed(issue_owner) {
+            continue;
+        }
+        match fetch_issue(client, issue_owner, issue_repo, reference.number).await {
+            Ok(issue) => issues.push(LinkedIssue {
+                owner: issue_owner.to_string(),
+                repo: issue_repo.to_string(),
+                number: issue.number,
+                title: issue.title,
+                state: issue.state,
+                url: issue.url,
+            }),
+            Err(err) => warn!(
+                error = %err,
+                "failed to fetch issue {issue_owner}/{issue_repo}#{} named in PR description",
+                reference.number
+            ),
+        }
+    }
+
+    issues
+}
+
+/// Fetch the author and body of a review or conversation comment, for quoting.
+pub async fn get_comment_author_and_body(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    comment_id: u64,
+) -> AppResult<(String, String)> {
+    let client = build_client(token)?;
+    for kind in ["pulls", "issues"] {
+        let response = client
+            .get(format!("{API_BASE}/repos/{owner}/{repo}/{kind}/comments/{comment_id}"))
+            .send_recorded()
             .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            continue;
         }
-        CommentMode::Review => {
-            let line_number = line.ok_or_else(|| {
-                AppError::Api(
-                    "Select a specific line before starting a review comment.".into(),
-                )
-            })?;
+        let response = ensure_success(response, &format!("fetch comment {comment_id}")).await?;
+        // Review comments carry the same author/body fields as issue comments
+        let comment = response.json::<GitHubIssueComment>().await?;
+        return Ok((comment.user.login, comment.body));
+    }
 
-            let comment_side = side.unwrap_or("RIGHT");
-            let mut review_comment_fields = Map::new();
-            review_comment_fields.insert("body".into(), Value::String(body.to_string()));
-            review_comment_fields.insert("path".into(), Value::String(path.to_string()));
-            review_comment_fields.insert(
-                "line".into(),
-                Value::Number(serde_json::Number::from(line_number)),
-            );
-            review_comment_fields.insert(
-                "side".into(),
-                Value::String(comment_side.to_string()),
-            );
-            review_comment_fields.insert(
-                "commit_id".into(),
-                Value::String(commit_id.to_string()),
-            );
+    Err(AppError::Api(format!(
+        "Comment {comment_id} was not found in {owner}/{repo}."
+    )))
+}
 
-            // Add in_reply_to if provided
-            if let Some(reply_to_id) = in_reply_to {
-                review_comment_fields.insert("in_reply_to".into(), Value::Number(reply_to_id.into()));
-            }
+pub async fn get_pull_request_metadata(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<PullRequestMetadata> {
+    let client = build_client(token)?;
+    let pr = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
+        .send_recorded()
+        .await?;
+    let pr = ensure_success(pr, &format!("get pull request metadata {owner}/{repo}#{number}")).await?;
+    let pr = pr.json::<GitHubPullRequest>().await?;
 
-            // If we don't have a pending_review_id, the user must call "Start review" first
-            let review_id = pending_review_id.ok_or_else(|| {
-                AppError::Api(
-                    "No pending review found. Please start a review first by clicking 'Start review'.".into(),
-                )
-            })?;
+    let (head_owner, head_repo) = head_fork(owner, repo, &pr.head).unzip();
 
-            // Add comment directly to the pending review using the review comments endpoint
-            let response = client
-                .post(format!(
-                    "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}/comments"
-                ))
-                .json(&Value::Object(review_comment_fields))
-                .send()
-                .await?;
+    Ok(PullRequestMetadata {
+        state: pr.state,
+        merged: pr.merged_at.is_some(),
+        locked: pr.locked.unwrap_or(false),
+        head_sha: pr.head.sha,
+        head_owner,
+        head_repo,
+    })
+}
 
-            ensure_success(
-                response,
-                &format!(
-                    "attach file comment to pending review for {owner}/{repo}#{number}"
-                ),
-            )
-            .await?;
+/// How `fetch_file_contents` returns a text file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentFormat {
+    /// The file as committed.
+    Raw,
+    /// GitHub's rendering of a markdown file, with relative links, images and mentions
+    /// resolved the way github.com shows them.
+    Html,
+}
+
+impl ContentFormat {
+    fn media_type(self) -> &'static str {
+        match self {
+            ContentFormat::Raw => "application/vnd.github.v3.raw",
+            ContentFormat::Html => "application/vnd.github.html",
         }
     }
-
-    Ok(())
 }
-async fn fetch_file_contents(
-    client: &reqwest::Client,
+
+#[allow(clippy::too_many_arguments)]
+pub async fn get_file_contents(
+    token: &str,
     owner: &str,
     repo: &str,
-    path: &str,
-    reference: &str,
-) -> AppResult<String> {
-    // Check if this is an image file
-    let is_image = path.to_ascii_lowercase().ends_with(".png") 
-        || path.to_ascii_lowercase().ends_with(".jpg")
-        || path.to_ascii_lowercase().ends_with(".jpeg")
-        || path.to_ascii_lowercase().ends_with(".gif")
-        || path.to_ascii_lowercase().ends_with(".svg")
-        || path.to_ascii_lowercase().ends_with(".webp")
-        || path.to_ascii_lowercase().ends_with(".bmp")
-        || path.to_ascii_lowercase().ends_with(".ico");
+    file_path: &str,
+    base_sha: &str,
+    head_sha: &str,
+    status: &str,
+    previous_filename: Option<&str>,
+    head_repository: Option<(&str, &str)>,
+    format: ContentFormat,
+) -> AppResult<(Option<String>, Option<String>)> {
+    if format == ContentFormat::Html
+        && !crate::local_files::is_markdown(std::path::Path::new(file_path))
+    {
+        return Err(AppError::InvalidInput(format!(
+            "GitHub only renders markdown files, not `{file_path}`"
+        )));
+    }
+    let client = build_client(token)?;
     
-    if is_image {
-        // For images, get the JSON response with base64 content
-        let response = client
-            .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
-            .query(&[("ref", reference)])
-            .send()
-            .await?;
-
-        let response = ensure_success(
-            response,
-            &format!("fetch file contents for {owner}/{repo}:{reference}:{path}"),
+    // The head commit of a fork PR need not be reachable from the base repository, so
+    // the head version is read from the fork.
+    let (head_owner, head_repo) = head_repository.unwrap_or((owner, repo));
+    let head_content = if status != "removed" {
+        Some(
+            fetch_file_contents_as(&client, head_owner, head_repo, file_path, head_sha, format)
+                .await?,
         )
-        .await?;
+    } else {
+        None
+    };
 
-        let content_json: Value = response.json().await?;
-        
-        // GitHub returns content as base64 in the "content" field
-        if let Some(content) = content_json.get("content").and_then(|c| c.as_str()) {
-            // Remove whitespace/newlines that GitHub adds to the base64 string
-            let cleaned: String = content.chars().filter(|c| !c.is_whitespace()).collect();
-            Ok(cleaned)
+    let base_content = if status != "added" {
+        // For renamed files, use the previous filename to fetch base content
+        let base_path = if status == "renamed" && previous_filename.is_some() {
+            previous_filename.unwrap()
         } else {
-            Err(AppError::Api("Image content not found in response".to_string()))
-        }
+            file_path
+        };
+        Some(fetch_file_contents_as(&client, owner, repo, base_path, base_sha, format).await?)
     } else {
-        // For text files, get raw content
-        let response = client
-            .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
-            .query(&[("ref", reference)])
-            .header(ACCEPT, "application/vnd.github.v3.raw")
-            .send()
-            .await?;
+        None
+    };
 
-        let response = ensure_success(
-            response,
-            &format!("fetch file contents for {owner}/{repo}:{reference}:{path}"),
-        )
+    Ok((head_content, base_content))
+}
+
+pub async fn submit_general_comment(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    body: &str,
+) -> AppResult<()> {
+    let client = build_client(token)?;
+    let response = client
+        .post(format!(
+            "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews"
+        ))
+        .json(&json!({
+            "body": body,
+            "event": "COMMENT",
+        }))
+        .send_recorded()
         .await?;
 
-        Ok(response.text().await?)
-    }
+    ensure_success(
+        response,
+        &format!("submit general comment for {owner}/{repo}#{number}"),
+    )
+    .await?;
+
+    Ok(())
 }
 
-async fn fetch_review_comments(
+async fn fetch_timeline(
     client: &reqwest::Client,
     owner: &str,
     repo: &str,
     number: u64,
-) -> AppResult<Vec<GitHubReviewComment>> {
-    let mut all_comments = Vec::new();
-    let mut page = 1;
-    
-    loop {
-        let response = client
-            .get(format!(
-                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"
-            ))
-            .query(&[("per_page", "100"), ("page", &page.to_string())])
-            .send()
-            .await?;
+) -> AppResult<Vec<GitHubTimelineEvent>> {
+    fetch_all_pages(
+        client,
+        &format!("{API_BASE}/repos/{owner}/{repo}/issues/{number}/timeline"),
+        &[],
+        &format!("fetch timeline for {owner}/{repo}#{number}"),
+    )
+    .await
+}
 
-        let response = ensure_success(
-            response,
-            &format!("list review comments for {owner}/{repo}#{number} (page {})", page),
+/// Pull requests looked up per batched query, well inside GitHub's node limits.
+const REVIEW_STATE_BATCH: usize = 50;
+
+/// Pull requests checked per batched attention query. Each brings up to 100 timeline
+/// items and 50 threads of 50 comments, which keeps a batch inside GitHub's node limit.
+const ATTENTION_BATCH: usize = 20;
+
+/// One query looking up `count` pull requests under aliases `pr0`, `pr1`, ..., with
+/// variables `$o{i}`, `$r{i}` and `$n{i}`, selecting `fields` of each.
+fn pull_request_batch_query(count: usize, fields: &str) -> String {
+    let params: Vec<String> = (0..count)
+        .map(|i| format!("$o{i}: String!, $r{i}: String!, $n{i}: Int!"))
+        .collect();
+    let lookups: String = (0..count)
+        .map(|i| {
+            format!(
+                "  pr{i}: repository(owner: $o{i}, name: $r{i}) {{\n    \
+                 pullRequest(number: $n{i}) {{ {fields} }}\n  }}\n"
+            )
+        })
+        .collect();
+    format!("query({}) {{\n{lookups}}}\n", params.join(", "))
+}
+
+/// Looks up `prs` in batches of `batch_size` aliased pull requests, `parse`-ing each
+/// batch's `data` into one entry per pull request. Owners the owner policy blocks are
+/// not asked about and stay `None`, as do pull requests that could not be looked up.
+async fn query_pull_request_batches<T: Clone>(
+    client: &reqwest::Client,
+    prs: &[(String, String, u64)],
+    batch_size: usize,
+    fields: &str,
+    context: &str,
+    parse: impl Fn(Value, usize) -> Vec<Option<T>>,
+) -> AppResult<Vec<Option<T>>> {
+    let mut results = vec![None; prs.len()];
+    let allowed: Vec<usize> = (0..prs.len())
+        .filter(|&index| crate::owner_policy::is_allowed(&prs[index].0))
+        .collect();
+
+    for batch in allowed.chunks(batch_size) {
+        let mut variables = Map::new();
+        for (slot, &index) in batch.iter().enumerate() {
+            let (owner, repo, number) = &prs[index];
+            variables.insert(format!("o{slot}"), json!(owner));
+            variables.insert(format!("r{slot}"), json!(repo));
+            variables.insert(format!("n{slot}"), json!(number));
+        }
+        let data: Value = graphql::query_batch(
+            client,
+            &pull_request_batch_query(batch.len(), fields),
+            Value::Object(variables),
+            context,
         )
         .await?;
-
-        let comments = response.json::<Vec<GitHubReviewComment>>().await?;
-        let count = comments.len();
-        all_comments.extend(comments);
-        
-        // If we got less than 100, we've reached the last page
-        if count < 100 {
-            break;
+        for (&index, result) in batch.iter().zip(parse(data, batch.len())) {
+            results[index] = result;
         }
-        
-        page += 1;
     }
-    
-    Ok(all_comments)
+    Ok(results)
 }
 
-pub async fn get_pending_review_comments(
+/// Pending reviews are only visible to their author, so `reviews(states: PENDING)`
+/// counts mine alone.
+const REVIEW_STATE_FIELDS: &str = "headRefOid state locked reviews(states: PENDING) { totalCount }";
+
+/// The batched query behind `pull_request_review_states`.
+pub fn review_states_query(count: usize) -> String {
+    pull_request_batch_query(count, REVIEW_STATE_FIELDS)
+}
+
+/// The states in the `data` of a `review_states_query`, in request order. Pull requests
+/// that no longer exist or that I can no longer see are `None`.
+pub fn parse_review_states(mut data: Value, count: usize) -> Vec<Option<PrReviewState>> {
+    (0..count)
+        .map(|i| {
+            let pr = data.get_mut(format!("pr{i}"))?.get_mut("pullRequest")?.take();
+            let pr: GraphQlReviewState = serde_json::from_value(pr).ok()?;
+            let merged = pr.state == "MERGED";
+            Some(PrReviewState {
+                head_sha: pr.head_ref_oid,
+                state: if pr.state == "OPEN" { "open" } else { "closed" }.to_string(),
+                merged,
+                locked: pr.locked,
+                has_pending_review: pr.reviews.total_count > 0,
+            })
+        })
+        .collect()
+}
+
+/// Head, state and whether my pending review still exists for each of `prs`, in
+/// batched GraphQL queries rather than a few REST calls per PR. Entries are `None` for
+/// PRs that could not be looked up, including owners the owner policy blocks.
+pub async fn pull_request_review_states(
+    token: &str,
+    prs: &[(String, String, u64)],
+) -> AppResult<Vec<Option<PrReviewState>>> {
+    let client = build_client(token)?;
+    query_pull_request_batches(
+        &client,
+        prs,
+        REVIEW_STATE_BATCH,
+        REVIEW_STATE_FIELDS,
+        "check pull requests under review",
+        parse_review_states,
+    )
+    .await
+}
+
+/// What `attention::needs_attention` weighs: my reviews, pushed commits, review
+/// requests and the review threads.
+const ATTENTION_FIELDS: &str = "state author { login } \
+    timelineItems(last: 100, itemTypes: [PULL_REQUEST_REVIEW, PULL_REQUEST_COMMIT, REVIEW_REQUESTED_EVENT]) { nodes { \
+    __typename \
+    ... on PullRequestReview { author { login } submittedAt } \
+    ... on PullRequestCommit { commit { committedDate } } \
+    ... on ReviewRequestedEvent { createdAt requestedReviewer { ... on User { login } } } } } \
+    reviewThreads(last: 50) { nodes { comments(first: 50) { nodes { author { login } createdAt } } } }";
+
+/// The batched query behind `pull_requests_need_attention`.
+pub fn attention_query(count: usize) -> String {
+    pull_request_batch_query(count, ATTENTION_FIELDS)
+}
+
+/// Whether each pull request in the `data` of an `attention_query` waits on `me`, in
+/// request order. Closed and merged PRs never do; PRs that could not be looked up are
+/// `None`.
+pub fn parse_attention(mut data: Value, count: usize, me: &str) -> Vec<Option<bool>> {
+    (0..count)
+        .map(|i| {
+            let pr = data.get_mut(format!("pr{i}"))?.get_mut("pullRequest")?.take();
+            let mut pr: GraphQlAttention = serde_json::from_value(pr).ok()?;
+            if pr.state != "OPEN" {
+                return Some(false);
+            }
+            let author = pr.author.take().map(|author| author.login).unwrap_or_default();
+            Some(needs_attention(me, &author, &pr.activity()))
+        })
+        .collect()
+}
+
+async fn attention_flags(
+    client: &reqwest::Client,
+    prs: &[(String, String, u64)],
+    login: &str,
+) -> AppResult<Vec<Option<bool>>> {
+    query_pull_request_batches(
+        client,
+        prs,
+        ATTENTION_BATCH,
+        ATTENTION_FIELDS,
+        "check which pull requests need attention",
+        |data, count| parse_attention(data, count, login),
+    )
+    .await
+}
+
+/// Whether each of `prs` is waiting on `login`, in batched GraphQL queries rather than
+/// two REST calls per PR.
+pub async fn pull_requests_need_attention(
+    token: &str,
+    prs: &[(String, String, u64)],
+    login: &str,
+) -> AppResult<Vec<Option<bool>>> {
+    let client = build_client(token)?;
+    attention_flags(&client, prs, login).await
+}
+
+/// When `login`'s review was last requested on the PR, from its timeline. Team requests
+/// are not attributed to individual members and so are not counted.
+pub async fn fetch_review_requested_at(
     token: &str,
     owner: &str,
     repo: &str,
     number: u64,
-    review_id: u64,
-    current_login: Option<&str>,
-) -> AppResult<Vec<PullRequestComment>> {
+    login: &str,
+) -> AppResult<Option<String>> {
     let client = build_client(token)?;
-    
-    // Fetch PR to get head SHA
-    let pr_response = client
-        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
-        .send()
-        .await?;
-    
-    let pr_response = ensure_success(
-        pr_response,
-        &format!("fetch pull request {owner}/{repo}#{number}"),
-    )
-    .await?;
-    
-    let pr = pr_response.json::<GitHubPullRequest>().await?;
-    let head_sha = pr.head.sha;
-    
-    let comments = fetch_pending_review_comments(&client, owner, repo, number, review_id).await?;
-    
-    // Fetch all PR files with pagination to get patches for position-to-line conversion
-    let mut all_files = Vec::new();
-    let mut page = 1;
-    
-    loop {
-        let files_response = client
-            .get(format!(
-                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/files"
-            ))
-            .query(&[("per_page", "100"), ("page", &page.to_string())])
-            .send()
-            .await?;
-
-        let files_response = ensure_success(
-            files_response,
-            &format!("list pull request files {owner}/{repo}#{number} (page {})", page),
-        )
-        .await?;
+    let events = fetch_timeline(&client, owner, repo, number).await?;
 
-        let files = files_response.json::<Vec<GitHubPullRequestFile>>().await?;
-        let count = files.len();
-        all_files.extend(files);
-        
-        // If we got less than 100, we've reached the last page
-        if count < 100 {
-            break;
-        }
-        
-        page += 1;
-    }
-    
-    // Build a map of file path to patch
-    let mut patches: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-    for file in all_files {
-        if let Some(patch) = file.patch {
-            patches.insert(file.filename, patch);
-        }
-    }
-    
-    let normalized_login = current_login
-        .filter(|login| !login.is_empty())
-        .map(|login| login.to_ascii_lowercase());
-    
-    let mapped_comments: Vec<PullRequestComment> = comments
-        .iter()
-        .map(|comment| {
-            let is_mine = normalized_login
+    Ok(events
+        .into_iter()
+        .filter(|e| e.event.as_deref() == Some("review_requested"))
+        .filter(|e| {
+            e.requested_reviewer
                 .as_ref()
-                .map(|login| comment.user.login.eq_ignore_ascii_case(login))
-                .unwrap_or(false);
-            
-            // Get the patch for this file
-            let patch = patches.get(&comment.path);
-            
-            map_review_comment(comment, is_mine, patch, &head_sha)
+                .is_some_and(|user| user.login.eq_ignore_ascii_case(login))
         })
-        .collect();
-    
-    Ok(mapped_comments)
+        .filter_map(|e| e.created_at)
+        .max())
 }
 
-async fn fetch_pending_review_comments(
-    client: &reqwest::Client,
+/// Re-request `reviewer`'s review and, when `comment_template` is given, post it as a
+/// conversation comment so the author knows a new round has started.
+pub async fn request_review_followup(
+    token: &str,
     owner: &str,
     repo: &str,
     number: u64,
-    review_id: u64,
-) -> AppResult<Vec<GitHubReviewComment>> {
-    let mut all_comments = Vec::new();
-    let mut page = 1;
-    
-    loop {
-        let response = client
-            .get(format!(
-                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}/comments"
-            ))
-            .query(&[("per_page", "100"), ("page", &page.to_string())])
-            .send()
-            .await?;
+    reviewer: &str,
+    comment_template: Option<&ReviewFollowup>,
+) -> AppResult<ReviewFollowupResult> {
+    let client = build_client(token)?;
+    let pr = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
+        .send_recorded()
+        .await?;
+    let pr = ensure_success(pr, &format!("get pull request {owner}/{repo}#{number}")).await?;
+    let pr = pr.json::<GitHubPullRequest>().await?;
 
-        let response = ensure_success(
-            response,
-            &format!("list pending review comments for {owner}/{repo}#{number} review {review_id} (page {})", page),
-        )
+    if pr.user.login.eq_ignore_ascii_case(reviewer) {
+        return Err(AppError::InvalidInput(
+            "cannot request a review from the pull request author".into(),
+        ));
+    }
+
+    let response = client
+        .post(format!(
+            "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/requested_reviewers"
+        ))
+        .json(&json!({ "reviewers": [reviewer] }))
+        .send_recorded()
         .await?;
+    ensure_success(
+        response,
+        &format!("re-request review on {owner}/{repo}#{number}"),
+    )
+    .await?;
 
-        let comments = response.json::<Vec<GitHubReviewComment>>().await?;
-        let count = comments.len();
-        all_comments.extend(comments);
-        
-        // If we got less than 100, we've reached the last page
-        if count < 100 {
-            break;
-        }
-        
-        page += 1;
+    let mut comment_url = None;
+    if let Some(followup) = comment_template {
+        let body = followup.render(&pr.user.login, reviewer, &pr.head.sha);
+        comment_url = Some(
+            create_issue_comment(
+                &client,
+                owner,
+                repo,
+                number,
+                &body,
+                &format!("post follow-up comment on {owner}/{repo}#{number}"),
+            )
+            .await?,
+        );
     }
-    
-    Ok(all_comments)
+
+    Ok(ReviewFollowupResult {
+        reviewer: reviewer.to_string(),
+        head_sha: pr.head.sha,
+        comment_url,
+    })
 }
 
-async fn fetch_issue_comments(
+/// Post a conversation comment and return its URL.
+async fn create_issue_comment(
     client: &reqwest::Client,
     owner: &str,
     repo: &str,
     number: u64,
-) -> AppResult<Vec<GitHubIssueComment>> {
-    let mut all_comments = Vec::new();
-    let mut page = 1;
-    
-    loop {
-        let response = client
-            .get(format!(
-                "{API_BASE}/repos/{owner}/{repo}/issues/{number}/comments"
-            ))
-            .query(&[("per_page", "100"), ("page", &page.to_string())])
-            .send()
-            .await?;
-
-        let response = ensure_success(
-            response,
-            &format!("list issue comments for {owner}/{repo}#{number} (page {})", page),
-        )
+    body: &str,
+    context: &str,
+) -> AppResult<String> {
+    let response = client
+        .post(format!(
+            "{API_BASE}/repos/{owner}/{repo}/issues/{number}/comments"
+        ))
+        .json(&json!({ "body": body }))
+        .send_recorded()
         .await?;
-
-        let comments = response.json::<Vec<GitHubIssueComment>>().await?;
-        let count = comments.len();
-        all_comments.extend(comments);
-        
-        // If we got less than 100, we've reached the last page
-        if count < 100 {
-            break;
-        }
-        
-        page += 1;
-    }
-    
-    Ok(all_comments)
+    let response = ensure_success(response, context).await?;
+    Ok(response.json::<GitHubIssueComment>().await?.html_url)
 }
 
-/// Update a review comment on a pull request
-pub async fn update_review_comment(
+/// Post a comment in the PR conversation, outside any review. Returns its URL.
+pub async fn post_conversation_comment(
     token: &str,
     owner: &str,
     repo: &str,
-    comment_id: u64,
+    number: u64,
     body: &str,
-) -> AppResult<()> {
+) -> AppResult<String> {
     let client = build_client(token)?;
-    
-    let payload = json!({
-        "body": body,
-    });
+    create_issue_comment(
+        &client,
+        owner,
+        repo,
+        number,
+        body,
+        &format!("post comment on {owner}/{repo}#{number}"),
+    )
+    .await
+}
+
+/// A complete review submitted in one request.
+pub struct NewReview<'a> {
+    pub commit_id: &'a str,
+    pub event: &'a str,
+    pub body: &'a str,
+    pub comments: &'a [crate::review_storage::ReviewComment],
+}
+
+/// Create and submit a review with all of its line comments at once. Returns the
+/// review id. File-level comments are not supported by this endpoint.
+pub async fn create_review(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    review: &NewReview<'_>,
+) -> AppResult<u64> {
+    let client = build_client(token)?;
+    let comments: Vec<Value> = review
+        .comments
+        .iter()
+        .map(|comment| {
+            let mut fields = Map::new();
+            fields.insert("path".into(), Value::String(comment.file_path.clone()));
+            fields.insert("line".into(), Value::Number(comment.line_number.into()));
+            fields.insert("side".into(), Value::String(comment.side.clone()));
+            fields.insert("body".into(), Value::String(stored_comment_body(comment)));
+            if let Some(suggestion) = &comment.suggestion {
+                insert_suggestion_range(&mut fields, suggestion, &comment.side)?;
+            }
+            Ok(Value::Object(fields))
+        })
+        .collect::<AppResult<_>>()?;
 
     let response = client
-        .patch(format!(
-            "{API_BASE}/repos/{owner}/{repo}/pulls/comments/{comment_id}"
-        ))
-        .json(&payload)
-        .send()
+        .post(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews"))
+        .json(&json!({
+            "commit_id": review.commit_id,
+            "event": review.event,
+            "body": review.body,
+            "comments": comments,
+        }))
+        .send_recorded()
         .await?;
-
-    ensure_success(
+    let response = ensure_success(
         response,
-        &format!("update review comment {comment_id} for {owner}/{repo}"),
+        &format!("submit {} review for {owner}/{repo}#{number}", review.event),
     )
     .await?;
 
-    Ok(())
+    Ok(response.json::<GitHubPullRequestReview>().await?.id)
 }
 
-/// Delete a review comment on a pull request
-pub async fn delete_review_comment(
+pub async fn create_pending_review(
     token: &str,
     owner: &str,
     repo: &str,
-    comment_id: u64,
-) -> AppResult<()> {
+    number: u64,
+    commit_id: Option<&str>,
+    _body: Option<&str>,
+    _current_login: Option<&str>,
+) -> AppResult<PullRequestReview> {
     let client = build_client(token)?;
+    
+    // Fetch the authenticated user to check review ownership
+    let user = fetch_authenticated_user(token).await?;
+    let normalized_login = user.login.to_ascii_lowercase();
+
+    // First check if there's already a pending review - you can only have one at a time
+    let existing_reviews = fetch_pull_request_reviews(&client, owner, repo, number).await?;
+    for review in existing_reviews {
+        let mapped = map_review(&review, Some(&normalized_login));
+        if mapped.is_mine && mapped.state.eq_ignore_ascii_case("pending") {
+            // Reuse existing pending review
+            return Ok(mapped);
+        }
+    }
+
+    // No existing pending review, create a new one
+    // Include commit_id if provided, otherwise GitHub uses the latest commit
+    let mut payload = Map::new();
+    if let Some(commit_id) = commit_id {
+        payload.insert("commit_id".into(), Value::String(commit_id.to_string()));
+    }
 
     let response = client
-        .delete(format!(
-            "{API_BASE}/repos/{owner}/{repo}/pulls/comments/{comment_id}"
+        .post(format!(
+            "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews"
         ))
-        .send()
+        .json(&Value::Object(payload))
+        .send_recorded()
         .await?;
 
-    ensure_success(
+    let response = ensure_success(
         response,
-        &format!("delete review comment {comment_id} for {owner}/{repo}"),
+        &format!("create pending review for {owner}/{repo}#{number}"),
     )
     .await?;
 
-    Ok(())
+    let review = response.json::<GitHubPullRequestReview>().await?;
+    Ok(map_review(&review, Some(&normalized_login)))
 }
 
-async fn fetch_pull_request_reviews(
-    client: &reqwest::Client,
+pub async fn submit_pending_review(
+    token: &str,
     owner: &str,
     repo: &str,
     number: u64,
-) -> AppResult<Vec<GitHubPullRequestReview>> {
-    let mut all_reviews = Vec::new();
-    let mut page = 1;
-    
-    loop {
-        let response = client
-            .get(format!(
-                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews"
-            ))
-            .query(&[("per_page", "100"), ("page", &page.to_string())])
-            .send()
-            .await?;
-
-        let response = ensure_success(
-            response,
-            &format!("list pull request reviews for {owner}/{repo}#{number} (page {})", page),
-        )
-        .await?;
+    review_id: u64,
+    event: &str,
+    body: Option<&str>,
+) -> AppResult<Option<String>> {
+    let client = build_client(token)?;
+    let mut payload = Map::new();
+    payload.insert("event".into(), Value::String(event.to_string()));
 
-        let reviews = response.json::<Vec<GitHubPullRequestReview>>().await?;
-        let count = reviews.len();
-        all_reviews.extend(reviews);
-        
-        // If we got less than 100, we've reached the last page
-        if count < 100 {
-            break;
+    if let Some(body) = body {
+        if !body.trim().is_empty() {
+            payload.insert("body".into(), Value::String(body.to_string()));
         }
-        
-        page += 1;
     }
-    
-    Ok(all_reviews)
-}
-
-fn build_comments(
-    current_login: Option<&str>,
-    review_comments: &[GitHubReviewComment],
-    issue_comments: &[GitHubIssueComment],
-    reviews: &[GitHubPullRequestReview],
-    pr_head_sha: &str,
-) -> Vec<PullRequestComment> {
-    let normalized_login = current_login
-        .filter(|login| !login.is_empty())
-        .map(|login| login.to_ascii_lowercase());
-
-    let mut collected = Vec::new();
 
-    for comment in review_comments {
-        let is_mine = normalized_login
-            .as_ref()
-            .map(|login| comment.user.login.eq_ignore_ascii_case(login))
-            .unwrap_or(false);
-        // No patch needed for submitted comments - they already have line numbers
-        collected.push(map_review_comment(comment, is_mine, None, pr_head_sha));
-    }
+    let response = client
+        .post(format!(
+            "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}/events"
+        ))
+        .json(&Value::Object(payload))
+        .send_recorded()
+        .await?;
 
-    for comment in issue_comments {
-        let is_mine = normalized_login
-            .as_ref()
-            .map(|login| comment.user.login.eq_ignore_ascii_case(login))
-            .unwrap_or(false);
-        collected.push(map_issue_comment(comment, is_mine));
-    }
+    let response = ensure_success(
+        response,
+        &format!("submit review {review_id} for {owner}/{repo}#{number}"),
+    )
+    .await?;
 
-    // Add review bodies as PR-level comments
-    for review in reviews {
-        if let Some(body) = &review.body {
-            if !body.trim().is_empty() {
-                let is_mine = normalized_login
-                    .as_ref()
-                    .map(|login| review.user.login.eq_ignore_ascii_case(login))
-                    .unwrap_or(false);
-                collected.push(map_review_body_comment(review, is_mine));
-            }
-        }
-    }
+    Ok(response.json::<GitHubPullRequestReview>().await?.commit_id)
+}
 
-    collected.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-    collected
+#[derive(Debug, Clone, Copy)]
+pub enum CommentMode {
+    Single,
+    Review,
 }
 
-fn build_reviews(
-    current_login: Option<&str>,
-    reviews: &[GitHubPullRequestReview],
-) -> Vec<PullRequestReview> {
-    let normalized_login = current_login
-        .filter(|login| !login.is_empty())
-        .map(|login| login.to_ascii_lowercase());
+/// `body` followed by a `suggestion` block with the proposed replacement. One trailing
+/// newline of the replacement is dropped, and the fence is longer than any run of backticks
+/// inside it so that code blocks in the replacement do not close it early.
+pub fn suggestion_body(body: &str, suggestion: &Suggestion) -> String {
+    let replacement = suggestion.replacement.replace("\r\n", "\n");
+    let longest_run = replacement.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let block = if replacement.is_empty() {
+        // An empty block suggests deleting the lines
+        format!("{fence}suggestion\n{fence}")
+    } else {
+        let text = replacement.strip_suffix('\n').unwrap_or(&replacement);
+        format!("{fence}suggestion\n{text}\n{fence}")
+    };
 
-    reviews
-        .iter()
-        .map(|review| map_review(review, normalized_login.as_deref()))
-        .collect()
+    let body = body.trim_end();
+    if body.is_empty() {
+        block
+    } else {
+        format!("{body}\n\n{block}")
+    }
 }
 
-fn map_review(
-    review: &GitHubPullRequestReview,
-    normalized_login: Option<&str>,
-) -> PullRequestReview {
-    let review_author_normalized = review.user.login.to_ascii_lowercase();
-    let is_mine = normalized_login
-        .map(|login| review_author_normalized == login)
-        .unwrap_or(false);
+/// Point a comment on `side` at the lines `suggestion` replaces, as a multi-line
+/// comment when it spans more than one. Suggestions on the base side are refused.
+fn insert_suggestion_range(
+    fields: &mut Map<String, Value>,
+    suggestion: &Suggestion,
+    side: &str,
+) -> AppResult<()> {
+    suggestion.validate_for_side(side)?;
+    fields.insert("line".into(), Value::Number(suggestion.end_line.into()));
+    fields.insert("side".into(), Value::String(side.to_string()));
+    if suggestion.start_line < suggestion.end_line {
+        fields.insert("start_line".into(), Value::Number(suggestion.start_line.into()));
+        fields.insert("start_side".into(), Value::String(side.to_string()));
+    }
+    Ok(())
+}
 
-    PullRequestReview {
-        id: review.id,
-        state: review.state.clone(),
-        author: review.user.login.clone(),
-        submitted_at: review.submitted_at.clone(),
-        body: review.body.clone(),
-        html_url: review.html_url.clone(),
-        commit_id: review.commit_id.clone(),
-        is_mine,
+/// The body of a stored comment as posted, with its suggestion if it has one.
+fn stored_comment_body(comment: &crate::review_storage::ReviewComment) -> String {
+    match &comment.suggestion {
+        Some(suggestion) => suggestion_body(&comment.body, suggestion),
+        None => comment.body.clone(),
     }
 }
 
-fn map_review_comment(comment: &GitHubReviewComment, is_mine: bool, patch: Option<&String>, pr_head_sha: &str) -> PullRequestComment {
-    // Check if this is a PR-level comment (empty path means it's a review body comment, not a file comment)
-    let is_pr_level = comment.path.trim().is_empty();
-    
-    // Check if this is a file-level comment
-    let is_file_level = comment.subject_type.as_deref() == Some("file");
-    
-    // Try to get line number from multiple possible fields, but only if not file-level and not PR-level
-    let mut line = if is_file_level || is_pr_level {
+pub async fn submit_file_comment(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    path: &str,
+    body: &str,
+    commit_id: &str,
+    line: Option<u64>,
+    side: Option<&str>,
+    subject_type: Option<&str>,
+    mode: CommentMode,
+    pending_review_id: Option<u64>,
+    in_reply_to: Option<u64>,
+    suggestion: Option<&Suggestion>,
+) -> AppResult<()> {
+    if let Some(suggestion) = suggestion {
+        suggestion.validate_for_side(side.unwrap_or("RIGHT"))?;
+    }
+    let client = build_client(token)?;
+    let body = match suggestion {
+        Some(suggestion) => suggestion_body(body, suggestion),
+        None => body.to_string(),
+    };
+
+    // If no line number provided, treat as file-level comment. Suggestions always
+    // target the lines they replace.
+    let effective_subject_type = if suggestion.is_some() {
         None
+    } else if subject_type.is_none() && line.is_none() {
+        Some("file")
     } else {
-        comment.line
-            .or(comment.original_line)
-            .or(comment.start_line)
-            .or(comment.original_start_line)
+        subject_type
     };
-    
-    // If we don't have a line number but we have a position and patch, convert it
-    if line.is_none() && !is_file_level && !is_pr_level {
-        if let (Some(position), Some(patch_text)) = (comment.position.or(comment.original_position), patch) {
-            line = convert_diff_position_to_line(patch_text, position, comment.side.as_deref().unwrap_or("RIGHT"));
-        }
+
+    if matches!(mode, CommentMode::Review) && matches!(effective_subject_type, Some("file")) {
+        return Err(AppError::Api(
+            "GitHub does not allow starting a review with a file-level comment via the REST API. Choose a specific line or post this as a single comment.".into(),
+        ));
     }
-    
-    // Determine if comment is outdated by checking if it references an older commit
-    let is_outdated = comment.outdated.or_else(|| {
-        if let Some(commit_id) = &comment.commit_id {
-            if commit_id != pr_head_sha {
-                return Some(true);
-            }
-        }
-        None
-    });
-    
-    PullRequestComment {
-        id: comment.id,
-        body: comment.body.clone(),
-        author: comment.user.login.clone(),
-        created_at: comment.created_at.clone(),
-        url: comment.html_url.clone(),
-        path: if is_pr_level { None } else { Some(comment.path.clone()) },
-        line,
-        side: comment.side.clone().or(comment.start_side.clone()),
-        is_review_comment: !is_pr_level, // PR-level review comments should be treated as non-review comments
-        is_draft: comment
-            .state
-            .as_deref()
-            .map(|state| state.eq_ignore_ascii_case("pending"))
-            .unwrap_or(false),
-        state: comment.state.clone(),
-        is_mine,
-        review_id: comment.pull_request_review_id,
-        in_reply_to_id: comment.in_reply_to_id,
-        outdated: is_outdated,
+
+    let mut single_comment_fields = Map::new();
+    single_comment_fields.insert("body".into(), Value::String(body.clone()));
+    single_comment_fields.insert("path".into(), Value::String(path.to_string()));
+    single_comment_fields.insert("commit_id".into(), Value::String(commit_id.to_string()));
+
+    if let Some(subject_type) = effective_subject_type {
+        single_comment_fields.insert(
+            "subject_type".into(),
+            Value::String(subject_type.to_string()),
+        );
+    } else if let Some(suggestion) = suggestion {
+        insert_suggestion_range(&mut single_comment_fields, suggestion, side.unwrap_or("RIGHT"))?;
+    } else if let Some(line_number) = line {
+        single_comment_fields.insert("line".into(), Value::Number(line_number.into()));
+        single_comment_fields.insert(
+            "side".into(),
+            Value::String(side.unwrap_or("RIGHT").to_string()),
+        );
     }
-}
 
-/// Converts a diff position to an absolute line number
-/// Position is 1-indexed and counts lines in the diff output
-/// Side is "LEFT" (base) or "RIGHT" (head)
-fn convert_diff_position_to_line(patch: &str, position: u64, side: &str) -> Option<u64> {
-    let mut current_position = 0u64;
-    let mut left_line = 0u64; // Current line in base file
-    let mut right_line = 0u64; // Current line in head file
-    
-    for line in patch.lines() {
-        // Parse hunk headers like: @@ -10,7 +10,8 @@
-        if line.starts_with("@@") {
-            if let Some(header) = parse_hunk_header(line) {
-                left_line = header.0;
-                right_line = header.1;
-            }
-            continue;
-        }
-        
-        // Each line in the diff (except headers) increments position
-        current_position += 1;
-        
-        if line.starts_with('-') {
-            // Deletion: only exists on LEFT side
-            if current_position == position && side == "LEFT" {
-                return Some(left_line);
-            }
-            left_line += 1;
-        } else if line.starts_with('+') {
-            // Addition: only exists on RIGHT side
-            if current_position == position && side == "RIGHT" {
-                return Some(right_line);
-            }
-            right_line += 1;
-        } else {
-            // Context line: exists on both sides
-            if current_position == position {
-                return Some(if side == "LEFT" { left_line } else { right_line });
-            }
-            left_line += 1;
-            right_line += 1;
-        }
+    // Add in_reply_to if provided
+    if let Some(reply_to_id) = in_reply_to {
+        single_comment_fields.insert("in_reply_to".into(), Value::Number(reply_to_id.into()));
     }
-    
-    None
+
+    match mode {
+        CommentMode::Single => {
+            let payload = Value::Object(single_comment_fields);
+            let response = client
+                .post(format!(
+                    "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"
+                ))
+                .json(&payload)
+                .send_recorded()
+                .await?;
+
+            ensure_success(
+                response,
+                &format!("submit single file comment for {owner}/{repo}#{number}"),
+            )
+            .await?;
+        }
+        CommentMode::Review => {
+            let line_number = suggestion.map(|s| s.end_line).or(line).ok_or_else(|| {
+                AppError::Api(
+                    "Select a specific line before starting a review comment.".into(),
+                )
+            })?;
+
+            let comment_side = side.unwrap_or("RIGHT");
+            let mut review_comment_fields = Map::new();
+            review_comment_fields.insert("body".into(), Value::String(body));
+            review_comment_fields.insert("path".into(), Value::String(path.to_string()));
+            review_comment_fields.insert(
+                "line".into(),
+                Value::Number(serde_json::Number::from(line_number)),
+            );
+            review_comment_fields.insert(
+                "side".into(),
+                Value::String(comment_side.to_string()),
+            );
+            review_comment_fields.insert(
+                "commit_id".into(),
+                Value::String(commit_id.to_string()),
+            );
+            if let Some(suggestion) = suggestion {
+                insert_suggestion_range(&mut review_comment_fields, suggestion, comment_side)?;
+            }
+
+            // Add in_reply_to if provided
+            if let Some(reply_to_id) = in_reply_to {
+                review_comment_fields.insert("in_reply_to".into(), Value::Number(reply_to_id.into()));
+            }
+
+            // If we don't have a pending_review_id, the user must call "Start review" first
+            let review_id = pending_review_id.ok_or_else(|| {
+                AppError::Api(
+                    "No pending review found. Please start a review first by clicking 'Start review'.".into(),
+                )
+            })?;
+
+            // Add comment directly to the pending review using the review comments endpoint
+            let response = client
+                .post(format!(
+                    "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}/comments"
+                ))
+                .json(&Value::Object(review_comment_fields))
+                .send_recorded()
+                .await?;
+
+            ensure_success(
+                response,
+                &format!(
+                    "attach file comment to pending review for {owner}/{repo}#{number}"
+                ),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The repository's `.github/doc-review.yml` on its default branch, or `None` when the
+/// repo does not have one.
+pub async fn fetch_repo_review_config(
+    token: &str,
+    owner: &str,
+    repo: &str,
+) -> AppResult<Option<String>> {
+    let client = build_client(token)?;
+    let path = crate::repo_config::REPO_CONFIG_PATH;
+    let response = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
+        .header(ACCEPT, "application/vnd.github.v3.raw")
+        .send_recorded()
+        .await?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let response = ensure_success(
+        response,
+        &format!("fetch review config for {owner}/{repo}"),
+    )
+    .await?;
+    Ok(Some(response.text().await?))
+}
+
+/// A translated file and its source at `reference`. A missing source is `None` rather
+/// than an error, since a translation may be added before its source is published.
+pub async fn fetch_localized_pair(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    reference: &str,
+    translation_path: &str,
+    source_locale: &str,
+) -> AppResult<crate::localization::LocalizedPair> {
+    let (Some(translation_locale), Some(source_path)) = (
+        crate::localization::locale_of(translation_path),
+        crate::localization::source_path(translation_path, source_locale),
+    ) else {
+        return Err(AppError::InvalidInput(format!(
+            "`{translation_path}` is not a translation of a `{source_locale}` file"
+        )));
+    };
+    let client = build_client(token)?;
+
+    let mut contents = Vec::with_capacity(2);
+    for path in [source_path.as_str(), translation_path] {
+        let response = client
+            .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
+            .query(&[("ref", reference)])
+            .header(ACCEPT, "application/vnd.github.v3.raw")
+            .send_recorded()
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            contents.push(None);
+            continue;
+        }
+        let response = ensure_success(
+            response,
+            &format!("fetch file contents for {owner}/{repo}:{reference}:{path}"),
+        )
+        .await?;
+        contents.push(Some(response.text().await?));
+    }
+    let translation_content = contents.pop().flatten();
+    let source_content = contents.pop().flatten();
+
+    Ok(crate::localization::LocalizedPair {
+        source_path,
+        source_locale: source_locale.to_string(),
+        source_content,
+        translation_path: translation_path.to_string(),
+        translation_locale,
+        translation_content,
+    })
+}
+
+/// Heading anchors removed by the markdown files a PR modifies or renames.
+pub async fn list_changed_anchors(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    base_sha: &str,
+    head_sha: &str,
+) -> AppResult<Vec<crate::anchor_impact::ChangedAnchor>> {
+    let client = build_client(token)?;
+    let files = fetch_pull_request_files(&client, owner, repo, number).await?;
+
+    let mut changed = Vec::new();
+    for file in files {
+        if !matches!(file.status.as_str(), "modified" | "renamed")
+            || !crate::local_files::is_markdown(std::path::Path::new(&file.filename))
+        {
+            continue;
+        }
+        let base_path = file.previous_filename.as_deref().unwrap_or(&file.filename);
+        let base = fetch_file_contents(&client, owner, repo, base_path, base_sha).await?;
+        let head = fetch_file_contents(&client, owner, repo, &file.filename, head_sha).await?;
+        changed.extend(crate::anchor_impact::changed_anchors(base_path, &base, &head));
+    }
+    Ok(changed)
+}
+
+/// Concurrent content requests while building a link index.
+const LINK_INDEX_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Deserialize)]
+struct GitTree {
+    tree: Vec<GitTreeEntry>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Index the in-repo anchor links of every markdown file at `reference`, up to
+/// `MAX_INDEXED_FILES` files.
+pub async fn build_link_index(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    reference: &str,
+) -> AppResult<crate::link_index::LinkIndex> {
+    use crate::link_index::{LinkIndex, MAX_INDEXED_FILES};
+    use crate::local_files::is_markdown_path;
+
+    let client = build_client(token)?;
+    let response = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/git/trees/{reference}"))
+        .query(&[("recursive", "1")])
+        .send_recorded()
+        .await?;
+    let response =
+        ensure_success(response, &format!("list files of {owner}/{repo} at {reference}")).await?;
+    let tree = response.json::<GitTree>().await?;
+
+    let mut paths: Vec<String> = tree
+        .tree
+        .into_iter()
+        .filter(|entry| entry.kind == "blob" && is_markdown_path(&entry.path))
+        .map(|entry| entry.path)
+        .collect();
+    let truncated = tree.truncated || paths.len() > MAX_INDEXED_FILES;
+    paths.truncate(MAX_INDEXED_FILES);
+
+    let permits = std::sync::Arc::new(tokio::sync::Semaphore::new(LINK_INDEX_CONCURRENCY));
+    let mut fetches = tokio::task::JoinSet::new();
+    for path in paths {
+        let client = client.clone();
+        let permits = permits.clone();
+        let (owner, repo, reference) = (owner.to_string(), repo.to_string(), reference.to_string());
+        fetches.spawn(async move {
+            let _permit = permits.acquire_owned().await.ok()?;
+            let content = fetch_file_contents(&client, &owner, &repo, &path, &reference).await;
+            match content {
+                Ok(content) => Some((path, content)),
+                Err(err) => {
+                    warn!(path = %path, error = %err, "skipping file in link index");
+                    None
+                }
+            }
+        });
+    }
+
+    let mut files = Vec::new();
+    while let Some(result) = fetches.join_next().await {
+        if let Ok(Some(file)) = result {
+            files.push(file);
+        }
+    }
+    files.sort();
+    Ok(LinkIndex::build(&files, truncated))
+}
+
+/// Raw text of a file at `reference`.
+pub async fn fetch_text_file(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    path: &str,
+    reference: &str,
+) -> AppResult<String> {
+    let client = build_client(token)?;
+    fetch_file_contents(&client, owner, repo, path, reference).await
+}
+
+async fn fetch_file_contents(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    path: &str,
+    reference: &str,
+) -> AppResult<String> {
+    fetch_file_contents_as(client, owner, repo, path, reference, ContentFormat::Raw).await
+}
+
+const IMAGE_EXTENSIONS: &[&str] =
+    &[".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp", ".bmp", ".ico"];
+
+/// Whether `path` names an image, whose contents GitHub returns base64 encoded.
+pub fn is_image_path(path: &str) -> bool {
+    let path = path.to_ascii_lowercase();
+    IMAGE_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+async fn fetch_file_contents_as(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    path: &str,
+    reference: &str,
+    format: ContentFormat,
+) -> AppResult<String> {
+    if is_image_path(path) {
+        // For images, get the JSON response with base64 content
+        let response = client
+            .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
+            .query(&[("ref", reference)])
+            .send_recorded()
+            .await?;
+
+        let response = ensure_success(
+            response,
+            &format!("fetch file contents for {owner}/{repo}:{reference}:{path}"),
+        )
+        .await?;
+
+        let content_json: Value = response.json().await?;
+        
+        // GitHub returns content as base64 in the "content" field
+        if let Some(content) = content_json.get("content").and_then(|c| c.as_str()) {
+            // Remove whitespace/newlines that GitHub adds to the base64 string
+            let cleaned: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+            Ok(cleaned)
+        } else {
+            Err(AppError::Api("Image content not found in response".to_string()))
+        }
+    } else {
+        // For text files, get raw content or GitHub's rendering of it
+        let response = client
+            .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
+            .query(&[("ref", reference)])
+            .header(ACCEPT, format.media_type())
+            .send_recorded()
+            .await?;
+
+        let response = ensure_success(
+            response,
+            &format!("fetch file contents for {owner}/{repo}:{reference}:{path}"),
+        )
+        .await?;
+
+        Ok(response.text().await?)
+    }
+}
+
+/// Review comments of a PR, or only those updated after `since` (ISO 8601).
+async fn fetch_review_comments(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    since: Option<&str>,
+) -> AppResult<Vec<GitHubReviewComment>> {
+    let mut all_comments = Vec::new();
+    let mut page = 1;
+    
+    loop {
+        let mut request = client
+            .get(format!(
+                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"
+            ))
+            .query(&[("per_page", "100"), ("page", &page.to_string())]);
+        if let Some(since) = since {
+            request = request.query(&[("since", since)]);
+        }
+        let response = request
+            .send_recorded()
+            .await?;
+
+        let response = ensure_success(
+            response,
+            &format!("list review comments for {owner}/{repo}#{number} (page {})", page),
+        )
+        .await?;
+
+        let comments = response.json::<Vec<GitHubReviewComment>>().await?;
+        let count = comments.len();
+        all_comments.extend(comments);
+        
+        // If we got less than 100, we've reached the last page
+        if count < 100 {
+            break;
+        }
+        
+        page += 1;
+    }
+    
+    Ok(all_comments)
+}
+
+async fn fetch_pull_request_files(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<Vec<GitHubPullRequestFile>> {
+    let mut all_files = Vec::new();
+    let mut page = 1;
+    
+    loop {
+        let files_response = client
+            .get(format!(
+                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/files"
+            ))
+            .query(&[("per_page", "100"), ("page", &page.to_string())])
+            .send_recorded()
+            .await?;
+
+        let files_response = ensure_success(
+            files_response,
+            &format!("list pull request files {owner}/{repo}#{number} (page {})", page),
+        )
+        .await?;
+
+        let files = files_response.json::<Vec<GitHubPullRequestFile>>().await?;
+        let count = files.len();
+        all_files.extend(files);
+        
+        // If we got less than 100, we've reached the last page
+        if count < 100 {
+            break;
+        }
+        
+        page += 1;
+    }
+    
+    Ok(all_files)
+}
+
+pub async fn get_pending_review_comments(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    review_id: u64,
+    current_login: Option<&str>,
+) -> AppResult<Vec<PullRequestComment>> {
+    let client = build_client(token)?;
+    
+    // Fetch PR to get head SHA
+    let pr_response = client
+        .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
+        .send_recorded()
+        .await?;
+    
+    let pr_response = ensure_success(
+        pr_response,
+        &format!("fetch pull request {owner}/{repo}#{number}"),
+    )
+    .await?;
+    
+    let pr = pr_response.json::<GitHubPullRequest>().await?;
+    let head_sha = pr.head.sha;
+    
+    let comments = fetch_pending_review_comments(&client, owner, repo, number, review_id).await?;
+    
+    // Fetch all PR files to get patches for position-to-line conversion
+    let all_files = fetch_pull_request_files(&client, owner, repo, number).await?;
+    
+    // Build a map of file path to patch
+    let mut patches: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    for file in all_files {
+        if let Some(patch) = file.patch {
+            patches.insert(file.filename, patch);
+        }
+    }
+    
+    let normalized_login = current_login
+        .filter(|login| !login.is_empty())
+        .map(|login| login.to_ascii_lowercase());
+    
+    let mapped_comments: Vec<PullRequestComment> = comments
+        .iter()
+        .map(|comment| {
+            let is_mine = normalized_login
+                .as_ref()
+                .map(|login| comment.user.login.eq_ignore_ascii_case(login))
+                .unwrap_or(false);
+            
+            // Get the patch for this file
+            let patch = patches.get(&comment.path);
+            
+            map_review_comment(comment, is_mine, patch, &head_sha)
+        })
+        .collect();
+    
+    Ok(mapped_comments)
+}
+
+async fn fetch_pending_review_comments(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    review_id: u64,
+) -> AppResult<Vec<GitHubReviewComment>> {
+    let mut all_comments = Vec::new();
+    let mut page = 1;
+    
+    loop {
+        let response = client
+            .get(format!(
+                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}/comments"
+            ))
+            .query(&[("per_page", "100"), ("page", &page.to_string())])
+            .send_recorded()
+            .await?;
+
+        let response = ensure_success(
+            response,
+            &format!("list pending review comments for {owner}/{repo}#{number} review {review_id} (page {})", page),
+        )
+        .await?;
+
+        let comments = response.json::<Vec<GitHubReviewComment>>().await?;
+        let count = comments.len();
+        all_comments.extend(comments);
+        
+        // If we got less than 100, we've reached the last page
+        if count < 100 {
+            break;
+        }
+        
+        page += 1;
+    }
+    
+    Ok(all_comments)
+}
+
+/// Conversation comments of a PR, or only those updated after `since` (ISO 8601).
+async fn fetch_issue_comments(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    since: Option<&str>,
+) -> AppResult<Vec<GitHubIssueComment>> {
+    let mut all_comments = Vec::new();
+    let mut page = 1;
+    
+    loop {
+        let mut request = client
+            .get(format!(
+                "{API_BASE}/repos/{owner}/{repo}/issues/{number}/comments"
+            ))
+            .query(&[("per_page", "100"), ("page", &page.to_string())]);
+        if let Some(since) = since {
+            request = request.query(&[("since", since)]);
+        }
+        let response = request
+            .send_recorded()
+            .await?;
+
+        let response = ensure_success(
+            response,
+            &format!("list issue comments for {owner}/{repo}#{number} (page {})", page),
+        )
+        .await?;
+
+        let comments = response.json::<Vec<GitHubIssueComment>>().await?;
+        let count = comments.len();
+        all_comments.extend(comments);
+        
+        // If we got less than 100, we've reached the last page
+        if count < 100 {
+            break;
+        }
+        
+        page += 1;
+    }
+    
+    Ok(all_comments)
+}
+
+/// Update a review comment on a pull request
+pub async fn update_review_comment(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    comment_id: u64,
+    body: &str,
+) -> AppResult<()> {
+    let client = build_client(token)?;
+    
+    let payload = json!({
+        "body": body,
+    });
+
+    let response = client
+        .patch(format!(
+            "{API_BASE}/repos/{owner}/{repo}/pulls/comments/{comment_id}"
+        ))
+        .json(&payload)
+        .send_recorded()
+        .await?;
+
+    ensure_success(
+        response,
+        &format!("update review comment {comment_id} for {owner}/{repo}"),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Delete a review comment on a pull request
+pub async fn delete_review_comment(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    comment_id: u64,
+) -> AppResult<()> {
+    let client = build_client(token)?;
+
+    let response = client
+        .delete(format!(
+            "{API_BASE}/repos/{owner}/{repo}/pulls/comments/{comment_id}"
+        ))
+        .send_recorded()
+        .await?;
+
+    ensure_success(
+        response,
+        &format!("delete review comment {comment_id} for {owner}/{repo}"),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// REST endpoint for the reactions on a comment. Review bodies have none.
+fn comment_reactions_url(
+    owner: &str,
+    repo: &str,
+    kind: CommentKind,
+    comment_id: u64,
+) -> AppResult<String> {
+    let collection = match kind {
+        CommentKind::ReviewComment => "pulls",
+        CommentKind::IssueComment => "issues",
+        CommentKind::Review => {
+            return Err(AppError::InvalidInput(
+                "GitHub's API does not support reactions on review summaries".into(),
+            ))
+        }
+    };
+    Ok(format!("{API_BASE}/repos/{owner}/{repo}/{collection}/comments/{comment_id}/reactions"))
+}
+
+/// React to a comment with `content` (`+1`, `hooray`, ...). Reacting again with a
+/// reaction I already left is not an error.
+pub async fn add_comment_reaction(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    kind: CommentKind,
+    comment_id: u64,
+    content: &str,
+) -> AppResult<()> {
+    let client = build_client(token)?;
+    let response = client
+        .post(comment_reactions_url(owner, repo, kind, comment_id)?)
+        .json(&json!({ "content": content }))
+        .send_recorded()
+        .await?;
+    ensure_success(
+        response,
+        &format!("add {content} reaction to comment {comment_id} in {owner}/{repo}"),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Take back my `content` reaction on a comment. Returns whether I had left one.
+pub async fn remove_comment_reaction(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    kind: CommentKind,
+    comment_id: u64,
+    content: &str,
+) -> AppResult<bool> {
+    let client = build_client(token)?;
+    let url = comment_reactions_url(owner, repo, kind, comment_id)?;
+    let login = fetch_authenticated_user(token).await?.login;
+
+    // Reactions are deleted by id, so find mine among everyone's
+    let mut page = 1;
+    let reaction_id = loop {
+        let response = client
+            .get(&url)
+            .query(&[("content", content), ("per_page", "100"), ("page", &page.to_string())])
+            .send_recorded()
+            .await?;
+        let response = ensure_success(
+            response,
+            &format!("list reactions on comment {comment_id} in {owner}/{repo} (page {page})"),
+        )
+        .await?;
+        let reactions = response.json::<Vec<GitHubReaction>>().await?;
+        let mine = reactions.iter().find(|reaction| {
+            reaction
+                .user
+                .as_ref()
+                .is_some_and(|user| user.login.eq_ignore_ascii_case(&login))
+        });
+        if let Some(mine) = mine {
+            break Some(mine.id);
+        }
+        if reactions.len() < 100 {
+            break None;
+        }
+        page += 1;
+    };
+    let Some(reaction_id) = reaction_id else {
+        return Ok(false);
+    };
+
+    let response = client
+        .delete(format!("{url}/{reaction_id}"))
+        .send_recorded()
+        .await?;
+    ensure_success(
+        response,
+        &format!("remove {content} reaction from comment {comment_id} in {owner}/{repo}"),
+    )
+    .await?;
+    Ok(true)
+}
+
+const REACTIONS_AND_PARTICIPANTS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      thumbsUp: reactions(content: THUMBS_UP) { totalCount }
+      thumbsDown: reactions(content: THUMBS_DOWN) { totalCount }
+      hooray: reactions(content: HOORAY) { totalCount }
+      participants(first: 100) { nodes { login } }
+    }
+  }
+}
+"#;
+
+async fn fetch_reactions_and_participants(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<(ReactionSummary, Vec<String>)> {
+    let data: GraphQlRepositoryData = graphql::query(
+        client,
+        REACTIONS_AND_PARTICIPANTS_QUERY,
+        json!({ "owner": owner, "repo": repo, "number": number }),
+        &format!("fetch reactions for {owner}/{repo}#{number}"),
+    )
+    .await?;
+
+    let Some(pr) = data.repository.and_then(|r| r.pull_request) else {
+        return Ok((ReactionSummary::default(), Vec::new()));
+    };
+    let reactions = ReactionSummary {
+        thumbs_up: pr.thumbs_up.total_count,
+        thumbs_down: pr.thumbs_down.total_count,
+        hooray: pr.hooray.total_count,
+    };
+    // Deleted accounts come back as null nodes
+    let logins = pr
+        .participants
+        .nodes
+        .into_iter()
+        .flatten()
+        .map(|user| user.login)
+        .collect();
+    Ok((reactions, logins))
+}
+
+/// How far back "recent" PRs by the author are counted.
+const RECENT_PR_WINDOW_DAYS: u32 = 90;
+
+const AUTHOR_CONTEXT_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $recent: String!, $merged: String!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) { authorAssociation }
+  }
+  recent: search(query: $recent, type: ISSUE, first: 0) { issueCount }
+  merged: search(query: $merged, type: ISSUE, first: 0) { issueCount }
+}
+"#;
+
+async fn fetch_author_context(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    author: &str,
+) -> AppResult<Option<AuthorContext>> {
+    let since = chrono::Utc::now() - chrono::Duration::days(i64::from(RECENT_PR_WINDOW_DAYS));
+    let scope = format!("repo:{owner}/{repo} is:pr author:{author}");
+    let data: GraphQlAuthorContextData = graphql::query(
+        client,
+        AUTHOR_CONTEXT_QUERY,
+        json!({
+            "owner": owner,
+            "repo": repo,
+            "number": number,
+            "recent": format!("{scope} created:>={}", since.format("%Y-%m-%d")),
+            "merged": format!("{scope} is:merged"),
+        }),
+        &format!("fetch author context for {owner}/{repo}#{number}"),
+    )
+    .await?;
+
+    let Some(pr) = data.repository.and_then(|r| r.pull_request) else {
+        return Ok(None);
+    };
+    let merged_pr_count = data.merged.issue_count;
+    let first_contribution =
+        crate::models::is_first_contribution(&pr.author_association, merged_pr_count);
+    Ok(Some(AuthorContext {
+        first_contribution,
+        association: pr.author_association,
+        recent_pr_count: data.recent.issue_count,
+        recent_window_days: RECENT_PR_WINDOW_DAYS,
+        merged_pr_count,
+    }))
+}
+
+const REVIEW_REQUESTS_QUERY: &str = r#"
+query($query: String!) {
+  search(query: $query, type: ISSUE, first: 100) {
+    nodes {
+      ... on PullRequest {
+        number
+        title
+        url
+        createdAt
+        changedFiles
+        additions
+        deletions
+        author { login }
+        repository { name owner { login } }
+      }
+    }
+  }
+}
+"#;
+
+/// Open pull requests where the authenticated user's review is requested, directly or
+/// through a team.
+pub async fn list_review_requests(token: &str) -> AppResult<Vec<crate::workload::AwaitingReview>> {
+    let client = build_client(token)?;
+    let data: GraphQlSearchData = graphql::query(
+        &client,
+        REVIEW_REQUESTS_QUERY,
+        json!({ "query": "is:pr is:open archived:false review-requested:@me" }),
+        "list review requests",
+    )
+    .await?;
+
+    Ok(data
+        .search
+        .nodes
+        .into_iter()
+        .flatten()
+        .filter(|pr| crate::owner_policy::is_allowed(&pr.repository.owner.login))
+        .map(|pr| crate::workload::AwaitingReview {
+            owner: pr.repository.owner.login,
+            repo: pr.repository.name,
+            number: pr.number,
+            title: pr.title,
+            author: pr.author.map(|a| a.login).unwrap_or_default(),
+            url: pr.url,
+            created_at: pr.created_at,
+            changed_files: pr.changed_files,
+            additions: pr.additions,
+            deletions: pr.deletions,
+            has_local_review: false,
+        })
+        .collect())
+}
+
+const VIEWER_PR_SEARCH_QUERY: &str = r#"
+query($query: String!, $first: Int!, $after: String) {
+  viewer { login }
+  search(query: $query, type: ISSUE, first: $first, after: $after) {
+    pageInfo { hasNextPage endCursor }
+    nodes {
+      ... on PullRequest {
+        number
+        title
+        repository { name owner { login } }
+      }
+    }
+  }
+}
+"#;
+
+/// Up to `limit` PRs found by `search`, with the authenticated user's login. PRs of
+/// owners outside the owner policy are left out.
+async fn search_pull_requests_as_viewer(
+    client: &reqwest::Client,
+    search: &str,
+    limit: usize,
+    context: &str,
+) -> AppResult<(String, Vec<GraphQlViewerPullRequest>)> {
+    let mut pulls = Vec::new();
+    let mut after: Option<String> = None;
+
+    let login = loop {
+        let data: GraphQlViewerSearchData = graphql::query(
+            client,
+            VIEWER_PR_SEARCH_QUERY,
+            json!({ "query": search, "first": limit.min(100), "after": after }),
+            context,
+        )
+        .await?;
+        pulls.extend(
+            data.search
+                .nodes
+                .into_iter()
+                .flatten()
+                .filter(|pr| crate::owner_policy::is_allowed(&pr.repository.owner.login)),
+        );
+        match data.search.page_info.end_cursor {
+            Some(cursor) if data.search.page_info.has_next_page && pulls.len() < limit => {
+                after = Some(cursor)
+            }
+            _ => break data.viewer.login,
+        }
+    };
+
+    pulls.truncate(limit);
+    Ok((login, pulls))
+}
+
+/// My comments, review and conversation, on PRs GitHub finds for `query`, newest first.
+/// Only the `MAX_SEARCHED_PRS` best matching PRs are read.
+pub async fn search_my_comments(
+    token: &str,
+    query: &str,
+    repo_filter: Option<&str>,
+) -> AppResult<Vec<CommentSearchHit>> {
+    let parsed = comment_search::parse_query(query);
+    let search = comment_search::search_query(&parsed, repo_filter)?;
+    // A search names its owner in the query rather than the path, so it is checked here
+    if let Some((owner, _)) = repo_filter.and_then(|repo| repo.trim().split_once('/')) {
+        crate::owner_policy::current()?.ensure_allowed(owner)?;
+    }
+    let client = build_client(token)?;
+    let (me, pulls) = search_pull_requests_as_viewer(
+        &client,
+        &search,
+        comment_search::MAX_SEARCHED_PRS,
+        "search my comments",
+    )
+    .await?;
+
+    let mut hits = Vec::new();
+    for pr in pulls {
+        let owner = pr.repository.owner.login;
+        let repo = pr.repository.name;
+        let review_comments = fetch_review_comments(&client, &owner, &repo, pr.number, None).await?;
+        let issue_comments = fetch_issue_comments(&client, &owner, &repo, pr.number, None).await?;
+
+        let hit = |comment_id: u64, url: &str, body: &str, path: Option<String>, line: Option<u64>, created_at: &str| {
+            CommentSearchHit {
+                owner: owner.clone(),
+                repo: repo.clone(),
+                pr_number: pr.number,
+                pr_title: pr.title.clone(),
+                comment_id,
+                url: url.to_string(),
+                path,
+                line,
+                created_at: created_at.to_string(),
+                snippet: comment_search::snippet(body, &parsed.terms),
+            }
+        };
+        hits.extend(
+            review_comments
+                .iter()
+                .filter(|c| c.user.login.eq_ignore_ascii_case(&me))
+                .filter(|c| comment_search::matches(&c.body, &parsed.terms))
+                .map(|c| {
+                    hit(
+                        c.id,
+                        &c.html_url,
+                        &c.body,
+                        Some(c.path.clone()).filter(|path| !path.is_empty()),
+                        c.line.or(c.original_line),
+                        &c.created_at,
+                    )
+                }),
+        );
+        hits.extend(
+            issue_comments
+                .iter()
+                .filter(|c| c.user.login.eq_ignore_ascii_case(&me))
+                .filter(|c| comment_search::matches(&c.body, &parsed.terms))
+                .map(|c| hit(c.id, &c.html_url, &c.body, None, None, &c.created_at)),
+        );
+    }
+
+    hits.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(hits)
+}
+
+/// Pull requests read per batched activity query. Each brings up to 100 reviews of 100
+/// comments and 100 conversation comments, which keeps a batch inside GitHub's node limit.
+const ACTIVITY_BATCH: usize = 20;
+
+/// Everything of a PR that can be review activity. Only the first 100 reviews, 100
+/// comments per review and last 100 conversation comments of each PR are read.
+const ACTIVITY_FIELDS: &str = "number title repository { name owner { login } } \
+    reviews(first: 100) { nodes { author { login } state url submittedAt body \
+    comments(first: 100) { nodes { path line originalLine url createdAt body } } } } \
+    comments(last: 100) { nodes { author { login } url createdAt body } }";
+
+/// The batched query behind `list_review_activity`.
+pub fn activity_query(count: usize) -> String {
+    pull_request_batch_query(count, ACTIVITY_FIELDS)
+}
+
+/// `me`'s submitted reviews, their comments, and `me`'s conversation comments at or
+/// after `since` in each pull request in the `data` of an `activity_query`, in request
+/// order. PRs that could not be looked up are `None`.
+pub fn parse_review_activity(
+    mut data: Value,
+    count: usize,
+    me: &str,
+    since: &str,
+) -> Vec<Option<Vec<ActivityRecord>>> {
+    (0..count)
+        .map(|i| {
+            let pr = data.get_mut(format!("pr{i}"))?.get_mut("pullRequest")?.take();
+            let pr: GraphQlActivity = serde_json::from_value(pr).ok()?;
+            let mine = |author: &Option<GraphQlActor>| {
+                author.as_ref().is_some_and(|a| a.login.eq_ignore_ascii_case(me))
+            };
+            let record = |kind, state, path, line, url: String, created_at: String, body| {
+                ActivityRecord {
+                    kind,
+                    owner: pr.repository.owner.login.clone(),
+                    repo: pr.repository.name.clone(),
+                    pr_number: pr.number,
+                    pr_title: pr.title.clone(),
+                    state,
+                    path,
+                    line,
+                    url,
+                    created_at,
+                    body,
+                }
+            };
+
+            let mut records = Vec::new();
+            let reviews = pr.reviews.nodes.iter().flatten();
+            for review in reviews.filter(|r| mine(&r.author) && r.state != "PENDING") {
+                let submitted = review.submitted_at.as_ref().filter(|at| at.as_str() >= since);
+                if let Some(submitted_at) = submitted {
+                    records.push(record(
+                        ActivityKind::Review,
+                        Some(review.state.clone()),
+                        None,
+                        None,
+                        review.url.clone(),
+                        submitted_at.clone(),
+                        review.body.clone(),
+                    ));
+                }
+                let comments = review.comments.nodes.iter().flatten();
+                for comment in comments.filter(|c| c.created_at.as_str() >= since) {
+                    records.push(record(
+                        ActivityKind::Comment,
+                        None,
+                        Some(comment.path.clone()).filter(|path| !path.is_empty()),
+                        comment.line.or(comment.original_line),
+                        comment.url.clone(),
+                        comment.created_at.clone(),
+                        comment.body.clone(),
+                    ));
+                }
+            }
+            let comments = pr.comments.nodes.iter().flatten();
+            for comment in comments.filter(|c| mine(&c.author) && c.created_at.as_str() >= since) {
+                records.push(record(
+                    ActivityKind::Comment,
+                    None,
+                    None,
+                    None,
+                    comment.url.clone(),
+                    comment.created_at.clone(),
+                    comment.body.clone(),
+                ));
+            }
+            Some(records)
+        })
+        .collect()
+}
+
+/// My submitted reviews and my comments since `since` (normalized, see
+/// `review_activity::normalize_since`) on PRs in `org` I reviewed, read in batched
+/// GraphQL queries rather than three REST calls per PR.
+pub async fn list_review_activity(
+    token: &str,
+    org: &str,
+    since: &str,
+) -> AppResult<Vec<ActivityRecord>> {
+    // The org is named in the search query, which the request check does not see
+    crate::owner_policy::current()?.ensure_allowed(org)?;
+    let client = build_client(token)?;
+    let (me, pulls) = search_pull_requests_as_viewer(
+        &client,
+        &review_activity::search_query(org, since),
+        review_activity::MAX_EXPORTED_PRS,
+        &format!("search review activity in {org}"),
+    )
+    .await?;
+
+    let prs: Vec<(String, String, u64)> = pulls
+        .into_iter()
+        .map(|pr| (pr.repository.owner.login, pr.repository.name, pr.number))
+        .collect();
+    let mut records: Vec<ActivityRecord> = query_pull_request_batches(
+        &client,
+        &prs,
+        ACTIVITY_BATCH,
+        ACTIVITY_FIELDS,
+        &format!("read review activity in {org}"),
+        |data, count| parse_review_activity(data, count, &me, since),
+    )
+    .await?
+    .into_iter()
+    .flatten()
+    .flatten()
+    .collect();
+
+    review_activity::sort_records(&mut records);
+    Ok(records)
+}
+
+async fn fetch_pull_request_reviews(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<Vec<GitHubPullRequestReview>> {
+    fetch_reviews_from_page(client, owner, repo, number, 1).await
+}
+
+/// Reviews of a PR from page `first_page` on.
+async fn fetch_reviews_from_page(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    first_page: u32,
+) -> AppResult<Vec<GitHubPullRequestReview>> {
+    let mut all_reviews = Vec::new();
+    let mut page = first_page;
+    
+    loop {
+        let response = client
+            .get(format!(
+                "{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews"
+            ))
+            .query(&[("per_page", "100"), ("page", &page.to_string())])
+            .send_recorded()
+            .await?;
+
+        let response = ensure_success(
+            response,
+            &format!("list pull request reviews for {owner}/{repo}#{number} (page {})", page),
+        )
+        .await?;
+
+        let reviews = response.json::<Vec<GitHubPullRequestReview>>().await?;
+        let count = reviews.len();
+        all_reviews.extend(reviews);
+        
+        // If we got less than 100, we've reached the last page
+        if count < 100 {
+            break;
+        }
+        
+        page += 1;
+    }
+    
+    Ok(all_reviews)
+}
+
+fn build_comments(
+    current_login: Option<&str>,
+    review_comments: &[GitHubReviewComment],
+    issue_comments: &[GitHubIssueComment],
+    reviews: &[GitHubPullRequestReview],
+    pr_head_sha: &str,
+) -> Vec<PullRequestComment> {
+    let normalized_login = current_login
+        .filter(|login| !login.is_empty())
+        .map(|login| login.to_ascii_lowercase());
+
+    let mut collected = Vec::new();
+
+    for comment in review_comments {
+        let is_mine = normalized_login
+            .as_ref()
+            .map(|login| comment.user.login.eq_ignore_ascii_case(login))
+            .unwrap_or(false);
+        // No patch needed for submitted comments - they already have line numbers
+        collected.push(map_review_comment(comment, is_mine, None, pr_head_sha));
+    }
+
+    for comment in issue_comments {
+        let is_mine = normalized_login
+            .as_ref()
+            .map(|login| comment.user.login.eq_ignore_ascii_case(login))
+            .unwrap_or(false);
+        collected.push(map_issue_comment(comment, is_mine));
+    }
+
+    // Add review bodies as PR-level comments
+    for review in reviews {
+        if let Some(body) = &review.body {
+            if !body.trim().is_empty() {
+                let is_mine = normalized_login
+                    .as_ref()
+                    .map(|login| review.user.login.eq_ignore_ascii_case(login))
+                    .unwrap_or(false);
+                collected.push(map_review_body_comment(review, is_mine));
+            }
+        }
+    }
+
+    collected.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    collected
+}
+
+/// Group line comments into threads. Replies are attached to the root of their chain;
+/// one whose parent is missing, e.g. deleted, starts a thread of its own. `resolved`
+/// maps root comment ids to GitHub's resolution state.
+pub fn build_threads(
+    comments: &[PullRequestComment],
+    resolved: Option<&std::collections::HashMap<u64, bool>>,
+) -> Vec<CommentThread> {
+    let line_comments: Vec<&PullRequestComment> =
+        comments.iter().filter(|c| c.is_review_comment).collect();
+    let parents: std::collections::HashMap<u64, Option<u64>> =
+        line_comments.iter().map(|c| (c.id, c.in_reply_to_id)).collect();
+    let root_of = |id: u64| {
+        let mut current = id;
+        // Bounded so a malformed chain cannot loop forever
+        for _ in 0..parents.len() {
+            match parents.get(&current).copied().flatten() {
+                Some(parent) if parents.contains_key(&parent) => current = parent,
+                _ => break,
+            }
+        }
+        current
+    };
+
+    let mut threads: Vec<CommentThread> = Vec::new();
+    let mut index: std::collections::HashMap<u64, usize> = std::collections::HashMap::new();
+    let mut replies: Vec<(u64, &PullRequestComment)> = Vec::new();
+    for comment in &line_comments {
+        let root = root_of(comment.id);
+        if root != comment.id {
+            replies.push((root, comment));
+            continue;
+        }
+        index.insert(comment.id, threads.len());
+        threads.push(CommentThread {
+            id: comment.id,
+            path: comment.path.clone(),
+            line: comment.line,
+            side: comment.side.clone(),
+            root: (*comment).clone(),
+            replies: Vec::new(),
+            resolved: resolved.map(|resolved| resolved.get(&comment.id).copied().unwrap_or(false)),
+            outdated: comment.outdated.unwrap_or(false),
+        });
+    }
+    for (root, reply) in replies {
+        if let Some(&position) = index.get(&root) {
+            threads[position].replies.push(reply.clone());
+        }
+    }
+
+    for thread in &mut threads {
+        thread.replies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    }
+    threads.sort_by(|a, b| a.root.created_at.cmp(&b.root.created_at));
+    threads
+}
+
+fn build_reviews(
+    current_login: Option<&str>,
+    reviews: &[GitHubPullRequestReview],
+) -> Vec<PullRequestReview> {
+    let normalized_login = current_login
+        .filter(|login| !login.is_empty())
+        .map(|login| login.to_ascii_lowercase());
+
+    reviews
+        .iter()
+        .map(|review| map_review(review, normalized_login.as_deref()))
+        .collect()
+}
+
+fn map_review(
+    review: &GitHubPullRequestReview,
+    normalized_login: Option<&str>,
+) -> PullRequestReview {
+    let review_author_normalized = review.user.login.to_ascii_lowercase();
+    let is_mine = normalized_login
+        .map(|login| review_author_normalized == login)
+        .unwrap_or(false);
+
+    PullRequestReview {
+        id: review.id,
+        state: review.state.clone(),
+        author: review.user.login.clone(),
+        submitted_at: review.submitted_at.clone(),
+        display_time: review.submitted_at.as_deref().map(display_time),
+        body: review.body.clone(),
+        html_url: review.html_url.clone(),
+        commit_id: review.commit_id.clone(),
+        is_mine,
+    }
+}
+
+fn map_review_comment(comment: &GitHubReviewComment, is_mine: bool, patch: Option<&String>, pr_head_sha: &str) -> PullRequestComment {
+    // Check if this is a PR-level comment (empty path means it's a review body comment, not a file comment)
+    let is_pr_level = comment.path.trim().is_empty();
+    
+    // Check if this is a file-level comment
+    let is_file_level = comment.subject_type.as_deref() == Some("file");
+    
+    // Try to get line number from multiple possible fields, but only if not file-level and not PR-level
+    let mut line = if is_file_level || is_pr_level {
+        None
+    } else {
+        comment.line
+            .or(comment.original_line)
+            .or(comment.start_line)
+            .or(comment.original_start_line)
+    };
+    
+    // If we don't have a line number but we have a position and patch, convert it
+    if line.is_none() && !is_file_level && !is_pr_level {
+        if let (Some(position), Some(patch_text)) = (comment.position.or(comment.original_position), patch) {
+            line = convert_diff_position_to_line(patch_text, position, comment.side.as_deref().unwrap_or("RIGHT"));
+        }
+    }
+    
+    // Determine if comment is outdated by checking if it references an older commit
+    let is_outdated = comment.outdated.or_else(|| {
+        if let Some(commit_id) = &comment.commit_id {
+            if commit_id != pr_head_sha {
+                return Some(true);
+            }
+        }
+        None
+    });
+    
+    PullRequestComment {
+        id: comment.id,
+        body: comment.body.clone(),
+        author: comment.user.login.clone(),
+        created_at: comment.created_at.clone(),
+        display_time: display_time(&comment.created_at),
+        url: comment.html_url.clone(),
+        path: if is_pr_level { None } else { Some(comment.path.clone()) },
+        line,
+        side: comment.side.clone().or(comment.start_side.clone()),
+        is_review_comment: !is_pr_level, // PR-level review comments should be treated as non-review comments
+        is_draft: comment
+            .state
+            .as_deref()
+            .map(|state| state.eq_ignore_ascii_case("pending"))
+            .unwrap_or(false),
+        state: comment.state.clone(),
+        is_mine,
+        review_id: comment.pull_request_review_id,
+        in_reply_to_id: comment.in_reply_to_id,
+        outdated: is_outdated,
+        new_since_last_visit: false,
+        reactions: comment.reactions.clone(),
+    }
+}
+
+/// Converts a diff position to an absolute line number
+/// Position is 1-indexed and counts lines in the diff output
+/// Side is "LEFT" (base) or "RIGHT" (head)
+fn convert_diff_position_to_line(patch: &str, position: u64, side: &str) -> Option<u64> {
+    let mut current_position = 0u64;
+    let mut left_line = 0u64; // Current line in base file
+    let mut right_line = 0u64; // Current line in head file
+    
+    for line in patch.lines() {
+        // Parse hunk headers like: @@ -10,7 +10,8 @@
+        if line.starts_with("@@") {
+            if let Some(header) = parse_hunk_header(line) {
+                left_line = header.0;
+                right_line = header.1;
+            }
+            continue;
+        }
+        
+        // Each line in the diff (except headers) increments position
+        current_position += 1;
+        
+        if line.starts_with('-') {
+            // Deletion: only exists on LEFT side
+            if current_position == position && side == "LEFT" {
+                return Some(left_line);
+            }
+            left_line += 1;
+        } else if line.starts_with('+') {
+            // Addition: only exists on RIGHT side
+            if current_position == position && side == "RIGHT" {
+                return Some(right_line);
+            }
+            right_line += 1;
+        } else {
+            // Context line: exists on both sides
+            if current_position == position {
+                return Some(if side == "LEFT" { left_line } else { right_line });
+            }
+            left_line += 1;
+            right_line += 1;
+        }
+    }
+    
+    None
+}
+
+/// Parses a unified diff hunk header to extract starting line numbers
+/// Format: @@ -start_left,count_left +start_right,count_right @@
+/// Returns (left_start, right_start)
+pub(crate) fn parse_hunk_header(line: &str) -> Option<(u64, u64)> {
+    // Extract the part between @@ and @@
+    let parts: Vec<&str> = line.split("@@").collect();
+    if parts.len() < 2 {
+        return None;
+    }
+    
+    let header = parts[1].trim();
+    let sides: Vec<&str> = header.split_whitespace().collect();
+    if sides.len() < 2 {
+        return None;
+    }
+    
+    // Parse left side: -start,count
+    let left_start = sides[0]
+        .trim_start_matches('-')
+        .split(',')
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    
+    // Parse right side: +start,count
+    let right_start = sides[1]
+        .trim_start_matches('+')
+        .split(',')
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    
+    Some((left_start, right_start))
+}
+
+fn map_issue_comment(comment: &GitHubIssueComment, is_mine: bool) -> PullRequestComment {
+    PullRequestComment {
+        id: comment.id,
+        body: comment.body.clone(),
+        author: comment.user.login.clone(),
+        created_at: comment.created_at.clone(),
+        display_time: display_time(&comment.created_at),
+        url: comment.html_url.clone(),
+        path: None,
+        line: None,
+        side: None,
+        is_review_comment: false,
+        is_draft: false,
+        state: None,
+        is_mine,
+        review_id: None,
+        in_reply_to_id: None,
+        outdated: None,
+        new_since_last_visit: false,
+        reactions: comment.reactions.clone(),
+    }
+}
+
+fn map_review_body_comment(review: &GitHubPullRequestReview, is_mine: bool) -> PullRequestComment {
+    PullRequestComment {
+        id: review.id,
+        body: review.body.clone().unwrap_or_default(),
+        author: review.user.login.clone(),
+        created_at: review.submitted_at.clone().unwrap_or_default(),
+        display_time: review.submitted_at.as_deref().map(display_time).unwrap_or_default(),
+        url: review.html_url.clone().unwrap_or_default(),
+        path: None,
+        line: None,
+        side: None,
+        is_review_comment: false,
+        is_draft: false,
+        state: Some(review.state.clone()),
+        is_mine,
+        review_id: Some(review.id),
+        in_reply_to_id: None,
+        outdated: None,
+        new_since_last_visit: false,
+        reactions: CommentReactions::default(),
+    }
+}
+
+fn detect_language(filename: &str) -> FileLanguage {
+    let lower = filename.to_ascii_lowercase();
+    
+    if lower.ends_with(".yml") || lower.ends_with(".yaml") {
+        "yaml".to_string()
+    } else if lower.ends_with(".md") || lower.ends_with(".markdown") {
+        "markdown".to_string()
+    } else if lower.ends_with(".json") {
+        "json".to_string()
+    } else if lower.ends_with(".js") || lower.ends_with(".jsx") {
+        "javascript".to_string()
+    } else if lower.ends_with(".ts") || lower.ends_with(".tsx") {
+        "typescript".to_string()
+    } else if lower.ends_with(".py") {
+        "python".to_string()
+    } else if lower.ends_with(".rs") {
+        "rust".to_string()
+    } else if lower.ends_with(".go") {
+        "go".to_string()
+    } else if lower.ends_with(".java") {
+        "java".to_string()
+    } else if lower.ends_with(".c") || lower.ends_with(".h") {
+        "c".to_string()
+    } else if lower.ends_with(".cpp") || lower.ends_with(".hpp") || lower.ends_with(".cc") {
+        "cpp".to_string()
+    } else if lower.ends_with(".cs") {
+        "csharp".to_string()
+    } else if lower.ends_with(".rb") {
+        "ruby".to_string()
+    } else if lower.ends_with(".php") {
+        "php".to_string()
+    } else if lower.ends_with(".html") || lower.ends_with(".htm") {
+        "html".to_string()
+    } else if lower.ends_with(".css") {
+        "css".to_string()
+    } else if lower.ends_with(".sh") || lower.ends_with(".bash") {
+        "shell".to_string()
+    } else if lower.ends_with(".xml") {
+        "xml".to_string()
+    } else if lower.ends_with(".sql") {
+        "sql".to_string()
+    } else if is_image_path(&lower) {
+        "image".to_string()
+    } else {
+        // Get extension or use "text" as fallback
+        filename
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_string())
+            .unwrap_or_else(|| "text".to_string())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubUser {
+    pub login: String,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubTimelineEvent {
+    event: Option<String>,
+    created_at: Option<String>,
+    requested_reviewer: Option<GitHubUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubPullRequest {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub updated_at: String,
+    pub head: GitRef,
+    pub base: GitRef,
+    pub user: GitHubUser,
+    pub state: String,
+    pub merged_at: Option<String>,
+    #[serde(default)]
+    pub locked: Option<bool>,
+    /// Number of conversation comments.
+    #[serde(default)]
+    pub comments: Option<u64>,
+    #[serde(default)]
+    pub review_comments: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitRef {
+    pub sha: String,
+    #[serde(rename = "ref")]
+    pub r#ref: String,
+    /// `None` once the fork a PR came from has been deleted.
+    #[serde(default)]
+    pub repo: Option<GitRefRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitRefRepository {
+    pub name: String,
+    pub owner: GitHubUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRepository {
+    pub name: String,
+    pub full_name: String,
+    pub owner: GitHubUser,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub default_branch: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub pushed_at: Option<String>,
+    #[serde(default)]
+    pub permissions: Option<GitHubRepoPermissions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRepoPermissions {
+    #[serde(default)]
+    pub push: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubOrganization {
+    pub login: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubContentEntry {
+    pub name: String,
+    pub path: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubPullRequestFile {
+    pub filename: String,
+    pub status: String,
+    pub additions: u32,
+    pub deletions: u32,
+    pub patch: Option<String>,
+    pub previous_filename: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubApiError {
+    message: Option<String>,
+    documentation_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlRepositoryData {
+    repository: Option<GraphQlRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlRepository {
+    pull_request: Option<GraphQlPullRequestActivity>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequestActivity {
+    thumbs_up: GraphQlCount,
+    thumbs_down: GraphQlCount,
+    hooray: GraphQlCount,
+    participants: GraphQlNodes<GraphQlActor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlReviewState {
+    head_ref_oid: String,
+    state: String,
+    locked: bool,
+    reviews: GraphQlCount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlCount {
+    total_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlNodes<T> {
+    nodes: Vec<Option<T>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlAttention {
+    state: String,
+    author: Option<GraphQlActor>,
+    timeline_items: GraphQlNodes<GraphQlTimelineItem>,
+    review_threads: GraphQlNodes<GraphQlAttentionThread>,
+}
+
+impl GraphQlAttention {
+    fn activity(self) -> Vec<TimelineActivity> {
+        let login = |actor: Option<GraphQlActor>| actor.map(|actor| actor.login);
+        let items = self.timeline_items.nodes.into_iter().flatten().filter_map(|item| {
+            Some(match item {
+                GraphQlTimelineItem::PullRequestReview { author, submitted_at } => {
+                    TimelineActivity::Reviewed { by: login(author)?, at: submitted_at? }
+                }
+                GraphQlTimelineItem::PullRequestCommit { commit } => {
+                    TimelineActivity::Committed { at: commit.committed_date }
+                }
+                GraphQlTimelineItem::ReviewRequestedEvent { created_at, requested_reviewer } => {
+                    TimelineActivity::ReviewRequested {
+                        reviewer: requested_reviewer?.login?,
+                        at: created_at,
+                    }
+                }
+            })
+        });
+        let threads = self.review_threads.nodes.into_iter().flatten().map(|thread| {
+            TimelineActivity::Thread {
+                comments: thread
+                    .comments
+                    .nodes
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|c| Some((login(c.author)?, c.created_at)))
+                    .collect(),
+            }
+        });
+        items.chain(threads).collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "__typename", rename_all_fields = "camelCase")]
+enum GraphQlTimelineItem {
+    PullRequestReview {
+        author: Option<GraphQlActor>,
+        /// `None` while the review is pending.
+        submitted_at: Option<String>,
+    },
+    PullRequestCommit {
+        commit: GraphQlCommitDate,
+    },
+    ReviewRequestedEvent {
+        created_at: String,
+        /// Teams have no login and are left out, as they are not one reviewer.
+        requested_reviewer: Option<GraphQlRequestedReviewer>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlCommitDate {
+    committed_date: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlRequestedReviewer {
+    login: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlAttentionThread {
+    comments: GraphQlNodes<GraphQlAttentionComment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlAttentionComment {
+    author: Option<GraphQlActor>,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlActor {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlMergeabilityData {
+    repository: Option<GraphQlMergeabilityRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlMergeabilityRepository {
+    pull_request: Option<GraphQlMergeability>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlMergeability {
+    mergeable: String,
+    merge_state_status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCompare {
+    ahead_by: u64,
+    behind_by: u64,
+    merge_base_commit: GitHubCommitRef,
+    #[serde(default)]
+    files: Vec<GitHubCompareFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCommitRef {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCompareFile {
+    filename: String,
+    #[serde(default)]
+    previous_filename: Option<String>,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    additions: u64,
+    #[serde(default)]
+    deletions: u64,
+    #[serde(default)]
+    patch: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssue {
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    user: GitHubUser,
+    #[serde(default)]
+    labels: Vec<GitHubLabel>,
+    html_url: String,
+    created_at: String,
+    closed_at: Option<String>,
+    #[serde(default)]
+    comments: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubLabel {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlLinkedIssuesData {
+    repository: Option<GraphQlLinkedIssuesRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlLinkedIssuesRepository {
+    pull_request: Option<GraphQlLinkedIssuesPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlLinkedIssuesPullRequest {
+    closing_issues_references: GraphQlNodes<GraphQlLinkedIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlLinkedIssue {
+    number: u64,
+    title: String,
+    state: String,
+    url: String,
+    repository: GraphQlRepositoryRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlReviewThreadsData {
+    repository: Option<GraphQlReviewThreadsRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlReviewThreadsRepository {
+    pull_request: Option<GraphQlReviewThreadsPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlReviewThreadsPullRequest {
+    review_threads: GraphQlNodes<GraphQlReviewThread>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlReviewThread {
+    is_resolved: bool,
+    comments: GraphQlNodes<GraphQlThreadComment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlThreadComment {
+    database_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlAuthorContextData {
+    repository: Option<GraphQlAuthorRepository>,
+    recent: GraphQlIssueCount,
+    merged: GraphQlIssueCount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlAuthorRepository {
+    pull_request: Option<GraphQlAuthorAssociation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlAuthorAssociation {
+    author_association: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlIssueCount {
+    issue_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlPullRequestSizeData {
+    repository: Option<GraphQlPullRequestSizeRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequestSizeRepository {
+    pull_requests: GraphQlPullRequestSizeConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequestSizeConnection {
+    page_info: GraphQlPageInfo,
+    nodes: Vec<Option<GraphQlPullRequestSize>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequestSize {
+    number: u64,
+    additions: u64,
+    deletions: u64,
+    changed_files: u64,
+    files: Option<GraphQlNodes<GraphQlPullRequestFileSize>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlPullRequestFileSize {
+    path: String,
+    additions: u64,
+    deletions: u64,
 }
 
-/// Parses a unified diff hunk header to extract starting line numbers
-/// Format: @@ -start_left,count_left +start_right,count_right @@
-/// Returns (left_start, right_start)
-fn parse_hunk_header(line: &str) -> Option<(u64, u64)> {
-    // Extract the part between @@ and @@
-    let parts: Vec<&str> = line.split("@@").collect();
-    if parts.len() < 2 {
-        return None;
-    }
-    
-    let header = parts[1].trim();
-    let sides: Vec<&str> = header.split_whitespace().collect();
-    if sides.len() < 2 {
-        return None;
-    }
-    
-    // Parse left side: -start,count
-    let left_start = sides[0]
-        .trim_start_matches('-')
-        .split(',')
-        .next()?
-        .parse::<u64>()
-        .ok()?;
-    
-    // Parse right side: +start,count
-    let right_start = sides[1]
-        .trim_start_matches('+')
-        .split(',')
-        .next()?
-        .parse::<u64>()
-        .ok()?;
-    
-    Some((left_start, right_start))
+#[derive(Debug, Deserialize)]
+struct GraphQlSearchData {
+    search: GraphQlNodes<GraphQlSearchPullRequest>,
 }
 
-fn map_issue_comment(comment: &GitHubIssueComment, is_mine: bool) -> PullRequestComment {
-    PullRequestComment {
-        id: comment.id,
-        body: comment.body.clone(),
-        author: comment.user.login.clone(),
-        created_at: comment.created_at.clone(),
-        url: comment.html_url.clone(),
-        path: None,
-        line: None,
-        side: None,
-        is_review_comment: false,
-        is_draft: false,
-        state: None,
-        is_mine,
-        review_id: None,
-        in_reply_to_id: None,
-        outdated: None,
-    }
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlSearchPullRequest {
+    number: u64,
+    title: String,
+    url: String,
+    created_at: String,
+    changed_files: u64,
+    additions: u64,
+    deletions: u64,
+    author: Option<GraphQlActor>,
+    repository: GraphQlRepositoryRef,
 }
 
-fn map_review_body_comment(review: &GitHubPullRequestReview, is_mine: bool) -> PullRequestComment {
-    PullRequestComment {
-        id: review.id,
-        body: review.body.clone().unwrap_or_default(),
-        author: review.user.login.clone(),
-        created_at: review.submitted_at.clone().unwrap_or_default(),
-        url: review.html_url.clone().unwrap_or_default(),
-        path: None,
-        line: None,
-        side: None,
-        is_review_comment: false,
-        is_draft: false,
-        state: Some(review.state.clone()),
-        is_mine,
-        review_id: Some(review.id),
-        in_reply_to_id: None,
-        outdated: None,
-    }
+#[derive(Debug, Deserialize)]
+struct GraphQlViewerSearchData {
+    viewer: GraphQlActor,
+    search: GraphQlViewerSearchConnection,
 }
 
-fn detect_language(filename: &str) -> FileLanguage {
-    let lower = filename.to_ascii_lowercase();
-    
-    if lower.ends_with(".yml") || lower.ends_with(".yaml") {
-        "yaml".to_string()
-    } else if lower.ends_with(".md") || lower.ends_with(".markdown") {
-        "markdown".to_string()
-    } else if lower.ends_with(".json") {
-        "json".to_string()
-    } else if lower.ends_with(".js") || lower.ends_with(".jsx") {
-        "javascript".to_string()
-    } else if lower.ends_with(".ts") || lower.ends_with(".tsx") {
-        "typescript".to_string()
-    } else if lower.ends_with(".py") {
-        "python".to_string()
-    } else if lower.ends_with(".rs") {
-        "rust".to_string()
-    } else if lower.ends_with(".go") {
-        "go".to_string()
-    } else if lower.ends_with(".java") {
-        "java".to_string()
-    } else if lower.ends_with(".c") || lower.ends_with(".h") {
-        "c".to_string()
-    } else if lower.ends_with(".cpp") || lower.ends_with(".hpp") || lower.ends_with(".cc") {
-        "cpp".to_string()
-    } else if lower.ends_with(".cs") {
-        "csharp".to_string()
-    } else if lower.ends_with(".rb") {
-        "ruby".to_string()
-    } else if lower.ends_with(".php") {
-        "php".to_string()
-    } else if lower.ends_with(".html") || lower.ends_with(".htm") {
-        "html".to_string()
-    } else if lower.ends_with(".css") {
-        "css".to_string()
-    } else if lower.ends_with(".sh") || lower.ends_with(".bash") {
-        "shell".to_string()
-    } else if lower.ends_with(".xml") {
-        "xml".to_string()
-    } else if lower.ends_with(".sql") {
-        "sql".to_string()
-    } else if lower.ends_with(".png") || lower.ends_with(".jpg") || lower.ends_with(".jpeg") || 
-              lower.ends_with(".gif") || lower.ends_with(".svg") || lower.ends_with(".webp") ||
-              lower.ends_with(".bmp") || lower.ends_with(".ico") {
-        "image".to_string()
-    } else {
-        // Get extension or use "text" as fallback
-        filename
-            .rsplit_once('.')
-            .map(|(_, ext)| ext.to_string())
-            .unwrap_or_else(|| "text".to_string())
-    }
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlViewerSearchConnection {
+    page_info: GraphQlPageInfo,
+    nodes: Vec<Option<GraphQlViewerPullRequest>>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct GitHubUser {
-    pub login: String,
-    pub avatar_url: Option<String>,
+struct GraphQlViewerPullRequest {
+    number: u64,
+    title: String,
+    repository: GraphQlRepositoryRef,
 }
 
 #[derive(Debug, Deserialize)]
-struct GitHubPullRequest {
-    pub number: u64,
-    pub title: String,
-    pub body: Option<String>,
-    pub updated_at: String,
-    pub head: GitRef,
-    pub base: GitRef,
-    pub user: GitHubUser,
-    pub state: String,
-    pub merged_at: Option<String>,
-    #[serde(default)]
-    pub locked: Option<bool>,
+struct GraphQlRepositoryRef {
+    name: String,
+    owner: GraphQlActor,
 }
 
 #[derive(Debug, Deserialize)]
-struct GitRef {
-    pub sha: String,
-    #[serde(rename = "ref")]
-    pub r#ref: String,
+struct GraphQlActivity {
+    number: u64,
+    title: String,
+    repository: GraphQlRepositoryRef,
+    reviews: GraphQlNodes<GraphQlActivityReview>,
+    comments: GraphQlNodes<GraphQlActivityComment>,
 }
 
 #[derive(Debug, Deserialize)]
-struct GitHubPullRequestFile {
-    pub filename: String,
-    pub status: String,
-    pub additions: u32,
-    pub deletions: u32,
-    pub patch: Option<String>,
-    pub previous_filename: Option<String>,
+#[serde(rename_all = "camelCase")]
+struct GraphQlActivityReview {
+    author: Option<GraphQlActor>,
+    state: String,
+    url: String,
+    /// `None` while the review is pending.
+    submitted_at: Option<String>,
+    body: String,
+    comments: GraphQlNodes<GraphQlActivityReviewComment>,
 }
 
 #[derive(Debug, Deserialize)]
-struct GitHubApiError {
-    message: Option<String>,
-    documentation_url: Option<String>,
+#[serde(rename_all = "camelCase")]
+struct GraphQlActivityReviewComment {
+    path: String,
+    line: Option<u64>,
+    original_line: Option<u64>,
+    url: String,
+    created_at: String,
+    body: String,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlActivityComment {
+    author: Option<GraphQlActor>,
+    url: String,
+    created_at: String,
+    body: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
 struct GitHubReviewComment {
     pub id: u64,
     pub body: String,
@@ -1465,18 +4445,29 @@ struct GitHubReviewComment {
     pub commit_id: Option<String>,
     #[allow(dead_code)]
     pub subject_type: Option<String>, // "line" or "file" - reserved for future use
+    #[serde(default)]
+    pub reactions: CommentReactions,
 }
 
 #[derive(Debug, Deserialize)]
+struct GitHubReaction {
+    id: u64,
+    /// `None` for deleted accounts.
+    user: Option<GitHubUser>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
 struct GitHubIssueComment {
     pub id: u64,
     pub body: String,
     pub user: GitHubUser,
     pub html_url: String,
     pub created_at: String,
+    #[serde(default)]
+    pub reactions: CommentReactions,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct GitHubPullRequestReview {
     pub id: u64,
     pub state: String,
@@ -1487,6 +4478,31 @@ struct GitHubPullRequestReview {
     pub submitted_at: Option<String>,
 }
 
+/// Sleep for `pause`, re-sending the submission progress with the time left every second
+/// so the UI can count down. `progress` is the comment number, total and file.
+async fn pause_with_countdown(
+    app: &tauri::AppHandle,
+    (current, total, file): (usize, usize, &str),
+    pause: std::time::Duration,
+    kind: RateLimitKind,
+) {
+    let deadline = tokio::time::Instant::now() + pause;
+    loop {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        let _ = app.emit("comment-submit-progress", serde_json::json!({
+            "current": current,
+            "total": total,
+            "file": file,
+            "waitTimeMs": remaining.as_millis() as u64,
+            "waitReason": kind.as_str(),
+        }));
+        if remaining.is_zero() {
+            break;
+        }
+        tokio::time::sleep(remaining.min(std::time::Duration::from_secs(1))).await;
+    }
+}
+
 pub async fn create_review_with_comments(
     app: &tauri::AppHandle,
     token: &str,
@@ -1497,7 +4513,7 @@ pub async fn create_review_with_comments(
     _body: Option<&str>,
     _event: Option<&str>,
     comments: &[crate::review_storage::ReviewComment],
-) -> AppResult<(Vec<i64>, Option<String>)> {
+) -> AppResult<CommentBatch> {
     fn is_submitted_too_quickly(body: &str) -> bool {
         // GitHub returns a 422 Validation Failed payload like:
         // {"message":"Validation Failed","errors":[{"field":"pull_request_review_thread.base","message":"was submitted too quickly"}],...}
@@ -1540,7 +4556,7 @@ pub async fn create_review_with_comments(
     // `pull_request_review_thread.issue` = "is locked".
     let pr_meta = client
         .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}"))
-        .send()
+        .send_recorded()
         .await?;
 
     let pr_meta = ensure_success(pr_meta, &format!("fetch PR metadata for {owner}/{repo}#{number}")).await?;
@@ -1551,20 +4567,22 @@ pub async fn create_review_with_comments(
         .unwrap_or(false);
 
     if is_locked {
-        return Ok((
-            Vec::new(),
-            Some(format!(
-                "Cannot submit review comments because this PR conversation is locked on GitHub. \
+        let message = format!(
+            "Cannot submit review comments because this PR conversation is locked on GitHub. \
 Re-opening the PR does not unlock a locked conversation. \
 Ask a repo maintainer to use \"Unlock conversation\" on PR #{number}, then retry."
-            )),
-        ));
+        );
+        return Ok(CommentBatch {
+            succeeded_ids: Vec::new(),
+            failed: submission_report::unposted(comments, "locked", &message),
+            summary: Some(message),
+        });
     }
     
     // Get the PR file list to validate comments
     let pr_files_response = client
         .get(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/files"))
-        .send()
+        .send_recorded()
         .await;
     
     if let Ok(files_resp) = pr_files_response {
@@ -1585,7 +4603,7 @@ Ask a repo maintainer to use \"Unlock conversation\" on PR #{number}, then retry
     
     let mut succeeded = 0;
     let mut failed = 0;
-    let mut errors = Vec::new();
+    let mut failures: Vec<FailedComment> = Vec::new();
     let mut succeeded_ids = Vec::new();
     let mut wait_time_ms: u64 = 0;
 
@@ -1593,6 +4611,10 @@ Ask a repo maintainer to use \"Unlock conversation\" on PR #{number}, then retry
     // 422 Validation Failed, pull_request_review_thread.base: "was submitted too quickly".
     // A small pacing delay + targeted retry dramatically improves success rates for large batches.
     const MIN_REQUEST_SPACING_MS: u64 = 1200;
+    // After a secondary rate limit the rest of the batch is paced more slowly.
+    const MAX_REQUEST_SPACING_MS: u64 = 10_000;
+    const RATE_LIMIT_MAX_RETRIES: u32 = 5;
+    let mut request_spacing_ms = MIN_REQUEST_SPACING_MS;
     const TOO_QUICK_MAX_RETRIES: usize = 6;
     const TOO_QUICK_BASE_BACKOFF_MS: u64 = 1200;
     const TOO_QUICK_MAX_BACKOFF_MS: u64 = 20_000;
@@ -1601,11 +4623,29 @@ Ask a repo maintainer to use \"Unlock conversation\" on PR #{number}, then retry
     // Submit each comment individually, continuing even if some fail
     'outer: for (index, comment) in comments.iter().enumerate() {
         let mut comment_obj = Map::new();
-        comment_obj.insert("body".into(), Value::String(comment.body.clone()));
+        comment_obj.insert("body".into(), Value::String(stored_comment_body(comment)));
         comment_obj.insert("commit_id".into(), Value::String(commit_id.to_string()));
         comment_obj.insert("path".into(), Value::String(comment.file_path.clone()));
+        // A reply to a GitHub comment joins its thread. A reply to another comment of this
+        // batch has no GitHub id to point at yet, so it starts a thread of its own.
+        if let Some(parent) = comment
+            .in_reply_to_id
+            .filter(|parent| !comments.iter().any(|c| c.id == *parent))
+        {
+            comment_obj.insert("in_reply_to".into(), Value::Number(parent.into()));
+        }
 
-        if comment.line_number == 0 {
+        if let Some(suggestion) = &comment.suggestion {
+            if let Err(err) = insert_suggestion_range(&mut comment_obj, suggestion, &comment.side) {
+                failed += 1;
+                failures.push(FailedComment::with_code(comment.id, "invalid_side", err.to_string()));
+                continue;
+            }
+            debug!(
+                "Posting suggestion to {}:{}-{}",
+                comment.file_path, suggestion.start_line, suggestion.end_line
+            );
+        } else if comment.line_number == 0 {
             comment_obj.insert("subject_type".into(), Value::String("file".to_string()));
             debug!("Posting file-level comment to {}", comment.file_path);
         } else {
@@ -1632,26 +4672,27 @@ Ask a repo maintainer to use \"Unlock conversation\" on PR #{number}, then retry
         }));
 
         let mut attempt = 0usize;
-        let mut response = loop {
+        let mut rate_limit_attempt = 0u32;
+        let response = loop {
             // Respect any wait time determined from previous API response.
             if wait_time_ms > 0 {
                 if wait_time_ms > 1000 {
                     warn!("Pausing for {}ms due to rate limit", wait_time_ms);
-                    let _ = app.emit("comment-submit-progress", serde_json::json!({
-                        "current": index + 1,
-                        "total": total,
-                        "file": comment.file_path,
-                        "waitTimeMs": wait_time_ms,
-                    }));
                 }
-                tokio::time::sleep(tokio::time::Duration::from_millis(wait_time_ms)).await;
+                pause_with_countdown(
+                    app,
+                    (index + 1, total, &comment.file_path),
+                    std::time::Duration::from_millis(wait_time_ms),
+                    RateLimitKind::Primary,
+                )
+                .await;
                 wait_time_ms = 0;
             }
 
             // Always pace requests to avoid GitHub 422 "submitted too quickly" errors.
             if let Some(last) = last_request_started_at {
                 let elapsed = last.elapsed();
-                let min = tokio::time::Duration::from_millis(MIN_REQUEST_SPACING_MS);
+                let min = tokio::time::Duration::from_millis(request_spacing_ms);
                 if elapsed < min {
                     tokio::time::sleep(min - elapsed).await;
                 }
@@ -1661,7 +4702,7 @@ Ask a repo maintainer to use \"Unlock conversation\" on PR #{number}, then retry
             let resp = match client
                 .post(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"))
                 .json(&comment_payload)
-                .send()
+                .send_recorded()
                 .await
             {
                 Ok(resp) => resp,
@@ -1672,11 +4713,75 @@ Ask a repo maintainer to use \"Unlock conversation\" on PR #{number}, then retry
                         comment.file_path, comment.line_number, e
                     );
                     warn!("✗ {}", error_msg);
-                    errors.push(error_msg);
+                    failures.push(FailedComment::with_code(comment.id, "network", error_msg));
                     continue 'outer;
                 }
             };
 
+            // A revoked token fails every remaining comment; stop and keep them for later.
+            if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+                expire_session(&format!("add review comment to {owner}/{repo}#{number}"));
+                let message = AppError::AuthExpired.to_string();
+                failures.extend(submission_report::unposted(&comments[index..], "auth_expired", &message));
+                return Ok(CommentBatch {
+                    succeeded_ids,
+                    failed: failures,
+                    summary: Some(message),
+                });
+            }
+
+            // Rate limits pause the whole submission and then retry the same comment.
+            if resp.status() == reqwest::StatusCode::FORBIDDEN
+                || resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+            {
+                let status = resp.status();
+                let headers = resp.headers().clone();
+                let body = resp.text().await.unwrap_or_default();
+                match rate_limit::classify(status, &headers, &body) {
+                    Some(kind) if rate_limit_attempt < RATE_LIMIT_MAX_RETRIES => {
+                        let pause = rate_limit::pause_for(kind, &headers, rate_limit_attempt, chrono::Utc::now());
+                        warn!(
+                            kind = kind.as_str(),
+                            status = status.as_u16(),
+                            pause_secs = pause.as_secs(),
+                            body_snippet = %body_snippet(&body, LOG_BODY_SNIPPET_CHARS),
+                            "GitHub rate limit hit; pausing submission"
+                        );
+                        if kind == RateLimitKind::Secondary {
+                            request_spacing_ms = (request_spacing_ms * 2).min(MAX_REQUEST_SPACING_MS);
+                        }
+                        pause_with_countdown(app, (index + 1, total, &comment.file_path), pause, kind).await;
+                        rate_limit_attempt += 1;
+                        continue;
+                    }
+                    Some(kind) => {
+                        failed += 1;
+                        let error_msg = format!(
+                            "Failed to post comment to {}:{} - still hitting the GitHub {} after {} pauses",
+                            comment.file_path,
+                            comment.line_number,
+                            kind.as_str().replace('_', " "),
+                            rate_limit_attempt
+                        );
+                        warn!("✗ {}", error_msg);
+                        failures.push(FailedComment::new(comment.id, error_msg));
+                        continue 'outer;
+                    }
+                    None => {
+                        failed += 1;
+                        let error_msg = format!(
+                            "Forbidden when posting comment to {}:{} - body: {}",
+                            comment.file_path,
+                            comment.line_number,
+                            body_snippet(&body, ERROR_BODY_SNIPPET_CHARS)
+                        );
+                        warn!("✗ {}", error_msg);
+                        failures.push(FailedComment::new(comment.id, error_msg));
+                        continue 'outer;
+                    }
+                }
+            }
+
             // Intercept 422 too-fast errors here, so we can retry the same comment.
             if resp.status() == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
                 let body = resp.text().await.unwrap_or_default();
@@ -1694,7 +4799,12 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                         body_snippet = %body_snippet(&body, ERROR_BODY_SNIPPET_CHARS),
                         "PR conversation is locked; aborting comment submission"
                     );
-                    return Ok((succeeded_ids, Some(msg)));
+                    failures.extend(submission_report::unposted(&comments[index..], "locked", &msg));
+                    return Ok(CommentBatch {
+                        succeeded_ids,
+                        failed: failures,
+                        summary: Some(msg),
+                    });
                 }
 
                 if is_submitted_too_quickly(&body) {
@@ -1734,7 +4844,7 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                         comment.file_path, comment.line_number, response_body_copy
                     );
                     warn!("✗ {}", error_msg);
-                    errors.push(error_msg);
+                    failures.push(FailedComment::new(comment.id, error_msg));
                     continue 'outer;
                 }
 
@@ -1764,7 +4874,7 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                         let retry_response = client
                             .post(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"))
                             .json(&file_comment_payload)
-                            .send()
+                            .send_recorded()
                             .await;
                         
                         match retry_response {
@@ -1810,7 +4920,7 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                                 comment.file_path, comment.line_number, err
                             );
                             warn!("✗ Failed: {}", error_msg);
-                            errors.push(error_msg);
+                            failures.push(FailedComment::new(comment.id, error_msg));
                         }
                     }
                     
@@ -1825,84 +4935,16 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                     comment.file_path, comment.line_number, response_body_copy
                 );
                 warn!("✗ {}", error_msg);
-                errors.push(error_msg);
+                failures.push(FailedComment::new(comment.id, error_msg));
                 continue 'outer;
             }
 
             break resp;
         };
 
-        let mut cloned_headers = response.headers().clone();
+        let cloned_headers = response.headers().clone();
         let status = response.status();
 
-        if status == reqwest::StatusCode::FORBIDDEN {
-            let body = response.text().await.unwrap_or_default();
-            let retry_after_header = cloned_headers
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|s| s.parse::<u64>().ok());
-
-            if body.to_lowercase().contains("rate limit") {
-                warn!(
-                    body_len = body.len(),
-                    body_snippet = %body_snippet(&body, LOG_BODY_SNIPPET_CHARS),
-                    "GitHub rate limit exceeded (403 Forbidden)"
-                );
-
-                let mut pause_ms = if let Some(secs) = retry_after_header {
-                    warn!("Pausing for Retry-After header: waiting {} seconds", secs);
-                    secs * 1000
-                } else if let Some(reset) = cloned_headers
-                    .get("x-ratelimit-reset")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| s.parse::<u64>().ok())
-                {
-                    let now = chrono::Utc::now().timestamp() as u64;
-                    let wait_secs = reset.saturating_sub(now);
-                    warn!("Pausing for X-RateLimit-Reset after 403: waiting {} seconds until {}", wait_secs, reset);
-                    wait_secs * 1000
-                } else {
-                    warn!("No Retry-After or Reset header found on 403; defaulting to 60s pause");
-                    60_000
-                };
-
-                if pause_ms == 0 {
-                    pause_ms = 1000;
-                }
-
-                tokio::time::sleep(tokio::time::Duration::from_millis(pause_ms)).await;
-
-                response = match client
-                    .post(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"))
-                    .json(&comment_payload)
-                    .send()
-                    .await {
-                    Ok(resp) => resp,
-                    Err(e) => {
-                        failed += 1;
-                        let error_msg = format!("Failed to post comment to {}:{} after rate limit wait - {}", comment.file_path, comment.line_number, e);
-                        warn!("✗ {}", error_msg);
-                        errors.push(error_msg);
-                        continue;
-                    }
-                };
-
-                cloned_headers = response.headers().clone();
-            } else {
-                failed += 1;
-                let user_snippet = body_snippet(&body, ERROR_BODY_SNIPPET_CHARS);
-                let error_msg = format!(
-                    "Forbidden when posting comment to {}:{} - body: {}",
-                    comment.file_path,
-                    comment.line_number,
-                    user_snippet
-                );
-                warn!("✗ {}", error_msg);
-                errors.push(error_msg);
-                continue;
-            }
-        }
-
         let limit = cloned_headers
             .get("x-ratelimit-limit")
             .and_then(|v| v.to_str().ok())
@@ -1985,7 +5027,7 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
             let retry_response = client
                 .post(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/comments"))
                 .json(&file_comment_payload)
-                .send()
+                .send_recorded()
                 .await;
             
             match retry_response {
@@ -2008,7 +5050,7 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                                 comment.file_path, comment.line_number, retry_err
                             );
                             warn!("✗ {}", error_msg);
-                            errors.push(error_msg);
+                            failures.push(FailedComment::new(comment.id, error_msg));
                         }
                     }
                 }
@@ -2019,7 +5061,7 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                         comment.file_path, comment.line_number, retry_err
                     );
                     warn!("✗ {}", error_msg);
-                    errors.push(error_msg);
+                    failures.push(FailedComment::new(comment.id, error_msg));
                 }
             }
         } else {
@@ -2030,105 +5072,17 @@ Re-opening the PR does not unlock the conversation; a maintainer must unlock it.
                 comment.file_path, comment.line_number, status, response_body_copy
             );
             warn!("✗ {}", error_msg);
-            errors.push(error_msg);
+            failures.push(FailedComment::new(comment.id, error_msg));
         }
     }
     
     info!("Submission complete: {} succeeded, {} failed", succeeded, failed);
     
-    if failed > 0 {
-        // Analyze first error to provide specific guidance
-        let (error_explanation, fix_suggestion) = if let Some(first_error) = errors.first() {
-            let err_lower = first_error.to_lowercase();
-            if err_lower.contains("pull_request_review_thread.line") && err_lower.contains("could not be resolved") {
-                (
-                    "Line numbers are not in the PR diff.",
-                    "These lines weren't changed in the PR. Refresh the PR to see if files were updated, or comment on changed lines only."
-                )
-            } else if err_lower.contains("was submitted too quickly") || err_lower.contains("pull_request_review_thread.base") && err_lower.contains("submitted too quickly") {
-                (
-                    "Comments submitted too quickly (rate limiting).",
-                    "GitHub is rate limiting comment submissions. Wait a moment and try again, or submit fewer comments at once."
-                )
-            } else if err_lower.contains("is locked") || err_lower.contains("pull_request_review_thread.issue") && err_lower.contains("locked") {
-                (
-                    "PR conversation is locked.",
-                    "Ask a repo maintainer to unlock the conversation on this PR before submitting comments."
-                )
-            } else if err_lower.contains("403") || err_lower.contains("forbidden") {
-                (
-                    "Permission denied or rate limit exceeded.",
-                    "Check your token has 'repo' scope. If rate limited, wait for rate limit reset (check X-RateLimit-Reset header)."
-                )
-            } else if err_lower.contains("401") || err_lower.contains("unauthorized") {
-                (
-                    "Authentication failed.",
-                    "Your GitHub token may be invalid or expired. Generate a new personal access token with 'repo' scope."
-                )
-            } else if err_lower.contains("not found") || err_lower.contains("404") {
-                (
-                    "File or PR not found.",
-                    "The file may have been deleted or the PR may have been closed. Refresh the PR to see current files."
-                )
-            } else if err_lower.contains("path") && err_lower.contains("invalid") {
-                (
-                    "Invalid file path.",
-                    "The file path doesn't exist in the PR. File may have been renamed or deleted. Refresh the PR."
-                )
-            } else if err_lower.contains("commit_id") || (err_lower.contains("commit") && err_lower.contains("outdated")) {
-                (
-                    "Commit ID is outdated.",
-                    "The PR was updated after you created comments. Refresh the PR to get the latest commit SHA."
-                )
-            } else if err_lower.contains("review comments is invalid") || err_lower.contains("review threads is invalid") {
-                (
-                    "Review API rate limiting or spam detection.",
-                    "GitHub detected too many review API calls. Wait a few moments before submitting more comments."
-                )
-            } else if err_lower.contains("side") && err_lower.contains("invalid") {
-                (
-                    "Invalid side parameter.",
-                    "The 'side' must be 'LEFT' (deletions) or 'RIGHT' (additions). Check your comment configuration."
-                )
-            } else if err_lower.contains("body") && (err_lower.contains("blank") || err_lower.contains("empty")) {
-                (
-                    "Comment body is empty.",
-                    "Comment text cannot be blank. Make sure all comments have content."
-                )
-            } else {
-                (
-                    "Comment submission failed.",
-                    "Check the logs for details. Common issues: PR updates, file changes, or API errors."
-                )
-            }
-        } else {
-            (
-                "Comment submission failed.",
-                "Check the logs for details."
-            )
-        };
-
-        let error_summary = if succeeded > 0 {
-            format!(
-                "Submitted {} of {} comments. {} failed.\n\n\
-                💡 Issue: {}\n\
-                To fix: {}",
-                succeeded, comments.len(), failed, error_explanation, fix_suggestion
-            )
-        } else {
-            format!(
-                "Failed to submit all {} comments.\n\n\
-                💡 Issue: {}\n\
-                To fix: {}",
-                comments.len(), error_explanation, fix_suggestion
-            )
-        };
-        // Return succeeded_ids along with error message
-        Ok((succeeded_ids, Some(error_summary)))
-    } else {
-        // All succeeded, no error message
-        Ok((succeeded_ids, None))
-    }
+    Ok(CommentBatch {
+        summary: submission_report::summarize(succeeded, comments.len(), &failures),
+        succeeded_ids,
+        failed: failures,
+    })
 }
 
 pub async fn fetch_file_content(
@@ -2143,7 +5097,7 @@ pub async fn fetch_file_content(
     let response = client
         .get(format!("{API_BASE}/repos/{owner}/{repo}/contents/{path}"))
         .query(&[("ref", reference)])
-        .send()
+        .send_recorded()
         .await?;
     
     let status = response.status();
@@ -2192,7 +5146,7 @@ pub async fn delete_review(
     
     let response = client
         .delete(format!("{API_BASE}/repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}"))
-        .send()
+        .send_recorded()
         .await?;
     
     ensure_success(
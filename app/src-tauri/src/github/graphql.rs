@@ -0,0 +1,487 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+
+use super::{
+    ensure_success, map_linked_issue, GitHubIssueComment, GitHubPullRequest,
+    GitHubPullRequestReview, GitHubReviewComment, GitHubUser, GitRef, GitRefRepository,
+    GraphQlActor, GraphQlCount, GraphQlLinkedIssue, GraphQlMergeability, GraphQlNodes,
+    GraphQlRepositoryRef, PullRequestParts, API_BASE,
+};
+use crate::error::{AppError, AppResult};
+use crate::models::{CommentReactions, ReactionSummary};
+use crate::oauth_scopes;
+use crate::recorder::RecordedSend;
+
+/// POST a GraphQL query and return its `data`. GraphQL reports most failures with a 200
+/// status and an `errors` array, so both are checked.
+pub(super) async fn query<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    query: &str,
+    variables: Value,
+    context: &str,
+) -> AppResult<T> {
+    run(client, query, variables, context, false).await
+}
+
+/// `query` for a batch of aliased lookups, where a repository or pull request that no
+/// longer exists or cannot be seen comes back as `null` rather than failing the batch.
+pub(super) async fn query_batch<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    query: &str,
+    variables: Value,
+    context: &str,
+) -> AppResult<T> {
+    run(client, query, variables, context, true).await
+}
+
+async fn run<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    query: &str,
+    variables: Value,
+    context: &str,
+    allow_not_found: bool,
+) -> AppResult<T> {
+    let response = client
+        .post(format!("{API_BASE}/graphql"))
+        .json(&json!({ "query": query, "variables": variables }))
+        .send_recorded()
+        .await?;
+    let response = ensure_success(response, context).await?;
+    let mut payload: Value = response.json().await?;
+
+    if let Some(errors) = payload.get("errors").and_then(Value::as_array) {
+        if let Some(scope) = errors
+            .iter()
+            .filter(|e| e.get("type").and_then(Value::as_str) == Some("INSUFFICIENT_SCOPES"))
+            .filter_map(|e| e.get("message").and_then(Value::as_str))
+            .find_map(oauth_scopes::graphql_missing_scope)
+        {
+            oauth_scopes::record(context, &scope);
+            return Err(AppError::MissingScope(scope));
+        }
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter(|e| {
+                !(allow_not_found && e.get("type").and_then(Value::as_str) == Some("NOT_FOUND"))
+            })
+            .filter_map(|e| e.get("message").and_then(Value::as_str))
+            .collect();
+        if !messages.is_empty() {
+            return Err(AppError::Api(format!("{context} failed: {}", messages.join("; "))));
+        }
+    }
+
+    Ok(serde_json::from_value(payload["data"].take())?)
+}
+
+
+/// A pull request and everything shown with it apart from its files (GraphQL has no
+/// patches) and the author's history (which needs the author's login). Each list asks for
+/// one page of 100, the most GitHub returns at once.
+const PULL_REQUEST_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      number title body updatedAt state mergedAt locked
+      headRefOid headRefName baseRefOid baseRefName
+      headRepository { name owner { login } }
+      author { login avatarUrl }
+      mergeable mergeStateStatus
+      thumbsUp: reactions(content: THUMBS_UP) { totalCount }
+      thumbsDown: reactions(content: THUMBS_DOWN) { totalCount }
+      hooray: reactions(content: HOORAY) { totalCount }
+      participants(first: 100) { nodes { login } }
+      closingIssuesReferences(first: 25) {
+        nodes { number title state url repository { name owner { login } } }
+      }
+      comments(first: 100) {
+        totalCount
+        nodes {
+          fullDatabaseId body url createdAt author { login avatarUrl }
+          reactionGroups { content reactors { totalCount } }
+        }
+      }
+      reviews(first: 100) {
+        totalCount
+        nodes {
+          fullDatabaseId state body url submittedAt
+          commit { oid }
+          author { login avatarUrl }
+        }
+      }
+      reviewThreads(first: 100) {
+        totalCount
+        nodes {
+          isResolved diffSide startDiffSide subjectType
+          comments(first: 100) {
+            totalCount
+            nodes {
+              fullDatabaseId body path url state createdAt outdated
+              line originalLine startLine originalStartLine originalPosition
+              commit { oid }
+              author { login avatarUrl }
+              pullRequestReview { fullDatabaseId }
+              replyTo { fullDatabaseId }
+              reactionGroups { content reactors { totalCount } }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// Everything but the files of a pull request from one query, or `None` when one of its
+/// lists runs past a page and has to be paged through over REST instead.
+pub(super) async fn fetch_pull_request(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> AppResult<Option<PullRequestParts>> {
+    let data: GraphQlPullRequestData = query(
+        client,
+        PULL_REQUEST_QUERY,
+        json!({ "owner": owner, "repo": repo, "number": number }),
+        &format!("fetch pull request {owner}/{repo}#{number}"),
+    )
+    .await?;
+    Ok(data
+        .repository
+        .and_then(|r| r.pull_request)
+        .and_then(into_parts))
+}
+
+fn is_complete<T>(page: &GraphQlPage<T>) -> bool {
+    page.nodes.len() >= page.total_count
+}
+
+/// Deleted accounts come back as a null author; REST shows them as `ghost`.
+fn user(author: Option<GraphQlAuthor>) -> GitHubUser {
+    match author {
+        Some(author) => GitHubUser {
+            login: author.login,
+            avatar_url: author.avatar_url,
+        },
+        None => GitHubUser {
+            login: "ghost".to_string(),
+            avatar_url: None,
+        },
+    }
+}
+
+/// Reaction groups as REST's per-comment rollup.
+fn reactions(groups: Vec<GraphQlReactionGroup>) -> CommentReactions {
+    let mut reactions = CommentReactions::default();
+    for group in groups {
+        let count = group.reactors.total_count;
+        match group.content.as_str() {
+            "THUMBS_UP" => reactions.thumbs_up = count,
+            "THUMBS_DOWN" => reactions.thumbs_down = count,
+            "LAUGH" => reactions.laugh = count,
+            "HOORAY" => reactions.hooray = count,
+            "CONFUSED" => reactions.confused = count,
+            "HEART" => reactions.heart = count,
+            "ROCKET" => reactions.rocket = count,
+            "EYES" => reactions.eyes = count,
+            _ => {}
+        }
+    }
+    reactions
+}
+
+/// The PR in the shapes the REST endpoints return, so both paths share the mapping code.
+fn into_parts(pr: GraphQlPullRequest) -> Option<PullRequestParts> {
+    let complete = is_complete(&pr.comments)
+        && is_complete(&pr.reviews)
+        && is_complete(&pr.review_threads)
+        && pr.review_threads.nodes.iter().flatten().all(|t| is_complete(&t.comments));
+    if !complete {
+        return None;
+    }
+
+    let threads: Vec<GraphQlThread> = pr.review_threads.nodes.into_iter().flatten().collect();
+
+    let review_comment_count = threads.iter().map(|t| t.comments.total_count as u64).sum();
+    let mut resolved = HashMap::new();
+    let mut review_comments = Vec::new();
+    for thread in threads {
+        let comments: Vec<GraphQlReviewComment> =
+            thread.comments.nodes.into_iter().flatten().collect();
+        if let Some(root) = comments.first() {
+            resolved.insert(root.full_database_id, thread.is_resolved);
+        }
+        let subject_type = thread.subject_type.map(|kind| kind.to_ascii_lowercase());
+        review_comments.extend(
+            comments
+                .into_iter()
+                // The viewer's unsubmitted comments, which the REST list leaves out too
+                .filter(|comment| comment.state != "PENDING")
+                .map(|comment| GitHubReviewComment {
+                    id: comment.full_database_id,
+                    body: comment.body,
+                    path: comment.path,
+                    line: comment.line,
+                    original_line: comment.original_line,
+                    original_position: comment.original_position,
+                    position: None,
+                    start_line: comment.start_line,
+                    original_start_line: comment.original_start_line,
+                    side: thread.diff_side.clone(),
+                    start_side: thread.start_diff_side.clone(),
+                    user: user(comment.author),
+                    html_url: comment.url,
+                    state: None,
+                    created_at: comment.created_at,
+                    pull_request_review_id: comment.pull_request_review.map(|r| r.full_database_id),
+                    in_reply_to_id: comment.reply_to.map(|r| r.full_database_id),
+                    outdated: Some(comment.outdated),
+                    commit_id: comment.commit.map(|c| c.oid),
+                    subject_type: subject_type.clone(),
+                    reactions: reactions(comment.reaction_groups),
+                }),
+        );
+    }
+
+    let issue_comments = pr
+        .comments
+        .nodes
+        .into_iter()
+        .flatten()
+        .map(|comment| GitHubIssueComment {
+            id: comment.full_database_id,
+            body: comment.body,
+            user: user(comment.author),
+            html_url: comment.url,
+            created_at: comment.created_at,
+            reactions: reactions(comment.reaction_groups),
+        })
+        .collect::<Vec<_>>();
+    let reviews = pr
+        .reviews
+        .nodes
+        .into_iter()
+        .flatten()
+        .map(|review| GitHubPullRequestReview {
+            id: review.full_database_id,
+            state: review.state,
+            user: user(review.author),
+            body: Some(review.body),
+            html_url: Some(review.url),
+            commit_id: review.commit.map(|c| c.oid),
+            submitted_at: review.submitted_at,
+        })
+        .collect();
+
+    Some(PullRequestParts {
+        pr: GitHubPullRequest {
+            number: pr.number,
+            title: pr.title,
+            body: Some(pr.body).filter(|body| !body.is_empty()),
+            updated_at: pr.updated_at,
+            head: GitRef {
+                sha: pr.head_ref_oid,
+                r#ref: pr.head_ref_name,
+                repo: pr.head_repository.map(|repository| GitRefRepository {
+                    name: repository.name,
+                    owner: GitHubUser {
+                        login: repository.owner.login,
+                        avatar_url: None,
+                    },
+                }),
+            },
+            base: GitRef {
+                sha: pr.base_ref_oid,
+                r#ref: pr.base_ref_name,
+                repo: None,
+            },
+            user: user(pr.author),
+            // REST has no merged state; merged PRs are closed ones with `merged_at`
+            state: match pr.state.as_str() {
+                "OPEN" => "open".to_string(),
+                _ => "closed".to_string(),
+            },
+            merged_at: pr.merged_at,
+            locked: Some(pr.locked),
+            comments: Some(issue_comments.len() as u64),
+            review_comments: Some(review_comment_count),
+        },
+        review_comments,
+        issue_comments,
+        reviews,
+        resolved: Some(resolved),
+        reactions: ReactionSummary {
+            thumbs_up: pr.thumbs_up.total_count,
+            thumbs_down: pr.thumbs_down.total_count,
+            hooray: pr.hooray.total_count,
+        },
+        participant_logins: pr
+            .participants
+            .nodes
+            .into_iter()
+            .flatten()
+            .map(|actor| actor.login)
+            .collect(),
+        linked_issues: pr
+            .closing_issues_references
+            .nodes
+            .into_iter()
+            .flatten()
+            .map(map_linked_issue)
+            .collect(),
+        mergeability: Some(GraphQlMergeability {
+            mergeable: pr.mergeable,
+            merge_state_status: pr.merge_state_status,
+        }),
+    })
+}
+
+/// Database ids past 32 bits only fit GraphQL's `BigInt`, which is sent as a string.
+fn big_int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BigInt {
+        Number(u64),
+        Text(String),
+    }
+    match BigInt::deserialize(deserializer)? {
+        BigInt::Number(number) => Ok(number),
+        BigInt::Text(text) => text.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlPullRequestData {
+    repository: Option<GraphQlPullRequestRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequestRepository {
+    pull_request: Option<GraphQlPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequest {
+    number: u64,
+    title: String,
+    body: String,
+    updated_at: String,
+    state: String,
+    merged_at: Option<String>,
+    locked: bool,
+    head_ref_oid: String,
+    head_ref_name: String,
+    /// `None` once the fork the PR came from has been deleted.
+    head_repository: Option<GraphQlRepositoryRef>,
+    base_ref_oid: String,
+    base_ref_name: String,
+    author: Option<GraphQlAuthor>,
+    mergeable: String,
+    merge_state_status: String,
+    thumbs_up: GraphQlCount,
+    thumbs_down: GraphQlCount,
+    hooray: GraphQlCount,
+    participants: GraphQlNodes<GraphQlActor>,
+    closing_issues_references: GraphQlNodes<GraphQlLinkedIssue>,
+    comments: GraphQlPage<GraphQlIssueComment>,
+    reviews: GraphQlPage<GraphQlReview>,
+    review_threads: GraphQlPage<GraphQlThread>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPage<T> {
+    total_count: usize,
+    nodes: Vec<Option<T>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlAuthor {
+    login: String,
+    avatar_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlCommit {
+    oid: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlIssueComment {
+    #[serde(deserialize_with = "big_int")]
+    full_database_id: u64,
+    body: String,
+    url: String,
+    created_at: String,
+    author: Option<GraphQlAuthor>,
+    #[serde(default)]
+    reaction_groups: Vec<GraphQlReactionGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlReactionGroup {
+    content: String,
+    reactors: GraphQlCount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlReview {
+    #[serde(deserialize_with = "big_int")]
+    full_database_id: u64,
+    state: String,
+    body: String,
+    url: String,
+    submitted_at: Option<String>,
+    commit: Option<GraphQlCommit>,
+    author: Option<GraphQlAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlThread {
+    is_resolved: bool,
+    diff_side: Option<String>,
+    start_diff_side: Option<String>,
+    subject_type: Option<String>,
+    comments: GraphQlPage<GraphQlReviewComment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlReviewComment {
+    #[serde(deserialize_with = "big_int")]
+    full_database_id: u64,
+    body: String,
+    path: String,
+    url: String,
+    state: String,
+    created_at: String,
+    outdated: bool,
+    line: Option<u64>,
+    original_line: Option<u64>,
+    start_line: Option<u64>,
+    original_start_line: Option<u64>,
+    original_position: Option<u64>,
+    commit: Option<GraphQlCommit>,
+    author: Option<GraphQlAuthor>,
+    pull_request_review: Option<GraphQlDatabaseRef>,
+    reply_to: Option<GraphQlDatabaseRef>,
+    #[serde(default)]
+    reaction_groups: Vec<GraphQlReactionGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlDatabaseRef {
+    #[serde(deserialize_with = "big_int")]
+    full_database_id: u64,
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_import::ImportedComment;
+use crate::review_storage::{ReviewComment, ReviewMetadata, ReviewStorage};
+
+pub const HANDOFF_FORMAT: &str = "github-review-handoff";
+pub const HANDOFF_VERSION: u32 = 1;
+
+/// Lines kept on each side of a commented line to find it again after the file changes.
+const ANCHOR_CONTEXT_LINES: usize = 2;
+
+/// Text around a commented line at the commit it was written against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineAnchor {
+    pub line_text: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandoffComment {
+    /// Id in the exporter's database; only used to rebuild reply threads on import.
+    pub id: i64,
+    pub file_path: String,
+    pub line_number: u64,
+    pub side: String,
+    pub body: String,
+    pub commit_id: String,
+    pub in_reply_to_id: Option<i64>,
+    #[serde(default)]
+    pub category: Option<String>,
+    pub anchor: Option<LineAnchor>,
+}
+
+/// A local review packaged so another reviewer can import it and carry on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewHandoff {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub exported_by: Option<String>,
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    pub commit_id: String,
+    pub body: Option<String>,
+    pub comments: Vec<HandoffComment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HandoffImport {
+    pub review: ReviewMetadata,
+    pub imported: usize,
+    /// Imported comment ids whose line could not be found at the new head; they keep
+    /// their original line and commit.
+    pub unanchored: Vec<i64>,
+}
+
+/// Capture the commented line and its neighbours. `line` is 1-based.
+pub fn anchor_for(content: &str, line: u64) -> Option<LineAnchor> {
+    let lines: Vec<&str> = content.lines().collect();
+    let index = usize::try_from(line).ok()?.checked_sub(1)?;
+    let line_text = lines.get(index)?;
+    let start = index.saturating_sub(ANCHOR_CONTEXT_LINES);
+    let end = (index + 1 + ANCHOR_CONTEXT_LINES).min(lines.len());
+    Some(LineAnchor {
+        line_text: line_text.to_string(),
+        before: lines[start..index].iter().map(|l| l.to_string()).collect(),
+        after: lines[index + 1..end].iter().map(|l| l.to_string()).collect(),
+    })
+}
+
+/// Find the anchored line in a newer version of the file. Every line with the same
+/// text is a candidate; the one whose neighbours match best wins, and ties go to the
+/// candidate nearest the original line.
+pub fn relocate(anchor: &LineAnchor, content: &str, original_line: u64) -> Option<u64> {
+    let lines: Vec<&str> = content.lines().collect();
+    let score = |index: usize| -> usize {
+        let before = anchor
+            .before
+            .iter()
+            .rev()
+            .enumerate()
+            .filter(|(offset, text)| {
+                index
+                    .checked_sub(offset + 1)
+                    .and_then(|i| lines.get(i))
+                    .is_some_and(|line| line == text)
+            })
+            .count();
+        let after = anchor
+            .after
+            .iter()
+            .enumerate()
+            .filter(|(offset, text)| lines.get(index + offset + 1).is_some_and(|line| line == text))
+            .count();
+        before + after
+    };
+
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| **line == anchor.line_text)
+        .map(|(index, _)| (index, score(index)))
+        .max_by(|(a_index, a_score), (b_index, b_score)| {
+            a_score.cmp(b_score).then_with(|| {
+                let distance = |i: &usize| (*i as i64 + 1 - original_line as i64).abs();
+                distance(b_index).cmp(&distance(a_index))
+            })
+        })
+        .map(|(index, _)| index as u64 + 1)
+}
+
+/// Build the handoff document. `contents` maps `(path, commit)` to file text and is
+/// used to capture anchors for comments on the head side.
+pub fn build_handoff(
+    metadata: &ReviewMetadata,
+    comments: &[ReviewComment],
+    contents: &HashMap<(String, String), String>,
+    exported_by: Option<String>,
+) -> ReviewHandoff {
+    let comments = comments
+        .iter()
+        .filter(|c| !c.deleted)
+        .map(|c| HandoffComment {
+            id: c.id,
+            file_path: c.file_path.clone(),
+            line_number: c.line_number,
+            side: c.side.clone(),
+            body: c.body.clone(),
+            commit_id: c.commit_id.clone(),
+            in_reply_to_id: c.in_reply_to_id,
+            category: c.category.clone(),
+            anchor: contents
+                .get(&(c.file_path.clone(), c.commit_id.clone()))
+                .and_then(|content| anchor_for(content, c.line_number)),
+        })
+        .collect();
+
+    ReviewHandoff {
+        format: HANDOFF_FORMAT.to_string(),
+        version: HANDOFF_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        exported_by,
+        owner: metadata.owner.clone(),
+        repo: metadata.repo.clone(),
+        pr_number: metadata.pr_number,
+        commit_id: metadata.commit_id.clone(),
+        body: metadata.body.clone(),
+        comments,
+    }
+}
+
+pub fn parse_handoff(json: &str) -> AppResult<ReviewHandoff> {
+    let handoff: ReviewHandoff = serde_json::from_str(json)?;
+    if handoff.format != HANDOFF_FORMAT {
+        return Err(AppError::InvalidInput(format!(
+            "not a review handoff file (format `{}`)",
+            handoff.format
+        )));
+    }
+    if handoff.version > HANDOFF_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "review handoff version {} is newer than this app supports ({HANDOFF_VERSION})",
+            handoff.version
+        )));
+    }
+    Ok(handoff)
+}
+
+/// Comments whose anchor must be re-resolved against `head_sha`, grouped by path.
+pub fn paths_needing_relocation(handoff: &ReviewHandoff, head_sha: &str) -> Vec<String> {
+    let mut paths: Vec<String> = handoff
+        .comments
+        .iter()
+        .filter(|c| c.side == "RIGHT" && c.anchor.is_some() && c.commit_id != head_sha)
+        .map(|c| c.file_path.clone())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// File name offered in the save dialog for the handoff.
+pub fn handoff_file_name(handoff: &ReviewHandoff) -> String {
+    format!(
+        "{}-{}-{}.review-handoff.json",
+        handoff.owner, handoff.repo, handoff.pr_number
+    )
+}
+
+/// Add the handoff's comments to local storage, merging into an existing local review of
+/// the same PR. `head_contents` holds files at `head_sha`; comments written against an
+/// older commit are moved to `head_sha` when their anchor is found there.
+pub async fn import_handoff(
+    storage: &ReviewStorage,
+    handoff: &ReviewHandoff,
+    head_sha: Option<&str>,
+    head_contents: &HashMap<String, String>,
+) -> AppResult<HandoffImport> {
+    let mut lost = Vec::new();
+    let comments: Vec<ImportedComment> = handoff
+        .comments
+        .iter()
+        .map(|comment| {
+            let mut line_number = comment.line_number;
+            let mut commit_id = comment.commit_id.as_str();
+
+            if let (Some(head), Some(anchor)) = (head_sha, &comment.anchor) {
+                if comment.side == "RIGHT" && comment.commit_id != head {
+                    match head_contents
+                        .get(&comment.file_path)
+                        .and_then(|content| relocate(anchor, content, comment.line_number))
+                    {
+                        Some(line) => {
+                            line_number = line;
+                            commit_id = head;
+                        }
+                        None => lost.push(comment.id),
+                    }
+                }
+            }
+
+            ImportedComment {
+                id: Some(comment.id),
+                file_path: comment.file_path.clone(),
+                line_number,
+                side: comment.side.clone(),
+                body: comment.body.clone(),
+                commit_id: Some(commit_id.to_string()),
+                in_reply_to_id: comment.in_reply_to_id,
+                category: comment.category.clone(),
+                general: false,
+                suggestion: None,
+            }
+        })
+        .collect();
+
+    let (review, ids) = storage
+        .import_comments(
+            &handoff.owner,
+            &handoff.repo,
+            handoff.pr_number,
+            head_sha.unwrap_or(&handoff.commit_id),
+            handoff.body.as_deref(),
+            &comments,
+        )
+        .await?;
+    let unanchored = handoff
+        .comments
+        .iter()
+        .zip(&ids)
+        .filter(|(comment, _)| lost.contains(&comment.id))
+        .map(|(_, id)| *id)
+        .collect();
+
+    Ok(HandoffImport {
+        review,
+        imported: ids.len(),
+        unanchored,
+    })
+}
@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::diff::added_lines;
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewStorage;
+use crate::settings;
+
+pub const HEADING_STYLE_KEY: &str = "heading_style";
+
+/// Words title case leaves lowercase unless they start or end the heading.
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on",
+    "or", "per", "so", "the", "to", "up", "via", "vs", "with", "yet",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadingCase {
+    /// Capitalization is not checked.
+    Any,
+    /// "Install the command-line tool"
+    Sentence,
+    /// "Install the Command-Line Tool"
+    Title,
+}
+
+/// Heading rules a docs style guide sets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeadingStyleConfig {
+    pub case: HeadingCase,
+    /// Deepest heading level allowed, 1-6.
+    pub max_depth: u8,
+    /// Whether `##` may be followed directly by `####`.
+    pub allow_skipped_levels: bool,
+    /// Names that keep their capitalization in sentence case, e.g. "Azure" or "Windows".
+    /// Words with inner capitals or digits ("GitHub", "API", "Python3") always do.
+    pub proper_nouns: Vec<String>,
+}
+
+impl Default for HeadingStyleConfig {
+    fn default() -> Self {
+        Self {
+            case: HeadingCase::Sentence,
+            max_depth: 4,
+            allow_skipped_levels: false,
+            proper_nouns: Vec::new(),
+        }
+    }
+}
+
+impl HeadingStyleConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(1..=6).contains(&self.max_depth) {
+            return Err(AppError::InvalidInput(format!(
+                "heading max_depth must be between 1 and 6, got {}",
+                self.max_depth
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadingIssueKind {
+    Case,
+    TooDeep,
+    SkippedLevel,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeadingFinding {
+    /// 1-based head line of the heading.
+    pub line: u64,
+    pub level: u8,
+    pub heading: String,
+    pub kind: HeadingIssueKind,
+    pub detail: String,
+    /// The heading text rewritten to the configured case, for case findings.
+    pub suggested: Option<String>,
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<HeadingStyleConfig> {
+    settings::load(storage, HEADING_STYLE_KEY)
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &HeadingStyleConfig) -> AppResult<()> {
+    config.validate()?;
+    settings::save(storage, HEADING_STYLE_KEY, config)
+}
+
+/// Level and text of an ATX heading line, or `None`.
+fn parse_heading(line: &str) -> Option<(u8, &str)> {
+    let trimmed = line.trim_end();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 || !trimmed[level..].starts_with(' ') {
+        return None;
+    }
+    let title = trimmed[level..].trim().trim_end_matches('#').trim();
+    (!title.is_empty()).then_some((level as u8, title))
+}
+
+/// Headings in `content` with their 1-based lines. Front matter and fenced code blocks
+/// are skipped, so YAML and shell comments are not mistaken for headings.
+fn headings(content: &str) -> Vec<(u64, u8, &str)> {
+    let mut found = Vec::new();
+    let mut in_fence = false;
+    let mut in_front_matter = content.lines().next().is_some_and(|l| l.trim_end() == "---");
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if in_front_matter {
+            in_front_matter = index == 0 || !matches!(line.trim_end(), "---" | "...");
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            if let Some((level, title)) = parse_heading(line) {
+                found.push((index as u64 + 1, level, title));
+            }
+        }
+    }
+    found
+}
+
+/// Word with leading and trailing punctuation (links, quotes, colons) split off. The
+/// target of a `[text](url)` link stays in the suffix.
+fn split_word(token: &str) -> (&str, &str, &str) {
+    let text = &token[..token.find("](").unwrap_or(token.len())];
+    let start = text.find(char::is_alphanumeric).unwrap_or(text.len());
+    let end = text
+        .rfind(char::is_alphanumeric)
+        .map_or(start, |i| i + token[i..].chars().next().map_or(1, char::len_utf8));
+    (&token[..start], &token[start..end], &token[end..])
+}
+
+/// Names, acronyms, and versions keep their spelling in any case style.
+fn is_fixed(word: &str, proper_nouns: &[String]) -> bool {
+    word.split('-').any(|part| part.chars().skip(1).any(|c| c.is_uppercase()))
+        || word.chars().any(|c| c.is_ascii_digit())
+        || proper_nouns.iter().any(|noun| noun == word)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// `title` rewritten to `case`. Inline code is left alone.
+pub fn recase(title: &str, case: HeadingCase, proper_nouns: &[String]) -> String {
+    if case == HeadingCase::Any {
+        return title.to_string();
+    }
+    let tokens: Vec<&str> = title.split(' ').collect();
+    let last_word = tokens.iter().rposition(|t| !split_word(t).1.is_empty());
+    let mut in_code = false;
+    let mut first = true;
+    let mut after_colon = false;
+    let mut out = Vec::with_capacity(tokens.len());
+
+    for (index, token) in tokens.iter().enumerate() {
+        let code_token = in_code || token.starts_with('`');
+        if token.matches('`').count() % 2 == 1 {
+            in_code = !in_code;
+        }
+        let (prefix, word, suffix) = split_word(token);
+        if code_token || word.is_empty() {
+            out.push(token.to_string());
+            first &= word.is_empty();
+            continue;
+        }
+
+        let fixed = is_fixed(word, proper_nouns);
+        let lowered = word.to_lowercase();
+        let noun = proper_nouns.iter().find(|noun| noun.to_lowercase() == lowered);
+        let rewritten = match (noun, fixed) {
+            (_, true) => word.to_string(),
+            (Some(noun), false) => noun.clone(),
+            (None, false) => match case {
+                HeadingCase::Sentence if first => capitalize(word),
+                // Either case reads fine after "Step 1:"
+                HeadingCase::Sentence if after_colon => word.to_string(),
+                HeadingCase::Sentence => lowered,
+                HeadingCase::Title
+                    if !first
+                        && Some(index) != last_word
+                        && !after_colon
+                        && MINOR_WORDS.contains(&lowered.as_str()) =>
+                {
+                    lowered
+                }
+                HeadingCase::Title => word
+                    .split('-')
+                    .map(capitalize)
+                    .collect::<Vec<_>>()
+                    .join("-"),
+                HeadingCase::Any => word.to_string(),
+            },
+        };
+        out.push(format!("{prefix}{rewritten}{suffix}"));
+        first = false;
+        after_colon = suffix.ends_with(':');
+    }
+    out.join(" ")
+}
+
+/// Check the headings of markdown `content`. With a `patch`, only headings on added
+/// lines are reported.
+pub fn check(
+    config: &HeadingStyleConfig,
+    content: &str,
+    patch: Option<&str>,
+) -> Vec<HeadingFinding> {
+    let changed: Option<HashSet<u64>> =
+        patch.map(|patch| added_lines(patch).into_iter().map(|(line, _)| line).collect());
+    let mut findings = Vec::new();
+    let mut previous_level: Option<u8> = None;
+
+    for (line, level, title) in headings(content) {
+        let introduced = changed.as_ref().is_none_or(|changed| changed.contains(&line));
+        let previous = previous_level.replace(level);
+        if !introduced {
+            continue;
+        }
+        let finding = |kind, detail: String, suggested| HeadingFinding {
+            line,
+            level,
+            heading: title.to_string(),
+            kind,
+            detail,
+            suggested,
+        };
+
+        let suggested = recase(title, config.case, &config.proper_nouns);
+        if suggested != title {
+            let style = match config.case {
+                HeadingCase::Title => "title case",
+                _ => "sentence case",
+            };
+            findings.push(finding(
+                HeadingIssueKind::Case,
+                format!("heading should use {style}: \"{suggested}\""),
+                Some(suggested),
+            ));
+        }
+        if level > config.max_depth {
+            findings.push(finding(
+                HeadingIssueKind::TooDeep,
+                format!("heading level {level} is deeper than the allowed {}", config.max_depth),
+                None,
+            ));
+        }
+        if let Some(previous) = previous.filter(|p| level > p + 1) {
+            if !config.allow_skipped_levels {
+                findings.push(finding(
+                    HeadingIssueKind::SkippedLevel,
+                    format!(
+                        "heading level {level} follows level {previous}; expected at most {}",
+                        previous + 1
+                    ),
+                    None,
+                ));
+            }
+        }
+    }
+
+    findings
+}
@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::allow_list::{self, AllowList, HOOKS_ALLOW_LIST_KEY};
+use crate::error::{AppError, AppResult};
+use crate::review_storage::{self, ReviewStorage};
+use crate::settings;
+
+pub const HOOKS_KEY: &str = "hooks";
+
+const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    ReviewStarted,
+    CommentAdded,
+    ReviewSubmitted,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::ReviewStarted => "review_started",
+            HookEvent::CommentAdded => "comment_added",
+            HookEvent::ReviewSubmitted => "review_submitted",
+        }
+    }
+}
+
+/// What a hook does when its event fires. Shell hooks run the program, given as an
+/// absolute path, directly (never through a shell), so templated arguments cannot inject
+/// extra commands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HookAction {
+    Shell {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    Http {
+        url: String,
+        #[serde(default = "default_http_method")]
+        method: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        /// Body template; when omitted the full event payload is sent as JSON.
+        #[serde(default)]
+        body: Option<String>,
+    },
+}
+
+fn default_http_method() -> String {
+    "POST".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hook {
+    pub name: String,
+    pub event: HookEvent,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub action: HookAction,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// User-configured hooks. A hook only runs if its program (for shell hooks) or host (for
+/// HTTP hooks) is on the hooks allow-list, which is kept apart from this config and only
+/// grows through a native dialog; see `allow_list::request`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    pub hooks: Vec<Hook>,
+    pub timeout_secs: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            hooks: Vec::new(),
+            timeout_secs: 30,
+        }
+    }
+}
+
+impl HooksConfig {
+    pub fn validate(&self, allowed: &AllowList) -> AppResult<()> {
+        for hook in &self.hooks {
+            if hook.name.trim().is_empty() {
+                return Err(AppError::InvalidInput("hook name must not be empty".into()));
+            }
+            check_allowed(allowed, &hook.action)?;
+        }
+        Ok(())
+    }
+
+    /// Programs and hosts the hooks need, for asking the user to allow them.
+    pub fn targets(&self) -> (Vec<String>, Vec<String>) {
+        let mut programs = Vec::new();
+        let mut hosts = Vec::new();
+        for hook in &self.hooks {
+            match &hook.action {
+                HookAction::Shell { program, .. } => programs.push(program.clone()),
+                HookAction::Http { url, .. } => {
+                    if let Some(host) = Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string)) {
+                        hosts.push(host);
+                    }
+                }
+            }
+        }
+        (programs, hosts)
+    }
+}
+
+/// Reject actions whose program or host is not on the allow-list.
+pub fn check_allowed(allowed: &AllowList, action: &HookAction) -> AppResult<()> {
+    match action {
+        HookAction::Shell { program, .. } => allowed.check_program(program, "hooks")?,
+        HookAction::Http { url, method, .. } => {
+            settings::ensure_one_of("hook method", method, HTTP_METHODS)?;
+            check_url(allowed, url)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_url(allowed: &AllowList, url: &str) -> AppResult<()> {
+    let parsed = Url::parse(url)?;
+    let host = parsed.host_str().unwrap_or_default();
+    allowed.check_host(host, "hooks")?;
+    let local = matches!(host, "localhost" | "127.0.0.1" | "[::1]");
+    if parsed.scheme() != "https" && !(local && parsed.scheme() == "http") {
+        return Err(AppError::InvalidInput(format!("hook URL must use https: {url}")));
+    }
+    Ok(())
+}
+
+/// Values available to hook templates as `{{name}}`. `{{payload}}` expands to all of
+/// them as a JSON object.
+#[derive(Debug, Clone, Default)]
+pub struct HookContext {
+    values: BTreeMap<String, String>,
+}
+
+impl HookContext {
+    pub fn new(event: HookEvent, owner: &str, repo: &str, pr_number: u64) -> Self {
+        Self::default()
+            .with("event", event.as_str())
+            .with("owner", owner)
+            .with("repo", repo)
+            .with("pr_number", pr_number)
+    }
+
+    pub fn with(mut self, key: &str, value: impl ToString) -> Self {
+        self.values.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn payload(&self) -> String {
+        serde_json::to_string(&self.values).unwrap_or_default()
+    }
+
+    /// Substitute `{{name}}` placeholders. Unknown names are left in place so a typo is
+    /// visible in the output rather than silently dropped. Values are substituted in a
+    /// single pass, so placeholder text inside a comment body is never expanded.
+    pub fn render(&self, template: &str) -> String {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                rest = &rest[start..];
+                break;
+            };
+            let key = after[..end].trim();
+            match (key, self.values.get(key)) {
+                ("payload", _) => out.push_str(&self.payload()),
+                (_, Some(value)) => out.push_str(value),
+                (_, None) => out.push_str(&rest[start..start + end + 4]),
+            }
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<HooksConfig> {
+    settings::load(storage, HOOKS_KEY)
+}
+
+pub fn load_allow_list(storage: &ReviewStorage) -> AppResult<AllowList> {
+    allow_list::load(storage, HOOKS_ALLOW_LIST_KEY)
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &HooksConfig) -> AppResult<()> {
+    config.validate(&load_allow_list(storage)?)?;
+    settings::save(storage, HOOKS_KEY, config)
+}
+
+/// Run every enabled hook for `event` in the background. Failures are logged and never
+/// affect the review operation that triggered them.
+pub fn dispatch(event: HookEvent, context: HookContext) {
+    let loaded = review_storage::get_storage()
+        .and_then(|storage| Ok((load_config(storage)?, load_allow_list(storage)?)));
+    let (config, allowed) = match loaded {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            tracing::warn!("Could not load hooks config: {}", err);
+            return;
+        }
+    };
+
+    let hooks: Vec<Hook> = config
+        .hooks
+        .iter()
+        .filter(|hook| hook.enabled && hook.event == event)
+        .cloned()
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        for hook in hooks {
+            match run_hook(&config, &allowed, &hook, &context).await {
+                Ok(outcome) => tracing::info!("Hook '{}' ran: {}", hook.name, outcome),
+                Err(err) => tracing::warn!("Hook '{}' failed: {}", hook.name, err),
+            }
+        }
+    });
+}
+
+/// Run a single hook and describe the result (exit status or HTTP status).
+pub async fn run_hook(
+    config: &HooksConfig,
+    allowed: &AllowList,
+    hook: &Hook,
+    context: &HookContext,
+) -> AppResult<String> {
+    check_allowed(allowed, &hook.action)?;
+    let timeout = Duration::from_secs(config.timeout_secs.max(1));
+
+    match &hook.action {
+        HookAction::Shell { program, args } => {
+            let rendered: Vec<String> = args.iter().map(|arg| context.render(arg)).collect();
+            let output = tokio::time::timeout(
+                timeout,
+                tokio::process::Command::new(program)
+                    .args(&rendered)
+                    .kill_on_drop(true)
+                    .output(),
+            )
+            .await??;
+
+            if output.status.success() {
+                Ok(format!("{program} exited with {}", output.status))
+            } else {
+                Err(AppError::Internal(format!(
+                    "{program} exited with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )))
+            }
+        }
+        HookAction::Http {
+            url,
+            method,
+            headers,
+            body,
+        } => {
+            // Re-check after rendering so a placeholder cannot redirect the request.
+            let url = context.render(url);
+            check_url(allowed, &url)?;
+            let method = reqwest::Method::from_bytes(method.as_bytes())
+                .map_err(|e| AppError::InvalidInput(e.to_string()))?;
+            let client = reqwest::Client::builder().timeout(timeout).build()?;
+            let mut request = client.request(method, &url);
+            for (name, value) in headers {
+                request = request.header(name, context.render(value));
+            }
+            request = match body {
+                Some(template) => request.body(context.render(template)),
+                None => request
+                    .header("Content-Type", "application/json")
+                    .body(context.payload()),
+            };
+
+            let response = request.send().await?;
+            let status = response.status();
+            if status.is_success() {
+                Ok(format!("HTTP {status}"))
+            } else {
+                Err(AppError::Api(format!("Hook request returned HTTP {status}")))
+            }
+        }
+    }
+}
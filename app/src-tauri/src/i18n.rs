@@ -0,0 +1,421 @@
+use std::fmt::Display;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Locales supported for backend-produced, user-facing strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Ja, Locale::De];
+}
+
+static CURRENT_LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+pub fn set_locale(locale: Locale) {
+    if let Ok(mut current) = CURRENT_LOCALE.write() {
+        *current = locale;
+    }
+}
+
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE.read().map(|l| *l).unwrap_or_default()
+}
+
+/// Message keys for every string the backend shows to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    MissingConfig,
+    OAuthCancelled,
+    InvalidOAuthCallback,
+    Http,
+    Io,
+    Url,
+    Serde,
+    Keyring,
+    Database,
+    Timeout,
+    Internal,
+    InvalidInput,
+    NoPendingReview,
+    SecretsBeforeApproval,
+    ContentFilterBlocked,
+    ContentFilterWarning,
+    CategoryRequestChangesBody,
+    CategoryCommentBody,
+    RepoConfigCategoryRequired,
+    TodoCandidateComment,
+    AltTextComment,
+    OwnerNotAllowed,
+    ConfirmationRequired,
+    BaseDriftDocChanged,
+    TasklistHeading,
+    TasklistFileComment,
+    ReviewBodyRequired,
+    AuthExpired,
+    SubmissionParked,
+    MissingScope,
+    RateLimited,
+    OrphanedComments,
+    StaleDateComment,
+    PathNotAllowed,
+    LogReview,
+    LogLocalFolder,
+    LogReviewForPr,
+    LogUrl,
+    LogRepository,
+    LogCreated,
+    LogCommit,
+    LogReviewBody,
+    LogTotalComments,
+    LogOverall,
+    LogLine,
+    LogOriginal,
+    LogDeleted,
+    LogReviewAbandoned,
+    LogReviewSubmitted,
+    LogReviewDeleted,
+    LogReviewMerged,
+    LogReviewClosed,
+    LogOriginalStart,
+    LogSnapshot,
+    LogTitle,
+    LogBase,
+    LogDiffstat,
+    LogAnchor,
+    ReviewExportHeading,
+    ReviewExportStarted,
+    ReviewExportDue,
+    FeatureHooks,
+    AllowProgramPrompt,
+    AllowHostPrompt,
+    FeatureSnippetChecks,
+    RemovedFileRightSide,
+    CommentNotFound,
+    CommentAlreadyGeneral,
+    CommentTemplateNotFound,
+    CommentTemplateNameRequired,
+    CommentTemplateEmptyBody,
+    CommentTemplateNameTaken,
+    ConfirmDeleteReview,
+    ConfirmDeleteGithubComment,
+    ConfirmClearLocalReview,
+    ConfirmClearLocalReviewDrafts,
+    ResetEverything,
+    ResetKeepDrafts,
+    ResetChosen,
+    ResetToken,
+    ResetCachedData,
+    ResetLocalReviews,
+    ResetLogs,
+    ResetSettings,
+    ListSeparator,
+}
+
+/// Translate a message for the current locale.
+pub fn t(msg: Msg) -> &'static str {
+    translate(current_locale(), msg)
+}
+
+/// Translate a message for the current locale, substituting `{0}`, `{1}`, ... placeholders.
+pub fn tf(msg: Msg, args: &[&dyn Display]) -> String {
+    fill(t(msg), args)
+}
+
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = template.to_string();
+    for (index, arg) in args.iter().enumerate() {
+        out = out.replace(&format!("{{{index}}}"), &arg.to_string());
+    }
+    out
+}
+
+/// `msg` in every locale, to recognise text written while another locale was active.
+pub fn all_translations(msg: Msg) -> impl Iterator<Item = &'static str> {
+    Locale::ALL.into_iter().map(move |locale| translate(locale, msg))
+}
+
+pub fn translate(locale: Locale, msg: Msg) -> &'static str {
+    match locale {
+        Locale::En => english(msg),
+        Locale::Ja => japanese(msg),
+        Locale::De => german(msg),
+    }
+}
+
+fn english(msg: Msg) -> &'static str {
+    match msg {
+        Msg::MissingConfig => "missing configuration value: {0}",
+        Msg::OAuthCancelled => "oauth flow was cancelled or timed out",
+        Msg::InvalidOAuthCallback => "received an invalid oauth callback",
+        Msg::Http => "http error: {0}",
+        Msg::Io => "io error: {0}",
+        Msg::Url => "url parse error: {0}",
+        Msg::Serde => "serialization error: {0}",
+        Msg::Keyring => "secure storage error: {0}",
+        Msg::Database => "database error: {0}",
+        Msg::Timeout => "operation timed out",
+        Msg::Internal => "internal error: {0}",
+        Msg::InvalidInput => "invalid input: {0}",
+        Msg::NoPendingReview => "No pending review found",
+        Msg::SecretsBeforeApproval => "This pull request adds {0} possible secret(s): {1}. Confirm to approve anyway.",
+        Msg::ContentFilterBlocked => "Review not submitted: the content filter blocked {0}",
+        Msg::ContentFilterWarning => "The content filter flagged {0}. Confirm to submit anyway.",
+        Msg::CategoryRequestChangesBody => "Requesting changes for the blocking issues noted inline.",
+        Msg::CategoryCommentBody => "Non-blocking suggestions and nits.",
+        Msg::RepoConfigCategoryRequired => "Review not submitted: {0} requires every comment to have one of these categories: {1}. Missing: {2}",
+        Msg::TodoCandidateComment => "Leftover `{0}` placeholder: please resolve this before merging.",
+        Msg::AltTextComment => "Image `{0}` needs alt text describing it for screen reader users.",
+        Msg::OwnerNotAllowed => "`{0}` is not an allowed owner under the organization policy in settings",
+        Msg::ConfirmationRequired => "Not confirmed: {0}. The confirmation is missing, expired, or for a different action; confirm again to continue.",
+        Msg::BaseDriftDocChanged => "{0} also changed on {1} since this PR branched; the content under review may conflict or be outdated.",
+        Msg::TasklistHeading => "Review punch list ({0} item(s))",
+        Msg::TasklistFileComment => "whole file",
+        Msg::ReviewBodyRequired => "a summary body is required to submit {0}",
+        Msg::AuthExpired => "GitHub rejected the sign-in; it may have expired or been revoked. Sign in again to continue.",
+        Msg::SubmissionParked => "GitHub sign-in expired during submission. Unsent comments were kept and the submission resumes after you sign in again.",
+        Msg::MissingScope => "GitHub needs the `{0}` permission for this. Grant it when asked and the action is retried.",
+        Msg::RateLimited => "GitHub rate limit reached; requests can resume at {0}.",
+        Msg::OrphanedComments => "Nothing was submitted: {0} comment(s) are on files no longer in this PR ({1}). Move them, make them general comments or drop them, then submit again.",
+        Msg::StaleDateComment => "This article changed but `{0}` is still `{1}`. Please update it to the date of this change.",
+        Msg::PathNotAllowed => "`{0}` is outside the folders allowed for local review. Open it with the folder picker or add it to the local roots in settings.",
+        Msg::LogReview => "Review",
+        Msg::LogLocalFolder => "Local folder",
+        Msg::LogReviewForPr => "Review for PR",
+        Msg::LogUrl => "URL",
+        Msg::LogRepository => "Repository",
+        Msg::LogCreated => "Created",
+        Msg::LogCommit => "Commit",
+        Msg::LogReviewBody => "Review Body",
+        Msg::LogTotalComments => "Total Comments",
+        Msg::LogOverall => "Overall",
+        Msg::LogLine => "Line",
+        Msg::LogOriginal => "ORIGINAL",
+        Msg::LogDeleted => "DELETED",
+        Msg::LogReviewAbandoned => "REVIEW ABANDONED at {0}",
+        Msg::LogReviewSubmitted => "REVIEW SUBMITTED TO GITHUB at {0}",
+        Msg::LogReviewDeleted => "REVIEW DELETED (NOT SUBMITTED TO GITHUB) at {0}",
+        Msg::LogReviewMerged => "REVIEW ARCHIVED (PR MERGED) at {0}",
+        Msg::LogReviewClosed => "REVIEW ARCHIVED (PR CLOSED) at {0}",
+        Msg::LogOriginalStart => "Original review started at {0}",
+        Msg::LogSnapshot => "Review context at submission",
+        Msg::LogTitle => "Title",
+        Msg::LogBase => "Base",
+        Msg::LogDiffstat => "{0} files changed, {1} insertions(+), {2} deletions(-)",
+        Msg::LogAnchor => "Anchor",
+        Msg::ReviewExportHeading => "Review of {0}",
+        Msg::ReviewExportStarted => "Started",
+        Msg::ReviewExportDue => "Due",
+        Msg::FeatureHooks => "Automation hooks",
+        Msg::AllowProgramPrompt => "{0}: allow running this program on your computer?\n\n{1}\n\nOnly allow programs you trust with content from pull requests.",
+        Msg::AllowHostPrompt => "{0}: allow sending review details to this host?\n\n{1}\n\nOnly allow hosts you trust.",
+        Msg::FeatureSnippetChecks => "Snippet checks",
+        Msg::RemovedFileRightSide => "`{0}` is deleted by this pull request, so it has no new version to comment on. Comment on a removed line or on the whole file.",
+        Msg::CommentNotFound => "comment {0} not found",
+        Msg::CommentAlreadyGeneral => "comment {0} is already a general comment",
+        Msg::CommentTemplateNotFound => "comment template {0} not found",
+        Msg::CommentTemplateNameRequired => "a comment template needs a name",
+        Msg::CommentTemplateEmptyBody => "comment template `{0}` has an empty body",
+        Msg::CommentTemplateNameTaken => "a comment template named `{0}` already exists",
+        Msg::ConfirmDeleteReview => "Delete pending review {0} on {1}/{2}#{3} from GitHub; its comments are kept in the trash",
+        Msg::ConfirmDeleteGithubComment => "Delete comment {0} in {1}/{2} from GitHub",
+        Msg::ConfirmClearLocalReview => "Clear the local review of {0}/{1}#{2}",
+        Msg::ConfirmClearLocalReviewDrafts => "Clear the local review of {0}/{1}#{2} and its {3} draft comment(s)",
+        Msg::ResetEverything => "Remove all traces of the app: sign out and delete cached data, local reviews and drafts, logs and recordings, and settings",
+        Msg::ResetKeepDrafts => "Sign out and clear cached data, keeping local reviews and drafts",
+        Msg::ResetChosen => "Reset app data: {0}",
+        Msg::ResetToken => "sign out and forget the GitHub tokens",
+        Msg::ResetCachedData => "cached pull request data",
+        Msg::ResetLocalReviews => "local reviews and draft comments",
+        Msg::ResetLogs => "review logs and request recordings",
+        Msg::ResetSettings => "settings",
+        Msg::ListSeparator => ", ",
+    }
+}
+
+fn japanese(msg: Msg) -> &'static str {
+    match msg {
+        Msg::MissingConfig => "設定値がありません: {0}",
+        Msg::OAuthCancelled => "OAuth 認証がキャンセルされたか、タイムアウトしました",
+        Msg::InvalidOAuthCallback => "無効な OAuth コールバックを受信しました",
+        Msg::Http => "HTTP エラー: {0}",
+        Msg::Io => "入出力エラー: {0}",
+        Msg::Url => "URL の解析エラー: {0}",
+        Msg::Serde => "シリアライズエラー: {0}",
+        Msg::Keyring => "セキュアストレージのエラー: {0}",
+        Msg::Database => "データベースエラー: {0}",
+        Msg::Timeout => "操作がタイムアウトしました",
+        Msg::Internal => "内部エラー: {0}",
+        Msg::InvalidInput => "無効な入力: {0}",
+        Msg::NoPendingReview => "保留中のレビューが見つかりません",
+        Msg::SecretsBeforeApproval => "このプルリクエストには秘密情報の可能性がある箇所が {0} 件あります: {1}。承認する場合は確認してください。",
+        Msg::ContentFilterBlocked => "レビューは送信されませんでした。コンテンツフィルターがブロックしました: {0}",
+        Msg::ContentFilterWarning => "コンテンツフィルターが警告しました: {0}。送信する場合は確認してください。",
+        Msg::CategoryRequestChangesBody => "インラインで指摘したブロッキングな問題について修正をお願いします。",
+        Msg::CategoryCommentBody => "ブロッキングではない提案と細かな指摘です。",
+        Msg::RepoConfigCategoryRequired => "レビューは送信されませんでした。{0} により、すべてのコメントに次のいずれかのカテゴリが必要です: {1}。未設定: {2}",
+        Msg::TodoCandidateComment => "`{0}` のプレースホルダーが残っています。マージ前に対応してください。",
+        Msg::AltTextComment => "画像 `{0}` にスクリーンリーダー利用者向けの代替テキストを追加してください。",
+        Msg::OwnerNotAllowed => "`{0}` は設定の組織ポリシーで許可されていないオーナーです",
+        Msg::ConfirmationRequired => "未確認の操作です: {0}。確認が無いか、期限切れか、別の操作のものです。もう一度確認してください。",
+        Msg::BaseDriftDocChanged => "{0} はこの PR の分岐後に {1} でも変更されています。レビュー中の内容が競合しているか古い可能性があります。",
+        Msg::TasklistHeading => "レビュー指摘リスト（{0} 件）",
+        Msg::TasklistFileComment => "ファイル全体",
+        Msg::ReviewBodyRequired => "{0} で送信するにはレビュー本文が必要です",
+        Msg::AuthExpired => "GitHub のサインインが拒否されました。期限切れか取り消された可能性があります。続けるには再度サインインしてください。",
+        Msg::SubmissionParked => "送信中に GitHub のサインインが期限切れになりました。未送信のコメントは保持され、再度サインインすると送信が再開されます。",
+        Msg::MissingScope => "この操作には GitHub の `{0}` 権限が必要です。許可すると操作が再試行されます。",
+        Msg::RateLimited => "GitHub のリクエスト制限に達しました。{0} 以降に再開できます。",
+        Msg::OrphanedComments => "送信されませんでした: {0} 件のコメントがこの PR に含まれなくなったファイル ({1}) にあります。移動、全体コメントへの変換、または削除をしてから再度送信してください。",
+        Msg::StaleDateComment => "記事が変更されましたが `{0}` は `{1}` のままです。この変更の日付に更新してください。",
+        Msg::PathNotAllowed => "`{0}` はローカルレビューで許可されたフォルダーの外にあります。フォルダー選択で開くか、設定のローカルルートに追加してください。",
+        Msg::LogReview => "レビュー",
+        Msg::LogLocalFolder => "ローカルフォルダー",
+        Msg::LogReviewForPr => "PR のレビュー",
+        Msg::LogUrl => "URL",
+        Msg::LogRepository => "リポジトリ",
+        Msg::LogCreated => "作成日時",
+        Msg::LogCommit => "コミット",
+        Msg::LogReviewBody => "レビュー本文",
+        Msg::LogTotalComments => "コメント数",
+        Msg::LogOverall => "全体",
+        Msg::LogLine => "行",
+        Msg::LogOriginal => "変更前",
+        Msg::LogDeleted => "削除済み",
+        Msg::LogReviewAbandoned => "レビュー破棄 {0}",
+        Msg::LogReviewSubmitted => "GitHub にレビュー送信 {0}",
+        Msg::LogReviewDeleted => "レビュー削除（GitHub 未送信） {0}",
+        Msg::LogReviewMerged => "レビューをアーカイブ（PR マージ済み） {0}",
+        Msg::LogReviewClosed => "レビューをアーカイブ（PR クローズ済み） {0}",
+        Msg::LogOriginalStart => "レビュー開始 {0}",
+        Msg::LogSnapshot => "送信時のレビュー内容",
+        Msg::LogTitle => "タイトル",
+        Msg::LogBase => "ベース",
+        Msg::LogDiffstat => "{0} ファイル変更、{1} 行追加(+)、{2} 行削除(-)",
+        Msg::LogAnchor => "アンカー",
+        Msg::ReviewExportHeading => "{0} のレビュー",
+        Msg::ReviewExportStarted => "開始",
+        Msg::ReviewExportDue => "期限",
+        Msg::FeatureHooks => "自動化フック",
+        Msg::AllowProgramPrompt => "{0}: このコンピューターで次のプログラムの実行を許可しますか？\n\n{1}\n\nプルリクエストの内容を渡しても信頼できるプログラムのみ許可してください。",
+        Msg::AllowHostPrompt => "{0}: 次のホストへのレビュー詳細の送信を許可しますか？\n\n{1}\n\n信頼できるホストのみ許可してください。",
+        Msg::FeatureSnippetChecks => "スニペットチェック",
+        Msg::RemovedFileRightSide => "`{0}` はこのプルリクエストで削除されるため、新しいバージョンにはコメントできません。削除された行かファイル全体にコメントしてください。",
+        Msg::CommentNotFound => "コメント {0} が見つかりません",
+        Msg::CommentAlreadyGeneral => "コメント {0} はすでに全体コメントです",
+        Msg::CommentTemplateNotFound => "コメントテンプレート {0} が見つかりません",
+        Msg::CommentTemplateNameRequired => "コメントテンプレートには名前が必要です",
+        Msg::CommentTemplateEmptyBody => "コメントテンプレート `{0}` の本文が空です",
+        Msg::CommentTemplateNameTaken => "`{0}` という名前のコメントテンプレートはすでに存在します",
+        Msg::ConfirmDeleteReview => "{1}/{2}#{3} の保留中のレビュー {0} を GitHub から削除します。コメントはゴミ箱に残ります",
+        Msg::ConfirmDeleteGithubComment => "{1}/{2} のコメント {0} を GitHub から削除します",
+        Msg::ConfirmClearLocalReview => "{0}/{1}#{2} のローカルレビューを消去します",
+        Msg::ConfirmClearLocalReviewDrafts => "{0}/{1}#{2} のローカルレビューと {3} 件の下書きコメントを消去します",
+        Msg::ResetEverything => "アプリのすべての痕跡を削除します: サインアウトし、キャッシュデータ、ローカルレビューと下書き、ログと記録、設定を削除します",
+        Msg::ResetKeepDrafts => "サインアウトしてキャッシュデータを消去します。ローカルレビューと下書きは残ります",
+        Msg::ResetChosen => "アプリデータをリセット: {0}",
+        Msg::ResetToken => "サインアウトして GitHub トークンを削除",
+        Msg::ResetCachedData => "キャッシュされたプルリクエストデータ",
+        Msg::ResetLocalReviews => "ローカルレビューと下書きコメント",
+        Msg::ResetLogs => "レビューログとリクエストの記録",
+        Msg::ResetSettings => "設定",
+        Msg::ListSeparator => "、",
+    }
+}
+
+fn german(msg: Msg) -> &'static str {
+    match msg {
+        Msg::MissingConfig => "fehlender Konfigurationswert: {0}",
+        Msg::OAuthCancelled => "OAuth-Anmeldung wurde abgebrochen oder ist abgelaufen",
+        Msg::InvalidOAuthCallback => "ungültiger OAuth-Callback empfangen",
+        Msg::Http => "HTTP-Fehler: {0}",
+        Msg::Io => "E/A-Fehler: {0}",
+        Msg::Url => "URL-Parserfehler: {0}",
+        Msg::Serde => "Serialisierungsfehler: {0}",
+        Msg::Keyring => "Fehler im sicheren Speicher: {0}",
+        Msg::Database => "Datenbankfehler: {0}",
+        Msg::Timeout => "Zeitüberschreitung des Vorgangs",
+        Msg::Internal => "interner Fehler: {0}",
+        Msg::InvalidInput => "ungültige Eingabe: {0}",
+        Msg::NoPendingReview => "Kein ausstehendes Review gefunden",
+        Msg::SecretsBeforeApproval => "Dieser Pull Request fügt {0} mögliche(s) Geheimnis(se) hinzu: {1}. Zum Genehmigen bitte bestätigen.",
+        Msg::ContentFilterBlocked => "Review nicht gesendet: Der Inhaltsfilter hat blockiert: {0}",
+        Msg::ContentFilterWarning => "Der Inhaltsfilter hat gewarnt: {0}. Zum Senden bitte bestätigen.",
+        Msg::CategoryRequestChangesBody => "Bitte die inline markierten blockierenden Punkte beheben.",
+        Msg::CategoryCommentBody => "Nicht blockierende Vorschläge und Kleinigkeiten.",
+        Msg::RepoConfigCategoryRequired => "Review nicht gesendet: {0} verlangt für jeden Kommentar eine dieser Kategorien: {1}. Fehlt bei: {2}",
+        Msg::TodoCandidateComment => "Übrig gebliebener `{0}`-Platzhalter: bitte vor dem Mergen auflösen.",
+        Msg::AltTextComment => "Bild `{0}` braucht einen Alternativtext für Screenreader-Nutzer.",
+        Msg::OwnerNotAllowed => "`{0}` ist laut Organisationsrichtlinie in den Einstellungen kein erlaubter Besitzer",
+        Msg::ConfirmationRequired => "Nicht bestätigt: {0}. Die Bestätigung fehlt, ist abgelaufen oder gilt für eine andere Aktion; bitte erneut bestätigen.",
+        Msg::BaseDriftDocChanged => "{0} wurde seit der Abzweigung dieses PRs auch auf {1} geändert; der geprüfte Inhalt kann Konflikte haben oder veraltet sein.",
+        Msg::TasklistHeading => "Review-Aufgabenliste ({0} Punkt(e))",
+        Msg::TasklistFileComment => "ganze Datei",
+        Msg::ReviewBodyRequired => "zum Absenden von {0} ist ein Zusammenfassungstext erforderlich",
+        Msg::AuthExpired => "GitHub hat die Anmeldung abgelehnt; sie ist möglicherweise abgelaufen oder wurde widerrufen. Bitte erneut anmelden.",
+        Msg::SubmissionParked => "Die GitHub-Anmeldung ist während des Absendens abgelaufen. Nicht gesendete Kommentare wurden behalten; das Absenden wird nach der erneuten Anmeldung fortgesetzt.",
+        Msg::MissingScope => "GitHub benötigt dafür die Berechtigung `{0}`. Nach dem Erteilen wird die Aktion erneut ausgeführt.",
+        Msg::RateLimited => "GitHub begrenzt Anfragen gerade; sie sind ab {0} wieder möglich.",
+        Msg::OrphanedComments => "Nichts wurde gesendet: {0} Kommentar(e) beziehen sich auf Dateien, die nicht mehr Teil dieses PRs sind ({1}). Verschieben Sie sie, wandeln Sie sie in allgemeine Kommentare um oder verwerfen Sie sie und senden Sie erneut.",
+        Msg::StaleDateComment => "Der Artikel wurde geändert, aber `{0}` ist noch `{1}`. Bitte auf das Datum dieser Änderung aktualisieren.",
+        Msg::PathNotAllowed => "`{0}` liegt außerhalb der für lokale Reviews erlaubten Ordner. Öffnen Sie ihn über die Ordnerauswahl oder fügen Sie ihn in den Einstellungen zu den lokalen Stammordnern hinzu.",
+        Msg::LogReview => "Review",
+        Msg::LogLocalFolder => "Lokaler Ordner",
+        Msg::LogReviewForPr => "Review für PR",
+        Msg::LogUrl => "URL",
+        Msg::LogRepository => "Repository",
+        Msg::LogCreated => "Erstellt",
+        Msg::LogCommit => "Commit",
+        Msg::LogReviewBody => "Review-Text",
+        Msg::LogTotalComments => "Kommentare gesamt",
+        Msg::LogOverall => "Gesamt",
+        Msg::LogLine => "Zeile",
+        Msg::LogOriginal => "ORIGINAL",
+        Msg::LogDeleted => "GELÖSCHT",
+        Msg::LogReviewAbandoned => "REVIEW VERWORFEN am {0}",
+        Msg::LogReviewSubmitted => "REVIEW AN GITHUB GESENDET am {0}",
+        Msg::LogReviewDeleted => "REVIEW GELÖSCHT (NICHT AN GITHUB GESENDET) am {0}",
+        Msg::LogReviewMerged => "REVIEW ARCHIVIERT (PR GEMERGT) am {0}",
+        Msg::LogReviewClosed => "REVIEW ARCHIVIERT (PR GESCHLOSSEN) am {0}",
+        Msg::LogOriginalStart => "Review ursprünglich begonnen am {0}",
+        Msg::LogSnapshot => "Review-Kontext beim Senden",
+        Msg::LogTitle => "Titel",
+        Msg::LogBase => "Basis",
+        Msg::LogDiffstat => "{0} Dateien geändert, {1} Einfügungen(+), {2} Löschungen(-)",
+        Msg::LogAnchor => "Anker",
+        Msg::ReviewExportHeading => "Review von {0}",
+        Msg::ReviewExportStarted => "Begonnen",
+        Msg::ReviewExportDue => "Fällig",
+        Msg::FeatureHooks => "Automatisierungs-Hooks",
+        Msg::AllowProgramPrompt => "{0}: Ausführen dieses Programms auf Ihrem Computer erlauben?\n\n{1}\n\nErlauben Sie nur Programme, denen Sie Inhalte aus Pull Requests anvertrauen.",
+        Msg::AllowHostPrompt => "{0}: Senden von Review-Details an diesen Host erlauben?\n\n{1}\n\nErlauben Sie nur Hosts, denen Sie vertrauen.",
+        Msg::FeatureSnippetChecks => "Snippet-Prüfungen",
+        Msg::RemovedFileRightSide => "`{0}` wird durch diesen Pull Request gelöscht und hat keine neue Version zum Kommentieren. Kommentieren Sie eine entfernte Zeile oder die ganze Datei.",
+        Msg::CommentNotFound => "Kommentar {0} nicht gefunden",
+        Msg::CommentAlreadyGeneral => "Kommentar {0} ist bereits ein allgemeiner Kommentar",
+        Msg::CommentTemplateNotFound => "Kommentarvorlage {0} nicht gefunden",
+        Msg::CommentTemplateNameRequired => "eine Kommentarvorlage braucht einen Namen",
+        Msg::CommentTemplateEmptyBody => "Kommentarvorlage `{0}` hat einen leeren Text",
+        Msg::CommentTemplateNameTaken => "eine Kommentarvorlage namens `{0}` existiert bereits",
+        Msg::ConfirmDeleteReview => "Ausstehendes Review {0} zu {1}/{2}#{3} auf GitHub löschen; seine Kommentare bleiben im Papierkorb",
+        Msg::ConfirmDeleteGithubComment => "Kommentar {0} in {1}/{2} auf GitHub löschen",
+        Msg::ConfirmClearLocalReview => "Lokales Review von {0}/{1}#{2} leeren",
+        Msg::ConfirmClearLocalReviewDrafts => "Lokales Review von {0}/{1}#{2} und seine {3} Kommentarentwürfe leeren",
+        Msg::ResetEverything => "Alle Spuren der App entfernen: abmelden und zwischengespeicherte Daten, lokale Reviews und Entwürfe, Protokolle und Aufzeichnungen sowie Einstellungen löschen",
+        Msg::ResetKeepDrafts => "Abmelden und zwischengespeicherte Daten leeren, lokale Reviews und Entwürfe bleiben erhalten",
+        Msg::ResetChosen => "App-Daten zurücksetzen: {0}",
+        Msg::ResetToken => "abmelden und die GitHub-Tokens vergessen",
+        Msg::ResetCachedData => "zwischengespeicherte Pull-Request-Daten",
+        Msg::ResetLocalReviews => "lokale Reviews und Kommentarentwürfe",
+        Msg::ResetLogs => "Review-Protokolle und Anfrageaufzeichnungen",
+        Msg::ResetSettings => "Einstellungen",
+        Msg::ListSeparator => ", ",
+    }
+}
@@ -0,0 +1,242 @@
+use std::sync::OnceLock;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use regex::Regex;
+use serde::Serialize;
+
+/// One side of a changed image.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageVersion {
+    /// Base64 payload as returned by the contents API, for the webview to display.
+    pub base64: String,
+    pub byte_size: usize,
+    /// `png`, `jpeg`, `gif`, `bmp`, `webp`, `ico`, or `svg`; `None` when unrecognised.
+    pub format: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageDiff {
+    pub path: String,
+    pub base: Option<ImageVersion>,
+    pub head: Option<ImageVersion>,
+    /// Head size minus base size, in bytes.
+    pub byte_size_delta: i64,
+    /// Bits that differ between the two 64-bit perceptual hashes.
+    pub hash_distance: Option<u32>,
+    /// `hash_distance` as a share of the hash, e.g. 37.5 for "changed by 38%". Only
+    /// PNGs are decoded, so other formats have no figure.
+    pub changed_percent: Option<f64>,
+}
+
+fn u16_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn u24_le(bytes: &[u8], at: usize) -> Option<u32> {
+    let b = bytes.get(at..at + 3)?;
+    Some(b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16)
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn i32_le(bytes: &[u8], at: usize) -> Option<i32> {
+    Some(i32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Walk JPEG segments to the first start-of-frame marker, which holds the size.
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut at = 2;
+    while at + 4 <= bytes.len() {
+        if bytes[at] != 0xFF {
+            return None;
+        }
+        let marker = bytes[at + 1];
+        let length = u16_be(bytes, at + 2)? as usize;
+        let is_sof = matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+        if is_sof {
+            return Some((u16_be(bytes, at + 7)?, u16_be(bytes, at + 5)?));
+        }
+        at += 2 + length;
+    }
+    None
+}
+
+fn webp_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        b"VP8 " => Some((u16_le(bytes, 26)? & 0x3FFF, u16_le(bytes, 28)? & 0x3FFF)),
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => Some((u24_le(bytes, 24)? + 1, u24_le(bytes, 27)? + 1)),
+        _ => None,
+    }
+}
+
+fn svg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    static SIZE: OnceLock<Regex> = OnceLock::new();
+    static VIEW_BOX: OnceLock<Regex> = OnceLock::new();
+    let text = std::str::from_utf8(bytes).ok()?;
+    let tag = &text[text.find("<svg")?..];
+    let tag = &tag[..tag.find('>')?];
+
+    let size = SIZE.get_or_init(|| {
+        Regex::new(r#"\b(width|height)\s*=\s*["']\s*([0-9.]+)(px)?\s*["']"#)
+            .expect("svg size pattern is valid")
+    });
+    let mut width = None;
+    let mut height = None;
+    for caps in size.captures_iter(tag) {
+        let value = caps[2].parse::<f64>().ok().map(|v| v.round() as u32);
+        match &caps[1] {
+            "width" => width = value,
+            _ => height = value,
+        }
+    }
+    if let (Some(width), Some(height)) = (width, height) {
+        return Some((width, height));
+    }
+
+    let view_box = VIEW_BOX.get_or_init(|| {
+        Regex::new(r#"\bviewBox\s*=\s*["']\s*[-0-9.]+[\s,]+[-0-9.]+[\s,]+([0-9.]+)[\s,]+([0-9.]+)"#)
+            .expect("svg viewBox pattern is valid")
+    });
+    let caps = view_box.captures(tag)?;
+    Some((
+        caps[1].parse::<f64>().ok()?.round() as u32,
+        caps[2].parse::<f64>().ok()?.round() as u32,
+    ))
+}
+
+/// Format and pixel size from the file header, without decoding the image.
+pub fn sniff(bytes: &[u8]) -> (Option<&'static str>, Option<(u32, u32)>) {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let size = u32_be(bytes, 16).zip(u32_be(bytes, 20));
+        (Some("png"), size)
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        (Some("jpeg"), jpeg_dimensions(bytes))
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        (Some("gif"), u16_le(bytes, 6).zip(u16_le(bytes, 8)))
+    } else if bytes.starts_with(b"BM") {
+        // Height is negative for top-down bitmaps
+        let size = i32_le(bytes, 18).zip(i32_le(bytes, 22));
+        (Some("bmp"), size.map(|(w, h)| (w.unsigned_abs(), h.unsigned_abs())))
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        (Some("webp"), webp_dimensions(bytes))
+    } else if bytes.starts_with(&[0, 0, 1, 0]) {
+        // The first icon entry; 0 stands for 256
+        let side = |at: usize| bytes.get(at).map(|&b| if b == 0 { 256 } else { b as u32 });
+        (Some("ico"), side(6).zip(side(7)))
+    } else if std::str::from_utf8(&bytes[..bytes.len().min(1024)])
+        .is_ok_and(|head| head.contains("<svg") || head.contains("<?xml"))
+    {
+        (Some("svg"), svg_dimensions(bytes))
+    } else {
+        (None, None)
+    }
+}
+
+/// Decode a PNG to 8-bit luminance, compositing any transparency over white.
+fn png_luminance(bytes: &[u8]) -> Option<(usize, usize, Vec<u8>)> {
+    let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).ok()?;
+
+    let channels = info.color_type.samples();
+    let (width, height) = (info.width as usize, info.height as usize);
+    let mut luma = Vec::with_capacity(width * height);
+    for row in buf.chunks(info.line_size).take(height) {
+        for px in row.chunks(channels).take(width) {
+            let (gray, alpha) = match px {
+                [g] => (*g as u32, 255),
+                [g, a] => (*g as u32, *a as u32),
+                [r, g, b] => ((*r as u32 * 299 + *g as u32 * 587 + *b as u32 * 114) / 1000, 255),
+                [r, g, b, a] => (
+                    (*r as u32 * 299 + *g as u32 * 587 + *b as u32 * 114) / 1000,
+                    *a as u32,
+                ),
+                _ => return None,
+            };
+            luma.push(((gray * alpha + 255 * (255 - alpha)) / 255) as u8);
+        }
+    }
+    Some((width, height, luma))
+}
+
+/// Difference hash: shrink to 9x8 by area averaging and record whether each pixel is
+/// brighter than its right neighbour. Resizing and recompression barely move it.
+pub fn dhash(width: usize, height: usize, luma: &[u8]) -> Option<u64> {
+    if width == 0 || height == 0 || luma.len() < width * height {
+        return None;
+    }
+    let cell = |x: usize, y: usize| -> u64 {
+        let x0 = x * width / 9;
+        let x1 = ((x + 1) * width / 9).max(x0 + 1).min(width);
+        let y0 = y * height / 8;
+        let y1 = ((y + 1) * height / 8).max(y0 + 1).min(height);
+        let x0 = x0.min(x1 - 1);
+        let y0 = y0.min(y1 - 1);
+        let sum: u64 = (y0..y1)
+            .flat_map(|yy| (x0..x1).map(move |xx| luma[yy * width + xx] as u64))
+            .sum();
+        sum / ((x1 - x0) * (y1 - y0)) as u64
+    };
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash = (hash << 1) | u64::from(cell(x, y) > cell(x + 1, y));
+        }
+    }
+    Some(hash)
+}
+
+fn version(base64: &str) -> Option<(ImageVersion, Option<u64>)> {
+    let bytes = STANDARD.decode(base64.trim()).ok()?;
+    let (format, size) = sniff(&bytes);
+    let hash = (format == Some("png"))
+        .then(|| png_luminance(&bytes))
+        .flatten()
+        .and_then(|(w, h, luma)| dhash(w, h, &luma));
+    Some((
+        ImageVersion {
+            base64: base64.to_string(),
+            byte_size: bytes.len(),
+            format: format.map(str::to_string),
+            width: size.map(|(w, _)| w),
+            height: size.map(|(_, h)| h),
+        },
+        hash,
+    ))
+}
+
+/// Compare the base and head versions of an image, each as base64. A side that is
+/// missing (added or removed file) or not valid base64 is `None`.
+pub fn compare(path: &str, base: Option<&str>, head: Option<&str>) -> ImageDiff {
+    let (base, base_hash) = base.and_then(version).unzip();
+    let (head, head_hash) = head.and_then(version).unzip();
+    let size = |v: &Option<ImageVersion>| v.as_ref().map_or(0, |v| v.byte_size as i64);
+    let hash_distance = match (base_hash.flatten(), head_hash.flatten()) {
+        (Some(a), Some(b)) => Some((a ^ b).count_ones()),
+        _ => None,
+    };
+
+    ImageDiff {
+        path: path.to_string(),
+        byte_size_delta: size(&head) - size(&base),
+        changed_percent: hash_distance.map(|d| d as f64 * 100.0 / 64.0),
+        hash_distance,
+        base,
+        head,
+    }
+}
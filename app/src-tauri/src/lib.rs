@@ -1,13 +1,86 @@
+mod acronyms;
+mod allow_list;
+mod alt_text;
+mod anchor_impact;
+mod api_version;
+mod attention;
 mod auth;
+mod cache;
+mod categories;
+mod comment_anchors;
+mod comment_export;
+mod comment_search;
+mod command_args;
+mod comment_sync;
+mod commands;
+mod confirmations;
+mod content_filter;
+mod diagrams;
+mod dialogs;
+mod diff;
+mod drift;
+mod emoji;
 mod error;
+mod events;
+mod folder_counts;
+mod front_matter;
 mod github;
+mod handoff;
+mod heading_style;
+mod hooks;
+mod i18n;
+mod image_diff;
+mod link_index;
+mod linked_issues;
+mod local_files;
+mod local_roots;
+mod localization;
+mod markdown_render;
+mod markdown_structure;
+mod metrics;
+mod migrations;
 mod models;
+mod oauth_scopes;
+mod orphans;
+mod owner_policy;
+mod permalinks;
+mod plugins;
+mod pr_cache;
+mod pr_size;
+mod pr_template;
+mod quick_comments;
+mod quiet_hours;
+mod quote;
+mod rate_limit;
+mod recorder;
+mod repo_config;
+mod reset;
+mod safe_mode;
+mod review_activity;
+mod review_import;
+mod review_profiles;
+mod review_snapshot;
 mod storage;
 mod review_storage;
+mod second_pass;
+mod secrets;
+mod session;
+mod settings;
+mod settings_bundle;
+mod snippet_checks;
+mod spec_validation;
+mod stale_reviews;
+mod submission_report;
+mod summary;
+mod time_format;
+mod todos;
+mod visits;
+mod workload;
 
 #[cfg(test)]
 mod tests;
 
+use crate::command_args::{Owner, PrNumber, Reaction, Repo, RepoPath};
 use crate::github::CommentMode;
 use auth::{
     check_auth_status, fetch_pull_request_details, fetch_file_contents_on_demand, list_repo_pull_requests, logout,
@@ -15,8 +88,9 @@ use auth::{
     finalize_pending_review,
 };
 use models::{AuthStatus, PullRequestDetail, PullRequestReview, PullRequestSummary};
-use review_storage::{ReviewComment, ReviewMetadata};
-use serde::Deserialize;
+use review_storage::{ParkedSubmission, ReviewComment, ReviewMetadata};
+use settings::Preferences;
+use serde::{Deserialize, Serialize};
 use tauri::Manager;
 use tracing::{error, info};
 
@@ -69,27 +143,6 @@ fn set_windows_dev_titlebar_color(window: &tauri::WebviewWindow) {
     }
 }
 
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct SubmitFileCommentArgs {
-    owner: String,
-    repo: String,
-    number: u64,
-    path: String,
-    body: String,
-    #[serde(alias = "commit_id")]
-    commit_id: String,
-    line: Option<u64>,
-    side: Option<String>,
-    #[serde(alias = "subject_type")]
-    subject_type: Option<String>,
-    mode: Option<String>,
-    #[serde(alias = "pending_review_id")]
-    pending_review_id: Option<u64>,
-    #[serde(alias = "in_reply_to")]
-    in_reply_to: Option<u64>,
-}
-
 fn init_logging() {
     let filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn"));
@@ -100,11 +153,6 @@ fn init_logging() {
         .try_init();
 }
 
-fn normalize_rel_path(base: &std::path::Path, path: &std::path::Path) -> String {
-    let rel = path.strip_prefix(base).unwrap_or(path);
-    rel.to_string_lossy().replace('\\', "/")
-}
-
 fn resolve_local_directory_path(input: &str) -> std::path::PathBuf {
     let raw = std::path::PathBuf::from(input);
     if raw.is_absolute() {
@@ -134,75 +182,68 @@ fn resolve_local_directory_path(input: &str) -> std::path::PathBuf {
     raw
 }
 
-fn collect_markdown_files(dir: &std::path::Path, out: &mut Vec<std::path::PathBuf>) -> Result<(), String> {
-    let entries = std::fs::read_dir(dir)
-        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
-
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read directory entry in {}: {}", dir.display(), e))?;
-        let path = entry.path();
-        let file_type = entry
-            .file_type()
-            .map_err(|e| format!("Failed to get file type for {}: {}", path.display(), e))?;
-
-        if file_type.is_dir() {
-            collect_markdown_files(&path, out)?;
-            continue;
-        }
-
-        if !file_type.is_file() {
-            continue;
-        }
-
-        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
-        if ext == "md" || ext == "markdown" || ext == "mdx" {
-            out.push(path);
-        }
+/// Canonical path of the local folder `directory`, which must exist under an allowed root.
+fn check_local_directory(directory: &str) -> Result<std::path::PathBuf, String> {
+    let resolved = local_files::extended_length(&resolve_local_directory_path(directory));
+    // Even checking whether a share exists would reach out to it
+    if local_roots::is_network_path(&resolved) {
+        return Err(crate::error::AppError::PathNotAllowed(directory.to_string()).to_string());
     }
-
-    Ok(())
-}
-
-#[tauri::command]
-async fn cmd_load_local_directory(directory: String) -> Result<PullRequestDetail, String> {
-    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
-    use base64::Engine;
-    use sha2::{Digest, Sha256};
-
-    let base = resolve_local_directory_path(&directory);
-    if !base.exists() {
+    if !resolved.exists() {
         let cwd = std::env::current_dir().ok();
         return Err(format!(
             "Local directory does not exist: {} (resolved to: {}). CWD: {}",
             directory,
-            base.display(),
+            resolved.display(),
             cwd.as_ref().map(|p| p.display().to_string()).unwrap_or_else(|| "<unknown>".into())
         ));
     }
-    if !base.is_dir() {
+    if !resolved.is_dir() {
         return Err(format!(
             "Local path is not a directory: {} (resolved to: {})",
             directory,
-            base.display()
+            resolved.display()
         ));
     }
 
+    // Only folders under an allowed root are read, whoever asked for them
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let roots = local_roots::load_config(storage).map_err(|e| e.to_string())?;
+    roots.check(&resolved).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_load_local_directory(
+    args: LoadLocalDirectoryArgs,
+) -> Result<PullRequestDetail, String> {
+    let LoadLocalDirectoryArgs { directory } = args;
+
+    let base = check_local_directory(&directory)?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+
     info!(
         "cmd_load_local_directory: input_dir='{}', resolved_dir='{}'",
         directory,
         base.display()
     );
 
-    let mut hasher = Sha256::new();
-    hasher.update(directory.as_bytes());
-    let digest = hasher.finalize();
-    let id = URL_SAFE_NO_PAD.encode(&digest[..12]);
-    let sha = format!("LOCAL-{}", id);
+    // Keyed by the canonical path, so every spelling of the folder opens the same review
+    let sha = local_files::folder_id(&base);
 
-    // Walk directory (blocking), then read contents (async)
-    let mut files: Vec<std::path::PathBuf> = Vec::new();
-    collect_markdown_files(&base, &mut files)?;
-    files.sort();
+    // Only the file list and what the secret scan found are returned; contents are read
+    // one file at a time with `cmd_read_local_file`
+    local_files::register_folder(&sha, base.clone());
+    let walk_base = base.clone();
+    let (files, mut skipped_files, secret_findings) = tokio::task::spawn_blocking(move || {
+        let mut skipped_files = Vec::new();
+        local_files::collect_markdown_files(&walk_base, &mut skipped_files).map(|files| {
+            let findings = local_files::scan_secrets(&walk_base, &files);
+            (files, skipped_files, findings)
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Failed to read directory {}: {}", base.display(), e))?;
 
     info!(
         "cmd_load_local_directory: found {} markdown-like files",
@@ -210,49 +251,148 @@ async fn cmd_load_local_directory(directory: String) -> Result<PullRequestDetail
     );
 
     let mut pr_files = Vec::with_capacity(files.len());
-
     for path in files {
-        let rel_path = normalize_rel_path(&base, &path);
-        let content = tokio::fs::read_to_string(&path)
-            .await
-            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
-
+        let Some(rel_path) = local_files::relative_path(&base, &path) else {
+            skipped_files.push(local_files::skipped(&base, &path, "name is not valid UTF-8"));
+            continue;
+        };
         pr_files.push(models::PullRequestFile {
             path: rel_path,
             status: "modified".to_string(),
             additions: 0,
             deletions: 0,
             patch: None,
-            head_content: Some(content),
+            head_content: None,
             base_content: None,
             language: "markdown".to_string(),
             previous_filename: None,
+            localized_pair: None,
         });
     }
+    for file in &skipped_files {
+        tracing::warn!("cmd_load_local_directory: skipped {}: {}", file.path, file.reason);
+    }
+    localization::pair_files(&mut pr_files, localization::DEFAULT_SOURCE_LOCALE);
 
     let title = base
         .file_name()
-        .and_then(|s| s.to_str())
-        .map(|s| format!("Local: {}", s))
+        .map(|s| format!("Local: {}", s.to_string_lossy()))
         .unwrap_or_else(|| format!("Local: {}", directory));
 
+    let number = storage
+        .local_folder_number(
+            &local_files::folder_key(&base),
+            &directory,
+            local_files::folder_number(&base),
+        )
+        .map_err(|e| e.to_string())?;
+
     Ok(PullRequestDetail {
-        number: 1,
+        number,
         title,
         body: Some(format!("Local directory mode: {}", directory)),
         author: "local".to_string(),
         head_sha: sha.clone(),
         base_sha: sha,
+        head_owner: None,
+        head_repo: None,
         files: pr_files,
         comments: Vec::new(),
+        threads: Vec::new(),
         my_comments: Vec::new(),
         reviews: Vec::new(),
+        secret_findings,
+        reactions: models::ReactionSummary::default(),
+        participants: Vec::new(),
+        author_context: None,
+        linked_issues: Vec::new(),
+        merge_status: None,
+        bookmarks: Vec::new(),
+        last_visited_at: None,
+        new_comment_count: 0,
+        skipped_files,
+        stale: false,
+        cached_at: None,
     })
 }
 
+/// Content of one file of a folder opened with `cmd_load_local_directory`. `base_id` is
+/// the `head_sha` that load returned; folders that were never loaded cannot be read.
+#[tauri::command]
+async fn cmd_read_local_file(args: ReadLocalFileArgs) -> Result<String, String> {
+    let ReadLocalFileArgs { base_id, rel_path: RepoPath(rel_path) } = args;
+
+    let base = local_files::loaded_folder(&base_id)
+        .ok_or_else(|| format!("Local folder {base_id} is not open; reload it"))?;
+    // Links are resolved before the check, so none can lead out of the folder
+    let path = local_roots::ensure_within(&base, &base.join(&rel_path))
+        .map_err(|e| e.to_string())?;
+    tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {rel_path}: {e}"))
+}
+
+/// The pseudo pull request number `cmd_load_local_directory` gives `directory`, so the
+/// view can select it before the folder is listed.
+#[tauri::command]
+fn cmd_get_local_folder_number(args: GetLocalFolderNumberArgs) -> Result<u64, String> {
+    let GetLocalFolderNumberArgs { directory } = args;
+
+    let base = check_local_directory(&directory)?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .local_folder_number(
+            &local_files::folder_key(&base),
+            &directory,
+            local_files::folder_number(&base),
+        )
+        .map_err(|e| e.to_string())
+}
+
+/// Sign in with the browser flow, or with `method: "device"` by entering a code on
+/// GitHub where the browser flow cannot listen for its redirect.
+#[tauri::command]
+async fn cmd_start_github_oauth(
+    app: tauri::AppHandle,
+    args: StartGithubOauthArgs,
+) -> Result<AuthStatus, String> {
+    let StartGithubOauthArgs { method } = args;
+    start_oauth_flow(&app, method.unwrap_or_default())
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// The last request GitHub refused for a missing scope, so the UI can offer to grant it
+/// and retry. Cleared once read.
+#[tauri::command]
+fn cmd_take_scope_upgrade() -> Option<oauth_scopes::ScopeUpgrade> {
+    oauth_scopes::take_pending()
+}
+
+/// GitHub's request quotas as last reported. With `refresh`, or before any request has
+/// reported one, they are fetched from GitHub first.
+#[tauri::command]
+async fn cmd_get_rate_limit(
+    args: GetRateLimitArgs,
+) -> Result<Vec<rate_limit::RateLimitStatus>, String> {
+    let GetRateLimitArgs { refresh } = args;
+    let known = rate_limit::current();
+    if !refresh && !known.is_empty() {
+        return Ok(known);
+    }
+    auth::refresh_rate_limits().await.map_err(|e| e.to_string())?;
+    Ok(rate_limit::current())
+}
+
 #[tauri::command]
-async fn cmd_start_github_oauth(app: tauri::AppHandle) -> Result<AuthStatus, String> {
-    start_oauth_flow(&app).await.map_err(|err| err.to_string())
+async fn cmd_upgrade_oauth_scope(
+    app: tauri::AppHandle,
+    args: UpgradeOauthScopeArgs,
+) -> Result<AuthStatus, String> {
+    let UpgradeOauthScopeArgs { scope, method } = args;
+    auth::upgrade_oauth_scope(&app, &scope, method.unwrap_or_default())
+        .await
+        .map_err(|err| err.to_string())
 }
 
 #[tauri::command]
@@ -275,20 +415,69 @@ async fn cmd_logout() -> Result<(), String> {
     logout().await.map_err(|err| err.to_string())
 }
 
+/// Saved GitHub accounts and which one is in use.
+#[tauri::command]
+fn cmd_list_accounts() -> Result<Vec<models::AccountProfile>, String> {
+    auth::list_accounts().map_err(|err| err.to_string())
+}
+
+/// Add a named account, e.g. `work`, and sign in to it; it becomes the active account.
+#[tauri::command]
+async fn cmd_add_account(args: AddAccountArgs) -> Result<AuthStatus, String> {
+    let AddAccountArgs { name, method } = args;
+    auth::add_account(&name, method.unwrap_or_default())
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Use another saved account's token for every request from now on.
+#[tauri::command]
+async fn cmd_switch_account(args: SwitchAccountArgs) -> Result<AuthStatus, String> {
+    let SwitchAccountArgs { name } = args;
+    auth::switch_account(&name).await.map_err(|err| err.to_string())
+}
+
+/// Forget a saved account and its token.
+#[tauri::command]
+fn cmd_remove_account(args: RemoveAccountArgs) -> Result<Vec<models::AccountProfile>, String> {
+    let RemoveAccountArgs { name } = args;
+    auth::remove_account(&name).map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 async fn cmd_list_pull_requests(
-    owner: String,
-    repo: String,
-    state: Option<String>,
-    current_login: Option<String>,
+    args: ListPullRequestsArgs,
 ) -> Result<Vec<PullRequestSummary>, String> {
+    let ListPullRequestsArgs { owner: Owner(owner), repo: Repo(repo), state, current_login } = args;
     if owner == "__local__" || repo == "local" {
         return Err("Local folder mode does not support listing GitHub pull requests".to_string());
     }
     info!("cmd_list_pull_requests: owner={}, repo={}, state={:?}", owner, repo, state);
     match list_repo_pull_requests(&owner, &repo, state.as_deref(), current_login.as_deref()).await {
-        Ok(prs) => {
+        Ok(mut prs) => {
             info!("cmd_list_pull_requests: success, found {} PRs", prs.len());
+            match review_storage::get_storage().and_then(|s| s.get_last_visits(&owner, &repo)) {
+                Ok(visits) => {
+                    for pr in &mut prs {
+                        pr.new_since_last_visit = visits
+                            .get(&pr.number)
+                            .is_some_and(|seen| visits::is_after(&pr.updated_at, seen));
+                    }
+                }
+                Err(err) => tracing::warn!("cmd_list_pull_requests: failed to load visits - {}", err),
+            }
+            match review_storage::get_storage()
+                .and_then(|s| s.get_pending_comment_counts(&owner, &repo))
+            {
+                Ok(counts) => {
+                    for pr in &mut prs {
+                        pr.pending_comment_count = counts.get(&pr.number).copied().unwrap_or(0);
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("cmd_list_pull_requests: failed to count pending comments - {}", err)
+                }
+            }
             Ok(prs)
         }
         Err(err) => {
@@ -299,34 +488,86 @@ async fn cmd_list_pull_requests(
 }
 
 #[tauri::command]
-async fn cmd_get_pull_request(
-    owner: String,
-    repo: String,
-    number: u64,
-    current_login: Option<String>,
-) -> Result<PullRequestDetail, String> {
+async fn cmd_get_pull_request(args: GetPullRequestArgs) -> Result<PullRequestDetail, String> {
+    let GetPullRequestArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+        current_login,
+    } = args;
     if owner == "__local__" || repo == "local" {
         return Err("Local folder mode does not support fetching GitHub pull request details".to_string());
     }
     info!("cmd_get_pull_request: owner={}, repo={}, pr={}", owner, repo, number);
-    match fetch_pull_request_details(&owner, &repo, number, current_login.as_deref()).await {
+    // Cached details belong to the account that fetched them
+    let login = storage::read_last_login().ok().flatten();
+    let cache = pr_cache::get().zip(login.as_deref());
+    let fetched = fetch_pull_request_details(&owner, &repo, number, current_login.as_deref()).await;
+    let mut pr = match fetched {
         Ok(pr) => {
             info!("cmd_get_pull_request: success, {} files", pr.files.len());
-            Ok(pr)
+            if let Some((cache, login)) = cache {
+                // Written in the background, so the response never waits on the disk
+                let (login, owner, repo, detail) =
+                    (login.to_string(), owner.clone(), repo.clone(), pr.clone());
+                tauri::async_runtime::spawn_blocking(move || {
+                    if let Err(err) = cache.save(&login, &owner, &repo, number, &detail) {
+                        tracing::warn!("cmd_get_pull_request: failed to cache details - {}", err);
+                    }
+                });
+            }
+            pr
         }
         Err(err) => {
-            error!("cmd_get_pull_request: error - {}", err);
-            Err(err.to_string())
+            // Offline, a PR opened before can still be read from its last fetch
+            let cached = match cache.filter(|_| pr_cache::is_offline_error(&err)) {
+                Some((cache, login)) => {
+                    let (login, owner, repo) = (login.to_string(), owner.clone(), repo.clone());
+                    tauri::async_runtime::spawn_blocking(move || {
+                        cache.load(&login, &owner, &repo, number)
+                    })
+                    .await
+                    .ok()
+                    .flatten()
+                }
+                None => None,
+            };
+            match cached {
+                Some(pr) => {
+                    tracing::warn!("cmd_get_pull_request: serving cached details - {}", err);
+                    pr
+                }
+                None => {
+                    error!("cmd_get_pull_request: error - {}", err);
+                    return Err(err.to_string());
+                }
+            }
+        }
+    };
+    match review_storage::get_storage().and_then(|s| s.get_bookmarks(&owner, &repo, number)) {
+        Ok(bookmarks) => pr.bookmarks = bookmarks,
+        Err(err) => tracing::warn!("cmd_get_pull_request: failed to load bookmarks - {}", err),
+    }
+    match review_storage::get_storage().and_then(|s| s.get_last_visit(&owner, &repo, number)) {
+        Ok(last_visit) => {
+            pr.new_comment_count = visits::mark_new_comments(&mut pr.comments, last_visit.as_deref());
+            visits::mark_new_in_threads(&mut pr.threads, last_visit.as_deref());
+            pr.last_visited_at = last_visit;
         }
+        Err(err) => tracing::warn!("cmd_get_pull_request: failed to load last visit - {}", err),
     }
+    Ok(pr)
 }
 
 #[tauri::command]
 async fn cmd_get_pull_request_metadata(
-    owner: String,
-    repo: String,
-    number: u64,
+    args: GetPullRequestMetadataArgs,
 ) -> Result<models::PullRequestMetadata, String> {
+    let GetPullRequestMetadataArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+    } = args;
     if owner == "__local__" || repo == "local" {
         return Err(
             "Local folder mode does not support fetching GitHub pull request metadata".to_string(),
@@ -339,26 +580,117 @@ async fn cmd_get_pull_request_metadata(
 
 #[tauri::command]
 async fn cmd_get_file_contents(
-    owner: String,
-    repo: String,
-    file_path: String,
-    base_sha: String,
-    head_sha: String,
-    status: String,
-    previous_filename: Option<String>,
+    args: GetFileContentsArgs,
 ) -> Result<(Option<String>, Option<String>), String> {
-    fetch_file_contents_on_demand(&owner, &repo, &file_path, &base_sha, &head_sha, &status, previous_filename.as_deref())
-        .await
-        .map_err(|err| err.to_string())
+    let GetFileContentsArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        file_path: RepoPath(file_path),
+        base_sha,
+        head_sha,
+        status,
+        previous_filename,
+        head_owner,
+        head_repo,
+    } = args;
+    let previous_filename = previous_filename.map(|RepoPath(path)| path);
+    let head_repository = head_repository(head_owner, head_repo);
+    fetch_file_contents_on_demand(
+        &owner,
+        &repo,
+        &file_path,
+        &base_sha,
+        &head_sha,
+        &status,
+        previous_filename.as_deref(),
+        head_repository.as_ref().map(|(owner, repo)| (owner.as_str(), repo.as_str())),
+    )
+    .await
+    .map_err(|err| err.to_string())
+}
+
+/// The fork a PR came from, given as `head_owner`/`head_repo` in the PR details.
+fn head_repository(head_owner: Option<Owner>, head_repo: Option<Repo>) -> Option<(String, String)> {
+    head_owner.zip(head_repo).map(|(Owner(owner), Repo(repo))| (owner, repo))
 }
 
+/// GitHub's rendered HTML of both versions of a changed markdown file, for previews that
+/// should match github.com exactly. Needs a connection, unlike rendering in the app.
 #[tauri::command]
-async fn cmd_submit_review_comment(
-    owner: String,
-    repo: String,
-    number: u64,
-    body: String,
-) -> Result<(), String> {
+async fn cmd_get_rendered_file_contents(
+    args: GetRenderedFileContentsArgs,
+) -> Result<(Option<String>, Option<String>), String> {
+    let GetRenderedFileContentsArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        file_path: RepoPath(file_path),
+        base_sha,
+        head_sha,
+        status,
+        previous_filename,
+        head_owner,
+        head_repo,
+    } = args;
+    let previous_filename = previous_filename.map(|RepoPath(path)| path);
+    let head_repository = head_repository(head_owner, head_repo);
+    auth::fetch_rendered_file_contents(
+        &owner,
+        &repo,
+        &file_path,
+        &base_sha,
+        &head_sha,
+        &status,
+        previous_filename.as_deref(),
+        head_repository.as_ref().map(|(owner, repo)| (owner.as_str(), repo.as_str())),
+    )
+    .await
+    .map_err(|err| err.to_string())
+}
+
+/// Both versions of a changed image with their sizes and how much the picture changed.
+#[tauri::command]
+async fn cmd_get_image_diff(args: GetImageDiffArgs) -> Result<image_diff::ImageDiff, String> {
+    let GetImageDiffArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        file_path: RepoPath(file_path),
+        base_sha,
+        head_sha,
+        status,
+        previous_filename,
+        head_owner,
+        head_repo,
+    } = args;
+    let previous_filename = previous_filename.map(|RepoPath(path)| path);
+    let head_repository = head_repository(head_owner, head_repo);
+    let (head, base) = fetch_file_contents_on_demand(
+        &owner,
+        &repo,
+        &file_path,
+        &base_sha,
+        &head_sha,
+        &status,
+        previous_filename.as_deref(),
+        head_repository.as_ref().map(|(owner, repo)| (owner.as_str(), repo.as_str())),
+    )
+    .await
+    .map_err(|err| err.to_string())?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        image_diff::compare(&file_path, base.as_deref(), head.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_submit_review_comment(args: SubmitReviewCommentArgs) -> Result<(), String> {
+    let SubmitReviewCommentArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+        body,
+    } = args;
     publish_review_comment(&owner, &repo, number, body)
         .await
         .map_err(|err| err.to_string())
@@ -367,10 +699,10 @@ async fn cmd_submit_review_comment(
 #[tauri::command]
 async fn cmd_submit_file_comment(args: SubmitFileCommentArgs) -> Result<(), String> {
     let SubmitFileCommentArgs {
-        owner,
-        repo,
-        number,
-        path,
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+        path: RepoPath(path),
         body,
         commit_id,
         line,
@@ -379,6 +711,7 @@ async fn cmd_submit_file_comment(args: SubmitFileCommentArgs) -> Result<(), Stri
         mode,
         pending_review_id,
         in_reply_to,
+        suggestion,
     } = args;
 
     let mode = match mode.as_deref() {
@@ -399,6 +732,7 @@ async fn cmd_submit_file_comment(args: SubmitFileCommentArgs) -> Result<(), Stri
         mode,
         pending_review_id,
         in_reply_to,
+        suggestion.as_ref(),
     )
     .await
     .map_err(|err| err.to_string())
@@ -406,13 +740,16 @@ async fn cmd_submit_file_comment(args: SubmitFileCommentArgs) -> Result<(), Stri
 
 #[tauri::command]
 async fn cmd_start_pending_review(
-    owner: String,
-    repo: String,
-    number: u64,
-    commit_id: Option<String>,
-    body: Option<String>,
-    current_login: Option<String>,
+    args: StartPendingReviewArgs,
 ) -> Result<PullRequestReview, String> {
+    let StartPendingReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+        commit_id,
+        body,
+        current_login,
+    } = args;
     start_pending_review(
         &owner,
         &repo,
@@ -425,17 +762,141 @@ async fn cmd_start_pending_review(
     .map_err(|err| err.to_string())
 }
 
+/// The requested event, or the default from preferences, checked against the
+/// summary-body policy.
+fn resolve_review_event(event: Option<&str>, body: Option<&str>) -> Result<String, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let preferences = settings::load_preferences(storage).map_err(|e| e.to_string())?;
+    let event = preferences.review_event(event);
+    preferences
+        .check_review_body(&event, body)
+        .map_err(|e| e.to_string())?;
+    Ok(event)
+}
+
+/// Keep a submission that failed because GitHub rejected the token, so it can be
+/// resumed after signing in again instead of leaving the review half-submitted.
+fn park_on_auth_expiry<T>(
+    submission: &ParkedSubmission,
+    result: Result<T, String>,
+) -> Result<T, String> {
+    let err = match result {
+        Ok(value) => {
+            // A parked copy of this submission would be sent a second time on resume
+            let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+            storage
+                .remove_parked_submission(&submission.owner, &submission.repo, submission.pr_number)
+                .map_err(|e| e.to_string())?;
+            return Ok(value);
+        }
+        Err(err) => err,
+    };
+    if !auth::session_expired() {
+        return Err(err);
+    }
+    park_submission(submission)?;
+    Err(i18n::t(i18n::Msg::SubmissionParked).to_string())
+}
+
+/// Comment-by-comment submission reports a revoked token per comment rather than as an
+/// error; park it the same way.
+fn park_report_on_auth_expiry(
+    submission: &ParkedSubmission,
+    mut report: submission_report::SubmissionReport,
+) -> Result<submission_report::SubmissionReport, String> {
+    if report.failed.iter().any(|f| f.error_code == "auth_expired") {
+        park_submission(submission)?;
+        report.summary = Some(i18n::t(i18n::Msg::SubmissionParked).to_string());
+    }
+    Ok(report)
+}
+
+/// GitHub login of the active account, or empty when it is not known yet.
+fn current_login() -> String {
+    storage::read_last_login().ok().flatten().unwrap_or_default()
+}
+
+fn park_submission(submission: &ParkedSubmission) -> Result<(), String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .park_submission(submission)
+        .map_err(|e| e.to_string())?;
+    info!(
+        "Parked submission for {}/{}#{} until the next sign-in",
+        submission.owner, submission.repo, submission.pr_number
+    );
+    Ok(())
+}
+
 #[tauri::command]
-async fn cmd_submit_pending_review(
+async fn cmd_submit_pending_review(args: SubmitPendingReviewArgs) -> Result<(), String> {
+    let SubmitPendingReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+        review_id,
+        event,
+        body,
+        acknowledge_secrets,
+    } = args;
+    let parked = ParkedSubmission {
+        owner: owner.clone(),
+        repo: repo.clone(),
+        pr_number: number,
+        review_id: Some(review_id),
+        event: event.clone(),
+        body: body.clone(),
+        mode: None,
+        login: current_login(),
+        parked_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let result = submit_pending_review(
+        owner,
+        repo,
+        number,
+        review_id,
+        event,
+        body,
+        acknowledge_secrets,
+    )
+    .await;
+    if result.is_err() {
+        metrics::record_usage(metrics::UsageKind::SubmissionFailed);
+    }
+    park_on_auth_expiry(&parked, result)
+}
+
+async fn submit_pending_review(
     owner: String,
     repo: String,
     number: u64,
     review_id: u64,
     event: Option<String>,
     body: Option<String>,
+    acknowledge_secrets: Option<bool>,
 ) -> Result<(), String> {
-    let event = event.unwrap_or_else(|| "COMMENT".into());
-    finalize_pending_review(
+    let event = resolve_review_event(event.as_deref(), body.as_deref())?;
+
+    // Approving a PR that adds credentials needs an explicit second confirmation
+    if event == "APPROVE" && !acknowledge_secrets.unwrap_or(false) {
+        let files = auth::fetch_pull_request_files(&owner, &repo, number)
+            .await
+            .map_err(|err| err.to_string())?;
+        let findings = secrets::scan_files(&files);
+        if !findings.is_empty() {
+            let locations = findings
+                .iter()
+                .map(|f| format!("{}:{} ({})", f.path, f.line.unwrap_or(0), f.description))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(i18n::tf(
+                i18n::Msg::SecretsBeforeApproval,
+                &[&findings.len(), &locations],
+            ));
+        }
+    }
+
+    let reviewed_commit = finalize_pending_review(
         &owner,
         &repo,
         number,
@@ -444,7 +905,17 @@ async fn cmd_submit_pending_review(
         body.as_deref(),
     )
     .await
-    .map_err(|err| err.to_string())
+    .map_err(|err| err.to_string())?;
+    record_turnaround(&owner, &repo, number, reviewed_commit.as_deref()).await;
+    metrics::record_usage(metrics::UsageKind::ReviewSubmitted);
+
+    hooks::dispatch(
+        hooks::HookEvent::ReviewSubmitted,
+        hooks::HookContext::new(hooks::HookEvent::ReviewSubmitted, &owner, &repo, number)
+            .with("review_event", &event)
+            .with("review_id", review_id),
+    );
+    Ok(())
 }
 
 #[tauri::command]
@@ -464,16 +935,22 @@ fn open_devtools_impl(_window: tauri::WebviewWindow) -> Result<(), String> {
 }
 
 #[tauri::command]
-async fn cmd_local_start_review(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-    commit_id: String,
-    body: Option<String>,
-    local_folder: Option<String>,
-) -> Result<ReviewMetadata, String> {
+async fn cmd_local_start_review(args: LocalStartReviewArgs) -> Result<ReviewMetadata, String> {
+    let LocalStartReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        commit_id,
+        body,
+        local_folder,
+    } = args;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
-    storage
+    let is_new = storage
+        .get_review_metadata(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?
+        .is_none();
+
+    let metadata = storage
         .start_review(
             &owner,
             &repo,
@@ -482,22 +959,56 @@ async fn cmd_local_start_review(
             body.as_deref(),
             local_folder.as_deref(),
         )
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    if is_new {
+        metrics::record_usage(metrics::UsageKind::ReviewStarted);
+        hooks::dispatch(
+            hooks::HookEvent::ReviewStarted,
+            hooks::HookContext::new(hooks::HookEvent::ReviewStarted, &owner, &repo, pr_number)
+                .with("commit_id", &commit_id),
+        );
+    }
+    Ok(metadata)
 }
 
-#[tauri::command]
-async fn cmd_local_add_comment(
-    owner: String,
-    repo: String,
+/// Refuse a line comment on the new side of a file the PR deletes, going by the PR's
+/// own file list rather than anything the caller says about the file. Only right-side
+/// line comments on GitHub PRs can be refused, so only those wait for the list.
+async fn ensure_side_exists(
+    owner: &str,
+    repo: &str,
     pr_number: u64,
-    file_path: String,
-    line_number: Option<u64>,
-    side: String,
-    body: String,
-    commit_id: String,
-    in_reply_to_id: Option<i64>,
-    local_folder: Option<String>,
-) -> Result<ReviewComment, String> {
+    file_path: &str,
+    side: &str,
+    line_number: u64,
+) -> Result<(), String> {
+    if owner == command_args::LOCAL_OWNER || side != "RIGHT" || line_number == 0 {
+        return Ok(());
+    }
+    let files = auth::fetch_pull_request_files(owner, repo, pr_number)
+        .await
+        .map_err(|e| e.to_string())?;
+    models::ensure_side_exists(&files, file_path, side, line_number).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_local_add_comment(args: LocalAddCommentArgs) -> Result<ReviewComment, String> {
+    let LocalAddCommentArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        file_path: RepoPath(file_path),
+        line_number,
+        side,
+        body,
+        commit_id,
+        in_reply_to_id,
+        local_folder,
+        suggestion,
+    } = args;
+    ensure_side_exists(&owner, &repo, pr_number, &file_path, &side, line_number.unwrap_or(0))
+        .await?;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
 
     // Ensure there is review metadata for log output, and persist the local folder path if provided.
@@ -512,29 +1023,281 @@ async fn cmd_local_add_comment(
         )
         .map_err(|e| e.to_string())?;
 
-    storage
-        .add_comment(
-            &owner,
-            &repo,
-            pr_number,
-            &file_path,
-            line_number.unwrap_or(0), // Use 0 for file-level comments
-            &side,
-            &body,
-            &commit_id,
-            in_reply_to_id,
-        )
-        .await
-        .map_err(|e| e.to_string())
-}
-
+    add_local_comment(
+        storage,
+        &owner,
+        &repo,
+        pr_number,
+        &file_path,
+        line_number.unwrap_or(0), // Use 0 for file-level comments
+        &side,
+        &body,
+        &commit_id,
+        in_reply_to_id,
+        suggestion,
+    )
+    .await
+}
+
+/// Store a comment in the local review, which must already be started, and tell
+/// everything that follows comments: usage metrics, anchor capture and `CommentAdded`
+/// hooks. Every way of adding a local comment goes through here.
+#[allow(clippy::too_many_arguments)]
+async fn add_local_comment(
+    storage: &review_storage::ReviewStorage,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    file_path: &str,
+    line_number: u64,
+    side: &str,
+    body: &str,
+    commit_id: &str,
+    in_reply_to_id: Option<i64>,
+    suggestion: Option<models::Suggestion>,
+) -> Result<ReviewComment, String> {
+    // Checked before anything is stored, so a bad suggestion leaves no comment behind
+    if let Some(suggestion) = &suggestion {
+        suggestion.validate_for_side(side).map_err(|e| e.to_string())?;
+    }
+    let comment = storage
+        .add_comment(
+            owner,
+            repo,
+            pr_number,
+            file_path,
+            line_number,
+            side,
+            body,
+            commit_id,
+            in_reply_to_id,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let comment = match suggestion {
+        Some(suggestion) => storage
+            .set_comment_suggestion(comment.id, Some(&suggestion))
+            .await
+            .map_err(|e| e.to_string())?,
+        None => comment,
+    };
+
+    metrics::record_usage(metrics::UsageKind::CommentAdded);
+    comment_anchors::spawn_capture(owner.to_string(), repo.to_string(), pr_number);
+    hooks::dispatch(
+        hooks::HookEvent::CommentAdded,
+        hooks::HookContext::new(hooks::HookEvent::CommentAdded, owner, repo, pr_number)
+            .with("file_path", file_path)
+            .with("line_number", line_number)
+            .with("side", side)
+            .with("comment_body", body),
+    );
+    Ok(comment)
+}
+
+/// Add a local comment from a quick-comment template in one call, starting the review at
+/// the PR's head commit if none is in progress.
+#[tauri::command]
+async fn cmd_add_quick_comment(args: AddQuickCommentArgs) -> Result<ReviewComment, String> {
+    let AddQuickCommentArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+        path: RepoPath(path),
+        line,
+        template_id,
+    } = args;
+    ensure_side_exists(&owner, &repo, pr, &path, "RIGHT", line.unwrap_or(0)).await?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let config = quick_comments::load_config(storage).map_err(|e| e.to_string())?;
+    let template = config.find(&template_id).map_err(|e| e.to_string())?;
+    let body = template.render(&quick_comments::CommentContext {
+        owner: &owner,
+        repo: &repo,
+        pr_number: pr,
+        path: &path,
+        line,
+    });
+
+    let commit_id = match storage
+        .get_review_metadata(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?
+    {
+        Some(metadata) => metadata.commit_id,
+        None => {
+            let head_sha = auth::fetch_pull_request_metadata(&owner, &repo, pr)
+                .await
+                .map_err(|e| e.to_string())?
+                .head_sha;
+            storage
+                .start_review(&owner, &repo, pr, &head_sha, None, None)
+                .map_err(|e| e.to_string())?
+                .commit_id
+        }
+    };
+
+    let line = line.unwrap_or(0);
+    let comment = add_local_comment(
+        storage, &owner, &repo, pr, &path, line, "RIGHT", &body, &commit_id, None, None,
+    )
+    .await?;
+    let comment = match template.category.as_deref() {
+        Some(category) => {
+            storage
+                .set_comment_category(comment.id, Some(category))
+                .await
+                .map_err(|e| e.to_string())?;
+            storage
+                .get_comment(comment.id)
+                .map_err(|e| e.to_string())?
+                .unwrap_or(comment)
+        }
+        None => comment,
+    };
+    Ok(comment)
+}
+
+#[tauri::command]
+fn cmd_get_quick_comment_templates() -> Result<quick_comments::QuickCommentConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    quick_comments::load_config(storage).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_set_quick_comment_templates(
+    args: SetQuickCommentTemplatesArgs,
+) -> Result<quick_comments::QuickCommentConfig, String> {
+    let SetQuickCommentTemplatesArgs { config } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    quick_comments::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+#[tauri::command]
+fn cmd_list_comment_templates() -> Result<Vec<review_storage::CommentTemplate>, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage.list_comment_templates().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_create_comment_template(
+    args: CreateCommentTemplateArgs,
+) -> Result<review_storage::CommentTemplate, String> {
+    let CreateCommentTemplateArgs { name, body, category } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .create_comment_template(&name, &body, category.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_update_comment_template(
+    args: UpdateCommentTemplateArgs,
+) -> Result<review_storage::CommentTemplate, String> {
+    let UpdateCommentTemplateArgs { id, name, body, category } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .update_comment_template(id, &name, &body, category.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a comment template; `false` when there was none with that id.
+#[tauri::command]
+fn cmd_delete_comment_template(args: DeleteCommentTemplateArgs) -> Result<bool, String> {
+    let DeleteCommentTemplateArgs { id } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage.delete_comment_template(id).map_err(|e| e.to_string())
+}
+
+/// A template with its placeholders filled in for where the comment goes, ready to insert
+/// into the comment editor.
+#[tauri::command]
+fn cmd_render_comment_template(
+    args: RenderCommentTemplateArgs,
+) -> Result<review_storage::CommentTemplate, String> {
+    let RenderCommentTemplateArgs {
+        id,
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+        path: RepoPath(path),
+        line,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let mut template = storage
+        .get_comment_template(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::tf(i18n::Msg::CommentTemplateNotFound, &[&id]))?;
+    template.body = quick_comments::fill_placeholders(
+        &template.body,
+        &quick_comments::CommentContext {
+            owner: &owner,
+            repo: &repo,
+            pr_number: pr,
+            path: &path,
+            line,
+        },
+    );
+    Ok(template)
+}
+
+#[tauri::command]
+fn cmd_get_review_profiles() -> Result<review_profiles::ReviewProfileConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    review_profiles::load_config(storage).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_set_review_profiles(
+    args: SetReviewProfilesArgs,
+) -> Result<review_profiles::ReviewProfileConfig, String> {
+    let SetReviewProfilesArgs { config } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    review_profiles::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// The profile chosen for a PR with the templates it offers; `None` when none is.
+#[tauri::command]
+fn cmd_get_active_profile(
+    args: GetActiveProfileArgs,
+) -> Result<Option<review_profiles::ActiveProfile>, String> {
+    let GetActiveProfileArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    review_profiles::active_profile(storage, &owner, &repo, pr_number).map_err(|e| e.to_string())
+}
+
+/// Switch the profile used on a PR, e.g. from an accessibility pass to a style pass.
+/// `profile: None` goes back to running every check.
+#[tauri::command]
+fn cmd_set_active_profile(
+    args: SetActiveProfileArgs,
+) -> Result<Option<review_profiles::ActiveProfile>, String> {
+    let SetActiveProfileArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        profile,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    review_profiles::set_active_profile(storage, &owner, &repo, pr_number, profile.as_deref())
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn cmd_local_update_review_commit(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-    new_commit_id: String,
+    args: LocalUpdateReviewCommitArgs,
 ) -> Result<ReviewMetadata, String> {
+    let LocalUpdateReviewCommitArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        new_commit_id,
+    } = args;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
     storage
         .update_review_commit(&owner, &repo, pr_number, &new_commit_id)
@@ -543,12 +1306,15 @@ async fn cmd_local_update_review_commit(
 
 #[tauri::command]
 async fn cmd_local_update_comment_file_path(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-    old_path: String,
-    new_path: String,
+    args: LocalUpdateCommentFilePathArgs,
 ) -> Result<usize, String> {
+    let LocalUpdateCommentFilePathArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        old_path: RepoPath(old_path),
+        new_path: RepoPath(new_path),
+    } = args;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
     storage
         .update_comment_file_path(&owner, &repo, pr_number, &old_path, &new_path)
@@ -557,10 +1323,8 @@ async fn cmd_local_update_comment_file_path(
 }
 
 #[tauri::command]
-async fn cmd_local_update_comment(
-    comment_id: i64,
-    body: String,
-) -> Result<ReviewComment, String> {
+async fn cmd_local_update_comment(args: LocalUpdateCommentArgs) -> Result<ReviewComment, String> {
+    let LocalUpdateCommentArgs { comment_id, body } = args;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
     storage
         .update_comment(comment_id, &body)
@@ -568,8 +1332,22 @@ async fn cmd_local_update_comment(
         .map_err(|e| e.to_string())
 }
 
+/// Attach a suggested change to a draft comment, or remove it with `None`.
+#[tauri::command]
+async fn cmd_local_set_comment_suggestion(
+    args: LocalSetCommentSuggestionArgs,
+) -> Result<ReviewComment, String> {
+    let LocalSetCommentSuggestionArgs { comment_id, suggestion } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .set_comment_suggestion(comment_id, suggestion.as_ref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
-async fn cmd_local_delete_comment(comment_id: i64) -> Result<(), String> {
+async fn cmd_local_delete_comment(args: LocalDeleteCommentArgs) -> Result<(), String> {
+    let LocalDeleteCommentArgs { comment_id } = args;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
     storage
         .delete_comment(comment_id)
@@ -577,14 +1355,33 @@ async fn cmd_local_delete_comment(comment_id: i64) -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Post a draft line comment in the PR conversation instead, with its file and line
+/// quoted above the text.
 #[tauri::command]
-async fn cmd_github_update_comment(
-    owner: String,
-    repo: String,
-    comment_id: u64,
-    body: String,
-) -> Result<(), String> {
+async fn cmd_convert_to_general_comment(
+    args: ConvertToGeneralCommentArgs,
+) -> Result<ReviewComment, String> {
+    let ConvertToGeneralCommentArgs { comment_id } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let comment = storage
+        .get_comment(comment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::tf(i18n::Msg::CommentNotFound, &[&comment_id]))?;
+    if comment.general {
+        return Ok(comment);
+    }
+    let body = quote::general_comment_body(&comment.file_path, comment.line_number, &comment.body);
+    storage
+        .convert_to_general_comment(comment_id, &body)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_github_update_comment(args: GithubUpdateCommentArgs) -> Result<(), String> {
     use auth::require_token;
+
+    let GithubUpdateCommentArgs { owner: Owner(owner), repo: Repo(repo), comment_id, body } = args;
     let token = require_token().map_err(|e| e.to_string())?;
     github::update_review_comment(&token, &owner, &repo, comment_id, &body)
         .await
@@ -592,300 +1389,3074 @@ async fn cmd_github_update_comment(
 }
 
 #[tauri::command]
-async fn cmd_github_delete_comment(
-    owner: String,
-    repo: String,
-    comment_id: u64,
-) -> Result<(), String> {
+async fn cmd_github_delete_comment(args: GithubDeleteCommentArgs) -> Result<(), String> {
     use auth::require_token;
+
+    let GithubDeleteCommentArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        comment_id,
+        confirmation_token,
+    } = args;
+    let action = confirmations::DestructiveAction::DeleteGithubComment {
+        owner: owner.clone(),
+        repo: repo.clone(),
+        comment_id,
+    };
+    confirmations::confirmations()
+        .redeem(&confirmation_token, &action)
+        .map_err(|e| e.to_string())?;
     let token = require_token().map_err(|e| e.to_string())?;
     github::delete_review_comment(&token, &owner, &repo, comment_id)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Heading anchors the PR's markdown changes remove, and the in-repo links at the head
+/// commit that still point at them.
+#[tauri::command]
+async fn cmd_get_anchor_impact(
+    args: GetAnchorImpactArgs,
+) -> Result<anchor_impact::AnchorImpactReport, String> {
+    let GetAnchorImpactArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+        base_sha,
+        head_sha,
+    } = args;
+    auth::anchor_impact(&owner, &repo, number, &base_sha, &head_sha)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// A translated file and the source file it mirrors (`docs/ja/...` for `docs/en/...`)
+/// at `reference`, so both can be shown side by side.
+#[tauri::command]
+async fn cmd_get_localized_pair(
+    args: GetLocalizedPairArgs,
+) -> Result<localization::LocalizedPair, String> {
+    let GetLocalizedPairArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        reference,
+        path: RepoPath(path),
+        source_locale,
+    } = args;
+    let source_locale = source_locale
+        .unwrap_or_else(|| localization::DEFAULT_SOURCE_LOCALE.to_string());
+    auth::fetch_localized_pair(&owner, &repo, &reference, &path, &source_locale)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_fetch_file_content(args: FetchFileContentArgs) -> Result<String, String> {
+    use auth::require_token;
+
+    let FetchFileContentArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        reference,
+        path: RepoPath(path),
+    } = args;
+    let token = require_token().map_err(|e| e.to_string())?;
+    github::fetch_file_content(&token, &owner, &repo, &reference, &path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_local_get_comments(args: LocalGetCommentsArgs) -> Result<Vec<ReviewComment>, String> {
+    let LocalGetCommentsArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .get_comments(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_local_get_review_metadata(
+    args: LocalGetReviewMetadataArgs,
+) -> Result<Option<ReviewMetadata>, String> {
+    let LocalGetReviewMetadataArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .get_review_metadata(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete trashed reviews older than the retention period in preferences.
+fn purge_expired_trash(storage: &review_storage::ReviewStorage) -> Result<usize, String> {
+    let retention_days = settings::load_preferences(storage)
+        .map_err(|e| e.to_string())?
+        .trash_retention_days;
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days));
+    storage
+        .purge_trash(&cutoff.to_rfc3339())
+        .map_err(|e| e.to_string())
+}
+
+/// Set the deadline of a local review (`YYYY-MM-DD` or RFC 3339), or clear it with `None`.
+#[tauri::command]
+fn cmd_local_set_review_due_date(
+    args: LocalSetReviewDueDateArgs,
+) -> Result<ReviewMetadata, String> {
+    let LocalSetReviewDueDateArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        due_at,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let due_at = due_at
+        .as_deref()
+        .map(workload::normalize_due_at)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    storage
+        .set_review_due_date(&owner, &repo, pr_number, due_at.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Cleared and abandoned local reviews that can still be restored.
+#[tauri::command]
+fn cmd_list_trashed_reviews() -> Result<Vec<review_storage::TrashedReview>, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    purge_expired_trash(storage)?;
+    storage.list_trashed_reviews().map_err(|e| e.to_string())
+}
+
+/// Find local reviews of merged or closed PRs and move them to the trash, or with
+/// `dry_run` only list them, e.g. to ask first.
+#[tauri::command]
+async fn cmd_prune_stale_reviews(
+    args: PruneStaleReviewsArgs,
+) -> Result<Vec<stale_reviews::StaleReview>, String> {
+    let PruneStaleReviewsArgs { dry_run } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let stale = stale_reviews::find_stale(storage)
+        .await
+        .map_err(|e| e.to_string())?;
+    if dry_run {
+        return Ok(stale);
+    }
+    stale_reviews::clear(storage, stale)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restore the most recently cleared or abandoned local review of a PR, with its comments.
+#[tauri::command]
+fn cmd_restore_cleared_review(args: RestoreClearedReviewArgs) -> Result<ReviewMetadata, String> {
+    let RestoreClearedReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    purge_expired_trash(storage)?;
+    storage
+        .restore_trashed_review(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())
+}
+
+/// Mark a PR's comments as seen up to `seen_at` (now when omitted), so later loads flag
+/// only what was posted after it. Returns the stored timestamp.
+#[tauri::command]
+fn cmd_mark_pull_request_seen(args: MarkPullRequestSeenArgs) -> Result<String, String> {
+    let MarkPullRequestSeenArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        seen_at,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let seen_at = visits::normalize_seen_at(seen_at.as_deref()).map_err(|e| e.to_string())?;
+    storage
+        .record_visit(&owner, &repo, pr_number, &seen_at)
+        .map_err(|e| e.to_string())?;
+    Ok(seen_at)
+}
+
+/// Pin a GitHub (`source = "github"`) or local (`source = "local"`) comment on a PR.
+#[tauri::command]
+fn cmd_add_bookmark(args: AddBookmarkArgs) -> Result<review_storage::Bookmark, String> {
+    let AddBookmarkArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        source,
+        comment_id,
+        note,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .add_bookmark(&owner, &repo, pr_number, &source, comment_id, note.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_remove_bookmark(args: RemoveBookmarkArgs) -> Result<bool, String> {
+    let RemoveBookmarkArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        source,
+        comment_id,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .remove_bookmark(&owner, &repo, pr_number, &source, comment_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_list_bookmarks(args: ListBookmarksArgs) -> Result<Vec<review_storage::Bookmark>, String> {
+    let ListBookmarksArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .get_bookmarks(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())
+}
+
+/// Mark one hunk of a file reviewed, or clear the mark, for progress finer than the
+/// whole-file viewed flag. `hunk` is any key the caller uses to name the hunk.
+#[tauri::command]
+fn cmd_mark_hunk_reviewed(
+    args: MarkHunkReviewedArgs,
+) -> Result<models::FileReviewProgress, String> {
+    let MarkHunkReviewedArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        file_path: RepoPath(file_path),
+        hunk,
+        hunk_count,
+        reviewed,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .mark_hunk_reviewed(&owner, &repo, pr_number, &file_path, &hunk, hunk_count, reviewed)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_get_hunk_progress(
+    args: GetHunkProgressArgs,
+) -> Result<Vec<models::FileReviewProgress>, String> {
+    let GetHunkProgressArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .get_hunk_progress(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_local_abandon_review(args: LocalAbandonReviewArgs) -> Result<(), String> {
+    let LocalAbandonReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .abandon_review(&owner, &repo, pr_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Ask the user in a native dialog to confirm one destructive call, saying exactly what
+/// will be deleted, and issue a short-lived, single-use token for it when they agree.
+/// `None` when they decline. The webview cannot answer the dialog, so it cannot mint a
+/// token without the user.
+#[tauri::command]
+async fn cmd_request_confirmation(
+    app: tauri::AppHandle,
+    args: RequestConfirmationArgs,
+) -> Result<Option<confirmations::ConfirmationToken>, String> {
+    let RequestConfirmationArgs { action } = args;
+    let local_comments = match &action {
+        confirmations::DestructiveAction::ClearLocalReview { owner, repo, pr_number } => {
+            let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+            let comments = storage
+                .get_comments(owner, repo, *pr_number)
+                .map_err(|e| e.to_string())?;
+            Some(comments.len())
+        }
+        _ => None,
+    };
+    let description = action.describe(local_comments);
+    if !dialogs::confirm(&app, description.clone())
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(None);
+    }
+    confirmations::confirmations()
+        .issue(action, description)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_local_clear_review(args: LocalClearReviewArgs) -> Result<(), String> {
+    let LocalClearReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        pr_title,
+        confirmation_token,
+    } = args;
+    let action = confirmations::DestructiveAction::ClearLocalReview {
+        owner: owner.clone(),
+        repo: repo.clone(),
+        pr_number,
+    };
+    confirmations::confirmations()
+        .redeem(&confirmation_token, &action)
+        .map_err(|e| e.to_string())?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .clear_review(&owner, &repo, pr_number, pr_title.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Remove the chosen kinds of app data, from "log out but keep my drafts" up to removing
+/// every trace of the app.
+#[tauri::command]
+async fn cmd_reset_app_data(app: tauri::AppHandle, args: ResetAppDataArgs) -> Result<(), String> {
+    let ResetAppDataArgs { options, confirmation_token } = args;
+    confirmations::confirmations()
+        .redeem(
+            &confirmation_token,
+            &confirmations::DestructiveAction::ResetAppData { options },
+        )
+        .map_err(|e| e.to_string())?;
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    reset::reset_app_data(storage, &data_dir, &options)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if options.settings {
+        let defaults = Preferences::default();
+        i18n::set_locale(defaults.locale);
+        time_format::set_zone(defaults.zone().unwrap_or_default());
+        metrics::set_usage_enabled(defaults.usage_metrics);
+    }
+    Ok(())
+}
+
+/// Whether this run started in safe mode after repeated crashes at startup, with the last
+/// crash. The `safe-mode` event carries the same status but may fire before the UI listens.
+#[tauri::command]
+fn cmd_get_safe_mode_status() -> safe_mode::SafeModeStatus {
+    safe_mode::status()
+}
+
+/// Run SQLite's integrity check on the review database.
+#[tauri::command]
+fn cmd_check_database() -> Result<safe_mode::DatabaseCheck, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    safe_mode::check_database(storage).map_err(|e| e.to_string())
+}
+
+/// Drop cached GitHub data and the saved session, keeping local reviews and settings.
+#[tauri::command]
+fn cmd_purge_cache() -> Result<(), String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    safe_mode::purge_cache(storage).map_err(|e| e.to_string())
+}
+
+/// Start normally from the next launch on.
+#[tauri::command]
+fn cmd_leave_safe_mode() -> Result<(), String> {
+    safe_mode::reset_crash_count().map_err(|e| e.to_string())
+}
+
+/// Remember when this review was requested and submitted, for turnaround stats, and the
+/// commit it was made on, for `cmd_get_changes_since_my_review`. Failures are logged and
+/// never fail the submission itself.
+async fn record_turnaround(owner: &str, repo: &str, number: u64, commit_id: Option<&str>) {
+    let submitted_at = chrono::Utc::now().to_rfc3339();
+    let requested_at = auth::review_requested_at(owner, repo, number)
+        .await
+        .unwrap_or_else(|err| {
+            tracing::warn!("Could not read review request time for {owner}/{repo}#{number}: {err}");
+            None
+        });
+    let recorded = review_storage::get_storage().and_then(|storage| {
+        storage.record_turnaround(owner, repo, number, requested_at.as_deref(), &submitted_at)?;
+        match commit_id {
+            Some(commit_id) => {
+                storage.record_review_commit(owner, repo, number, commit_id, &submitted_at)
+            }
+            None => Ok(()),
+        }
+    });
+    if let Err(err) = recorded {
+        tracing::warn!("Could not record review turnaround: {err}");
+    }
+}
+
+/// Outbound content filter: blocking findings always stop a submission, warnings stop
+/// it until the user confirms. The repository's review policy, when it has one, adds
+/// banned phrases and may require every comment to be categorized.
+fn check_outbound_content(
+    storage: &review_storage::ReviewStorage,
+    repo_policy: Option<&repo_config::RepoReviewConfig>,
+    body: Option<&str>,
+    comments: &[ReviewComment],
+    acknowledge_warnings: bool,
+) -> Result<(), String> {
+    let local = content_filter::load_config(storage).map_err(|e| e.to_string())?;
+    let effective = repo_config::merge(local, repo_policy);
+
+    let uncategorized =
+        repo_config::comments_missing_category(&effective.required_categories, comments);
+    if !uncategorized.is_empty() {
+        let locations = uncategorized
+            .iter()
+            .map(|c| format!("{}:{}", c.file_path, c.line_number))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(i18n::tf(
+            i18n::Msg::RepoConfigCategoryRequired,
+            &[
+                &repo_config::REPO_CONFIG_PATH,
+                &effective.required_categories.join(", "),
+                &locations,
+            ],
+        ));
+    }
+
+    let filter_report = content_filter::check_review(&effective.content_filter, body, comments);
+    if !filter_report.blocked.is_empty() {
+        return Err(i18n::tf(
+            i18n::Msg::ContentFilterBlocked,
+            &[&content_filter::FilterReport::describe(&filter_report.blocked)],
+        ));
+    }
+    if !filter_report.warnings.is_empty() && !acknowledge_warnings {
+        return Err(i18n::tf(
+            i18n::Msg::ContentFilterWarning,
+            &[&content_filter::FilterReport::describe(&filter_report.warnings)],
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn cmd_submit_local_review(
+    app: tauri::AppHandle,
+    args: SubmitLocalReviewArgs,
+) -> Result<submission_report::SubmissionReport, String> {
+    let SubmitLocalReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        event,
+        body,
+        acknowledge_warnings,
+        mode,
+    } = args;
+    let parked = ParkedSubmission {
+        owner: owner.clone(),
+        repo: repo.clone(),
+        pr_number,
+        review_id: None,
+        event: event.clone(),
+        body: body.clone(),
+        mode,
+        login: current_login(),
+        parked_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let result = submit_local_review(
+        &app,
+        owner,
+        repo,
+        pr_number,
+        event,
+        body,
+        acknowledge_warnings,
+        mode,
+    )
+    .await;
+    if !matches!(&result, Ok(report) if report.failed.is_empty()) {
+        metrics::record_usage(metrics::UsageKind::SubmissionFailed);
+    }
+    let report = park_on_auth_expiry(&parked, result)?;
+    park_report_on_auth_expiry(&parked, report)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn submit_local_review(
+    app: &tauri::AppHandle,
+    owner: String,
+    repo: String,
+    pr_number: u64,
+    event: Option<String>,
+    body: Option<String>,
+    acknowledge_warnings: Option<bool>,
+    mode: Option<comment_export::SubmissionMode>,
+) -> Result<submission_report::SubmissionReport, String> {
+    use auth::submit_review_with_comments;
+    use auth::fetch_pull_request_details;
+    
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    
+    // Get metadata and comments
+    let metadata = storage
+        .get_review_metadata(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::t(i18n::Msg::NoPendingReview).to_string())?;
+    
+    let comments = storage
+        .get_comments(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?;
+    let event = resolve_review_event(
+        event.as_deref(),
+        body.as_deref().or(metadata.body.as_deref()),
+    )?;
+
+    let repo_policy = auth::repo_review_config(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())?;
+    check_outbound_content(
+        storage,
+        repo_policy.as_ref(),
+        body.as_deref().or(metadata.body.as_deref()),
+        &comments,
+        acknowledge_warnings.unwrap_or(false),
+    )?;
+    
+    let (batch, review_url, reviewed_commit, snapshot) = if mode.unwrap_or_default()
+        == comment_export::SubmissionMode::Consolidated
+    {
+        // One review body, no line anchors: submitted against the current head
+        let head_sha = auth::fetch_pull_request_metadata(&owner, &repo, pr_number)
+            .await
+            .map_err(|e| e.to_string())?
+            .head_sha;
+        let review_body = comment_export::render_consolidated_review(
+            body.as_deref().or(metadata.body.as_deref()),
+            &comments,
+            &github::web_base_url(),
+            &owner,
+            &repo,
+        );
+        let review = github::NewReview {
+            commit_id: &head_sha,
+            event: &event,
+            body: &review_body,
+            comments: &[],
+        };
+        let review_id = auth::create_review(&owner, &repo, pr_number, &review)
+            .await
+            .map_err(|e| e.to_string())?;
+        let batch = submission_report::CommentBatch {
+            succeeded_ids: comments.iter().map(|c| c.id).collect(),
+            ..Default::default()
+        };
+        let review_url = permalinks::comment_url(
+            &github::web_base_url(),
+            &owner,
+            &repo,
+            pr_number,
+            permalinks::CommentKind::Review,
+            review_id,
+        )
+        .ok();
+        (batch, review_url, head_sha, None)
+    } else {
+        // Check if PR has been updated since comments were created
+        let pr_detail = fetch_pull_request_details(&owner, &repo, pr_number, None)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // GitHub rejects the whole batch over one comment on a file the PR no longer touches
+        let orphaned = orphans::find_orphans(&comments, &pr_detail.files);
+        if !orphaned.is_empty() {
+            let report = submission_report::SubmissionReport {
+                owner: owner.clone(),
+                repo: repo.clone(),
+                pr_number,
+                posted: 0,
+                failed: Vec::new(),
+                review_url: None,
+                summary: Some(orphans::describe(&orphaned)),
+                submitted_at: chrono::Utc::now().to_rfc3339(),
+                orphaned,
+            };
+            save_submission_report(storage, &report);
+            return Ok(report);
+        }
+
+        let commit_id_to_use = if pr_detail.head_sha != metadata.commit_id {
+            tracing::warn!(
+                "⚠️  WARNING: PR has been updated since you created these comments!\n   \
+                Your comments were created for: {}\n   \
+                Current PR head commit:      {}\n   \
+                Using CURRENT commit for submission to maximize success rate.",
+                metadata.commit_id, pr_detail.head_sha
+            );
+            &pr_detail.head_sha
+        } else {
+            &metadata.commit_id
+        };
+
+        // Submit to GitHub, one comment at a time; general comments go to the conversation
+        let (general, anchored): (Vec<ReviewComment>, Vec<ReviewComment>) =
+            comments.iter().cloned().partition(|c| c.general);
+        let mut batch = submit_review_with_comments(
+            app,
+            &owner,
+            &repo,
+            pr_number,
+            commit_id_to_use,
+            body.as_deref().or(metadata.body.as_deref()),
+            Some(&event),
+            &anchored,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        for comment in &general {
+            match auth::post_conversation_comment(&owner, &repo, pr_number, &comment.body).await {
+                Ok(_) => batch.succeeded_ids.push(comment.id),
+                Err(e) => batch
+                    .failed
+                    .push(submission_report::FailedComment::new(comment.id, e.to_string())),
+            }
+        }
+        if !general.is_empty() {
+            batch.summary = submission_report::summarize(
+                batch.succeeded_ids.len(),
+                comments.len(),
+                &batch.failed,
+            );
+        }
+        let pr_url = format!("{}/{owner}/{repo}/pull/{pr_number}", github::web_base_url());
+        let snapshot = review_snapshot::ReviewSnapshot::from_detail(&pr_detail);
+        (batch, Some(pr_url), pr_detail.head_sha.clone(), Some(snapshot))
+    };
+    
+    // Delete only successfully posted comments from DB (but they remain in log file)
+    for &comment_id in &batch.succeeded_ids {
+        storage
+            .delete_comment_preserve_log(comment_id)
+            .map_err(|e| e.to_string())?;
+    }
+    
+    // If all comments were posted, mark the review as submitted
+    let remaining_comments = storage
+        .get_comments(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?;
+    
+    if remaining_comments.is_empty() {
+        let snapshot = match snapshot {
+            Some(snapshot) => Some(snapshot),
+            None => capture_review_snapshot(&owner, &repo, pr_number).await,
+        };
+        storage
+            .mark_review_submitted(&owner, &repo, pr_number, snapshot.as_ref())
+            .await
+            .map_err(|e| e.to_string())?;
+        record_turnaround(&owner, &repo, pr_number, Some(&reviewed_commit)).await;
+        metrics::record_usage(metrics::UsageKind::ReviewSubmitted);
+
+        hooks::dispatch(
+            hooks::HookEvent::ReviewSubmitted,
+            hooks::HookContext::new(hooks::HookEvent::ReviewSubmitted, &owner, &repo, pr_number)
+                .with("review_event", &event)
+                .with("comment_count", comments.len()),
+        );
+    }
+    
+    let report = submission_report::SubmissionReport {
+        owner: owner.clone(),
+        repo: repo.clone(),
+        pr_number,
+        posted: batch.succeeded_ids.len(),
+        failed: batch.failed,
+        review_url,
+        summary: batch.summary,
+        submitted_at: chrono::Utc::now().to_rfc3339(),
+        orphaned: Vec::new(),
+    };
+    save_submission_report(storage, &report);
+    Ok(report)
+}
+
+/// The PR as it stands now, for the log of a review being submitted. A failure here is
+/// logged and the log goes without it.
+async fn capture_review_snapshot(
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+) -> Option<review_snapshot::ReviewSnapshot> {
+    match auth::fetch_pull_request_details(owner, repo, pr_number, None).await {
+        Ok(detail) => Some(review_snapshot::ReviewSnapshot::from_detail(&detail)),
+        Err(e) => {
+            tracing::warn!("Could not capture the PR for the review log: {}", e);
+            None
+        }
+    }
+}
+
+/// Keep the report for `cmd_get_last_submission_report`. A failure here is logged and
+/// never fails the submission.
+fn save_submission_report(
+    storage: &review_storage::ReviewStorage,
+    report: &submission_report::SubmissionReport,
+) {
+    if let Err(e) = settings::save(storage, submission_report::LAST_REPORT_KEY, report) {
+        tracing::warn!("Failed to save submission report: {}", e);
+    }
+}
+
+/// Draft comments on files the PR no longer touches, which a submission would stop on.
+#[tauri::command]
+async fn cmd_find_orphaned_comments(
+    args: FindOrphanedCommentsArgs,
+) -> Result<Vec<orphans::OrphanedComment>, String> {
+    let FindOrphanedCommentsArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let comments = storage
+        .get_comments(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?;
+    if comments.is_empty() {
+        return Ok(Vec::new());
+    }
+    let files = auth::fetch_pull_request_files(&owner, &repo, pr_number)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(orphans::find_orphans(&comments, &files))
+}
+
+#[tauri::command]
+fn cmd_get_last_submission_report() -> Result<Option<submission_report::SubmissionReport>, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    settings::load(storage, submission_report::LAST_REPORT_KEY).map_err(|e| e.to_string())
+}
+
+/// Outcome of resuming one parked submission.
+#[derive(Debug, Clone, Serialize)]
+struct ResumedSubmission {
+    owner: String,
+    repo: String,
+    pr_number: u64,
+    error: Option<String>,
+}
+
+#[tauri::command]
+fn cmd_list_parked_submissions() -> Result<Vec<ParkedSubmission>, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage.get_parked_submissions().map_err(|e| e.to_string())
+}
+
+/// Retry the parked submissions of the account that is now signed in. Submissions
+/// parked by another GitHub user wait for that user. Warnings are not taken as
+/// acknowledged from before, so a submission that has any is reported and stays parked
+/// until the user submits it again; a submission is only unparked once it succeeds.
+#[tauri::command]
+async fn cmd_resume_parked_submissions(
+    app: tauri::AppHandle,
+) -> Result<Vec<ResumedSubmission>, String> {
+    let login = current_login();
+    if login.is_empty() {
+        return Ok(Vec::new());
+    }
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let parked = storage.get_parked_submissions().map_err(|e| e.to_string())?;
+
+    let mut resumed = Vec::new();
+    for submission in parked.into_iter().filter(|s| s.login == login) {
+        let error = match submission.review_id {
+            Some(review_id) => {
+                let result = submit_pending_review(
+                    submission.owner.clone(),
+                    submission.repo.clone(),
+                    submission.pr_number,
+                    review_id,
+                    submission.event.clone(),
+                    submission.body.clone(),
+                    None,
+                )
+                .await;
+                park_on_auth_expiry(&submission, result).err()
+            }
+            None => {
+                let result = submit_local_review(
+                    &app,
+                    submission.owner.clone(),
+                    submission.repo.clone(),
+                    submission.pr_number,
+                    submission.event.clone(),
+                    submission.body.clone(),
+                    None,
+                    submission.mode,
+                )
+                .await;
+                park_on_auth_expiry(&submission, result)
+                    .and_then(|report| park_report_on_auth_expiry(&submission, report))
+                    .map_or_else(Some, |report| report.summary)
+            }
+        };
+        resumed.push(ResumedSubmission {
+            owner: submission.owner.clone(),
+            repo: submission.repo.clone(),
+            pr_number: submission.pr_number,
+            error,
+        });
+    }
+    Ok(resumed)
+}
+
+#[tauri::command]
+fn cmd_discard_parked_submission(args: DiscardParkedSubmissionArgs) -> Result<bool, String> {
+    let DiscardParkedSubmissionArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .remove_parked_submission(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_local_set_comment_category(args: LocalSetCommentCategoryArgs) -> Result<(), String> {
+    let LocalSetCommentCategoryArgs { comment_id, category } = args;
+    if let Some(category) = category.as_deref() {
+        categories::validate_category(category).map_err(|e| e.to_string())?;
+    }
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    storage
+        .set_comment_category(comment_id, category.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Render the pending local comments of a PR as one markdown task list. With `post`, the
+/// list is also posted as a conversation comment; the local comments are kept either way.
+#[tauri::command]
+async fn cmd_export_comments_as_tasklist(
+    args: ExportCommentsAsTasklistArgs,
+) -> Result<comment_export::TasklistExport, String> {
+    let ExportCommentsAsTasklistArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+        post,
+        acknowledge_warnings,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let comments = storage
+        .get_comments(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?;
+    if comments.is_empty() {
+        return Err(i18n::t(i18n::Msg::NoPendingReview).to_string());
+    }
+    let mut export =
+        comment_export::render_tasklist(&comments, &github::web_base_url(), &owner, &repo);
+
+    if post.unwrap_or(false) {
+        let repo_policy = auth::repo_review_config(&owner, &repo)
+            .await
+            .map_err(|e| e.to_string())?;
+        check_outbound_content(
+            storage,
+            repo_policy.as_ref(),
+            None,
+            &comments,
+            acknowledge_warnings.unwrap_or(false),
+        )?;
+        export.comment_url = Some(
+            auth::post_conversation_comment(&owner, &repo, pr, &export.body)
+                .await
+                .map_err(|e| e.to_string())?,
+        );
+    }
+
+    Ok(export)
+}
+
+/// Write a local review and its comments as markdown, JSON or CSV to a file the user
+/// chooses in a save dialog, to share or archive it outside the app. Returns the path of
+/// the written file, or `None` when the dialog was cancelled.
+#[tauri::command]
+async fn cmd_export_review(
+    app: tauri::AppHandle,
+    args: ExportReviewArgs,
+) -> Result<Option<String>, String> {
+    let ExportReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        format,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let metadata = storage
+        .get_review_metadata(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::t(i18n::Msg::NoPendingReview).to_string())?;
+    // Anchor what was not anchored when written, e.g. while offline
+    if metadata.local_folder.is_none() {
+        let captured = comment_anchors::capture_missing(storage, &owner, &repo, pr_number).await;
+        if let Err(err) = captured {
+            tracing::warn!("Exporting {owner}/{repo}#{pr_number} without some anchors: {err}");
+        }
+    }
+    let comments = storage
+        .get_comments(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?;
+    let anchors = storage
+        .get_comment_anchors(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?;
+    let web_base = github::web_base_url();
+    let content =
+        comment_export::render_review_export(format, &metadata, &comments, &anchors, &web_base)
+            .map_err(|e| e.to_string())?;
+    let file_name = comment_export::export_file_name(&metadata, format);
+    let path = dialogs::save_file(&app, file_name, content)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(path.map(|path| path.display().to_string()))
+}
+
+/// Add the comments of a JSON file the user picks, in the schema `cmd_export_review`
+/// writes, to the local review of a PR. Nothing is imported unless every comment is on a
+/// file the PR changes. `None` when the file dialog was cancelled.
+#[tauri::command]
+async fn cmd_import_review(
+    app: tauri::AppHandle,
+    args: ImportReviewArgs,
+) -> Result<Option<review_import::ReviewImport>, String> {
+    let ImportReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+    } = args;
+    let Some(path) = dialogs::pick_file(&app, &["json"])
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let json = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let comments = review_import::parse_review_json(&json).map_err(|e| e.to_string())?;
+    import_into_review(owner, repo, pr_number, comments).await.map(Some)
+}
+
+/// Add the comments of a CSV or markdown punch list the user picks, one `path, line,
+/// body` per row or task, to the local review of a PR. Nothing is imported unless every
+/// comment is on a file the PR changes. `None` when the file dialog was cancelled.
+#[tauri::command]
+async fn cmd_import_comments(
+    app: tauri::AppHandle,
+    args: ImportCommentsArgs,
+) -> Result<Option<review_import::ReviewImport>, String> {
+    let ImportCommentsArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+        format,
+    } = args;
+    let Some(file) = dialogs::pick_file(&app, format.extensions())
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let content = tokio::fs::read_to_string(&file)
+        .await
+        .map_err(|e| e.to_string())?;
+    let comments =
+        review_import::parse_punch_list(&content, format).map_err(|e| e.to_string())?;
+    import_into_review(owner, repo, pr, comments).await.map(Some)
+}
+
+/// Check imported comments against the PR's files, then add them to its local review,
+/// starting one at the PR's head when there is none.
+async fn import_into_review(
+    owner: String,
+    repo: String,
+    pr_number: u64,
+    comments: Vec<review_import::ImportedComment>,
+) -> Result<review_import::ReviewImport, String> {
+    let files = auth::fetch_pull_request_files(&owner, &repo, pr_number)
+        .await
+        .map_err(|e| e.to_string())?;
+    review_import::validate_comments(&comments, &files).map_err(|e| e.to_string())?;
+
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let head_sha = match storage
+        .get_review_metadata(&owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?
+    {
+        Some(metadata) => metadata.commit_id,
+        None => {
+            auth::fetch_pull_request_metadata(&owner, &repo, pr_number)
+                .await
+                .map_err(|e| e.to_string())?
+                .head_sha
+        }
+    };
+    let imported =
+        review_import::import_comments(storage, &owner, &repo, pr_number, &head_sha, &comments)
+            .await
+            .map_err(|e| e.to_string())?;
+    comment_anchors::spawn_capture(owner, repo, pr_number);
+    Ok(imported)
+}
+
+/// Submit the local review split by comment category: by default blocking comments go
+/// out as a REQUEST_CHANGES review and everything else as a separate COMMENT review.
+/// `mapping` overrides the target per category (`uncategorized` for comments without one).
+#[tauri::command]
+async fn cmd_submit_by_category(
+    app: tauri::AppHandle,
+    args: SubmitByCategoryArgs,
+) -> Result<categories::CategorySubmissionResult, String> {
+    let SubmitByCategoryArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+        mapping,
+        body,
+        acknowledge_warnings,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let metadata = storage
+        .get_review_metadata(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::t(i18n::Msg::NoPendingReview).to_string())?;
+    let comments = storage
+        .get_comments(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?;
+    let body = body.or(metadata.body);
+
+    let repo_policy = auth::repo_review_config(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())?;
+    check_outbound_content(
+        storage,
+        repo_policy.as_ref(),
+        body.as_deref(),
+        &comments,
+        acknowledge_warnings.unwrap_or(false),
+    )?;
+    let plan = categories::plan_submission(&comments, &mapping.unwrap_or_default())
+        .map_err(|e| e.to_string())?;
+
+    // Comments are submitted against the current head, as in cmd_submit_local_review
+    let head_sha = auth::fetch_pull_request_metadata(&owner, &repo, pr)
+        .await
+        .map_err(|e| e.to_string())?
+        .head_sha;
+
+    let mut result = categories::CategorySubmissionResult::default();
+    let mut review_event = "COMMENT";
+
+    // Batches go out in order and stop at the first failure; what was posted before it
+    // is still reported, and its comments are already out of the local review
+    let outcome: Result<(), String> = async {
+        if !plan.request_changes.is_empty() {
+            let review_body = body
+                .clone()
+                .unwrap_or_else(|| i18n::t(i18n::Msg::CategoryRequestChangesBody).to_string());
+            let review = github::NewReview {
+                commit_id: &head_sha,
+                event: "REQUEST_CHANGES",
+                body: &review_body,
+                comments: &plan.request_changes,
+            };
+            result.request_changes_review_id = Some(
+                auth::create_review(&owner, &repo, pr, &review)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            );
+            for comment in &plan.request_changes {
+                storage
+                    .delete_comment_preserve_log(comment.id)
+                    .map_err(|e| e.to_string())?;
+            }
+            review_event = "REQUEST_CHANGES";
+        }
+
+        if !plan.comment.is_empty() {
+            // The review body goes with the first review only
+            let review_body = match (&result.request_changes_review_id, &body) {
+                (None, Some(body)) => body.clone(),
+                _ => i18n::t(i18n::Msg::CategoryCommentBody).to_string(),
+            };
+            let review = github::NewReview {
+                commit_id: &head_sha,
+                event: "COMMENT",
+                body: &review_body,
+                comments: &plan.comment,
+            };
+            result.comment_review_id = Some(
+                auth::create_review(&owner, &repo, pr, &review)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            );
+            for comment in &plan.comment {
+                storage
+                    .delete_comment_preserve_log(comment.id)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+
+        if !plan.single.is_empty() {
+            let batch = auth::submit_review_with_comments(
+                &app,
+                &owner,
+                &repo,
+                pr,
+                &head_sha,
+                None,
+                None,
+                &plan.single,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            result.single_comments_posted = batch.succeeded_ids.len();
+            for comment_id in batch.succeeded_ids {
+                storage
+                    .delete_comment_preserve_log(comment_id)
+                    .map_err(|e| e.to_string())?;
+            }
+            if let Some(summary) = batch.summary {
+                return Err(summary);
+            }
+        }
+        Ok(())
+    }
+    .await;
+    result.error = outcome.err();
+
+    let remaining_comments = storage
+        .get_comments(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?;
+    if remaining_comments.is_empty() {
+        let snapshot = capture_review_snapshot(&owner, &repo, pr).await;
+        storage
+            .mark_review_submitted(&owner, &repo, pr, snapshot.as_ref())
+            .await
+            .map_err(|e| e.to_string())?;
+        record_turnaround(&owner, &repo, pr, Some(&head_sha)).await;
+        metrics::record_usage(metrics::UsageKind::ReviewSubmitted);
+
+        hooks::dispatch(
+            hooks::HookEvent::ReviewSubmitted,
+            hooks::HookContext::new(hooks::HookEvent::ReviewSubmitted, &owner, &repo, pr)
+                .with("review_event", review_event)
+                .with("comment_count", comments.len()),
+        );
+    }
+
+    if result.error.is_some() {
+        metrics::record_usage(metrics::UsageKind::SubmissionFailed);
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+async fn cmd_delete_review(args: DeleteReviewArgs) -> Result<usize, String> {
+    use auth::require_token_for_delete;
+    use github::delete_review;
+
+    let DeleteReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        review_id,
+        confirmation_token,
+    } = args;
+
+    let action = confirmations::DestructiveAction::DeleteReview {
+        owner: owner.clone(),
+        repo: repo.clone(),
+        pr_number,
+        review_id,
+    };
+    confirmations::confirmations()
+        .redeem(&confirmation_token, &action)
+        .map_err(|e| e.to_string())?;
+    let token = require_token_for_delete().map_err(|e| e.to_string())?;
+
+    // GitHub keeps no copy of a deleted pending review, so its drafts go to the trash
+    // first; if they cannot be saved nothing is deleted.
+    let comments = github::get_pending_review_comments(&token, &owner, &repo, pr_number, review_id, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !comments.is_empty() {
+        let head_sha = auth::fetch_pull_request_metadata(&owner, &repo, pr_number)
+            .await
+            .map_err(|e| e.to_string())?
+            .head_sha;
+        let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+        storage
+            .archive_pending_review(&owner, &repo, pr_number, &head_sha, &comments)
+            .map_err(|e| e.to_string())?;
+        info!(
+            "Archived {} comment(s) of pending review {} on {}/{}#{}",
+            comments.len(),
+            review_id,
+            owner,
+            repo,
+            pr_number
+        );
+    }
+
+    delete_review(&token, &owner, &repo, pr_number, review_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(comments.len())
+}
+
+/// Find my past comments across PRs, e.g. everything I said about versioning.
+#[tauri::command]
+async fn cmd_search_my_comments(
+    args: SearchMyCommentsArgs,
+) -> Result<Vec<comment_search::CommentSearchHit>, String> {
+    let SearchMyCommentsArgs { query, repo_filter } = args;
+    auth::search_my_comments(&query, repo_filter.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_get_pending_review_comments(
+    args: GetPendingReviewCommentsArgs,
+) -> Result<Vec<models::PullRequestComment>, String> {
+    use auth::require_token;
+    use github::get_pending_review_comments;
+
+    let GetPendingReviewCommentsArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        review_id,
+        current_login,
+    } = args;
+    
+    let token = require_token().map_err(|e| e.to_string())?;
+    
+    get_pending_review_comments(
+        &token, 
+        &owner, 
+        &repo, 
+        pr_number, 
+        review_id,
+        current_login.as_deref()
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn is_local_folder_review(pr: &models::PrUnderReview) -> bool {
+    pr.owner == "__local__" && pr.repo == "local"
+}
+
+#[tauri::command]
+async fn cmd_get_prs_under_review() -> Result<Vec<models::PrUnderReview>, String> {
+    tracing::info!("cmd_get_prs_under_review called");
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    
+    // Get all review metadata from storage
+    let all_reviews = storage.get_all_review_metadata().map_err(|e| e.to_string())?;
+    tracing::info!("Found {} reviews in storage", all_reviews.len());
+    
+    let roots = local_roots::load_config(storage).unwrap_or_else(|err| {
+        tracing::warn!("Could not load local roots: {err}");
+        Default::default()
+    });
+
+    // Folder walks block on disk I/O, so they run on the blocking pool, all at once
+    let mut walks = tokio::task::JoinSet::new();
+    for (index, metadata) in all_reviews.iter().enumerate() {
+        let is_local_folder = metadata.owner == "__local__" && metadata.repo == "local";
+        if let Some(local_folder) = metadata.local_folder.clone().filter(|_| is_local_folder) {
+            let roots = roots.clone();
+            walks.spawn_blocking(move || {
+                // Folders outside the allowed roots are listed but not walked
+                let count = roots
+                    .check(&resolve_local_directory_path(&local_folder))
+                    .ok()
+                    .and_then(|base| folder_counts::markdown_file_count(&base).ok());
+                (index, count.unwrap_or(0))
+            });
+        }
+    }
+    let mut total_counts = vec![0; all_reviews.len()];
+    while let Some(walk) = walks.join_next().await {
+        match walk {
+            Ok((index, count)) => total_counts[index] = count,
+            Err(err) => tracing::warn!("Counting local folder files failed: {err}"),
+        }
+    }
+
+    let pending_counts = storage.get_all_pending_comment_counts().unwrap_or_else(|err| {
+        tracing::warn!("Could not count pending comments: {err}");
+        std::collections::HashMap::new()
+    });
+    let mut reviewed_files = storage.get_all_hunk_reviewed_files().unwrap_or_else(|err| {
+        tracing::warn!("Could not list hunk-reviewed files: {err}");
+        std::collections::HashMap::new()
+    });
+
+    let mut prs_under_review: Vec<models::PrUnderReview> = all_reviews
+        .into_iter()
+        .zip(total_counts)
+        .map(|(metadata, total_count)| {
+            let key = (metadata.owner.clone(), metadata.repo.clone(), metadata.pr_number);
+            let hunk_reviewed_files = reviewed_files.remove(&key).unwrap_or_default();
+            models::PrUnderReview {
+                owner: metadata.owner.clone(),
+                repo: metadata.repo.clone(),
+                number: metadata.pr_number,
+                title: String::new(), // Will be filled in by frontend
+                has_local_review: true,
+                has_pending_review: false,
+                viewed_count: hunk_reviewed_files.len(),
+                hunk_reviewed_files,
+                total_count,
+                local_folder: metadata.local_folder.clone(),
+                needs_my_attention: None,
+                overdue: metadata
+                    .due_at
+                    .as_deref()
+                    .is_some_and(|due| workload::is_overdue(due, chrono::Utc::now())),
+                due_at: metadata.due_at.clone(),
+                pending_comment_count: pending_counts.get(&key).copied().unwrap_or(0),
+                head_sha: None,
+                state: None,
+                merged: None,
+                locked: None,
+            }
+        })
+        .collect();
+
+    let on_github: Vec<(String, String, u64)> = prs_under_review
+        .iter()
+        .filter(|pr| !is_local_folder_review(pr))
+        .map(|pr| (pr.owner.clone(), pr.repo.clone(), pr.number))
+        .collect();
+    if !on_github.is_empty() {
+        let (attention, states) = tokio::join!(
+            auth::needs_my_attention(&on_github),
+            auth::pull_request_review_states(&on_github),
+        );
+        match attention {
+            Ok(flags) => {
+                let mut flags = flags.into_iter();
+                for pr in prs_under_review.iter_mut().filter(|pr| !is_local_folder_review(pr)) {
+                    pr.needs_my_attention = flags.next().flatten();
+                }
+            }
+            Err(err) => tracing::warn!("Could not check which PRs need attention: {err}"),
+        }
+        match states {
+            Ok(states) => {
+                let mut states = states.into_iter();
+                for pr in prs_under_review.iter_mut().filter(|pr| !is_local_folder_review(pr)) {
+                    if let Some(state) = states.next().flatten() {
+                        pr.has_pending_review = state.has_pending_review;
+                        pr.head_sha = Some(state.head_sha);
+                        pr.state = Some(state.state);
+                        pr.merged = Some(state.merged);
+                        pr.locked = Some(state.locked);
+                    }
+                }
+            }
+            Err(err) => tracing::warn!("Could not check pull requests under review: {err}"),
+        }
+    }
+    
+    Ok(prs_under_review)
+}
+
+/// What is waiting on me: PRs where my review is requested and local reviews with unsent
+/// comments, with ages and sizes for a "what next" view. Local reviews are still listed
+/// when GitHub cannot be reached.
+#[tauri::command]
+async fn cmd_get_review_workload() -> Result<workload::ReviewWorkload, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let mut local = Vec::new();
+    for metadata in storage.get_all_review_metadata().map_err(|e| e.to_string())? {
+        let comments = storage
+            .get_comments(&metadata.owner, &metadata.repo, metadata.pr_number)
+            .map_err(|e| e.to_string())?;
+        local.push((metadata, comments));
+    }
+
+    let (awaiting, github_error) = match auth::list_review_requests().await {
+        Ok(awaiting) => (awaiting, None),
+        Err(err) => {
+            tracing::warn!("Could not list review requests: {err}");
+            (Vec::new(), Some(err.to_string()))
+        }
+    };
+
+    let mut result = workload::build_workload(awaiting, &local, chrono::Utc::now());
+    result.github_error = github_error;
+    Ok(result)
+}
+
+/// Review turnaround for submissions in `range` (`week`, `month`, `quarter`, `year`, or
+/// `all`), measured from the latest review request to submission.
+#[tauri::command]
+fn cmd_get_turnaround_stats(
+    args: GetTurnaroundStatsArgs,
+) -> Result<metrics::TurnaroundStats, String> {
+    let GetTurnaroundStatsArgs { range } = args;
+    let since = metrics::range_start(&range, chrono::Utc::now()).map_err(|e| e.to_string())?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let records = storage
+        .get_turnaround_records(since.map(|t| t.to_rfc3339()).as_deref())
+        .map_err(|e| e.to_string())?;
+    Ok(metrics::compute_stats(&range, &records))
+}
+
+/// Counts and API latency percentiles for `range` (`week`, `month`, `quarter`, `year`, or
+/// `all`) from the opt-in usage metrics. Everything stays in the local database.
+#[tauri::command]
+fn cmd_get_usage_metrics(args: GetUsageMetricsArgs) -> Result<metrics::UsageMetrics, String> {
+    let GetUsageMetricsArgs { range } = args;
+    metrics::flush_api_latencies();
+    let since = metrics::range_start(&range, chrono::Utc::now()).map_err(|e| e.to_string())?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let records = storage
+        .get_usage_events(since.map(|t| t.to_rfc3339()).as_deref())
+        .map_err(|e| e.to_string())?;
+    Ok(metrics::compute_usage(&range, metrics::usage_enabled(), &records))
+}
+
+#[tauri::command]
+fn cmd_get_storage_info(app: tauri::AppHandle) -> Result<String, String> {
+    let data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+    
+    let db_path = data_dir.join("reviews.db");
+    let log_dir = data_dir.join("review_logs");
+    
+    let info = format!(
+        "Storage Directory: {:?}\nDatabase: {:?}\nLog Directory: {:?}\nDB Exists: {}\nLog Dir Exists: {}",
+        data_dir,
+        db_path,
+        log_dir,
+        db_path.exists(),
+        log_dir.exists()
+    );
+    
+    Ok(info)
+}
+
+#[tauri::command]
+fn cmd_open_log_folder(app: tauri::AppHandle) -> Result<(), String> {
+    let data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+    
+    let log_dir = data_dir.join("review_logs");
+    
+    // Create the directory if it doesn't exist
+    if !log_dir.exists() {
+        std::fs::create_dir_all(&log_dir)
+            .map_err(|e| format!("Failed to create log directory: {:?}", e))?;
+    }
+    
+    // Open the log directory in the system's file explorer
+    open::that(&log_dir)
+        .map_err(|e| format!("Failed to open log folder: {:?}", e))?;
+    
+    Ok(())
+}
+
+#[tauri::command]
+fn cmd_get_migration_report() -> Option<migrations::MigrationReport> {
+    migrations::last_report().cloned()
+}
+
+/// GitHub API deprecation warnings seen this session.
+#[tauri::command]
+fn cmd_get_api_deprecations() -> Vec<api_version::ApiDeprecation> {
+    api_version::recorded()
+}
+
+/// Record sanitized GitHub requests and responses to a file under `recordings/` in the app
+/// data directory, to attach to a bug report.
+#[tauri::command]
+fn cmd_start_request_recording(app: tauri::AppHandle) -> Result<recorder::RecorderStatus, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+    let file_name = format!("session-{}.jsonl", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+    recorder::Recorder::global()
+        .start(data_dir.join(recorder::RECORDINGS_DIR).join(file_name))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_stop_request_recording() -> Result<recorder::RecorderStatus, String> {
+    recorder::Recorder::global().stop().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_get_request_recording_status() -> recorder::RecorderStatus {
+    recorder::Recorder::global().status()
+}
+
+/// Answer GitHub requests from a recording instead of the network, to reproduce a report.
+/// Debug builds only.
+#[tauri::command]
+fn cmd_start_request_replay(args: StartRequestReplayArgs) -> Result<usize, String> {
+    let StartRequestReplayArgs { path } = args;
+    recorder::Recorder::global()
+        .start_replay(std::path::Path::new(&path))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_stop_request_replay() {
+    recorder::Recorder::global().stop_replay();
+}
+
+#[tauri::command]
+fn cmd_get_preferences() -> Result<Preferences, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    settings::load_preferences(storage).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_set_preferences(args: SetPreferencesArgs) -> Result<Preferences, String> {
+    let SetPreferencesArgs { preferences } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    settings::save_preferences(storage, &preferences).map_err(|e| e.to_string())?;
+    i18n::set_locale(preferences.locale);
+    time_format::set_zone(preferences.zone().unwrap_or_default());
+    metrics::set_usage_enabled(preferences.usage_metrics);
+    Ok(preferences)
+}
+
+/// Export preferences, templates and review rules for others to import, to a file the
+/// user chooses in a save dialog. Returns the path of the written file, or `None` when
+/// the dialog was cancelled.
+#[tauri::command]
+async fn cmd_export_settings(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let bundle = settings_bundle::build_bundle(storage).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+    let path = dialogs::save_file(&app, settings_bundle::bundle_file_name(), json)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(path.map(|path| path.display().to_string()))
+}
+
+/// Import a settings export the user picks, replacing the sections it contains. `None`
+/// when the file dialog was cancelled.
+#[tauri::command]
+async fn cmd_import_settings(
+    app: tauri::AppHandle,
+) -> Result<Option<settings_bundle::SettingsImport>, String> {
+    let Some(src) = dialogs::pick_file(&app, &["json"])
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let json = tokio::fs::read_to_string(&src)
+        .await
+        .map_err(|e| e.to_string())?;
+    let bundle = settings_bundle::parse_bundle(&json).map_err(|e| e.to_string())?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let report = settings_bundle::import_bundle(storage, bundle).map_err(|e| e.to_string())?;
+    let preferences = settings::load_preferences(storage).map_err(|e| e.to_string())?;
+    i18n::set_locale(preferences.locale);
+    time_format::set_zone(preferences.zone().unwrap_or_default());
+    metrics::set_usage_enabled(preferences.usage_metrics);
+    Ok(Some(report))
+}
+
+#[tauri::command]
+async fn cmd_open_url(args: OpenUrlArgs) -> Result<(), String> {
+    let OpenUrlArgs { url } = args;
+    open::that(&url)
+        .map_err(|e| format!("Failed to open URL: {:?}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+fn cmd_get_hooks_config() -> Result<hooks::HooksConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    hooks::load_config(storage).map_err(|e| e.to_string())
+}
+
+/// Programs and hosts the hooks use that are not allowed yet are put to the user in a
+/// native dialog first; the webview cannot allow them itself.
+#[tauri::command]
+async fn cmd_set_hooks_config(
+    app: tauri::AppHandle,
+    args: SetHooksConfigArgs,
+) -> Result<hooks::HooksConfig, String> {
+    let SetHooksConfigArgs { config } = args;
+    let (programs, hosts) = config.targets();
+    allow_list::request(
+        &app,
+        allow_list::HOOKS_ALLOW_LIST_KEY,
+        i18n::Msg::FeatureHooks,
+        &programs,
+        &hosts,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    hooks::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// Run one configured hook immediately with sample values so the user can check it.
+#[tauri::command]
+async fn cmd_test_hook(args: TestHookArgs) -> Result<String, String> {
+    let TestHookArgs { name } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let config = hooks::load_config(storage).map_err(|e| e.to_string())?;
+    let allowed = hooks::load_allow_list(storage).map_err(|e| e.to_string())?;
+    let hook = config
+        .hooks
+        .iter()
+        .find(|hook| hook.name == name)
+        .ok_or_else(|| format!("No hook named '{}'", name))?;
+
+    let context = hooks::HookContext::new(hook.event, "octocat", "hello-world", 1)
+        .with("comment_body", "Test comment")
+        .with("review_event", "COMMENT");
+    hooks::run_hook(&config, &allowed, hook, &context)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_list_my_repositories(
+    args: ListMyRepositoriesArgs,
+) -> Result<Vec<models::RepositorySummary>, String> {
+    let ListMyRepositoriesArgs { filter, force_refresh } = args;
+    auth::list_my_repositories(filter.as_deref(), force_refresh.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_list_starred_repositories(
+    args: ListStarredRepositoriesArgs,
+) -> Result<Vec<models::RepositorySummary>, String> {
+    let ListStarredRepositoriesArgs { force_refresh } = args;
+    auth::list_starred_repositories(force_refresh.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_list_org_repositories(
+    args: ListOrgRepositoriesArgs,
+) -> Result<Vec<models::RepositorySummary>, String> {
+    let ListOrgRepositoriesArgs { org: Owner(org), filter, force_refresh } = args;
+    auth::list_org_repositories(&org, filter.as_deref(), force_refresh.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_list_my_organizations(
+    args: ListMyOrganizationsArgs,
+) -> Result<Vec<models::OrganizationSummary>, String> {
+    let ListMyOrganizationsArgs { force_refresh } = args;
+    auth::list_my_organizations(force_refresh.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_summarize_pr(args: SummarizePrArgs) -> Result<summary::PrSummary, String> {
+    let SummarizePrArgs { owner: Owner(owner), repo: Repo(repo), number: PrNumber(number) } = args;
+    let files = auth::fetch_pull_request_files(&owner, &repo, number)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(summary::summarize(&files))
+}
+
+#[tauri::command]
+async fn cmd_get_side_by_side_diff(
+    args: GetSideBySideDiffArgs,
+) -> Result<diff::SideBySideDiff, String> {
+    let GetSideBySideDiffArgs {
+        path: RepoPath(path),
+        base_content,
+        head_content,
+        context,
+        ignore_whitespace,
+    } = args;
+    tauri::async_runtime::spawn_blocking(move || {
+        diff::side_by_side(
+            &path,
+            base_content.as_deref().unwrap_or(""),
+            head_content.as_deref().unwrap_or(""),
+            context,
+            ignore_whitespace.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Second pass over a PR: what changed since my last review, in the files I commented on.
+#[tauri::command]
+async fn cmd_get_changes_since_my_review(
+    args: GetChangesSinceMyReviewArgs,
+) -> Result<second_pass::ChangesSinceReview, String> {
+    let GetChangesSinceMyReviewArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let stored = storage
+        .get_review_commit(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?;
+    auth::changes_since_my_review(&owner, &repo, pr, stored)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// How far the PR is behind its base branch, and which of its files also changed there.
+#[tauri::command]
+async fn cmd_check_base_drift(args: CheckBaseDriftArgs) -> Result<drift::BaseDrift, String> {
+    let CheckBaseDriftArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+    } = args;
+    auth::check_base_drift(&owner, &repo, number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Peek at an issue, such as one a PR links to, without leaving the review.
+#[tauri::command]
+async fn cmd_get_issue(args: GetIssueArgs) -> Result<models::IssueDetail, String> {
+    let GetIssueArgs { owner: Owner(owner), repo: Repo(repo), number: PrNumber(number) } = args;
+    auth::get_issue(&owner, &repo, number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Whether the built-in `analyzer` runs on the PR `owner`, `repo` and `pr_number` name,
+/// going by the review profile active on it. Without a PR every check runs.
+fn analyzer_enabled(
+    owner: Option<Owner>,
+    repo: Option<Repo>,
+    pr_number: Option<PrNumber>,
+    analyzer: &str,
+) -> Result<bool, String> {
+    let (Some(Owner(owner)), Some(Repo(repo)), Some(PrNumber(pr_number))) =
+        (owner, repo, pr_number)
+    else {
+        return Ok(true);
+    };
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    review_profiles::analyzer_enabled(storage, &owner, &repo, pr_number, analyzer)
+        .map_err(|e| e.to_string())
+}
+
+/// Acronyms the change introduces without spelling them out on first use. `patch` limits
+/// findings to added lines; without it the whole file counts as new.
+#[tauri::command]
+fn cmd_check_acronyms(args: CheckAcronymsArgs) -> Result<Vec<acronyms::AcronymFinding>, String> {
+    let CheckAcronymsArgs { owner, repo, pr_number, path: RepoPath(path), head_content, patch } =
+        args;
+    if !analyzer_enabled(owner, repo, pr_number, "acronyms")? {
+        return Ok(Vec::new());
+    }
+    Ok(acronyms::check_file(&path, &head_content, patch.as_deref()))
+}
+
+/// Images in changed markdown with empty or missing alt text, with draft comments.
+#[tauri::command]
+fn cmd_check_alt_text(args: CheckAltTextArgs) -> Result<Vec<alt_text::AltTextFinding>, String> {
+    let CheckAltTextArgs { owner, repo, pr_number, path: RepoPath(path), head_content, patch } =
+        args;
+    if !analyzer_enabled(owner, repo, pr_number, "alt_text")? {
+        return Ok(Vec::new());
+    }
+    Ok(alt_text::check_file(&path, &head_content, patch.as_deref()))
+}
+
+/// Date front matter (`ms.date`, `last_reviewed`, or `date_fields`) that a change to a
+/// markdown file left as it was, with draft comments suggesting today's date.
+#[tauri::command]
+fn cmd_check_front_matter_freshness(
+    args: CheckFrontMatterFreshnessArgs,
+) -> Result<Vec<front_matter::StaleDateFinding>, String> {
+    let CheckFrontMatterFreshnessArgs {
+        owner,
+        repo,
+        pr_number,
+        path: RepoPath(path),
+        head_content,
+        patch,
+        date_fields,
+    } = args;
+    if !analyzer_enabled(owner, repo, pr_number, "front_matter")? {
+        return Ok(Vec::new());
+    }
+    let date_fields = date_fields.unwrap_or_else(|| {
+        front_matter::DEFAULT_DATE_FIELDS.iter().map(|f| f.to_string()).collect()
+    });
+    let today = chrono::Local::now().date_naive();
+    Ok(front_matter::check_freshness(&path, &head_content, &patch, &date_fields, today))
+}
+
+/// Render markdown as GitHub would, reusing the cached HTML when the same blob was
+/// rendered before for this repository.
+#[tauri::command]
+async fn cmd_render_markdown(
+    args: RenderMarkdownArgs,
+) -> Result<markdown_render::RenderedMarkdown, String> {
+    let RenderMarkdownArgs { owner, repo, content } = args;
+    let owner = owner.map(|Owner(owner)| owner);
+    let repo = repo.map(|Repo(repo)| repo);
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let blob_sha = markdown_render::git_blob_sha(&content);
+    let context = markdown_render::render_context(owner.as_deref(), repo.as_deref());
+
+    if let Some(html) = storage
+        .get_rendered_markdown(&blob_sha, &context)
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(markdown_render::RenderedMarkdown { blob_sha, html, cached: true });
+    }
+
+    let html = auth::render_markdown(&content, Some(context.as_str()).filter(|c| !c.is_empty()))
+        .await
+        .map_err(|e| e.to_string())?;
+    storage
+        .put_rendered_markdown(
+            &blob_sha,
+            &context,
+            &html,
+            markdown_render::RENDER_CACHE_MAX_ENTRIES,
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(markdown_render::RenderedMarkdown { blob_sha, html, cached: false })
+}
+
+/// Syntax problems in the mermaid and PlantUML fences of markdown `content`, so a
+/// broken diagram is caught in review rather than after publishing.
+#[tauri::command]
+fn cmd_validate_diagrams(
+    args: ValidateDiagramsArgs,
+) -> Result<Vec<diagrams::DiagramIssue>, String> {
+    let ValidateDiagramsArgs { owner, repo, pr_number, content } = args;
+    if !analyzer_enabled(owner, repo, pr_number, "diagrams")? {
+        return Ok(Vec::new());
+    }
+    Ok(diagrams::validate(&content))
+}
+
+/// Broken tables, unclosed code fences, and malformed lists in markdown `content`, at
+/// head line numbers. With `patch`, only problems the change touches are returned.
+#[tauri::command]
+fn cmd_validate_markdown_structure(
+    args: ValidateMarkdownStructureArgs,
+) -> Result<Vec<markdown_structure::StructureIssue>, String> {
+    let ValidateMarkdownStructureArgs { owner, repo, pr_number, content, patch } = args;
+    if !analyzer_enabled(owner, repo, pr_number, "markdown_structure")? {
+        return Ok(Vec::new());
+    }
+    Ok(markdown_structure::validate(&content, patch.as_deref()))
+}
+
+/// Syntax errors in a changed `.json`/`.yaml` file, plus spec errors when it is an
+/// OpenAPI or JSON Schema document, with the line each one is on. `None` when the
+/// review profile active on the PR leaves spec checks out.
+#[tauri::command]
+fn cmd_validate_spec(
+    args: ValidateSpecArgs,
+) -> Result<Option<spec_validation::SpecReport>, String> {
+    let ValidateSpecArgs { owner, repo, pr_number, path: RepoPath(path), content } = args;
+    if !analyzer_enabled(owner, repo, pr_number, "spec")? {
+        return Ok(None);
+    }
+    Ok(Some(spec_validation::validate(&path, &content)))
+}
+
+#[tauri::command]
+fn cmd_get_heading_style_config() -> Result<heading_style::HeadingStyleConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    heading_style::load_config(storage).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_set_heading_style_config(
+    args: SetHeadingStyleConfigArgs,
+) -> Result<heading_style::HeadingStyleConfig, String> {
+    let SetHeadingStyleConfigArgs { config } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    heading_style::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+#[tauri::command]
+fn cmd_get_local_roots() -> Result<local_roots::LocalRootsConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    local_roots::load_config(storage).map_err(|e| e.to_string())
+}
+
+/// Drop local roots the user removed. Roots are only added through `cmd_add_local_root`.
+#[tauri::command]
+fn cmd_set_local_roots(args: SetLocalRootsArgs) -> Result<local_roots::LocalRootsConfig, String> {
+    let SetLocalRootsArgs { config } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    local_roots::retain_roots(storage, &config).map_err(|e| e.to_string())
+}
+
+/// Show the folder picker and allow the chosen folder for local directory mode. Returns
+/// the canonical folder to open, or `None` when the picker was cancelled.
+#[tauri::command]
+async fn cmd_add_local_root(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let Some(directory) = dialogs::pick_folder(&app)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let config = local_roots::add_root(storage, &directory).map_err(|e| e.to_string())?;
+    let canonical = config.check(&directory).map_err(|e| e.to_string())?;
+    Ok(Some(canonical.display().to_string()))
+}
+
+/// Heading capitalization, depth, and skipped levels in markdown `content`, checked
+/// against the configured style. With `patch`, only changed headings are reported.
+#[tauri::command]
+fn cmd_check_headings(
+    args: CheckHeadingsArgs,
+) -> Result<Vec<heading_style::HeadingFinding>, String> {
+    let CheckHeadingsArgs { owner, repo, pr_number, content, patch } = args;
+    if !analyzer_enabled(owner, repo, pr_number, "headings")? {
+        return Ok(Vec::new());
+    }
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let config = heading_style::load_config(storage).map_err(|e| e.to_string())?;
+    Ok(heading_style::check(&config, &content, patch.as_deref()))
+}
+
+#[tauri::command]
+fn cmd_get_snippet_checks_config() -> Result<snippet_checks::SnippetChecksConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    snippet_checks::load_config(storage).map_err(|e| e.to_string())
+}
+
+/// Programs the checks run that are not allowed yet are put to the user in a native
+/// dialog first; the webview cannot allow them itself.
+#[tauri::command]
+async fn cmd_set_snippet_checks_config(
+    app: tauri::AppHandle,
+    args: SetSnippetChecksConfigArgs,
+) -> Result<snippet_checks::SnippetChecksConfig, String> {
+    let SetSnippetChecksConfigArgs { config } = args;
+    allow_list::request(
+        &app,
+        allow_list::SNIPPET_CHECKS_ALLOW_LIST_KEY,
+        i18n::Msg::FeatureSnippetChecks,
+        &config.programs(),
+        &[],
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    snippet_checks::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// Run the configured local checks (compilers, linters) on the fenced code blocks of
+/// markdown `content`. With `patch`, only blocks the change touches are checked.
+#[tauri::command]
+async fn cmd_check_snippets(
+    args: CheckSnippetsArgs,
+) -> Result<Vec<snippet_checks::SnippetFinding>, String> {
+    let CheckSnippetsArgs { owner, repo, pr_number, content, patch } = args;
+    if !analyzer_enabled(owner, repo, pr_number, "snippets")? {
+        return Ok(Vec::new());
+    }
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let config = snippet_checks::load_config(storage).map_err(|e| e.to_string())?;
+    let allowed = snippet_checks::load_allow_list(storage).map_err(|e| e.to_string())?;
+    snippet_checks::run_checks(&config, &allowed, &content, patch.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// React to a review or conversation comment.
+#[tauri::command]
+async fn cmd_add_comment_reaction(args: AddCommentReactionArgs) -> Result<(), String> {
+    let AddCommentReactionArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        kind,
+        comment_id,
+        content: Reaction(content),
+    } = args;
+    auth::add_comment_reaction(&owner, &repo, kind, comment_id, &content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Take back my reaction on a comment; `false` when I had not reacted that way.
+#[tauri::command]
+async fn cmd_remove_comment_reaction(args: RemoveCommentReactionArgs) -> Result<bool, String> {
+    let RemoveCommentReactionArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        kind,
+        comment_id,
+        content: Reaction(content),
+    } = args;
+    auth::remove_comment_reaction(&owner, &repo, kind, comment_id, &content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_get_comment_permalink(args: GetCommentPermalinkArgs) -> Result<String, String> {
+    let GetCommentPermalinkArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+        comment_id,
+    } = args;
+    auth::get_comment_permalink(&owner, &repo, pr, comment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_get_line_permalink(args: GetLinePermalinkArgs) -> Result<String, String> {
+    let GetLinePermalinkArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        sha,
+        path: RepoPath(path),
+        line,
+    } = args;
+    permalinks::line_url(&github::web_base_url(), &owner, &repo, &sha, &path, line)
+        .map_err(|e| e.to_string())
+}
+
+/// Build a quoted reply. With `owner` and `repo` the id is a GitHub comment; without
+/// them it is a local draft comment.
+#[tauri::command]
+async fn cmd_build_quote_reply(args: BuildQuoteReplyArgs) -> Result<String, String> {
+    let BuildQuoteReplyArgs { comment_id, owner, repo } = args;
+    let owner = owner.map(|Owner(owner)| owner);
+    let repo = repo.map(|Repo(repo)| repo);
+    if let (Some(owner), Some(repo)) = (owner, repo) {
+        let comment_id = u64::try_from(comment_id).map_err(|e| e.to_string())?;
+        let (login, body) = auth::get_comment_author_and_body(&owner, &repo, comment_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(quote::build_reply(&quote::github_attribution(&login), &body));
+    }
+
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let comment = storage
+        .get_comment(comment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::tf(i18n::Msg::CommentNotFound, &[&comment_id]))?;
+    Ok(quote::build_reply(
+        &quote::draft_attribution(&comment.file_path, comment.line_number),
+        &comment.body,
+    ))
+}
+
+#[tauri::command]
+async fn cmd_expand_emoji(args: ExpandEmojiArgs) -> Result<String, String> {
+    let ExpandEmojiArgs { text } = args;
+    let emoji = auth::emoji_set().await.map_err(|e| e.to_string())?;
+    Ok(emoji.expand(&text))
+}
+
+#[tauri::command]
+async fn cmd_list_emoji(args: ListEmojiArgs) -> Result<Vec<emoji::EmojiEntry>, String> {
+    let ListEmojiArgs { filter, limit } = args;
+    let emoji = auth::emoji_set().await.map_err(|e| e.to_string())?;
+    Ok(emoji.search(filter.as_deref().unwrap_or(""), limit.unwrap_or(usize::MAX)))
+}
+
+/// Re-request my review after the author pushes fixes. `post_comment` overrides the
+/// stored follow-up preference for this call.
+#[tauri::command]
+async fn cmd_request_changes_followup(
+    args: RequestChangesFollowupArgs,
+) -> Result<models::ReviewFollowupResult, String> {
+    let RequestChangesFollowupArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr: PrNumber(pr),
+        post_comment,
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let followup = settings::load_preferences(storage)
+        .map_err(|e| e.to_string())?
+        .followup;
+    let template = post_comment
+        .unwrap_or(followup.post_comment)
+        .then_some(&followup);
+    auth::request_review_followup(&owner, &repo, pr, template)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Export my reviews and comments in `org` since `since`, as CSV or JSON, to a file the
+/// user chooses in a save dialog. `None` when the dialog was cancelled.
+#[tauri::command]
+async fn cmd_export_review_activity(
+    app: tauri::AppHandle,
+    args: ExportReviewActivityArgs,
+) -> Result<Option<review_activity::ActivityExport>, String> {
+    let ExportReviewActivityArgs { org: Owner(org), since, format } = args;
+    let since = review_activity::normalize_since(&since).map_err(|e| e.to_string())?;
+    let records = auth::list_review_activity(&org, &since)
+        .await
+        .map_err(|e| e.to_string())?;
+    let contents = review_activity::render_export(&records, format).map_err(|e| e.to_string())?;
+    let path = dialogs::save_file(&app, review_activity::export_file_name(&org, format), contents)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(path.map(|path| review_activity::export_summary(&records, &path)))
+}
+
+/// Export a local review so a colleague can import it and continue, to a file the user
+/// chooses in a save dialog. Returns the path of the written file, or `None` when the
+/// dialog was cancelled.
+#[tauri::command]
+async fn cmd_handoff_review(
+    app: tauri::AppHandle,
+    args: HandoffReviewArgs,
+) -> Result<Option<String>, String> {
+    let HandoffReviewArgs { owner: Owner(owner), repo: Repo(repo), pr: PrNumber(pr) } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let metadata = storage
+        .get_review_metadata(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No local review for {owner}/{repo}#{pr}"))?;
+    if metadata.local_folder.is_some() {
+        return Err("Local folder reviews cannot be handed off".to_string());
+    }
+    let comments = storage
+        .get_comments(&owner, &repo, pr)
+        .map_err(|e| e.to_string())?;
+
+    // Anchors are best effort: without them the importer keeps the original line numbers
+    let mut contents = std::collections::HashMap::new();
+    for comment in comments.iter().filter(|c| c.side == "RIGHT") {
+        let key = (comment.file_path.clone(), comment.commit_id.clone());
+        if contents.contains_key(&key) {
+            continue;
+        }
+        match auth::fetch_text_file(&owner, &repo, &key.0, &key.1).await {
+            Ok(content) => {
+                contents.insert(key, content);
+            }
+            Err(err) => tracing::warn!(path = %key.0, error = %err, "no anchor context for handoff"),
+        }
+    }
+
+    let exported_by = crate::storage::read_last_login().ok().flatten();
+    let document = handoff::build_handoff(&metadata, &comments, &contents, exported_by);
+    let json = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+    let path = dialogs::save_file(&app, handoff::handoff_file_name(&document), json)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(path.map(|path| path.display().to_string()))
+}
+
+/// Import a review handoff file the user picks. When `head_sha` is newer than the
+/// handoff's commit, comments are moved to their matching lines at `head_sha` where
+/// possible. `None` when the file dialog was cancelled.
+#[tauri::command]
+async fn cmd_import_review_handoff(
+    app: tauri::AppHandle,
+    args: ImportReviewHandoffArgs,
+) -> Result<Option<handoff::HandoffImport>, String> {
+    let ImportReviewHandoffArgs { head_sha } = args;
+    let Some(path) = dialogs::pick_file(&app, &["json"])
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let json = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let document = handoff::parse_handoff(&json).map_err(|e| e.to_string())?;
+
+    let mut head_contents = std::collections::HashMap::new();
+    if let Some(head) = head_sha.as_deref() {
+        for file_path in handoff::paths_needing_relocation(&document, head) {
+            match auth::fetch_text_file(&document.owner, &document.repo, &file_path, head).await {
+                Ok(content) => {
+                    head_contents.insert(file_path, content);
+                }
+                Err(err) => tracing::warn!(path = %file_path, error = %err, "cannot re-anchor handoff comments"),
+            }
+        }
+    }
+
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    handoff::import_handoff(storage, &document, head_sha.as_deref(), &head_contents)
+        .await
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// `None` when the review profile active on the PR leaves the template check out.
+#[tauri::command]
+async fn cmd_check_pr_template(
+    args: CheckPrTemplateArgs,
+) -> Result<Option<pr_template::TemplateCheck>, String> {
+    let CheckPrTemplateArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        number: PrNumber(number),
+    } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    if !review_profiles::analyzer_enabled(storage, &owner, &repo, number, "pr_template")
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(None);
+    }
+    auth::check_pull_request_template(&owner, &repo, number)
+        .await
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_get_content_filter() -> Result<content_filter::ContentFilterConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    content_filter::load_config(storage).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_set_content_filter(
+    args: SetContentFilterArgs,
+) -> Result<content_filter::ContentFilterConfig, String> {
+    let SetContentFilterArgs { config } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    content_filter::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// `TODO`, `FIXME`, and `TBD` markers the PR adds, as candidate review comments.
+#[tauri::command]
+async fn cmd_extract_todos(args: ExtractTodosArgs) -> Result<Vec<todos::TodoFinding>, String> {
+    let ExtractTodosArgs { owner: Owner(owner), repo: Repo(repo), number: PrNumber(number) } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    if !review_profiles::analyzer_enabled(storage, &owner, &repo, number, "todos")
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(Vec::new());
+    }
+    let files = auth::fetch_pull_request_files(&owner, &repo, number)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(todos::scan_files(&files))
+}
+
+/// The repository's `.github/doc-review.yml` merged over local settings. With
+/// `pr_number`, the checklist of the review profile active on that PR follows the
+/// repository's own.
+#[tauri::command]
+async fn cmd_get_repo_review_config(
+    args: GetRepoReviewConfigArgs,
+) -> Result<repo_config::EffectiveReviewConfig, String> {
+    let GetRepoReviewConfigArgs { owner: Owner(owner), repo: Repo(repo), pr_number } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    let local = content_filter::load_config(storage).map_err(|e| e.to_string())?;
+    let repo_policy = auth::repo_review_config(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut effective = repo_config::merge(local, repo_policy.as_ref());
+    if let Some(PrNumber(pr_number)) = pr_number {
+        review_profiles::extend_checklist(
+            storage,
+            &owner,
+            &repo,
+            pr_number,
+            &mut effective.checklist,
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(effective)
+}
+
+/// Of `paths`, the files the repository's review policy puts in scope, in the same order.
+#[tauri::command]
+async fn cmd_filter_review_files(args: FilterReviewFilesArgs) -> Result<Vec<String>, String> {
+    let FilterReviewFilesArgs { owner: Owner(owner), repo: Repo(repo), paths } = args;
+    let paths: Vec<String> = paths.into_iter().map(|path| path.0).collect();
+    let repo_policy = auth::repo_review_config(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(match repo_policy {
+        Some(policy) => paths.into_iter().filter(|p| policy.files.matches(p)).collect(),
+        None => paths,
+    })
+}
+
+#[tauri::command]
+fn cmd_get_quiet_hours() -> Result<quiet_hours::QuietHoursConfig, String> {
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    quiet_hours::load_config(storage).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn cmd_set_quiet_hours(args: SetQuietHoursArgs) -> Result<quiet_hours::QuietHoursConfig, String> {
+    let SetQuietHoursArgs { config } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    quiet_hours::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// Where the user was when the app last saved its session, to reopen after a crash or
+/// restart; `None` when nothing was open.
+#[tauri::command]
+fn cmd_get_session() -> Result<Option<session::SessionState>, String> {
+    // Restoring the session may be what keeps crashing the app
+    if safe_mode::is_active() {
+        return Ok(None);
+    }
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    session::load(storage).map_err(|e| e.to_string())
+}
+
+/// Save the open PR or folder, file, scroll position and draft. Saving a session with
+/// nothing open clears it.
 #[tauri::command]
-async fn cmd_fetch_file_content(
-    owner: String,
-    repo: String,
-    reference: String,
-    path: String,
-) -> Result<String, String> {
-    use auth::require_token;
-    let token = require_token().map_err(|e| e.to_string())?;
-    github::fetch_file_content(&token, &owner, &repo, &reference, &path)
-        .await
-        .map_err(|e| e.to_string())
+fn cmd_save_session(args: SaveSessionArgs) -> Result<session::SessionState, String> {
+    let SaveSessionArgs { session } = args;
+    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
+    session::save(storage, session).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn cmd_local_get_comments(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-) -> Result<Vec<ReviewComment>, String> {
+fn cmd_get_pr_size_config() -> Result<pr_size::PrSizeConfig, String> {
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
-    storage
-        .get_comments(&owner, &repo, pr_number)
-        .map_err(|e| e.to_string())
+    pr_size::load_config(storage).map_err(|e| e.to_string())
 }
 
+/// Save size thresholds and reading speeds. Pull request lists use them from the next
+/// refresh on.
 #[tauri::command]
-fn cmd_local_get_review_metadata(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-) -> Result<Option<ReviewMetadata>, String> {
+fn cmd_set_pr_size_config(args: SetPrSizeConfigArgs) -> Result<pr_size::PrSizeConfig, String> {
+    let SetPrSizeConfigArgs { config } = args;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
-    storage
-        .get_review_metadata(&owner, &repo, pr_number)
-        .map_err(|e| e.to_string())
+    pr_size::save_config(storage, &config).map_err(|e| e.to_string())?;
+    Ok(config)
 }
 
+/// The users and organizations the app may reach, as read from the policy file at
+/// startup. The policy is not settable from here.
 #[tauri::command]
-async fn cmd_local_abandon_review(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-) -> Result<(), String> {
-    let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
-    storage
-        .abandon_review(&owner, &repo, pr_number)
-        .await
-        .map_err(|e| e.to_string())
+fn cmd_get_owner_policy() -> Result<owner_policy::OwnerPolicy, String> {
+    owner_policy::current().cloned().map_err(|e| e.to_string())
 }
 
+/// Whether it is currently quiet hours and, if so, when they end.
 #[tauri::command]
-async fn cmd_local_clear_review(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-    pr_title: Option<String>,
-) -> Result<(), String> {
+fn cmd_get_quiet_hours_status() -> Result<quiet_hours::QuietHoursStatus, String> {
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
-    storage
-        .clear_review(&owner, &repo, pr_number, pr_title.as_deref())
-        .await
+    quiet_hours::load_config(storage)
+        .and_then(|config| config.status_at(chrono::Utc::now()))
         .map_err(|e| e.to_string())
 }
 
+/// Run the outbound content filter over a local review without submitting it.
 #[tauri::command]
-async fn cmd_submit_local_review(
-    app: tauri::AppHandle,
-    owner: String,
-    repo: String,
-    pr_number: u64,
-    event: Option<String>,
-    body: Option<String>,
-) -> Result<(), String> {
-    use auth::submit_review_with_comments;
-    use auth::fetch_pull_request_details;
-    
+fn cmd_check_local_review_content(
+    args: CheckLocalReviewContentArgs,
+) -> Result<content_filter::FilterReport, String> {
+    let CheckLocalReviewContentArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        body,
+    } = args;
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
-    
-    // Get metadata and comments
+    let config = content_filter::load_config(storage).map_err(|e| e.to_string())?;
     let metadata = storage
         .get_review_metadata(&owner, &repo, pr_number)
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "No pending review found".to_string())?;
-    
-    let comments = storage
-        .get_comments(&owner, &repo, pr_number)
-        .map_err(|e| e.to_string())?;
-    
-    // Check if PR has been updated since comments were created
-    let pr_detail = fetch_pull_request_details(&owner, &repo, pr_number, None)
-        .await
         .map_err(|e| e.to_string())?;
-    
-    let commit_id_to_use = if pr_detail.head_sha != metadata.commit_id {
-        tracing::warn!(
-            "⚠️  WARNING: PR has been updated since you created these comments!\n   \
-            Your comments were created for: {}\n   \
-            Current PR head commit:      {}\n   \
-            Using CURRENT commit for submission to maximize success rate.",
-            metadata.commit_id, pr_detail.head_sha
-        );
-        &pr_detail.head_sha
-    } else {
-        &metadata.commit_id
-    };
-    
-    // Submit to GitHub - returns (succeeded_ids, optional_error_message)
-    let (succeeded_ids, error_msg) = submit_review_with_comments(
-        &app,
-        &owner,
-        &repo,
-        pr_number,
-        commit_id_to_use,
-        body.as_deref().or(metadata.body.as_deref()),
-        event.as_deref(),
-        &comments,
-    )
-    .await
-    .map_err(|e| e.to_string())?;
-    
-    // Delete only successfully posted comments from DB (but they remain in log file)
-    for comment_id in succeeded_ids {
-        storage
-            .delete_comment_preserve_log(comment_id)
-            .map_err(|e| e.to_string())?;
-    }
-    
-    // If all comments were posted, mark the review as submitted
-    let remaining_comments = storage
+    let comments = storage
         .get_comments(&owner, &repo, pr_number)
         .map_err(|e| e.to_string())?;
-    
-    if remaining_comments.is_empty() {
-        storage
-            .mark_review_submitted(&owner, &repo, pr_number, None)
-            .await
-            .map_err(|e| e.to_string())?;
-    }
-    
-    // Return error if there was a partial or complete failure
-    if let Some(err) = error_msg {
-        Err(err)
-    } else {
-        Ok(())
-    }
+
+    let review_body = body.or_else(|| metadata.and_then(|m| m.body));
+    Ok(content_filter::check_review(&config, review_body.as_deref(), &comments))
 }
 
 #[tauri::command]
-async fn cmd_delete_review(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-    review_id: u64,
-) -> Result<(), String> {
-    use auth::require_token_for_delete;
-    use github::delete_review;
-    
-    let token = require_token_for_delete().map_err(|e| e.to_string())?;
-    
-    delete_review(&token, &owner, &repo, pr_number, review_id)
-        .await
-        .map_err(|e| e.to_string())?;
-    
-    Ok(())
+fn cmd_list_plugins() -> Result<Vec<plugins::PluginInfo>, String> {
+    let host = plugins::get_plugins().map_err(|e| e.to_string())?;
+    host.list().map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn cmd_get_pending_review_comments(
-    owner: String,
-    repo: String,
-    pr_number: u64,
-    review_id: u64,
-    current_login: Option<String>,
-) -> Result<Vec<models::PullRequestComment>, String> {
-    use auth::require_token;
-    use github::get_pending_review_comments;
-    
-    let token = require_token().map_err(|e| e.to_string())?;
-    
-    get_pending_review_comments(
-        &token, 
-        &owner, 
-        &repo, 
-        pr_number, 
-        review_id,
-        current_login.as_deref()
-    )
-    .await
-    .map_err(|e| e.to_string())
+async fn cmd_reload_plugins() -> Result<Vec<plugins::PluginInfo>, String> {
+    let host = plugins::get_plugins().map_err(|e| e.to_string())?;
+    tauri::async_runtime::spawn_blocking(move || host.reload())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn cmd_get_prs_under_review() -> Result<Vec<models::PrUnderReview>, String> {
-    tracing::info!("cmd_get_prs_under_review called");
+async fn cmd_run_plugins(args: RunPluginsArgs) -> Result<Vec<plugins::PluginFinding>, String> {
+    let RunPluginsArgs {
+        owner: Owner(owner),
+        repo: Repo(repo),
+        pr_number: PrNumber(pr_number),
+        commit_id,
+        files,
+        save_as_drafts,
+    } = args;
+
+    // With a profile active on the PR, only the plugins it lists run
     let storage = review_storage::get_storage().map_err(|e| e.to_string())?;
-    
-    // Get all review metadata from storage
-    let all_reviews = storage.get_all_review_metadata().map_err(|e| e.to_string())?;
-    tracing::info!("Found {} reviews in storage", all_reviews.len());
-    
-    let prs_under_review: Vec<models::PrUnderReview> = all_reviews
-        .into_iter()
-        .map(|metadata| {
-            let is_local_folder = metadata.owner == "__local__" && metadata.repo == "local";
-            let total_count = if is_local_folder {
-                if let Some(local_folder) = metadata.local_folder.as_deref() {
-                    let base = resolve_local_directory_path(local_folder);
-                    let mut files: Vec<std::path::PathBuf> = Vec::new();
-                    match collect_markdown_files(&base, &mut files) {
-                        Ok(()) => files.len(),
-                        Err(_) => 0,
-                    }
-                } else {
-                    0
-                }
-            } else {
-                0
-            };
+    let only = review_profiles::active_profile(storage, &owner, &repo, pr_number)
+        .map_err(|e| e.to_string())?
+        .map(|active| active.profile.plugins);
+    let host = plugins::get_plugins().map_err(|e| e.to_string())?;
+    let findings =
+        tauri::async_runtime::spawn_blocking(move || host.analyze(&files, only.as_deref()))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
 
-            models::PrUnderReview {
-                owner: metadata.owner.clone(),
-                repo: metadata.repo.clone(),
-                number: metadata.pr_number,
-                title: String::new(), // Will be filled in by frontend
-                has_local_review: true,
-                has_pending_review: false,
-                viewed_count: 0,
-                total_count,
-                local_folder: metadata.local_folder.clone(),
+    if save_as_drafts && !findings.is_empty() {
+        let files = if owner == command_args::LOCAL_OWNER {
+            Vec::new()
+        } else {
+            auth::fetch_pull_request_files(&owner, &repo, pr_number)
+                .await
+                .map_err(|e| e.to_string())?
+        };
+        storage
+            .start_review(&owner, &repo, pr_number, &commit_id, None, None)
+            .map_err(|e| e.to_string())?;
+        for finding in &findings {
+            // File-level findings become file comments, at line 0
+            let line = finding.line.map(u64::from).unwrap_or(0);
+            // The finding is still reported; only its draft is left out
+            if let Err(err) = models::ensure_side_exists(&files, &finding.path, "RIGHT", line) {
+                tracing::warn!("cmd_run_plugins: no draft for {} - {}", finding.path, err);
+                continue;
             }
-        })
-        .collect();
-    
-    Ok(prs_under_review)
+            let body = finding.draft_body();
+            add_local_comment(
+                storage, &owner, &repo, pr_number, &finding.path, line, "RIGHT", &body,
+                &commit_id, None, None,
+            )
+            .await?;
+        }
+    }
+
+    Ok(findings)
 }
 
 #[tauri::command]
-fn cmd_get_storage_info(app: tauri::AppHandle) -> Result<String, String> {
-    let data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
-    
-    let db_path = data_dir.join("reviews.db");
-    let log_dir = data_dir.join("review_logs");
-    
-    let info = format!(
-        "Storage Directory: {:?}\nDatabase: {:?}\nLog Directory: {:?}\nDB Exists: {}\nLog Dir Exists: {}",
-        data_dir,
-        db_path,
-        log_dir,
-        db_path.exists(),
-        log_dir.exists()
-    );
-    
-    Ok(info)
+fn cmd_list_commands() -> Vec<commands::CommandInfo> {
+    command_registry()
 }
 
-#[tauri::command]
-fn cmd_open_log_folder(app: tauri::AppHandle) -> Result<(), String> {
-    let data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
-    
-    let log_dir = data_dir.join("review_logs");
-    
-    // Create the directory if it doesn't exist
-    if !log_dir.exists() {
-        std::fs::create_dir_all(&log_dir)
-            .map_err(|e| format!("Failed to create log directory: {:?}", e))?;
-    }
-    
-    // Open the log directory in the system's file explorer
-    open::that(&log_dir)
-        .map_err(|e| format!("Failed to open log folder: {:?}", e))?;
-    
-    Ok(())
+/// Every command exposed to the frontend, listed once. `app_commands!(handler)` builds the
+/// Tauri invoke handler, `app_commands!(registry)` builds the metadata returned by
+/// `cmd_list_commands`, and `app_commands!(args)` declares the argument struct of each
+/// command that takes parameters, so the three cannot drift apart. The frontend passes
+/// those parameters as one `args` object with camelCase keys; unknown keys are rejected
+/// instead of silently ignored, and `command_args` types are validated before the command
+/// runs. Tauri-injected parameters (app handle, window) are not part of the struct.
+macro_rules! app_commands {
+    ($mode:ident) => {
+        app_commands! {@$mode
+            cmd_load_local_directory("Open Local Folder", auth = false, LoadLocalDirectoryArgs {
+                directory: String,
+            }),
+            cmd_read_local_file("Read Local File", auth = false, ReadLocalFileArgs {
+                base_id: String, rel_path: RepoPath,
+            }),
+            cmd_get_local_folder_number("Get Local Folder Number", auth = false,
+                GetLocalFolderNumberArgs {
+                    directory: String,
+                }),
+            cmd_start_github_oauth("Sign In with GitHub", auth = false, StartGithubOauthArgs {
+                method: Option<auth::LoginMethod>,
+            }),
+            cmd_take_scope_upgrade("Show Missing GitHub Permission", auth = false),
+            cmd_get_rate_limit("Show GitHub Rate Limits", auth = true, GetRateLimitArgs {
+                /// Ask GitHub even when a recent response already reported the quotas.
+                #[serde(default)]
+                refresh: bool,
+            }),
+            cmd_upgrade_oauth_scope("Grant GitHub Permission", auth = false, UpgradeOauthScopeArgs {
+                scope: String, method: Option<auth::LoginMethod>,
+            }),
+            cmd_check_auth_status("Check Sign-In Status", auth = false),
+            cmd_logout("Sign Out", auth = false),
+            cmd_list_accounts("List GitHub Accounts", auth = false),
+            cmd_add_account("Add GitHub Account", auth = false, AddAccountArgs {
+                name: String, method: Option<auth::LoginMethod>,
+            }),
+            cmd_switch_account("Switch GitHub Account", auth = false, SwitchAccountArgs {
+                name: String,
+            }),
+            cmd_remove_account("Remove GitHub Account", auth = false, RemoveAccountArgs {
+                name: String,
+            }),
+            cmd_list_pull_requests("List Pull Requests", auth = true, ListPullRequestsArgs {
+                owner: Owner, repo: Repo, state: Option<String>, current_login: Option<String>,
+            }),
+            cmd_get_pull_request("Open Pull Request", auth = true, GetPullRequestArgs {
+                owner: Owner, repo: Repo, number: PrNumber, current_login: Option<String>,
+            }),
+            cmd_get_pull_request_metadata("Get Pull Request Metadata", auth = true,
+                GetPullRequestMetadataArgs {
+                    owner: Owner, repo: Repo, number: PrNumber,
+                }),
+            cmd_get_file_contents("Get File Contents", auth = true, GetFileContentsArgs {
+                owner: Owner, repo: Repo, file_path: RepoPath, base_sha: String, head_sha: String,
+                status: String, previous_filename: Option<RepoPath>, head_owner: Option<Owner>,
+                head_repo: Option<Repo>,
+            }),
+            cmd_get_rendered_file_contents("Get Rendered Markdown From GitHub", auth = true,
+                GetRenderedFileContentsArgs {
+                    owner: Owner, repo: Repo, file_path: RepoPath, base_sha: String,
+                    head_sha: String, status: String, previous_filename: Option<RepoPath>,
+                    head_owner: Option<Owner>, head_repo: Option<Repo>,
+                }),
+            cmd_get_image_diff("Compare Image Versions", auth = true, GetImageDiffArgs {
+                owner: Owner, repo: Repo, file_path: RepoPath, base_sha: String, head_sha: String,
+                status: String, previous_filename: Option<RepoPath>, head_owner: Option<Owner>,
+                head_repo: Option<Repo>,
+            }),
+            cmd_submit_review_comment("Post PR Comment", auth = true, SubmitReviewCommentArgs {
+                owner: Owner, repo: Repo, number: PrNumber, body: String,
+            }),
+            cmd_submit_file_comment("Post File Comment", auth = true, SubmitFileCommentArgs {
+                owner: Owner, repo: Repo, number: PrNumber, path: RepoPath, body: String,
+                commit_id: String, line: Option<u64>, side: Option<String>,
+                subject_type: Option<String>, mode: Option<String>, pending_review_id: Option<u64>,
+                in_reply_to: Option<u64>, suggestion: Option<models::Suggestion>,
+            }),
+            cmd_start_pending_review("Start Pending Review", auth = true, StartPendingReviewArgs {
+                owner: Owner, repo: Repo, number: PrNumber, commit_id: Option<String>,
+                body: Option<String>, current_login: Option<String>,
+            }),
+            cmd_submit_pending_review("Submit Pending Review", auth = true,
+                SubmitPendingReviewArgs {
+                    owner: Owner, repo: Repo, number: PrNumber, review_id: u64,
+                    event: Option<String>, body: Option<String>, acknowledge_secrets: Option<bool>,
+                }),
+            cmd_delete_review("Delete Pending Review", auth = true, DeleteReviewArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, review_id: u64,
+                confirmation_token: String,
+            }),
+            cmd_get_pending_review_comments("Get Pending Review Comments", auth = true,
+                GetPendingReviewCommentsArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, review_id: u64,
+                    current_login: Option<String>,
+                }),
+            cmd_search_my_comments("Search My Comments", auth = true, SearchMyCommentsArgs {
+                query: String, repo_filter: Option<String>,
+            }),
+            cmd_open_devtools("Open Developer Tools", auth = false),
+            cmd_open_log_folder("Open Log Folder", auth = false),
+            cmd_get_prs_under_review("List PRs Under Review", auth = false),
+            cmd_get_review_workload("Get Review Workload", auth = false),
+            cmd_get_turnaround_stats("Get Review Turnaround Stats", auth = false,
+                GetTurnaroundStatsArgs {
+                    range: String,
+                }),
+            cmd_get_usage_metrics("Get Usage Metrics", auth = false, GetUsageMetricsArgs {
+                range: String,
+            }),
+            cmd_local_start_review("Start Local Review", auth = false, LocalStartReviewArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, commit_id: String,
+                body: Option<String>, local_folder: Option<String>,
+            }),
+            cmd_local_add_comment("Add Local Comment", auth = false, LocalAddCommentArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, file_path: RepoPath,
+                line_number: Option<u64>, side: String, body: String, commit_id: String,
+                in_reply_to_id: Option<i64>, local_folder: Option<String>,
+                suggestion: Option<models::Suggestion>,
+            }),
+            cmd_add_quick_comment("Add Quick Comment", auth = false, AddQuickCommentArgs {
+                owner: Owner, repo: Repo, pr: PrNumber, path: RepoPath, line: Option<u64>,
+                template_id: String,
+            }),
+            cmd_get_quick_comment_templates("Get Quick Comment Templates", auth = false),
+            cmd_set_quick_comment_templates("Save Quick Comment Templates", auth = false,
+                SetQuickCommentTemplatesArgs {
+                    config: quick_comments::QuickCommentConfig,
+                }),
+            cmd_list_comment_templates("List Comment Templates", auth = false),
+            cmd_create_comment_template("Save Comment Template", auth = false,
+                CreateCommentTemplateArgs {
+                    name: String, body: String, category: Option<String>,
+                }),
+            cmd_update_comment_template("Edit Comment Template", auth = false,
+                UpdateCommentTemplateArgs {
+                    id: i64, name: String, body: String, category: Option<String>,
+                }),
+            cmd_delete_comment_template("Delete Comment Template", auth = false,
+                DeleteCommentTemplateArgs {
+                    id: i64,
+                }),
+            cmd_render_comment_template("Fill In Comment Template", auth = false,
+                RenderCommentTemplateArgs {
+                    id: i64, owner: Owner, repo: Repo, pr: PrNumber, path: RepoPath,
+                    line: Option<u64>,
+                }),
+            cmd_get_review_profiles("Get Review Profiles", auth = false),
+            cmd_set_review_profiles("Save Review Profiles", auth = false,
+                SetReviewProfilesArgs {
+                    config: review_profiles::ReviewProfileConfig,
+                }),
+            cmd_get_active_profile("Get Active Review Profile", auth = false,
+                GetActiveProfileArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber,
+                }),
+            cmd_set_active_profile("Switch Review Profile", auth = false,
+                SetActiveProfileArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, profile: Option<String>,
+                }),
+            cmd_local_update_review_commit("Update Local Review Commit", auth = false,
+                LocalUpdateReviewCommitArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, new_commit_id: String,
+                }),
+            cmd_local_update_comment_file_path("Move Local Comments to Renamed File", auth = false,
+                LocalUpdateCommentFilePathArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, old_path: RepoPath,
+                    new_path: RepoPath,
+                }),
+            cmd_local_update_comment("Edit Local Comment", auth = false, LocalUpdateCommentArgs {
+                comment_id: i64, body: String,
+            }),
+            cmd_local_set_comment_suggestion("Suggest Change in Local Comment", auth = false,
+                LocalSetCommentSuggestionArgs {
+                    comment_id: i64, suggestion: Option<models::Suggestion>,
+                }),
+            cmd_local_delete_comment("Delete Local Comment", auth = false, LocalDeleteCommentArgs {
+                comment_id: i64,
+            }),
+            cmd_convert_to_general_comment("Convert to General Comment", auth = false,
+                ConvertToGeneralCommentArgs {
+                    comment_id: i64,
+                }),
+            cmd_github_update_comment("Edit GitHub Comment", auth = true, GithubUpdateCommentArgs {
+                owner: Owner, repo: Repo, comment_id: u64, body: String,
+            }),
+            cmd_github_delete_comment("Delete GitHub Comment", auth = true,
+                GithubDeleteCommentArgs {
+                    owner: Owner, repo: Repo, comment_id: u64, confirmation_token: String,
+                }),
+            cmd_get_anchor_impact("Find Links to Changed Anchors", auth = true,
+                GetAnchorImpactArgs {
+                    owner: Owner, repo: Repo, number: PrNumber, base_sha: String, head_sha: String,
+                }),
+            cmd_get_localized_pair("Compare Translation with Source", auth = true,
+                GetLocalizedPairArgs {
+                    owner: Owner, repo: Repo, reference: String, path: RepoPath,
+                    source_locale: Option<String>,
+                }),
+            cmd_fetch_file_content("Fetch File at Ref", auth = true, FetchFileContentArgs {
+                owner: Owner, repo: Repo, reference: String, path: RepoPath,
+            }),
+            cmd_local_get_comments("Get Local Comments", auth = false, LocalGetCommentsArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber,
+            }),
+            cmd_local_get_review_metadata("Get Local Review", auth = false,
+                LocalGetReviewMetadataArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber,
+                }),
+            cmd_local_abandon_review("Abandon Local Review", auth = false, LocalAbandonReviewArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber,
+            }),
+            cmd_local_set_review_due_date("Set Local Review Due Date", auth = false,
+                LocalSetReviewDueDateArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, due_at: Option<String>,
+                }),
+            cmd_list_trashed_reviews("List Trashed Reviews", auth = false),
+            cmd_prune_stale_reviews("Clear Reviews of Closed PRs", auth = true,
+                PruneStaleReviewsArgs {
+                    /// List the reviews without clearing them.
+                    #[serde(default)]
+                    dry_run: bool,
+                }),
+            cmd_restore_cleared_review("Restore Cleared Review", auth = false,
+                RestoreClearedReviewArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber,
+                }),
+            cmd_mark_pull_request_seen("Mark Pull Request Seen", auth = false,
+                MarkPullRequestSeenArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, seen_at: Option<String>,
+                }),
+            cmd_add_bookmark("Bookmark Comment", auth = false, AddBookmarkArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, source: String, comment_id: i64,
+                note: Option<String>,
+            }),
+            cmd_remove_bookmark("Remove Bookmark", auth = false, RemoveBookmarkArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, source: String, comment_id: i64,
+            }),
+            cmd_list_bookmarks("List Bookmarks", auth = false, ListBookmarksArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber,
+            }),
+            cmd_mark_hunk_reviewed("Mark Hunk Reviewed", auth = false, MarkHunkReviewedArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, file_path: RepoPath, hunk: String,
+                hunk_count: u32, reviewed: bool,
+            }),
+            cmd_get_hunk_progress("Get Hunk Review Progress", auth = false, GetHunkProgressArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber,
+            }),
+            cmd_local_clear_review("Clear Local Review", auth = false, LocalClearReviewArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, pr_title: Option<String>,
+                confirmation_token: String,
+            }),
+            cmd_request_confirmation("Request Delete Confirmation", auth = false,
+                RequestConfirmationArgs {
+                    action: confirmations::DestructiveAction,
+                }),
+            cmd_reset_app_data("Reset App Data", auth = false, ResetAppDataArgs {
+                options: reset::ResetOptions, confirmation_token: String,
+            }),
+            cmd_get_safe_mode_status("Get Safe Mode Status", auth = false),
+            cmd_check_database("Check Database", auth = false),
+            cmd_purge_cache("Purge Cached Data", auth = false),
+            cmd_leave_safe_mode("Leave Safe Mode", auth = false),
+            cmd_submit_local_review("Submit Local Review to GitHub", auth = true,
+                SubmitLocalReviewArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, event: Option<String>,
+                    body: Option<String>, acknowledge_warnings: Option<bool>,
+                    mode: Option<comment_export::SubmissionMode>,
+                }),
+            cmd_get_last_submission_report("Show Last Submission Report", auth = false),
+            cmd_find_orphaned_comments("Find Comments on Removed Files", auth = true,
+                FindOrphanedCommentsArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber,
+                }),
+            cmd_list_parked_submissions("List Parked Submissions", auth = false),
+            cmd_resume_parked_submissions("Resume Parked Submissions", auth = true),
+            cmd_discard_parked_submission("Discard Parked Submission", auth = false,
+                DiscardParkedSubmissionArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber,
+                }),
+            cmd_get_storage_info("Show Storage Info", auth = false),
+            cmd_get_migration_report("Show Migration Report", auth = false),
+            cmd_get_api_deprecations("Show GitHub API Deprecations", auth = false),
+            cmd_start_request_recording("Start Recording GitHub Requests", auth = false),
+            cmd_stop_request_recording("Stop Recording GitHub Requests", auth = false),
+            cmd_get_request_recording_status("Show Request Recording Status", auth = false),
+            cmd_start_request_replay("Replay Recorded GitHub Requests", auth = false,
+                StartRequestReplayArgs {
+                    path: String,
+                }),
+            cmd_stop_request_replay("Stop Replaying GitHub Requests", auth = false),
+            cmd_get_preferences("Get Preferences", auth = false),
+            cmd_set_preferences("Save Preferences", auth = false, SetPreferencesArgs {
+                preferences: Preferences,
+            }),
+            cmd_export_settings("Export Settings", auth = false),
+            cmd_import_settings("Import Settings", auth = false),
+            cmd_get_hooks_config("Get Automation Hooks", auth = false),
+            cmd_set_hooks_config("Save Automation Hooks", auth = false, SetHooksConfigArgs {
+                config: hooks::HooksConfig,
+            }),
+            cmd_test_hook("Test Automation Hook", auth = false, TestHookArgs {
+                name: String,
+            }),
+            cmd_list_my_repositories("List My Repositories", auth = true, ListMyRepositoriesArgs {
+                filter: Option<String>, force_refresh: Option<bool>,
+            }),
+            cmd_list_starred_repositories("List Starred Repositories", auth = true,
+                ListStarredRepositoriesArgs {
+                    force_refresh: Option<bool>,
+                }),
+            cmd_list_org_repositories("List Organization Repositories", auth = true,
+                ListOrgRepositoriesArgs {
+                    org: Owner, filter: Option<String>, force_refresh: Option<bool>,
+                }),
+            cmd_list_my_organizations("List My Organizations", auth = true,
+                ListMyOrganizationsArgs {
+                    force_refresh: Option<bool>,
+                }),
+            cmd_summarize_pr("Summarize Pull Request", auth = true, SummarizePrArgs {
+                owner: Owner, repo: Repo, number: PrNumber,
+            }),
+            cmd_check_pr_template("Check PR Description Against Template", auth = true,
+                CheckPrTemplateArgs {
+                    owner: Owner, repo: Repo, number: PrNumber,
+                }),
+            cmd_get_side_by_side_diff("Get Side-by-Side Diff", auth = false, GetSideBySideDiffArgs {
+                path: RepoPath, base_content: Option<String>, head_content: Option<String>,
+                context: Option<usize>, ignore_whitespace: Option<bool>,
+            }),
+            cmd_get_changes_since_my_review("Show Changes Since My Review", auth = true,
+                GetChangesSinceMyReviewArgs {
+                    owner: Owner, repo: Repo, pr: PrNumber,
+                }),
+            cmd_check_base_drift("Check Base Branch Drift", auth = true, CheckBaseDriftArgs {
+                owner: Owner, repo: Repo, number: PrNumber,
+            }),
+            cmd_get_issue("Get Issue", auth = true, GetIssueArgs {
+                owner: Owner, repo: Repo, number: PrNumber,
+            }),
+            cmd_check_acronyms("Check Acronym First Use", auth = false, CheckAcronymsArgs {
+                owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                path: RepoPath, head_content: String, patch: Option<String>,
+            }),
+            cmd_check_alt_text("Check Image Alt Text", auth = false, CheckAltTextArgs {
+                owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                path: RepoPath, head_content: String, patch: Option<String>,
+            }),
+            cmd_check_front_matter_freshness("Check Front Matter Dates", auth = false,
+                CheckFrontMatterFreshnessArgs {
+                    owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                    path: RepoPath, head_content: String, patch: String,
+                    date_fields: Option<Vec<String>>,
+                }),
+            cmd_validate_markdown_structure("Validate Markdown Structure", auth = false,
+                ValidateMarkdownStructureArgs {
+                    owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                    content: String, patch: Option<String>,
+                }),
+            cmd_validate_diagrams("Validate Diagrams", auth = false, ValidateDiagramsArgs {
+                owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                content: String,
+            }),
+            cmd_validate_spec("Validate API Spec", auth = false, ValidateSpecArgs {
+                owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                path: RepoPath, content: String,
+            }),
+            cmd_get_heading_style_config("Get Heading Style", auth = false),
+            cmd_set_heading_style_config("Save Heading Style", auth = false,
+                SetHeadingStyleConfigArgs {
+                    config: heading_style::HeadingStyleConfig,
+                }),
+            cmd_get_local_roots("Get Local Roots", auth = false),
+            cmd_set_local_roots("Save Local Roots", auth = false, SetLocalRootsArgs {
+                config: local_roots::LocalRootsConfig,
+            }),
+            cmd_add_local_root("Allow Local Folder", auth = false),
+            cmd_check_headings("Check Heading Style", auth = false, CheckHeadingsArgs {
+                owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                content: String, patch: Option<String>,
+            }),
+            cmd_get_snippet_checks_config("Get Snippet Checks", auth = false),
+            cmd_set_snippet_checks_config("Save Snippet Checks", auth = false,
+                SetSnippetChecksConfigArgs {
+                    config: snippet_checks::SnippetChecksConfig,
+                }),
+            cmd_check_snippets("Check Code Snippets", auth = false, CheckSnippetsArgs {
+                owner: Option<Owner>, repo: Option<Repo>, pr_number: Option<PrNumber>,
+                content: String, patch: Option<String>,
+            }),
+            cmd_render_markdown("Render Markdown", auth = true, RenderMarkdownArgs {
+                owner: Option<Owner>, repo: Option<Repo>, content: String,
+            }),
+            cmd_add_comment_reaction("React to Comment", auth = true, AddCommentReactionArgs {
+                owner: Owner, repo: Repo, kind: permalinks::CommentKind, comment_id: u64,
+                content: Reaction,
+            }),
+            cmd_remove_comment_reaction("Remove Comment Reaction", auth = true,
+                RemoveCommentReactionArgs {
+                    owner: Owner, repo: Repo, kind: permalinks::CommentKind, comment_id: u64,
+                    content: Reaction,
+                }),
+            cmd_get_comment_permalink("Copy Comment Link", auth = true, GetCommentPermalinkArgs {
+                owner: Owner, repo: Repo, pr: PrNumber, comment_id: u64,
+            }),
+            cmd_get_line_permalink("Copy Line Link", auth = false, GetLinePermalinkArgs {
+                owner: Owner, repo: Repo, sha: String, path: RepoPath, line: u64,
+            }),
+            cmd_build_quote_reply("Quote Reply", auth = false, BuildQuoteReplyArgs {
+                comment_id: i64, owner: Option<Owner>, repo: Option<Repo>,
+            }),
+            cmd_expand_emoji("Expand Emoji Shortcodes", auth = true, ExpandEmojiArgs {
+                text: String,
+            }),
+            cmd_list_emoji("List Emoji", auth = true, ListEmojiArgs {
+                filter: Option<String>, limit: Option<usize>,
+            }),
+            cmd_request_changes_followup("Re-request My Review", auth = true,
+                RequestChangesFollowupArgs {
+                    owner: Owner, repo: Repo, pr: PrNumber, post_comment: Option<bool>,
+                }),
+            cmd_handoff_review("Hand Off Local Review", auth = true, HandoffReviewArgs {
+                owner: Owner, repo: Repo, pr: PrNumber,
+            }),
+            cmd_export_review_activity("Export Review Activity", auth = true,
+                ExportReviewActivityArgs {
+                    org: Owner, since: String, format: review_activity::ActivityExportFormat,
+                }),
+            cmd_import_review_handoff("Import Review Handoff", auth = false,
+                ImportReviewHandoffArgs {
+                    head_sha: Option<String>,
+                }),
+            cmd_local_set_comment_category("Set Local Comment Category", auth = false,
+                LocalSetCommentCategoryArgs {
+                    comment_id: i64, category: Option<String>,
+                }),
+            cmd_export_comments_as_tasklist("Export Comments as Task List", auth = false,
+                ExportCommentsAsTasklistArgs {
+                    owner: Owner, repo: Repo, pr: PrNumber, post: Option<bool>,
+                    acknowledge_warnings: Option<bool>,
+                }),
+            cmd_export_review("Export Review", auth = false, ExportReviewArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber,
+                format: comment_export::ReviewExportFormat,
+            }),
+            cmd_import_review("Import Review", auth = true, ImportReviewArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber,
+            }),
+            cmd_import_comments("Import Comments From a Punch List", auth = true,
+                ImportCommentsArgs {
+                    owner: Owner, repo: Repo, pr: PrNumber,
+                    format: review_import::PunchListFormat,
+                }),
+            cmd_submit_by_category("Submit Review by Category", auth = true, SubmitByCategoryArgs {
+                owner: Owner, repo: Repo, pr: PrNumber,
+                mapping: Option<std::collections::HashMap<String, categories::SubmissionTarget>>,
+                body: Option<String>, acknowledge_warnings: Option<bool>,
+            }),
+            cmd_get_content_filter("Get Outbound Content Filter", auth = false),
+            cmd_set_content_filter("Save Outbound Content Filter", auth = false,
+                SetContentFilterArgs {
+                    config: content_filter::ContentFilterConfig,
+                }),
+            cmd_check_local_review_content("Check Local Review Content", auth = false,
+                CheckLocalReviewContentArgs {
+                    owner: Owner, repo: Repo, pr_number: PrNumber, body: Option<String>,
+                }),
+            cmd_extract_todos("Extract TODO Markers", auth = true, ExtractTodosArgs {
+                owner: Owner, repo: Repo, number: PrNumber,
+            }),
+            cmd_get_repo_review_config("Get Repository Review Config", auth = true,
+                GetRepoReviewConfigArgs {
+                    owner: Owner, repo: Repo, pr_number: Option<PrNumber>,
+                }),
+            cmd_filter_review_files("Filter Files by Repository Review Config", auth = true,
+                FilterReviewFilesArgs {
+                    owner: Owner, repo: Repo, paths: Vec<RepoPath>,
+                }),
+            cmd_get_quiet_hours("Get Quiet Hours", auth = false),
+            cmd_set_quiet_hours("Save Quiet Hours", auth = false, SetQuietHoursArgs {
+                config: quiet_hours::QuietHoursConfig,
+            }),
+            cmd_get_quiet_hours_status("Get Quiet Hours Status", auth = false),
+            cmd_get_session("Get Saved Session", auth = false),
+            cmd_save_session("Save Session", auth = false, SaveSessionArgs {
+                session: session::SessionState,
+            }),
+            cmd_get_pr_size_config("Get PR Size Settings", auth = false),
+            cmd_set_pr_size_config("Save PR Size Settings", auth = false, SetPrSizeConfigArgs {
+                config: pr_size::PrSizeConfig,
+            }),
+            cmd_get_owner_policy("Get Organization Policy", auth = false),
+            cmd_list_plugins("List Analyzer Plugins", auth = false),
+            cmd_reload_plugins("Reload Analyzer Plugins", auth = false),
+            cmd_run_plugins("Run Analyzer Plugins", auth = false, RunPluginsArgs {
+                owner: Owner, repo: Repo, pr_number: PrNumber, commit_id: String,
+                files: Vec<plugins::PluginFileInput>,
+                /// Save each finding as a local draft comment in addition to returning it.
+                #[serde(default)]
+                save_as_drafts: bool,
+            }),
+            cmd_list_commands("List Commands", auth = false),
+            cmd_open_url("Open URL in Browser", auth = false, OpenUrlArgs {
+                url: String,
+            }),
+        }
+    };
+    (@handler $($name:ident($title:literal, auth = $auth:literal $(, $args:ident {
+        $($(#[$meta:meta])* $param:ident: $ty:ty),* $(,)?
+    })?)),* $(,)?) => {
+        tauri::generate_handler![$($name),*]
+    };
+    (@registry $($name:ident($title:literal, auth = $auth:literal $(, $args:ident {
+        $($(#[$meta:meta])* $param:ident: $ty:ty),* $(,)?
+    })?)),* $(,)?) => {
+        vec![$(
+            commands::CommandInfo::new(
+                stringify!($name),
+                $title,
+                $auth,
+                vec![$($(
+                    commands::ParamInfo::new(stringify!($param), stringify!($ty))
+                        .with_attributes(&[$(stringify!($meta)),*])
+                ),*)?],
+            )
+        ),*]
+    };
+    (@args $($name:ident($title:literal, auth = $auth:literal $(, $args:ident {
+        $($(#[$meta:meta])* $param:ident: $ty:ty),* $(,)?
+    })?)),* $(,)?) => {
+        $($(
+            #[derive(Deserialize)]
+            #[serde(rename_all = "camelCase", deny_unknown_fields)]
+            struct $args {
+                $($(#[$meta])* $param: $ty),*
+            }
+        )?)*
+    };
 }
 
-#[tauri::command]
-async fn cmd_open_url(url: String) -> Result<(), String> {
-    open::that(&url)
-        .map_err(|e| format!("Failed to open URL: {:?}", e))?;
-    Ok(())
+app_commands!(args);
+
+fn command_registry() -> Vec<commands::CommandInfo> {
+    app_commands!(registry)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -901,17 +4472,11 @@ pub fn run() {
             let data_dir = app.path().app_data_dir()
                 .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
             
-            tracing::info!("Initializing review storage at {:?}", data_dir);
-            
-            review_storage::init_storage(&data_dir)
-                .map_err(|e| {
-                    tracing::error!("Failed to initialize review storage: {:?}", e);
-                    format!("Failed to initialize review storage: {:?}", e)
-                })?;
-            
-            tracing::info!("Review storage initialized successfully");
-            
-            // Set up panic handler to log panics to the log folder
+            // Decide on safe mode before anything else can crash
+            let startup = safe_mode::begin_startup(&data_dir);
+
+            // Set up panic handler to log panics to the log folder and count crashes at
+            // startup towards safe mode
             let log_dir = data_dir.join("review_logs");
             std::panic::set_hook(Box::new(move |panic_info| {
                 let payload = panic_info.payload();
@@ -930,6 +4495,7 @@ pub fn run() {
                 };
                 
                 let crash_msg = format!("PANIC occurred at {}: {}", location, msg);
+                safe_mode::record_crash(msg, &location);
                 
                 // Log to tracing/stderr
                 tracing::error!("{}", crash_msg);
@@ -955,6 +4521,72 @@ pub fn run() {
                 }
             }));
             
+            // Upgrade older app-data layouts before anything opens the database
+            match migrations::run_startup_migrations(&data_dir) {
+                Ok(report) if !report.applied.is_empty() || report.error.is_some() => {
+                    tracing::info!(
+                        "App data migrated from v{} to v{} (backup: {:?}, error: {:?})",
+                        report.previous_version,
+                        report.current_version,
+                        report.backup_dir,
+                        report.error
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to run app data migrations: {:?}", e),
+            }
+
+            events::init(app.handle().clone());
+            pr_cache::init(&data_dir);
+
+            tracing::info!("Initializing review storage at {:?}", data_dir);
+            
+            review_storage::init_storage(&data_dir)
+                .map_err(|e| {
+                    tracing::error!("Failed to initialize review storage: {:?}", e);
+                    format!("Failed to initialize review storage: {:?}", e)
+                })?;
+            
+            tracing::info!("Review storage initialized successfully");
+
+            owner_policy::init(&data_dir);
+
+            // Apply the stored locale before any user-facing strings are produced
+            if let Ok(storage) = review_storage::get_storage() {
+                match settings::load_preferences(storage) {
+                    Ok(prefs) => {
+                        i18n::set_locale(prefs.locale);
+                        time_format::set_zone(prefs.zone().unwrap_or_default());
+                        metrics::set_usage_enabled(prefs.usage_metrics);
+                    }
+                    Err(e) => tracing::warn!("Failed to load preferences: {}", e),
+                }
+                if !startup.active {
+                    if let Err(e) = purge_expired_trash(storage) {
+                        tracing::warn!("Failed to purge expired trashed reviews: {}", e);
+                    }
+                    if let Err(e) = metrics::purge_old_usage(storage, chrono::Utc::now()) {
+                        tracing::warn!("Failed to purge old usage metrics: {}", e);
+                    }
+                    stale_reviews::spawn_background_check(storage);
+                }
+            }
+
+            if startup.active {
+                tracing::warn!(
+                    "Starting in safe mode after {} crashes at startup; last: {:?}",
+                    startup.startup_crashes,
+                    startup.last_crash
+                );
+                events::emit(safe_mode::SAFE_MODE_EVENT, startup);
+            } else {
+                // Analyzer plugins are optional; a broken plugin setup must not block startup
+                if let Err(e) = plugins::init_plugins(&data_dir) {
+                    tracing::error!("Failed to initialize analyzer plugins: {}", e);
+                }
+                safe_mode::spawn_clean_start_timer();
+            }
+
             eprintln!("🚀 Application starting - if crash occurs, check crash.log in log folder");
 
             #[cfg(debug_assertions)]
@@ -969,41 +4601,12 @@ pub fn run() {
             
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            cmd_load_local_directory,
-            cmd_start_github_oauth,
-            cmd_check_auth_status,
-            cmd_logout,
-            cmd_list_pull_requests,
-            cmd_get_pull_request,
-            cmd_get_pull_request_metadata,
-            cmd_get_file_contents,
-            cmd_submit_review_comment,
-            cmd_submit_file_comment,
-            cmd_start_pending_review,
-            cmd_submit_pending_review,
-            cmd_delete_review,
-            cmd_get_pending_review_comments,
-            cmd_open_devtools,
-            cmd_open_log_folder,
-            cmd_get_prs_under_review,
-            cmd_local_start_review,
-            cmd_local_add_comment,
-            cmd_local_update_review_commit,
-            cmd_local_update_comment_file_path,
-            cmd_local_update_comment,
-            cmd_local_delete_comment,
-            cmd_github_update_comment,
-            cmd_github_delete_comment,
-            cmd_fetch_file_content,
-            cmd_local_get_comments,
-            cmd_local_get_review_metadata,
-            cmd_local_abandon_review,
-            cmd_local_clear_review,
-            cmd_submit_local_review,
-            cmd_get_storage_info,
-            cmd_open_url
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(app_commands!(handler))
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                metrics::flush_api_latencies();
+            }
+        });
 }
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::local_files::is_markdown_path;
+
+/// Most markdown files read from a repository to build an index; larger doc sets are
+/// indexed partially and the index says so.
+pub const MAX_INDEXED_FILES: usize = 500;
+
+/// A heading anchor as GitHub generates it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Anchor {
+    /// Fragment without `#`, e.g. `install-the-cli`.
+    pub slug: String,
+    /// 1-based line of the heading.
+    pub line: u64,
+}
+
+/// A link from one markdown file to a heading in another (or the same) file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IndexedLink {
+    pub source_path: String,
+    /// 1-based line of the link in the source file.
+    pub line: u64,
+    /// Repository-relative path of the linked file.
+    pub target_path: String,
+    /// Fragment without `#`, lowercased.
+    pub anchor: String,
+}
+
+/// In-repo links to heading anchors, collected from every indexed markdown file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LinkIndex {
+    pub links: Vec<IndexedLink>,
+    pub indexed_files: usize,
+    /// Whether the repository had more markdown files than `MAX_INDEXED_FILES`.
+    pub truncated: bool,
+}
+
+fn inline_link_regex() -> &'static Regex {
+    static INLINE: OnceLock<Regex> = OnceLock::new();
+    INLINE.get_or_init(|| {
+        Regex::new(r#"\]\(\s*<?([^)\s>]*)>?(?:\s+"[^"]*")?\s*\)"#)
+            .expect("inline link pattern is valid")
+    })
+}
+
+fn reference_definition_regex() -> &'static Regex {
+    static DEFINITION: OnceLock<Regex> = OnceLock::new();
+    DEFINITION.get_or_init(|| {
+        Regex::new(r"^\s{0,3}\[[^\]]+\]:\s*<?(\S+?)>?(?:\s|$)")
+            .expect("reference definition pattern is valid")
+    })
+}
+
+/// GitHub's anchor for a heading: lowercase, punctuation dropped, spaces as hyphens.
+pub fn slugify(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Lines of `content` outside fenced code blocks, with their 1-based numbers.
+fn prose_lines(content: &str) -> impl Iterator<Item = (u64, &str)> {
+    let mut in_fence = false;
+    content.lines().enumerate().filter_map(move |(index, line)| {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            return None;
+        }
+        (!in_fence).then_some((index as u64 + 1, line))
+    })
+}
+
+/// Heading anchors of markdown `content` in order. Repeated headings get `-1`, `-2`, ...
+/// suffixes as on GitHub.
+pub fn anchors(content: &str) -> Vec<Anchor> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut anchors = Vec::new();
+    for (line, text) in prose_lines(content) {
+        let trimmed = text.trim_end();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if level == 0 || level > 6 || !trimmed[level..].starts_with(' ') {
+            continue;
+        }
+        let title = trimmed[level..].trim().trim_end_matches('#');
+        // Inline code and link syntax contribute only their text
+        let title = title.replace('`', "");
+        let base = slugify(&title);
+        let count = seen.entry(base.clone()).or_insert(0);
+        let slug = if *count == 0 { base } else { format!("{base}-{count}") };
+        *count += 1;
+        anchors.push(Anchor { slug, line });
+    }
+    anchors
+}
+
+/// Resolve `target` (already stripped of its fragment) against the directory of
+/// `source_path`. `None` for links that leave the repository.
+pub fn resolve(source_path: &str, target: &str) -> Option<String> {
+    if target.is_empty() {
+        return Some(source_path.to_string());
+    }
+    let mut parts: Vec<&str> = if target.starts_with('/') {
+        Vec::new()
+    } else {
+        let mut dir: Vec<&str> = source_path.split('/').collect();
+        dir.pop();
+        dir
+    };
+    for part in target.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            _ => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Links with a fragment in markdown `content` at `path`. External links (anything
+/// with a scheme) are skipped.
+pub fn links(path: &str, content: &str) -> Vec<IndexedLink> {
+    let mut found = Vec::new();
+    for (line, text) in prose_lines(content) {
+        let inline = inline_link_regex().captures_iter(text).map(|caps| caps[1].to_string());
+        let definition = reference_definition_regex()
+            .captures(text)
+            .map(|caps| caps[1].to_string());
+        for target in inline.chain(definition) {
+            let Some((target, fragment)) = target.split_once('#') else {
+                continue;
+            };
+            if fragment.is_empty() || target.contains(':') {
+                continue;
+            }
+            if !target.is_empty() && !is_markdown_path(target) {
+                continue;
+            }
+            if let Some(target_path) = resolve(path, target) {
+                found.push(IndexedLink {
+                    source_path: path.to_string(),
+                    line,
+                    target_path,
+                    anchor: fragment.to_lowercase(),
+                });
+            }
+        }
+    }
+    found
+}
+
+impl LinkIndex {
+    /// Index `(path, content)` pairs of markdown files.
+    pub fn build(files: &[(String, String)], truncated: bool) -> Self {
+        Self {
+            links: files
+                .iter()
+                .flat_map(|(path, content)| links(path, content))
+                .collect(),
+            indexed_files: files.len(),
+            truncated,
+        }
+    }
+
+    /// Links pointing at `anchor` in `target_path`.
+    pub fn links_to<'a>(
+        &'a self,
+        target_path: &'a str,
+        anchor: &'a str,
+    ) -> impl Iterator<Item = &'a IndexedLink> + 'a {
+        self.links
+            .iter()
+            .filter(move |link| link.target_path == target_path && link.anchor == anchor)
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// An issue a PR description says it closes. `owner`/`repo` are `None` for a bare `#123`,
+/// which refers to the PR's own repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueReference {
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub number: u64,
+}
+
+fn closing_keyword_regex() -> &'static Regex {
+    static CLOSING: OnceLock<Regex> = OnceLock::new();
+    CLOSING.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:close[sd]?|fix(?:e[sd])?|resolve[sd]?)\s*:?\s+(?:https?://[^/\s]+/([\w.-]+)/([\w.-]+)/issues/(\d+)|(?:([\w.-]+)/([\w.-]+))?#(\d+))\b",
+        )
+        .expect("closing keyword pattern is valid")
+    })
+}
+
+/// Issues referenced with GitHub's closing keywords ("Fixes #123", "closes org/repo#4",
+/// "Resolves https://github.com/org/repo/issues/5"), in order and without duplicates.
+/// Code spans and fenced blocks are skipped.
+pub fn closing_references(body: &str) -> Vec<IssueReference> {
+    let mut references: Vec<IssueReference> = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let prose: String = line.split('`').step_by(2).collect::<Vec<_>>().join(" ");
+        for caps in closing_keyword_regex().captures_iter(&prose) {
+            let owner = caps.get(1).or(caps.get(4)).map(|m| m.as_str().to_string());
+            let repo = caps.get(2).or(caps.get(5)).map(|m| m.as_str().to_string());
+            let Some(number) = caps
+                .get(3)
+                .or(caps.get(6))
+                .and_then(|m| m.as_str().parse().ok())
+            else {
+                continue;
+            };
+            let reference = IssueReference { owner, repo, number };
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+    }
+
+    references
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::models::{SecretFinding, SkippedFile};
+use crate::secrets;
+
+/// Canonical paths of the folders opened in local directory mode, by the id their files
+/// are read back with.
+fn loaded_folders() -> &'static Mutex<HashMap<String, PathBuf>> {
+    static FOLDERS: OnceLock<Mutex<HashMap<String, PathBuf>>> = OnceLock::new();
+    FOLDERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Remember `base`, which has passed the local roots check, under `id`.
+pub fn register_folder(id: &str, base: PathBuf) {
+    if let Ok(mut folders) = loaded_folders().lock() {
+        folders.insert(id.to_string(), base);
+    }
+}
+
+pub fn loaded_folder(id: &str) -> Option<PathBuf> {
+    loaded_folders().lock().ok()?.get(id).cloned()
+}
+
+/// Name local reviews of the folder at `base` are stored under: its canonical path as
+/// returned by `LocalRootsConfig::check`, so `docs`, `docs/` and the folder's absolute
+/// path all open the same review.
+pub fn folder_key(base: &Path) -> String {
+    base.to_string_lossy().into_owned()
+}
+
+/// The `LOCAL-…` id standing in for a head commit of the folder at canonical path `base`,
+/// under which it is registered for `cmd_read_local_file`.
+pub fn folder_id(base: &Path) -> String {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(base.as_os_str().as_encoded_bytes());
+    format!("LOCAL-{}", URL_SAFE_NO_PAD.encode(&digest[..12]))
+}
+
+/// Pseudo pull request number for local reviews of `directory`, from a hash of its
+/// canonical path as returned by `LocalRootsConfig::check`, so that the same folder keeps
+/// landing on the same number however it was entered. Collisions are resolved by
+/// `ReviewStorage::local_folder_number`.
+pub fn folder_number(directory: &Path) -> u64 {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(directory.as_os_str().as_encoded_bytes());
+    let hash = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) & 0x7fff_ffff;
+    u64::from(hash).max(2)
+}
+
+/// Whether `path` has a markdown extension. The extension is compared as an `OsStr`, so
+/// names that are not valid UTF-8 are still recognized.
+pub fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ["md", "markdown", "mdx"].iter().any(|m| ext.eq_ignore_ascii_case(m)))
+}
+
+/// [`is_markdown`] for a repository path such as `docs/intro.md`.
+pub fn is_markdown_path(path: &str) -> bool {
+    is_markdown(Path::new(path))
+}
+
+/// `path` as an absolute extended-length path (`\\?\C:\...`), which Windows APIs accept
+/// past the 260-character `MAX_PATH` limit. Verbatim paths skip Windows' own `..` and
+/// `/` handling, so the path is made absolute first.
+#[cfg(windows)]
+pub fn extended_length(path: &Path) -> PathBuf {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    use std::path::{Component, Prefix};
+
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+    match absolute.components().next() {
+        Some(Component::Prefix(prefix)) if matches!(prefix.kind(), Prefix::Disk(_)) => {
+            let wide: Vec<u16> = r"\\?\"
+                .encode_utf16()
+                .chain(absolute.as_os_str().encode_wide())
+                .collect();
+            PathBuf::from(OsString::from_wide(&wide))
+        }
+        _ => absolute,
+    }
+}
+
+/// Other platforms have no path length limit to work around.
+#[cfg(not(windows))]
+pub fn extended_length(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// `path` relative to `base` with `/` separators, or `None` when a name in it is not
+/// valid UTF-8 and so cannot be shown or stored without losing bytes.
+pub fn relative_path(base: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(base).unwrap_or(path);
+    let parts: Option<Vec<&str>> = rel.components().map(|c| c.as_os_str().to_str()).collect();
+    parts.map(|parts| parts.join("/"))
+}
+
+/// A file under `base` that was left out, named as closely as its bytes allow.
+pub fn skipped(base: &Path, path: &Path, reason: impl ToString) -> SkippedFile {
+    let rel = path.strip_prefix(base).unwrap_or(path);
+    SkippedFile {
+        path: rel.to_string_lossy().replace('\\', "/"),
+        reason: reason.to_string(),
+    }
+}
+
+/// Likely credentials in `files` under `base`. Each file is read only to scan it and its
+/// content is dropped right after, so a large folder is never held in memory at once.
+/// Files that cannot be read are left out; opening them reports the error.
+pub fn scan_secrets(base: &Path, files: &[PathBuf]) -> Vec<SecretFinding> {
+    files
+        .iter()
+        .filter_map(|path| {
+            let rel_path = relative_path(base, path)?;
+            let content = fs::read_to_string(path).ok()?;
+            Some(secrets::scan_content(&rel_path, &content))
+        })
+        .flatten()
+        .collect()
+}
+
+/// Markdown files under `dir`, sorted. Symlinks are not followed. Subfolders and entries
+/// that cannot be read are added to `skipped` rather than failing the walk; only an
+/// unreadable `dir` is an error.
+pub fn collect_markdown_files(
+    dir: &Path,
+    skipped: &mut Vec<SkippedFile>,
+) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    walk(dir, dir, &mut files, skipped)?;
+    files.sort();
+    Ok(files)
+}
+
+fn walk(
+    base: &Path,
+    dir: &Path,
+    out: &mut Vec<PathBuf>,
+    skipped_files: &mut Vec<SkippedFile>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                skipped_files.push(skipped(base, dir, err));
+                continue;
+            }
+        };
+        let path = entry.path();
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(err) => {
+                skipped_files.push(skipped(base, &path, err));
+                continue;
+            }
+        };
+
+        if file_type.is_dir() {
+            if let Err(err) = walk(base, &path, out, skipped_files) {
+                skipped_files.push(skipped(base, &path, err));
+            }
+        } else if file_type.is_file() && is_markdown(&path) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
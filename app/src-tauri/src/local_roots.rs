@@ -0,0 +1,152 @@
+use std::path::{Component, Path, PathBuf, Prefix};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewStorage;
+use crate::settings;
+
+pub const LOCAL_ROOTS_KEY: &str = "local_roots";
+
+/// Folders local directory mode may read from. A folder is allowed when it is one of
+/// these roots or inside one, after symlinks and `..` segments are resolved. Roots are
+/// only added from the native folder picker the backend shows; the webview can remove
+/// them but never add one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalRootsConfig {
+    pub roots: Vec<String>,
+}
+
+impl LocalRootsConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        for root in &self.roots {
+            let path = Path::new(root);
+            if is_network_path(path) {
+                return Err(AppError::InvalidInput(format!(
+                    "local root {root:?} is a network share"
+                )));
+            }
+            if !path.is_absolute() {
+                return Err(AppError::InvalidInput(format!(
+                    "local root {root:?} must be an absolute path"
+                )));
+            }
+            if is_too_broad(path) {
+                return Err(AppError::InvalidInput(format!(
+                    "local root {root:?} is a filesystem or home root; pick the folder with the documents instead"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Canonical form of `dir` when it lies inside one of the roots. Roots that no
+    /// longer exist, and roots too broad to be allowed, are ignored.
+    pub fn check(&self, dir: &Path) -> AppResult<PathBuf> {
+        let canonical = canonical_local(dir)?;
+        let allowed = self
+            .roots
+            .iter()
+            .filter_map(|root| std::fs::canonicalize(root).ok())
+            .filter(|root| !is_too_broad(root))
+            .any(|root| canonical.starts_with(root));
+        if !allowed {
+            return Err(AppError::PathNotAllowed(dir.display().to_string()));
+        }
+        Ok(canonical)
+    }
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<LocalRootsConfig> {
+    settings::load(storage, LOCAL_ROOTS_KEY)
+}
+
+fn save_config(storage: &ReviewStorage, config: &LocalRootsConfig) -> AppResult<()> {
+    config.validate()?;
+    settings::save(storage, LOCAL_ROOTS_KEY, config)
+}
+
+/// Keep only the stored roots listed in `config`. Roots the store does not hold yet are
+/// refused, since only the folder picker may add one.
+pub fn retain_roots(storage: &ReviewStorage, config: &LocalRootsConfig) -> AppResult<LocalRootsConfig> {
+    let mut stored = load_config(storage)?;
+    if let Some(unknown) = config.roots.iter().find(|root| !stored.roots.contains(root)) {
+        return Err(AppError::PathNotAllowed(unknown.clone()));
+    }
+    stored.roots.retain(|root| config.roots.contains(root));
+    save_config(storage, &stored)?;
+    Ok(stored)
+}
+
+/// Allow `dir`, which the user chose in the folder picker, unless an existing root
+/// already covers it.
+pub fn add_root(storage: &ReviewStorage, dir: &Path) -> AppResult<LocalRootsConfig> {
+    let mut config = load_config(storage)?;
+    if config.check(dir).is_err() {
+        let canonical = canonical_local(dir)?;
+        config.roots.push(canonical.to_string_lossy().into_owned());
+        save_config(storage, &config)?;
+    }
+    Ok(config)
+}
+
+/// Whether `path` names a network location: `\\server\share`, `//server/share`, or a
+/// `\\?\UNC\` or `\\.\` device path. Verbatim disk paths such as `\\?\C:\docs`, which
+/// canonicalization produces on Windows, are local.
+pub fn is_network_path(path: &Path) -> bool {
+    let prefix = match path.components().next() {
+        Some(Component::Prefix(prefix)) => Some(prefix.kind()),
+        _ => None,
+    };
+    match prefix {
+        Some(Prefix::VerbatimDisk(_) | Prefix::Disk(_)) => false,
+        Some(_) => true,
+        None => {
+            let text = path.to_string_lossy();
+            let mut chars = text.chars();
+            matches!(
+                (chars.next(), chars.next()),
+                (Some('/' | '\\'), Some('/' | '\\'))
+            )
+        }
+    }
+}
+
+/// Whether `path` is a filesystem root, the home folder or one of its ancestors. Allowing
+/// any of these would open every document on the machine to local review.
+pub fn is_too_broad(path: &Path) -> bool {
+    if path.parent().is_none() {
+        return true;
+    }
+    let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) else {
+        return false;
+    };
+    let home = PathBuf::from(home);
+    let canonical_home = std::fs::canonicalize(&home).unwrap_or_else(|_| home.clone());
+    home.starts_with(path) || canonical_home.starts_with(path)
+}
+
+/// `dir` with symlinks and `..` resolved. Network paths are refused before anything
+/// touches them, and again afterwards in case a link pointed at a share.
+fn canonical_local(dir: &Path) -> AppResult<PathBuf> {
+    if is_network_path(dir) {
+        return Err(AppError::PathNotAllowed(dir.display().to_string()));
+    }
+    let canonical = std::fs::canonicalize(dir)
+        .map_err(|e| AppError::InvalidInput(format!("cannot open {}: {e}", dir.display())))?;
+    if is_network_path(&canonical) {
+        return Err(AppError::PathNotAllowed(dir.display().to_string()));
+    }
+    Ok(canonical)
+}
+
+/// Canonical form of `path` when it is still inside the canonical folder `base`, so a
+/// file swapped for a link after the walk is not followed out of the folder.
+pub fn ensure_within(base: &Path, path: &Path) -> AppResult<PathBuf> {
+    let canonical = canonical_local(path)?;
+    if !canonical.starts_with(base) {
+        return Err(AppError::PathNotAllowed(path.display().to_string()));
+    }
+    Ok(canonical)
+}
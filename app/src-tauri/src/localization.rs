@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::models::PullRequestFile;
+
+/// Locale the other language folders are translated from.
+pub const DEFAULT_SOURCE_LOCALE: &str = "en";
+
+/// Language codes recognized as folder names. A plain two-letter list would also match
+/// folders such as `ci` or `ui`, so only languages docs are commonly translated into
+/// are listed.
+const LANGUAGES: &[&str] = &[
+    "ar", "bg", "ca", "cs", "da", "de", "el", "en", "es", "et", "fa", "fi", "fr", "he", "hi",
+    "hr", "hu", "id", "it", "ja", "ko", "lt", "lv", "ms", "nb", "nl", "no", "pl", "pt", "ro",
+    "ru", "sk", "sl", "sr", "sv", "th", "tr", "uk", "vi", "zh",
+];
+
+/// A translated file and its source, side by side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LocalizedPair {
+    pub source_path: String,
+    pub source_locale: String,
+    /// `None` when the source file does not exist at the requested ref.
+    pub source_content: Option<String>,
+    pub translation_path: String,
+    pub translation_locale: String,
+    pub translation_content: Option<String>,
+}
+
+/// Language part of a locale folder name: `ja` for `ja`, `ja-JP`, or `ja_jp`.
+fn language(segment: &str) -> Option<String> {
+    let mut parts = segment.splitn(2, ['-', '_']);
+    let language = parts.next()?.to_ascii_lowercase();
+    let region_ok = parts.next().is_none_or(|region| {
+        (2..=4).contains(&region.len()) && region.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    (region_ok && LANGUAGES.contains(&language.as_str())).then_some(language)
+}
+
+/// Index of the first directory in `path` named after a locale.
+fn locale_index(segments: &[&str]) -> Option<usize> {
+    let directories = segments.len().saturating_sub(1);
+    segments[..directories]
+        .iter()
+        .position(|segment| language(segment).is_some())
+}
+
+/// Locale folder of `path` as written, e.g. `ja-jp` for `docs/ja-jp/intro.md`.
+pub fn locale_of(path: &str) -> Option<String> {
+    let segments: Vec<&str> = path.split('/').collect();
+    locale_index(&segments).map(|index| segments[index].to_string())
+}
+
+/// `path` with its locale folder replaced by `locale`.
+pub fn with_locale(path: &str, locale: &str) -> Option<String> {
+    let mut segments: Vec<&str> = path.split('/').collect();
+    let index = locale_index(&segments)?;
+    segments[index] = locale;
+    Some(segments.join("/"))
+}
+
+/// Whether two locale folder names are the same language, so `en` matches `en-us`.
+pub fn same_language(a: &str, b: &str) -> bool {
+    language(a).is_some_and(|a| language(b) == Some(a))
+}
+
+/// Source file a translation mirrors, or `None` when `path` has no locale folder or is
+/// itself in the source language.
+pub fn source_path(path: &str, source_locale: &str) -> Option<String> {
+    let locale = locale_of(path)?;
+    if same_language(&locale, source_locale) {
+        return None;
+    }
+    with_locale(path, source_locale)
+}
+
+/// Link translated files to their source when both are in `files`: a translation gets
+/// the source path and the source gets its first translation. Files are matched on
+/// everything but the locale folder, so `docs/en-us/a.md` pairs with `docs/ja-jp/a.md`.
+/// Removed files have no head version to open side by side and stay unpaired.
+pub fn pair_files(files: &mut [PullRequestFile], source_locale: &str) {
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, file) in files.iter().enumerate() {
+        if file.status == "removed" {
+            continue;
+        }
+        if let Some(key) = with_locale(&file.path, "\0") {
+            groups.entry(key).or_default().push(index);
+        }
+    }
+
+    for members in groups.values().filter(|members| members.len() > 1) {
+        let Some(&source) = members.iter().find(|&&index| {
+            locale_of(&files[index].path).is_some_and(|l| same_language(&l, source_locale))
+        }) else {
+            continue;
+        };
+        let source_path = files[source].path.clone();
+        let translations: Vec<usize> =
+            members.iter().copied().filter(|&index| index != source).collect();
+        files[source].localized_pair = Some(files[translations[0]].path.clone());
+        for index in translations {
+            files[index].localized_pair = Some(source_path.clone());
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+
+/// Rendered documents kept in the cache; the least recently viewed go first.
+pub const RENDER_CACHE_MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenderedMarkdown {
+    pub blob_sha: String,
+    pub html: String,
+    /// Whether the HTML came from the cache rather than a fresh render.
+    pub cached: bool,
+}
+
+/// The git blob SHA of `content`, the same one GitHub reports for the file, so a blob
+/// renders once however many PRs or commits it appears in.
+pub fn git_blob_sha(content: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(format!("blob {}\0", content.len()).as_bytes());
+    hasher.update(content.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Cache key part for the repository references resolve against; `""` without one.
+pub fn render_context(owner: Option<&str>, repo: Option<&str>) -> String {
+    match (owner, repo) {
+        (Some(owner), Some(repo)) => format!("{owner}/{repo}"),
+        _ => String::new(),
+    }
+}
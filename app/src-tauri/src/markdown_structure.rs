@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::diff::added_lines;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructureIssueKind {
+    /// A table row, or the delimiter row, has a different column count than the header.
+    TableColumnMismatch,
+    /// A code fence is still open at the end of the file.
+    UnclosedFence,
+    /// `-item` or `1.item` right after a list item: without a space the line renders as
+    /// a paragraph.
+    ListMarkerMissingSpace,
+    /// Switching bullet characters mid-list starts a new list.
+    ListMarkerMismatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StructureIssue {
+    pub kind: StructureIssueKind,
+    /// 1-based head line to attach a comment to.
+    pub line: u64,
+    pub detail: String,
+}
+
+struct Found {
+    issue: StructureIssue,
+    /// Lines of the table, fence, or list the issue belongs to; an issue counts as
+    /// introduced by the diff when any of them changed.
+    block: RangeInclusive<u64>,
+}
+
+fn delimiter_row_regex() -> &'static Regex {
+    static DELIMITER: OnceLock<Regex> = OnceLock::new();
+    DELIMITER.get_or_init(|| {
+        Regex::new(r"^\s*\|?\s*:?-+:?\s*(\|\s*:?-+:?\s*)*\|?\s*$")
+            .expect("delimiter row pattern is valid")
+    })
+}
+
+fn list_item_regex() -> &'static Regex {
+    static LIST_ITEM: OnceLock<Regex> = OnceLock::new();
+    LIST_ITEM.get_or_init(|| {
+        Regex::new(r"^(\s*)([-*+]|\d{1,9}[.)])(\s+|$)").expect("list item pattern is valid")
+    })
+}
+
+fn missing_space_regex() -> &'static Regex {
+    static MISSING_SPACE: OnceLock<Regex> = OnceLock::new();
+    MISSING_SPACE.get_or_init(|| {
+        Regex::new(r"^\s*([-+]|\d{1,9}[.)])\p{L}").expect("missing space pattern is valid")
+    })
+}
+
+/// Cells in a table row. Pipes escaped with `\` or inside code spans do not split.
+fn table_cells(line: &str) -> usize {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = trimmed.strip_suffix('|').filter(|t| !t.ends_with('\\')).unwrap_or(trimmed);
+
+    let mut cells = 1;
+    let mut in_code = false;
+    let mut escaped = false;
+    for c in trimmed.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '`' => in_code = !in_code,
+            '|' if !in_code => cells += 1,
+            _ => {}
+        }
+    }
+    cells
+}
+
+/// Opening fence character and length, e.g. `("`", 4)` for "````rust".
+fn fence_open(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let c = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|x| *x == c).count();
+    (len >= 3).then_some((c, len))
+}
+
+fn closes_fence(line: &str, open: (char, usize)) -> bool {
+    fence_open(line).is_some_and(|(c, len)| {
+        c == open.0 && len >= open.1 && line.trim().chars().all(|x| x == c)
+    })
+}
+
+/// A fenced code block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// First word of the info string, lowercased; empty when there is none.
+    pub language: String,
+    /// 1-based line of the opening fence; the code starts on the next line.
+    pub fence_line: u64,
+    pub lines: Vec<String>,
+}
+
+/// Closed fenced code blocks in markdown `content`, in order.
+pub fn code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<((char, usize), CodeBlock)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        match open.take() {
+            Some((fence, block)) if closes_fence(line, fence) => blocks.push(block),
+            Some((fence, mut block)) => {
+                block.lines.push(line.to_string());
+                open = Some((fence, block));
+            }
+            None => {
+                if let Some(fence) = fence_open(line) {
+                    let info = line.trim_start().trim_start_matches(fence.0);
+                    let language = info
+                        .split(|c: char| c.is_whitespace() || c == '{' || c == ',')
+                        .next()
+                        .unwrap_or_default()
+                        .to_lowercase();
+                    open = Some((
+                        fence,
+                        CodeBlock {
+                            language,
+                            fence_line: index as u64 + 1,
+                            lines: Vec::new(),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+fn scan(lines: &[&str]) -> Vec<Found> {
+    let mut found = Vec::new();
+    let mut fence: Option<(char, usize, u64)> = None;
+    // Indentation and bullet of the list items seen since the last blank line
+    let mut bullets: Vec<(usize, String)> = Vec::new();
+    let mut list_start = 0u64;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let line_number = index as u64 + 1;
+
+        if let Some((c, len, _)) = fence {
+            if closes_fence(line, (c, len)) {
+                fence = None;
+            }
+            index += 1;
+            continue;
+        }
+        if let Some((c, len)) = fence_open(line) {
+            fence = Some((c, len, line_number));
+            index += 1;
+            continue;
+        }
+        if line.trim().is_empty() {
+            bullets.clear();
+            index += 1;
+            continue;
+        }
+
+        // A header row followed by a delimiter row starts a table
+        if line.contains('|')
+            && lines
+                .get(index + 1)
+                .is_some_and(|next| next.contains('|') && delimiter_row_regex().is_match(next))
+        {
+            let columns = table_cells(line);
+            let mut end = index + 2;
+            while end < lines.len() && lines[end].contains('|') && !lines[end].trim().is_empty() {
+                end += 1;
+            }
+            let block = line_number..=end as u64;
+            for (row, row_line) in lines.iter().enumerate().take(end).skip(index + 1) {
+                let cells = table_cells(row_line);
+                if cells != columns {
+                    found.push(Found {
+                        issue: StructureIssue {
+                            kind: StructureIssueKind::TableColumnMismatch,
+                            line: row as u64 + 1,
+                            detail: format!("row has {cells} column(s), header has {columns}"),
+                        },
+                        block: block.clone(),
+                    });
+                }
+            }
+            bullets.clear();
+            index = end;
+            continue;
+        }
+
+        // Only right after a real list item, so prose starting with `-v` is left alone
+        let after_list_item = index
+            .checked_sub(1)
+            .is_some_and(|previous| list_item_regex().is_match(lines[previous]));
+        if after_list_item && missing_space_regex().is_match(line) {
+            found.push(Found {
+                issue: StructureIssue {
+                    kind: StructureIssueKind::ListMarkerMissingSpace,
+                    line: line_number,
+                    detail: "list marker needs a space after it".to_string(),
+                },
+                block: line_number..=line_number,
+            });
+        }
+
+        if let Some(caps) = list_item_regex().captures(line) {
+            let indent = caps[1].len();
+            let marker = caps[2].to_string();
+            if bullets.is_empty() {
+                list_start = line_number;
+            }
+            let is_bullet = |m: &str| matches!(m, "-" | "*" | "+");
+            if is_bullet(&marker) {
+                if let Some((_, previous)) = bullets
+                    .iter()
+                    .rev()
+                    .find(|(i, m)| *i == indent && is_bullet(m))
+                {
+                    if *previous != marker {
+                        found.push(Found {
+                            issue: StructureIssue {
+                                kind: StructureIssueKind::ListMarkerMismatch,
+                                line: line_number,
+                                detail: format!(
+                                    "bullet `{marker}` follows `{previous}` in the same list"
+                                ),
+                            },
+                            block: list_start..=line_number,
+                        });
+                    }
+                }
+            }
+            bullets.push((indent, marker));
+        }
+
+        index += 1;
+    }
+
+    if let Some((c, len, opened_at)) = fence {
+        found.push(Found {
+            issue: StructureIssue {
+                kind: StructureIssueKind::UnclosedFence,
+                line: opened_at,
+                detail: format!("code fence `{}` is never closed", c.to_string().repeat(len)),
+            },
+            block: opened_at..=lines.len() as u64,
+        });
+    }
+
+    found
+}
+
+/// Structural problems in markdown `content`. With a `patch`, only problems in a table,
+/// list, or fence the patch touches are returned.
+pub fn validate(content: &str, patch: Option<&str>) -> Vec<StructureIssue> {
+    let lines: Vec<&str> = content.lines().collect();
+    let changed: Option<HashSet<u64>> =
+        patch.map(|patch| added_lines(patch).into_iter().map(|(line, _)| line).collect());
+
+    scan(&lines)
+        .into_iter()
+        .filter(|found| {
+            changed
+                .as_ref()
+                .is_none_or(|changed| found.block.clone().any(|line| changed.contains(&line)))
+        })
+        .map(|found| found.issue)
+        .collect()
+}
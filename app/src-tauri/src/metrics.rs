@@ -0,0 +1,212 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::error::AppResult;
+use crate::review_storage::{ReviewStorage, TurnaroundRecord, UsageRecord};
+
+/// Usage events older than this are dropped at startup.
+const USAGE_RETENTION_DAYS: i64 = 365;
+
+/// Follows the `usage_metrics` preference so recording needs no settings read.
+static USAGE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// API latencies waiting to be written, as `(occurred_at, duration_ms)`. Requests only
+/// touch this queue, so they never wait on the database lock.
+static PENDING_LATENCIES: Mutex<Vec<(String, u64)>> = Mutex::new(Vec::new());
+
+/// Queued latencies that start a background flush.
+const LATENCY_FLUSH_THRESHOLD: usize = 50;
+
+/// Rolling windows for turnaround stats; `quarter` is the last 90 days.
+pub const TURNAROUND_RANGES: &[&str] = &["week", "month", "quarter", "year", "all"];
+
+/// Earliest submission time included in `range`, or `None` for everything.
+pub fn range_start(range: &str, now: DateTime<Utc>) -> AppResult<Option<DateTime<Utc>>> {
+    crate::settings::ensure_one_of("range", range, TURNAROUND_RANGES)?;
+    let days = match range {
+        "week" => 7,
+        "month" => 30,
+        "quarter" => 90,
+        "year" => 365,
+        _ => return Ok(None),
+    };
+    Ok(Some(now - Duration::days(days)))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnaroundStats {
+    pub range: String,
+    /// Reviews submitted in the range.
+    pub reviews: usize,
+    /// Reviews with a known request time, which the figures below are based on.
+    pub measured: usize,
+    pub average_hours: Option<f64>,
+    pub median_hours: Option<f64>,
+    pub p90_hours: Option<f64>,
+    pub slowest: Option<TurnaroundRecord>,
+}
+
+/// Hours from request to submission; `None` without a request time.
+pub fn turnaround_hours(record: &TurnaroundRecord) -> Option<f64> {
+    let requested = DateTime::parse_from_rfc3339(record.requested_at.as_deref()?).ok()?;
+    let submitted = DateTime::parse_from_rfc3339(&record.submitted_at).ok()?;
+    Some((submitted - requested).num_seconds().max(0) as f64 / 3600.0)
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[f64], pct: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+pub fn compute_stats(range: &str, records: &[TurnaroundRecord]) -> TurnaroundStats {
+    let mut measured: Vec<(f64, &TurnaroundRecord)> = records
+        .iter()
+        .filter_map(|record| turnaround_hours(record).map(|hours| (hours, record)))
+        .collect();
+    measured.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let hours: Vec<f64> = measured.iter().map(|(hours, _)| *hours).collect();
+
+    let median_hours = match hours.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(hours[n / 2]),
+        n => Some((hours[n / 2 - 1] + hours[n / 2]) / 2.0),
+    };
+
+    TurnaroundStats {
+        range: range.to_string(),
+        reviews: records.len(),
+        measured: hours.len(),
+        average_hours: (!hours.is_empty()).then(|| hours.iter().sum::<f64>() / hours.len() as f64),
+        median_hours,
+        p90_hours: percentile(&hours, 90.0),
+        slowest: measured.last().map(|(_, record)| (*record).clone()),
+    }
+}
+
+/// What the opt-in usage metrics count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageKind {
+    ReviewStarted,
+    CommentAdded,
+    ReviewSubmitted,
+    SubmissionFailed,
+    ApiRequest,
+}
+
+impl UsageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageKind::ReviewStarted => "review_started",
+            UsageKind::CommentAdded => "comment_added",
+            UsageKind::ReviewSubmitted => "review_submitted",
+            UsageKind::SubmissionFailed => "submission_failed",
+            UsageKind::ApiRequest => "api_request",
+        }
+    }
+}
+
+pub fn set_usage_enabled(enabled: bool) {
+    USAGE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn usage_enabled() -> bool {
+    USAGE_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Count `kind` when usage metrics are on. Failures are logged and otherwise ignored.
+pub fn record_usage(kind: UsageKind) {
+    if !usage_enabled() {
+        return;
+    }
+    let result = crate::review_storage::get_storage().and_then(|storage| {
+        storage.record_usage_event(kind.as_str(), &Utc::now().to_rfc3339(), None)
+    });
+    if let Err(e) = result {
+        tracing::debug!("Failed to record usage event {}: {}", kind.as_str(), e);
+    }
+}
+
+/// Count a GitHub API request that took `elapsed`, when usage metrics are on. The event is
+/// queued and written with others by [`flush_api_latencies`] off the request path.
+pub fn record_api_latency(elapsed: std::time::Duration) {
+    if !usage_enabled() {
+        return;
+    }
+    let queued = match PENDING_LATENCIES.lock() {
+        Ok(mut pending) => {
+            pending.push((Utc::now().to_rfc3339(), elapsed.as_millis() as u64));
+            pending.len()
+        }
+        Err(_) => return,
+    };
+    if queued == LATENCY_FLUSH_THRESHOLD {
+        tauri::async_runtime::spawn_blocking(flush_api_latencies);
+    }
+}
+
+/// Write the queued API latencies in one transaction. Runs when the queue fills, before
+/// usage figures are read, and on exit; failures are logged and the batch dropped.
+pub fn flush_api_latencies() {
+    let batch = match PENDING_LATENCIES.lock() {
+        Ok(mut pending) => std::mem::take(&mut *pending),
+        Err(_) => return,
+    };
+    if batch.is_empty() {
+        return;
+    }
+    let result = crate::review_storage::get_storage()
+        .and_then(|storage| storage.record_usage_events(UsageKind::ApiRequest.as_str(), &batch));
+    if let Err(e) = result {
+        tracing::debug!("Failed to record {} API latencies: {}", batch.len(), e);
+    }
+}
+
+/// Drop usage events past the retention period. Returns how many were removed.
+pub fn purge_old_usage(storage: &ReviewStorage, now: DateTime<Utc>) -> AppResult<usize> {
+    storage.purge_usage_events(&(now - Duration::days(USAGE_RETENTION_DAYS)).to_rfc3339())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageMetrics {
+    pub range: String,
+    /// Whether usage is being counted now; the figures cover whatever was counted before.
+    pub enabled: bool,
+    pub reviews_started: usize,
+    pub comments_added: usize,
+    pub reviews_submitted: usize,
+    pub submission_failures: usize,
+    pub api_requests: usize,
+    pub api_latency_p50_ms: Option<f64>,
+    pub api_latency_p90_ms: Option<f64>,
+    pub api_latency_p99_ms: Option<f64>,
+}
+
+pub fn compute_usage(range: &str, enabled: bool, records: &[UsageRecord]) -> UsageMetrics {
+    let count = |kind: UsageKind| records.iter().filter(|r| r.kind == kind.as_str()).count();
+    let mut latencies: Vec<f64> = records
+        .iter()
+        .filter(|record| record.kind == UsageKind::ApiRequest.as_str())
+        .filter_map(|record| record.duration_ms.map(|ms| ms as f64))
+        .collect();
+    latencies.sort_by(f64::total_cmp);
+
+    UsageMetrics {
+        range: range.to_string(),
+        enabled,
+        reviews_started: count(UsageKind::ReviewStarted),
+        comments_added: count(UsageKind::CommentAdded),
+        reviews_submitted: count(UsageKind::ReviewSubmitted),
+        submission_failures: count(UsageKind::SubmissionFailed),
+        api_requests: count(UsageKind::ApiRequest),
+        api_latency_p50_ms: percentile(&latencies, 50.0),
+        api_latency_p90_ms: percentile(&latencies, 90.0),
+        api_latency_p99_ms: percentile(&latencies, 99.0),
+    }
+}
@@ -0,0 +1,207 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+/// Version of the on-disk app-data layout written by this build.
+pub const CURRENT_DATA_VERSION: u32 = 2;
+
+const VERSION_FILE: &str = "data_version.json";
+const BACKUP_DIR: &str = "backups";
+const DB_FILE: &str = "reviews.db";
+const LOG_DIR: &str = "review_logs";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VersionMarker {
+    version: u32,
+    updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedMigration {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub description: String,
+    pub actions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub previous_version: u32,
+    pub current_version: u32,
+    pub backup_dir: Option<String>,
+    pub applied: Vec<AppliedMigration>,
+    pub error: Option<String>,
+    pub ran_at: String,
+}
+
+struct Migration {
+    from_version: u32,
+    description: &'static str,
+    apply: fn(&Path) -> AppResult<Vec<String>>,
+}
+
+/// Ordered list of layout upgrades. Each entry moves the data dir from `from_version`
+/// to `from_version + 1`; add new entries at the end and bump `CURRENT_DATA_VERSION`.
+/// Version 2 only introduced the version marker, so upgrading from 1 needs no entry.
+const MIGRATIONS: &[Migration] = &[];
+
+static LAST_REPORT: OnceLock<MigrationReport> = OnceLock::new();
+
+pub fn last_report() -> Option<&'static MigrationReport> {
+    LAST_REPORT.get()
+}
+
+/// Run pending migrations and remember the report for `cmd_get_migration_report`.
+pub fn run_startup_migrations(data_dir: &Path) -> AppResult<MigrationReport> {
+    let report = run_migrations(data_dir)?;
+    let _ = LAST_REPORT.set(report.clone());
+    Ok(report)
+}
+
+/// Bring `data_dir` up to `CURRENT_DATA_VERSION`.
+///
+/// The database and log folder are copied into `backups/` before any migration runs.
+/// If a migration fails, the database is restored from that backup and the version
+/// marker is left at the last successful step.
+pub fn run_migrations(data_dir: &Path) -> AppResult<MigrationReport> {
+    std::fs::create_dir_all(data_dir)?;
+
+    let previous_version = detect_version(data_dir)?;
+    let mut report = MigrationReport {
+        previous_version,
+        current_version: previous_version,
+        backup_dir: None,
+        applied: Vec::new(),
+        error: None,
+        ran_at: Utc::now().to_rfc3339(),
+    };
+
+    if previous_version > CURRENT_DATA_VERSION {
+        tracing::warn!(
+            "App data version {} is newer than this build supports ({})",
+            previous_version,
+            CURRENT_DATA_VERSION
+        );
+        return Ok(report);
+    }
+
+    if previous_version == CURRENT_DATA_VERSION {
+        return Ok(report);
+    }
+
+    let backup_dir = create_backup(data_dir, previous_version)?;
+    report.backup_dir = Some(backup_dir.display().to_string());
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|m| m.from_version >= previous_version && m.from_version < CURRENT_DATA_VERSION)
+    {
+        tracing::info!(
+            "Migrating app data from v{} to v{}: {}",
+            migration.from_version,
+            migration.from_version + 1,
+            migration.description
+        );
+
+        match (migration.apply)(data_dir) {
+            Ok(actions) => {
+                report.current_version = migration.from_version + 1;
+                write_version(data_dir, report.current_version)?;
+                report.applied.push(AppliedMigration {
+                    from_version: migration.from_version,
+                    to_version: migration.from_version + 1,
+                    description: migration.description.to_string(),
+                    actions,
+                });
+            }
+            Err(err) => {
+                tracing::error!("Migration from v{} failed: {}", migration.from_version, err);
+                restore_database(&backup_dir, data_dir)?;
+                report.error = Some(err.to_string());
+                return Ok(report);
+            }
+        }
+    }
+
+    // Versions without a registered migration only need the marker bumped.
+    if report.current_version < CURRENT_DATA_VERSION {
+        report.current_version = CURRENT_DATA_VERSION;
+        write_version(data_dir, CURRENT_DATA_VERSION)?;
+    }
+
+    Ok(report)
+}
+
+fn detect_version(data_dir: &Path) -> AppResult<u32> {
+    let marker_path = data_dir.join(VERSION_FILE);
+    if marker_path.exists() {
+        let raw = std::fs::read_to_string(&marker_path)?;
+        let marker: VersionMarker = serde_json::from_str(&raw)?;
+        return Ok(marker.version);
+    }
+
+    // Data written before the marker existed is version 1; an empty dir is a fresh install.
+    if data_dir.join(DB_FILE).exists() || data_dir.join(LOG_DIR).exists() {
+        Ok(1)
+    } else {
+        write_version(data_dir, CURRENT_DATA_VERSION)?;
+        Ok(CURRENT_DATA_VERSION)
+    }
+}
+
+fn write_version(data_dir: &Path, version: u32) -> AppResult<()> {
+    let marker = VersionMarker {
+        version,
+        updated_at: Utc::now().to_rfc3339(),
+    };
+    std::fs::write(data_dir.join(VERSION_FILE), serde_json::to_string_pretty(&marker)?)?;
+    Ok(())
+}
+
+fn create_backup(data_dir: &Path, version: u32) -> AppResult<PathBuf> {
+    let stamp = Utc::now().format("%Y%m%d-%H%M%S");
+    let backup_dir = data_dir.join(BACKUP_DIR).join(format!("v{}-{}", version, stamp));
+    std::fs::create_dir_all(&backup_dir)?;
+
+    let db_path = data_dir.join(DB_FILE);
+    if db_path.exists() {
+        std::fs::copy(&db_path, backup_dir.join(DB_FILE))?;
+    }
+
+    let log_dir = data_dir.join(LOG_DIR);
+    if log_dir.is_dir() {
+        copy_dir(&log_dir, &backup_dir.join(LOG_DIR))?;
+    }
+
+    tracing::info!("Backed up app data to {:?}", backup_dir);
+    Ok(backup_dir)
+}
+
+fn restore_database(backup_dir: &Path, data_dir: &Path) -> AppResult<()> {
+    let backup_db = backup_dir.join(DB_FILE);
+    if backup_db.exists() {
+        std::fs::copy(&backup_db, data_dir.join(DB_FILE))?;
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> AppResult<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_file() {
+            std::fs::copy(entry.path(), &target).map_err(|e| {
+                AppError::Internal(format!("Failed to back up {:?}: {}", entry.path(), e))
+            })?;
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use serde::Serialize;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::i18n::{tf, Msg};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct PrUnderReview {
@@ -9,8 +14,89 @@ pub struct PrUnderReview {
     pub has_local_review: bool,
     pub has_pending_review: bool,
     pub viewed_count: usize,
+    /// Files with every hunk marked reviewed. The view counts them as viewed along with
+    /// the files it marked viewed itself, once each.
+    pub hunk_reviewed_files: Vec<String>,
     pub total_count: usize,
     pub local_folder: Option<String>,
+    /// Whether the PR is waiting on me; `None` for local folders or when GitHub could
+    /// not be asked.
+    pub needs_my_attention: Option<bool>,
+    /// Deadline for submitting the local review, RFC 3339 UTC.
+    pub due_at: Option<String>,
+    pub overdue: bool,
+    /// Unsubmitted comments in the local review.
+    pub pending_comment_count: usize,
+    /// Current head, `open`/`closed`, merged and locked, as GitHub reports them now;
+    /// `None` for local folders or when GitHub could not be asked.
+    pub head_sha: Option<String>,
+    pub state: Option<String>,
+    pub merged: Option<bool>,
+    pub locked: Option<bool>,
+}
+
+/// What GitHub says about a pull request under review right now, from one batched query
+/// for the whole sidebar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrReviewState {
+    pub head_sha: String,
+    /// `open` or `closed`, as REST reports it.
+    pub state: String,
+    pub merged: bool,
+    pub locked: bool,
+    /// My pending review on GitHub still exists.
+    pub has_pending_review: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RepositorySummary {
+    pub owner: String,
+    pub name: String,
+    pub full_name: String,
+    pub description: Option<String>,
+    pub private: bool,
+    pub archived: bool,
+    pub fork: bool,
+    pub default_branch: String,
+    pub updated_at: String,
+    pub pushed_at: Option<String>,
+    pub can_push: bool,
+    /// How the user is connected to the repo: "owner", "collaborator" or
+    /// "organization_member"; `None` for repos that are only starred.
+    pub affiliation: Option<String>,
+    /// `updated_at` of the most recently updated pull request, when looked up.
+    pub last_pr_activity: Option<String>,
+}
+
+impl RepositorySummary {
+    /// Case-insensitive match against `owner/name` and the description.
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty()
+            || self.full_name.to_lowercase().contains(&filter)
+            || self
+                .description
+                .as_deref()
+                .is_some_and(|d| d.to_lowercase().contains(&filter))
+    }
+}
+
+/// Most recent pull request activity first, then most recent push; repos without
+/// known activity sort last.
+pub fn sort_by_recent_activity(repos: &mut [RepositorySummary]) {
+    repos.sort_by(|a, b| {
+        b.last_pr_activity
+            .cmp(&a.last_pr_activity)
+            .then_with(|| b.pushed_at.cmp(&a.pushed_at))
+            .then_with(|| a.full_name.cmp(&b.full_name))
+    });
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct OrganizationSummary {
+    pub login: String,
+    pub description: Option<String>,
+    pub avatar_url: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -21,6 +107,17 @@ pub struct AuthStatus {
     pub is_offline: bool, // true if authenticated using cached data without network verification
 }
 
+/// A named GitHub account, e.g. `work` or `personal`, with its own stored token.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AccountProfile {
+    pub name: String,
+    /// GitHub login it last signed in as.
+    pub login: Option<String>,
+    pub signed_in: bool,
+    /// Requests use this account's token.
+    pub active: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PullRequestSummary {
     pub number: u64,
@@ -33,6 +130,23 @@ pub struct PullRequestSummary {
     pub state: String,
     pub merged: bool,
     pub locked: bool,
+    /// Size class and review time estimate; `None` when GitHub did not return line counts.
+    pub size: Option<crate::pr_size::PrSize>,
+    /// Updated since I last looked at it; `false` for PRs I have never opened.
+    pub new_since_last_visit: bool,
+    /// My review was re-requested, commits landed after my review, or the author replied
+    /// to my threads. Only computed for open PRs when the current login is known.
+    pub needs_my_attention: bool,
+    /// Unsubmitted comments in my local review of this PR.
+    pub pending_comment_count: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ReviewFollowupResult {
+    pub reviewer: String,
+    pub head_sha: String,
+    /// URL of the nudge comment, when one was posted.
+    pub comment_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -40,9 +154,15 @@ pub struct PullRequestMetadata {
     pub state: String,
     pub merged: bool,
     pub locked: bool,
+    pub head_sha: String,
+    /// Owner of the fork the changes come from; `None` when they are on a branch of this
+    /// repository, or the fork has since been deleted.
+    pub head_owner: Option<String>,
+    /// Name of that fork, set along with `head_owner`.
+    pub head_repo: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PullRequestDetail {
     pub number: u64,
     pub title: String,
@@ -50,15 +170,199 @@ pub struct PullRequestDetail {
     pub author: String,
     pub head_sha: String,
     pub base_sha: String,
+    /// Owner of the fork the changes come from; `None` when they are on a branch of this
+    /// repository, or the fork has since been deleted.
+    #[serde(default)]
+    pub head_owner: Option<String>,
+    /// Name of that fork, set along with `head_owner`.
+    #[serde(default)]
+    pub head_repo: Option<String>,
     pub files: Vec<PullRequestFile>,
     pub comments: Vec<PullRequestComment>,
+    /// Line comments from `comments` grouped into conversations, oldest first.
+    pub threads: Vec<CommentThread>,
     pub my_comments: Vec<PullRequestComment>,
     pub reviews: Vec<PullRequestReview>,
+    pub secret_findings: Vec<SecretFinding>,
+    pub reactions: ReactionSummary,
+    pub participants: Vec<Participant>,
+    /// `None` for local folders, or when GitHub could not be asked.
+    pub author_context: Option<AuthorContext>,
+    /// Issues this PR closes: GitHub's linked issues plus any named with closing keywords
+    /// in the description.
+    pub linked_issues: Vec<LinkedIssue>,
+    pub merge_status: Option<MergeStatus>,
+    /// Comments pinned on this PR, from local storage.
+    pub bookmarks: Vec<crate::review_storage::Bookmark>,
+    /// When I last marked this PR as seen; `None` on a first visit.
+    pub last_visited_at: Option<String>,
+    /// Comments by others posted since `last_visited_at`.
+    pub new_comment_count: usize,
+    /// Files of a local folder that could not be loaded. Always empty for pull requests.
+    pub skipped_files: Vec<SkippedFile>,
+    /// Served from the offline cache because GitHub could not be reached.
+    #[serde(default)]
+    pub stale: bool,
+    /// When the cached copy was fetched; `None` for fresh results.
+    #[serde(default)]
+    pub cached_at: Option<String>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// A local folder file left out of the review, and why.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SkippedFile {
+    /// Path relative to the folder, with any bytes that are not UTF-8 replaced.
+    pub path: String,
+    pub reason: String,
+}
+
+/// Mergeability of a PR into its base branch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MergeStatus {
+    /// `mergeable`, `conflicting`, or `unknown` while GitHub is still computing it.
+    pub mergeable: String,
+    /// GitHub's merge state, e.g. `clean`, `dirty`, `behind`, `blocked`.
+    pub merge_state: String,
+    /// Files of a conflicting PR that also changed on the base branch; the conflicts are
+    /// almost always among these.
+    pub conflicting_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LinkedIssue {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    /// `open` or `closed`.
+    pub state: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct IssueDetail {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: String,
+    pub author: String,
+    pub labels: Vec<String>,
+    pub url: String,
+    pub created_at: String,
+    pub closed_at: Option<String>,
+    pub comments: u64,
+}
+
+/// How familiar the PR author is with the repository, so feedback can be pitched right.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuthorContext {
+    /// GitHub's author association, e.g. `MEMBER`, `CONTRIBUTOR`, `FIRST_TIME_CONTRIBUTOR`.
+    pub association: String,
+    pub first_contribution: bool,
+    /// PRs the author opened in this repository within the last `recent_window_days`.
+    pub recent_pr_count: u32,
+    pub recent_window_days: u32,
+    /// Merged PRs by the author in this repository, ever.
+    pub merged_pr_count: u32,
+}
+
+/// Whether this looks like the author's first contribution: GitHub says so, or an author
+/// without repository access has nothing merged yet.
+pub fn is_first_contribution(association: &str, merged_pr_count: u32) -> bool {
+    match association {
+        "FIRST_TIMER" | "FIRST_TIME_CONTRIBUTOR" => true,
+        "OWNER" | "MEMBER" | "COLLABORATOR" => false,
+        _ => merged_pr_count == 0,
+    }
+}
+
+/// Reaction totals on the PR description.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ReactionSummary {
+    pub thumbs_up: u32,
+    pub thumbs_down: u32,
+    pub hooray: u32,
+}
+
+/// Reaction counts on a review or conversation comment. Deserializes from the rollup
+/// REST returns with each comment, where the thumbs are named `+1` and `-1`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CommentReactions {
+    #[serde(alias = "+1")]
+    pub thumbs_up: u32,
+    #[serde(alias = "-1")]
+    pub thumbs_down: u32,
+    pub laugh: u32,
+    pub hooray: u32,
+    pub confused: u32,
+    pub heart: u32,
+    pub rocket: u32,
+    pub eyes: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Participant {
+    pub login: String,
+    /// Inline and conversation comments, plus non-empty review bodies.
+    pub comments: u32,
+    pub reviews: u32,
+}
+
+/// Combine GitHub's participant list with per-author comment and review counts, most
+/// active first. Authors missing from `participants` are still included.
+pub fn tally_participants(
+    participants: &[String],
+    comment_authors: &[&str],
+    review_authors: &[&str],
+) -> Vec<Participant> {
+    fn entry<'a>(tally: &'a mut BTreeMap<String, Participant>, login: &str) -> &'a mut Participant {
+        tally
+            .entry(login.to_ascii_lowercase())
+            .or_insert_with(|| Participant {
+                login: login.to_string(),
+                comments: 0,
+                reviews: 0,
+            })
+    }
+
+    let mut tally = BTreeMap::new();
+    for login in participants {
+        entry(&mut tally, login);
+    }
+    for login in comment_authors {
+        entry(&mut tally, login).comments += 1;
+    }
+    for login in review_authors {
+        entry(&mut tally, login).reviews += 1;
+    }
+
+    let mut tally: Vec<Participant> = tally.into_values().collect();
+    tally.sort_by(|a, b| {
+        (b.comments + b.reviews)
+            .cmp(&(a.comments + a.reviews))
+            .then_with(|| a.login.cmp(&b.login))
+    });
+    tally
+}
+
+/// A likely credential found in a PR file. `excerpt` is redacted so the secret itself
+/// is never echoed back to the UI or logs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SecretFinding {
+    pub path: String,
+    pub line: Option<u64>,
+    pub rule_id: String,
+    pub description: String,
+    pub excerpt: String,
+    pub severity: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PullRequestFile {
     pub path: String,
+    /// GitHub's file status, e.g. `added`, `modified`, `renamed` or `removed`. A removed
+    /// file has no head version.
     pub status: String,
     pub additions: u32,
     pub deletions: u32,
@@ -67,16 +371,21 @@ pub struct PullRequestFile {
     pub base_content: Option<String>,
     pub language: FileLanguage,
     pub previous_filename: Option<String>,
+    /// For a file in a locale folder: its source when it is a translation, or its first
+    /// translation when it is the source, if both are part of the change.
+    pub localized_pair: Option<String>,
 }
 
 pub type FileLanguage = String;
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PullRequestComment {
     pub id: u64,
     pub body: String,
     pub author: String,
     pub created_at: String,
+    /// `created_at` in the user's locale and time zone.
+    pub display_time: String,
     pub url: String,
     pub path: Option<String>,
     pub line: Option<u64>,
@@ -88,14 +397,116 @@ pub struct PullRequestComment {
     pub review_id: Option<u64>,
     pub in_reply_to_id: Option<u64>,
     pub outdated: Option<bool>,
+    /// Posted by someone else since my last visit to the PR.
+    pub new_since_last_visit: bool,
+    /// Always empty for review bodies, which the API has no reactions for.
+    pub reactions: CommentReactions,
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// Replacement text a reviewer proposes for lines `start_line..=end_line` of the head
+/// version of a file, posted as a `suggestion` block the author can apply on GitHub.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Empty to suggest deleting the lines.
+    pub replacement: String,
+    pub start_line: u64,
+    pub end_line: u64,
+}
+
+impl Suggestion {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.start_line == 0 || self.start_line > self.end_line {
+            return Err(AppError::InvalidInput(format!(
+                "suggestion lines {}-{} are not a valid range",
+                self.start_line, self.end_line
+            )));
+        }
+        Ok(())
+    }
+
+    /// Suggestions replace lines of the head version, so a comment on the base side
+    /// cannot carry one.
+    pub fn validate_for_side(&self, side: &str) -> AppResult<()> {
+        self.validate()?;
+        if side == "LEFT" {
+            return Err(AppError::InvalidInput(
+                "suggestions can only be made on the new version of a file".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Refuse a line comment on the new side of a file that `files`, the PR's own list,
+/// shows as deleted: it only has its base version. File-level comments, left-side
+/// comments and paths not in `files` pass.
+pub fn ensure_side_exists(
+    files: &[PullRequestFile],
+    file_path: &str,
+    side: &str,
+    line_number: u64,
+) -> AppResult<()> {
+    let removed = files
+        .iter()
+        .any(|file| file.path == file_path && file.status == "removed");
+    if removed && side == "RIGHT" && line_number > 0 {
+        return Err(AppError::InvalidInput(tf(Msg::RemovedFileRightSide, &[&file_path])));
+    }
+    Ok(())
+}
+
+/// How many hunks of a file have been marked reviewed, for files too long to track with
+/// the whole-file viewed flag alone.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct FileReviewProgress {
+    pub file_path: String,
+    pub reviewed_hunks: u32,
+    pub hunk_count: u32,
+    /// Rounded down, so 100 means every hunk is reviewed.
+    pub percent: u8,
+}
+
+impl FileReviewProgress {
+    pub fn new(file_path: String, reviewed_hunks: u32, hunk_count: u32) -> Self {
+        let reviewed_hunks = reviewed_hunks.min(hunk_count);
+        let percent = if hunk_count == 0 {
+            0
+        } else {
+            (u64::from(reviewed_hunks) * 100 / u64::from(hunk_count)) as u8
+        };
+        Self {
+            file_path,
+            reviewed_hunks,
+            hunk_count,
+            percent,
+        }
+    }
+}
+
+/// A line comment and the replies to it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommentThread {
+    /// Id of the root comment.
+    pub id: u64,
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub side: Option<String>,
+    pub root: PullRequestComment,
+    /// Oldest first.
+    pub replies: Vec<PullRequestComment>,
+    /// `None` when GitHub could not be asked.
+    pub resolved: Option<bool>,
+    pub outdated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PullRequestReview {
     pub id: u64,
     pub state: String,
     pub author: String,
     pub submitted_at: Option<String>,
+    /// `submitted_at` in the user's locale and time zone.
+    pub display_time: Option<String>,
     pub body: Option<String>,
     pub html_url: Option<String>,
     pub commit_id: Option<String>,
@@ -0,0 +1,105 @@
+use std::sync::Mutex;
+
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::events;
+
+/// Emitted with a `ScopeUpgrade` payload when GitHub refuses a request for lack of a scope.
+pub const SCOPE_UPGRADE_EVENT: &str = "scope-upgrade-required";
+
+/// Scopes that include narrower ones, as documented for GitHub OAuth apps.
+const IMPLIED_SCOPES: &[(&str, &[&str])] = &[
+    ("repo", &["repo:status", "repo_deployment", "public_repo", "repo:invite", "security_events"]),
+    ("admin:org", &["write:org", "read:org", "manage_runners:org"]),
+    ("write:org", &["read:org"]),
+    ("admin:public_key", &["write:public_key", "read:public_key"]),
+    ("write:public_key", &["read:public_key"]),
+    ("admin:repo_hook", &["write:repo_hook", "read:repo_hook"]),
+    ("write:repo_hook", &["read:repo_hook"]),
+    ("user", &["read:user", "user:email", "user:follow"]),
+    ("write:packages", &["read:packages"]),
+    ("delete:packages", &["read:packages"]),
+    ("write:discussion", &["read:discussion"]),
+];
+
+static PENDING: Mutex<Option<ScopeUpgrade>> = Mutex::new(None);
+
+/// A request GitHub refused until the token is granted `scope`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScopeUpgrade {
+    /// What the app was doing, e.g. `delete pending review`.
+    pub context: String,
+    pub scope: String,
+}
+
+fn scope_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|scope| !scope.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `granted` includes `needed`, directly or through a broader scope.
+pub fn covers(granted: &[String], needed: &str) -> bool {
+    granted.iter().any(|scope| {
+        scope == needed
+            || IMPLIED_SCOPES
+                .iter()
+                .any(|(broad, narrower)| scope == broad && narrower.contains(&needed))
+    })
+}
+
+/// The scope to ask for when a 403 names accepted scopes the token has none of. GitHub
+/// accepts any one of them; the first is the one it lists as preferred.
+pub fn missing_scope(headers: &HeaderMap) -> Option<String> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let accepted = scope_list(header("x-accepted-oauth-scopes")?);
+    let granted = scope_list(header("x-oauth-scopes").unwrap_or_default());
+    if accepted.iter().any(|scope| covers(&granted, scope)) {
+        return None;
+    }
+    accepted.into_iter().next()
+}
+
+/// The scope named by a GraphQL `INSUFFICIENT_SCOPES` error message, e.g. "... requires
+/// one of the following scopes: ['read:org']".
+pub fn graphql_missing_scope(message: &str) -> Option<String> {
+    let (_, list) = message.split_once("scopes: [")?;
+    let (list, _) = list.split_once(']')?;
+    list.split(',')
+        .map(|scope| scope.trim().trim_matches(|c| c == '\'' || c == '"'))
+        .find(|scope| !scope.is_empty())
+        .map(str::to_string)
+}
+
+/// Remember a refused request so the UI can offer to grant `scope` and retry it.
+pub fn record(context: &str, scope: &str) {
+    let upgrade = ScopeUpgrade {
+        context: context.to_string(),
+        scope: scope.to_string(),
+    };
+    warn!(context, scope, "GitHub token is missing a required scope");
+    events::emit(SCOPE_UPGRADE_EVENT, &upgrade);
+    if let Ok(mut pending) = PENDING.lock() {
+        *pending = Some(upgrade);
+    }
+}
+
+/// The last refused request, cleared once read.
+pub fn take_pending() -> Option<ScopeUpgrade> {
+    PENDING.lock().ok().and_then(|mut pending| pending.take())
+}
+
+/// `base` with `extra` added, without repeating a scope already covered.
+pub fn with_scope(base: &str, extra: &str) -> String {
+    let granted = scope_list(&base.replace(' ', ","));
+    if covers(&granted, extra) {
+        base.to_string()
+    } else {
+        format!("{base} {extra}")
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tf, Msg};
+use crate::models::PullRequestFile;
+use crate::review_storage::ReviewComment;
+
+/// What the reviewer can do with a comment whose file left the PR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanAction {
+    /// Re-anchor on another file of the PR, see `cmd_local_update_comment_file_path`.
+    Move,
+    /// Post it in the PR conversation instead, see `cmd_convert_to_general_comment`.
+    Convert,
+    /// Delete the draft.
+    Drop,
+}
+
+/// A draft comment on a file that is no longer part of the PR, e.g. because the author
+/// reverted it. GitHub would reject it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrphanedComment {
+    pub comment_id: i64,
+    pub file_path: String,
+    pub line_number: u64,
+    /// The PR file this path was renamed to, when there is one.
+    pub suggested_path: Option<String>,
+    pub actions: Vec<OrphanAction>,
+    /// Draft replies in this comment's thread, which go wherever the comment goes.
+    pub reply_ids: Vec<i64>,
+}
+
+/// Line comments starting a thread on paths missing from the PR's file list, in the
+/// order given. Replies are not checked on their own: they belong to their thread, so
+/// they are listed with its first comment, and a reply to a comment already on GitHub
+/// is posted in that thread wherever its file went.
+pub fn find_orphans(comments: &[ReviewComment], files: &[PullRequestFile]) -> Vec<OrphanedComment> {
+    let in_pr: HashSet<&str> = files.iter().map(|file| file.path.as_str()).collect();
+    comments
+        .iter()
+        .filter(|comment| comment.in_reply_to_id.is_none())
+        .filter(|comment| !comment.general && !in_pr.contains(comment.file_path.as_str()))
+        .map(|comment| OrphanedComment {
+            comment_id: comment.id,
+            reply_ids: thread_replies(comment.id, comments),
+            file_path: comment.file_path.clone(),
+            line_number: comment.line_number,
+            suggested_path: files
+                .iter()
+                .find(|file| file.previous_filename.as_deref() == Some(comment.file_path.as_str()))
+                .map(|file| file.path.clone()),
+            actions: vec![OrphanAction::Move, OrphanAction::Convert, OrphanAction::Drop],
+        })
+        .collect()
+}
+
+/// Ids of every draft replying to `root`, directly or to another reply, in id order.
+fn thread_replies(root: i64, comments: &[ReviewComment]) -> Vec<i64> {
+    let mut replies = Vec::new();
+    let mut parents = vec![root];
+    while let Some(parent) = parents.pop() {
+        for reply in comments.iter().filter(|c| c.in_reply_to_id == Some(parent)) {
+            if reply.id != root && !replies.contains(&reply.id) {
+                replies.push(reply.id);
+                parents.push(reply.id);
+            }
+        }
+    }
+    replies.sort_unstable();
+    replies
+}
+
+/// Why a submission stopped before posting anything.
+pub fn describe(orphans: &[OrphanedComment]) -> String {
+    let mut paths: Vec<&str> = orphans.iter().map(|orphan| orphan.file_path.as_str()).collect();
+    paths.sort_unstable();
+    paths.dedup();
+    tf(Msg::OrphanedComments, &[&orphans.len(), &paths.join(", ")])
+}
@@ -0,0 +1,153 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AppError, AppResult};
+
+/// File under the app data dir holding the policy. It is only ever read by the app, so
+/// a script in the webview cannot loosen it; an administrator places it there.
+pub const OWNER_POLICY_FILE: &str = "owner_policy.json";
+
+const GITHUB_API_HOST: &str = "api.github.com";
+
+/// Which repository owners (users or organizations) the app may talk to, for machines
+/// where only company orgs should be reachable. Matching ignores case.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OwnerPolicy {
+    /// When non-empty, only these owners are allowed.
+    pub allowed_owners: Vec<String>,
+    /// Never allowed, even when also on the allow-list.
+    pub blocked_owners: Vec<String>,
+}
+
+fn owner_name() -> &'static Regex {
+    static OWNER_NAME: OnceLock<Regex> = OnceLock::new();
+    OWNER_NAME.get_or_init(|| {
+        Regex::new(r"^[A-Za-z0-9](?:[A-Za-z0-9-]{0,38})$").expect("owner name pattern is valid")
+    })
+}
+
+impl OwnerPolicy {
+    pub fn validate(&self) -> AppResult<()> {
+        for owner in self.allowed_owners.iter().chain(&self.blocked_owners) {
+            if !owner_name().is_match(owner) {
+                return Err(AppError::InvalidInput(format!(
+                    "`{owner}` is not a valid GitHub user or organization name"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_allowed(&self, owner: &str) -> bool {
+        let listed = |owners: &[String]| owners.iter().any(|o| o.eq_ignore_ascii_case(owner));
+        !listed(&self.blocked_owners)
+            && (self.allowed_owners.is_empty() || listed(&self.allowed_owners))
+    }
+
+    pub fn ensure_allowed(&self, owner: &str) -> AppResult<()> {
+        if self.is_allowed(owner) {
+            Ok(())
+        } else {
+            Err(AppError::OwnerNotAllowed(owner.to_string()))
+        }
+    }
+
+    /// Refuse a GitHub API request that reaches an owner this policy rules out.
+    pub fn ensure_request_allowed(&self, request: &reqwest::Request) -> AppResult<()> {
+        request_owners(request)
+            .iter()
+            .try_for_each(|owner| self.ensure_allowed(owner))
+    }
+}
+
+/// The owners a GitHub API request addresses: the `{owner}` of a `/repos/{owner}/…`,
+/// `/orgs/{owner}…` or `/users/{owner}…` path, and for GraphQL the `owner`, `org` and
+/// batched `o<N>` variables. Requests to other hosts address none.
+pub fn request_owners(request: &reqwest::Request) -> Vec<String> {
+    if request.url().host_str() != Some(GITHUB_API_HOST) {
+        return Vec::new();
+    }
+    let mut segments = request.url().path_segments().into_iter().flatten();
+    match (segments.next(), segments.next()) {
+        (Some("repos" | "orgs" | "users"), Some(owner)) if !owner.is_empty() => {
+            vec![owner.to_string()]
+        }
+        (Some("graphql"), None) => graphql_owners(request),
+        _ => Vec::new(),
+    }
+}
+
+fn graphql_owners(request: &reqwest::Request) -> Vec<String> {
+    let Some(body) = request.body().and_then(|body| body.as_bytes()) else {
+        return Vec::new();
+    };
+    let Ok(payload) = serde_json::from_slice::<Value>(body) else {
+        return Vec::new();
+    };
+    let Some(variables) = payload.get("variables").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    variables
+        .iter()
+        .filter(|(name, _)| {
+            let batched = name
+                .strip_prefix('o')
+                .is_some_and(|slot| !slot.is_empty() && slot.bytes().all(|b| b.is_ascii_digit()));
+            *name == "owner" || *name == "org" || batched
+        })
+        .filter_map(|(_, value)| value.as_str().map(str::to_string))
+        .collect()
+}
+
+/// The policy in force, loaded once at startup. `Err` holds why it could not be read.
+static ACTIVE: OnceLock<Result<OwnerPolicy, String>> = OnceLock::new();
+
+/// Load the policy from `data_dir`.
+pub fn init(data_dir: &Path) {
+    let loaded = load_policy(data_dir).map_err(|err| {
+        tracing::error!(error = %err, "could not load owner policy; refusing all owners");
+        err.to_string()
+    });
+    let _ = ACTIVE.set(loaded);
+}
+
+/// The policy in force. Fails when it was never loaded or could not be read, so a
+/// broken policy file blocks every owner rather than none.
+pub fn current() -> AppResult<&'static OwnerPolicy> {
+    match ACTIVE.get() {
+        Some(Ok(policy)) => Ok(policy),
+        Some(Err(err)) => Err(AppError::Internal(format!("owner policy is unreadable: {err}"))),
+        None => Err(AppError::Internal("owner policy is not loaded".into())),
+    }
+}
+
+/// Whether `owner` may be listed. An unreadable policy allows no one.
+pub fn is_allowed(owner: &str) -> bool {
+    current().is_ok_and(|policy| policy.is_allowed(owner))
+}
+
+/// Check `request` against the policy in force. Called for every GitHub request, so no
+/// caller can forget it.
+pub fn ensure_request_allowed(request: &reqwest::Request) -> AppResult<()> {
+    current()?.ensure_request_allowed(request)
+}
+
+/// The policy stored in `data_dir`: the default when there is no file, an error when
+/// the file cannot be read or holds an invalid policy.
+pub fn load_policy(data_dir: &Path) -> AppResult<OwnerPolicy> {
+    let raw = match std::fs::read(data_dir.join(OWNER_POLICY_FILE)) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(OwnerPolicy::default())
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let policy: OwnerPolicy = serde_json::from_slice(&raw)?;
+    policy.validate()?;
+    Ok(policy)
+}
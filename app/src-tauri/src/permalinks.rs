@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::error::{AppError, AppResult};
+
+/// Where a comment id lives; each kind uses a different anchor on the PR page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentKind {
+    /// Inline comment on a diff line (`#discussion_r<id>`).
+    ReviewComment,
+    /// Comment in the PR conversation (`#issuecomment-<id>`).
+    IssueComment,
+    /// Top-level review body (`#pullrequestreview-<id>`).
+    Review,
+}
+
+/// Web host for an API base: `https://api.github.com` maps to `https://github.com`,
+/// and a GitHub Enterprise Server base such as `https://ghe.example.com/api/v3` maps to
+/// `https://ghe.example.com`.
+pub fn web_base(api_base: &str) -> String {
+    let trimmed = api_base.trim_end_matches('/');
+    if let Some(rest) = trimmed.strip_prefix("https://api.") {
+        return format!("https://{rest}");
+    }
+    trimmed
+        .strip_suffix("/api/v3")
+        .unwrap_or(trimmed)
+        .to_string()
+}
+
+fn repo_url(web_base: &str, owner: &str, repo: &str) -> AppResult<Url> {
+    let mut url = Url::parse(web_base)?;
+    url.path_segments_mut()
+        .map_err(|_| AppError::InvalidInput(format!("not a web URL: {web_base}")))?
+        .pop_if_empty()
+        .extend([owner, repo]);
+    Ok(url)
+}
+
+pub fn comment_url(
+    web_base: &str,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    kind: CommentKind,
+    comment_id: u64,
+) -> AppResult<String> {
+    let mut url = repo_url(web_base, owner, repo)?;
+    url.path_segments_mut()
+        .map_err(|_| AppError::InvalidInput(format!("not a web URL: {web_base}")))?
+        .extend(["pull", &pr_number.to_string()]);
+    let anchor = match kind {
+        CommentKind::ReviewComment => format!("discussion_r{comment_id}"),
+        CommentKind::IssueComment => format!("issuecomment-{comment_id}"),
+        CommentKind::Review => format!("pullrequestreview-{comment_id}"),
+    };
+    url.set_fragment(Some(&anchor));
+    Ok(url.to_string())
+}
+
+/// Link to one line of a file at a fixed commit. Path segments are percent-encoded, so
+/// spaces and `#` in file names survive.
+pub fn line_url(
+    web_base: &str,
+    owner: &str,
+    repo: &str,
+    sha: &str,
+    path: &str,
+    line: u64,
+) -> AppResult<String> {
+    if sha.len() < 7 || sha.len() > 40 || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::InvalidInput(format!("not a commit SHA: {sha}")));
+    }
+    if line == 0 {
+        return Err(AppError::InvalidInput("line numbers start at 1".into()));
+    }
+    let path = path.trim_matches('/');
+    if path.is_empty() {
+        return Err(AppError::InvalidInput("file path must not be empty".into()));
+    }
+
+    let mut url = repo_url(web_base, owner, repo)?;
+    url.path_segments_mut()
+        .map_err(|_| AppError::InvalidInput(format!("not a web URL: {web_base}")))?
+        .extend(["blob", sha])
+        .extend(path.split('/'));
+    url.set_fragment(Some(&format!("L{line}")));
+    Ok(url.to_string())
+}
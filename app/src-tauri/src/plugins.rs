@@ -0,0 +1,278 @@
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+use wasmtime::component::{Component, Linker};
+use wasmtime::{Config, Engine, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::command_args::RepoPath;
+use crate::error::{AppError, AppResult};
+
+mod bindings {
+    wasmtime::component::bindgen!({ world: "analyzer", path: "wit" });
+}
+
+use bindings::docreviewer::analyzer::types::{FileInput, Severity};
+use bindings::Analyzer;
+
+const PLUGIN_DIR: &str = "plugins";
+/// Instruction budget for a single `analyze` call, so a runaway plugin cannot hang the app.
+const FUEL_PER_CALL: u64 = 500_000_000;
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub path: String,
+    /// Set when the file could not be compiled or does not implement the analyzer world.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginFileInput {
+    pub path: RepoPath,
+    pub content: String,
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginFinding {
+    pub plugin: String,
+    pub path: String,
+    pub line: Option<u32>,
+    pub severity: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl PluginFinding {
+    /// Body used when a finding is saved as a draft review comment. A suggestion replaces
+    /// the commented line, so file-level findings are saved without theirs.
+    pub fn draft_body(&self) -> String {
+        let mut body = format!("**{}** ({}): {}", self.plugin, self.severity, self.message);
+        if let Some(suggestion) = self.suggestion.as_ref().filter(|_| self.line.is_some()) {
+            body.push_str(&format!("\n\n```suggestion\n{}\n```", suggestion));
+        }
+        body
+    }
+}
+
+struct LoadedPlugin {
+    name: String,
+    path: PathBuf,
+    component: Component,
+}
+
+/// Loads analyzer components from `<app data>/plugins/*.wasm` and runs them in a sandbox
+/// with no host imports, a fuel budget, and a memory cap.
+pub struct PluginHost {
+    engine: Engine,
+    dir: PathBuf,
+    plugins: RwLock<Vec<LoadedPlugin>>,
+    failed: RwLock<Vec<PluginInfo>>,
+}
+
+impl PluginHost {
+    pub fn new(data_dir: &Path) -> AppResult<Self> {
+        let mut config = Config::new();
+        config.wasm_component_model(true);
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(plugin_error)?;
+
+        let host = Self {
+            engine,
+            dir: data_dir.join(PLUGIN_DIR),
+            plugins: RwLock::new(Vec::new()),
+            failed: RwLock::new(Vec::new()),
+        };
+        host.reload()?;
+        Ok(host)
+    }
+
+    /// Rescan the plugin directory, compiling every `.wasm` component found.
+    pub fn reload(&self) -> AppResult<Vec<PluginInfo>> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = Vec::new();
+        let mut failed = Vec::new();
+        for path in paths {
+            match self.load_plugin(&path) {
+                Ok(plugin) => {
+                    tracing::info!("Loaded analyzer plugin '{}' from {:?}", plugin.name, path);
+                    loaded.push(plugin);
+                }
+                Err(err) => {
+                    tracing::warn!("Failed to load plugin {:?}: {}", path, err);
+                    failed.push(PluginInfo {
+                        name: file_stem(&path),
+                        path: path.display().to_string(),
+                        error: Some(err.to_string()),
+                    });
+                }
+            }
+        }
+
+        *self
+            .plugins
+            .write()
+            .map_err(|_| AppError::Internal("Lock poisoned".into()))? = loaded;
+        *self
+            .failed
+            .write()
+            .map_err(|_| AppError::Internal("Lock poisoned".into()))? = failed;
+        self.list()
+    }
+
+    pub fn list(&self) -> AppResult<Vec<PluginInfo>> {
+        let plugins = self
+            .plugins
+            .read()
+            .map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let failed = self
+            .failed
+            .read()
+            .map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        Ok(plugins
+            .iter()
+            .map(|plugin| PluginInfo {
+                name: plugin.name.clone(),
+                path: plugin.path.display().to_string(),
+                error: None,
+            })
+            .chain(failed.iter().cloned())
+            .collect())
+    }
+
+    /// Run the loaded plugins over every file: all of them, or only those named in `only`.
+    /// A plugin that traps or runs out of fuel is logged and skipped; it does not stop the
+    /// others.
+    pub fn analyze(
+        &self,
+        files: &[PluginFileInput],
+        only: Option<&[String]>,
+    ) -> AppResult<Vec<PluginFinding>> {
+        let plugins = self
+            .plugins
+            .read()
+            .map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut findings = Vec::new();
+        for plugin in plugins
+            .iter()
+            .filter(|plugin| only.is_none_or(|names| names.contains(&plugin.name)))
+        {
+            for file in files {
+                match self.run_plugin(plugin, file) {
+                    Ok(mut results) => findings.append(&mut results),
+                    Err(err) => tracing::warn!(
+                        "Plugin '{}' failed on {}: {}",
+                        plugin.name,
+                        file.path.0,
+                        err
+                    ),
+                }
+            }
+        }
+        Ok(findings)
+    }
+
+    fn load_plugin(&self, path: &Path) -> AppResult<LoadedPlugin> {
+        let component = Component::from_file(&self.engine, path).map_err(plugin_error)?;
+        let (mut store, analyzer) = self.instantiate(&component)?;
+        let name = analyzer.call_name(&mut store).map_err(plugin_error)?;
+        let name = if name.trim().is_empty() {
+            file_stem(path)
+        } else {
+            name
+        };
+
+        Ok(LoadedPlugin {
+            name,
+            path: path.to_path_buf(),
+            component,
+        })
+    }
+
+    fn run_plugin(
+        &self,
+        plugin: &LoadedPlugin,
+        file: &PluginFileInput,
+    ) -> AppResult<Vec<PluginFinding>> {
+        let (mut store, analyzer) = self.instantiate(&plugin.component)?;
+        let input = FileInput {
+            path: file.path.0.clone(),
+            content: file.content.clone(),
+            diff: file.diff.clone(),
+        };
+        let results = analyzer
+            .call_analyze(&mut store, &input)
+            .map_err(plugin_error)?;
+
+        Ok(results
+            .into_iter()
+            .map(|finding| PluginFinding {
+                plugin: plugin.name.clone(),
+                path: file.path.0.clone(),
+                line: finding.line,
+                severity: severity_name(finding.severity).to_string(),
+                message: finding.message,
+                suggestion: finding.suggestion,
+            })
+            .collect())
+    }
+
+    fn instantiate(&self, component: &Component) -> AppResult<(Store<StoreLimits>, Analyzer)> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(FUEL_PER_CALL).map_err(plugin_error)?;
+
+        // No imports are linked, so plugins cannot reach the host.
+        let linker = Linker::new(&self.engine);
+        let analyzer = Analyzer::instantiate(&mut store, component, &linker).map_err(plugin_error)?;
+        Ok((store, analyzer))
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn plugin_error(err: wasmtime::Error) -> AppError {
+    AppError::Internal(format!("plugin error: {err:#}"))
+}
+
+static PLUGIN_HOST: OnceLock<PluginHost> = OnceLock::new();
+
+pub fn init_plugins(data_dir: &Path) -> AppResult<()> {
+    let host = PluginHost::new(data_dir)?;
+    PLUGIN_HOST
+        .set(host)
+        .map_err(|_| AppError::Internal("Plugin host already initialized".into()))?;
+    Ok(())
+}
+
+pub fn get_plugins() -> AppResult<&'static PluginHost> {
+    PLUGIN_HOST
+        .get()
+        .ok_or_else(|| AppError::Internal("Plugin host not initialized".into()))
+}
@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+
+use crate::error::{AppError, AppResult};
+use crate::models::PullRequestDetail;
+
+/// Folder under the app data dir holding one JSON file per pull request.
+pub const PR_CACHE_DIR: &str = "pr_cache";
+
+/// Cached details older than this are neither served nor kept.
+pub const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// Pull requests kept across all accounts; the least recently saved go first.
+pub const MAX_ENTRIES: usize = 200;
+
+/// The last-fetched details of each pull request, so one already opened can be read
+/// while GitHub is out of reach. Each GitHub account has its own, so details fetched
+/// with one token are never shown to another.
+pub struct PrCache {
+    dir: PathBuf,
+}
+
+static PR_CACHE: OnceLock<PrCache> = OnceLock::new();
+
+impl PrCache {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(PR_CACHE_DIR),
+        }
+    }
+
+    /// Owner and repo are validated command arguments and logins come from GitHub, so
+    /// they are safe as path parts.
+    fn path(&self, login: &str, owner: &str, repo: &str, number: u64) -> PathBuf {
+        self.dir.join(login).join(owner).join(repo).join(format!("{number}.json"))
+    }
+
+    pub fn save(
+        &self,
+        login: &str,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        detail: &PullRequestDetail,
+    ) -> AppResult<()> {
+        let path = self.path(login, owner, repo, number);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut detail = detail.clone();
+        detail.cached_at = Some(Utc::now().to_rfc3339());
+        // Write then rename, so a crash mid-write never leaves half a file behind
+        let partial = path.with_extension("json.partial");
+        std::fs::write(&partial, serde_json::to_vec(&detail)?)?;
+        std::fs::rename(&partial, &path)?;
+        self.prune(SystemTime::now())
+    }
+
+    /// Delete entries older than [`MAX_AGE`] at `now`, then the least recently saved ones
+    /// past [`MAX_ENTRIES`].
+    pub fn prune(&self, now: SystemTime) -> AppResult<()> {
+        let mut entries = Vec::new();
+        collect_entries(&self.dir, &mut entries)?;
+        entries.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+        for (index, (path, modified)) in entries.iter().enumerate() {
+            let expired = now.duration_since(*modified).is_ok_and(|age| age > MAX_AGE);
+            if expired || index >= MAX_ENTRIES {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The cached details marked as stale, or `None` when `login` never cached this PR,
+    /// its file cannot be read, or it is older than [`MAX_AGE`].
+    pub fn load(&self, login: &str, owner: &str, repo: &str, number: u64) -> Option<PullRequestDetail> {
+        let raw = std::fs::read(self.path(login, owner, repo, number)).ok()?;
+        match serde_json::from_slice::<PullRequestDetail>(&raw) {
+            Ok(detail) if is_expired(&detail, Utc::now()) => None,
+            Ok(mut detail) => {
+                detail.stale = true;
+                Some(detail)
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "cached details of {owner}/{repo}#{number} are unreadable");
+                None
+            }
+        }
+    }
+
+    pub fn clear(&self) -> AppResult<()> {
+        if self.dir.exists() {
+            std::fs::remove_dir_all(&self.dir)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_expired(detail: &PullRequestDetail, now: DateTime<Utc>) -> bool {
+    let Some(cached_at) = detail.cached_at.as_deref() else {
+        return true;
+    };
+    DateTime::parse_from_rfc3339(cached_at).map_or(true, |cached_at| {
+        (now - cached_at.with_timezone(&Utc)).to_std().is_ok_and(|age| age > MAX_AGE)
+    })
+}
+
+/// Every cached `.json` file under `dir` with when it was written.
+fn collect_entries(dir: &Path, entries: &mut Vec<(PathBuf, SystemTime)>) -> AppResult<()> {
+    let read_dir = match std::fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    for entry in read_dir {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_entries(&path, entries)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            entries.push((path, entry.metadata()?.modified()?));
+        }
+    }
+    Ok(())
+}
+
+pub fn init(data_dir: &Path) {
+    let _ = PR_CACHE.set(PrCache::new(data_dir));
+}
+
+pub fn get() -> Option<&'static PrCache> {
+    PR_CACHE.get()
+}
+
+/// Errors that mean GitHub could not be reached, rather than that it said no.
+pub fn is_offline_error(err: &AppError) -> bool {
+    match err {
+        AppError::Http(err) => err.is_connect() || err.is_timeout() || err.is_request(),
+        AppError::Timeout | AppError::RateLimited(_) => true,
+        _ => false,
+    }
+}
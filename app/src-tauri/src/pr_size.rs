@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewStorage;
+use crate::settings;
+
+pub const PR_SIZE_KEY: &str = "pr_size";
+
+const PROSE_EXTENSIONS: &[&str] = &["md", "mdx", "markdown", "rst", "txt", "adoc", "asciidoc"];
+const ASSET_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "webp", "ico", "svg", "pdf", "lock", "snap",
+];
+const GENERATED_FILES: &[&str] = &["package-lock.json", "yarn.lock", "pnpm-lock.yaml", "Cargo.lock"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SizeClass {
+    Xs,
+    S,
+    M,
+    L,
+    Xl,
+    Xxl,
+}
+
+/// Size thresholds and reading speeds, stored in settings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PrSizeConfig {
+    /// Upper bounds, in reviewed lines, for XS, S, M, L and XL; anything above is XXL.
+    pub thresholds: [u64; 5],
+    /// Changed lines of documentation read per minute.
+    pub prose_lines_per_minute: f64,
+    /// Changed lines of code or config read per minute.
+    pub code_lines_per_minute: f64,
+    /// Time to open and orient in each file, whatever its size.
+    pub minutes_per_file: f64,
+}
+
+impl Default for PrSizeConfig {
+    fn default() -> Self {
+        Self {
+            thresholds: [10, 50, 250, 1000, 2500],
+            prose_lines_per_minute: 20.0,
+            code_lines_per_minute: 8.0,
+            minutes_per_file: 0.5,
+        }
+    }
+}
+
+impl PrSizeConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(AppError::InvalidInput(
+                "size thresholds must be strictly increasing".to_string(),
+            ));
+        }
+        let rates = [
+            ("prose_lines_per_minute", self.prose_lines_per_minute),
+            ("code_lines_per_minute", self.code_lines_per_minute),
+        ];
+        for (field, rate) in rates {
+            if !(rate.is_finite() && rate > 0.0) {
+                return Err(AppError::InvalidInput(format!("{field} must be greater than 0")));
+            }
+        }
+        if !(self.minutes_per_file.is_finite() && self.minutes_per_file >= 0.0) {
+            return Err(AppError::InvalidInput(
+                "minutes_per_file must not be negative".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Line counts for one changed file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: String,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrSize {
+    pub class: SizeClass,
+    /// Added plus deleted lines, not counting images, lock files and other assets.
+    pub reviewed_lines: u64,
+    pub estimated_minutes: u64,
+}
+
+enum FileKind {
+    Prose,
+    Code,
+    Asset,
+}
+
+fn extension(path: &str) -> Option<String> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase())
+}
+
+/// Whether `path` is documentation (markdown, reStructuredText, plain text, AsciiDoc).
+pub fn is_prose_path(path: &str) -> bool {
+    extension(path).is_some_and(|ext| PROSE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+fn file_kind(path: &str) -> FileKind {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match extension(path).as_deref() {
+        _ if GENERATED_FILES.contains(&name) || name.contains(".min.") => FileKind::Asset,
+        Some(ext) if ASSET_EXTENSIONS.contains(&ext) => FileKind::Asset,
+        _ if is_prose_path(path) => FileKind::Prose,
+        _ => FileKind::Code,
+    }
+}
+
+/// Classify a PR from its per-file changes. `additions` and `deletions` are the PR totals;
+/// lines in files beyond those listed (GitHub caps file lists) count as code.
+pub fn classify(
+    additions: u64,
+    deletions: u64,
+    files: &[FileChange],
+    changed_files: u64,
+    config: &PrSizeConfig,
+) -> PrSize {
+    let mut prose_lines = 0;
+    let mut code_lines = 0;
+    let mut listed_lines = 0;
+    for file in files {
+        let lines = file.additions + file.deletions;
+        listed_lines += lines;
+        match file_kind(&file.path) {
+            FileKind::Prose => prose_lines += lines,
+            FileKind::Code => code_lines += lines,
+            FileKind::Asset => {}
+        }
+    }
+    code_lines += (additions + deletions).saturating_sub(listed_lines);
+
+    let reviewed_lines = prose_lines + code_lines;
+    let class = match config.thresholds.iter().position(|max| reviewed_lines <= *max) {
+        Some(0) => SizeClass::Xs,
+        Some(1) => SizeClass::S,
+        Some(2) => SizeClass::M,
+        Some(3) => SizeClass::L,
+        Some(_) => SizeClass::Xl,
+        None => SizeClass::Xxl,
+    };
+
+    let file_count = changed_files.max(files.len() as u64);
+    let minutes = prose_lines as f64 / config.prose_lines_per_minute
+        + code_lines as f64 / config.code_lines_per_minute
+        + file_count as f64 * config.minutes_per_file;
+
+    PrSize {
+        class,
+        reviewed_lines,
+        estimated_minutes: (minutes.ceil() as u64).max(1),
+    }
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<PrSizeConfig> {
+    settings::load(storage, PR_SIZE_KEY)
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &PrSizeConfig) -> AppResult<()> {
+    config.validate()?;
+    settings::save(storage, PR_SIZE_KEY, config)
+}
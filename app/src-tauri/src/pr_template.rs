@@ -0,0 +1,190 @@
+use serde::Serialize;
+
+/// Result of comparing a PR description against the repository's PR template.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct TemplateCheck {
+    /// Template the body was compared against; `None` when the repo has no template.
+    pub template_path: Option<String>,
+    /// Template headings that do not appear in the body.
+    pub missing_sections: Vec<String>,
+    /// Headings present in the body but left empty or identical to the template text.
+    pub empty_sections: Vec<String>,
+    /// Template checkboxes deleted from the body.
+    pub missing_checkboxes: Vec<String>,
+    /// Template checkboxes present in the body but not ticked.
+    pub unchecked_checkboxes: Vec<String>,
+    pub is_complete: bool,
+}
+
+impl TemplateCheck {
+    fn issue_count(&self) -> usize {
+        self.missing_sections.len()
+            + self.empty_sections.len()
+            + self.missing_checkboxes.len()
+            + self.unchecked_checkboxes.len()
+    }
+}
+
+struct Section {
+    title: String,
+    key: String,
+    content: String,
+    has_checkboxes: bool,
+}
+
+struct Checkbox {
+    label: String,
+    key: String,
+    checked: bool,
+}
+
+/// Remove `<!-- ... -->` comments, which templates use for instructions.
+fn strip_html_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Lowercase, keep letters/digits, collapse everything else to single spaces.
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_heading(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 || !trimmed[level..].starts_with(' ') {
+        return None;
+    }
+    let title = trimmed[level..].trim().trim_end_matches('#').trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+fn parse_checkbox(line: &str) -> Option<(bool, String)> {
+    let trimmed = line.trim_start();
+    let rest = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))?
+        .trim_start();
+    let (checked, label) = if let Some(label) = rest.strip_prefix("[ ]") {
+        (false, label)
+    } else if let Some(label) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, label)
+    } else {
+        return None;
+    };
+    let label = label.trim();
+    (!label.is_empty()).then(|| (checked, label.to_string()))
+}
+
+fn parse_sections(text: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    for line in text.lines() {
+        if let Some(title) = parse_heading(line) {
+            sections.push(Section {
+                key: normalize(&title),
+                title,
+                content: String::new(),
+                has_checkboxes: false,
+            });
+        } else if parse_checkbox(line).is_some() {
+            // Checkboxes are checked separately and do not count as section text
+            if let Some(section) = sections.last_mut() {
+                section.has_checkboxes = true;
+            }
+        } else if let Some(section) = sections.last_mut() {
+            section.content.push_str(line);
+            section.content.push('\n');
+        }
+    }
+    sections
+}
+
+fn parse_checkboxes(text: &str) -> Vec<Checkbox> {
+    text.lines()
+        .filter_map(parse_checkbox)
+        .map(|(checked, label)| Checkbox {
+            key: normalize(&label),
+            label,
+            checked,
+        })
+        .collect()
+}
+
+/// Compare one template with a PR body. Headings containing "optional" are not required.
+pub fn check_body(template_path: &str, template: &str, body: &str) -> TemplateCheck {
+    let template = strip_html_comments(template);
+    let body = strip_html_comments(body);
+
+    let template_sections = parse_sections(&template);
+    let body_sections = parse_sections(&body);
+    let body_checkboxes = parse_checkboxes(&body);
+
+    let mut check = TemplateCheck {
+        template_path: Some(template_path.to_string()),
+        ..TemplateCheck::default()
+    };
+
+    for section in &template_sections {
+        if section.key.contains("optional") {
+            continue;
+        }
+        match body_sections.iter().find(|s| s.key == section.key) {
+            None => check.missing_sections.push(section.title.clone()),
+            Some(found) => {
+                let prompt = normalize(&section.content);
+                if prompt.is_empty() && section.has_checkboxes {
+                    // A checklist-only section is covered by the checkbox checks
+                    continue;
+                }
+                let filled = normalize(&found.content);
+                if filled.is_empty() || filled == prompt {
+                    check.empty_sections.push(section.title.clone());
+                }
+            }
+        }
+    }
+
+    for checkbox in parse_checkboxes(&template) {
+        match body_checkboxes.iter().find(|c| c.key == checkbox.key) {
+            None => check.missing_checkboxes.push(checkbox.label),
+            Some(found) if !found.checked => check.unchecked_checkboxes.push(checkbox.label),
+            Some(_) => {}
+        }
+    }
+
+    check.is_complete = check.issue_count() == 0;
+    check
+}
+
+/// Check the body against every template and report the closest match, since repos
+/// with several templates let the author pick one.
+pub fn check_against_templates(templates: &[(String, String)], body: &str) -> TemplateCheck {
+    templates
+        .iter()
+        .map(|(path, template)| check_body(path, template, body))
+        .min_by_key(TemplateCheck::issue_count)
+        .unwrap_or(TemplateCheck {
+            is_complete: true,
+            ..TemplateCheck::default()
+        })
+}
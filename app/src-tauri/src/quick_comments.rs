@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+use crate::categories::validate_category;
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewStorage;
+
+/// Set once quick comments are first saved; until then the defaults apply.
+pub const QUICK_COMMENTS_SAVED_KEY: &str = "quick_comments_saved";
+
+/// A canned comment the UI can bind to a key. Stored as a comment template named
+/// `label`, so it shows in the library too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuickCommentTemplate {
+    pub id: String,
+    pub label: String,
+    /// Comment text. Supports `{{owner}}`, `{{repo}}`, `{{pr}}`, `{{path}}`, `{{file}}`
+    /// (file name only) and `{{line}}`.
+    pub body: String,
+    /// Category given to the new comment, e.g. `nit`.
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct QuickCommentConfig {
+    pub templates: Vec<QuickCommentTemplate>,
+}
+
+fn template(id: &str, label: &str, body: &str, category: Option<&str>) -> QuickCommentTemplate {
+    QuickCommentTemplate {
+        id: id.to_string(),
+        label: label.to_string(),
+        body: body.to_string(),
+        category: category.map(str::to_string),
+    }
+}
+
+impl Default for QuickCommentConfig {
+    fn default() -> Self {
+        Self {
+            templates: vec![
+                template("typo", "Typo", "Typo on line {{line}}.", Some("nit")),
+                template(
+                    "unclear",
+                    "Unclear",
+                    "This is hard to follow; could you reword it?",
+                    Some("suggestion"),
+                ),
+                template("source", "Needs source", "Can we link a source for this?", Some("question")),
+                template("broken-link", "Broken link", "This link looks broken.", Some("blocking")),
+            ],
+        }
+    }
+}
+
+impl QuickCommentConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        for (index, template) in self.templates.iter().enumerate() {
+            if template.id.trim().is_empty() {
+                return Err(AppError::InvalidInput(format!(
+                    "quick comment template {} has an empty id",
+                    index + 1
+                )));
+            }
+            if template.label.trim().is_empty() {
+                return Err(AppError::InvalidInput(format!(
+                    "quick comment template `{}` has an empty label",
+                    template.id
+                )));
+            }
+            if self.templates[..index].iter().any(|other| other.id == template.id) {
+                return Err(AppError::InvalidInput(format!(
+                    "quick comment template id `{}` is used more than once",
+                    template.id
+                )));
+            }
+            if self.templates[..index]
+                .iter()
+                .any(|other| other.label.trim().eq_ignore_ascii_case(template.label.trim()))
+            {
+                return Err(AppError::InvalidInput(format!(
+                    "quick comment label `{}` is used more than once",
+                    template.label
+                )));
+            }
+            if template.body.trim().is_empty() {
+                return Err(AppError::InvalidInput(format!(
+                    "quick comment template `{}` has an empty body",
+                    template.id
+                )));
+            }
+            if let Some(category) = template.category.as_deref() {
+                validate_category(category)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn find(&self, id: &str) -> AppResult<&QuickCommentTemplate> {
+        self.templates
+            .iter()
+            .find(|template| template.id == id)
+            .ok_or_else(|| AppError::InvalidInput(format!("unknown quick comment template `{id}`")))
+    }
+}
+
+/// Where a quick comment goes. `line` is `None` for a file-level comment.
+pub struct CommentContext<'a> {
+    pub owner: &'a str,
+    pub repo: &'a str,
+    pub pr_number: u64,
+    pub path: &'a str,
+    pub line: Option<u64>,
+}
+
+impl QuickCommentTemplate {
+    /// The body with placeholders filled in.
+    pub fn render(&self, context: &CommentContext<'_>) -> String {
+        fill_placeholders(&self.body, context)
+    }
+}
+
+/// Fill in `{{owner}}`, `{{repo}}`, `{{pr}}`, `{{path}}`, `{{file}}` and `{{line}}`, the
+/// last empty for file-level comments. Other text in braces is left as written.
+pub fn fill_placeholders(body: &str, context: &CommentContext<'_>) -> String {
+    let file = context.path.rsplit('/').next().unwrap_or(context.path);
+    let line = context.line.map(|line| line.to_string()).unwrap_or_default();
+    body.replace("{{owner}}", context.owner)
+        .replace("{{repo}}", context.repo)
+        .replace("{{pr}}", &context.pr_number.to_string())
+        .replace("{{path}}", context.path)
+        .replace("{{file}}", file)
+        .replace("{{line}}", &line)
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<QuickCommentConfig> {
+    if storage.get_setting(QUICK_COMMENTS_SAVED_KEY)?.is_none() {
+        return Ok(QuickCommentConfig::default());
+    }
+    let templates = storage
+        .list_quick_comment_templates()?
+        .into_iter()
+        .filter_map(|template| {
+            Some(QuickCommentTemplate {
+                id: template.quick_key?,
+                label: template.name,
+                body: template.body,
+                category: template.category,
+            })
+        })
+        .collect();
+    Ok(QuickCommentConfig { templates })
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &QuickCommentConfig) -> AppResult<()> {
+    config.validate()?;
+    storage.replace_quick_comment_templates(&config.templates)
+}
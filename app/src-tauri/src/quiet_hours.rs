@@ -0,0 +1,143 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewStorage;
+use crate::settings;
+use crate::time_format;
+
+pub const QUIET_HOURS_KEY: &str = "quiet_hours";
+
+pub const WEEKDAYS: &[&str] = &["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// Longest stretch searched for the end of a quiet period; a config that is quiet all
+/// week never resumes.
+const MAX_SEARCH_MINUTES: i64 = 8 * 24 * 60;
+
+/// Evenings, weekends and other times the user does not want to be disturbed. This only
+/// describes the quiet times; the app has no desktop alerts or scheduled submissions to
+/// hold back yet, so `status_at` is for whatever wants to ask.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietHoursConfig {
+    pub enabled: bool,
+    /// IANA zone such as `Europe/Berlin`; `None` follows the system clock.
+    pub timezone: Option<String>,
+    /// Daily quiet window as `HH:MM`. A window whose end is before its start runs past
+    /// midnight, e.g. 18:00 to 09:00.
+    pub start: String,
+    pub end: String,
+    /// Whole days that are quiet, e.g. `sat` and `sun`.
+    pub quiet_days: Vec<String>,
+}
+
+impl Default for QuietHoursConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timezone: None,
+            start: "18:00".to_string(),
+            end: "09:00".to_string(),
+            quiet_days: vec!["sat".to_string(), "sun".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuietHoursStatus {
+    pub quiet: bool,
+    /// RFC 3339 time when quiet hours end; `None` when not quiet or never ending.
+    pub resumes_at: Option<String>,
+}
+
+fn parse_time(field: &str, value: &str) -> AppResult<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| AppError::InvalidInput(format!("{field} must be HH:MM, got `{value}`")))
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    WEEKDAYS[day.num_days_from_monday() as usize]
+}
+
+impl QuietHoursConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        parse_time("start", &self.start)?;
+        parse_time("end", &self.end)?;
+        self.zone()?;
+        for day in &self.quiet_days {
+            settings::ensure_one_of("quiet_days", day, WEEKDAYS)?;
+        }
+        Ok(())
+    }
+
+    fn zone(&self) -> AppResult<Option<Tz>> {
+        time_format::parse_zone(self.timezone.as_deref())
+    }
+
+    /// Weekday and wall-clock time of `instant` in the configured zone.
+    fn local_parts(&self, instant: DateTime<Utc>) -> AppResult<(Weekday, NaiveTime)> {
+        let naive = match self.zone()? {
+            Some(tz) => tz.from_utc_datetime(&instant.naive_utc()).naive_local(),
+            None => Local.from_utc_datetime(&instant.naive_utc()).naive_local(),
+        };
+        Ok((naive.weekday(), naive.time()))
+    }
+
+    pub fn is_quiet_at(&self, instant: DateTime<Utc>) -> AppResult<bool> {
+        if !self.enabled {
+            return Ok(false);
+        }
+        let start = parse_time("start", &self.start)?;
+        let end = parse_time("end", &self.end)?;
+        let (day, time) = self.local_parts(instant)?;
+
+        if self.quiet_days.iter().any(|d| d == weekday_name(day)) {
+            return Ok(true);
+        }
+        Ok(match start.cmp(&end) {
+            std::cmp::Ordering::Less => time >= start && time < end,
+            std::cmp::Ordering::Greater => time >= start || time < end,
+            std::cmp::Ordering::Equal => false,
+        })
+    }
+
+    /// First moment at or after `instant` outside quiet hours, to the minute. Walking
+    /// forward in local time keeps DST changes and midnight-crossing windows correct.
+    pub fn next_allowed_at(&self, instant: DateTime<Utc>) -> AppResult<Option<DateTime<Utc>>> {
+        if !self.is_quiet_at(instant)? {
+            return Ok(Some(instant));
+        }
+        let mut candidate = instant
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(instant);
+        for _ in 0..MAX_SEARCH_MINUTES {
+            candidate += Duration::minutes(1);
+            if !self.is_quiet_at(candidate)? {
+                return Ok(Some(candidate));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn status_at(&self, instant: DateTime<Utc>) -> AppResult<QuietHoursStatus> {
+        let quiet = self.is_quiet_at(instant)?;
+        let resumes_at = if quiet {
+            self.next_allowed_at(instant)?.map(|t| t.to_rfc3339())
+        } else {
+            None
+        };
+        Ok(QuietHoursStatus { quiet, resumes_at })
+    }
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<QuietHoursConfig> {
+    settings::load(storage, QUIET_HOURS_KEY)
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &QuietHoursConfig) -> AppResult<()> {
+    config.validate()?;
+    settings::save(storage, QUIET_HOURS_KEY, config)
+}
+
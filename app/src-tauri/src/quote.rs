@@ -0,0 +1,47 @@
+/// Prefix every line with `> `. Blank lines get a bare `>` so the quote stays one block,
+/// and suggestion fences become plain `diff` fences so quoting a suggestion does not
+/// offer it for commit a second time.
+pub fn quote_body(body: &str) -> String {
+    body.trim_end()
+        .lines()
+        .map(|line| {
+            let line = line.trim_end();
+            let line = match line.trim_start().strip_prefix("```suggestion") {
+                Some(rest) if rest.trim().is_empty() => "```diff",
+                _ => line,
+            };
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Attribution for a comment posted on GitHub; the mention notifies the author.
+pub fn github_attribution(login: &str) -> String {
+    format!("@{login} wrote:")
+}
+
+/// Attribution for a local draft, which has no author yet.
+pub fn draft_attribution(path: &str, line: u64) -> String {
+    format!("On `{path}` line {line}:")
+}
+
+/// Body of a line comment turned into a general one, with where it was written quoted
+/// above it.
+pub fn general_comment_body(path: &str, line: u64, body: &str) -> String {
+    let anchor = if line == 0 {
+        format!("On `{path}`:")
+    } else {
+        draft_attribution(path, line)
+    };
+    format!("{}\n\n{body}", quote_body(&anchor))
+}
+
+/// Reply body with the attribution, the quoted comment and an empty line to type after.
+pub fn build_reply(attribution: &str, body: &str) -> String {
+    format!("{attribution}\n\n{}\n\n", quote_body(body))
+}
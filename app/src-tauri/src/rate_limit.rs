@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::Serialize;
+
+use crate::events;
+
+/// GitHub asks for at least a minute before retrying after a secondary limit without
+/// `Retry-After`; each further hit doubles it up to this cap.
+const SECONDARY_BASE_PAUSE: Duration = Duration::from_secs(60);
+const SECONDARY_MAX_PAUSE: Duration = Duration::from_secs(15 * 60);
+/// Used when a primary limit gives neither `Retry-After` nor a reset time.
+const PRIMARY_FALLBACK_PAUSE: Duration = Duration::from_secs(60);
+/// Once under a tenth of the quota is left, requests are spread over what remains of the
+/// window, at most this far apart.
+const MAX_LOW_SPACING: Duration = Duration::from_secs(10);
+/// With the quota used up, requests wait for the reset when it is this close; further off
+/// they go out and fail with a clear error rather than hang.
+const MAX_QUEUE_WAIT: Duration = Duration::from_secs(90);
+
+/// Sent once per window when a quota runs low, with its `RateLimitStatus`.
+pub const RATE_LIMIT_LOW_EVENT: &str = "rate-limit-low";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKind {
+    /// The hourly request quota is used up.
+    Primary,
+    /// Too many requests, or too much content created, in a short time.
+    Secondary,
+}
+
+impl RateLimitKind {
+    /// Name sent with progress events.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Primary => "rate_limit",
+            Self::Secondary => "secondary_rate_limit",
+        }
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim)
+}
+
+/// Whether a response is a rate limit, and which one. GitHub reports both kinds as 403 or
+/// 429; the body tells them apart.
+pub fn classify(status: StatusCode, headers: &HeaderMap, body: &str) -> Option<RateLimitKind> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let body = body.to_ascii_lowercase();
+    if body.contains("secondary rate limit") || body.contains("abuse detection") {
+        return Some(RateLimitKind::Secondary);
+    }
+    if body.contains("rate limit") || header(headers, "x-ratelimit-remaining") == Some("0") {
+        return Some(RateLimitKind::Primary);
+    }
+    (status == StatusCode::TOO_MANY_REQUESTS).then_some(RateLimitKind::Secondary)
+}
+
+/// `Retry-After` as seconds or an HTTP date.
+pub fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = header(headers, "retry-after")?;
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let until = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((until - now).to_std().unwrap_or_default())
+}
+
+/// How long to hold every further request. `attempt` counts earlier limits hit for the
+/// same request, starting at 0.
+pub fn pause_for(
+    kind: RateLimitKind,
+    headers: &HeaderMap,
+    attempt: u32,
+    now: DateTime<Utc>,
+) -> Duration {
+    let pause = retry_after(headers, now).unwrap_or_else(|| match kind {
+        RateLimitKind::Primary => header(headers, "x-ratelimit-reset")
+            .and_then(|reset| reset.parse::<i64>().ok())
+            .map(|reset| Duration::from_secs(reset.saturating_sub(now.timestamp()).max(0) as u64))
+            .unwrap_or(PRIMARY_FALLBACK_PAUSE),
+        RateLimitKind::Secondary => SECONDARY_BASE_PAUSE
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(SECONDARY_MAX_PAUSE),
+    });
+    pause.max(Duration::from_secs(1))
+}
+
+/// A quota as GitHub last reported it in the `x-ratelimit-*` headers.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RateLimitStatus {
+    /// `core`, `graphql`, `search` and so on; REST calls report `core`.
+    pub resource: String,
+    pub limit: u32,
+    pub remaining: u32,
+    /// RFC 3339 UTC time the quota refills.
+    pub reset_at: String,
+    #[serde(skip)]
+    reset: i64,
+    /// Earliest time the next throttled request on this quota may go out.
+    #[serde(skip)]
+    next_slot: Option<DateTime<Utc>>,
+}
+
+impl RateLimitStatus {
+    pub fn is_low(&self) -> bool {
+        self.remaining <= self.limit / 10
+    }
+
+    /// How long to hold the next request on this quota at `now`, if at all.
+    pub fn delay_before_request(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let until_reset =
+            Duration::from_secs(self.reset.saturating_sub(now.timestamp()).max(0) as u64);
+        if until_reset.is_zero() || !self.is_low() {
+            return None;
+        }
+        if self.remaining == 0 {
+            return (until_reset <= MAX_QUEUE_WAIT).then_some(until_reset);
+        }
+        Some((until_reset / (self.remaining + 1)).min(MAX_LOW_SPACING))
+    }
+
+    /// Claim the next request slot at `now` and count it against `remaining`, returning
+    /// how long to wait for it. Slots are spaced one after another, so requests throttled
+    /// at the same time do not all wake together.
+    pub fn reserve(&mut self, now: DateTime<Utc>) -> Option<Duration> {
+        let spacing = self.delay_before_request(now)?;
+        if self.remaining == 0 {
+            // Everything waits for the reset, which no amount of spacing brings closer
+            return Some(spacing);
+        }
+        let start = self.next_slot.filter(|slot| *slot > now).unwrap_or(now);
+        let slot = start + chrono::Duration::from_std(spacing).ok()?;
+        self.next_slot = Some(slot);
+        self.remaining -= 1;
+        (slot - now).to_std().ok()
+    }
+}
+
+impl RateLimitStatus {
+    /// `reset` is in epoch seconds, as GitHub sends it.
+    pub fn new(resource: &str, limit: u32, remaining: u32, reset: i64) -> Option<Self> {
+        Some(Self {
+            resource: resource.to_string(),
+            limit,
+            remaining,
+            reset_at: Utc.timestamp_opt(reset, 0).single()?.to_rfc3339(),
+            reset,
+            next_slot: None,
+        })
+    }
+}
+
+/// The quota in `headers`, if the response carried one.
+pub fn parse_status(headers: &HeaderMap) -> Option<RateLimitStatus> {
+    RateLimitStatus::new(
+        header(headers, "x-ratelimit-resource").unwrap_or("core"),
+        header(headers, "x-ratelimit-limit")?.parse().ok()?,
+        header(headers, "x-ratelimit-remaining")?.parse().ok()?,
+        header(headers, "x-ratelimit-reset")?.parse().ok()?,
+    )
+}
+
+/// Every quota in a `GET /rate_limit` response.
+pub fn parse_rate_limit_body(body: &serde_json::Value) -> Vec<RateLimitStatus> {
+    let Some(resources) = body.get("resources").and_then(|r| r.as_object()) else {
+        return Vec::new();
+    };
+    resources
+        .iter()
+        .filter_map(|(resource, quota)| {
+            let field = |name: &str| quota.get(name).and_then(|v| v.as_i64());
+            RateLimitStatus::new(
+                resource,
+                u32::try_from(field("limit")?).ok()?,
+                u32::try_from(field("remaining")?).ok()?,
+                field("reset")?,
+            )
+        })
+        .collect()
+}
+
+/// Quota a request to `path` counts against, going by GitHub's documented resources.
+pub fn resource_for_path(path: &str) -> &'static str {
+    if path.ends_with("/graphql") {
+        "graphql"
+    } else if path.contains("/search/code") {
+        "code_search"
+    } else if path.contains("/search/") {
+        "search"
+    } else {
+        "core"
+    }
+}
+
+fn statuses() -> &'static Mutex<HashMap<String, RateLimitStatus>> {
+    static STATUSES: OnceLock<Mutex<HashMap<String, RateLimitStatus>>> = OnceLock::new();
+    STATUSES.get_or_init(Default::default)
+}
+
+/// Keep the quota a response reported.
+pub fn observe(headers: &HeaderMap) {
+    if let Some(status) = parse_status(headers) {
+        record(status);
+    }
+}
+
+/// Keep `status` as the latest for its resource, telling the UI the first time in a
+/// window that it is running low. Within one window the lower `remaining` wins, since
+/// responses can arrive out of order and throttled requests already count themselves.
+pub fn record(mut status: RateLimitStatus) {
+    let Ok(mut statuses) = statuses().lock() else {
+        return;
+    };
+    let previous = statuses
+        .get(&status.resource)
+        .filter(|previous| previous.reset == status.reset);
+    if let Some(previous) = previous {
+        status.remaining = status.remaining.min(previous.remaining);
+        status.next_slot = previous.next_slot;
+    }
+    let was_low = previous.is_some_and(RateLimitStatus::is_low);
+    if status.is_low() && !was_low {
+        tracing::warn!(
+            resource = %status.resource,
+            remaining = status.remaining,
+            limit = status.limit,
+            reset_at = %status.reset_at,
+            "GitHub rate limit running low"
+        );
+        events::emit(RATE_LIMIT_LOW_EVENT, status.clone());
+    }
+    statuses.insert(status.resource.clone(), status);
+}
+
+/// Every quota seen so far this session, by resource name.
+pub fn current() -> Vec<RateLimitStatus> {
+    let mut all: Vec<RateLimitStatus> = statuses()
+        .lock()
+        .map(|statuses| statuses.values().cloned().collect())
+        .unwrap_or_default();
+    all.sort_by(|a, b| a.resource.cmp(&b.resource));
+    all
+}
+
+/// Hold a request to `path` while its quota is low, so calls are spread over the rest of
+/// the window instead of running into 403s. The slot is claimed under the same lock that
+/// holds the quota, so concurrent requests queue behind each other.
+pub async fn throttle(path: &str) {
+    let delay = statuses().lock().ok().and_then(|mut statuses| {
+        statuses
+            .get_mut(resource_for_path(path))
+            .and_then(|status| status.reserve(Utc::now()))
+    });
+    if let Some(delay) = delay {
+        tracing::info!(
+            path,
+            delay_ms = delay.as_millis() as u64,
+            "Holding request for the rate limit"
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
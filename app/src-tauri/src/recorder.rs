@@ -0,0 +1,415 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::fs::File;
+use std::io::Write;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use chrono::Utc;
+use regex::Regex;
+use reqwest::ResponseBuilderExt;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::{AppError, AppResult};
+
+pub const RECORDING_FORMAT: &str = "github-review-recording";
+pub const RECORDING_VERSION: u32 = 2;
+/// Directory under the app data directory that holds recordings.
+pub const RECORDINGS_DIR: &str = "recordings";
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Response headers never written to a recording.
+const DROPPED_HEADERS: &[&str] = &["set-cookie", "cookie", "authorization", "proxy-authorization"];
+
+/// One GitHub request and the response it got, with credentials removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub method: String,
+    pub url: String,
+    pub request_body: Option<String>,
+    pub status: u16,
+    pub response_headers: BTreeMap<String, String>,
+    pub response_body: String,
+    pub recorded_at: String,
+}
+
+/// A session's GitHub traffic, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    pub format: String,
+    pub version: u32,
+    pub started_at: String,
+    pub exchanges: Vec<RecordedExchange>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self {
+            format: RECORDING_FORMAT.to_string(),
+            version: RECORDING_VERSION,
+            started_at: Utc::now().to_rfc3339(),
+            exchanges: Vec::new(),
+        }
+    }
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The first line of a recording file; each line after it is one exchange.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordingHeader {
+    format: String,
+    version: u32,
+    started_at: String,
+}
+
+/// Read a recording: JSON Lines as written since version 2, or the single JSON document
+/// of version 1.
+pub fn parse_recording(text: &str) -> AppResult<Recording> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let recording = match lines.next().map(serde_json::from_str::<RecordingHeader>) {
+        Some(Ok(header)) => Recording {
+            format: header.format,
+            version: header.version,
+            started_at: header.started_at,
+            exchanges: lines.map(serde_json::from_str).collect::<Result<_, _>>()?,
+        },
+        _ => serde_json::from_str(text)?,
+    };
+    if recording.format != RECORDING_FORMAT {
+        return Err(AppError::InvalidInput(format!(
+            "not a request recording (format `{}`)",
+            recording.format
+        )));
+    }
+    if recording.version > RECORDING_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "request recording version {} is newer than this app supports ({RECORDING_VERSION})",
+            recording.version
+        )));
+    }
+    Ok(recording)
+}
+
+/// `recording` as JSON Lines, the way a recording file holds it.
+pub fn recording_to_jsonl(recording: &Recording) -> AppResult<String> {
+    let header = RecordingHeader {
+        format: recording.format.clone(),
+        version: recording.version,
+        started_at: recording.started_at.clone(),
+    };
+    let mut text = serde_json::to_string(&header)?;
+    text.push('\n');
+    for exchange in &recording.exchanges {
+        text.push_str(&serde_json::to_string(exchange)?);
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Serves recorded responses back in order. A request that was made several times, such
+/// as a paged or polled one, gets each recorded response in turn.
+pub struct Replay {
+    exchanges: Vec<RecordedExchange>,
+    used: Vec<bool>,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Self {
+        let used = vec![false; recording.exchanges.len()];
+        Self {
+            exchanges: recording.exchanges,
+            used,
+        }
+    }
+
+    pub fn next_response(&mut self, method: &str, url: &str) -> Option<&RecordedExchange> {
+        let index = self.exchanges.iter().enumerate().position(|(index, exchange)| {
+            !self.used[index] && exchange.method.eq_ignore_ascii_case(method) && exchange.url == url
+        })?;
+        self.used[index] = true;
+        Some(&self.exchanges[index])
+    }
+}
+
+fn token_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r#"\b(gh[pousr]_[A-Za-z0-9]{20,255}|github_pat_[A-Za-z0-9_]{20,255})\b|(?i)(bearer|token) [A-Za-z0-9_.\-]{20,}|(?i)((access_token|client_secret|refresh_token)"?\s*[:=]\s*"?)[^"&\s,}]+"#,
+        )
+        .expect("token pattern is valid")
+    })
+}
+
+/// `text` with GitHub tokens, bearer credentials and OAuth secrets replaced.
+pub fn redact_tokens(text: &str) -> String {
+    token_pattern()
+        .replace_all(text, |captures: &regex::Captures<'_>| match captures.get(3) {
+            Some(key) => format!("{}{REDACTED}", key.as_str()),
+            None => REDACTED.to_string(),
+        })
+        .into_owned()
+}
+
+pub fn sanitize_headers(headers: &reqwest::header::HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| !DROPPED_HEADERS.contains(&name.as_str()))
+        .map(|(name, value)| {
+            (
+                name.as_str().to_string(),
+                redact_tokens(&String::from_utf8_lossy(value.as_bytes())),
+            )
+        })
+        .collect()
+}
+
+struct ActiveRecording {
+    path: PathBuf,
+    file: File,
+    exchange_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecorderStatus {
+    pub recording: bool,
+    /// File holding the recording; kept after `stop` so it can be attached to a report.
+    pub path: Option<String>,
+    pub exchange_count: usize,
+}
+
+/// Where GitHub traffic is being recorded to or replayed from. The app shares one,
+/// `Recorder::global`; tests make their own so they never see each other's traffic.
+pub struct Recorder {
+    active: Mutex<Option<ActiveRecording>>,
+    replaying: Mutex<Option<Replay>>,
+}
+
+static RECORDER: Recorder = Recorder::new();
+
+fn lock<T>(mutex: &Mutex<T>) -> AppResult<MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|_| AppError::Internal("Lock poisoned".into()))
+}
+
+impl Recorder {
+    pub const fn new() -> Self {
+        Self {
+            active: Mutex::new(None),
+            replaying: Mutex::new(None),
+        }
+    }
+
+    pub fn global() -> &'static Recorder {
+        &RECORDER
+    }
+
+    /// Start recording to `path`, replacing any recording in progress.
+    pub fn start(&self, path: PathBuf) -> AppResult<RecorderStatus> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(&path)?;
+        file.write_all(recording_to_jsonl(&Recording::new())?.as_bytes())?;
+        let status = RecorderStatus {
+            recording: true,
+            path: Some(path.display().to_string()),
+            exchange_count: 0,
+        };
+        *lock(&self.active)? = Some(ActiveRecording {
+            path,
+            file,
+            exchange_count: 0,
+        });
+        Ok(status)
+    }
+
+    pub fn stop(&self) -> AppResult<RecorderStatus> {
+        let Some(mut active) = lock(&self.active)?.take() else {
+            return Ok(self.status());
+        };
+        active.file.flush()?;
+        Ok(RecorderStatus {
+            recording: false,
+            path: Some(active.path.display().to_string()),
+            exchange_count: active.exchange_count,
+        })
+    }
+
+    pub fn status(&self) -> RecorderStatus {
+        match self.active.lock().ok().as_ref().and_then(|active| active.as_ref()) {
+            Some(active) => RecorderStatus {
+                recording: true,
+                path: Some(active.path.display().to_string()),
+                exchange_count: active.exchange_count,
+            },
+            None => RecorderStatus {
+                recording: false,
+                path: None,
+                exchange_count: 0,
+            },
+        }
+    }
+
+    fn is_recording(&self) -> bool {
+        self.active.lock().is_ok_and(|active| active.is_some())
+    }
+
+    /// Append an exchange as one line, so a crash keeps what was captured so far without
+    /// rewriting the file each time.
+    fn record(&self, exchange: &RecordedExchange) {
+        let Ok(mut active) = self.active.lock() else {
+            return;
+        };
+        let Some(active) = active.as_mut() else {
+            return;
+        };
+        let written = serde_json::to_string(exchange)
+            .map_err(AppError::from)
+            .and_then(|line| Ok(writeln!(active.file, "{line}")?));
+        match written {
+            Ok(()) => active.exchange_count += 1,
+            Err(e) => warn!("Failed to write request recording: {}", e),
+        }
+    }
+
+    /// Answer GitHub requests from the recording at `path` instead of the network.
+    /// Returns the number of recorded exchanges. Only debug builds replay, so a release
+    /// build always talks to GitHub.
+    pub fn start_replay(&self, path: &Path) -> AppResult<usize> {
+        if !cfg!(debug_assertions) {
+            return Err(AppError::InvalidInput(
+                "replaying recorded requests is only available in debug builds".into(),
+            ));
+        }
+        let recording = parse_recording(&std::fs::read_to_string(path)?)?;
+        let count = recording.exchanges.len();
+        *lock(&self.replaying)? = Some(Replay::new(recording));
+        Ok(count)
+    }
+
+    pub fn stop_replay(&self) {
+        if let Ok(mut replaying) = self.replaying.lock() {
+            *replaying = None;
+        }
+    }
+
+    /// The recorded answer to a request while replaying. Requests the recording does not
+    /// hold get a 404, as GitHub would give for something that is not there.
+    fn replayed_response(&self, method: &str, url: &reqwest::Url) -> Option<reqwest::Response> {
+        if !cfg!(debug_assertions) {
+            return None;
+        }
+        let mut replaying = self.replaying.lock().ok()?;
+        let replay = replaying.as_mut()?;
+        let (status, headers, body) =
+            match replay.next_response(method, &redact_tokens(url.as_str())) {
+                Some(exchange) => (
+                    exchange.status,
+                    exchange.response_headers.clone(),
+                    exchange.response_body.clone(),
+                ),
+                None => {
+                    warn!("No recorded response for {} {}", method, url);
+                    (
+                        404,
+                        BTreeMap::new(),
+                        r#"{"message":"Not in the replayed recording"}"#.to_string(),
+                    )
+                }
+            };
+
+        let mut builder = http::Response::builder().status(status).url(url.clone());
+        for (name, value) in &headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        builder.body(body).ok().map(reqwest::Response::from)
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `send` that also records the exchange while a recording is running, or answers from a
+/// replayed recording. A recorded response body is buffered and handed back unchanged.
+/// Requests reaching an owner the owner policy rules out are refused before they leave.
+pub trait RecordedSend {
+    /// Send through the app's shared [`Recorder`].
+    fn send_recorded(self) -> impl Future<Output = AppResult<reqwest::Response>> + Send;
+    fn send_via(
+        self,
+        recorder: &Recorder,
+    ) -> impl Future<Output = AppResult<reqwest::Response>> + Send;
+}
+
+impl RecordedSend for reqwest::RequestBuilder {
+    async fn send_recorded(self) -> AppResult<reqwest::Response> {
+        self.send_via(Recorder::global()).await
+    }
+
+    async fn send_via(self, recorder: &Recorder) -> AppResult<reqwest::Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+        let replayed = recorder.replayed_response(request.method().as_str(), request.url());
+        if let Some(response) = replayed {
+            return Ok(response);
+        }
+        crate::owner_policy::ensure_request_allowed(&request)?;
+        crate::rate_limit::throttle(request.url().path()).await;
+        let started = std::time::Instant::now();
+        if !recorder.is_recording() {
+            let response = client.execute(request).await;
+            crate::metrics::record_api_latency(started.elapsed());
+            return Ok(response?);
+        }
+
+        let method = request.method().to_string();
+        let url = request.url().clone();
+        let request_body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| redact_tokens(&String::from_utf8_lossy(bytes)));
+
+        let response = client.execute(request).await;
+        crate::metrics::record_api_latency(started.elapsed());
+        let response = response?;
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let response_url = response.url().clone();
+        let body = response.bytes().await?;
+
+        recorder.record(&RecordedExchange {
+            method,
+            url: redact_tokens(url.as_str()),
+            request_body,
+            status: status.as_u16(),
+            response_headers: sanitize_headers(&headers),
+            response_body: redact_tokens(&String::from_utf8_lossy(&body)),
+            recorded_at: Utc::now().to_rfc3339(),
+        });
+
+        let mut builder = http::Response::builder()
+            .status(status)
+            .version(version)
+            .url(response_url);
+        if let Some(rebuilt) = builder.headers_mut() {
+            *rebuilt = headers;
+        }
+        let rebuilt = builder
+            .body(body)
+            .expect("status, version and headers come from a valid response");
+        Ok(reqwest::Response::from(rebuilt))
+    }
+}
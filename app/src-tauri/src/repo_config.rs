@@ -0,0 +1,142 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::content_filter::ContentFilterConfig;
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewComment;
+
+/// Review policy file a repository can keep next to its docs.
+pub const REPO_CONFIG_PATH: &str = ".github/doc-review.yml";
+
+/// Which changed files are part of the review. Patterns are globs: `*` stays within a
+/// directory, `**` crosses directories, and a pattern without `/` matches at any depth.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileFilters {
+    /// Files to review; empty means every file.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl FileFilters {
+    pub fn matches(&self, path: &str) -> bool {
+        let hit = |patterns: &[String]| patterns.iter().any(|p| glob_matches(p, path));
+        (self.include.is_empty() || hit(&self.include)) && !hit(&self.exclude)
+    }
+}
+
+/// Contents of `.github/doc-review.yml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepoReviewConfig {
+    pub files: FileFilters,
+    /// Items every reviewer should tick off before submitting.
+    pub checklist: Vec<String>,
+    /// When set, every comment must carry one of these categories before submission.
+    pub required_categories: Vec<String>,
+    /// Added to the banned phrases from local settings.
+    pub banned_phrases: Vec<String>,
+}
+
+/// Repository policy merged over local settings.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EffectiveReviewConfig {
+    /// Path of the repository file the policy came from; `None` when the repo has none.
+    pub source: Option<String>,
+    pub files: FileFilters,
+    pub checklist: Vec<String>,
+    pub required_categories: Vec<String>,
+    pub content_filter: ContentFilterConfig,
+}
+
+/// Parse the file's YAML. An empty file, or one holding only comments, means no policy.
+pub fn parse_repo_config(text: &str) -> AppResult<RepoReviewConfig> {
+    let config = serde_yaml::from_str::<Option<RepoReviewConfig>>(text)
+        .map_err(|e| AppError::InvalidInput(format!("{REPO_CONFIG_PATH}: {e}")))?
+        .unwrap_or_default();
+    for category in &config.required_categories {
+        crate::categories::validate_category(category)
+            .map_err(|e| AppError::InvalidInput(format!("{REPO_CONFIG_PATH}: {e}")))?;
+    }
+    Ok(config)
+}
+
+/// Apply the repository's policy on top of local settings. Banned phrases from both
+/// sides apply; everything else only comes from the repository.
+pub fn merge(local: ContentFilterConfig, repo: Option<&RepoReviewConfig>) -> EffectiveReviewConfig {
+    let Some(repo) = repo else {
+        return EffectiveReviewConfig {
+            content_filter: local,
+            ..EffectiveReviewConfig::default()
+        };
+    };
+
+    let mut content_filter = local;
+    for phrase in &repo.banned_phrases {
+        let known = content_filter
+            .banned_phrases
+            .iter()
+            .any(|p| p.trim().eq_ignore_ascii_case(phrase.trim()));
+        if !known && !phrase.trim().is_empty() {
+            content_filter.banned_phrases.push(phrase.clone());
+        }
+    }
+
+    EffectiveReviewConfig {
+        source: Some(REPO_CONFIG_PATH.to_string()),
+        files: repo.files.clone(),
+        checklist: repo.checklist.clone(),
+        required_categories: repo.required_categories.clone(),
+        content_filter,
+    }
+}
+
+/// Pending comments that do not carry one of the required categories.
+pub fn comments_missing_category<'a>(
+    required: &[String],
+    comments: &'a [ReviewComment],
+) -> Vec<&'a ReviewComment> {
+    if required.is_empty() {
+        return Vec::new();
+    }
+    comments
+        .iter()
+        .filter(|c| !c.deleted)
+        .filter(|c| {
+            !c.category
+                .as_deref()
+                .is_some_and(|category| required.iter().any(|r| r == category))
+        })
+        .collect()
+}
+
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim().trim_start_matches('/');
+    if pattern.is_empty() {
+        return false;
+    }
+    let mut regex = String::from("^");
+    if !pattern.contains('/') {
+        regex.push_str("(?:.*/)?");
+    }
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    // A directory pattern covers everything below it
+    regex.push_str("(?:/.*)?$");
+    Regex::new(&regex).is_ok_and(|re| re.is_match(path))
+}
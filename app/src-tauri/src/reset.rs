@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth;
+use crate::error::{AppError, AppResult};
+use crate::i18n::{t, tf, Msg};
+use crate::pr_cache::PrCache;
+use crate::recorder;
+use crate::review_storage::ReviewStorage;
+
+/// What `reset_app_data` removes. Installed analyzer plugins are never touched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResetOptions {
+    /// Sign out of every account and forget their stored GitHub tokens.
+    pub token: bool,
+    /// Cached pull request data: rendered markdown, visit times, in-memory listings and
+    /// the offline copies of opened PRs.
+    pub cached_data: bool,
+    /// Local reviews with their drafts, trash, bookmarks, parked submissions and
+    /// turnaround history.
+    pub local_reviews: bool,
+    /// Review logs, the crash log and request recordings.
+    pub logs: bool,
+    pub settings: bool,
+}
+
+impl ResetOptions {
+    /// Remove all traces of the app.
+    pub fn everything() -> Self {
+        Self {
+            token: true,
+            cached_data: true,
+            local_reviews: true,
+            logs: true,
+            settings: true,
+        }
+    }
+
+    /// Log out but keep local drafts, logs and settings.
+    pub fn keep_drafts() -> Self {
+        Self {
+            token: true,
+            cached_data: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// What will be removed, for the confirmation dialog.
+    pub fn describe(&self) -> String {
+        if *self == Self::everything() {
+            return t(Msg::ResetEverything).to_string();
+        }
+        if *self == Self::keep_drafts() {
+            return t(Msg::ResetKeepDrafts).to_string();
+        }
+
+        let parts = [
+            (self.token, Msg::ResetToken),
+            (self.cached_data, Msg::ResetCachedData),
+            (self.local_reviews, Msg::ResetLocalReviews),
+            (self.logs, Msg::ResetLogs),
+            (self.settings, Msg::ResetSettings),
+        ];
+        let chosen = parts
+            .into_iter()
+            .filter(|(selected, _)| *selected)
+            .map(|(_, part)| t(part))
+            .collect::<Vec<_>>();
+        tf(Msg::ResetChosen, &[&chosen.join(t(Msg::ListSeparator))])
+    }
+}
+
+/// Remove the chosen kinds of data. Database rows are deleted and the file compacted, so
+/// nothing removed lingers on disk.
+pub async fn reset_app_data(
+    storage: &ReviewStorage,
+    data_dir: &Path,
+    options: &ResetOptions,
+) -> AppResult<()> {
+    if options.is_empty() {
+        return Err(AppError::InvalidInput(
+            "choose at least one kind of data to reset".into(),
+        ));
+    }
+
+    if options.token {
+        auth::forget_all_accounts()?;
+    }
+    if options.cached_data {
+        auth::clear_listing_caches();
+        storage.clear_cached_data()?;
+        PrCache::new(data_dir).clear()?;
+    }
+    if options.local_reviews {
+        storage.clear_local_reviews()?;
+    }
+    if options.settings {
+        storage.clear_settings()?;
+    }
+    if options.logs {
+        recorder::Recorder::global().stop()?;
+        storage.clear_logs()?;
+        let recordings = data_dir.join(recorder::RECORDINGS_DIR);
+        if recordings.exists() {
+            std::fs::remove_dir_all(&recordings)?;
+        }
+    }
+    if options.cached_data || options.local_reviews || options.settings {
+        storage.compact()?;
+    }
+
+    tracing::info!(?options, "App data reset");
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+/// GitHub search returns at most this many results for one query.
+pub const MAX_EXPORTED_PRS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    Review,
+    Comment,
+}
+
+impl ActivityKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Review => "review",
+            Self::Comment => "comment",
+        }
+    }
+}
+
+/// A review I submitted or a comment I posted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityRecord {
+    pub kind: ActivityKind,
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    pub pr_title: String,
+    /// Review state such as `APPROVED`; `None` for comments.
+    pub state: Option<String>,
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub url: String,
+    pub created_at: String,
+    pub body: String,
+}
+
+/// What `cmd_export_review_activity` wrote.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityExport {
+    pub path: String,
+    pub reviews: usize,
+    pub comments: usize,
+}
+
+/// `since` as a date (`2024-01-01`) or RFC 3339 timestamp, normalized to UTC with a `Z`
+/// like GitHub's own timestamps so the two compare as strings.
+pub fn normalize_since(since: &str) -> AppResult<String> {
+    let since = since.trim();
+    let instant = match NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        Ok(date) => date.and_hms_opt(0, 0, 0).map(|midnight| midnight.and_utc()),
+        Err(_) => DateTime::parse_from_rfc3339(since)
+            .ok()
+            .map(|parsed| parsed.with_timezone(&Utc)),
+    };
+    instant
+        .map(|instant| instant.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "since must be a date like 2024-01-01 or an RFC 3339 timestamp, got {since}"
+            ))
+        })
+}
+
+/// PRs in `org` I reviewed that changed on or after `since` (normalized).
+pub fn search_query(org: &str, since: &str) -> String {
+    format!("is:pr org:{org} reviewed-by:@me updated:>={since}")
+}
+
+/// Oldest first, then by PR.
+pub fn sort_records(records: &mut [ActivityRecord]) {
+    records.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| (&a.owner, &a.repo, a.pr_number).cmp(&(&b.owner, &b.repo, b.pr_number)))
+    });
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn to_csv(records: &[ActivityRecord]) -> String {
+    let mut csv = String::from("kind,owner,repo,pr_number,pr_title,state,path,line,url,created_at,body\n");
+    for record in records {
+        let fields = [
+            record.kind.as_str().to_string(),
+            record.owner.clone(),
+            record.repo.clone(),
+            record.pr_number.to_string(),
+            record.pr_title.clone(),
+            record.state.clone().unwrap_or_default(),
+            record.path.clone().unwrap_or_default(),
+            record.line.map(|line| line.to_string()).unwrap_or_default(),
+            record.url.clone(),
+            record.created_at.clone(),
+            record.body.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// File formats `cmd_export_review_activity` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityExportFormat {
+    Json,
+    Csv,
+}
+
+impl ActivityExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Name offered in the save dialog for an export of `org`.
+pub fn export_file_name(org: &str, format: ActivityExportFormat) -> String {
+    format!("{org}-review-activity.{}", format.extension())
+}
+
+pub fn render_export(
+    records: &[ActivityRecord],
+    format: ActivityExportFormat,
+) -> AppResult<String> {
+    Ok(match format {
+        ActivityExportFormat::Csv => to_csv(records),
+        ActivityExportFormat::Json => serde_json::to_string_pretty(records)?,
+    })
+}
+
+/// Describe an export of `records` written to `path`.
+pub fn export_summary(records: &[ActivityRecord], path: &Path) -> ActivityExport {
+    let reviews = records.iter().filter(|r| r.kind == ActivityKind::Review).count();
+    ActivityExport {
+        path: path.display().to_string(),
+        reviews,
+        comments: records.len() - reviews,
+    }
+}
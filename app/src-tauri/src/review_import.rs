@@ -0,0 +1,388 @@
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::i18n::{all_translations, Msg};
+use crate::models::{ensure_side_exists, PullRequestFile, Suggestion};
+use crate::review_storage::{ReviewMetadata, ReviewStorage};
+
+const SIDES: &[&str] = &["LEFT", "RIGHT"];
+
+/// A comment to add to a local review. Exports from `cmd_export_review` read as-is;
+/// only `file_path` and `body` are required, so scripts can write these by hand.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ImportedComment {
+    /// Id in the file; only used to rebuild reply threads.
+    #[serde(default)]
+    pub id: Option<i64>,
+    pub file_path: String,
+    /// `0` for a comment on the whole file.
+    #[serde(default)]
+    pub line_number: u64,
+    #[serde(default = "default_side")]
+    pub side: String,
+    pub body: String,
+    /// Commit the line number refers to; the review's commit when absent.
+    #[serde(default)]
+    pub commit_id: Option<String>,
+    #[serde(default)]
+    pub in_reply_to_id: Option<i64>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub general: bool,
+    #[serde(default)]
+    pub suggestion: Option<Suggestion>,
+}
+
+fn default_side() -> String {
+    "RIGHT".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportDocument {
+    comments: Vec<ImportedComment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewImport {
+    pub review: ReviewMetadata,
+    pub imported: usize,
+}
+
+/// Comments of a JSON review export. Its `review` section is ignored; the comments go
+/// to whichever PR the import is for.
+pub fn parse_review_json(json: &str) -> AppResult<Vec<ImportedComment>> {
+    let document: ImportDocument = serde_json::from_str(json)?;
+    Ok(document.comments)
+}
+
+/// How a punch list for `cmd_import_comments` is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PunchListFormat {
+    /// Rows of path, line and body, optionally under a header row naming the columns.
+    Csv,
+    /// Open task list items such as `- [ ] docs/a.md:12 body`, or items under a heading
+    /// naming the file in backticks, as the task list export writes them.
+    Markdown,
+}
+
+impl PunchListFormat {
+    /// File extensions offered first when picking a punch list of this format.
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            PunchListFormat::Csv => &["csv"],
+            PunchListFormat::Markdown => &["md", "markdown"],
+        }
+    }
+}
+
+const PATH_COLUMNS: &[&str] = &["path", "file", "file_path"];
+const LINE_COLUMNS: &[&str] = &["line", "line_number"];
+const BODY_COLUMNS: &[&str] = &["body", "comment"];
+
+/// Comments drafted outside the app, e.g. in a spreadsheet during a review meeting.
+pub fn parse_punch_list(content: &str, format: PunchListFormat) -> AppResult<Vec<ImportedComment>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    match format {
+        PunchListFormat::Csv => parse_csv_punch_list(content),
+        PunchListFormat::Markdown => parse_markdown_punch_list(content),
+    }
+}
+
+fn punch_list_comment(path: &str, line_number: u64, body: &str) -> ImportedComment {
+    ImportedComment {
+        id: None,
+        file_path: path.to_string(),
+        line_number,
+        side: default_side(),
+        body: body.to_string(),
+        commit_id: None,
+        in_reply_to_id: None,
+        category: None,
+        general: false,
+        suggestion: None,
+    }
+}
+
+/// `12` or `L12`; blank for a comment on the whole file.
+fn parse_line_number(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(0);
+    }
+    value.strip_prefix('L').unwrap_or(value).parse().ok()
+}
+
+/// Records of RFC 4180 CSV: quoted fields may hold commas, newlines and doubled quotes.
+fn csv_records(content: &str) -> AppResult<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => record.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(AppError::InvalidInput("the CSV ends inside a quoted field".to_string()));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|record| record.iter().any(|field| !field.trim().is_empty()));
+    Ok(records)
+}
+
+fn parse_csv_punch_list(content: &str) -> AppResult<Vec<ImportedComment>> {
+    let records = csv_records(content)?;
+    let Some(first) = records.first() else {
+        return Ok(Vec::new());
+    };
+    let find = |names: &[&str]| {
+        first
+            .iter()
+            .position(|cell| names.contains(&cell.trim().to_ascii_lowercase().as_str()))
+    };
+
+    // Without a header row the columns are path, line and body
+    let (path, body) = (find(PATH_COLUMNS), find(BODY_COLUMNS));
+    let has_header = path.is_some() && body.is_some();
+    let (path, line, body, side, category) = if has_header {
+        let (side, category) = (find(&["side"]), find(&["category"]));
+        (path.unwrap_or(0), find(LINE_COLUMNS), body.unwrap_or(0), side, category)
+    } else {
+        (0, Some(1), 2, None, None)
+    };
+    let rows = records.iter().enumerate().skip(usize::from(has_header));
+
+    let mut comments = Vec::new();
+    for (index, record) in rows {
+        let row = index + 1;
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .map(|value| value.trim())
+                .unwrap_or("")
+        };
+        let file_path = cell(Some(path));
+        if file_path.is_empty() {
+            return Err(AppError::InvalidInput(format!("row {row} has no file path")));
+        }
+        let line_number = parse_line_number(cell(line)).ok_or_else(|| {
+            AppError::InvalidInput(format!("row {row}: `{}` is not a line number", cell(line)))
+        })?;
+        // Undo the guard exports put in front of text a spreadsheet would run as a formula
+        let body = cell(Some(body));
+        let body = match body.strip_prefix('\'') {
+            Some(rest) if rest.starts_with(['=', '+', '-', '@']) => rest,
+            _ => body,
+        };
+
+        let mut comment = punch_list_comment(file_path, line_number, body);
+        if !cell(side).is_empty() {
+            comment.side = cell(side).to_ascii_uppercase();
+        }
+        comment.category = Some(cell(category).to_string()).filter(|c| !c.is_empty());
+        comments.push(comment);
+    }
+    Ok(comments)
+}
+
+fn task_item_regex() -> &'static Regex {
+    static ITEM: OnceLock<Regex> = OnceLock::new();
+    ITEM.get_or_init(|| {
+        Regex::new(r"^\s*[-*+]\s+\[([ xX])\]\s+(.*)$").expect("task item pattern is valid")
+    })
+}
+
+fn file_heading_regex() -> &'static Regex {
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    HEADING.get_or_init(|| {
+        Regex::new(r"^#{1,6}\s+`([^`]+)`\s*$").expect("file heading pattern is valid")
+    })
+}
+
+/// `L12`, `[L12](permalink)` or the export's "whole file", at the start of an item.
+fn line_reference_regex() -> &'static Regex {
+    static REFERENCE: OnceLock<Regex> = OnceLock::new();
+    REFERENCE.get_or_init(|| {
+        Regex::new(r"^(?:\[L(\d+)\]\([^)]*\)|L(\d+)\b)").expect("line reference pattern is valid")
+    })
+}
+
+fn category_regex() -> &'static Regex {
+    static CATEGORY: OnceLock<Regex> = OnceLock::new();
+    CATEGORY.get_or_init(|| {
+        Regex::new(r"^\*\*([\w-]+)\*\*:?\s*").expect("category pattern is valid")
+    })
+}
+
+/// Split `docs/a.md:12 body` or `` `docs/a.md:12` body `` into path, line and the rest.
+fn split_location(text: &str) -> Option<(&str, u64, &str)> {
+    let (location, rest) = match text.strip_prefix('`') {
+        Some(quoted) => quoted.split_once('`')?,
+        None => text.split_once(char::is_whitespace).unwrap_or((text, "")),
+    };
+    let location = location.trim_end_matches(':');
+    let (path, line) = match location.rsplit_once(':') {
+        Some((path, line)) => (path, parse_line_number(line)?),
+        None => (location, 0),
+    };
+    Some((path, line, rest)).filter(|(path, _, _)| !path.is_empty())
+}
+
+fn parse_markdown_punch_list(content: &str) -> AppResult<Vec<ImportedComment>> {
+    let mut comments: Vec<ImportedComment> = Vec::new();
+    let mut current_file: Option<String> = None;
+    // Whether the last item was imported, so its indented lines continue its body
+    let mut continuing = false;
+    let mut blank_lines = 0;
+
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        if let Some(caps) = file_heading_regex().captures(line) {
+            current_file = Some(caps[1].to_string());
+            continuing = false;
+            continue;
+        }
+        if line.starts_with('#') {
+            current_file = None;
+            continuing = false;
+            continue;
+        }
+        let Some(caps) = task_item_regex().captures(line) else {
+            if line.trim().is_empty() {
+                blank_lines += 1;
+            } else if let (true, Some(text), Some(comment)) =
+                (continuing, line.strip_prefix("  "), comments.last_mut())
+            {
+                comment.body.push_str(&"\n".repeat(blank_lines + 1));
+                comment.body.push_str(text);
+                blank_lines = 0;
+            } else {
+                continuing = false;
+            }
+            continue;
+        };
+        blank_lines = 0;
+        // Ticked items were dealt with in the meeting
+        continuing = &caps[1] == " ";
+        if !continuing {
+            continue;
+        }
+
+        let text = caps[2].trim();
+        let (file_path, line_number, rest) = match &current_file {
+            Some(path) => match line_reference_regex().captures(text) {
+                Some(reference) => {
+                    let line = reference.get(1).or(reference.get(2)).map_or("", |m| m.as_str());
+                    (path.as_str(), line.parse().unwrap_or(0), &text[reference[0].len()..])
+                }
+                None => {
+                    // The list may have been exported while another locale was active
+                    let rest = all_translations(Msg::TasklistFileComment)
+                        .find_map(|label| text.strip_prefix(label))
+                        .unwrap_or(text);
+                    (path.as_str(), 0, rest)
+                }
+            },
+            None => split_location(text).ok_or_else(|| {
+                AppError::InvalidInput(format!(
+                    "line {number}: expected `path:line` before the comment"
+                ))
+            })?,
+        };
+        let rest = rest.trim_start_matches([':', '-', '\u{2013}', '\u{2014}', ' ']);
+        let (category, body) = match category_regex().captures(rest) {
+            Some(caps) => (Some(caps[1].to_string()), &rest[caps[0].len()..]),
+            None => (None, rest),
+        };
+        if body.trim().is_empty() {
+            return Err(AppError::InvalidInput(format!("line {number} has no comment")));
+        }
+
+        let mut comment = punch_list_comment(file_path, line_number, body.trim());
+        comment.category = category;
+        comments.push(comment);
+    }
+    Ok(comments)
+}
+
+/// Check every comment before any is stored, so a bad file imports nothing. Each comment
+/// must be on a file the PR changes; general comments only record where they were
+/// written, so theirs may be any path.
+pub fn validate_comments(comments: &[ImportedComment], files: &[PullRequestFile]) -> AppResult<()> {
+    if comments.is_empty() {
+        return Err(AppError::InvalidInput("the file has no comments to import".to_string()));
+    }
+    let mut unknown = BTreeSet::new();
+    for (index, comment) in comments.iter().enumerate() {
+        let number = index + 1;
+        if comment.body.trim().is_empty() {
+            return Err(AppError::InvalidInput(format!("comment {number} has no body")));
+        }
+        crate::settings::ensure_one_of("side", &comment.side, SIDES)?;
+        if let Some(category) = comment.category.as_deref() {
+            crate::categories::validate_category(category)?;
+        }
+        if let Some(suggestion) = &comment.suggestion {
+            suggestion.validate_for_side(&comment.side)?;
+        }
+        if comment.general {
+            continue;
+        }
+        if !files.iter().any(|file| file.path == comment.file_path) {
+            unknown.insert(comment.file_path.as_str());
+        }
+        ensure_side_exists(files, &comment.file_path, &comment.side, comment.line_number)?;
+    }
+    if !unknown.is_empty() {
+        let paths: Vec<&str> = unknown.into_iter().collect();
+        return Err(AppError::InvalidInput(format!(
+            "not changed in this pull request: {}",
+            paths.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Add validated comments to the local review of `owner/repo#pr_number`, starting one
+/// at `head_sha` when there is none. Replies whose parent is not in the file are added
+/// as top-level comments. Either every comment is added or none is.
+pub async fn import_comments(
+    storage: &ReviewStorage,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    head_sha: &str,
+    comments: &[ImportedComment],
+) -> AppResult<ReviewImport> {
+    let (review, ids) = storage
+        .import_comments(owner, repo, pr_number, head_sha, None, comments)
+        .await?;
+
+    Ok(ReviewImport {
+        review,
+        imported: ids.len(),
+    })
+}
@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::{CommentTemplate, ReviewStorage};
+use crate::settings::{self, ensure_one_of};
+
+pub const REVIEW_PROFILES_KEY: &str = "review_profiles";
+
+/// Built-in checks a profile can turn on, named after the command that runs each.
+pub const BUILT_IN_ANALYZERS: &[&str] = &[
+    "acronyms",
+    "alt_text",
+    "front_matter",
+    "markdown_structure",
+    "diagrams",
+    "spec",
+    "headings",
+    "snippets",
+    "todos",
+    "pr_template",
+];
+
+/// One pass over a review, e.g. accessibility or technical accuracy, and the checks,
+/// checklist and comment templates that go with it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewProfile {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Built-in checks to run, from `BUILT_IN_ANALYZERS`.
+    #[serde(default)]
+    pub analyzers: Vec<String>,
+    /// Analyzer plugins to run, by name. Names of plugins that are not installed are kept,
+    /// so a profile survives reinstalling one.
+    #[serde(default)]
+    pub plugins: Vec<String>,
+    /// Items to tick off before submitting, listed after the repository's own checklist.
+    #[serde(default)]
+    pub checklist: Vec<String>,
+    /// Comment templates offered, by name; empty offers the whole library.
+    #[serde(default)]
+    pub templates: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ReviewProfileConfig {
+    pub profiles: Vec<ReviewProfile>,
+}
+
+fn profile(name: &str, description: &str, analyzers: &[&str], checklist: &[&str]) -> ReviewProfile {
+    ReviewProfile {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        analyzers: analyzers.iter().map(|a| a.to_string()).collect(),
+        plugins: Vec::new(),
+        checklist: checklist.iter().map(|c| c.to_string()).collect(),
+        templates: Vec::new(),
+    }
+}
+
+impl Default for ReviewProfileConfig {
+    fn default() -> Self {
+        Self {
+            profiles: vec![
+                profile(
+                    "Accessibility pass",
+                    "Images, headings and structure for readers using assistive technology",
+                    &["alt_text", "headings", "markdown_structure", "acronyms"],
+                    &[
+                        "Every image has alt text that says what it shows",
+                        "Link text makes sense out of context",
+                    ],
+                ),
+                profile(
+                    "Technical accuracy pass",
+                    "Code samples, API specs and diagrams match what ships",
+                    &["snippets", "spec", "diagrams", "todos"],
+                    &["Code samples run as written", "Version numbers and limits are current"],
+                ),
+                profile(
+                    "Style pass",
+                    "Voice, terminology and formatting",
+                    &["headings", "acronyms", "front_matter", "pr_template"],
+                    &["Terminology follows the style guide"],
+                ),
+            ],
+        }
+    }
+}
+
+impl ReviewProfileConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        for (index, profile) in self.profiles.iter().enumerate() {
+            let name = profile.name.trim();
+            if name.is_empty() {
+                return Err(AppError::InvalidInput(format!(
+                    "review profile {} has an empty name",
+                    index + 1
+                )));
+            }
+            if self.profiles[..index]
+                .iter()
+                .any(|other| other.name.trim().eq_ignore_ascii_case(name))
+            {
+                return Err(AppError::InvalidInput(format!(
+                    "review profile `{name}` is defined more than once"
+                )));
+            }
+            for analyzer in &profile.analyzers {
+                ensure_one_of("analyzers", analyzer, BUILT_IN_ANALYZERS)?;
+            }
+            if profile.checklist.iter().any(|item| item.trim().is_empty()) {
+                return Err(AppError::InvalidInput(format!(
+                    "review profile `{name}` has an empty checklist item"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The profile called `name`, ignoring case as names are unique that way.
+    pub fn find(&self, name: &str) -> AppResult<&ReviewProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name.trim().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| AppError::InvalidInput(format!("unknown review profile `{name}`")))
+    }
+}
+
+/// The profile used on a review, with its templates looked up in the library.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ActiveProfile {
+    pub profile: ReviewProfile,
+    pub templates: Vec<CommentTemplate>,
+    /// Template names the profile lists that are not in the library.
+    pub missing_templates: Vec<String>,
+}
+
+/// Pair a profile with the library templates it offers.
+pub fn resolve(profile: ReviewProfile, library: Vec<CommentTemplate>) -> ActiveProfile {
+    if profile.templates.is_empty() {
+        return ActiveProfile {
+            profile,
+            templates: library,
+            missing_templates: Vec::new(),
+        };
+    }
+    let templates = library
+        .into_iter()
+        .filter(|template| {
+            profile
+                .templates
+                .iter()
+                .any(|name| name.trim().eq_ignore_ascii_case(&template.name))
+        })
+        .collect::<Vec<_>>();
+    let missing_templates = profile
+        .templates
+        .iter()
+        .filter(|name| {
+            !templates
+                .iter()
+                .any(|template| name.trim().eq_ignore_ascii_case(&template.name))
+        })
+        .cloned()
+        .collect();
+    ActiveProfile {
+        profile,
+        templates,
+        missing_templates,
+    }
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<ReviewProfileConfig> {
+    settings::load(storage, REVIEW_PROFILES_KEY)
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &ReviewProfileConfig) -> AppResult<()> {
+    config.validate()?;
+    settings::save(storage, REVIEW_PROFILES_KEY, config)
+}
+
+/// The profile chosen for a PR. A choice whose profile has since been deleted or renamed
+/// counts as none.
+pub fn active_profile(
+    storage: &ReviewStorage,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+) -> AppResult<Option<ActiveProfile>> {
+    let Some(name) = storage.get_active_profile(owner, repo, pr_number)? else {
+        return Ok(None);
+    };
+    let Ok(profile) = load_config(storage)?.find(&name).cloned() else {
+        return Ok(None);
+    };
+    Ok(Some(resolve(profile, storage.list_comment_templates()?)))
+}
+
+/// Whether the built-in `analyzer` runs on a PR: every check runs until a profile is
+/// chosen, then only the ones it lists.
+pub fn analyzer_enabled(
+    storage: &ReviewStorage,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    analyzer: &str,
+) -> AppResult<bool> {
+    Ok(active_profile(storage, owner, repo, pr_number)?
+        .is_none_or(|active| active.profile.analyzers.iter().any(|a| a == analyzer)))
+}
+
+/// Add the checklist of the profile active on a PR after `checklist`, skipping items
+/// already on it.
+pub fn extend_checklist(
+    storage: &ReviewStorage,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    checklist: &mut Vec<String>,
+) -> AppResult<()> {
+    let Some(active) = active_profile(storage, owner, repo, pr_number)? else {
+        return Ok(());
+    };
+    for item in active.profile.checklist {
+        let known = checklist
+            .iter()
+            .any(|existing| existing.trim().eq_ignore_ascii_case(item.trim()));
+        if !known {
+            checklist.push(item);
+        }
+    }
+    Ok(())
+}
+
+/// Make `name` the profile used on a PR, or clear the choice with `None`.
+pub fn set_active_profile(
+    storage: &ReviewStorage,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    name: Option<&str>,
+) -> AppResult<Option<ActiveProfile>> {
+    let profile = match name {
+        Some(name) => Some(load_config(storage)?.find(name)?.clone()),
+        None => None,
+    };
+    storage.set_active_profile(owner, repo, pr_number, profile.as_ref().map(|p| p.name.as_str()))?;
+    profile
+        .map(|profile| Ok(resolve(profile, storage.list_comment_templates()?)))
+        .transpose()
+}
@@ -0,0 +1,95 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{t, tf, Msg};
+use crate::models::PullRequestDetail;
+
+/// A changed file as it stood when the review was submitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    pub path: String,
+    /// GitHub's file status, e.g. `added`, `modified` or `renamed`.
+    pub status: String,
+    pub additions: u32,
+    pub deletions: u32,
+    pub previous_filename: Option<String>,
+}
+
+/// What the pull request looked like when the review went out, written into the review
+/// log so it still makes sense once the PR has moved on or GitHub is out of reach.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewSnapshot {
+    pub title: String,
+    pub head_sha: String,
+    pub base_sha: String,
+    pub files: Vec<SnapshotFile>,
+    /// RFC 3339 UTC.
+    pub captured_at: String,
+}
+
+impl ReviewSnapshot {
+    pub fn from_detail(detail: &PullRequestDetail) -> Self {
+        Self {
+            title: detail.title.clone(),
+            head_sha: detail.head_sha.clone(),
+            base_sha: detail.base_sha.clone(),
+            files: detail
+                .files
+                .iter()
+                .map(|file| SnapshotFile {
+                    path: file.path.clone(),
+                    status: file.status.clone(),
+                    additions: file.additions,
+                    deletions: file.deletions,
+                    previous_filename: file.previous_filename.clone(),
+                })
+                .collect(),
+            captured_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Lines added and removed across all files.
+    pub fn totals(&self) -> (u64, u64) {
+        self.files.iter().fold((0, 0), |(added, removed), file| {
+            (added + u64::from(file.additions), removed + u64::from(file.deletions))
+        })
+    }
+
+    /// The snapshot as `# ` comment lines for the top of a review log.
+    pub fn log_lines(&self) -> String {
+        let (added, removed) = self.totals();
+        let mut lines = format!("# {}\n", t(Msg::LogSnapshot));
+        lines.push_str(&format!("#   {}: {}\n", t(Msg::LogTitle), self.title));
+        lines.push_str(&format!("#   {}: {}\n", t(Msg::LogCommit), self.head_sha));
+        lines.push_str(&format!("#   {}: {}\n", t(Msg::LogBase), self.base_sha));
+        lines.push_str(&format!(
+            "#   {}\n",
+            tf(Msg::LogDiffstat, &[&self.files.len(), &added, &removed])
+        ));
+        for file in &self.files {
+            let path = match &file.previous_filename {
+                Some(previous) if *previous != file.path => format!("{previous} -> {}", file.path),
+                _ => file.path.clone(),
+            };
+            lines.push_str(&format!(
+                "#     {} {} (+{} -{})\n",
+                status_letter(&file.status),
+                path,
+                file.additions,
+                file.deletions
+            ));
+        }
+        lines
+    }
+}
+
+/// One-letter status in the style of `git diff --name-status`.
+fn status_letter(status: &str) -> char {
+    match status {
+        "added" => 'A',
+        "removed" => 'D',
+        "renamed" => 'R',
+        "copied" => 'C',
+        _ => 'M',
+    }
+}
@@ -1,12 +1,26 @@
 use crate::error::{AppError, AppResult};
 use crate::auth::require_token;
+use crate::comment_export::SubmissionMode;
+use crate::i18n::{t, tf, Msg};
+use crate::models::{FileReviewProgress, Suggestion};
+use crate::quick_comments::{QuickCommentTemplate, QUICK_COMMENTS_SAVED_KEY};
+use crate::recorder::RecordedSend;
+use crate::review_import::ImportedComment;
+use crate::review_snapshot::ReviewSnapshot;
+use crate::second_pass::ReviewedCommit;
+use crate::time_format::display_time;
 use chrono::Utc;
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use tokio::fs;
 
+/// Largest pseudo pull request number for a local folder, matching GitHub's 32-bit numbers.
+/// Number 1 is left to reviews saved before each folder had its own number.
+const LOCAL_MAX_NUMBER: u64 = i32::MAX as u64;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewComment {
     pub id: i64,
@@ -22,6 +36,15 @@ pub struct ReviewComment {
     pub updated_at: String,
     pub deleted: bool,
     pub in_reply_to_id: Option<i64>,
+    /// Reviewer-assigned category such as `blocking` or `nit`; see `categories`.
+    pub category: Option<String>,
+    /// Posted in the PR conversation rather than on `file_path`/`line_number`, which only
+    /// record where it was written.
+    #[serde(default)]
+    pub general: bool,
+    /// Replacement text proposed for the lines this comment is on; see `Suggestion`.
+    #[serde(default)]
+    pub suggestion: Option<Suggestion>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +57,111 @@ pub struct ReviewMetadata {
     pub local_folder: Option<String>,
     pub created_at: String,
     pub log_file_index: i32,
+    /// Deadline for submitting the review, RFC 3339 UTC.
+    #[serde(default)]
+    pub due_at: Option<String>,
+}
+
+/// When a review was requested from me and when I submitted it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnaroundRecord {
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    /// `None` when the PR's timeline had no review request for me, e.g. unrequested reviews.
+    pub requested_at: Option<String>,
+    pub submitted_at: String,
+}
+
+/// One counted event of the opt-in usage metrics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub kind: String,
+    pub occurred_at: String,
+    /// How long it took, for timed events such as API requests.
+    pub duration_ms: Option<u64>,
+}
+
+/// A cleared or abandoned local review kept for a while so it can be restored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedReview {
+    pub id: i64,
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    pub commit_id: String,
+    pub body: Option<String>,
+    pub local_folder: Option<String>,
+    pub created_at: String,
+    /// `cleared`, `abandoned`, `deleted_on_github`, `merged` or `closed`.
+    pub reason: String,
+    pub trashed_at: String,
+    pub comment_count: u64,
+}
+
+/// A comment pinned on a PR so it is easy to find again. `source` says whether
+/// `comment_id` is a GitHub comment id or a local review comment id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    /// `github` or `local`.
+    pub source: String,
+    pub comment_id: i64,
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+pub const BOOKMARK_SOURCES: &[&str] = &["github", "local"];
+
+/// A saved comment, e.g. "Please add a test", to insert while reviewing. The body takes
+/// the placeholders of quick comments; see `quick_comments::fill_placeholders`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentTemplate {
+    pub id: i64,
+    pub name: String,
+    pub body: String,
+    /// Category given to comments made from it, e.g. `nit`.
+    pub category: Option<String>,
+    /// Id the UI binds a key to when the template is also a quick comment.
+    #[serde(default)]
+    pub quick_key: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl CommentTemplate {
+    pub fn validate(&self) -> AppResult<()> {
+        validate_comment_template(&self.name, &self.body, self.category.as_deref()).map(|_| ())
+    }
+}
+
+/// What a comment's line looked like when it was written, so the finding can be checked
+/// against the content even after a force-push rewrites the commit away.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentAnchor {
+    /// Git blob SHA of the commented file at the comment's commit.
+    pub blob_sha: String,
+    /// SHA-256 of the commented line's text; `None` for comments on the whole file.
+    pub line_hash: Option<String>,
+}
+
+/// A submission cut short because GitHub rejected the token, kept until it can be resumed
+/// after signing in again as the same GitHub user. One per PR; parking again replaces it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParkedSubmission {
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    /// The pending GitHub review being submitted; `None` for a local review.
+    pub review_id: Option<u64>,
+    pub event: Option<String>,
+    pub body: Option<String>,
+    pub mode: Option<SubmissionMode>,
+    /// GitHub login the submission was made as.
+    pub login: String,
+    pub parked_at: String,
 }
 
 pub struct ReviewStorage {
@@ -71,6 +199,12 @@ impl ReviewStorage {
             "ALTER TABLE review_metadata ADD COLUMN local_folder TEXT",
             [],
         );
+
+        // Migration: Add due_at column if it doesn't exist
+        let _ = conn.execute(
+            "ALTER TABLE review_metadata ADD COLUMN due_at TEXT",
+            [],
+        );
         
         conn.execute(
             "CREATE TABLE IF NOT EXISTS review_comments (
@@ -104,13 +238,270 @@ impl ReviewStorage {
             "ALTER TABLE review_comments ADD COLUMN in_reply_to_id INTEGER",
             [],
         );
+
+        // Migration: Add category column if it doesn't exist
+        let _ = conn.execute(
+            "ALTER TABLE review_comments ADD COLUMN category TEXT",
+            [],
+        );
+
+        // Migration: Add general column if it doesn't exist
+        let _ = conn.execute(
+            "ALTER TABLE review_comments ADD COLUMN general INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+
+        // Migration: Add suggestion column (JSON) if it doesn't exist
+        let _ = conn.execute(
+            "ALTER TABLE review_comments ADD COLUMN suggestion TEXT",
+            [],
+        );
         
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_review_comments_pr 
              ON review_comments(owner, repo, pr_number)",
             [],
         )?;
-        
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS review_turnaround (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                requested_at TEXT,
+                submitted_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Opt-in usage metrics; kept on this machine only
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                occurred_at TEXT NOT NULL,
+                duration_ms INTEGER
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_usage_events_occurred_at ON usage_events(occurred_at)",
+            [],
+        )?;
+
+        // Saved comments to insert while reviewing
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS comment_templates (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                body TEXT NOT NULL,
+                category TEXT,
+                quick_key TEXT,
+                quick_position INTEGER,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_comment_templates_quick_key
+             ON comment_templates(quick_key)",
+            [],
+        )?;
+
+        // Content hashes of commented lines, captured after the comment is written
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS comment_anchors (
+                comment_id INTEGER PRIMARY KEY,
+                blob_sha TEXT NOT NULL,
+                line_hash TEXT,
+                captured_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // The commit my last submitted review of each PR was made on
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS review_commits (
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                commit_id TEXT NOT NULL,
+                submitted_at TEXT NOT NULL,
+                PRIMARY KEY (owner, repo, pr_number)
+            )",
+            [],
+        )?;
+
+        // Hunks marked reviewed within a file, and how many hunks the file had when they
+        // were marked
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS reviewed_hunks (
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                file_path TEXT NOT NULL,
+                hunk_key TEXT NOT NULL,
+                reviewed_at TEXT NOT NULL,
+                PRIMARY KEY (owner, repo, pr_number, file_path, hunk_key)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS file_hunk_counts (
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                file_path TEXT NOT NULL,
+                hunk_count INTEGER NOT NULL,
+                PRIMARY KEY (owner, repo, pr_number, file_path)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS rendered_markdown (
+                blob_sha TEXT NOT NULL,
+                context TEXT NOT NULL,
+                html TEXT NOT NULL,
+                last_used_at TEXT NOT NULL,
+                PRIMARY KEY (blob_sha, context)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS trashed_reviews (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                commit_id TEXT NOT NULL,
+                body TEXT,
+                local_folder TEXT,
+                created_at TEXT NOT NULL,
+                log_file_index INTEGER NOT NULL DEFAULT 0,
+                reason TEXT NOT NULL,
+                trashed_at TEXT NOT NULL,
+                due_at TEXT
+            )",
+            [],
+        )?;
+
+        // Migration: Add due_at column if it doesn't exist
+        let _ = conn.execute(
+            "ALTER TABLE trashed_reviews ADD COLUMN due_at TEXT",
+            [],
+        );
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS trashed_review_comments (
+                trash_id INTEGER NOT NULL,
+                id INTEGER NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER NOT NULL,
+                side TEXT NOT NULL,
+                body TEXT NOT NULL,
+                commit_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                in_reply_to_id INTEGER,
+                category TEXT,
+                general INTEGER NOT NULL DEFAULT 0,
+                suggestion TEXT,
+                FOREIGN KEY (trash_id) REFERENCES trashed_reviews(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        let _ = conn.execute(
+            "ALTER TABLE trashed_review_comments ADD COLUMN general INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        let _ = conn.execute(
+            "ALTER TABLE trashed_review_comments ADD COLUMN suggestion TEXT",
+            [],
+        );
+
+        // Pseudo pull request numbers of local folder reviews, one per folder
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS local_folders (
+                folder TEXT PRIMARY KEY,
+                pr_number INTEGER NOT NULL UNIQUE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bookmarks (
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                source TEXT NOT NULL,
+                comment_id INTEGER NOT NULL,
+                note TEXT,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (owner, repo, pr_number, source, comment_id)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS pr_visits (
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                last_seen_at TEXT NOT NULL,
+                PRIMARY KEY (owner, repo, pr_number)
+            )",
+            [],
+        )?;
+
+        // Reviewer profile chosen for each PR, by name
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS active_profiles (
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                profile TEXT NOT NULL,
+                set_at TEXT NOT NULL,
+                PRIMARY KEY (owner, repo, pr_number)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS parked_submissions (
+                owner TEXT NOT NULL,
+                repo TEXT NOT NULL,
+                pr_number INTEGER NOT NULL,
+                review_id INTEGER,
+                event TEXT,
+                body TEXT,
+                mode TEXT,
+                login TEXT NOT NULL DEFAULT '',
+                parked_at TEXT NOT NULL,
+                PRIMARY KEY (owner, repo, pr_number)
+            )",
+            [],
+        )?;
+
+        // Migration: Add login column if it doesn't exist
+        let _ = conn.execute(
+            "ALTER TABLE parked_submissions ADD COLUMN login TEXT NOT NULL DEFAULT ''",
+            [],
+        );
+
         let log_dir = data_dir.join("review_logs");
         std::fs::create_dir_all(&log_dir)?;
         
@@ -136,7 +527,7 @@ impl ReviewStorage {
         // Check if review already exists
         let existing: Option<ReviewMetadata> = conn
             .query_row(
-                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index 
+                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at 
                  FROM review_metadata 
                  WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
                 params![owner, repo, pr_number],
@@ -150,6 +541,7 @@ impl ReviewStorage {
                         local_folder: row.get(5)?,
                         created_at: row.get(6)?,
                         log_file_index: row.get(7)?,
+                        due_at: row.get(8)?,
                     })
                 },
             )
@@ -189,9 +581,30 @@ impl ReviewStorage {
             local_folder: local_folder.map(String::from),
             created_at,
             log_file_index,
+            due_at: None,
         })
     }
     
+    /// Set or clear the deadline of a review in progress
+    pub fn set_review_due_date(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        due_at: Option<&str>,
+    ) -> AppResult<ReviewMetadata> {
+        {
+            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            conn.execute(
+                "UPDATE review_metadata SET due_at = ?1 WHERE owner = ?2 AND repo = ?3 AND pr_number = ?4",
+                params![due_at, owner, repo, pr_number],
+            )?;
+        }
+
+        self.get_review_metadata(owner, repo, pr_number)?
+            .ok_or_else(|| AppError::InvalidInput(t(Msg::NoPendingReview).to_string()))
+    }
+
     /// Update the commit_id for an existing review (useful when PR is updated)
     pub fn update_review_commit(
         &self,
@@ -206,7 +619,7 @@ impl ReviewStorage {
         // Check if review exists
         let existing: Option<ReviewMetadata> = conn
             .query_row(
-                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index 
+                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at 
                  FROM review_metadata 
                  WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
                 params![owner, repo, pr_number],
@@ -220,6 +633,7 @@ impl ReviewStorage {
                         local_folder: row.get(5)?,
                         created_at: row.get(6)?,
                         log_file_index: row.get(7)?,
+                        due_at: row.get(8)?,
                     })
                 },
             )
@@ -240,7 +654,7 @@ impl ReviewStorage {
         
         // Return updated metadata
         let metadata = conn.query_row(
-            "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index 
+            "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at 
              FROM review_metadata 
              WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
             params![owner, repo, pr_number],
@@ -254,6 +668,7 @@ impl ReviewStorage {
                     local_folder: row.get(5)?,
                     created_at: row.get(6)?,
                     log_file_index: row.get(7)?,
+                    due_at: row.get(8)?,
                 })
             },
         )?;
@@ -304,6 +719,9 @@ impl ReviewStorage {
                 updated_at: now,
                 deleted: false,
                 in_reply_to_id,
+                category: None,
+                general: false,
+                suggestion: None,
             }
         };
         
@@ -313,6 +731,97 @@ impl ReviewStorage {
         Ok(comment)
     }
     
+    /// Add imported comments to the review of a PR, starting one at `commit_id` with
+    /// `body` when there is none, in one transaction so a failure leaves nothing behind.
+    /// Replies are linked to parents from the same import by their `id` there; others
+    /// become top-level comments. Returns the review and the new id of each comment, in
+    /// the order given.
+    pub async fn import_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        body: Option<&str>,
+        comments: &[ImportedComment],
+    ) -> AppResult<(ReviewMetadata, Vec<i64>)> {
+        let existing = self.get_review_metadata(owner, repo, pr_number)?;
+        let now = Utc::now().to_rfc3339();
+        let log_file_index = match &existing {
+            Some(review) => review.log_file_index,
+            None => self.find_next_log_index(owner, repo, pr_number, None),
+        };
+        let review = existing.unwrap_or_else(|| ReviewMetadata {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            pr_number,
+            commit_id: commit_id.to_string(),
+            body: body.map(String::from),
+            local_folder: None,
+            created_at: now.clone(),
+            log_file_index,
+            due_at: None,
+        });
+
+        let ids = {
+            let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT OR IGNORE INTO review_metadata (owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index)
+                 VALUES (?1, ?2, ?3, ?4, ?5, NULL, ?6, ?7)",
+                params![owner, repo, pr_number, review.commit_id, review.body, review.created_at, log_file_index],
+            )?;
+
+            // Parents before replies, whatever order the comments come in
+            let mut order: Vec<usize> = (0..comments.len()).collect();
+            order.sort_by_key(|&index| comments[index].in_reply_to_id.is_some());
+
+            let mut new_ids: HashMap<i64, i64> = HashMap::new();
+            let mut ids = vec![0; comments.len()];
+            for index in order {
+                let comment = &comments[index];
+                let in_reply_to_id = comment
+                    .in_reply_to_id
+                    .and_then(|parent| new_ids.get(&parent).copied());
+                let suggestion = match &comment.suggestion {
+                    Some(suggestion) if !comment.general => Some(serde_json::to_string(suggestion)?),
+                    _ => None,
+                };
+                tx.execute(
+                    "INSERT INTO review_comments
+                     (owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id, category, general, suggestion)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9, 0, ?10, ?11, ?12, ?13)",
+                    params![
+                        owner,
+                        repo,
+                        pr_number,
+                        comment.file_path,
+                        comment.line_number,
+                        comment.side,
+                        comment.body,
+                        comment.commit_id.as_deref().unwrap_or(&review.commit_id),
+                        &now,
+                        in_reply_to_id,
+                        comment.category,
+                        comment.general,
+                        suggestion
+                    ],
+                )?;
+                ids[index] = tx.last_insert_rowid();
+                if let Some(id) = comment.id {
+                    new_ids.insert(id, ids[index]);
+                }
+            }
+            tx.commit()?;
+            ids
+        };
+
+        // Update log file
+        self.write_log(owner, repo, pr_number).await?;
+
+        Ok((review, ids))
+    }
+
     /// Update an existing comment
     pub async fn update_comment(
         &self,
@@ -330,7 +839,7 @@ impl ReviewStorage {
             )?;
             
             conn.query_row(
-                "SELECT id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id
+                "SELECT id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id, category, general, suggestion
                  FROM review_comments WHERE id = ?1",
                 params![comment_id],
                 |row| {
@@ -348,6 +857,9 @@ impl ReviewStorage {
                         updated_at: row.get(10)?,
                         deleted: row.get::<_, i64>(11)? != 0,
                         in_reply_to_id: row.get(12).ok(),
+                        category: row.get(13)?,
+                        general: row.get::<_, i64>(14)? != 0,
+                        suggestion: parse_suggestion(row.get(15)?),
                     })
                 },
             )?
@@ -375,8 +887,12 @@ impl ReviewStorage {
                 "UPDATE review_comments SET deleted = 1 WHERE id = ?1",
                 params![comment_id],
             )?;
-            
-            result
+            conn.execute(
+                "DELETE FROM bookmarks WHERE source = 'local' AND comment_id = ?1",
+                params![comment_id],
+            )?;
+            
+            result
         };
         
         // Update log file
@@ -385,6 +901,94 @@ impl ReviewStorage {
         Ok(())
     }
     
+    /// Set or clear a comment's category
+    pub async fn set_comment_category(&self, comment_id: i64, category: Option<&str>) -> AppResult<()> {
+        let affected = {
+            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            conn.execute(
+                "UPDATE review_comments SET category = ?1, updated_at = ?2 WHERE id = ?3 AND deleted = 0",
+                params![category, Utc::now().to_rfc3339(), comment_id],
+            )?
+        };
+
+        let comment = match self.get_comment(comment_id)? {
+            Some(comment) if affected > 0 => comment,
+            _ => return Err(AppError::InvalidInput(tf(Msg::CommentNotFound, &[&comment_id]))),
+        };
+
+        // Update log file
+        self.write_log(&comment.owner, &comment.repo, comment.pr_number).await?;
+
+        Ok(())
+    }
+
+    /// Set or clear the change a comment suggests
+    pub async fn set_comment_suggestion(
+        &self,
+        comment_id: i64,
+        suggestion: Option<&Suggestion>,
+    ) -> AppResult<ReviewComment> {
+        if let Some(suggestion) = suggestion {
+            let comment = self
+                .get_comment(comment_id)?
+                .ok_or_else(|| AppError::InvalidInput(tf(Msg::CommentNotFound, &[&comment_id])))?;
+            suggestion.validate_for_side(&comment.side)?;
+        }
+        let json = suggestion.map(serde_json::to_string).transpose()?;
+        let affected = {
+            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            conn.execute(
+                "UPDATE review_comments SET suggestion = ?1, updated_at = ?2
+                 WHERE id = ?3 AND deleted = 0 AND general = 0",
+                params![json, Utc::now().to_rfc3339(), comment_id],
+            )?
+        };
+
+        let comment = match self.get_comment(comment_id)? {
+            Some(comment) if affected > 0 => comment,
+            Some(_) => {
+                return Err(AppError::InvalidInput(format!(
+                    "comment {comment_id} is a general comment and cannot suggest a change"
+                )))
+            }
+            None => return Err(AppError::InvalidInput(tf(Msg::CommentNotFound, &[&comment_id]))),
+        };
+
+        // Update log file
+        self.write_log(&comment.owner, &comment.repo, comment.pr_number).await?;
+
+        Ok(comment)
+    }
+
+    /// Turn a line comment into a PR-level one with `body`. Its file and line are kept;
+    /// a suggestion cannot be applied from the conversation, so it is dropped.
+    pub async fn convert_to_general_comment(&self, comment_id: i64, body: &str) -> AppResult<ReviewComment> {
+        let affected = {
+            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            conn.execute(
+                "UPDATE review_comments SET general = 1, body = ?1, suggestion = NULL, updated_at = ?2
+                 WHERE id = ?3 AND deleted = 0 AND general = 0",
+                params![body, Utc::now().to_rfc3339(), comment_id],
+            )?
+        };
+
+        let comment = match self.get_comment(comment_id)? {
+            Some(comment) if affected > 0 => comment,
+            Some(_) => {
+                return Err(AppError::InvalidInput(tf(
+                    Msg::CommentAlreadyGeneral,
+                    &[&comment_id],
+                )))
+            }
+            None => return Err(AppError::InvalidInput(tf(Msg::CommentNotFound, &[&comment_id]))),
+        };
+
+        // Update log file
+        self.write_log(&comment.owner, &comment.repo, comment.pr_number).await?;
+
+        Ok(comment)
+    }
+
     /// Delete a comment from DB without updating the log file (for successfully posted comments)
     pub fn delete_comment_preserve_log(&self, comment_id: i64) -> AppResult<()> {
         let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
@@ -393,6 +997,7 @@ impl ReviewStorage {
             "DELETE FROM review_comments WHERE id = ?1",
             params![comment_id],
         )?;
+        conn.execute("DELETE FROM comment_anchors WHERE comment_id = ?1", params![comment_id])?;
         
         Ok(())
     }
@@ -436,7 +1041,7 @@ impl ReviewStorage {
         let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
         
         let mut stmt = conn.prepare(
-            "SELECT id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id
+            "SELECT id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id, category, general, suggestion
              FROM review_comments
              WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3 AND deleted = 0
              ORDER BY file_path, line_number"
@@ -458,6 +1063,9 @@ impl ReviewStorage {
                     updated_at: row.get(10)?,
                     deleted: row.get::<_, i64>(11)? != 0,
                     in_reply_to_id: row.get(12).ok(),
+                    category: row.get(13)?,
+                    general: row.get::<_, i64>(14)? != 0,
+                    suggestion: parse_suggestion(row.get(15)?),
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -465,6 +1073,42 @@ impl ReviewStorage {
         Ok(comments)
     }
     
+    /// Get a single comment by id (excluding deleted ones)
+    pub fn get_comment(&self, comment_id: i64) -> AppResult<Option<ReviewComment>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let comment = conn
+            .query_row(
+                "SELECT id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id, category, general, suggestion
+                 FROM review_comments
+                 WHERE id = ?1 AND deleted = 0",
+                params![comment_id],
+                |row| {
+                    Ok(ReviewComment {
+                        id: row.get(0)?,
+                        owner: row.get(1)?,
+                        repo: row.get(2)?,
+                        pr_number: row.get(3)?,
+                        file_path: row.get(4)?,
+                        line_number: row.get(5)?,
+                        side: row.get(6)?,
+                        body: row.get(7)?,
+                        commit_id: row.get(8)?,
+                        created_at: row.get(9)?,
+                        updated_at: row.get(10)?,
+                        deleted: row.get::<_, i64>(11)? != 0,
+                        in_reply_to_id: row.get(12).ok(),
+                        category: row.get(13)?,
+                        general: row.get::<_, i64>(14)? != 0,
+                        suggestion: parse_suggestion(row.get(15)?),
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(comment)
+    }
+
     /// Get review metadata
     pub fn get_review_metadata(
         &self,
@@ -476,7 +1120,7 @@ impl ReviewStorage {
         
         let metadata = conn
             .query_row(
-                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index
+                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at
                  FROM review_metadata
                  WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
                 params![owner, repo, pr_number],
@@ -490,6 +1134,7 @@ impl ReviewStorage {
                         local_folder: row.get(5)?,
                         created_at: row.get(6)?,
                         log_file_index: row.get(7)?,
+                        due_at: row.get(8)?,
                     })
                 },
             )
@@ -503,7 +1148,7 @@ impl ReviewStorage {
         let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
         
         let mut stmt = conn.prepare(
-            "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index
+            "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at
              FROM review_metadata"
         )?;
         
@@ -517,6 +1162,7 @@ impl ReviewStorage {
                 local_folder: row.get(5)?,
                 created_at: row.get(6)?,
                 log_file_index: row.get(7)?,
+                due_at: row.get(8)?,
             })
         })?;
         
@@ -528,7 +1174,7 @@ impl ReviewStorage {
         Ok(results)
     }
     
-    /// Abandon a review (mark log file as abandoned, delete from DB)
+    /// Abandon a review (mark log file as abandoned, move it to the trash)
     pub async fn abandon_review(
         &self,
         owner: &str,
@@ -540,7 +1186,7 @@ impl ReviewStorage {
             
             let metadata: Option<ReviewMetadata> = conn
                 .query_row(
-                    "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index
+                    "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at
                      FROM review_metadata
                      WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
                     params![owner, repo, pr_number],
@@ -554,6 +1200,7 @@ impl ReviewStorage {
                             local_folder: row.get(5)?,
                             created_at: row.get(6)?,
                             log_file_index: row.get(7)?,
+                            due_at: row.get(8)?,
                         })
                     },
                 )
@@ -566,10 +1213,11 @@ impl ReviewStorage {
             // Mark log file as abandoned
             let log_path = self.get_log_path(owner, repo, pr_number, meta.log_file_index, meta.local_folder.as_deref());
             if log_path.exists() {
-                let abandoned_time = Utc::now().to_rfc3339();
+                let abandoned_time = display_time(&Utc::now().to_rfc3339());
                 let header = format!(
-                    "# REVIEW ABANDONED at {}\n# Original review started at {}\n\n",
-                    abandoned_time, meta.created_at
+                    "# {}\n# {}\n\n",
+                    tf(Msg::LogReviewAbandoned, &[&abandoned_time]),
+                    tf(Msg::LogOriginalStart, &[&display_time(&meta.created_at)])
                 );
                 
                 let existing_content = fs::read_to_string(&log_path).await.unwrap_or_default();
@@ -577,31 +1225,29 @@ impl ReviewStorage {
                 fs::write(&log_path, new_content).await?;
             }
             
-            // Delete from database
-            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
-            conn.execute(
-                "DELETE FROM review_metadata WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
-                params![owner, repo, pr_number],
-            )?;
+            // Move to the trash so it can still be restored
+            let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            move_review_to_trash(&mut conn, &meta, "abandoned")?;
         }
         
         Ok(())
     }
     
-    /// Clear a completed review from database
+    /// Clear a completed review from database. `snapshot`, when given, is written under
+    /// the submitted header of the log.
     pub async fn mark_review_submitted(
         &self,
         owner: &str,
         repo: &str,
         pr_number: u64,
-        _pr_title: Option<&str>,
+        snapshot: Option<&ReviewSnapshot>,
     ) -> AppResult<()> {
         let metadata = {
             let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
             
             let metadata: Option<ReviewMetadata> = conn
                 .query_row(
-                    "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index
+                    "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at
                      FROM review_metadata
                      WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
                     params![owner, repo, pr_number],
@@ -615,6 +1261,7 @@ impl ReviewStorage {
                             local_folder: row.get(5)?,
                             created_at: row.get(6)?,
                             log_file_index: row.get(7)?,
+                            due_at: row.get(8)?,
                         })
                     },
                 )
@@ -627,10 +1274,12 @@ impl ReviewStorage {
             // Mark log file as submitted
             let log_path = self.get_log_path(owner, repo, pr_number, meta.log_file_index, meta.local_folder.as_deref());
             if log_path.exists() {
-                let submitted_time = Utc::now().to_rfc3339();
+                let submitted_time = display_time(&Utc::now().to_rfc3339());
                 let header = format!(
-                    "# REVIEW SUBMITTED TO GITHUB at {}\n# Original review started at {}\n\n",
-                    submitted_time, meta.created_at
+                    "# {}\n# {}\n{}\n",
+                    tf(Msg::LogReviewSubmitted, &[&submitted_time]),
+                    tf(Msg::LogOriginalStart, &[&display_time(&meta.created_at)]),
+                    snapshot.map(ReviewSnapshot::log_lines).unwrap_or_default()
                 );
                 
                 let existing_content = fs::read_to_string(&log_path).await.unwrap_or_default();
@@ -661,7 +1310,7 @@ impl ReviewStorage {
             
             let metadata: Option<ReviewMetadata> = conn
                 .query_row(
-                    "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index
+                    "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at
                      FROM review_metadata
                      WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
                     params![owner, repo, pr_number],
@@ -675,6 +1324,7 @@ impl ReviewStorage {
                             local_folder: row.get(5)?,
                             created_at: row.get(6)?,
                             log_file_index: row.get(7)?,
+                            due_at: row.get(8)?,
                         })
                     },
                 )
@@ -687,10 +1337,11 @@ impl ReviewStorage {
             // Mark log file as deleted
             let log_path = self.get_log_path(owner, repo, pr_number, meta.log_file_index, meta.local_folder.as_deref());
             if log_path.exists() {
-                let deleted_time = Utc::now().to_rfc3339();
+                let deleted_time = display_time(&Utc::now().to_rfc3339());
                 let header = format!(
-                    "# REVIEW DELETED (NOT SUBMITTED TO GITHUB) at {}\n# Original review started at {}\n\n",
-                    deleted_time, meta.created_at
+                    "# {}\n# {}\n\n",
+                    tf(Msg::LogReviewDeleted, &[&deleted_time]),
+                    tf(Msg::LogOriginalStart, &[&display_time(&meta.created_at)])
                 );
                 
                 let existing_content = fs::read_to_string(&log_path).await.unwrap_or_default();
@@ -698,105 +1349,1294 @@ impl ReviewStorage {
                 fs::write(&log_path, new_content).await?;
             }
             
-            // Delete from database
-            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
-            conn.execute(
-                "DELETE FROM review_metadata WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
-                params![owner, repo, pr_number],
-            )?;
+            // Move to the trash so it can still be restored
+            let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            move_review_to_trash(&mut conn, &meta, "cleared")?;
         }
         
         Ok(())
     }
     
-    fn get_log_path(
+    /// Move the review of a merged or closed PR to the trash, noting why at the top of
+    /// its log. Returns whether there was a review to move.
+    pub async fn archive_closed_review(
         &self,
         owner: &str,
         repo: &str,
         pr_number: u64,
-        index: i32,
-        local_folder: Option<&str>,
-    ) -> PathBuf {
-        let is_local_folder = owner == "__local__" && repo == "local";
+        merged: bool,
+    ) -> AppResult<bool> {
+        let Some(meta) = self.get_review_metadata(owner, repo, pr_number)? else {
+            return Ok(false);
+        };
 
-        let filename = if is_local_folder {
-            let folder_name = local_folder
-                .and_then(|path| Path::new(path).file_name().and_then(|name| name.to_str()))
-                .unwrap_or("local-folder");
+        let log_path = self.get_log_path(owner, repo, pr_number, meta.log_file_index, meta.local_folder.as_deref());
+        if log_path.exists() {
+            let archived_time = display_time(&Utc::now().to_rfc3339());
+            let msg = if merged { Msg::LogReviewMerged } else { Msg::LogReviewClosed };
+            let header = format!(
+                "# {}\n# {}\n\n",
+                tf(msg, &[&archived_time]),
+                tf(Msg::LogOriginalStart, &[&display_time(&meta.created_at)])
+            );
 
-            let safe_folder_name: String = folder_name
-                .chars()
-                .map(|c| match c {
-                    '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
-                    _ => c,
+            let existing_content = fs::read_to_string(&log_path).await.unwrap_or_default();
+            fs::write(&log_path, format!("{}{}", header, existing_content)).await?;
+        }
+
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        move_review_to_trash(&mut conn, &meta, if merged { "merged" } else { "closed" })?;
+        Ok(true)
+    }
+
+    /// Trashed reviews, most recently trashed first
+    pub fn list_trashed_reviews(&self) -> AppResult<Vec<TrashedReview>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.owner, t.repo, t.pr_number, t.commit_id, t.body, t.local_folder,
+                    t.created_at, t.reason, t.trashed_at,
+                    (SELECT COUNT(*) FROM trashed_review_comments c WHERE c.trash_id = t.id)
+             FROM trashed_reviews t
+             ORDER BY t.trashed_at DESC, t.id DESC",
+        )?;
+        let trashed = stmt
+            .query_map([], |row| {
+                Ok(TrashedReview {
+                    id: row.get(0)?,
+                    owner: row.get(1)?,
+                    repo: row.get(2)?,
+                    pr_number: row.get(3)?,
+                    commit_id: row.get(4)?,
+                    body: row.get(5)?,
+                    local_folder: row.get(6)?,
+                    created_at: row.get(7)?,
+                    reason: row.get(8)?,
+                    trashed_at: row.get(9)?,
+                    comment_count: row.get(10)?,
                 })
-                .collect();
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
 
-            let safe_folder_name = safe_folder_name.trim();
-            let safe_folder_name = if safe_folder_name.is_empty() {
-                "local-folder"
-            } else {
-                safe_folder_name
-            };
+        Ok(trashed)
+    }
 
-            if index == 0 {
-                format!("{}.log", safe_folder_name)
-            } else {
-                format!("{}-{}.log", safe_folder_name, index)
-            }
-        } else if index == 0 {
-            format!("{}-{}-{}.log", owner, repo, pr_number)
-        } else {
-            format!("{}-{}-{}-{}.log", owner, repo, pr_number, index)
+    /// Bring back the most recently trashed review of a PR with its comments. Fails if
+    /// the PR already has a review in progress, so nothing is overwritten.
+    pub fn restore_trashed_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> AppResult<ReviewMetadata> {
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+
+        let trashed: Option<(i64, ReviewMetadata)> = tx
+            .query_row(
+                "SELECT id, owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at
+                 FROM trashed_reviews
+                 WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3
+                 ORDER BY trashed_at DESC, id DESC
+                 LIMIT 1",
+                params![owner, repo, pr_number],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        ReviewMetadata {
+                            owner: row.get(1)?,
+                            repo: row.get(2)?,
+                            pr_number: row.get(3)?,
+                            commit_id: row.get(4)?,
+                            body: row.get(5)?,
+                            local_folder: row.get(6)?,
+                            created_at: row.get(7)?,
+                            log_file_index: row.get(8)?,
+                            due_at: row.get(9)?,
+                        },
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((trash_id, metadata)) = trashed else {
+            return Err(AppError::InvalidInput(format!(
+                "no trashed review for {owner}/{repo}#{pr_number}"
+            )));
         };
 
-        self.log_dir.join(filename)
+        let in_progress: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM review_metadata WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3)",
+            params![owner, repo, pr_number],
+            |row| row.get(0),
+        )?;
+        if in_progress {
+            return Err(AppError::InvalidInput(format!(
+                "{owner}/{repo}#{pr_number} already has a review in progress; submit or clear it before restoring"
+            )));
+        }
+
+        tx.execute(
+            "INSERT INTO review_metadata (owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                metadata.owner,
+                metadata.repo,
+                metadata.pr_number,
+                metadata.commit_id,
+                metadata.body,
+                metadata.local_folder,
+                metadata.created_at,
+                metadata.log_file_index,
+                metadata.due_at
+            ],
+        )?;
+        // Original ids are kept so replies still point at their parents
+        tx.execute(
+            "INSERT INTO review_comments (id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id, category, general, suggestion)
+             SELECT id, ?2, ?3, ?4, file_path, line_number, side, body, commit_id, created_at, updated_at, 0, in_reply_to_id, category, general, suggestion
+             FROM trashed_review_comments
+             WHERE trash_id = ?1",
+            params![trash_id, owner, repo, pr_number],
+        )?;
+        tx.execute("DELETE FROM trashed_review_comments WHERE trash_id = ?1", params![trash_id])?;
+        tx.execute("DELETE FROM trashed_reviews WHERE id = ?1", params![trash_id])?;
+        tx.commit()?;
+
+        Ok(metadata)
     }
-    
-    fn find_next_log_index(&self, owner: &str, repo: &str, pr_number: u64, local_folder: Option<&str>) -> i32 {
-        let mut index = 0;
-        loop {
-            let log_path = self.get_log_path(owner, repo, pr_number, index, local_folder);
-            if !log_path.exists() {
-                return index;
-            }
-            index += 1;
+
+    /// Keep the comments of a GitHub pending review in the trash before the review is
+    /// deleted, so they can be restored as a local review. Returns the trash id.
+    pub fn archive_pending_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        comments: &[crate::models::PullRequestComment],
+    ) -> AppResult<i64> {
+        let now = Utc::now().to_rfc3339();
+        let created_at = comments
+            .iter()
+            .map(|comment| comment.created_at.as_str())
+            .min()
+            .unwrap_or(&now);
+
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO trashed_reviews (owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, reason, trashed_at)
+             VALUES (?1, ?2, ?3, ?4, NULL, NULL, ?5, 0, 'deleted_on_github', ?6)",
+            params![owner, repo, pr_number, commit_id, created_at, now],
+        )?;
+        let trash_id = tx.last_insert_rowid();
+
+        // GitHub ids would clash with local ones once restored, so each comment gets a
+        // fresh local id, reserved in the comments' sequence so no new draft takes it
+        let last_id: i64 = tx.query_row(
+            "SELECT MAX(
+                 COALESCE((SELECT seq FROM sqlite_sequence WHERE name = 'review_comments'), 0),
+                 COALESCE((SELECT MAX(id) FROM review_comments), 0),
+                 COALESCE((SELECT MAX(id) FROM trashed_review_comments), 0)
+             )",
+            [],
+            |row| row.get(0),
+        )?;
+        let new_ids: HashMap<u64, i64> = comments
+            .iter()
+            .zip(last_id + 1..)
+            .map(|(comment, id)| (comment.id, id))
+            .collect();
+        for comment in comments {
+            // Replies point at their parent's new id; a parent not archived is dropped
+            let in_reply_to_id = comment
+                .in_reply_to_id
+                .and_then(|parent| new_ids.get(&parent).copied());
+            tx.execute(
+                "INSERT INTO trashed_review_comments (trash_id, id, file_path, line_number, side, body, commit_id, created_at, updated_at, in_reply_to_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8, ?9)",
+                params![
+                    trash_id,
+                    new_ids[&comment.id],
+                    comment.path.as_deref().unwrap_or_default(),
+                    comment.line.unwrap_or(0),
+                    comment.side.as_deref().unwrap_or("RIGHT"),
+                    comment.body,
+                    commit_id,
+                    comment.created_at,
+                    in_reply_to_id
+                ],
+            )?;
+        }
+        let reserved = last_id + comments.len() as i64;
+        let updated = tx.execute(
+            "UPDATE sqlite_sequence SET seq = ?1 WHERE name = 'review_comments'",
+            params![reserved],
+        )?;
+        if updated == 0 {
+            tx.execute(
+                "INSERT INTO sqlite_sequence (name, seq) VALUES ('review_comments', ?1)",
+                params![reserved],
+            )?;
         }
+        tx.commit()?;
+
+        Ok(trash_id)
     }
-    
-    async fn fetch_pr_title(&self, owner: &str, repo: &str, pr_number: u64) -> AppResult<String> {
-        let token = require_token()?;
-        let client = reqwest::Client::builder()
-            .user_agent("github-review-app")
-            .build()?;
-        
-        let url = format!("https://api.github.com/repos/{owner}/{repo}/pulls/{pr_number}");
-        let response = client
-            .get(&url)
-            .header("Authorization", format!("Bearer {token}"))
-            .send()
-            .await?;
-        
-        if !response.status().is_success() {
-            return Err(AppError::Api(format!("Failed to fetch PR title: {}", response.status())));
+
+    /// Permanently delete reviews trashed before `before` (RFC 3339). Returns how many
+    /// were removed.
+    pub fn purge_trash(&self, before: &str) -> AppResult<usize> {
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM comment_anchors
+             WHERE comment_id IN (
+                 SELECT c.id FROM trashed_review_comments c
+                 JOIN trashed_reviews t ON c.trash_id = t.id
+                 WHERE t.trashed_at < ?1
+             )",
+            params![before],
+        )?;
+        tx.execute(
+            "DELETE FROM trashed_review_comments
+             WHERE trash_id IN (SELECT id FROM trashed_reviews WHERE trashed_at < ?1)",
+            params![before],
+        )?;
+        let purged = tx.execute("DELETE FROM trashed_reviews WHERE trashed_at < ?1", params![before])?;
+        tx.commit()?;
+
+        Ok(purged)
+    }
+
+    /// Record that a review was submitted, with when it was requested if known
+    pub fn record_turnaround(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        requested_at: Option<&str>,
+        submitted_at: &str,
+    ) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT INTO review_turnaround (owner, repo, pr_number, requested_at, submitted_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![owner, repo, pr_number, requested_at, submitted_at],
+        )?;
+
+        Ok(())
+    }
+
+    /// Count one usage event, with how long it took if it was timed
+    pub fn record_usage_event(
+        &self,
+        kind: &str,
+        occurred_at: &str,
+        duration_ms: Option<u64>,
+    ) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT INTO usage_events (kind, occurred_at, duration_ms) VALUES (?1, ?2, ?3)",
+            params![kind, occurred_at, duration_ms],
+        )?;
+
+        Ok(())
+    }
+
+    /// Count several events of one `kind`, given as `(occurred_at, duration_ms)`, in a
+    /// single transaction
+    pub fn record_usage_events(&self, kind: &str, events: &[(String, u64)]) -> AppResult<()> {
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO usage_events (kind, occurred_at, duration_ms) VALUES (?1, ?2, ?3)",
+            )?;
+            for (occurred_at, duration_ms) in events {
+                stmt.execute(params![kind, occurred_at, duration_ms])?;
+            }
         }
-        
-        let pr_data: serde_json::Value = response.json().await?;
-        let title = pr_data["title"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
-        
-        Ok(title)
+        tx.commit()?;
+
+        Ok(())
     }
-    
-    async fn write_log(&self, owner: &str, repo: &str, pr_number: u64) -> AppResult<()> {
-        tracing::info!("Writing log file for {}/{}#{}", owner, repo, pr_number);
-        let (metadata, comments) = {
-            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
-            
-            let metadata: ReviewMetadata = conn.query_row(
-                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index
+
+    /// Usage events at or after `since` (RFC 3339), oldest first
+    pub fn get_usage_events(&self, since: Option<&str>) -> AppResult<Vec<UsageRecord>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT kind, occurred_at, duration_ms
+             FROM usage_events
+             WHERE ?1 IS NULL OR occurred_at >= ?1
+             ORDER BY occurred_at ASC",
+        )?;
+        let records = stmt
+            .query_map(params![since], |row| {
+                Ok(UsageRecord {
+                    kind: row.get(0)?,
+                    occurred_at: row.get(1)?,
+                    duration_ms: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+
+    /// Anchors of the comments of a PR's local review, by comment id
+    pub fn get_comment_anchors(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> AppResult<HashMap<i64, CommentAnchor>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        load_comment_anchors(&conn, owner, repo, pr_number)
+    }
+
+    /// Store anchors of comments, replacing any they had. The review log shows them from
+    /// the next time it is written.
+    pub fn set_comment_anchors(&self, anchors: &[(i64, CommentAnchor)]) -> AppResult<()> {
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        for (comment_id, anchor) in anchors {
+            tx.execute(
+                "INSERT OR REPLACE INTO comment_anchors (comment_id, blob_sha, line_hash, captured_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![comment_id, anchor.blob_sha, anchor.line_hash, now],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Drop usage events older than `before` (RFC 3339). Returns how many were removed.
+    pub fn purge_usage_events(&self, before: &str) -> AppResult<usize> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let purged =
+            conn.execute("DELETE FROM usage_events WHERE occurred_at < ?1", params![before])?;
+
+        Ok(purged)
+    }
+
+    /// Remember that my review of a PR, submitted at `submitted_at`, was made on `commit_id`
+    pub fn record_review_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        submitted_at: &str,
+    ) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO review_commits (owner, repo, pr_number, commit_id, submitted_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![owner, repo, pr_number, commit_id, submitted_at],
+        )?;
+
+        Ok(())
+    }
+
+    /// The commit of my last review of a PR submitted from this app
+    pub fn get_review_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> AppResult<Option<ReviewedCommit>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let commit = conn
+            .query_row(
+                "SELECT commit_id, submitted_at FROM review_commits
+                 WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+                params![owner, repo, pr_number],
+                |row| {
+                    Ok(ReviewedCommit {
+                        commit_id: row.get(0)?,
+                        submitted_at: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(commit)
+    }
+
+    /// Mark one hunk of a file reviewed, or clear the mark, and return the file's progress.
+    /// Hunks are named by the caller. A `hunk_count` different from the one last recorded
+    /// means the diff has changed, so the file's earlier marks are dropped.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mark_hunk_reviewed(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        file_path: &str,
+        hunk_key: &str,
+        hunk_count: u32,
+        reviewed: bool,
+    ) -> AppResult<FileReviewProgress> {
+        if hunk_count == 0 {
+            return Err(AppError::InvalidInput(format!("{file_path} has no hunks to review")));
+        }
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+
+        let recorded: Option<u32> = tx
+            .query_row(
+                "SELECT hunk_count FROM file_hunk_counts
+                 WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3 AND file_path = ?4",
+                params![owner, repo, pr_number, file_path],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if recorded != Some(hunk_count) {
+            tx.execute(
+                "DELETE FROM reviewed_hunks
+                 WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3 AND file_path = ?4",
+                params![owner, repo, pr_number, file_path],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO file_hunk_counts (owner, repo, pr_number, file_path, hunk_count)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![owner, repo, pr_number, file_path, hunk_count],
+            )?;
+        }
+
+        if reviewed {
+            tx.execute(
+                "INSERT OR IGNORE INTO reviewed_hunks (owner, repo, pr_number, file_path, hunk_key, reviewed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![owner, repo, pr_number, file_path, hunk_key, Utc::now().to_rfc3339()],
+            )?;
+        } else {
+            tx.execute(
+                "DELETE FROM reviewed_hunks
+                 WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3 AND file_path = ?4 AND hunk_key = ?5",
+                params![owner, repo, pr_number, file_path, hunk_key],
+            )?;
+        }
+
+        let reviewed_hunks: u32 = tx.query_row(
+            "SELECT COUNT(*) FROM reviewed_hunks
+             WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3 AND file_path = ?4",
+            params![owner, repo, pr_number, file_path],
+            |row| row.get(0),
+        )?;
+        tx.commit()?;
+
+        Ok(FileReviewProgress::new(file_path.to_string(), reviewed_hunks, hunk_count))
+    }
+
+    /// Hunk review progress of every file of a PR with hunks marked, sorted by path.
+    pub fn get_hunk_progress(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> AppResult<Vec<FileReviewProgress>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT c.file_path, COUNT(h.hunk_key), c.hunk_count
+             FROM file_hunk_counts c
+             LEFT JOIN reviewed_hunks h
+               ON h.owner = c.owner AND h.repo = c.repo AND h.pr_number = c.pr_number
+              AND h.file_path = c.file_path
+             WHERE c.owner = ?1 AND c.repo = ?2 AND c.pr_number = ?3
+             GROUP BY c.file_path
+             ORDER BY c.file_path",
+        )?;
+        let progress = stmt
+            .query_map(params![owner, repo, pr_number], |row| {
+                Ok(FileReviewProgress::new(row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(progress)
+    }
+
+    /// Paths of the files with every hunk reviewed, sorted, keyed by
+    /// `(owner, repo, pr_number)`.
+    pub fn get_all_hunk_reviewed_files(
+        &self,
+    ) -> AppResult<HashMap<(String, String, u64), Vec<String>>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT c.owner, c.repo, c.pr_number, c.file_path
+             FROM file_hunk_counts c
+             WHERE c.hunk_count <= (
+                 SELECT COUNT(*) FROM reviewed_hunks h
+                 WHERE h.owner = c.owner AND h.repo = c.repo AND h.pr_number = c.pr_number
+                   AND h.file_path = c.file_path
+             )
+             ORDER BY c.owner, c.repo, c.pr_number, c.file_path",
+        )?;
+        let mut files: HashMap<(String, String, u64), Vec<String>> = HashMap::new();
+        let rows = stmt.query_map([], |row| {
+            Ok(((row.get(0)?, row.get(1)?, row.get(2)?), row.get::<_, String>(3)?))
+        })?;
+        for row in rows {
+            let (key, file_path) = row?;
+            files.entry(key).or_default().push(file_path);
+        }
+
+        Ok(files)
+    }
+
+    /// Turnaround records submitted at or after `since` (RFC 3339), oldest first
+    pub fn get_turnaround_records(&self, since: Option<&str>) -> AppResult<Vec<TurnaroundRecord>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT owner, repo, pr_number, requested_at, submitted_at
+             FROM review_turnaround
+             WHERE ?1 IS NULL OR submitted_at >= ?1
+             ORDER BY submitted_at ASC",
+        )?;
+        let records = stmt
+            .query_map(params![since], |row| {
+                Ok(TurnaroundRecord {
+                    owner: row.get(0)?,
+                    repo: row.get(1)?,
+                    pr_number: row.get(2)?,
+                    requested_at: row.get(3)?,
+                    submitted_at: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+
+    /// Cached HTML for a blob rendered in `context`, marking it as recently used
+    pub fn get_rendered_markdown(&self, blob_sha: &str, context: &str) -> AppResult<Option<String>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let html: Option<String> = conn
+            .query_row(
+                "SELECT html FROM rendered_markdown WHERE blob_sha = ?1 AND context = ?2",
+                params![blob_sha, context],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if html.is_some() {
+            conn.execute(
+                "UPDATE rendered_markdown SET last_used_at = ?3 WHERE blob_sha = ?1 AND context = ?2",
+                params![blob_sha, context, Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        Ok(html)
+    }
+
+    /// Cache rendered HTML, then drop the least recently used entries beyond `max_entries`
+    pub fn put_rendered_markdown(
+        &self,
+        blob_sha: &str,
+        context: &str,
+        html: &str,
+        max_entries: usize,
+    ) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT INTO rendered_markdown (blob_sha, context, html, last_used_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(blob_sha, context) DO UPDATE
+             SET html = excluded.html, last_used_at = excluded.last_used_at",
+            params![blob_sha, context, html, Utc::now().to_rfc3339()],
+        )?;
+        conn.execute(
+            "DELETE FROM rendered_markdown WHERE rowid NOT IN (
+                SELECT rowid FROM rendered_markdown ORDER BY last_used_at DESC LIMIT ?1
+             )",
+            params![max_entries as i64],
+        )?;
+
+        Ok(())
+    }
+
+    /// Pin a comment on a PR, or update the note of an existing bookmark
+    pub fn add_bookmark(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        source: &str,
+        comment_id: i64,
+        note: Option<&str>,
+    ) -> AppResult<Bookmark> {
+        if !BOOKMARK_SOURCES.contains(&source) {
+            return Err(AppError::InvalidInput(format!(
+                "bookmark source must be one of {}",
+                BOOKMARK_SOURCES.join(", ")
+            )));
+        }
+        let note = note.map(str::trim).filter(|note| !note.is_empty());
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT INTO bookmarks (owner, repo, pr_number, source, comment_id, note, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(owner, repo, pr_number, source, comment_id) DO UPDATE SET note = excluded.note",
+            params![owner, repo, pr_number, source, comment_id, note, Utc::now().to_rfc3339()],
+        )?;
+        let bookmark = conn.query_row(
+            "SELECT owner, repo, pr_number, source, comment_id, note, created_at FROM bookmarks
+             WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3 AND source = ?4 AND comment_id = ?5",
+            params![owner, repo, pr_number, source, comment_id],
+            bookmark_from_row,
+        )?;
+
+        Ok(bookmark)
+    }
+
+    /// Unpin a comment. Returns whether a bookmark was removed.
+    pub fn remove_bookmark(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        source: &str,
+        comment_id: i64,
+    ) -> AppResult<bool> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let removed = conn.execute(
+            "DELETE FROM bookmarks
+             WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3 AND source = ?4 AND comment_id = ?5",
+            params![owner, repo, pr_number, source, comment_id],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Bookmarks of a PR, oldest first
+    pub fn get_bookmarks(&self, owner: &str, repo: &str, pr_number: u64) -> AppResult<Vec<Bookmark>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT owner, repo, pr_number, source, comment_id, note, created_at FROM bookmarks
+             WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3
+             ORDER BY created_at ASC, comment_id ASC",
+        )?;
+        let bookmarks = stmt
+            .query_map(params![owner, repo, pr_number], bookmark_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(bookmarks)
+    }
+
+    /// Save a new comment template
+    pub fn create_comment_template(
+        &self,
+        name: &str,
+        body: &str,
+        category: Option<&str>,
+    ) -> AppResult<CommentTemplate> {
+        let name = validate_comment_template(name, body, category)?;
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        ensure_template_name_free(&conn, name, None)?;
+
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO comment_templates (name, body, category, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)",
+            params![name, body, category, now],
+        )?;
+        let template = conn.query_row(
+            "SELECT id, name, body, category, quick_key, created_at, updated_at FROM comment_templates
+             WHERE id = ?1",
+            params![conn.last_insert_rowid()],
+            comment_template_from_row,
+        )?;
+
+        Ok(template)
+    }
+
+    /// Every comment template, by name
+    pub fn list_comment_templates(&self) -> AppResult<Vec<CommentTemplate>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, body, category, quick_key, created_at, updated_at FROM comment_templates
+             ORDER BY name COLLATE NOCASE ASC, id ASC",
+        )?;
+        let templates = stmt
+            .query_map([], comment_template_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(templates)
+    }
+
+    pub fn get_comment_template(&self, id: i64) -> AppResult<Option<CommentTemplate>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let template = conn
+            .query_row(
+                "SELECT id, name, body, category, quick_key, created_at, updated_at FROM comment_templates
+                 WHERE id = ?1",
+                params![id],
+                comment_template_from_row,
+            )
+            .optional()?;
+
+        Ok(template)
+    }
+
+    /// Replace a template's name, body and category
+    pub fn update_comment_template(
+        &self,
+        id: i64,
+        name: &str,
+        body: &str,
+        category: Option<&str>,
+    ) -> AppResult<CommentTemplate> {
+        let name = validate_comment_template(name, body, category)?;
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        ensure_template_name_free(&conn, name, Some(id))?;
+
+        let affected = conn.execute(
+            "UPDATE comment_templates SET name = ?1, body = ?2, category = ?3, updated_at = ?4
+             WHERE id = ?5",
+            params![name, body, category, Utc::now().to_rfc3339(), id],
+        )?;
+        if affected == 0 {
+            return Err(AppError::InvalidInput(tf(Msg::CommentTemplateNotFound, &[&id])));
+        }
+        let template = conn.query_row(
+            "SELECT id, name, body, category, quick_key, created_at, updated_at FROM comment_templates
+             WHERE id = ?1",
+            params![id],
+            comment_template_from_row,
+        )?;
+
+        Ok(template)
+    }
+
+    /// Templates offered as quick comments, in the order they were saved
+    pub fn list_quick_comment_templates(&self) -> AppResult<Vec<CommentTemplate>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, body, category, quick_key, created_at, updated_at FROM comment_templates
+             WHERE quick_key IS NOT NULL
+             ORDER BY quick_position ASC, id ASC",
+        )?;
+        let templates = stmt
+            .query_map([], comment_template_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(templates)
+    }
+
+    /// Make `templates` the quick comments, in order. A template already keyed or named
+    /// like one of them is updated in place; templates that drop off the list stay in
+    /// the library without a key.
+    pub fn replace_quick_comment_templates(
+        &self,
+        templates: &[QuickCommentTemplate],
+    ) -> AppResult<()> {
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+        save_quick_comment_templates(&tx, templates)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Write an imported settings bundle in one transaction: the `settings` entries as
+    /// raw JSON, the library `templates`, then the `quick_comments` list. Nothing is
+    /// written unless all of it is.
+    pub fn import_settings(
+        &self,
+        settings: &[(&str, String)],
+        templates: &[CommentTemplate],
+        quick_comments: Option<&[QuickCommentTemplate]>,
+    ) -> AppResult<()> {
+        let mut conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        for (key, value) in settings {
+            tx.execute(
+                "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                params![key, value, now],
+            )?;
+        }
+        // Before quick comments, which pick up library templates of the same name
+        for template in templates {
+            let category = template.category.as_deref();
+            let name = validate_comment_template(&template.name, &template.body, category)?;
+            upsert_comment_template(&tx, name, &template.body, category)?;
+        }
+        if let Some(quick_comments) = quick_comments {
+            save_quick_comment_templates(&tx, quick_comments)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Delete a template. Returns whether one was removed.
+    pub fn delete_comment_template(&self, id: i64) -> AppResult<bool> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let removed = conn.execute("DELETE FROM comment_templates WHERE id = ?1", params![id])?;
+
+        Ok(removed > 0)
+    }
+
+    /// Keep a submission to resume after signing in again
+    pub fn park_submission(&self, submission: &ParkedSubmission) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO parked_submissions
+             (owner, repo, pr_number, review_id, event, body, mode, login, parked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                submission.owner,
+                submission.repo,
+                submission.pr_number,
+                submission.review_id,
+                submission.event,
+                submission.body,
+                submission.mode.map(SubmissionMode::as_str),
+                submission.login,
+                submission.parked_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Parked submissions of every account, oldest first
+    pub fn get_parked_submissions(&self) -> AppResult<Vec<ParkedSubmission>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT owner, repo, pr_number, review_id, event, body, mode, login, parked_at
+             FROM parked_submissions ORDER BY parked_at ASC",
+        )?;
+        let parked = stmt
+            .query_map([], |row| {
+                Ok(ParkedSubmission {
+                    owner: row.get(0)?,
+                    repo: row.get(1)?,
+                    pr_number: row.get(2)?,
+                    review_id: row.get(3)?,
+                    event: row.get(4)?,
+                    body: row.get(5)?,
+                    mode: row
+                        .get::<_, Option<String>>(6)?
+                        .and_then(|mode| SubmissionMode::from_name(&mode)),
+                    login: row.get(7)?,
+                    parked_at: row.get(8)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(parked)
+    }
+
+    /// Returns whether a parked submission was removed.
+    pub fn remove_parked_submission(&self, owner: &str, repo: &str, pr_number: u64) -> AppResult<bool> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let removed = conn.execute(
+            "DELETE FROM parked_submissions WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+            params![owner, repo, pr_number],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Remember when I last looked at a PR's comments
+    pub fn record_visit(&self, owner: &str, repo: &str, pr_number: u64, seen_at: &str) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT INTO pr_visits (owner, repo, pr_number, last_seen_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(owner, repo, pr_number) DO UPDATE SET last_seen_at = excluded.last_seen_at",
+            params![owner, repo, pr_number, seen_at],
+        )?;
+
+        Ok(())
+    }
+
+    /// When I last looked at a PR, if ever
+    pub fn get_last_visit(&self, owner: &str, repo: &str, pr_number: u64) -> AppResult<Option<String>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let seen_at = conn
+            .query_row(
+                "SELECT last_seen_at FROM pr_visits WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+                params![owner, repo, pr_number],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(seen_at)
+    }
+
+    /// Last visits to every PR of a repository I have looked at, keyed by PR number
+    pub fn get_last_visits(&self, owner: &str, repo: &str) -> AppResult<HashMap<u64, String>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT pr_number, last_seen_at FROM pr_visits WHERE owner = ?1 AND repo = ?2",
+        )?;
+        let visits = stmt
+            .query_map(params![owner, repo], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(visits)
+    }
+
+    /// Make `profile` the one used on a PR, or go back to none with `None`
+    pub fn set_active_profile(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        profile: Option<&str>,
+    ) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        match profile {
+            Some(profile) => conn.execute(
+                "INSERT INTO active_profiles (owner, repo, pr_number, profile, set_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(owner, repo, pr_number) DO UPDATE
+                 SET profile = excluded.profile, set_at = excluded.set_at",
+                params![owner, repo, pr_number, profile, Utc::now().to_rfc3339()],
+            )?,
+            None => conn.execute(
+                "DELETE FROM active_profiles WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+                params![owner, repo, pr_number],
+            )?,
+        };
+
+        Ok(())
+    }
+
+    /// Name of the profile used on a PR, if one was chosen
+    pub fn get_active_profile(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> AppResult<Option<String>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let profile = conn
+            .query_row(
+                "SELECT profile FROM active_profiles
+                 WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+                params![owner, repo, pr_number],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(profile)
+    }
+
+    /// Number of unsubmitted comments per PR of `owner/repo`. PRs without local
+    /// comments are absent from the map.
+    pub fn get_pending_comment_counts(&self, owner: &str, repo: &str) -> AppResult<HashMap<u64, usize>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT pr_number, COUNT(*) FROM review_comments
+             WHERE owner = ?1 AND repo = ?2 AND deleted = 0
+             GROUP BY pr_number",
+        )?;
+        let counts = stmt
+            .query_map(params![owner, repo], |row| {
+                Ok((row.get(0)?, row.get::<_, i64>(1)? as usize))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(counts)
+    }
+
+    /// Number of unsubmitted comments per local review, keyed by `(owner, repo, pr_number)`.
+    pub fn get_all_pending_comment_counts(&self) -> AppResult<HashMap<(String, String, u64), usize>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT owner, repo, pr_number, COUNT(*) FROM review_comments
+             WHERE deleted = 0
+             GROUP BY owner, repo, pr_number",
+        )?;
+        let counts = stmt
+            .query_map([], |row| {
+                Ok(((row.get(0)?, row.get(1)?, row.get(2)?), row.get::<_, i64>(3)? as usize))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(counts)
+    }
+
+    /// The pseudo pull request number local reviews of `folder`, a canonical path, are
+    /// stored under. A folder seen before keeps its number. A new one gets `preferred`, or
+    /// the next free number when another folder holds it, except that a folder already
+    /// reviewed under the old fixed number 1 keeps that. Those reviews recorded the folder
+    /// as it was entered, so `entered` is matched too.
+    pub fn local_folder_number(
+        &self,
+        folder: &str,
+        entered: &str,
+        preferred: u64,
+    ) -> AppResult<u64> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let existing = conn
+            .query_row(
+                "SELECT pr_number FROM local_folders WHERE folder = ?1",
+                params![folder],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(number) = existing {
+            return Ok(number);
+        }
+
+        let legacy = conn
+            .query_row(
+                "SELECT 1 FROM review_metadata
+                 WHERE owner = '__local__' AND repo = 'local' AND pr_number = 1
+                   AND local_folder IN (?1, ?2)",
+                params![folder, entered],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        let mut number = if legacy { 1 } else { preferred.clamp(2, LOCAL_MAX_NUMBER) };
+        loop {
+            let taken = conn
+                .query_row(
+                    "SELECT 1 FROM local_folders WHERE pr_number = ?1",
+                    params![number],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some();
+            if !taken {
+                break;
+            }
+            number = if number >= LOCAL_MAX_NUMBER { 2 } else { number + 1 };
+        }
+
+        conn.execute(
+            "INSERT INTO local_folders (folder, pr_number) VALUES (?1, ?2)",
+            params![folder, number],
+        )?;
+        Ok(number)
+    }
+
+    /// Read a raw setting value by key
+    pub fn get_setting(&self, key: &str) -> AppResult<Option<String>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let value = conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(value)
+    }
+
+    /// Insert or replace a raw setting value
+    pub fn set_setting(&self, key: &str, value: &str) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![key, value, Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Forget data derived from GitHub: rendered markdown and PR visit times.
+    pub fn clear_cached_data(&self) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute_batch(
+            "BEGIN;
+             DELETE FROM rendered_markdown;
+             DELETE FROM pr_visits;
+             COMMIT;",
+        )?;
+
+        Ok(())
+    }
+
+    /// Delete every local review and what hangs off it, including the trash.
+    pub fn clear_local_reviews(&self) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        conn.execute_batch(
+            "BEGIN;
+             DELETE FROM review_comments;
+             DELETE FROM review_metadata;
+             DELETE FROM trashed_review_comments;
+             DELETE FROM trashed_reviews;
+             DELETE FROM bookmarks;
+             DELETE FROM parked_submissions;
+             DELETE FROM review_turnaround;
+             DELETE FROM usage_events;
+             DELETE FROM review_commits;
+             DELETE FROM reviewed_hunks;
+             DELETE FROM file_hunk_counts;
+             DELETE FROM comment_anchors;
+             DELETE FROM active_profiles;
+             COMMIT;",
+        )?;
+
+        Ok(())
+    }
+
+    pub fn clear_settings(&self) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        conn.execute("DELETE FROM settings", [])?;
+        conn.execute("DELETE FROM comment_templates", [])?;
+        Ok(())
+    }
+
+    /// Rows of SQLite's integrity check; a single `ok` when the database is sound.
+    pub fn integrity_check(&self) -> AppResult<Vec<String>> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+
+        let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+        let rows = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(rows)
+    }
+
+    /// Delete review logs and the crash log.
+    pub fn clear_logs(&self) -> AppResult<()> {
+        if self.log_dir.exists() {
+            std::fs::remove_dir_all(&self.log_dir)?;
+        }
+        std::fs::create_dir_all(&self.log_dir)?;
+        Ok(())
+    }
+
+    /// Rewrite the database file so deleted rows do not linger in free pages.
+    pub fn compact(&self) -> AppResult<()> {
+        let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+        conn.execute_batch("VACUUM;")?;
+        Ok(())
+    }
+
+    fn get_log_path(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        index: i32,
+        local_folder: Option<&str>,
+    ) -> PathBuf {
+        let is_local_folder = owner == "__local__" && repo == "local";
+
+        let filename = if is_local_folder {
+            let folder_name = local_folder
+                .and_then(|path| Path::new(path).file_name().and_then(|name| name.to_str()))
+                .unwrap_or("local-folder");
+
+            let safe_folder_name: String = folder_name
+                .chars()
+                .map(|c| match c {
+                    '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+                    _ => c,
+                })
+                .collect();
+
+            let safe_folder_name = safe_folder_name.trim();
+            let safe_folder_name = if safe_folder_name.is_empty() {
+                "local-folder"
+            } else {
+                safe_folder_name
+            };
+
+            if index == 0 {
+                format!("{}.log", safe_folder_name)
+            } else {
+                format!("{}-{}.log", safe_folder_name, index)
+            }
+        } else if index == 0 {
+            format!("{}-{}-{}.log", owner, repo, pr_number)
+        } else {
+            format!("{}-{}-{}-{}.log", owner, repo, pr_number, index)
+        };
+
+        self.log_dir.join(filename)
+    }
+    
+    fn find_next_log_index(&self, owner: &str, repo: &str, pr_number: u64, local_folder: Option<&str>) -> i32 {
+        let mut index = 0;
+        loop {
+            let log_path = self.get_log_path(owner, repo, pr_number, index, local_folder);
+            if !log_path.exists() {
+                return index;
+            }
+            index += 1;
+        }
+    }
+    
+    async fn fetch_pr_title(&self, owner: &str, repo: &str, pr_number: u64) -> AppResult<String> {
+        let token = require_token()?;
+        let client = reqwest::Client::builder()
+            .user_agent("github-review-app")
+            .build()?;
+        
+        let url = format!("https://api.github.com/repos/{owner}/{repo}/pulls/{pr_number}");
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .send_recorded()
+            .await?;
+        
+        if !response.status().is_success() {
+            return Err(AppError::Api(format!("Failed to fetch PR title: {}", response.status())));
+        }
+        
+        let pr_data: serde_json::Value = response.json().await?;
+        let title = pr_data["title"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        
+        Ok(title)
+    }
+    
+    async fn write_log(&self, owner: &str, repo: &str, pr_number: u64) -> AppResult<()> {
+        tracing::info!("Writing log file for {}/{}#{}", owner, repo, pr_number);
+        let (metadata, comments, anchors) = {
+            let conn = self.conn.lock().map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+            
+            let metadata: ReviewMetadata = conn.query_row(
+                "SELECT owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, due_at
                  FROM review_metadata
                  WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
                 params![owner, repo, pr_number],
@@ -810,12 +2650,13 @@ impl ReviewStorage {
                         local_folder: row.get(5)?,
                         created_at: row.get(6)?,
                         log_file_index: row.get(7)?,
+                        due_at: row.get(8)?,
                     })
                 },
             )?;
             
             let mut stmt = conn.prepare(
-                "SELECT id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id
+                "SELECT id, owner, repo, pr_number, file_path, line_number, side, body, commit_id, created_at, updated_at, deleted, in_reply_to_id, category, general, suggestion
                  FROM review_comments
                  WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3
                  ORDER BY file_path, line_number"
@@ -837,11 +2678,15 @@ impl ReviewStorage {
                         updated_at: row.get(10)?,
                         deleted: row.get::<_, i64>(11)? != 0,
                         in_reply_to_id: row.get(12).ok(),
+                        category: row.get(13)?,
+                        general: row.get::<_, i64>(14)? != 0,
+                        suggestion: parse_suggestion(row.get(15)?),
                     })
                 })?
                 .collect::<Result<Vec<_>, _>>()?;
+            let anchors = load_comment_anchors(&conn, owner, repo, pr_number)?;
             
-            (metadata, comments)
+            (metadata, comments, anchors)
         };
         
         let log_path = self.get_log_path(owner, repo, pr_number, metadata.log_file_index, metadata.local_folder.as_deref());
@@ -859,30 +2704,30 @@ impl ReviewStorage {
         
         let mut content = String::new();
         if is_local_folder {
-            content.push_str("# Review\n");
+            content.push_str(&format!("# {}\n", t(Msg::LogReview)));
             if let Some(local_folder) = &metadata.local_folder {
-                content.push_str(&format!("# Local folder: {}\n", local_folder));
+                content.push_str(&format!("# {}: {}\n", t(Msg::LogLocalFolder), local_folder));
             } else {
-                content.push_str("# Local folder: \n");
+                content.push_str(&format!("# {}: \n", t(Msg::LogLocalFolder)));
             }
         } else if pr_title.is_empty() {
-            content.push_str(&format!("# Review for PR #{}\n", pr_number));
-            content.push_str(&format!("# URL: https://github.com/{}/{}/pull/{}\n", owner, repo, pr_number));
-            content.push_str(&format!("# Repository: {}/{}\n", owner, repo));
+            content.push_str(&format!("# {} #{}\n", t(Msg::LogReviewForPr), pr_number));
+            content.push_str(&format!("# {}: https://github.com/{}/{}/pull/{}\n", t(Msg::LogUrl), owner, repo, pr_number));
+            content.push_str(&format!("# {}: {}/{}\n", t(Msg::LogRepository), owner, repo));
         } else {
-            content.push_str(&format!("# Review for PR #{}: {}\n", pr_number, pr_title));
-            content.push_str(&format!("# URL: https://github.com/{}/{}/pull/{}\n", owner, repo, pr_number));
-            content.push_str(&format!("# Repository: {}/{}\n", owner, repo));
+            content.push_str(&format!("# {} #{}: {}\n", t(Msg::LogReviewForPr), pr_number, pr_title));
+            content.push_str(&format!("# {}: https://github.com/{}/{}/pull/{}\n", t(Msg::LogUrl), owner, repo, pr_number));
+            content.push_str(&format!("# {}: {}/{}\n", t(Msg::LogRepository), owner, repo));
         }
-        content.push_str(&format!("# Created: {}\n", metadata.created_at));
+        content.push_str(&format!("# {}: {}\n", t(Msg::LogCreated), display_time(&metadata.created_at)));
         if !is_local_folder {
-            content.push_str(&format!("# Commit: {}\n", metadata.commit_id));
+            content.push_str(&format!("# {}: {}\n", t(Msg::LogCommit), metadata.commit_id));
         }
         if let Some(body) = &metadata.body {
-            content.push_str(&format!("# Review Body: {}\n", body));
+            content.push_str(&format!("# {}: {}\n", t(Msg::LogReviewBody), body));
         }
         let active_count = comments.iter().filter(|c| !c.deleted).count();
-        content.push_str(&format!("# Total Comments: {}\n\n", active_count));
+        content.push_str(&format!("# {}: {}\n\n", t(Msg::LogTotalComments), active_count));
         
         let mut current_file: Option<String> = None;
         for comment in comments {
@@ -894,23 +2739,40 @@ impl ReviewStorage {
             // File-level comments (line_number = 0) should show "Overall" instead of "Line 0"
             let is_file_level = comment.line_number == 0;
             let line_label = if is_file_level {
-                "Overall".to_string()
+                t(Msg::LogOverall).to_string()
             } else {
-                format!("Line {}", comment.line_number)
+                format!("{} {}", t(Msg::LogLine), comment.line_number)
             };
 
             let side_label = if !is_file_level && comment.side.eq_ignore_ascii_case("LEFT") {
-                " (ORIGINAL)"
+                format!(" ({})", t(Msg::LogOriginal))
             } else {
-                ""
+                String::new()
             };
             
-            let deleted_prefix = if comment.deleted { "DELETED - " } else { "" };
+            let deleted_prefix = if comment.deleted {
+                format!("{} - ", t(Msg::LogDeleted))
+            } else {
+                String::new()
+            };
             
+            let category_prefix = comment
+                .category
+                .as_ref()
+                .map(|category| format!("[{category}] "))
+                .unwrap_or_default();
+
             content.push_str(&format!(
-                "    {}{}{}: {}\n",
-                deleted_prefix, line_label, side_label, comment.body
+                "    {}{}{}: {}{}\n",
+                deleted_prefix, line_label, side_label, category_prefix, comment.body
             ));
+            if let Some(anchor) = anchors.get(&comment.id) {
+                content.push_str(&format!("      {}: blob {}", t(Msg::LogAnchor), anchor.blob_sha));
+                if let Some(line_hash) = &anchor.line_hash {
+                    content.push_str(&format!(", line sha256 {line_hash}"));
+                }
+                content.push('\n');
+            }
         }
         
         // Overwrite log file with current state
@@ -925,6 +2787,218 @@ impl ReviewStorage {
 use std::sync::OnceLock;
 static REVIEW_STORAGE: OnceLock<ReviewStorage> = OnceLock::new();
 
+/// The stored `suggestion` JSON. A value that no longer parses is dropped rather than
+/// failing the whole read.
+fn parse_suggestion(json: Option<String>) -> Option<Suggestion> {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+}
+
+fn load_comment_anchors(
+    conn: &Connection,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+) -> AppResult<HashMap<i64, CommentAnchor>> {
+    let mut stmt = conn.prepare(
+        "SELECT a.comment_id, a.blob_sha, a.line_hash
+         FROM comment_anchors a
+         JOIN review_comments c ON c.id = a.comment_id
+         WHERE c.owner = ?1 AND c.repo = ?2 AND c.pr_number = ?3",
+    )?;
+    let anchors = stmt
+        .query_map(params![owner, repo, pr_number], |row| {
+            Ok((
+                row.get(0)?,
+                CommentAnchor {
+                    blob_sha: row.get(1)?,
+                    line_hash: row.get(2)?,
+                },
+            ))
+        })?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(anchors)
+}
+
+/// The trimmed name, once the template is known to be usable
+fn validate_comment_template<'a>(
+    name: &'a str,
+    body: &str,
+    category: Option<&str>,
+) -> AppResult<&'a str> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput(t(Msg::CommentTemplateNameRequired).to_string()));
+    }
+    if body.trim().is_empty() {
+        return Err(AppError::InvalidInput(tf(Msg::CommentTemplateEmptyBody, &[&name])));
+    }
+    if let Some(category) = category {
+        crate::categories::validate_category(category)?;
+    }
+    Ok(name)
+}
+
+/// Names are matched ignoring case, so the list never shows two that look the same
+fn ensure_template_name_free(conn: &Connection, name: &str, except: Option<i64>) -> AppResult<()> {
+    let taken: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM comment_templates
+                       WHERE name = ?1 COLLATE NOCASE AND (?2 IS NULL OR id != ?2))",
+        params![name, except],
+        |row| row.get(0),
+    )?;
+    if taken {
+        return Err(AppError::InvalidInput(tf(Msg::CommentTemplateNameTaken, &[&name])));
+    }
+    Ok(())
+}
+
+/// Insert or update the template called `name`, returning its id
+fn upsert_comment_template(
+    conn: &Connection,
+    name: &str,
+    body: &str,
+    category: Option<&str>,
+) -> AppResult<i64> {
+    let now = Utc::now().to_rfc3339();
+    let existing: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM comment_templates WHERE name = ?1 COLLATE NOCASE",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match existing {
+        Some(id) => {
+            conn.execute(
+                "UPDATE comment_templates SET body = ?1, category = ?2, updated_at = ?3
+                 WHERE id = ?4",
+                params![body, category, now, id],
+            )?;
+            Ok(id)
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO comment_templates (name, body, category, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?4)",
+                params![name, body, category, now],
+            )?;
+            Ok(conn.last_insert_rowid())
+        }
+    }
+}
+
+fn save_quick_comment_templates(
+    conn: &Connection,
+    templates: &[QuickCommentTemplate],
+) -> AppResult<()> {
+    conn.execute(
+        "UPDATE comment_templates SET quick_key = NULL, quick_position = NULL
+         WHERE quick_key IS NOT NULL",
+        [],
+    )?;
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES (?1, 'true', ?2)",
+        params![QUICK_COMMENTS_SAVED_KEY, Utc::now().to_rfc3339()],
+    )?;
+    for (position, template) in templates.iter().enumerate() {
+        let name = validate_comment_template(
+            &template.label,
+            &template.body,
+            template.category.as_deref(),
+        )?;
+        let id = upsert_comment_template(conn, name, &template.body, template.category.as_deref())?;
+        conn.execute(
+            "UPDATE comment_templates SET quick_key = ?1, quick_position = ?2 WHERE id = ?3",
+            params![template.id, position as i64, id],
+        )?;
+    }
+    Ok(())
+}
+
+fn comment_template_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CommentTemplate> {
+    Ok(CommentTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        body: row.get(2)?,
+        category: row.get(3)?,
+        quick_key: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+fn bookmark_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Bookmark> {
+    Ok(Bookmark {
+        owner: row.get(0)?,
+        repo: row.get(1)?,
+        pr_number: row.get(2)?,
+        source: row.get(3)?,
+        comment_id: row.get(4)?,
+        note: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+/// Copy a review and its live comments into the trash tables, then remove them from the
+/// active tables, all in one transaction.
+fn move_review_to_trash(
+    conn: &mut Connection,
+    meta: &ReviewMetadata,
+    reason: &str,
+) -> AppResult<()> {
+    let tx = conn.transaction()?;
+
+    tx.execute(
+        "INSERT INTO trashed_reviews (owner, repo, pr_number, commit_id, body, local_folder, created_at, log_file_index, reason, trashed_at, due_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            meta.owner,
+            meta.repo,
+            meta.pr_number,
+            meta.commit_id,
+            meta.body,
+            meta.local_folder,
+            meta.created_at,
+            meta.log_file_index,
+            reason,
+            Utc::now().to_rfc3339(),
+            meta.due_at
+        ],
+    )?;
+    let trash_id = tx.last_insert_rowid();
+    tx.execute(
+        "INSERT INTO trashed_review_comments (trash_id, id, file_path, line_number, side, body, commit_id, created_at, updated_at, in_reply_to_id, category, general, suggestion)
+         SELECT ?1, id, file_path, line_number, side, body, commit_id, created_at, updated_at, in_reply_to_id, category, general, suggestion
+         FROM review_comments
+         WHERE owner = ?2 AND repo = ?3 AND pr_number = ?4 AND deleted = 0",
+        params![trash_id, meta.owner, meta.repo, meta.pr_number],
+    )?;
+    tx.execute(
+        "DELETE FROM review_comments WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+        params![meta.owner, meta.repo, meta.pr_number],
+    )?;
+    tx.execute(
+        "DELETE FROM review_metadata WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+        params![meta.owner, meta.repo, meta.pr_number],
+    )?;
+    tx.execute(
+        "DELETE FROM active_profiles WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+        params![meta.owner, meta.repo, meta.pr_number],
+    )?;
+    // Hunk marks are not kept in the trash; a restored review starts its progress over
+    tx.execute(
+        "DELETE FROM reviewed_hunks WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+        params![meta.owner, meta.repo, meta.pr_number],
+    )?;
+    tx.execute(
+        "DELETE FROM file_hunk_counts WHERE owner = ?1 AND repo = ?2 AND pr_number = ?3",
+        params![meta.owner, meta.repo, meta.pr_number],
+    )?;
+    tx.commit()?;
+
+    Ok(())
+}
+
 pub fn init_storage(data_dir: &Path) -> AppResult<()> {
     let storage = ReviewStorage::new(data_dir)?;
     REVIEW_STORAGE
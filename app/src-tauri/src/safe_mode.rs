@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppResult;
+use crate::review_storage::ReviewStorage;
+
+/// Sent at startup with the safe mode status when the app starts in safe mode.
+pub const SAFE_MODE_EVENT: &str = "safe-mode";
+
+/// Crash-loop bookkeeping, kept outside the database since the database may be the cause.
+const STATE_FILE: &str = "startup_state.json";
+
+/// A panic this soon after launch counts as a crash at startup.
+const STARTUP_WINDOW: Duration = Duration::from_secs(60);
+
+/// Crashes at startup in a row after which the app starts in safe mode.
+pub const CRASH_LOOP_THRESHOLD: u32 = 3;
+
+/// The panic that ended the last run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashSummary {
+    pub message: String,
+    pub location: String,
+    /// RFC 3339 UTC.
+    pub occurred_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupState {
+    /// Launches in a row that panicked within the startup window.
+    pub startup_crashes: u32,
+    pub last_crash: Option<CrashSummary>,
+}
+
+impl StartupState {
+    /// Note `crash`, counting it towards a crash loop when it happened during startup.
+    pub fn with_crash(mut self, crash: CrashSummary, during_startup: bool) -> Self {
+        if during_startup {
+            self.startup_crashes = self.startup_crashes.saturating_add(1);
+        }
+        self.last_crash = Some(crash);
+        self
+    }
+
+    /// The app made it through startup, so earlier crashes no longer form a loop.
+    pub fn started_cleanly(mut self) -> Self {
+        self.startup_crashes = 0;
+        self
+    }
+
+    pub fn is_crash_loop(&self) -> bool {
+        self.startup_crashes >= CRASH_LOOP_THRESHOLD
+    }
+}
+
+/// Whether this run is in safe mode, and why.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SafeModeStatus {
+    /// Cached state and background jobs were skipped at startup.
+    pub active: bool,
+    pub startup_crashes: u32,
+    pub last_crash: Option<CrashSummary>,
+}
+
+/// Result of `PRAGMA integrity_check` on the review database.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatabaseCheck {
+    pub ok: bool,
+    /// What SQLite reported wrong; empty when `ok`.
+    pub problems: Vec<String>,
+}
+
+struct Startup {
+    data_dir: PathBuf,
+    started_at: Instant,
+    status: SafeModeStatus,
+}
+
+static STARTUP: OnceLock<Startup> = OnceLock::new();
+
+pub fn read_state(data_dir: &Path) -> StartupState {
+    let path = data_dir.join(STATE_FILE);
+    let Ok(raw) = std::fs::read_to_string(&path) else {
+        return StartupState::default();
+    };
+    match serde_json::from_str(&raw) {
+        Ok(state) => state,
+        Err(err) => {
+            tracing::warn!(error = %err, "startup state is unreadable; starting afresh");
+            StartupState::default()
+        }
+    }
+}
+
+pub fn write_state(data_dir: &Path, state: &StartupState) -> AppResult<()> {
+    std::fs::create_dir_all(data_dir)?;
+    std::fs::write(data_dir.join(STATE_FILE), serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// Decide at launch whether to start in safe mode. Call before anything that could
+/// panic, so crashes during the rest of startup are counted.
+pub fn begin_startup(data_dir: &Path) -> SafeModeStatus {
+    let state = read_state(data_dir);
+    let status = SafeModeStatus {
+        active: state.is_crash_loop(),
+        startup_crashes: state.startup_crashes,
+        last_crash: state.last_crash,
+    };
+    let _ = STARTUP.set(Startup {
+        data_dir: data_dir.to_path_buf(),
+        started_at: Instant::now(),
+        status: status.clone(),
+    });
+    status
+}
+
+pub fn status() -> SafeModeStatus {
+    STARTUP.get().map(|startup| startup.status.clone()).unwrap_or_default()
+}
+
+pub fn is_active() -> bool {
+    STARTUP.get().is_some_and(|startup| startup.status.active)
+}
+
+/// Called from the panic hook. Failures are ignored; there is nowhere left to report them.
+pub fn record_crash(message: &str, location: &str) {
+    let Some(startup) = STARTUP.get() else {
+        return;
+    };
+    let crash = CrashSummary {
+        message: message.to_string(),
+        location: location.to_string(),
+        occurred_at: Utc::now().to_rfc3339(),
+    };
+    let during_startup = startup.started_at.elapsed() < STARTUP_WINDOW;
+    let state = read_state(&startup.data_dir).with_crash(crash, during_startup);
+    let _ = write_state(&startup.data_dir, &state);
+}
+
+/// Once the startup window passes without a panic, reset the crash count. Not started in
+/// safe mode, where surviving startup says nothing about the skipped parts.
+pub fn spawn_clean_start_timer() {
+    tauri::async_runtime::spawn(async {
+        tokio::time::sleep(STARTUP_WINDOW).await;
+        if let Err(e) = reset_crash_count() {
+            tracing::warn!("Failed to reset the startup crash count: {}", e);
+        }
+    });
+}
+
+/// Start normally next launch. The current run stays in safe mode until restarted.
+pub fn reset_crash_count() -> AppResult<()> {
+    let Some(startup) = STARTUP.get() else {
+        return Ok(());
+    };
+    let state = read_state(&startup.data_dir);
+    if state.startup_crashes == 0 {
+        return Ok(());
+    }
+    write_state(&startup.data_dir, &state.started_cleanly())
+}
+
+pub fn check_database(storage: &ReviewStorage) -> AppResult<DatabaseCheck> {
+    let problems: Vec<String> = storage
+        .integrity_check()?
+        .into_iter()
+        .filter(|line| line != "ok")
+        .collect();
+    Ok(DatabaseCheck {
+        ok: problems.is_empty(),
+        problems,
+    })
+}
+
+/// Drop data derived from GitHub and the saved session, the usual suspects when the app
+/// crashes while restoring where it left off. Local reviews and settings are kept.
+pub fn purge_cache(storage: &ReviewStorage) -> AppResult<()> {
+    crate::auth::clear_listing_caches();
+    storage.clear_cached_data()?;
+    if let Some(cache) = crate::pr_cache::get() {
+        cache.clear()?;
+    }
+    crate::session::save(storage, crate::session::SessionState::default())?;
+    storage.compact()?;
+    tracing::info!("Cached data and the saved session were purged");
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use chrono::DateTime;
+use serde::Serialize;
+
+/// The commit one of my reviews of a PR was made on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewedCommit {
+    pub commit_id: String,
+    /// RFC 3339.
+    pub submitted_at: String,
+}
+
+/// A file that changed between the reviewed commit and the current head.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangedFile {
+    pub path: String,
+    /// Name at the reviewed commit, for renames.
+    pub previous_path: Option<String>,
+    pub status: String,
+    pub additions: u64,
+    pub deletions: u64,
+    /// Unified diff from the reviewed commit; `None` for binary or very large files.
+    pub patch: Option<String>,
+}
+
+/// What changed, in the files I commented on, since my last review of a PR.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangesSinceReview {
+    /// `None` when I have not reviewed the PR yet, so there is no second pass to make.
+    pub reviewed_commit: Option<String>,
+    pub head_sha: String,
+    /// Files I commented on that changed since, sorted by path.
+    pub files: Vec<ChangedFile>,
+    /// Files I commented on that are unchanged since, sorted.
+    pub unchanged_files: Vec<String>,
+}
+
+/// Whichever review was submitted last. A timestamp that does not parse counts as older.
+pub fn latest(a: Option<ReviewedCommit>, b: Option<ReviewedCommit>) -> Option<ReviewedCommit> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let a_at = DateTime::parse_from_rfc3339(&a.submitted_at).ok();
+            let b_at = DateTime::parse_from_rfc3339(&b.submitted_at).ok();
+            Some(if b_at > a_at { b } else { a })
+        }
+        (a, b) => a.or(b),
+    }
+}
+
+/// Keep the changes to files I commented on, matching renamed files by either name, and
+/// list the commented files that did not change.
+pub fn build(
+    reviewed_commit: Option<String>,
+    head_sha: &str,
+    changed: Vec<ChangedFile>,
+    commented_paths: &HashSet<String>,
+) -> ChangesSinceReview {
+    let mut files: Vec<ChangedFile> = changed
+        .into_iter()
+        .filter(|file| {
+            commented_paths.contains(&file.path)
+                || file
+                    .previous_path
+                    .as_ref()
+                    .is_some_and(|previous| commented_paths.contains(previous))
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let touched: HashSet<&str> = files
+        .iter()
+        .flat_map(|file| std::iter::once(file.path.as_str()).chain(file.previous_path.as_deref()))
+        .collect();
+    let mut unchanged_files: Vec<String> = commented_paths
+        .iter()
+        .filter(|path| !touched.contains(path.as_str()))
+        .cloned()
+        .collect();
+    unchanged_files.sort();
+
+    ChangesSinceReview {
+        reviewed_commit,
+        head_sha: head_sha.to_string(),
+        files,
+        unchanged_files,
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::diff::added_lines;
+use crate::models::{PullRequestFile, SecretFinding};
+
+struct SecretRule {
+    id: &'static str,
+    description: &'static str,
+    pattern: &'static str,
+}
+
+/// Patterns for common credential formats, modelled on the gitleaks default rule set.
+const RULES: &[SecretRule] = &[
+    SecretRule {
+        id: "private-key",
+        description: "Private key block",
+        pattern: r"-----BEGIN ((RSA|DSA|EC|OPENSSH|PGP|ENCRYPTED) )?PRIVATE KEY( BLOCK)?-----",
+    },
+    SecretRule {
+        id: "aws-access-key-id",
+        description: "AWS access key ID",
+        pattern: r"\b(AKIA|ASIA|AGPA|AIDA|AROA|ANPA|ANVA)[0-9A-Z]{16}\b",
+    },
+    SecretRule {
+        id: "github-token",
+        description: "GitHub token",
+        pattern: r"\b(gh[pousr]_[A-Za-z0-9]{36,255}|github_pat_[A-Za-z0-9_]{22,255})\b",
+    },
+    SecretRule {
+        id: "slack-token",
+        description: "Slack token",
+        pattern: r"\bxox[abposr]-[0-9A-Za-z-]{10,}\b",
+    },
+    SecretRule {
+        id: "slack-webhook",
+        description: "Slack webhook URL",
+        pattern: r"https://hooks\.slack\.com/services/T[A-Za-z0-9_]+/B[A-Za-z0-9_]+/[A-Za-z0-9_]+",
+    },
+    SecretRule {
+        id: "google-api-key",
+        description: "Google API key",
+        pattern: r"\bAIza[0-9A-Za-z_-]{35}\b",
+    },
+    SecretRule {
+        id: "stripe-secret-key",
+        description: "Stripe secret key",
+        pattern: r"\b(sk|rk)_live_[0-9A-Za-z]{24,}\b",
+    },
+    SecretRule {
+        id: "azure-storage-key",
+        description: "Azure storage account key",
+        pattern: r"AccountKey=[A-Za-z0-9+/]{86}==",
+    },
+    SecretRule {
+        id: "jwt",
+        description: "JSON Web Token",
+        pattern: r"\beyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}",
+    },
+    SecretRule {
+        id: "url-credentials",
+        description: "Password embedded in a URL",
+        pattern: r"[a-z][a-z0-9+.-]*://[^\s:/@]+:[^\s@/]{6,}@[^\s/]+",
+    },
+    SecretRule {
+        id: "generic-secret",
+        description: "Secret-like assignment",
+        pattern: r#"(?i)\b(api[_-]?key|secret|passw(or)?d|access[_-]?token|auth[_-]?token|client[_-]?secret)\b["']?\s*[:=]\s*["']?[A-Za-z0-9/+_=-]{16,}"#,
+    },
+];
+
+/// Substrings that mark a match as a documentation placeholder rather than a real secret.
+const PLACEHOLDER_MARKERS: &[&str] = &[
+    "example", "sample", "dummy", "placeholder", "xxxx", "****", "your_", "your-", "<", "${",
+    "{{", "redacted", "changeme",
+];
+
+fn compiled_rules() -> &'static [(&'static SecretRule, Regex)] {
+    static COMPILED: OnceLock<Vec<(&'static SecretRule, Regex)>> = OnceLock::new();
+    COMPILED.get_or_init(|| {
+        RULES
+            .iter()
+            .map(|rule| {
+                let regex = Regex::new(rule.pattern).expect("secret rule pattern is valid");
+                (rule, regex)
+            })
+            .collect()
+    })
+}
+
+fn is_placeholder(matched: &str) -> bool {
+    let lower = matched.to_ascii_lowercase();
+    PLACEHOLDER_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+/// Keep the first four characters so the reviewer can find the match, mask the rest.
+fn redact(matched: &str) -> String {
+    let visible: String = matched.chars().take(4).collect();
+    let hidden = matched.chars().count().saturating_sub(4).min(16);
+    format!("{}{}", visible, "*".repeat(hidden))
+}
+
+/// Scan one line of text, returning a finding for every rule that matches.
+pub fn scan_line(path: &str, line_number: Option<u64>, text: &str) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+    for (rule, regex) in compiled_rules() {
+        if let Some(found) = regex.find(text) {
+            if is_placeholder(found.as_str()) {
+                continue;
+            }
+            findings.push(SecretFinding {
+                path: path.to_string(),
+                line: line_number,
+                rule_id: rule.id.to_string(),
+                description: rule.description.to_string(),
+                excerpt: redact(found.as_str()),
+                severity: "high".to_string(),
+            });
+        }
+    }
+    findings
+}
+
+/// Scan full file content; line numbers are 1-based.
+pub fn scan_content(path: &str, content: &str) -> Vec<SecretFinding> {
+    content
+        .lines()
+        .enumerate()
+        .flat_map(|(index, line)| scan_line(path, Some(index as u64 + 1), line))
+        .collect()
+}
+
+/// Scan only the lines a patch adds, reporting their line numbers in the head version.
+pub fn scan_patch(path: &str, patch: &str) -> Vec<SecretFinding> {
+    added_lines(patch)
+        .into_iter()
+        .flat_map(|(line_number, text)| scan_line(path, Some(line_number), text))
+        .collect()
+}
+
+/// Scan PR files, preferring the patch (only new lines) and falling back to full
+/// content when no patch is available, as in local folder mode. Removed files add
+/// nothing, so they are skipped even when their base content is loaded.
+pub fn scan_files(files: &[PullRequestFile]) -> Vec<SecretFinding> {
+    files
+        .iter()
+        .filter(|file| file.status != "removed")
+        .flat_map(|file| match (&file.patch, &file.head_content) {
+            (Some(patch), _) => scan_patch(&file.path, patch),
+            (None, Some(content)) => scan_content(&file.path, content),
+            (None, None) => Vec::new(),
+        })
+        .collect()
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::ReviewStorage;
+use crate::settings;
+
+pub const SESSION_KEY: &str = "session";
+
+/// Drafts longer than this are not kept in the session; the UI saves them as local
+/// comments long before they get there.
+const MAX_DRAFT_LEN: usize = 65_536;
+
+/// A comment being written when the session was saved.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftComment {
+    /// `None` for a general PR comment.
+    pub file_path: Option<String>,
+    pub line: Option<u64>,
+    /// Local comment being replied to, if any.
+    #[serde(default)]
+    pub in_reply_to_id: Option<i64>,
+    pub body: String,
+}
+
+/// Where the user was, saved as they move around so that reopening the app after a
+/// crash or restart puts them back there.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SessionState {
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub pr_number: Option<u64>,
+    /// Folder path for local directory mode.
+    pub local_folder: Option<String>,
+    pub selected_file: Option<String>,
+    /// First line of `selected_file` in view, scrolled back to on restore.
+    pub scroll_anchor_line: Option<u64>,
+    pub draft: Option<DraftComment>,
+    /// RFC 3339 UTC, set when saved.
+    pub saved_at: Option<String>,
+}
+
+impl SessionState {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.pr_number.is_some() && (self.owner.is_none() || self.repo.is_none()) {
+            return Err(AppError::InvalidInput(
+                "a session with a pull request needs its owner and repo".into(),
+            ));
+        }
+        if self.scroll_anchor_line == Some(0) {
+            return Err(AppError::InvalidInput("scroll_anchor_line starts at 1".into()));
+        }
+        if let Some(draft) = &self.draft {
+            if draft.body.len() > MAX_DRAFT_LEN {
+                return Err(AppError::InvalidInput(format!(
+                    "draft comment is longer than {MAX_DRAFT_LEN} bytes"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Nothing open, so there is nothing to restore.
+    pub fn is_empty(&self) -> bool {
+        self.pr_number.is_none() && self.local_folder.is_none()
+    }
+}
+
+/// The saved session, or `None` when there is nothing to restore.
+pub fn load(storage: &ReviewStorage) -> AppResult<Option<SessionState>> {
+    let session: SessionState = settings::load(storage, SESSION_KEY)?;
+    Ok((!session.is_empty()).then_some(session))
+}
+
+/// Save `session`, stamping when. An empty session clears the saved one.
+pub fn save(storage: &ReviewStorage, mut session: SessionState) -> AppResult<SessionState> {
+    session.validate()?;
+    if session.is_empty() {
+        session = SessionState::default();
+    } else {
+        session.saved_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    settings::save(storage, SESSION_KEY, &session)?;
+    Ok(session)
+}
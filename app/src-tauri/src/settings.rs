@@ -0,0 +1,232 @@
+use chrono_tz::Tz;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::i18n::{tf, Locale, Msg};
+use crate::review_storage::ReviewStorage;
+use crate::time_format;
+
+pub const PREFERENCES_KEY: &str = "preferences";
+
+pub const THEMES: &[&str] = &["system", "light", "dark"];
+pub const DIFF_VIEW_MODES: &[&str] = &["split", "unified"];
+pub const REVIEW_EVENTS: &[&str] = &["COMMENT", "APPROVE", "REQUEST_CHANGES"];
+pub const STALE_REVIEW_ACTIONS: &[&str] = &["off", "prompt", "clear"];
+pub const MARKDOWN_PREVIEWS: &[&str] = &["local", "github"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DiffViewDefaults {
+    pub mode: String,
+    pub ignore_whitespace: bool,
+    pub wrap_lines: bool,
+    pub context_lines: u32,
+    /// `local` renders markdown previews in the app, which also works offline; `github`
+    /// shows GitHub's own rendering from `cmd_get_rendered_file_contents`.
+    pub markdown_preview: String,
+}
+
+impl Default for DiffViewDefaults {
+    fn default() -> Self {
+        Self {
+            mode: "split".to_string(),
+            ignore_whitespace: false,
+            wrap_lines: true,
+            context_lines: 3,
+            markdown_preview: "local".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SubmissionConfirmations {
+    pub confirm_comment: bool,
+    pub confirm_approve: bool,
+    pub confirm_request_changes: bool,
+}
+
+impl Default for SubmissionConfirmations {
+    fn default() -> Self {
+        Self {
+            confirm_comment: false,
+            confirm_approve: true,
+            confirm_request_changes: true,
+        }
+    }
+}
+
+/// Comment posted when re-requesting your own review after the author pushes fixes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ReviewFollowup {
+    pub post_comment: bool,
+    /// Supports `{{author}}`, `{{reviewer}}`, `{{head_sha}}` and `{{short_sha}}`.
+    pub comment_template: String,
+}
+
+impl Default for ReviewFollowup {
+    fn default() -> Self {
+        Self {
+            post_comment: true,
+            comment_template: "Thanks @{{author}}, re-reviewing now at {{short_sha}}.".to_string(),
+        }
+    }
+}
+
+impl ReviewFollowup {
+    pub fn render(&self, author: &str, reviewer: &str, head_sha: &str) -> String {
+        let short_sha: String = head_sha.chars().take(7).collect();
+        self.comment_template
+            .replace("{{author}}", author)
+            .replace("{{reviewer}}", reviewer)
+            .replace("{{head_sha}}", head_sha)
+            .replace("{{short_sha}}", &short_sha)
+    }
+}
+
+/// What the background check does with local reviews whose PR was merged or closed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct StaleReviewPolicy {
+    /// `off`, `prompt` to ask before clearing, or `clear` to move them to the trash unasked.
+    pub action: String,
+}
+
+impl Default for StaleReviewPolicy {
+    fn default() -> Self {
+        Self {
+            action: "prompt".to_string(),
+        }
+    }
+}
+
+/// Per-user preferences persisted in the settings table.
+///
+/// Unknown or missing fields fall back to defaults so older stored values keep loading
+/// after new preferences are added.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    pub theme: String,
+    pub locale: Locale,
+    /// IANA zone for displayed times, e.g. `Europe/Berlin`; `None` follows the system clock.
+    pub timezone: Option<String>,
+    pub diff_view: DiffViewDefaults,
+    /// Event used when a submission does not name one.
+    pub default_review_event: String,
+    /// Refuse APPROVE and REQUEST_CHANGES submissions without a summary body.
+    pub require_verdict_body: bool,
+    pub confirmations: SubmissionConfirmations,
+    pub followup: ReviewFollowup,
+    /// Days a cleared or abandoned local review stays restorable.
+    pub trash_retention_days: u32,
+    pub stale_reviews: StaleReviewPolicy,
+    /// Count reviews, comments, failed submissions and API latency on this machine for
+    /// `cmd_get_usage_metrics`. Off unless turned on; nothing is ever uploaded.
+    pub usage_metrics: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            theme: "dark".to_string(),
+            locale: Locale::default(),
+            timezone: None,
+            diff_view: DiffViewDefaults::default(),
+            default_review_event: "COMMENT".to_string(),
+            require_verdict_body: false,
+            confirmations: SubmissionConfirmations::default(),
+            followup: ReviewFollowup::default(),
+            trash_retention_days: 30,
+            stale_reviews: StaleReviewPolicy::default(),
+            usage_metrics: false,
+        }
+    }
+}
+
+impl Preferences {
+    pub fn validate(&self) -> AppResult<()> {
+        ensure_one_of("theme", &self.theme, THEMES)?;
+        self.zone()?;
+        ensure_one_of("diff_view.mode", &self.diff_view.mode, DIFF_VIEW_MODES)?;
+        ensure_one_of(
+            "diff_view.markdown_preview",
+            &self.diff_view.markdown_preview,
+            MARKDOWN_PREVIEWS,
+        )?;
+        ensure_one_of("default_review_event", &self.default_review_event, REVIEW_EVENTS)?;
+        ensure_one_of("stale_reviews.action", &self.stale_reviews.action, STALE_REVIEW_ACTIONS)?;
+        if !(1..=365).contains(&self.trash_retention_days) {
+            return Err(AppError::InvalidInput(format!(
+                "trash_retention_days must be between 1 and 365, got {}",
+                self.trash_retention_days
+            )));
+        }
+        Ok(())
+    }
+
+    /// `event` when given, otherwise the default event.
+    pub fn review_event(&self, event: Option<&str>) -> String {
+        event
+            .map(str::trim)
+            .filter(|event| !event.is_empty())
+            .unwrap_or(&self.default_review_event)
+            .to_ascii_uppercase()
+    }
+
+    /// Refuses an approval or change request with a blank body when the policy is on.
+    pub fn check_review_body(&self, event: &str, body: Option<&str>) -> AppResult<()> {
+        let verdict = event == "APPROVE" || event == "REQUEST_CHANGES";
+        if self.require_verdict_body && verdict && body.is_none_or(|body| body.trim().is_empty()) {
+            return Err(AppError::InvalidInput(tf(Msg::ReviewBodyRequired, &[&event])));
+        }
+        Ok(())
+    }
+
+    pub fn zone(&self) -> AppResult<Option<Tz>> {
+        time_format::parse_zone(self.timezone.as_deref())
+    }
+}
+
+pub fn ensure_one_of(field: &str, value: &str, allowed: &[&str]) -> AppResult<()> {
+    if allowed.contains(&value) {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "{field} must be one of {}, got `{value}`",
+            allowed.join(", ")
+        )))
+    }
+}
+
+/// Load a typed settings value, falling back to its default when nothing is stored
+/// or the stored JSON can no longer be parsed.
+pub fn load<T: DeserializeOwned + Default>(storage: &ReviewStorage, key: &str) -> AppResult<T> {
+    let Some(raw) = storage.get_setting(key)? else {
+        return Ok(T::default());
+    };
+
+    match serde_json::from_str(&raw) {
+        Ok(value) => Ok(value),
+        Err(err) => {
+            tracing::warn!(key, error = %err, "stored setting is unreadable; using defaults");
+            Ok(T::default())
+        }
+    }
+}
+
+pub fn save<T: Serialize>(storage: &ReviewStorage, key: &str, value: &T) -> AppResult<()> {
+    let raw = serde_json::to_string(value)?;
+    storage.set_setting(key, &raw)
+}
+
+pub fn load_preferences(storage: &ReviewStorage) -> AppResult<Preferences> {
+    load(storage, PREFERENCES_KEY)
+}
+
+pub fn save_preferences(storage: &ReviewStorage, preferences: &Preferences) -> AppResult<()> {
+    preferences.validate()?;
+    save(storage, PREFERENCES_KEY, preferences)
+}
@@ -0,0 +1,194 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::content_filter::{self, ContentFilterConfig};
+use crate::error::{AppError, AppResult};
+use crate::heading_style::{self, HeadingStyleConfig};
+use crate::pr_size::{self, PrSizeConfig};
+use crate::quick_comments::{self, QuickCommentConfig};
+use crate::quiet_hours::{self, QuietHoursConfig};
+use crate::review_profiles::{self, ReviewProfileConfig};
+use crate::review_storage::{CommentTemplate, ReviewStorage};
+use crate::settings::{self, Preferences};
+use crate::snippet_checks::{self, SnippetChecksConfig};
+
+pub const SETTINGS_FORMAT: &str = "github-review-settings";
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// A reviewer setup one person exports and others import. Tokens are kept in the system
+/// keychain rather than in settings, so they can never end up here. Hooks are left out
+/// because their URLs and headers often carry credentials and their commands would run
+/// on the importing machine, and local roots because they are paths on this machine.
+/// Sections missing from a file are left alone on import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub preferences: Option<Preferences>,
+    #[serde(default)]
+    pub quick_comments: Option<QuickCommentConfig>,
+    /// The template library, matched by name on import.
+    #[serde(default)]
+    pub comment_templates: Option<Vec<CommentTemplate>>,
+    #[serde(default)]
+    pub content_filter: Option<ContentFilterConfig>,
+    #[serde(default)]
+    pub heading_style: Option<HeadingStyleConfig>,
+    /// Checks are only imported for programs this machine allows; see `import_bundle`.
+    #[serde(default)]
+    pub snippet_checks: Option<SnippetChecksConfig>,
+    #[serde(default)]
+    pub pr_size: Option<PrSizeConfig>,
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHoursConfig>,
+    #[serde(default)]
+    pub review_profiles: Option<ReviewProfileConfig>,
+}
+
+/// What an import changed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingsImport {
+    /// Sections that were saved, by their name in the file.
+    pub imported: Vec<String>,
+    /// Parts of the file that were not applied, and why.
+    pub skipped: Vec<String>,
+}
+
+pub fn build_bundle(storage: &ReviewStorage) -> AppResult<SettingsBundle> {
+    Ok(SettingsBundle {
+        format: SETTINGS_FORMAT.to_string(),
+        version: SETTINGS_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        preferences: Some(settings::load_preferences(storage)?),
+        quick_comments: Some(quick_comments::load_config(storage)?),
+        comment_templates: Some(storage.list_comment_templates()?),
+        content_filter: Some(content_filter::load_config(storage)?),
+        heading_style: Some(heading_style::load_config(storage)?),
+        snippet_checks: Some(snippet_checks::load_config(storage)?),
+        pr_size: Some(pr_size::load_config(storage)?),
+        quiet_hours: Some(quiet_hours::load_config(storage)?),
+        review_profiles: Some(review_profiles::load_config(storage)?),
+    })
+}
+
+pub fn parse_bundle(json: &str) -> AppResult<SettingsBundle> {
+    let bundle: SettingsBundle = serde_json::from_str(json)?;
+    if bundle.format != SETTINGS_FORMAT {
+        return Err(AppError::InvalidInput(format!(
+            "not a settings export file (format `{}`)",
+            bundle.format
+        )));
+    }
+    if bundle.version > SETTINGS_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "settings export version {} is newer than this app supports ({SETTINGS_VERSION})",
+            bundle.version
+        )));
+    }
+    Ok(bundle)
+}
+
+/// File name offered in the save dialog for a settings export.
+pub fn bundle_file_name() -> String {
+    format!("{SETTINGS_FORMAT}.json")
+}
+
+/// Save every section present in `bundle`. Sections are all validated, then written in
+/// one transaction, so a bad file changes nothing. The snippet checks allow-list is never
+/// part of a bundle, since importing one would let the file choose what runs here; checks
+/// for programs this machine does not allow are skipped. The usage metrics switch keeps
+/// its local value too: whether usage is recorded is up to whoever uses this machine.
+pub fn import_bundle(storage: &ReviewStorage, bundle: SettingsBundle) -> AppResult<SettingsImport> {
+    let mut skipped = Vec::new();
+    let allowed_programs = snippet_checks::load_allow_list(storage)?;
+    let snippet_checks = bundle.snippet_checks.map(|mut imported| {
+        imported.checks.retain(|check| {
+            let allowed = snippet_checks::check_allowed(&allowed_programs, check).is_ok();
+            if !allowed {
+                skipped.push(format!(
+                    "snippet check for `{}`: program `{}` is not in the local allow-list",
+                    check.language, check.program
+                ));
+            }
+            allowed
+        });
+        imported
+    });
+    let preferences = match bundle.preferences {
+        Some(mut preferences) => {
+            preferences.usage_metrics = settings::load_preferences(storage)?.usage_metrics;
+            Some(preferences)
+        }
+        None => None,
+    };
+
+    if let Some(preferences) = &preferences {
+        preferences.validate()?;
+    }
+    if let Some(config) = &bundle.quick_comments {
+        config.validate()?;
+    }
+    for template in bundle.comment_templates.iter().flatten() {
+        template.validate()?;
+    }
+    if let Some(config) = &bundle.content_filter {
+        config.validate()?;
+    }
+    if let Some(config) = &bundle.heading_style {
+        config.validate()?;
+    }
+    if let Some(config) = &snippet_checks {
+        config.validate(&allowed_programs)?;
+    }
+    if let Some(config) = &bundle.pr_size {
+        config.validate()?;
+    }
+    if let Some(config) = &bundle.quiet_hours {
+        config.validate()?;
+    }
+    if let Some(config) = &bundle.review_profiles {
+        config.validate()?;
+    }
+
+    let sections = [
+        ("preferences", settings::PREFERENCES_KEY, to_json(&preferences)?),
+        ("content_filter", content_filter::CONTENT_FILTER_KEY, to_json(&bundle.content_filter)?),
+        ("heading_style", heading_style::HEADING_STYLE_KEY, to_json(&bundle.heading_style)?),
+        ("snippet_checks", snippet_checks::SNIPPET_CHECKS_KEY, to_json(&snippet_checks)?),
+        ("pr_size", pr_size::PR_SIZE_KEY, to_json(&bundle.pr_size)?),
+        ("quiet_hours", quiet_hours::QUIET_HOURS_KEY, to_json(&bundle.quiet_hours)?),
+        (
+            "review_profiles",
+            review_profiles::REVIEW_PROFILES_KEY,
+            to_json(&bundle.review_profiles)?,
+        ),
+    ];
+    let mut imported = Vec::new();
+    let mut entries = Vec::new();
+    for (name, key, value) in sections {
+        if let Some(value) = value {
+            entries.push((key, value));
+            imported.push(name.to_string());
+        }
+    }
+    if bundle.comment_templates.is_some() {
+        imported.push("comment_templates".to_string());
+    }
+    if bundle.quick_comments.is_some() {
+        imported.push("quick_comments".to_string());
+    }
+
+    storage.import_settings(
+        &entries,
+        bundle.comment_templates.as_deref().unwrap_or_default(),
+        bundle.quick_comments.as_ref().map(|config| config.templates.as_slice()),
+    )?;
+    Ok(SettingsImport { imported, skipped })
+}
+
+/// A section as the JSON it is stored as, or `None` when the file leaves it out.
+fn to_json<T: Serialize>(section: &Option<T>) -> AppResult<Option<String>> {
+    Ok(section.as_ref().map(serde_json::to_string).transpose()?)
+}
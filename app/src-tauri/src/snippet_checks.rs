@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::allow_list::{self, AllowList, SNIPPET_CHECKS_ALLOW_LIST_KEY};
+use crate::diff::added_lines;
+use crate::error::{AppError, AppResult};
+use crate::markdown_structure::{code_blocks, CodeBlock};
+use crate::review_storage::ReviewStorage;
+use crate::settings;
+
+pub const SNIPPET_CHECKS_KEY: &str = "snippet_checks";
+
+/// Placeholder in a check's arguments for the snippet file; the file name is appended
+/// when no argument uses it.
+pub const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Longest tool output kept per finding.
+const MAX_OUTPUT_CHARS: usize = 4000;
+
+/// Environment variables passed through to check programs; everything else is cleared.
+const PASSED_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USERPROFILE",
+    "SYSTEMROOT",
+    "RUSTUP_HOME",
+    "CARGO_HOME",
+];
+
+/// A local command run against every fenced block of `language`, e.g. `/usr/bin/rustc`
+/// with `["--edition", "2021", "--emit=metadata", "{file}"]` for `rust` blocks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnippetCheck {
+    /// Fence language, matched case-insensitively.
+    pub language: String,
+    /// Absolute path of the program.
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extension of the file the snippet is written to, without the dot.
+    pub extension: String,
+}
+
+/// Configured snippet checks. Their programs must be on the snippet checks allow-list,
+/// which is kept apart from this config in the same way as for automation hooks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnippetChecksConfig {
+    pub checks: Vec<SnippetCheck>,
+    pub timeout_secs: u64,
+}
+
+impl Default for SnippetChecksConfig {
+    fn default() -> Self {
+        Self {
+            checks: Vec::new(),
+            timeout_secs: 30,
+        }
+    }
+}
+
+impl SnippetChecksConfig {
+    pub fn validate(&self, allowed: &AllowList) -> AppResult<()> {
+        for check in &self.checks {
+            if check.language.trim().is_empty() {
+                return Err(AppError::InvalidInput(
+                    "snippet check language must not be empty".into(),
+                ));
+            }
+            if check.extension.is_empty()
+                || !check.extension.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                return Err(AppError::InvalidInput(format!(
+                    "snippet check extension `{}` must be letters and digits only",
+                    check.extension
+                )));
+            }
+            check_allowed(allowed, check)?;
+        }
+        Ok(())
+    }
+
+    /// Programs the checks run, for asking the user to allow them.
+    pub fn programs(&self) -> Vec<String> {
+        self.checks.iter().map(|check| check.program.clone()).collect()
+    }
+
+    fn check_for(&self, language: &str) -> Option<&SnippetCheck> {
+        self.checks
+            .iter()
+            .find(|check| check.language.eq_ignore_ascii_case(language))
+    }
+}
+
+pub fn check_allowed(allowed: &AllowList, check: &SnippetCheck) -> AppResult<()> {
+    allowed.check_program(&check.program, "snippet checks")
+}
+
+/// A snippet whose check command failed or timed out.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnippetFinding {
+    pub language: String,
+    /// 1-based line of the opening fence in the markdown file.
+    pub line: u64,
+    pub program: String,
+    /// Tool output with the sandbox path stripped, truncated to a few thousand chars.
+    pub output: String,
+}
+
+pub fn load_config(storage: &ReviewStorage) -> AppResult<SnippetChecksConfig> {
+    settings::load(storage, SNIPPET_CHECKS_KEY)
+}
+
+pub fn load_allow_list(storage: &ReviewStorage) -> AppResult<AllowList> {
+    allow_list::load(storage, SNIPPET_CHECKS_ALLOW_LIST_KEY)
+}
+
+pub fn save_config(storage: &ReviewStorage, config: &SnippetChecksConfig) -> AppResult<()> {
+    config.validate(&load_allow_list(storage)?)?;
+    settings::save(storage, SNIPPET_CHECKS_KEY, config)
+}
+
+/// Blocks in `content` that have a configured check. With a `patch`, only blocks whose
+/// fence or body the patch touches are returned.
+pub fn blocks_to_check(
+    config: &SnippetChecksConfig,
+    content: &str,
+    patch: Option<&str>,
+) -> Vec<CodeBlock> {
+    let changed: Option<HashSet<u64>> =
+        patch.map(|patch| added_lines(patch).into_iter().map(|(line, _)| line).collect());
+
+    code_blocks(content)
+        .into_iter()
+        .filter(|block| config.check_for(&block.language).is_some())
+        .filter(|block| {
+            let closing = block.fence_line + block.lines.len() as u64 + 1;
+            changed.as_ref().is_none_or(|changed| {
+                (block.fence_line..=closing).any(|line| changed.contains(&line))
+            })
+        })
+        .collect()
+}
+
+/// Arguments for one run, with `{file}` replaced by `file_name`.
+pub fn render_args(args: &[String], file_name: &str) -> Vec<String> {
+    let mut rendered: Vec<String> = args
+        .iter()
+        .map(|arg| arg.replace(FILE_PLACEHOLDER, file_name))
+        .collect();
+    if !args.iter().any(|arg| arg.contains(FILE_PLACEHOLDER)) {
+        rendered.push(file_name.to_string());
+    }
+    rendered
+}
+
+/// Combined stderr and stdout with the sandbox directory removed from paths.
+fn clean_output(stdout: &[u8], stderr: &[u8], sandbox: &Path) -> String {
+    let mut text = String::from_utf8_lossy(stderr).trim().to_string();
+    let stdout = String::from_utf8_lossy(stdout);
+    if !stdout.trim().is_empty() {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(stdout.trim());
+    }
+    let prefix = format!("{}{}", sandbox.display(), std::path::MAIN_SEPARATOR);
+    let text = text.replace(&prefix, "");
+    match text.char_indices().nth(MAX_OUTPUT_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// Temporary directory the snippets are written to and checks run in; removed on drop.
+struct Sandbox(PathBuf);
+
+impl Sandbox {
+    async fn create() -> AppResult<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let dir = std::env::temp_dir()
+            .join(format!("github-review-snippets-{}-{nanos}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run the configured check for each block in `content` (limited to blocks `patch`
+/// touches) and report the ones that fail. Each snippet is written to a fresh temp
+/// directory that is also the working directory, programs run directly with a minimal
+/// environment and no stdin, and each run is killed after the configured timeout.
+pub async fn run_checks(
+    config: &SnippetChecksConfig,
+    allowed: &AllowList,
+    content: &str,
+    patch: Option<&str>,
+) -> AppResult<Vec<SnippetFinding>> {
+    let blocks = blocks_to_check(config, content, patch);
+    if blocks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let timeout = Duration::from_secs(config.timeout_secs.max(1));
+    let mut findings = Vec::new();
+    for (index, block) in blocks.iter().enumerate() {
+        let Some(check) = config.check_for(&block.language) else {
+            continue;
+        };
+        check_allowed(allowed, check)?;
+
+        // A directory per snippet so output files from one run cannot affect the next
+        let sandbox = Sandbox::create().await?;
+        let file_name = format!("snippet_{}.{}", index + 1, check.extension);
+        let mut source = block.lines.join("\n");
+        source.push('\n');
+        tokio::fs::write(sandbox.0.join(&file_name), source).await?;
+
+        let mut command = tokio::process::Command::new(&check.program);
+        command
+            .args(render_args(&check.args, &file_name))
+            .current_dir(&sandbox.0)
+            .env_clear()
+            .env("TMPDIR", &sandbox.0)
+            .env("TEMP", &sandbox.0)
+            .env("TMP", &sandbox.0)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        for name in PASSED_ENV {
+            if let Some(value) = std::env::var_os(name) {
+                command.env(name, value);
+            }
+        }
+
+        let output = match tokio::time::timeout(timeout, command.output()).await {
+            Ok(output) => output?,
+            Err(_) => {
+                findings.push(SnippetFinding {
+                    language: block.language.clone(),
+                    line: block.fence_line,
+                    program: check.program.clone(),
+                    output: format!("timed out after {}s", timeout.as_secs()),
+                });
+                continue;
+            }
+        };
+        if !output.status.success() {
+            let text = clean_output(&output.stdout, &output.stderr, &sandbox.0);
+            findings.push(SnippetFinding {
+                language: block.language.clone(),
+                line: block.fence_line,
+                program: check.program.clone(),
+                output: if text.is_empty() {
+                    format!("{} exited with {}", check.program, output.status)
+                } else {
+                    text
+                },
+            });
+        }
+    }
+
+    Ok(findings)
+}
@@ -0,0 +1,551 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const HTTP_OPERATIONS: &[&str] =
+    &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+const OPENAPI_PARAMETER_LOCATIONS: &[&str] = &["query", "header", "path", "cookie"];
+const SWAGGER_PARAMETER_LOCATIONS: &[&str] = &["query", "header", "path", "formData", "body"];
+const SCHEMA_TYPES: &[&str] =
+    &["string", "number", "integer", "boolean", "array", "object", "null"];
+/// Keywords whose value is a single subschema.
+const SCHEMA_SINGLE: &[&str] =
+    &["items", "additionalProperties", "not", "if", "then", "else", "contains"];
+/// Keywords whose value is a list of subschemas.
+const SCHEMA_LISTS: &[&str] = &["allOf", "anyOf", "oneOf", "prefixItems"];
+/// Keywords whose value maps names to subschemas.
+const SCHEMA_MAPS: &[&str] = &["properties", "patternProperties", "$defs", "definitions"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecKind {
+    /// OpenAPI 3.x (`openapi: 3.x.y`).
+    OpenApi,
+    /// OpenAPI 2.0 (`swagger: "2.0"`).
+    Swagger,
+    JsonSchema,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecIssue {
+    /// 1-based line; the closest key that could be located when the exact one cannot.
+    pub line: u64,
+    /// JSON pointer to the offending value, empty for syntax errors.
+    pub pointer: String,
+    pub detail: String,
+}
+
+/// Result of validating one spec file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecReport {
+    /// `None` when the file parsed but does not look like a spec; no issues are reported.
+    pub kind: Option<SpecKind>,
+    pub issues: Vec<SpecIssue>,
+}
+
+fn response_code_regex() -> &'static Regex {
+    static RESPONSE_CODE: OnceLock<Regex> = OnceLock::new();
+    RESPONSE_CODE
+        .get_or_init(|| Regex::new(r"^[1-5](\d\d|XX)$").expect("response code pattern is valid"))
+}
+
+fn path_template_regex() -> &'static Regex {
+    static PATH_TEMPLATE: OnceLock<Regex> = OnceLock::new();
+    PATH_TEMPLATE
+        .get_or_init(|| Regex::new(r"\{([^}/]+)\}").expect("path template pattern is valid"))
+}
+
+pub fn is_spec_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.ends_with(".json") || lower.ends_with(".yaml") || lower.ends_with(".yml")
+}
+
+/// What kind of spec `document` is, from its top-level keys.
+pub fn detect(path: &str, document: &Value) -> Option<SpecKind> {
+    let object = document.as_object()?;
+    if object.contains_key("openapi") {
+        Some(SpecKind::OpenApi)
+    } else if object.contains_key("swagger") {
+        Some(SpecKind::Swagger)
+    } else if object
+        .get("$schema")
+        .and_then(Value::as_str)
+        .is_some_and(|schema| schema.contains("json-schema"))
+        || path.to_lowercase().contains(".schema.")
+    {
+        Some(SpecKind::JsonSchema)
+    } else {
+        None
+    }
+}
+
+/// Parse and check a changed `.json`/`.yaml` file. Files that parse but are not OpenAPI
+/// or JSON Schema documents produce no issues; syntax errors are always reported.
+pub fn validate(path: &str, content: &str) -> SpecReport {
+    if !is_spec_path(path) {
+        return SpecReport {
+            kind: None,
+            issues: Vec::new(),
+        };
+    }
+    let yaml = !path.to_lowercase().ends_with(".json");
+    let parsed = if yaml {
+        serde_yaml::from_str::<Value>(content)
+            .map_err(|e| (e.location().map(|l| l.line() as u64).unwrap_or(1), e.to_string()))
+    } else {
+        serde_json::from_str::<Value>(content).map_err(|e| (e.line() as u64, e.to_string()))
+    };
+    let document = match parsed {
+        Ok(document) => document,
+        Err((line, detail)) => {
+            return SpecReport {
+                kind: None,
+                issues: vec![SpecIssue {
+                    line: line.max(1),
+                    pointer: String::new(),
+                    detail,
+                }],
+            }
+        }
+    };
+
+    let kind = detect(path, &document);
+    let mut checker = Checker {
+        root: &document,
+        found: Vec::new(),
+        operation_ids: HashMap::new(),
+    };
+    match kind {
+        Some(SpecKind::OpenApi) => checker.api(&document, false),
+        Some(SpecKind::Swagger) => checker.api(&document, true),
+        Some(SpecKind::JsonSchema) => checker.schema(&document, &mut Vec::new()),
+        None => {}
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let issues = checker
+        .found
+        .into_iter()
+        .map(|(path, detail)| SpecIssue {
+            line: locate(&lines, &path),
+            pointer: pointer(&path),
+            detail,
+        })
+        .collect();
+    SpecReport { kind, issues }
+}
+
+/// JSON pointer for `path`, escaping `~` and `/` in keys.
+fn pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Whether `line` declares `key` as a mapping key in YAML or JSON.
+fn declares_key(line: &str, key: &str) -> bool {
+    let trimmed = line.trim_start();
+    let trimmed = trimmed.strip_prefix("- ").unwrap_or(trimmed).trim_start();
+    trimmed.starts_with(&format!("{key}:"))
+        || line.contains(&format!("\"{key}\":"))
+        || line.contains(&format!("\"{key}\" :"))
+        || line.contains(&format!("'{key}':"))
+}
+
+/// Best-effort line of the value at `path`: each key is searched for after the line of
+/// the previous one, and YAML list indexes count `- ` items. Stops at the deepest
+/// segment it can find.
+fn locate(lines: &[&str], path: &[String]) -> u64 {
+    let mut at: Option<usize> = None;
+    for segment in path {
+        let start = at.map_or(0, |line| line + 1);
+        let found = match segment.parse::<usize>() {
+            Ok(index) => {
+                let mut items = lines
+                    .iter()
+                    .enumerate()
+                    .skip(start)
+                    .filter(|(_, line)| line.trim_start().starts_with("- "));
+                let first = items.next();
+                let indent = first.map(|(_, line)| line.len() - line.trim_start().len());
+                first
+                    .into_iter()
+                    .chain(items.filter(|(_, line)| {
+                        Some(line.len() - line.trim_start().len()) == indent
+                    }))
+                    .nth(index)
+                    .map(|(i, _)| i)
+            }
+            Err(_) => None,
+        }
+        .or_else(|| (start..lines.len()).find(|&i| declares_key(lines[i], segment)));
+        match found {
+            Some(line) => at = Some(line),
+            None => break,
+        }
+    }
+    at.map_or(1, |line| line as u64 + 1)
+}
+
+struct Checker<'a> {
+    root: &'a Value,
+    found: Vec<(Vec<String>, String)>,
+    /// First location of each `operationId`, to report duplicates.
+    operation_ids: HashMap<String, Vec<String>>,
+}
+
+fn at(path: &[String], more: &[&str]) -> Vec<String> {
+    path.iter().cloned().chain(more.iter().map(|s| s.to_string())).collect()
+}
+
+impl Checker<'_> {
+    fn push(&mut self, path: Vec<String>, detail: impl Into<String>) {
+        self.found.push((path, detail.into()));
+    }
+
+    fn required_string(&mut self, object: &Map<String, Value>, path: &[String], key: &str) {
+        if !object.get(key).is_some_and(Value::is_string) {
+            let detail = match object.get(key) {
+                Some(_) => format!("`{key}` must be a string"),
+                None => format!("missing required `{key}`"),
+            };
+            let path = if object.contains_key(key) { at(path, &[key]) } else { path.to_vec() };
+            self.push(path, detail);
+        }
+    }
+
+    /// Local `$ref`s must point at something in this document.
+    fn reference(&mut self, reference: &str, path: &[String]) {
+        if let Some(pointer) = reference.strip_prefix('#') {
+            if !pointer.is_empty() && self.root.pointer(pointer).is_none() {
+                let detail = format!("`$ref` target `{reference}` does not exist");
+                self.push(at(path, &["$ref"]), detail);
+            }
+        }
+    }
+
+    fn api(&mut self, document: &Value, swagger: bool) {
+        let Some(root) = document.as_object() else {
+            return;
+        };
+        let top = Vec::new();
+        let version_key = if swagger { "swagger" } else { "openapi" };
+        let version_path = vec![version_key.to_string()];
+        match root.get(version_key).and_then(Value::as_str) {
+            Some(version) if swagger && version != "2.0" => self.push(
+                version_path,
+                format!("`swagger` must be \"2.0\", found \"{version}\""),
+            ),
+            Some(version) if !swagger && !version.starts_with("3.") => self.push(
+                version_path,
+                format!("unsupported OpenAPI version \"{version}\""),
+            ),
+            Some(_) => {}
+            None => self.push(version_path, format!("`{version_key}` must be a version string")),
+        }
+
+        match root.get("info").and_then(Value::as_object) {
+            Some(info) => {
+                let path = vec!["info".to_string()];
+                self.required_string(info, &path, "title");
+                self.required_string(info, &path, "version");
+            }
+            None => self.push(top.clone(), "missing required `info` object"),
+        }
+
+        // 3.1 allows a document with only webhooks or components
+        let paths_optional = !swagger
+            && root.get("openapi").and_then(Value::as_str).is_some_and(|v| v.starts_with("3.1"))
+            && (root.contains_key("webhooks") || root.contains_key("components"));
+        match root.get("paths") {
+            Some(Value::Object(paths)) => self.paths(paths, swagger),
+            Some(_) => self.push(vec!["paths".into()], "`paths` must be an object"),
+            None if !paths_optional => self.push(top, "missing required `paths` object"),
+            None => {}
+        }
+
+        for key in ["components", "definitions", "parameters", "responses", "webhooks"] {
+            if let Some(section) = root.get(key) {
+                self.refs_and_schemas(section, &mut vec![key.to_string()]);
+            }
+        }
+    }
+
+    fn paths(&mut self, paths: &Map<String, Value>, swagger: bool) {
+        for (template, item) in paths {
+            let path = vec!["paths".to_string(), template.clone()];
+            if !template.starts_with('/') && !template.starts_with("x-") {
+                self.push(path.clone(), format!("path `{template}` must start with `/`"));
+            }
+            let Some(item) = item.as_object() else {
+                continue;
+            };
+            if let Some(reference) = item.get("$ref").and_then(Value::as_str) {
+                self.reference(reference, &path);
+                continue;
+            }
+
+            let shared_path = at(&path, &["parameters"]);
+            let shared = self.parameters(item.get("parameters"), &shared_path, swagger);
+            if let Some(parameters) = item.get("parameters") {
+                self.refs_and_schemas(parameters, &mut shared_path.clone());
+            }
+            let template_names: Vec<String> = path_template_regex()
+                .captures_iter(template)
+                .map(|caps| caps[1].to_string())
+                .collect();
+
+            for method in HTTP_OPERATIONS {
+                let Some(value) = item.get(*method) else {
+                    continue;
+                };
+                let op_path = at(&path, &[method]);
+                let Some(operation) = value.as_object() else {
+                    self.push(op_path, format!("`{method}` operation must be an object"));
+                    continue;
+                };
+
+                if let Some(id) = operation.get("operationId").and_then(Value::as_str) {
+                    match self.operation_ids.get(id) {
+                        Some(first) => {
+                            let first = pointer(first);
+                            self.push(
+                                at(&op_path, &["operationId"]),
+                                format!("duplicate operationId `{id}` (first used at {first})"),
+                            );
+                        }
+                        None => {
+                            self.operation_ids.insert(id.to_string(), op_path.clone());
+                        }
+                    }
+                }
+
+                let own_path = at(&op_path, &["parameters"]);
+                let own = self.parameters(operation.get("parameters"), &own_path, swagger);
+                // A `$ref` parameter may declare any name, so skip the check when present
+                if let (Some(shared), Some(own)) = (&shared, &own) {
+                    for name in &template_names {
+                        if !shared.contains(name) && !own.contains(name) {
+                            self.push(
+                                op_path.clone(),
+                                format!("path parameter `{name}` is not declared with `in: path`"),
+                            );
+                        }
+                    }
+                }
+
+                match operation.get("responses") {
+                    Some(Value::Object(responses)) if responses.is_empty() => {
+                        self.push(at(&op_path, &["responses"]), "`responses` must not be empty")
+                    }
+                    Some(Value::Object(responses)) => {
+                        for code in responses.keys() {
+                            let valid = code == "default"
+                                || code.starts_with("x-")
+                                || response_code_regex().is_match(code);
+                            if !valid {
+                                self.push(
+                                    at(&op_path, &["responses", code]),
+                                    format!("`{code}` is not an HTTP status code"),
+                                );
+                            }
+                        }
+                    }
+                    Some(_) => {
+                        self.push(at(&op_path, &["responses"]), "`responses` must be an object")
+                    }
+                    None => self.push(op_path.clone(), "missing required `responses`"),
+                }
+
+                self.refs_and_schemas(value, &mut op_path.clone());
+            }
+        }
+    }
+
+    /// Check a `parameters` list and return the names declared `in: path`, or `None`
+    /// when a `$ref` hides what is declared.
+    fn parameters(
+        &mut self,
+        parameters: Option<&Value>,
+        path: &[String],
+        swagger: bool,
+    ) -> Option<HashSet<String>> {
+        let mut names = HashSet::new();
+        let Some(parameters) = parameters else {
+            return Some(names);
+        };
+        let Some(parameters) = parameters.as_array() else {
+            self.push(path.to_vec(), "`parameters` must be a list");
+            return Some(names);
+        };
+
+        let locations = if swagger {
+            SWAGGER_PARAMETER_LOCATIONS
+        } else {
+            OPENAPI_PARAMETER_LOCATIONS
+        };
+        let mut opaque = false;
+        for (index, parameter) in parameters.iter().enumerate() {
+            let param_path = at(path, &[&index.to_string()]);
+            let Some(parameter) = parameter.as_object() else {
+                self.push(param_path, "parameter must be an object");
+                continue;
+            };
+            if parameter.contains_key("$ref") {
+                opaque = true;
+                continue;
+            }
+            self.required_string(parameter, &param_path, "name");
+            match parameter.get("in").and_then(Value::as_str) {
+                Some(location) if !locations.contains(&location) => self.push(
+                    at(&param_path, &["in"]),
+                    format!("`in: {location}` must be one of {}", locations.join(", ")),
+                ),
+                Some("path") => {
+                    if parameter.get("required") != Some(&Value::Bool(true)) {
+                        self.push(param_path.clone(), "path parameters must be `required: true`");
+                    }
+                    if let Some(name) = parameter.get("name").and_then(Value::as_str) {
+                        names.insert(name.to_string());
+                    }
+                }
+                Some(_) => {}
+                None => self.push(param_path, "missing required `in`"),
+            }
+        }
+        (!opaque).then_some(names)
+    }
+
+    /// Resolve `$ref`s and check every `schema` found under `value`.
+    fn refs_and_schemas(&mut self, value: &Value, path: &mut Vec<String>) {
+        match value {
+            Value::Object(object) => {
+                if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+                    self.reference(reference, path);
+                }
+                for (key, child) in object {
+                    // Named schemas under `components/schemas` or Swagger `definitions`
+                    let named_schema = path.last().is_some_and(|parent| parent == "schemas")
+                        || path.len() == 1 && path[0] == "definitions";
+                    path.push(key.clone());
+                    if key == "schema" || named_schema {
+                        self.schema(child, path);
+                    } else if key != "example" && key != "examples" {
+                        self.refs_and_schemas(child, path);
+                    }
+                    path.pop();
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    path.push(index.to_string());
+                    self.refs_and_schemas(item, path);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn schema(&mut self, schema: &Value, path: &mut Vec<String>) {
+        let Some(object) = schema.as_object() else {
+            if !schema.is_boolean() {
+                self.push(path.clone(), "schema must be an object or boolean");
+            }
+            return;
+        };
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            self.reference(reference, path);
+        }
+
+        match object.get("type") {
+            Some(Value::String(name)) if !SCHEMA_TYPES.contains(&name.as_str()) => {
+                self.push(at(path, &["type"]), format!("unknown type `{name}`"))
+            }
+            Some(Value::Array(names)) => {
+                for name in names {
+                    match name.as_str() {
+                        Some(name) if SCHEMA_TYPES.contains(&name) => {}
+                        _ => self.push(at(path, &["type"]), format!("unknown type `{name}`")),
+                    }
+                }
+            }
+            Some(Value::String(_)) | None => {}
+            Some(_) => self.push(at(path, &["type"]), "`type` must be a string or list"),
+        }
+
+        if let Some(required) = object.get("required") {
+            let names = required.as_array().filter(|names| names.iter().all(Value::is_string));
+            match (names, object.get("properties").and_then(Value::as_object)) {
+                (None, _) if !required.is_boolean() => self.push(
+                    at(path, &["required"]),
+                    "`required` must be a list of property names",
+                ),
+                (Some(names), Some(properties)) if object.get("additionalProperties").is_none() => {
+                    for name in names.iter().filter_map(Value::as_str) {
+                        if !properties.contains_key(name) && !object.contains_key("allOf") {
+                            self.push(
+                                at(path, &["required"]),
+                                format!("required property `{name}` is not in `properties`"),
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for key in SCHEMA_SINGLE {
+            if let Some(child) = object.get(*key) {
+                path.push(key.to_string());
+                // `items` may also be a tuple list in older drafts
+                match child {
+                    Value::Array(items) if *key == "items" => {
+                        for (index, item) in items.iter().enumerate() {
+                            path.push(index.to_string());
+                            self.schema(item, path);
+                            path.pop();
+                        }
+                    }
+                    _ => self.schema(child, path),
+                }
+                path.pop();
+            }
+        }
+        for key in SCHEMA_LISTS {
+            if let Some(child) = object.get(*key) {
+                path.push(key.to_string());
+                match child.as_array() {
+                    Some(items) => {
+                        for (index, item) in items.iter().enumerate() {
+                            path.push(index.to_string());
+                            self.schema(item, path);
+                            path.pop();
+                        }
+                    }
+                    None => self.push(path.clone(), format!("`{key}` must be a list of schemas")),
+                }
+                path.pop();
+            }
+        }
+        for key in SCHEMA_MAPS {
+            if let Some(child) = object.get(*key) {
+                path.push(key.to_string());
+                match child.as_object() {
+                    Some(entries) => {
+                        for (name, item) in entries {
+                            path.push(name.clone());
+                            self.schema(item, path);
+                            path.pop();
+                        }
+                    }
+                    None => self.push(path.clone(), format!("`{key}` must be an object")),
+                }
+                path.pop();
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+use crate::events;
+use crate::models::PrReviewState;
+use crate::review_storage::{ReviewMetadata, ReviewStorage};
+use crate::settings;
+
+/// Sent with the stale reviews found when the policy is to ask before clearing them.
+pub const STALE_REVIEWS_FOUND_EVENT: &str = "stale-reviews-found";
+/// Sent with the stale reviews the background check moved to the trash.
+pub const STALE_REVIEWS_CLEARED_EVENT: &str = "stale-reviews-cleared";
+
+/// The first check waits for startup to settle; later ones run at this interval.
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// A local review whose pull request is no longer open.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StaleReview {
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    pub merged: bool,
+    /// Unsubmitted local comments that go to the trash with the review.
+    pub pending_comment_count: usize,
+}
+
+fn is_local_folder(metadata: &ReviewMetadata) -> bool {
+    metadata.owner == "__local__" && metadata.repo == "local"
+}
+
+/// Pair GitHub reviews with their states, one per review in order, and keep those whose
+/// PR was merged or closed. Reviews GitHub told us nothing about are kept as they are.
+pub fn select_stale(
+    reviews: &[ReviewMetadata],
+    states: &[Option<PrReviewState>],
+    pending_counts: &std::collections::HashMap<(String, String, u64), usize>,
+) -> Vec<StaleReview> {
+    reviews
+        .iter()
+        .zip(states)
+        .filter_map(|(review, state)| {
+            let state = state.as_ref().filter(|state| state.state == "closed")?;
+            let key = (review.owner.clone(), review.repo.clone(), review.pr_number);
+            Some(StaleReview {
+                owner: review.owner.clone(),
+                repo: review.repo.clone(),
+                pr_number: review.pr_number,
+                merged: state.merged,
+                pending_comment_count: pending_counts.get(&key).copied().unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// Local reviews of pull requests that were merged or closed on GitHub.
+pub async fn find_stale(storage: &ReviewStorage) -> AppResult<Vec<StaleReview>> {
+    let reviews: Vec<ReviewMetadata> = storage
+        .get_all_review_metadata()?
+        .into_iter()
+        .filter(|metadata| !is_local_folder(metadata))
+        .collect();
+    if reviews.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let keys: Vec<(String, String, u64)> = reviews
+        .iter()
+        .map(|metadata| (metadata.owner.clone(), metadata.repo.clone(), metadata.pr_number))
+        .collect();
+    let states = crate::auth::pull_request_review_states(&keys).await?;
+    let pending_counts = storage.get_all_pending_comment_counts()?;
+    Ok(select_stale(&reviews, &states, &pending_counts))
+}
+
+/// Move the given reviews to the trash, where they stay restorable for the trash
+/// retention period. Returns those that were still there to move.
+pub async fn clear(storage: &ReviewStorage, stale: Vec<StaleReview>) -> AppResult<Vec<StaleReview>> {
+    let mut cleared = Vec::with_capacity(stale.len());
+    for review in stale {
+        if storage
+            .archive_closed_review(&review.owner, &review.repo, review.pr_number, review.merged)
+            .await?
+        {
+            cleared.push(review);
+        }
+    }
+    Ok(cleared)
+}
+
+/// One pass of the background job: find stale reviews and ask about or clear them, as
+/// the `stale_reviews` preference says.
+async fn check(storage: &ReviewStorage) -> AppResult<()> {
+    let policy = settings::load_preferences(storage)?.stale_reviews;
+    if policy.action == "off" {
+        return Ok(());
+    }
+
+    let stale = find_stale(storage).await?;
+    if stale.is_empty() {
+        return Ok(());
+    }
+    if policy.action == "clear" {
+        let cleared = clear(storage, stale).await?;
+        tracing::info!("Moved {} review(s) of merged or closed PRs to the trash", cleared.len());
+        events::emit(STALE_REVIEWS_CLEARED_EVENT, cleared);
+    } else {
+        events::emit(STALE_REVIEWS_FOUND_EVENT, stale);
+    }
+    Ok(())
+}
+
+/// Start the periodic check for reviews of merged or closed PRs.
+pub fn spawn_background_check(storage: &'static ReviewStorage) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_CHECK_DELAY).await;
+        loop {
+            match check(storage).await {
+                Ok(()) => {}
+                // Not signed in yet; try again next time
+                Err(AppError::OAuthCancelled) => {}
+                Err(err) => tracing::warn!("Checking for reviews of closed PRs failed: {err}"),
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
@@ -1,47 +1,135 @@
+use std::sync::Mutex;
+
 use keyring::{Entry, Error as KeyringError};
+use serde::{Deserialize, Serialize};
 
 use crate::error::{AppError, AppResult};
 
 const SERVICE_NAME: &str = "github-review";
 const ACCOUNT_NAME: &str = "github-token";
 const LOGIN_ACCOUNT_NAME: &str = "github-login";
+const PROFILES_ACCOUNT_NAME: &str = "github-profiles";
 
-pub fn store_token(token: &str) -> AppResult<()> {
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME)?;
-    entry.set_password(token)?;
-    Ok(())
+/// The profile a single-account install signed in to. Its credentials keep the original
+/// keyring entries, so upgrading does not sign anyone out.
+pub const DEFAULT_PROFILE: &str = "default";
+
+const MAX_PROFILE_NAME_LEN: usize = 32;
+
+/// The named accounts, e.g. `work` and `personal`, and which one requests use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileIndex {
+    pub active: String,
+    pub profiles: Vec<String>,
 }
 
-pub fn store_last_login(login: &str) -> AppResult<()> {
-    let entry = Entry::new(SERVICE_NAME, LOGIN_ACCOUNT_NAME)?;
-    entry.set_password(login)?;
+impl Default for ProfileIndex {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE.to_string(),
+            profiles: vec![DEFAULT_PROFILE.to_string()],
+        }
+    }
+}
+
+impl ProfileIndex {
+    pub fn contains(&self, name: &str) -> bool {
+        self.profiles.iter().any(|profile| profile == name)
+    }
+
+    pub fn add(&mut self, name: &str) -> AppResult<()> {
+        validate_profile_name(name)?;
+        if self.contains(name) {
+            return Err(AppError::InvalidInput(format!("an account named `{name}` already exists")));
+        }
+        self.profiles.push(name.to_string());
+        Ok(())
+    }
+
+    pub fn switch_to(&mut self, name: &str) -> AppResult<()> {
+        if !self.contains(name) {
+            return Err(AppError::InvalidInput(format!("no account named `{name}`")));
+        }
+        self.active = name.to_string();
+        Ok(())
+    }
+
+    /// Drop `name`. Removing the active profile makes the first remaining one active, or
+    /// an empty default profile when none remain.
+    pub fn remove(&mut self, name: &str) -> AppResult<()> {
+        if !self.contains(name) {
+            return Err(AppError::InvalidInput(format!("no account named `{name}`")));
+        }
+        self.profiles.retain(|profile| profile != name);
+        if self.profiles.is_empty() {
+            *self = Self::default();
+        } else if self.active == name {
+            self.active = self.profiles[0].clone();
+        }
+        Ok(())
+    }
+}
+
+/// Profile names become part of keyring entry names, so they are kept short and plain.
+pub fn validate_profile_name(name: &str) -> AppResult<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_PROFILE_NAME_LEN
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(AppError::InvalidInput(format!(
+            "invalid account name `{name}`: expected up to {MAX_PROFILE_NAME_LEN} letters, \
+             digits, '-' and '_'"
+        )));
+    }
     Ok(())
 }
 
-pub fn read_last_login() -> AppResult<Option<String>> {
-    let entry = Entry::new(SERVICE_NAME, LOGIN_ACCOUNT_NAME)?;
-    match entry.get_password() {
-        Ok(password) => Ok(Some(password)),
-        Err(err) => match err {
-            KeyringError::NoEntry => Ok(None),
-            other => Err(AppError::from(other)),
-        },
+/// The active profile with its token and login, read from the keyring once instead of on
+/// every request. Any keyring write drops it, so switching accounts or signing in or out
+/// is picked up by the next read.
+#[derive(Debug, Clone)]
+struct ActiveCredentials {
+    profile: String,
+    token: Option<String>,
+    login: Option<String>,
+}
+
+static ACTIVE: Mutex<Option<ActiveCredentials>> = Mutex::new(None);
+
+fn active() -> AppResult<ActiveCredentials> {
+    let mut cached = ACTIVE
+        .lock()
+        .map_err(|_| AppError::Internal("Lock poisoned".into()))?;
+    if let Some(active) = cached.as_ref() {
+        return Ok(active.clone());
     }
+    let profile = read_profiles()?.active;
+    let active = ActiveCredentials {
+        token: read_profile_token(&profile)?,
+        login: read_profile_login(&profile)?,
+        profile,
+    };
+    *cached = Some(active.clone());
+    Ok(active)
 }
 
-pub fn delete_last_login() -> AppResult<()> {
-    let entry = Entry::new(SERVICE_NAME, LOGIN_ACCOUNT_NAME)?;
-    match entry.delete_password() {
-        Ok(_) => Ok(()),
-        Err(err) => match err {
-            KeyringError::NoEntry => Ok(()),
-            other => Err(AppError::from(other)),
-        },
+fn forget_active() {
+    if let Ok(mut cached) = ACTIVE.lock() {
+        *cached = None;
     }
 }
 
-pub fn read_token() -> AppResult<Option<String>> {
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME)?;
+/// Keyring entry name of `base` for `profile`.
+pub fn profile_account(base: &str, profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        base.to_string()
+    } else {
+        format!("{base}:{profile}")
+    }
+}
+
+fn read_entry(account: &str) -> AppResult<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, account)?;
     match entry.get_password() {
         Ok(password) => Ok(Some(password)),
         Err(err) => match err {
@@ -51,9 +139,20 @@ pub fn read_token() -> AppResult<Option<String>> {
     }
 }
 
-pub fn delete_token() -> AppResult<()> {
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME)?;
-    match entry.delete_password() {
+fn write_entry(account: &str, value: &str) -> AppResult<()> {
+    let entry = Entry::new(SERVICE_NAME, account)?;
+    let written = entry.set_password(value);
+    // After the write, so a read racing it cannot cache the old value
+    forget_active();
+    written?;
+    Ok(())
+}
+
+fn delete_entry(account: &str) -> AppResult<()> {
+    let entry = Entry::new(SERVICE_NAME, account)?;
+    let deleted = entry.delete_password();
+    forget_active();
+    match deleted {
         Ok(_) => Ok(()),
         Err(err) => match err {
             KeyringError::NoEntry => Ok(()),
@@ -61,3 +160,62 @@ pub fn delete_token() -> AppResult<()> {
         },
     }
 }
+
+pub fn read_profiles() -> AppResult<ProfileIndex> {
+    let Some(raw) = read_entry(PROFILES_ACCOUNT_NAME)? else {
+        return Ok(ProfileIndex::default());
+    };
+    match serde_json::from_str(&raw) {
+        Ok(index) => Ok(index),
+        Err(err) => {
+            tracing::warn!(error = %err, "stored account list is unreadable; using the default");
+            Ok(ProfileIndex::default())
+        }
+    }
+}
+
+pub fn store_profiles(index: &ProfileIndex) -> AppResult<()> {
+    write_entry(PROFILES_ACCOUNT_NAME, &serde_json::to_string(index)?)
+}
+
+fn active_profile() -> AppResult<String> {
+    Ok(active()?.profile)
+}
+
+pub fn store_token(token: &str) -> AppResult<()> {
+    write_entry(&profile_account(ACCOUNT_NAME, &active_profile()?), token)
+}
+
+pub fn store_last_login(login: &str) -> AppResult<()> {
+    write_entry(&profile_account(LOGIN_ACCOUNT_NAME, &active_profile()?), login)
+}
+pub fn read_last_login() -> AppResult<Option<String>> {
+    Ok(active()?.login)
+}
+
+pub fn read_profile_login(profile: &str) -> AppResult<Option<String>> {
+    read_entry(&profile_account(LOGIN_ACCOUNT_NAME, profile))
+}
+
+pub fn delete_last_login() -> AppResult<()> {
+    delete_entry(&profile_account(LOGIN_ACCOUNT_NAME, &active_profile()?))
+}
+
+/// Token of the active profile.
+pub fn read_token() -> AppResult<Option<String>> {
+    Ok(active()?.token)
+}
+
+pub fn read_profile_token(profile: &str) -> AppResult<Option<String>> {
+    read_entry(&profile_account(ACCOUNT_NAME, profile))
+}
+
+pub fn delete_token() -> AppResult<()> {
+    delete_entry(&profile_account(ACCOUNT_NAME, &active_profile()?))
+}
+
+/// Forget the token and login saved for `profile`.
+pub fn delete_profile_credentials(profile: &str) -> AppResult<()> {
+    delete_entry(&profile_account(ACCOUNT_NAME, profile))?;
+    delete_entry(&profile_account(LOGIN_ACCOUNT_NAME, profile))
+}
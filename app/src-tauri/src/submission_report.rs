@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use crate::orphans::OrphanedComment;
+use crate::review_storage::ReviewComment;
+
+/// Settings key the last report is kept under.
+pub const LAST_REPORT_KEY: &str = "last_submission_report";
+
+/// A draft comment GitHub did not accept. It stays in local storage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailedComment {
+    pub comment_id: i64,
+    /// Stable reason, e.g. `line_not_in_diff` or `locked`; see `error_code`.
+    pub error_code: String,
+    pub message: String,
+}
+
+impl FailedComment {
+    /// A failure whose reason is read from GitHub's error text.
+    pub fn new(comment_id: i64, message: String) -> Self {
+        Self::with_code(comment_id, error_code(&message), message)
+    }
+
+    pub fn with_code(comment_id: i64, error_code: &str, message: String) -> Self {
+        Self {
+            comment_id,
+            error_code: error_code.to_string(),
+            message,
+        }
+    }
+}
+
+/// The same failure for every comment not posted because the whole batch stopped.
+pub fn unposted(comments: &[ReviewComment], error_code: &str, message: &str) -> Vec<FailedComment> {
+    comments
+        .iter()
+        .map(|comment| FailedComment::with_code(comment.id, error_code, message.to_string()))
+        .collect()
+}
+
+/// Outcome of posting draft comments one by one.
+#[derive(Debug, Clone, Default)]
+pub struct CommentBatch {
+    pub succeeded_ids: Vec<i64>,
+    pub failed: Vec<FailedComment>,
+    /// What went wrong and how to fix it, when anything failed.
+    pub summary: Option<String>,
+}
+
+/// What a local review submission did, kept for `cmd_get_last_submission_report`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionReport {
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u64,
+    pub posted: usize,
+    pub failed: Vec<FailedComment>,
+    /// The submitted review, or the PR when comments were posted individually.
+    pub review_url: Option<String>,
+    pub summary: Option<String>,
+    pub submitted_at: String,
+    /// Comments on files that left the PR; when any are listed nothing was submitted.
+    #[serde(default)]
+    pub orphaned: Vec<OrphanedComment>,
+}
+
+/// Classify GitHub's error text for a rejected comment.
+pub fn error_code(message: &str) -> &'static str {
+    let err = message.to_lowercase();
+    if err.contains("pull_request_review_thread.line") && err.contains("could not be resolved") {
+        "line_not_in_diff"
+    } else if err.contains("submitted too quickly") {
+        "submitted_too_quickly"
+    } else if err.contains("is locked") {
+        "locked"
+    } else if err.contains("rate limit") {
+        "rate_limited"
+    } else if err.contains("403") || err.contains("forbidden") {
+        "forbidden"
+    } else if err.contains("401") || err.contains("unauthorized") {
+        "unauthorized"
+    } else if err.contains("not found") || err.contains("404") {
+        "not_found"
+    } else if err.contains("path") && err.contains("invalid") {
+        "invalid_path"
+    } else if err.contains("commit_id") || (err.contains("commit") && err.contains("outdated")) {
+        "outdated_commit"
+    } else if err.contains("review comments is invalid") || err.contains("review threads is invalid") {
+        "spam_detection"
+    } else if err.contains("side") && err.contains("invalid") {
+        "invalid_side"
+    } else if err.contains("body") && (err.contains("blank") || err.contains("empty")) {
+        "empty_body"
+    } else {
+        "unknown"
+    }
+}
+
+/// What an error code means and how to fix it.
+pub fn explain(error_code: &str) -> (&'static str, &'static str) {
+    match error_code {
+        "line_not_in_diff" => (
+            "Line numbers are not in the PR diff.",
+            "These lines weren't changed in the PR. Refresh the PR to see if files were updated, or comment on changed lines only.",
+        ),
+        "submitted_too_quickly" => (
+            "Comments submitted too quickly (rate limiting).",
+            "GitHub is rate limiting comment submissions. Wait a moment and try again, or submit fewer comments at once.",
+        ),
+        "locked" => (
+            "PR conversation is locked.",
+            "Ask a repo maintainer to unlock the conversation on this PR before submitting comments.",
+        ),
+        "rate_limited" | "forbidden" => (
+            "Permission denied or rate limit exceeded.",
+            "Check your token has 'repo' scope. If rate limited, wait for rate limit reset (check X-RateLimit-Reset header).",
+        ),
+        "unauthorized" | "auth_expired" => (
+            "Authentication failed.",
+            "Your GitHub sign-in may be invalid or expired. Sign in again and retry.",
+        ),
+        "not_found" => (
+            "File or PR not found.",
+            "The file may have been deleted or the PR may have been closed. Refresh the PR to see current files.",
+        ),
+        "invalid_path" => (
+            "Invalid file path.",
+            "The file path doesn't exist in the PR. File may have been renamed or deleted. Refresh the PR.",
+        ),
+        "outdated_commit" => (
+            "Commit ID is outdated.",
+            "The PR was updated after you created comments. Refresh the PR to get the latest commit SHA.",
+        ),
+        "spam_detection" => (
+            "Review API rate limiting or spam detection.",
+            "GitHub detected too many review API calls. Wait a few moments before submitting more comments.",
+        ),
+        "invalid_side" => (
+            "Invalid side parameter.",
+            "The 'side' must be 'LEFT' (deletions) or 'RIGHT' (additions). Check your comment configuration.",
+        ),
+        "empty_body" => (
+            "Comment body is empty.",
+            "Comment text cannot be blank. Make sure all comments have content.",
+        ),
+        _ => (
+            "Comment submission failed.",
+            "Check the logs for details. Common issues: PR updates, file changes, or API errors.",
+        ),
+    }
+}
+
+/// The message shown when some comments failed, based on the first failure.
+pub fn summarize(posted: usize, total: usize, failed: &[FailedComment]) -> Option<String> {
+    let first = failed.first()?;
+    let (explanation, fix) = explain(&first.error_code);
+    Some(if posted > 0 {
+        format!(
+            "Submitted {posted} of {total} comments. {} failed.\n\n💡 Issue: {explanation}\nTo fix: {fix}",
+            failed.len()
+        )
+    } else {
+        format!("Failed to submit all {total} comments.\n\n💡 Issue: {explanation}\nTo fix: {fix}")
+    })
+}
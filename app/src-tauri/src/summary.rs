@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::github::parse_hunk_header;
+use crate::local_files::is_markdown_path;
+use crate::models::PullRequestFile;
+
+const DOC_EXTENSIONS: &[&str] = &["md", "markdown", "mdx", "rst", "adoc", "txt"];
+const TOP_N: usize = 10;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DirectorySummary {
+    pub directory: String,
+    pub files: usize,
+    pub additions: u32,
+    pub deletions: u32,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FileChange {
+    pub path: String,
+    pub status: String,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RenamedDoc {
+    pub from: String,
+    pub to: String,
+}
+
+/// Headings added to or removed from one markdown file, e.g. `## Install`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TocChange {
+    pub path: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HeadingChurn {
+    pub path: String,
+    pub heading: String,
+    pub changed_lines: u32,
+}
+
+/// Structural overview of a pull request, built from file stats and patches only.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PrSummary {
+    pub total_files: usize,
+    pub total_additions: u32,
+    pub total_deletions: u32,
+    pub directories: Vec<DirectorySummary>,
+    pub largest_changes: Vec<FileChange>,
+    pub added_docs: Vec<String>,
+    pub removed_docs: Vec<String>,
+    pub renamed_docs: Vec<RenamedDoc>,
+    pub toc_changes: Vec<TocChange>,
+    pub top_headings: Vec<HeadingChurn>,
+}
+
+pub fn is_doc(path: &str) -> bool {
+    path.rsplit_once('.')
+        .map(|(_, ext)| DOC_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn directory_of(path: &str) -> String {
+    path.rsplit_once('/')
+        .map(|(dir, _)| dir.to_string())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+/// Return `## Title` for an ATX heading line, or `None`.
+fn parse_heading(line: &str) -> Option<String> {
+    let trimmed = line.trim_end();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let title = trimmed[level..].trim().trim_end_matches('#').trim();
+    if title.is_empty() || !trimmed[level..].starts_with(' ') {
+        return None;
+    }
+    Some(format!("{} {}", "#".repeat(level), title))
+}
+
+pub fn summarize(files: &[PullRequestFile]) -> PrSummary {
+    let mut directories: BTreeMap<String, DirectorySummary> = BTreeMap::new();
+    let mut added_docs = Vec::new();
+    let mut removed_docs = Vec::new();
+    let mut renamed_docs = Vec::new();
+    let mut toc_changes = Vec::new();
+    let mut top_headings = Vec::new();
+
+    for file in files {
+        let directory = directory_of(&file.path);
+        let entry = directories
+            .entry(directory.clone())
+            .or_insert_with(|| DirectorySummary {
+                directory,
+                files: 0,
+                additions: 0,
+                deletions: 0,
+                paths: Vec::new(),
+            });
+        entry.files += 1;
+        entry.additions += file.additions;
+        entry.deletions += file.deletions;
+        entry.paths.push(file.path.clone());
+
+        if is_doc(&file.path) {
+            match file.status.as_str() {
+                "added" => added_docs.push(file.path.clone()),
+                "removed" => removed_docs.push(file.path.clone()),
+                "renamed" => {
+                    if let Some(from) = &file.previous_filename {
+                        renamed_docs.push(RenamedDoc {
+                            from: from.clone(),
+                            to: file.path.clone(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let (true, Some(patch)) = (is_markdown_path(&file.path), &file.patch) {
+            let (toc, churn) = analyze_markdown_patch(&file.path, patch);
+            if !toc.added.is_empty() || !toc.removed.is_empty() {
+                toc_changes.push(toc);
+            }
+            top_headings.extend(churn);
+        }
+    }
+
+    let mut directories: Vec<DirectorySummary> = directories.into_values().collect();
+    directories.sort_by(|a, b| {
+        (b.additions + b.deletions)
+            .cmp(&(a.additions + a.deletions))
+            .then_with(|| a.directory.cmp(&b.directory))
+    });
+
+    let mut largest_changes: Vec<FileChange> = files
+        .iter()
+        .map(|file| FileChange {
+            path: file.path.clone(),
+            status: file.status.clone(),
+            additions: file.additions,
+            deletions: file.deletions,
+        })
+        .collect();
+    largest_changes.sort_by(|a, b| {
+        (b.additions + b.deletions)
+            .cmp(&(a.additions + a.deletions))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    largest_changes.truncate(TOP_N);
+
+    top_headings.sort_by(|a, b| {
+        b.changed_lines
+            .cmp(&a.changed_lines)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    top_headings.truncate(TOP_N);
+
+    PrSummary {
+        total_files: files.len(),
+        total_additions: files.iter().map(|f| f.additions).sum(),
+        total_deletions: files.iter().map(|f| f.deletions).sum(),
+        directories,
+        largest_changes,
+        added_docs,
+        removed_docs,
+        renamed_docs,
+        toc_changes,
+        top_headings,
+    }
+}
+
+/// Collect heading additions/removals and attribute each changed line to the heading
+/// it falls under. Only headings visible in the patch are known, so changes above the
+/// first heading of a hunk use the hunk header's section text when git supplied one.
+fn analyze_markdown_patch(path: &str, patch: &str) -> (TocChange, Vec<HeadingChurn>) {
+    let mut toc = TocChange {
+        path: path.to_string(),
+        added: Vec::new(),
+        removed: Vec::new(),
+    };
+    let mut churn: BTreeMap<String, u32> = BTreeMap::new();
+    let mut current = String::from("(top of file)");
+
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            if parse_hunk_header(line).is_some_and(|(_, right)| right <= 1) {
+                current = String::from("(top of file)");
+            }
+            let section = line.splitn(3, "@@").nth(2).unwrap_or("").trim();
+            if let Some(heading) = parse_heading(section) {
+                current = heading;
+            }
+            continue;
+        }
+
+        if let Some(added) = line.strip_prefix('+') {
+            if let Some(heading) = parse_heading(added) {
+                toc.added.push(heading.clone());
+                current = heading;
+            }
+            *churn.entry(current.clone()).or_default() += 1;
+        } else if let Some(removed) = line.strip_prefix('-') {
+            if let Some(heading) = parse_heading(removed) {
+                toc.removed.push(heading);
+            }
+            *churn.entry(current.clone()).or_default() += 1;
+        } else if let Some(heading) = line.strip_prefix(' ').and_then(parse_heading) {
+            current = heading;
+        }
+    }
+
+    // A heading removed and re-added unchanged is not a TOC change
+    let unchanged: Vec<String> = toc
+        .added
+        .iter()
+        .filter(|h| toc.removed.contains(h))
+        .cloned()
+        .collect();
+    toc.added.retain(|h| !unchanged.contains(h));
+    toc.removed.retain(|h| !unchanged.contains(h));
+
+    let churn = churn
+        .into_iter()
+        .map(|(heading, changed_lines)| HeadingChurn {
+            path: path.to_string(),
+            heading,
+            changed_lines,
+        })
+        .collect();
+    (toc, churn)
+}
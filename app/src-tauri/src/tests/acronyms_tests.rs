@@ -0,0 +1,66 @@
+// Category 43: Acronym First-Use Tests (acronyms.rs)
+// Tests for acronyms introduced without being spelled out
+
+use std::collections::HashSet;
+
+use crate::acronyms;
+
+/// Test Case 43.1: Undefined and Late-Defined Acronyms Are Reported
+#[test]
+fn test_undefined_and_late_definitions() {
+    let content = "\
+# Setup
+
+Configure the SDK before calling the API.
+Requests go through the CDN.
+The Content Delivery Network (CDN) caches responses.
+";
+
+    let findings = acronyms::check_content("docs/setup.md", content, None);
+
+    let summary: Vec<(&str, u64, Option<u64>)> = findings
+        .iter()
+        .map(|f| (f.acronym.as_str(), f.line, f.defined_at))
+        .collect();
+    assert_eq!(summary, vec![("SDK", 3, None), ("CDN", 4, Some(5))]);
+}
+
+/// Test Case 43.2: Both Definition Styles Count on First Use
+#[test]
+fn test_definition_styles() {
+    let content = "\
+Use Single Sign-On (SSO) for every account.
+MFA (multi-factor authentication) is required; SSO and MFAs apply everywhere.
+";
+
+    assert!(acronyms::check_content("a.md", content, None).is_empty());
+}
+
+/// Test Case 43.3: Code, Well-Known Acronyms, and Admonitions Are Skipped
+#[test]
+fn test_skips_code_and_common_words() {
+    let content = "\
+> [!NOTE]
+> You MUST set the `GRPC_PORT` value over HTTPS.
+
+```
+export AWS_REGION=us-east-1
+```
+";
+
+    assert!(acronyms::check_content("a.md", content, None).is_empty());
+}
+
+/// Test Case 43.4: Only Acronyms First Used on Changed Lines Are Reported
+#[test]
+fn test_limited_to_changed_lines() {
+    let content = "The SLA is documented elsewhere.\nNew text mentions the RPO and the SLA.\n";
+    let changed: HashSet<u64> = [2].into_iter().collect();
+
+    let findings = acronyms::check_content("a.md", content, Some(&changed));
+    let names: Vec<&str> = findings.iter().map(|f| f.acronym.as_str()).collect();
+    assert_eq!(names, vec!["RPO"]);
+
+    let patch = "@@ -1,1 +1,2 @@\n The SLA is documented elsewhere.\n+New text mentions the RPO and the SLA.";
+    assert_eq!(acronyms::check_file("a.md", content, Some(patch)), findings);
+}
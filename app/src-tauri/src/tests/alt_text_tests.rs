@@ -0,0 +1,57 @@
+// Category 46: Alt Text Tests (alt_text.rs)
+// Tests for images added without alt text
+
+use crate::alt_text::{self, AltTextIssue};
+
+/// Test Case 46.1: Markdown and HTML Images Without Alt Text
+#[test]
+fn test_scan_line_finds_images() {
+    let findings = alt_text::scan_line(
+        "docs/a.md",
+        3,
+        r#"![](img/one.png) ![  ][diagram] ![Chart](ok.png) <img src="two.png"> <IMG alt="" src='three.png'> <img alt="Logo" src="ok.svg">"#,
+    );
+
+    let summary: Vec<(&str, AltTextIssue)> = findings
+        .iter()
+        .map(|f| (f.image.as_str(), f.issue))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("img/one.png", AltTextIssue::Empty),
+            ("diagram", AltTextIssue::Empty),
+            ("two.png", AltTextIssue::Missing),
+            ("three.png", AltTextIssue::Empty),
+        ]
+    );
+    assert!(findings.iter().all(|f| f.line == 3));
+    assert!(findings[0].suggested_comment.contains("img/one.png"));
+}
+
+/// Test Case 46.2: Code Is Skipped and Patches Limit the Lines Checked
+#[test]
+fn test_check_file_skips_code_and_unchanged_lines() {
+    let content = "\
+![](old.png)
+Use `![](syntax)` to embed.
+
+```md
+![](in-fence.png)
+```
+![](new.png)
+";
+
+    let all: Vec<u64> = alt_text::check_file("a.md", content, None)
+        .iter()
+        .map(|f| f.line)
+        .collect();
+    assert_eq!(all, vec![1, 7]);
+
+    let patch = "@@ -1,6 +1,7 @@\n ![](old.png)\n Use `![](syntax)` to embed.\n \n ```md\n ![](in-fence.png)\n ```\n+![](new.png)";
+    let changed: Vec<u64> = alt_text::check_file("a.md", content, Some(patch))
+        .iter()
+        .map(|f| f.line)
+        .collect();
+    assert_eq!(changed, vec![7]);
+}
@@ -0,0 +1,50 @@
+// Category 76: Anchor Impact Tests (anchor_impact.rs)
+// Tests for detecting removed heading anchors and the links they break
+
+use crate::anchor_impact;
+use crate::link_index::LinkIndex;
+
+/// Test Case 76.1: Renamed Headings Paired With Their Replacements
+#[test]
+fn test_renamed_anchors() {
+    let base = "# Guide\n\n## Install\n\n## Configure\n";
+    let head = "# Guide\n\n## Install the CLI\n\n## Configure\n";
+    let changes = anchor_impact::changed_anchors("docs/guide.md", base, head);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].old_anchor, "install");
+    assert_eq!(changes[0].new_anchor.as_deref(), Some("install-the-cli"));
+    assert_eq!(changes[0].line, Some(3));
+
+    // Removing a heading without adding one leaves no replacement
+    let changes = anchor_impact::changed_anchors("docs/guide.md", base, "# Guide\n\n## Configure\n");
+    assert_eq!(changes[0].new_anchor, None);
+    assert!(anchor_impact::changed_anchors("docs/guide.md", base, base).is_empty());
+}
+
+/// Test Case 76.2: Links to Removed Anchors Reported
+#[test]
+fn test_broken_links_reported() {
+    let base = "# Guide\n\n## Install\n";
+    let head = "# Guide\n\n## Installation\n";
+    let changes = anchor_impact::changed_anchors("docs/guide.md", base, head);
+
+    let files = vec![
+        ("README.md".to_string(), "Start [here](docs/guide.md#install).\n".to_string()),
+        ("docs/faq.md".to_string(), "[ok](guide.md#guide) [old](guide.md#INSTALL)\n".to_string()),
+    ];
+    let report = anchor_impact::report(changes, &LinkIndex::build(&files, true));
+
+    let broken: Vec<(&str, u64)> = report
+        .broken_links
+        .iter()
+        .map(|l| (l.source_path.as_str(), l.line))
+        .collect();
+    assert_eq!(broken, vec![("README.md", 1), ("docs/faq.md", 1)]);
+    assert!(report
+        .broken_links
+        .iter()
+        .all(|l| l.suggested_anchor.as_deref() == Some("installation")));
+    assert_eq!(report.indexed_files, 2);
+    assert!(report.truncated);
+}
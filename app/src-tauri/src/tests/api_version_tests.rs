@@ -0,0 +1,69 @@
+// Category 58: API Version and Deprecation Tests (api_version.rs)
+// Tests for spotting GitHub API deprecation and sunset signals on responses
+
+use crate::api_version::{detect, is_unsupported_version, API_VERSION};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+
+fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in pairs {
+        map.append(*name, HeaderValue::from_static(value));
+    }
+    map
+}
+
+/// Test Case 58.1: Ordinary Responses Carry No Warning
+#[test]
+fn test_detect_nothing() {
+    let map = headers(&[
+        ("x-github-api-version-selected", API_VERSION),
+        ("link", "<https://api.github.com/repos/o/r/pulls?page=2>; rel=\"next\""),
+    ]);
+    assert!(detect(&map, "list pull requests", "/repos/o/r/pulls").is_none());
+}
+
+/// Test Case 58.2: Deprecation and Sunset Headers Are Parsed
+#[test]
+fn test_detect_deprecation_and_sunset() {
+    let map = headers(&[
+        ("deprecation", "@1688169599"),
+        ("sunset", "Sun, 30 Jun 2024 23:59:59 GMT"),
+        (
+            "link",
+            "<https://api.github.com/repos/o/r/pulls?page=2>; rel=\"next\", <https://docs.github.com/changes>; rel=\"deprecation\"; type=\"text/html\"",
+        ),
+    ]);
+    let warning = detect(&map, "fetch pull request", "/repos/o/r/pulls/1").unwrap();
+
+    assert!(warning.deprecated);
+    assert_eq!(warning.deprecated_at.as_deref(), Some("2023-06-30T23:59:59+00:00"));
+    assert_eq!(warning.sunset_at.as_deref(), Some("2024-06-30T23:59:59+00:00"));
+    assert_eq!(warning.link.as_deref(), Some("https://docs.github.com/changes"));
+    assert_eq!(warning.endpoint, "/repos/o/r/pulls/1");
+    assert!(!warning.unsupported_version);
+}
+
+/// Test Case 58.3: A Different Served Version Is Reported
+#[test]
+fn test_detect_selected_version() {
+    let map = headers(&[("x-github-api-version-selected", "2026-03-10")]);
+    let warning = detect(&map, "fetch user", "/user").unwrap();
+
+    assert_eq!(warning.selected_version.as_deref(), Some("2026-03-10"));
+    assert!(!warning.deprecated);
+
+    let map = headers(&[("deprecation", "true")]);
+    let warning = detect(&map, "fetch user", "/user").unwrap();
+    assert!(warning.deprecated);
+    assert!(warning.deprecated_at.is_none());
+}
+
+/// Test Case 58.4: Rejected API Versions Are Recognized
+#[test]
+fn test_is_unsupported_version() {
+    let body = r#"{"message":"Unsupported 'X-GitHub-Api-Version' header. Supported API versions: 2026-03-10"}"#;
+    assert!(is_unsupported_version(StatusCode::BAD_REQUEST, body));
+    assert!(!is_unsupported_version(StatusCode::NOT_FOUND, body));
+    assert!(!is_unsupported_version(StatusCode::BAD_REQUEST, r#"{"message":"Validation Failed"}"#));
+}
@@ -0,0 +1,85 @@
+// Category 54: Attention Tests (attention.rs)
+// Tests for deciding whether a PR is waiting on me
+
+use crate::attention::{needs_attention, TimelineActivity};
+
+fn reviewed(by: &str, at: &str) -> TimelineActivity {
+    TimelineActivity::Reviewed { by: by.to_string(), at: at.to_string() }
+}
+
+fn requested(reviewer: &str, at: &str) -> TimelineActivity {
+    TimelineActivity::ReviewRequested { reviewer: reviewer.to_string(), at: at.to_string() }
+}
+
+fn committed(at: &str) -> TimelineActivity {
+    TimelineActivity::Committed { at: at.to_string() }
+}
+
+fn thread(comments: &[(&str, &str)]) -> TimelineActivity {
+    TimelineActivity::Thread {
+        comments: comments.iter().map(|(by, at)| (by.to_string(), at.to_string())).collect(),
+    }
+}
+
+/// Test Case 54.1: Review Requested and Not Yet Given
+#[test]
+fn test_requested_review_needs_attention() {
+    let activity = vec![requested("Me", "2024-03-01T10:00:00Z")];
+    assert!(needs_attention("me", "author", &activity));
+}
+
+/// Test Case 54.2: Re-Request After My Review
+#[test]
+fn test_rerequested_review_needs_attention() {
+    let answered = vec![
+        requested("me", "2024-03-01T10:00:00Z"),
+        reviewed("me", "2024-03-02T10:00:00Z"),
+    ];
+    assert!(!needs_attention("me", "author", &answered));
+
+    let mut rerequested = answered.clone();
+    rerequested.push(requested("me", "2024-03-03T10:00:00Z"));
+    assert!(needs_attention("me", "author", &rerequested));
+}
+
+/// Test Case 54.3: New Commits After My Review
+#[test]
+fn test_new_commits_need_attention() {
+    let activity = vec![
+        committed("2024-03-01T09:00:00Z"),
+        reviewed("me", "2024-03-02T10:00:00Z"),
+    ];
+    assert!(!needs_attention("me", "author", &activity));
+
+    let mut pushed = activity.clone();
+    pushed.push(committed("2024-03-02T12:00:00Z"));
+    assert!(needs_attention("me", "author", &pushed));
+}
+
+/// Test Case 54.4: Author Replied in My Thread
+#[test]
+fn test_author_reply_needs_attention() {
+    let replied = vec![thread(&[
+        ("me", "2024-03-01T10:00:00Z"),
+        ("author", "2024-03-01T11:00:00Z"),
+    ])];
+    assert!(needs_attention("me", "author", &replied));
+
+    let answered_back = vec![thread(&[
+        ("me", "2024-03-01T10:00:00Z"),
+        ("author", "2024-03-01T11:00:00Z"),
+        ("me", "2024-03-01T12:00:00Z"),
+    ])];
+    assert!(!needs_attention("me", "author", &answered_back));
+
+    // Threads I never commented in do not count
+    let others = vec![thread(&[("someone", "2024-03-01T10:00:00Z"), ("author", "2024-03-01T11:00:00Z")])];
+    assert!(!needs_attention("me", "author", &others));
+}
+
+/// Test Case 54.5: Never My Turn on My Own PR
+#[test]
+fn test_own_pr_never_needs_attention() {
+    let activity = vec![requested("me", "2024-03-01T10:00:00Z")];
+    assert!(!needs_attention("Me", "me", &activity));
+}
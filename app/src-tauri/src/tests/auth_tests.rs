@@ -0,0 +1,52 @@
+// Category 84: Device Sign-In Tests (auth.rs)
+// Tests for reading GitHub's device flow token responses
+
+use crate::auth::{device_poll_outcome, DevicePoll, LoginMethod};
+use crate::error::AppError;
+use serde_json::json;
+
+/// Test Case 84.1: Token and Pending Responses
+#[test]
+fn test_device_poll_token_and_pending() {
+    let token = json!({ "access_token": "gho_abc", "token_type": "bearer", "scope": "repo" });
+    assert_eq!(device_poll_outcome(&token, 5).unwrap(), DevicePoll::Token("gho_abc".into()));
+
+    let pending = json!({ "error": "authorization_pending" });
+    assert_eq!(device_poll_outcome(&pending, 5).unwrap(), DevicePoll::Pending);
+}
+
+/// Test Case 84.2: Slow Down Lengthens the Interval
+#[test]
+fn test_device_poll_slow_down() {
+    let named = json!({ "error": "slow_down", "interval": 12 });
+    assert_eq!(device_poll_outcome(&named, 5).unwrap(), DevicePoll::SlowDown(12));
+
+    let unnamed = json!({ "error": "slow_down" });
+    assert_eq!(device_poll_outcome(&unnamed, 5).unwrap(), DevicePoll::SlowDown(10));
+}
+
+/// Test Case 84.3: Denied, Expired and Unknown Errors End the Sign-In
+#[test]
+fn test_device_poll_errors() {
+    let denied = json!({ "error": "access_denied" });
+    assert!(matches!(device_poll_outcome(&denied, 5), Err(AppError::OAuthCancelled)));
+
+    let expired = json!({ "error": "expired_token" });
+    assert!(matches!(device_poll_outcome(&expired, 5), Err(AppError::Timeout)));
+
+    let disabled = json!({
+        "error": "device_flow_disabled",
+        "error_description": "Device flow must be enabled for this app"
+    });
+    let err = device_poll_outcome(&disabled, 5).unwrap_err().to_string();
+    assert!(err.contains("Device flow must be enabled"), "{err}");
+}
+
+/// Test Case 84.4: Login Method Defaults to the Browser Flow
+#[test]
+fn test_login_method() {
+    assert_eq!(LoginMethod::default(), LoginMethod::Browser);
+    let device: LoginMethod = serde_json::from_value(json!("device")).unwrap();
+    assert_eq!(device, LoginMethod::Device);
+    assert!(serde_json::from_value::<LoginMethod>(json!("password")).is_err());
+}
@@ -0,0 +1,75 @@
+// Category 31: Repository Listing Cache Tests (cache.rs, models.rs)
+// Tests for the TTL cache behind repository pickers and repository filtering
+
+use crate::cache::TtlCache;
+use crate::models::{self, RepositorySummary};
+use std::time::Duration;
+
+fn repo(full_name: &str, description: Option<&str>) -> RepositorySummary {
+    let (owner, name) = full_name.split_once('/').unwrap();
+    RepositorySummary {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        full_name: full_name.to_string(),
+        description: description.map(str::to_string),
+        private: false,
+        archived: false,
+        fork: false,
+        default_branch: "main".to_string(),
+        updated_at: "2024-01-01T00:00:00Z".to_string(),
+        pushed_at: None,
+        can_push: true,
+        affiliation: None,
+        last_pr_activity: None,
+    }
+}
+
+/// Test Case 31.1: Cached Values Returned Until Cleared
+#[test]
+fn test_cache_get_insert_clear() {
+    let cache: TtlCache<Vec<String>> = TtlCache::new(Duration::from_secs(60));
+    assert!(cache.get("@me").is_none());
+
+    cache.insert("@me", vec!["octocat/docs".to_string()]);
+    assert_eq!(cache.get("@me").unwrap(), vec!["octocat/docs"]);
+
+    cache.clear();
+    assert!(cache.get("@me").is_none());
+}
+
+/// Test Case 31.2: Expired Entries Ignored
+#[test]
+fn test_cache_expiry() {
+    let cache: TtlCache<u32> = TtlCache::new(Duration::from_millis(0));
+    cache.insert("key", 1);
+    assert!(cache.get("key").is_none());
+}
+
+/// Test Case 31.3: Repository Filter Matches Name and Description
+#[test]
+fn test_repository_matches() {
+    let docs = repo("Contoso/Product-Docs", Some("Public documentation site"));
+
+    assert!(docs.matches("product-docs"));
+    assert!(docs.matches("contoso/"));
+    assert!(docs.matches("DOCUMENTATION"));
+    assert!(docs.matches("  "));
+    assert!(!docs.matches("api"));
+}
+
+/// Test Case 31.4: Repositories Sorted By Recent PR Activity
+#[test]
+fn test_sort_by_recent_activity() {
+    let mut quiet = repo("octocat/quiet", None);
+    quiet.pushed_at = Some("2024-06-01T00:00:00Z".to_string());
+    let mut busy = repo("octocat/busy", None);
+    busy.last_pr_activity = Some("2024-05-01T00:00:00Z".to_string());
+    let mut busiest = repo("octocat/busiest", None);
+    busiest.last_pr_activity = Some("2024-05-20T00:00:00Z".to_string());
+
+    let mut repos = vec![quiet, busy, busiest];
+    models::sort_by_recent_activity(&mut repos);
+
+    let names: Vec<_> = repos.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["busiest", "busy", "quiet"]);
+}
@@ -0,0 +1,105 @@
+// Category 37: Comment Category Tests (categories.rs, review_storage.rs)
+// Tests for per-comment categories and splitting a review by category on submit
+
+use std::collections::HashMap;
+
+use crate::categories::{plan_submission, SubmissionTarget, UNCATEGORIZED};
+use crate::review_storage::ReviewComment;
+use super::create_test_storage;
+
+fn comment(id: i64, line: u64, category: Option<&str>) -> ReviewComment {
+    ReviewComment {
+        id,
+        owner: "o".to_string(),
+        repo: "r".to_string(),
+        pr_number: 1,
+        file_path: "guide.md".to_string(),
+        line_number: line,
+        side: "RIGHT".to_string(),
+        body: format!("comment {id}"),
+        commit_id: "abc".to_string(),
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: "2024-01-01T00:00:00Z".to_string(),
+        deleted: false,
+        in_reply_to_id: None,
+        category: category.map(str::to_string),
+        general: false,
+        suggestion: None,
+    }
+}
+
+fn ids(comments: &[ReviewComment]) -> Vec<i64> {
+    comments.iter().map(|c| c.id).collect()
+}
+
+/// Test Case 37.1: By default blocking comments request changes and the rest comment
+#[test]
+fn test_default_plan() {
+    let comments = vec![
+        comment(1, 3, Some("blocking")),
+        comment(2, 4, Some("nit")),
+        comment(3, 5, None),
+        comment(4, 0, Some("blocking")),
+    ];
+    let plan = plan_submission(&comments, &HashMap::new()).unwrap();
+
+    assert_eq!(ids(&plan.request_changes), vec![1]);
+    assert_eq!(ids(&plan.comment), vec![2, 3]);
+    // File-level comments cannot join a review
+    assert_eq!(ids(&plan.single), vec![4]);
+}
+
+/// Test Case 37.2: The mapping overrides targets per category
+#[test]
+fn test_mapping_overrides() {
+    let comments = vec![comment(1, 3, Some("nit")), comment(2, 4, None)];
+    let mapping = HashMap::from([
+        ("nit".to_string(), SubmissionTarget::Single),
+        (UNCATEGORIZED.to_string(), SubmissionTarget::RequestChanges),
+    ]);
+    let plan = plan_submission(&comments, &mapping).unwrap();
+
+    assert_eq!(ids(&plan.single), vec![1]);
+    assert_eq!(ids(&plan.request_changes), vec![2]);
+    assert!(plan.comment.is_empty());
+}
+
+/// Test Case 37.3: Unknown categories in the mapping are rejected
+#[test]
+fn test_mapping_unknown_category() {
+    let mapping = HashMap::from([("typo".to_string(), SubmissionTarget::Comment)]);
+    assert!(plan_submission(&[], &mapping).is_err());
+}
+
+/// Test Case 37.4: Categories persist in storage and can be cleared
+#[tokio::test]
+async fn test_storage_category() {
+    let (storage, _temp_dir) = create_test_storage();
+    storage.start_review("o", "r", 1, "abc", None, None).unwrap();
+    let added = storage
+        .add_comment("o", "r", 1, "guide.md", 3, "RIGHT", "Broken link", "abc", None)
+        .await
+        .unwrap();
+    assert!(added.category.is_none());
+
+    storage.set_comment_category(added.id, Some("blocking")).await.unwrap();
+    let loaded = storage.get_comments("o", "r", 1).unwrap();
+    assert_eq!(loaded[0].category.as_deref(), Some("blocking"));
+
+    storage.set_comment_category(added.id, None).await.unwrap();
+    assert!(storage.get_comment(added.id).unwrap().unwrap().category.is_none());
+    assert!(storage.set_comment_category(added.id + 1, Some("nit")).await.is_err());
+}
+
+/// Test Case 37.5: Replies are posted singly so they stay in their thread
+#[test]
+fn test_replies_posted_singly() {
+    let mut reply = comment(2, 4, Some("blocking"));
+    reply.in_reply_to_id = Some(987_654_321);
+    let comments = vec![comment(1, 3, Some("blocking")), reply];
+    let plan = plan_submission(&comments, &HashMap::new()).unwrap();
+
+    assert_eq!(ids(&plan.request_changes), vec![1]);
+    assert_eq!(ids(&plan.single), vec![2]);
+    assert_eq!(plan.single[0].in_reply_to_id, Some(987_654_321));
+}
@@ -0,0 +1,122 @@
+// Category 78: Command Argument Tests (command_args.rs)
+// Tests for validated argument types and the generated per-command argument structs
+
+use crate::command_args::{Owner, PrNumber, Reaction, Repo, RepoPath};
+use serde_json::json;
+
+fn parse<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// Deserialization error for a value that should be rejected.
+fn rejection<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> String {
+    parse::<T>(value).err().expect("value should be rejected")
+}
+
+/// Test Case 78.1: Owners Are GitHub Logins or the Local Owner
+#[test]
+fn test_owner_validation() {
+    for owner in ["octocat", "my-org", "jdoe_acme", "__local__"] {
+        assert_eq!(parse::<Owner>(json!(owner)).unwrap(), Owner(owner.to_string()));
+    }
+    for owner in ["", "-leading", "owner/repo", "has space", &"a".repeat(40)] {
+        let err = parse::<Owner>(json!(owner)).unwrap_err();
+        assert!(err.starts_with("invalid owner"), "{err}");
+    }
+}
+
+/// Test Case 78.2: Repository Names Allow Dots but Not Path Syntax
+#[test]
+fn test_repo_validation() {
+    for repo in ["react", "docs.github.com", "my_repo-1", "local"] {
+        assert!(parse::<Repo>(json!(repo)).is_ok(), "{repo}");
+    }
+    for repo in ["", ".", "..", "a/b", "a\\b"] {
+        let err = parse::<Repo>(json!(repo)).unwrap_err();
+        assert!(err.starts_with("invalid repository"), "{err}");
+    }
+}
+
+/// Test Case 78.3: Pull Request Numbers Are Range Checked
+#[test]
+fn test_pr_number_range() {
+    assert_eq!(parse::<PrNumber>(json!(1)).unwrap(), PrNumber(1));
+    assert_eq!(parse::<PrNumber>(json!(2147483647)).unwrap(), PrNumber(2147483647));
+    assert!(parse::<PrNumber>(json!(0)).unwrap_err().starts_with("invalid pull request number"));
+    assert!(parse::<PrNumber>(json!(2147483648u64)).is_err());
+    assert!(parse::<PrNumber>(json!(-1)).is_err());
+}
+
+/// Test Case 78.4: Repository Paths Cannot Leave the Tree
+#[test]
+fn test_repo_path_validation() {
+    for path in ["README.md", "docs/guide/intro.md", "docs/..hidden/a.md", "a b/c.md"] {
+        assert!(parse::<RepoPath>(json!(path)).is_ok(), "{path}");
+    }
+    for path in [
+        "",
+        "../secrets.md",
+        "docs/../../etc/passwd",
+        "docs\\..\\..\\x",
+        "/etc/passwd",
+        "\\\\server\\share\\a.md",
+        "C:\\Windows\\win.ini",
+        "a\0b",
+    ] {
+        let err = parse::<RepoPath>(json!(path)).unwrap_err();
+        assert!(err.starts_with("invalid path"), "{err}");
+    }
+}
+
+/// Test Case 78.5: Argument Structs Use camelCase Keys and Reject Unknown Ones
+#[test]
+fn test_args_struct_keys() {
+    let args: crate::ListPullRequestsArgs = parse(json!({
+        "owner": "octocat",
+        "repo": "hello-world",
+        "currentLogin": "me",
+    }))
+    .unwrap();
+    assert_eq!(args.owner, Owner("octocat".to_string()));
+    assert_eq!(args.state, None);
+    assert_eq!(args.current_login.as_deref(), Some("me"));
+
+    let err = rejection::<crate::ListPullRequestsArgs>(json!({
+        "owner": "octocat",
+        "repo": "hello-world",
+        "current_login": "me",
+    }));
+    assert!(err.contains("unknown field `current_login`"), "{err}");
+
+    let err = rejection::<crate::GetPullRequestArgs>(json!({
+        "owner": "octocat",
+        "repo": "hello-world",
+    }));
+    assert!(err.contains("missing field `number`"), "{err}");
+}
+
+/// Test Case 78.6: Invalid Field Values Fail Deserialization of the Whole Struct
+#[test]
+fn test_args_struct_validation() {
+    let err = rejection::<crate::GetFileContentsArgs>(json!({
+        "owner": "octocat",
+        "repo": "hello-world",
+        "filePath": "../../outside.md",
+        "baseSha": "abc",
+        "headSha": "def",
+        "status": "modified",
+    }));
+    assert!(err.contains("invalid path \"../../outside.md\""), "{err}");
+}
+
+/// Test Case 78.7: Reactions Use GitHub's Names
+#[test]
+fn test_reaction_validation() {
+    for reaction in ["+1", "-1", "hooray", "eyes"] {
+        assert_eq!(parse::<Reaction>(json!(reaction)).unwrap(), Reaction(reaction.to_string()));
+    }
+    for reaction in ["", "thumbs_up", "THUMBS_UP", "tada"] {
+        let err = parse::<Reaction>(json!(reaction)).unwrap_err();
+        assert!(err.starts_with("invalid reaction"), "{err}");
+    }
+}
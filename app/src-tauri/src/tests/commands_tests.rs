@@ -0,0 +1,98 @@
+// Category 24: Command Registry Tests (commands.rs)
+// Tests for the command metadata exposed to the frontend command palette
+
+use crate::command_registry;
+use crate::commands::{to_camel_case, ParamInfo};
+
+/// Test Case 24.1: Registry Lists Every Command Once
+#[test]
+fn test_registry_has_unique_names() {
+    let registry = command_registry();
+    let mut names: Vec<_> = registry.iter().map(|c| c.name).collect();
+    names.sort();
+    names.dedup();
+
+    assert_eq!(names.len(), registry.len());
+    assert!(names.contains(&"cmd_list_commands"));
+    assert!(registry.iter().all(|c| c.name.starts_with("cmd_") && !c.title.is_empty()));
+}
+
+/// Test Case 24.2: Parameter Schema Derived From Rust Types
+#[test]
+fn test_param_schema_from_types() {
+    let registry = command_registry();
+    let list_prs = registry
+        .iter()
+        .find(|c| c.name == "cmd_list_pull_requests")
+        .expect("cmd_list_pull_requests should be registered");
+
+    assert!(list_prs.requires_auth);
+    let names: Vec<_> = list_prs.params.iter().map(|p| p.js_name.as_str()).collect();
+    assert_eq!(names, vec!["owner", "repo", "state", "currentLogin"]);
+
+    // Validated argument types are described by the JSON value they accept
+    assert_eq!(list_prs.params[0].rust_type, "Owner");
+    assert_eq!(list_prs.params[0].kind, "string");
+
+    let state = &list_prs.params[2];
+    assert_eq!(state.kind, "string");
+    assert!(!state.required);
+
+    let number = ParamInfo::new("pr_number", "u64");
+    assert_eq!(number.kind, "integer");
+    assert!(number.required);
+}
+
+/// Test Case 24.3: Injected Parameters Are Omitted
+#[test]
+fn test_injected_params_omitted() {
+    let registry = command_registry();
+    let submit = registry
+        .iter()
+        .find(|c| c.name == "cmd_submit_local_review")
+        .unwrap();
+
+    assert!(submit.params.iter().all(|p| p.name != "app"));
+    // Every field of the registry's argument struct, in order, and nothing else
+    assert_eq!(submit.params.len(), 7);
+    let names: Vec<_> = submit.params.iter().map(|p| p.name).collect();
+    assert_eq!(
+        names,
+        ["owner", "repo", "pr_number", "event", "body", "acknowledge_warnings", "mode"]
+    );
+}
+
+/// Test Case 24.4: Snake Case To Camel Case
+#[test]
+fn test_to_camel_case() {
+    assert_eq!(to_camel_case("in_reply_to_id"), "inReplyToId");
+    assert_eq!(to_camel_case("owner"), "owner");
+}
+
+/// Test Case 24.5: Lists and Defaulted Fields
+#[test]
+fn test_array_and_defaulted_params() {
+    let registry = command_registry();
+    let run_plugins = registry
+        .iter()
+        .find(|c| c.name == "cmd_run_plugins")
+        .expect("cmd_run_plugins should be registered");
+    let param = |name: &str| run_plugins.params.iter().find(|p| p.name == name).unwrap();
+
+    let files = param("files");
+    assert_eq!(files.kind, "array");
+    assert!(files.required);
+
+    // `#[serde(default)]` fields may be left out; a doc comment alone changes nothing
+    let save_as_drafts = param("save_as_drafts");
+    assert_eq!(save_as_drafts.kind, "boolean");
+    assert!(!save_as_drafts.required);
+    assert!(param("commit_id").required);
+
+    let paths = ParamInfo::new("paths", "Option < Vec < RepoPath > >");
+    assert_eq!(paths.kind, "array");
+    assert!(!paths.required);
+    let refresh = ParamInfo::new("refresh", "bool").with_attributes(&["doc = \"Ask again.\""]);
+    assert!(refresh.required);
+    assert!(!refresh.with_attributes(&["serde(default)"]).required);
+}
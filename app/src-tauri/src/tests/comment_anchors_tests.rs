@@ -0,0 +1,88 @@
+// Category 90: Comment Anchor Tests (comment_anchors.rs)
+// Tests for hashing commented content and storing the hashes with local comments
+
+use crate::comment_anchors::{can_anchor, comment_anchor, line_hash};
+use crate::markdown_render::git_blob_sha;
+use crate::review_storage::CommentAnchor;
+use super::create_test_storage;
+
+/// Test Case 90.1: Anchors Hash the File as Git Does and the Line Without Its Ending
+#[test]
+fn test_comment_anchor() {
+    let content = "# Title\r\nintro\n";
+
+    let anchor = comment_anchor(content, 1);
+    assert_eq!(anchor.blob_sha, git_blob_sha(content));
+    assert_eq!(anchor.line_hash.as_deref(), Some(line_hash("# Title").as_str()));
+    assert_eq!(
+        line_hash("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    assert_eq!(comment_anchor(content, 0).line_hash, None);
+    assert_eq!(comment_anchor(content, 9).line_hash, None);
+    assert_eq!(comment_anchor(content, 9).blob_sha, anchor.blob_sha);
+}
+
+/// Test Case 90.2: Stored Anchors Follow Their Comments Into the Review Log
+#[tokio::test]
+async fn test_comment_anchors_storage() {
+    let (storage, temp) = create_test_storage();
+    storage.start_review("o", "r", 1, "abc", None, None).unwrap();
+    let comment = storage
+        .add_comment("o", "r", 1, "docs/a.md", 2, "RIGHT", "Typo", "abc", None)
+        .await
+        .unwrap();
+    let removed = storage
+        .add_comment("o", "r", 1, "docs/a.md", 3, "RIGHT", "Posted", "abc", None)
+        .await
+        .unwrap();
+    assert!(can_anchor(&comment));
+
+    let anchor = CommentAnchor {
+        blob_sha: "b1".to_string(),
+        line_hash: Some("h1".to_string()),
+    };
+    let anchors = [(comment.id, anchor.clone()), (removed.id, anchor.clone())];
+    storage.set_comment_anchors(&anchors).unwrap();
+    storage.delete_comment_preserve_log(removed.id).unwrap();
+
+    let stored = storage.get_comment_anchors("o", "r", 1).unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored.get(&comment.id), Some(&anchor));
+
+    let read_log = || {
+        std::fs::read_dir(temp.path().join("review_logs"))
+            .unwrap()
+            .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect::<String>()
+    };
+    // Storing anchors leaves the log alone; the next write includes them
+    assert!(!read_log().contains("Anchor: blob b1"));
+    storage.update_comment(comment.id, "Typo here").await.unwrap();
+    assert!(read_log().contains("Anchor: blob b1, line sha256 h1"));
+}
+
+/// Test Case 90.3: Left-Side, General and Image Comments Are Not Anchored
+#[tokio::test]
+async fn test_can_anchor() {
+    let (storage, _temp) = create_test_storage();
+    storage.start_review("o", "r", 1, "abc", None, None).unwrap();
+    let left = storage
+        .add_comment("o", "r", 1, "docs/a.md", 2, "LEFT", "Old", "abc", None)
+        .await
+        .unwrap();
+    let image = storage
+        .add_comment("o", "r", 1, "img/Logo.PNG", 0, "RIGHT", "Blurry", "abc", None)
+        .await
+        .unwrap();
+    let mut general = storage
+        .add_comment("o", "r", 1, "docs/a.md", 2, "RIGHT", "Overall", "abc", None)
+        .await
+        .unwrap();
+    general.general = true;
+
+    assert!(!can_anchor(&left));
+    assert!(!can_anchor(&image));
+    assert!(!can_anchor(&general));
+}
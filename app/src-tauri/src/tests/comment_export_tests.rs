@@ -0,0 +1,230 @@
+// Category 55: Comment Export Tests (comment_export.rs)
+// Tests for rendering pending comments as a markdown task list and exporting reviews
+
+use std::collections::HashMap;
+
+use crate::comment_export::{
+    export_file_name, render_consolidated_review, render_review_csv, render_review_export,
+    render_tasklist, ReviewExportFormat,
+};
+use crate::review_storage::{CommentAnchor, ReviewComment, ReviewMetadata};
+
+const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+fn comment(id: i64, path: &str, line: u64, body: &str) -> ReviewComment {
+    ReviewComment {
+        id,
+        owner: "o".to_string(),
+        repo: "r".to_string(),
+        pr_number: 1,
+        file_path: path.to_string(),
+        line_number: line,
+        side: "RIGHT".to_string(),
+        body: body.to_string(),
+        commit_id: SHA.to_string(),
+        created_at: format!("2024-01-01T00:00:{id:02}Z"),
+        updated_at: "2024-01-01T00:00:00Z".to_string(),
+        deleted: false,
+        in_reply_to_id: None,
+        category: None,
+        general: false,
+        suggestion: None,
+    }
+}
+
+/// Test Case 55.1: Comments Grouped by File in Line Order With Permalinks
+#[test]
+fn test_tasklist_groups_by_file() {
+    let mut blocking = comment(1, "docs/b.md", 20, "Wrong command");
+    blocking.category = Some("blocking".to_string());
+    let comments = vec![
+        blocking,
+        comment(2, "docs/a.md", 3, "Typo"),
+        comment(3, "docs/b.md", 5, "Missing link"),
+    ];
+
+    let export = render_tasklist(&comments, "https://github.com", "o", "r");
+
+    assert_eq!(export.item_count, 3);
+    assert_eq!(export.comment_url, None);
+    let a = export.body.find("### `docs/a.md`").unwrap();
+    let b = export.body.find("### `docs/b.md`").unwrap();
+    let line5 = export.body.find("Missing link").unwrap();
+    let line20 = export.body.find("Wrong command").unwrap();
+    assert!(a < b && b < line5 && line5 < line20);
+    assert!(export.body.contains(&format!(
+        "- [ ] [L20](https://github.com/o/r/blob/{SHA}/docs/b.md#L20) **blocking**: Wrong command\n"
+    )));
+}
+
+/// Test Case 55.2: Multi-Line Bodies Stay Inside Their Item
+#[test]
+fn test_tasklist_multiline_body() {
+    let comments = vec![comment(1, "a.md", 2, "First line\nSecond line\n\nAfter blank\n")];
+
+    let export = render_tasklist(&comments, "https://github.com", "o", "r");
+
+    assert!(export.body.contains("First line\n  Second line\n\n  After blank\n"));
+}
+
+/// Test Case 55.3: Deleted and File-Level Comments
+#[test]
+fn test_tasklist_deleted_and_file_level() {
+    let mut deleted = comment(1, "a.md", 2, "Gone");
+    deleted.deleted = true;
+    let mut bad_sha = comment(3, "a.md", 4, "No link");
+    bad_sha.commit_id = "local".to_string();
+    let comments = vec![deleted, comment(2, "a.md", 0, "Whole file note"), bad_sha];
+
+    let export = render_tasklist(&comments, "https://github.com", "o", "r");
+
+    assert_eq!(export.item_count, 2);
+    assert!(!export.body.contains("Gone"));
+    assert!(export.body.contains("- [ ] L4 No link\n"));
+    assert_eq!(export.body.matches("- [ ] ").count(), 2);
+}
+
+/// Test Case 55.4: Consolidated Review Body Lists Comments by File
+#[test]
+fn test_consolidated_review_body() {
+    let mut nit = comment(2, "docs/a.md", 7, "Use the active voice");
+    nit.category = Some("nit".to_string());
+    let comments = vec![comment(1, "docs/b.md", 0, "Split this page"), nit];
+
+    let review = render_consolidated_review(
+        Some("  Thanks, a few notes below.  "),
+        &comments,
+        "https://github.com",
+        "o",
+        "r",
+    );
+
+    assert!(review.starts_with("Thanks, a few notes below.\n\n---\n\n### `docs/a.md`\n"));
+    assert!(review.contains(&format!(
+        "- [L7](https://github.com/o/r/blob/{SHA}/docs/a.md#L7) **nit**: Use the active voice\n"
+    )));
+    assert!(review.find("docs/a.md").unwrap() < review.find("Split this page").unwrap());
+    assert!(!review.contains("- [ ]"));
+
+    let without_body = render_consolidated_review(None, &comments, "https://github.com", "o", "r");
+    assert!(without_body.starts_with("### `docs/a.md`"));
+}
+
+/// Test Case 55.5: General Comments Lead the Consolidated Review Without an Anchor
+#[test]
+fn test_consolidated_review_general_comments() {
+    let mut general = comment(3, "docs/gone.md", 4, "> On `docs/gone.md` line 4:\n\nWhy remove this?");
+    general.general = true;
+    let comments = vec![comment(1, "docs/a.md", 2, "Fix typo"), general];
+
+    let review = render_consolidated_review(Some("Notes"), &comments, "https://github.com", "o", "r");
+
+    assert!(review.starts_with("Notes\n\n---\n\n> On `docs/gone.md` line 4:\n\nWhy remove this?\n"));
+    assert!(!review.contains("### `docs/gone.md`"));
+    assert!(review.contains("### `docs/a.md`"));
+}
+
+fn metadata() -> ReviewMetadata {
+    ReviewMetadata {
+        owner: "o".to_string(),
+        repo: "r".to_string(),
+        pr_number: 1,
+        commit_id: SHA.to_string(),
+        body: Some("Looks close".to_string()),
+        local_folder: None,
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        log_file_index: 0,
+        due_at: None,
+    }
+}
+
+/// Test Case 55.6: Review Export as Markdown and JSON Skips Deleted Comments
+#[test]
+fn test_review_export_markdown_and_json() {
+    let mut deleted = comment(2, "docs/a.md", 9, "Never mind");
+    deleted.deleted = true;
+    let comments = vec![comment(1, "docs/a.md", 3, "Typo"), deleted];
+
+    let (anchors, web) = (HashMap::new(), "https://github.com");
+    let markdown =
+        render_review_export(ReviewExportFormat::Markdown, &metadata(), &comments, &anchors, web)
+            .unwrap();
+    assert!(markdown.starts_with("# Review of o/r#1\n"));
+    assert!(markdown.contains("\nLooks close\n"));
+    assert!(markdown.contains(&format!(
+        "- [L3](https://github.com/o/r/blob/{SHA}/docs/a.md#L3): Typo\n"
+    )));
+    assert!(!markdown.contains("Never mind"));
+
+    let json = render_review_export(ReviewExportFormat::Json, &metadata(), &comments, &anchors, web)
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["review"]["pr_number"], 1);
+    assert_eq!(value["comments"].as_array().unwrap().len(), 1);
+    assert_eq!(value["comments"][0]["body"], "Typo");
+}
+
+/// Test Case 55.7: CSV Export Quotes Fields and Defuses Formulas
+#[test]
+fn test_review_export_csv() {
+    let mut nit = comment(1, "docs/a.md", 3, "Say \"hello\", then\nwave");
+    nit.category = Some("nit".to_string());
+    let formula = comment(2, "docs/b.md", 0, "=HYPERLINK(\"x\")");
+
+    let csv = render_review_csv(&[nit, formula], &HashMap::new());
+    let lines: Vec<&str> = csv.split("\r\n").collect();
+
+    assert!(lines[0].starts_with("id,file_path,line_number,side,category,"));
+    assert!(csv.contains(",nit,false,,"));
+    assert!(csv.contains(",\"Say \"\"hello\"\", then\nwave\"\r\n"));
+    assert!(csv.contains(",\"'=HYPERLINK(\"\"x\"\")\"\r\n"));
+}
+
+/// Test Case 55.8: The Save Dialog Suggests a File Named After the Review
+#[test]
+fn test_export_file_name() {
+    assert_eq!(export_file_name(&metadata(), ReviewExportFormat::Json), "o-r-1-review.json");
+    assert_eq!(export_file_name(&metadata(), ReviewExportFormat::Csv), "o-r-1-review.csv");
+}
+
+/// Test Case 55.9: Exports Carry Each Anchored Comment's Hashes
+#[test]
+fn test_review_export_includes_anchors() {
+    let comments = vec![comment(1, "docs/a.md", 3, "Typo"), comment(2, "docs/a.md", 0, "Whole")];
+    let anchors = HashMap::from([
+        (
+            1,
+            CommentAnchor {
+                blob_sha: "b1".to_string(),
+                line_hash: Some("h1".to_string()),
+            },
+        ),
+        (
+            2,
+            CommentAnchor {
+                blob_sha: "b1".to_string(),
+                line_hash: None,
+            },
+        ),
+    ]);
+    let web = "https://github.com";
+
+    let json = render_review_export(ReviewExportFormat::Json, &metadata(), &comments, &anchors, web)
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let exported = value["comments"].as_array().unwrap();
+    let typo = exported.iter().find(|c| c["id"] == 1).unwrap();
+    assert_eq!(typo["blob_sha"], "b1");
+    assert_eq!(typo["line_hash"], "h1");
+    assert_eq!(typo["body"], "Typo");
+
+    let csv = render_review_csv(&comments, &anchors);
+    assert!(csv.contains(&format!(",{SHA},b1,h1,")));
+    assert!(csv.contains(&format!(",{SHA},b1,,")));
+
+    let markdown =
+        render_review_export(ReviewExportFormat::Markdown, &metadata(), &comments, &anchors, web)
+            .unwrap();
+    assert!(markdown.contains(": Typo <!-- blob b1 line-sha256 h1 -->\n"));
+    assert!(markdown.contains(": Whole <!-- blob b1 -->\n"));
+}
@@ -0,0 +1,57 @@
+// Category 67: Comment Search Tests (comment_search.rs)
+// Tests for building cross-PR searches of my comments and matching their text
+
+use crate::comment_search::{matches, parse_query, search_query, snippet};
+
+/// Test Case 67.1: Qualifiers Are Kept Apart From Words to Match
+#[test]
+fn test_parse_query() {
+    let query = parse_query("  Versioning updated:>2024-05-01  semver ");
+    assert_eq!(query.terms, vec!["versioning", "semver"]);
+    assert_eq!(query.qualifiers, vec!["updated:>2024-05-01"]);
+}
+
+/// Test Case 67.2: Search Is Limited to PRs I Commented On
+#[test]
+fn test_search_query() {
+    let query = parse_query("versioning created:>2024-05-01");
+    assert_eq!(
+        search_query(&query, None).unwrap(),
+        "is:pr commenter:@me created:>2024-05-01 versioning"
+    );
+    assert_eq!(
+        search_query(&query, Some(" octo/app ")).unwrap(),
+        "is:pr commenter:@me repo:octo/app created:>2024-05-01 versioning"
+    );
+    assert_eq!(search_query(&query, Some("")).unwrap(), "is:pr commenter:@me created:>2024-05-01 versioning");
+}
+
+/// Test Case 67.3: Malformed Searches Are Rejected
+#[test]
+fn test_search_query_rejects() {
+    assert!(search_query(&parse_query("author:me"), None).is_err());
+    assert!(search_query(&parse_query("versioning"), Some("octo")).is_err());
+    assert!(search_query(&parse_query("versioning"), Some("octo/app/extra")).is_err());
+}
+
+/// Test Case 67.4: Every Word Must Appear, Ignoring Case
+#[test]
+fn test_matches() {
+    let terms = parse_query("semver major").terms;
+    assert!(matches("Bump the MAJOR version per SemVer", &terms));
+    assert!(!matches("Bump the major version", &terms));
+}
+
+/// Test Case 67.5: Snippets Keep Context Around the Match
+#[test]
+fn test_snippet() {
+    let terms = parse_query("semver").terms;
+    assert_eq!(snippet("Please follow\nsemver here.", &terms), "Please follow semver here.");
+
+    let long = format!("{} semver {}", "a ".repeat(100), "b ".repeat(100));
+    let cut = snippet(&long, &terms);
+    assert!(cut.starts_with('…'));
+    assert!(cut.ends_with('…'));
+    assert!(cut.contains("semver"));
+    assert!(cut.chars().count() < 170);
+}
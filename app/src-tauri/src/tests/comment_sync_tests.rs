@@ -0,0 +1,49 @@
+// Category 63: Incremental Comment Refresh Tests (comment_sync.rs)
+// Tests for merging changed comments and reviews into a cached PR discussion
+
+use chrono::{TimeZone, Utc};
+
+use crate::comment_sync::{count_mismatch, merge_by_id, settled_reviews, since_param};
+
+/// Test Case 63.1: Since Overlaps the Previous Fetch
+#[test]
+fn test_since_param() {
+    let fetched_at = Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 30).unwrap();
+    assert_eq!(since_param(fetched_at), "2024-03-01T09:59:30Z");
+}
+
+/// Test Case 63.2: Updates Replace Cached Items and New Ones Are Added in Id Order
+#[test]
+fn test_merge_by_id() {
+    let mut cached = vec![(1, "first"), (3, "third")];
+    merge_by_id(&mut cached, vec![(4, "fourth"), (3, "third, edited"), (2, "second")], |c| c.0);
+    assert_eq!(
+        cached,
+        vec![(1, "first"), (2, "second"), (3, "third, edited"), (4, "fourth")]
+    );
+
+    merge_by_id(&mut cached, Vec::new(), |c| c.0);
+    assert_eq!(cached.len(), 4);
+}
+
+/// Test Case 63.3: Only Full Pages Before a Pending Review Are Kept
+#[test]
+fn test_settled_reviews() {
+    assert_eq!(settled_reviews(&[], 100), 0);
+    assert_eq!(settled_reviews(&[false; 250], 100), 200);
+    assert_eq!(settled_reviews(&[false; 99], 100), 0);
+
+    let mut pending = vec![false; 250];
+    pending[120] = true;
+    assert_eq!(settled_reviews(&pending, 100), 100);
+    pending[40] = true;
+    assert_eq!(settled_reviews(&pending, 100), 0);
+}
+
+/// Test Case 63.4: A Count Mismatch Signals Deletions
+#[test]
+fn test_count_mismatch() {
+    assert!(!count_mismatch(3, Some(3)));
+    assert!(count_mismatch(4, Some(3)));
+    assert!(!count_mismatch(4, None));
+}
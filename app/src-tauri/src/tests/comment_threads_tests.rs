@@ -0,0 +1,105 @@
+// Category 66: Comment Thread Tests (github.rs, visits.rs)
+// Tests for grouping line comments into threads with their replies
+
+use std::collections::HashMap;
+
+use crate::github::build_threads;
+use crate::models::PullRequestComment;
+use crate::visits::mark_new_in_threads;
+
+fn comment(id: u64, created_at: &str, in_reply_to_id: Option<u64>) -> PullRequestComment {
+    PullRequestComment {
+        id,
+        body: format!("comment {id}"),
+        author: "reviewer".to_string(),
+        created_at: created_at.to_string(),
+        display_time: String::new(),
+        url: format!("https://github.com/o/r/pull/1#discussion_r{id}"),
+        path: Some("src/lib.rs".to_string()),
+        line: Some(10),
+        side: Some("RIGHT".to_string()),
+        is_review_comment: true,
+        is_draft: false,
+        state: None,
+        is_mine: false,
+        review_id: None,
+        in_reply_to_id,
+        outdated: None,
+        new_since_last_visit: false,
+        reactions: Default::default(),
+    }
+}
+
+/// Test Case 66.1: Replies Join Their Root in Time Order
+#[test]
+fn test_replies_grouped_under_root() {
+    let comments = vec![
+        comment(1, "2024-01-01T10:00:00Z", None),
+        comment(3, "2024-01-01T12:00:00Z", Some(1)),
+        comment(2, "2024-01-01T11:00:00Z", Some(1)),
+        comment(4, "2024-01-01T09:00:00Z", None),
+    ];
+
+    let threads = build_threads(&comments, None);
+    assert_eq!(threads.iter().map(|t| t.id).collect::<Vec<_>>(), vec![4, 1]);
+    let replies: Vec<u64> = threads[1].replies.iter().map(|c| c.id).collect();
+    assert_eq!(replies, vec![2, 3]);
+    assert_eq!(threads[1].path.as_deref(), Some("src/lib.rs"));
+    assert_eq!(threads[1].line, Some(10));
+    assert_eq!(threads[1].resolved, None);
+}
+
+/// Test Case 66.2: Reply Chains Resolve to the First Comment
+#[test]
+fn test_reply_chain_finds_root() {
+    let comments = vec![
+        comment(1, "2024-01-01T10:00:00Z", None),
+        comment(2, "2024-01-01T11:00:00Z", Some(1)),
+        comment(3, "2024-01-01T12:00:00Z", Some(2)),
+    ];
+    let threads = build_threads(&comments, None);
+    assert_eq!(threads.len(), 1);
+    assert_eq!(threads[0].replies.len(), 2);
+}
+
+/// Test Case 66.3: Orphaned Replies and Conversation Comments
+#[test]
+fn test_missing_parent_and_issue_comments() {
+    let mut conversation = comment(5, "2024-01-01T08:00:00Z", None);
+    conversation.is_review_comment = false;
+    conversation.path = None;
+    let comments = vec![conversation, comment(7, "2024-01-01T10:00:00Z", Some(6))];
+
+    let threads = build_threads(&comments, None);
+    assert_eq!(threads.len(), 1);
+    assert_eq!(threads[0].id, 7);
+    assert!(threads[0].replies.is_empty());
+}
+
+/// Test Case 66.4: Resolution State Comes From the Root Id
+#[test]
+fn test_resolution_state() {
+    let mut outdated = comment(2, "2024-01-01T11:00:00Z", None);
+    outdated.outdated = Some(true);
+    let comments = vec![comment(1, "2024-01-01T10:00:00Z", None), outdated];
+    let resolved = HashMap::from([(1, true)]);
+
+    let threads = build_threads(&comments, Some(&resolved));
+    assert_eq!(threads[0].resolved, Some(true));
+    assert_eq!(threads[1].resolved, Some(false));
+    assert!(!threads[0].outdated);
+    assert!(threads[1].outdated);
+}
+
+/// Test Case 66.5: New-Since-Visit Flags Reach Thread Copies
+#[test]
+fn test_mark_new_in_threads() {
+    let comments = vec![
+        comment(1, "2024-01-01T10:00:00Z", None),
+        comment(2, "2024-01-03T10:00:00Z", Some(1)),
+    ];
+    let mut threads = build_threads(&comments, None);
+    mark_new_in_threads(&mut threads, Some("2024-01-02T00:00:00Z"));
+    assert!(!threads[0].root.new_since_last_visit);
+    assert!(threads[0].replies[0].new_since_last_visit);
+}
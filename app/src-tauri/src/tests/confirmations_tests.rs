@@ -0,0 +1,78 @@
+// Category 49: Confirmation Token Tests (confirmations.rs)
+// Tests for single-use tokens guarding destructive commands
+
+use std::time::Duration;
+
+use crate::confirmations::{ConfirmationStore, DestructiveAction};
+use crate::error::AppError;
+use crate::i18n::{fill, translate, Locale, Msg};
+
+fn clear_review(pr_number: u64) -> DestructiveAction {
+    DestructiveAction::ClearLocalReview {
+        owner: "octo".to_string(),
+        repo: "docs".to_string(),
+        pr_number,
+    }
+}
+
+/// Test Case 49.1: Token Redeems Once for Its Action
+#[test]
+fn test_token_is_single_use() {
+    let store = ConfirmationStore::new(Duration::from_secs(60));
+    let issued = store.issue(clear_review(7), "clear".to_string()).unwrap();
+
+    assert_eq!(issued.token.len(), 32);
+    assert!(store.redeem(&issued.token, &clear_review(7)).is_ok());
+    assert!(matches!(
+        store.redeem(&issued.token, &clear_review(7)),
+        Err(AppError::ConfirmationRequired(_))
+    ));
+}
+
+/// Test Case 49.2: Token Issued for Another Action Is Rejected and Consumed
+#[test]
+fn test_token_bound_to_action() {
+    let store = ConfirmationStore::new(Duration::from_secs(60));
+    let issued = store.issue(clear_review(7), "clear".to_string()).unwrap();
+
+    let err = store.redeem(&issued.token, &clear_review(8)).unwrap_err();
+    assert!(err.to_string().contains("octo/docs#8"));
+    assert!(store.redeem(&issued.token, &clear_review(7)).is_err());
+    assert!(store.redeem("made-up", &clear_review(7)).is_err());
+}
+
+/// Test Case 49.3: Expired Tokens Are Rejected
+#[test]
+fn test_token_expires() {
+    let store = ConfirmationStore::new(Duration::ZERO);
+    let issued = store.issue(clear_review(7), "clear".to_string()).unwrap();
+    assert!(store.redeem(&issued.token, &clear_review(7)).is_err());
+}
+
+/// Test Case 49.4: Descriptions Name What Will Be Deleted
+#[test]
+fn test_describe_actions() {
+    assert_eq!(
+        clear_review(7).describe(Some(40)),
+        "Clear the local review of octo/docs#7 and its 40 draft comment(s)"
+    );
+    let delete = DestructiveAction::DeleteReview {
+        owner: "octo".to_string(),
+        repo: "docs".to_string(),
+        pr_number: 7,
+        review_id: 99,
+    };
+    assert!(delete.describe(None).contains("pending review 99"));
+
+    let parsed: DestructiveAction = serde_json::from_str(
+        r#"{"kind":"delete_github_comment","owner":"octo","repo":"docs","comment_id":5}"#,
+    )
+    .unwrap();
+    assert!(parsed.describe(None).contains("comment 5 in octo/docs"));
+
+    // Every locale names the same details
+    for locale in Locale::ALL {
+        let text = fill(translate(locale, Msg::ConfirmDeleteReview), &[&99, &"octo", &"docs", &7]);
+        assert!(text.contains("99") && text.contains("octo/docs#7"), "{text}");
+    }
+}
@@ -0,0 +1,122 @@
+// Category 69: Diagram Validation Tests (diagrams.rs)
+// Tests for catching broken mermaid and PlantUML fences in markdown
+
+use crate::diagrams::validate;
+
+fn lines_and_details(content: &str) -> Vec<(u64, String)> {
+    validate(content)
+        .into_iter()
+        .map(|issue| (issue.line, issue.detail))
+        .collect()
+}
+
+/// Test Case 69.1: Valid Diagrams Pass
+#[test]
+fn test_valid_diagrams() {
+    let content = "\
+# Flow
+
+```mermaid
+%% comment
+flowchart LR
+    A[Start] -->|go| B(Step)
+    B --> C{Done?}
+    C>Note] --- D
+    subgraph Group
+        D --> E
+    end
+```
+
+```mermaid
+sequenceDiagram
+    Alice->>+Bob: Hello
+    alt ok
+        Bob-->>-Alice: Hi
+    else failed
+        Bob-xAlice: Error
+    end
+```
+
+```plantuml
+@startuml
+Alice -> Bob: hello
+@enduml
+```
+
+```rust
+fn main() { (
+```
+";
+    assert!(validate(content).is_empty(), "{:?}", validate(content));
+}
+
+/// Test Case 69.2: Unknown Type and Direction
+#[test]
+fn test_mermaid_header() {
+    let content = "```mermaid\nflowchar LR\nA-->B\n```\n\n```mermaid\ngraph XY\nA-->B\n```\n";
+    let issues = lines_and_details(content);
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0], (2, "unknown diagram type `flowchar`".to_string()));
+    assert_eq!(issues[1].0, 7);
+    assert!(issues[1].1.contains("`XY`"));
+}
+
+/// Test Case 69.3: Flowchart Brackets, Dangling Arrows and Blocks
+#[test]
+fn test_flowchart_problems() {
+    let content = "```mermaid\ngraph TD\nA[Start --> B\nB -->\nsubgraph S\nC --> D\n```\n";
+    let issues = lines_and_details(content);
+    assert_eq!(
+        issues,
+        vec![
+            (3, "`[` is never closed".to_string()),
+            (4, "`-->` has no target node".to_string()),
+            (5, "`subgraph` is never closed with `end`".to_string()),
+        ]
+    );
+}
+
+/// Test Case 69.4: Sequence Messages and Blocks
+#[test]
+fn test_sequence_problems() {
+    let content = "```mermaid\nsequenceDiagram\nAlice->>Bob Hello\nAlice->>: Hi\nelse nope\nend\n```\n";
+    let issues = lines_and_details(content);
+    assert_eq!(
+        issues,
+        vec![
+            (3, "message needs `: text` after the participants".to_string()),
+            (4, "message arrow needs a sending and a receiving participant".to_string()),
+            (5, "`else` outside an `alt` block".to_string()),
+            (6, "`end` without an open block".to_string()),
+        ]
+    );
+}
+
+/// Test Case 69.5: PlantUML Start and End Markers
+#[test]
+fn test_plantuml_markers() {
+    let missing_end = lines_and_details("```puml\n@startuml\nA -> B\n```\n");
+    assert_eq!(missing_end, vec![(2, "`@startuml` is never closed with `@enduml`".to_string())]);
+
+    let mismatched = lines_and_details("```plantuml\n@startuml\n@endmindmap\n```\n");
+    assert_eq!(mismatched[0].0, 3);
+
+    let no_start = validate("```plantuml\nA -> B\n```\n");
+    assert_eq!(no_start[0].language, "plantuml");
+    assert_eq!(no_start[0].detail, "diagram must start with `@startuml`");
+
+    assert_eq!(lines_and_details("```mermaid\n\n```\n"), vec![(2, "diagram is empty".to_string())]);
+}
+
+/// Test Case 69.6: Class and State Bodies Span Several Lines
+#[test]
+fn test_multiline_bodies() {
+    let class = "```mermaid\nclassDiagram\nclass Animal {\n  +String name\n  +eat(food)\n}\n```\n";
+    assert!(validate(class).is_empty());
+
+    let state = "```mermaid\nstateDiagram-v2\nstate Moving {\n  Slow --> Fast\n}\n```\n";
+    assert!(validate(state).is_empty());
+
+    let er = "```mermaid\nerDiagram\nCUSTOMER {\n  string name\n```\n";
+    assert_eq!(lines_and_details(er), vec![(3, "`{` is never closed".to_string())]);
+}
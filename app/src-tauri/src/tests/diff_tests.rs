@@ -0,0 +1,108 @@
+// Category 32: Side-by-Side Diff Tests (diff.rs)
+// Tests for row alignment, line numbering, hunk context and intraline spans
+
+use crate::diff::{side_by_side, RowKind, Span};
+
+/// Test Case 32.1: Identical content produces only equal rows
+#[test]
+fn test_identical_content() {
+    let diff = side_by_side("a.md", "one\ntwo\n", "one\ntwo\n", None, false);
+
+    assert_eq!(diff.rows.len(), 2);
+    assert!(diff.rows.iter().all(|r| r.kind == RowKind::Equal));
+    assert_eq!(diff.additions, 0);
+    assert_eq!(diff.deletions, 0);
+}
+
+/// Test Case 32.2: Inserted lines align against an empty left side
+#[test]
+fn test_added_lines() {
+    let diff = side_by_side("a.md", "one\nthree\n", "one\ntwo\nthree\n", None, false);
+
+    let kinds: Vec<RowKind> = diff.rows.iter().map(|r| r.kind).collect();
+    assert_eq!(kinds, vec![RowKind::Equal, RowKind::Added, RowKind::Equal]);
+    let added = &diff.rows[1];
+    assert!(added.left.is_none());
+    assert_eq!(added.right.as_ref().unwrap().line_number, 2);
+    assert_eq!(diff.rows[2].left.as_ref().unwrap().line_number, 2);
+    assert_eq!(diff.rows[2].right.as_ref().unwrap().line_number, 3);
+    assert_eq!(diff.additions, 1);
+}
+
+/// Test Case 32.3: Replaced lines pair up as modified rows with intraline spans
+#[test]
+fn test_modified_line_spans() {
+    let diff = side_by_side("a.md", "the quick fox\n", "the slow fox\n", None, false);
+
+    assert_eq!(diff.rows.len(), 1);
+    let row = &diff.rows[0];
+    assert_eq!(row.kind, RowKind::Modified);
+    let left = row.left.as_ref().unwrap();
+    let right = row.right.as_ref().unwrap();
+    assert!(!left.spans.is_empty());
+    assert!(!right.spans.is_empty());
+    // Unchanged prefix and suffix are never highlighted
+    assert!(left.spans.iter().all(|s| s.start >= 4 && s.end <= 9));
+    assert!(right.spans.iter().all(|s| s.start >= 4 && s.end <= 8));
+}
+
+/// Test Case 32.4: Uneven replacements fill the shorter side with one-sided rows
+#[test]
+fn test_uneven_replace() {
+    let diff = side_by_side("a.md", "a\nold1\nz\n", "a\nnew1\nnew2\nz\n", None, false);
+
+    let kinds: Vec<RowKind> = diff.rows.iter().map(|r| r.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![RowKind::Equal, RowKind::Modified, RowKind::Added, RowKind::Equal]
+    );
+    assert_eq!(diff.additions, 2);
+    assert_eq!(diff.deletions, 1);
+}
+
+/// Test Case 32.5: Context limits output to hunks and reports skipped lines
+#[test]
+fn test_context_skip_rows() {
+    let base: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+    let head = base.replace("line 10\n", "line ten\n");
+    let diff = side_by_side("a.md", &base, &head, Some(2), false);
+
+    assert_eq!(diff.rows.first().unwrap().kind, RowKind::Skip);
+    assert_eq!(diff.rows.first().unwrap().skipped, Some(7));
+    assert_eq!(diff.rows.last().unwrap().kind, RowKind::Skip);
+    assert_eq!(diff.rows.last().unwrap().skipped, Some(8));
+    // 2 context + 1 modified + 2 context, plus the two skip rows
+    assert_eq!(diff.rows.len(), 7);
+    let modified = diff.rows.iter().find(|r| r.kind == RowKind::Modified).unwrap();
+    assert_eq!(modified.right.as_ref().unwrap().line_number, 10);
+}
+
+/// Test Case 32.6: Whitespace-only changes can be ignored
+#[test]
+fn test_ignore_whitespace() {
+    let diff = side_by_side("a.py", "if x:\n  y = 1\n", "if x:\n    y  =  1\n", None, true);
+
+    assert!(diff.rows.iter().all(|r| r.kind == RowKind::Equal));
+    // The displayed text is still the original line
+    assert_eq!(diff.rows[1].right.as_ref().unwrap().text, "    y  =  1");
+}
+
+/// Test Case 32.7: Intraline spans are measured in UTF-16 code units
+#[test]
+fn test_spans_utf16() {
+    let diff = side_by_side("a.md", "😀 a\n", "😀 b\n", None, false);
+
+    let row = &diff.rows[0];
+    assert_eq!(row.left.as_ref().unwrap().spans, vec![Span { start: 3, end: 4 }]);
+    assert_eq!(row.right.as_ref().unwrap().spans, vec![Span { start: 3, end: 4 }]);
+}
+
+/// Test Case 32.8: Added and removed files diff against empty content
+#[test]
+fn test_new_file() {
+    let diff = side_by_side("new.md", "", "a\nb\n", Some(3), false);
+
+    assert_eq!(diff.rows.len(), 2);
+    assert!(diff.rows.iter().all(|r| r.kind == RowKind::Added));
+    assert_eq!(diff.additions, 2);
+}
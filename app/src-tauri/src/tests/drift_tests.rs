@@ -0,0 +1,52 @@
+// Category 52: Base Drift Tests (drift.rs)
+// Tests for files changed both in a PR and on its base branch
+
+use crate::drift::{build_drift, overlapping};
+
+fn paths(list: &[&str]) -> Vec<String> {
+    list.iter().map(|p| p.to_string()).collect()
+}
+
+/// Test Case 52.1: Overlapping Files and Doc Warnings
+#[test]
+fn test_overlapping_files() {
+    let drift = build_drift(
+        "main",
+        "abc123",
+        4,
+        2,
+        &paths(&["docs/setup.md", "src/lib.rs", "README.md", "docs/other.md"]),
+        &paths(&["src/lib.rs", "docs/setup.md", "docs/new.md", "docs/setup.md"]),
+    );
+
+    assert_eq!(drift.behind_by, 4);
+    assert_eq!(drift.ahead_by, 2);
+    assert_eq!(drift.base_changed_files, 4);
+    assert_eq!(drift.overlapping_files, paths(&["docs/setup.md", "src/lib.rs"]));
+    assert_eq!(drift.warnings.len(), 1);
+    assert!(drift.warnings[0].contains("docs/setup.md"));
+    assert!(drift.warnings[0].contains("main"));
+}
+
+/// Test Case 52.2: Up-to-Date Branch Has No Overlap
+#[test]
+fn test_up_to_date_branch() {
+    let drift = build_drift("main", "abc123", 0, 3, &[], &paths(&["docs/setup.md"]));
+
+    assert_eq!(drift.behind_by, 0);
+    assert!(drift.overlapping_files.is_empty());
+    assert!(drift.warnings.is_empty());
+}
+
+/// Test Case 52.3: Conflict Candidates Include Renamed Paths
+#[test]
+fn test_overlapping_renamed_paths() {
+    // A PR renaming old.rs -> new.rs conflicts with base edits to old.rs
+    let candidates = overlapping(
+        &paths(&["src/old.rs", "Cargo.toml"]),
+        &paths(&["src/new.rs", "src/old.rs", "README.md"]),
+    );
+
+    assert_eq!(candidates, paths(&["src/old.rs"]));
+    assert!(overlapping(&[], &paths(&["src/new.rs"])).is_empty());
+}
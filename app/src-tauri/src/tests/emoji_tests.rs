@@ -0,0 +1,58 @@
+// Category 35: Emoji Tests (emoji.rs)
+// Tests for shortcode expansion and autocomplete over GitHub's emoji list
+
+use std::collections::HashMap;
+
+use crate::emoji::EmojiSet;
+
+fn sample_set() -> EmojiSet {
+    let map: HashMap<String, String> = [
+        ("+1", "https://github.githubassets.com/images/icons/emoji/unicode/1f44d.png?v8"),
+        ("tada", "https://github.githubassets.com/images/icons/emoji/unicode/1f389.png?v8"),
+        ("us", "https://github.githubassets.com/images/icons/emoji/unicode/1f1fa-1f1f8.png?v8"),
+        ("shipit", "https://github.githubassets.com/images/icons/emoji/shipit.png?v8"),
+        ("ship", "https://github.githubassets.com/images/icons/emoji/unicode/1f6a2.png?v8"),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+    EmojiSet::from_api(map)
+}
+
+/// Test Case 35.1: Unicode emoji are decoded from their image URLs
+#[test]
+fn test_unicode_from_api() {
+    let set = sample_set();
+    assert_eq!(set.get("+1").unwrap().unicode.as_deref(), Some("👍"));
+    assert_eq!(set.get("us").unwrap().unicode.as_deref(), Some("🇺🇸"));
+    assert!(set.get("shipit").unwrap().unicode.is_none());
+}
+
+/// Test Case 35.2: Expansion replaces known shortcodes and keeps unknown ones
+#[test]
+fn test_expand() {
+    let set = sample_set();
+    assert_eq!(set.expand(":tada: done :nope:"), "🎉 done :nope:");
+    let custom = set.expand(":shipit:");
+    assert!(custom.starts_with("<img class=\"emoji\""));
+    assert!(custom.contains("shipit.png"));
+}
+
+/// Test Case 35.3: Code spans and fenced blocks are not expanded
+#[test]
+fn test_expand_skips_code() {
+    let set = sample_set();
+    let text = "use `:tada:` for :tada:\n```\n:+1:\n```\n:+1:";
+    assert_eq!(set.expand(text), "use `:tada:` for 🎉\n```\n:+1:\n```\n👍");
+}
+
+/// Test Case 35.4: Search ranks prefix matches before substring matches
+#[test]
+fn test_search() {
+    let set = sample_set();
+    let names: Vec<String> = set.search(":ship", 10).into_iter().map(|e| e.shortcode).collect();
+    assert_eq!(names, vec!["ship", "shipit"]);
+    let names: Vec<String> = set.search("a", 10).into_iter().map(|e| e.shortcode).collect();
+    assert_eq!(names, vec!["tada"]);
+    assert_eq!(set.search("", 2).len(), 2);
+}
@@ -165,3 +165,12 @@ fn test_app_result_type_alias() {
     assert_eq!(returns_ok().unwrap(), 42);
     assert!(returns_err().is_err());
 }
+
+/// Test Case 1.14: AppError Display - InvalidInput
+#[test]
+fn test_invalid_input_display() {
+    let error = AppError::InvalidInput("theme must be one of light, dark".to_string());
+    let display = format!("{}", error);
+    assert!(display.contains("invalid input"));
+    assert!(display.contains("theme must be one of"));
+}
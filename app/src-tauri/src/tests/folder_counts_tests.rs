@@ -0,0 +1,44 @@
+// Category 77: Local Folder Count Tests (folder_counts.rs)
+// Tests for counting markdown files in local review folders and invalidating the cache
+
+use std::fs;
+
+use crate::folder_counts::markdown_file_count;
+use tempfile::TempDir;
+
+/// Test Case 77.1: Markdown Files Are Counted Recursively
+#[test]
+fn test_counts_markdown_recursively() {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir_all(temp.path().join("guide/advanced")).unwrap();
+    fs::write(temp.path().join("README.md"), "# Readme").unwrap();
+    fs::write(temp.path().join("notes.txt"), "not docs").unwrap();
+    fs::write(temp.path().join("guide/intro.markdown"), "# Intro").unwrap();
+    fs::write(temp.path().join("guide/advanced/page.MDX"), "# Page").unwrap();
+
+    assert_eq!(markdown_file_count(temp.path()).unwrap(), 3);
+}
+
+/// Test Case 77.2: Cached Count Follows Added and Removed Nested Files
+#[test]
+fn test_cached_count_is_invalidated() {
+    let temp = TempDir::new().unwrap();
+    let nested = temp.path().join("a/b");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(nested.join("one.md"), "").unwrap();
+    assert_eq!(markdown_file_count(temp.path()).unwrap(), 1);
+    assert_eq!(markdown_file_count(temp.path()).unwrap(), 1);
+
+    fs::write(nested.join("two.md"), "").unwrap();
+    assert_eq!(markdown_file_count(temp.path()).unwrap(), 2);
+
+    fs::remove_dir_all(temp.path().join("a")).unwrap();
+    assert_eq!(markdown_file_count(temp.path()).unwrap(), 0);
+}
+
+/// Test Case 77.3: Missing Folder Is an Error
+#[test]
+fn test_missing_folder() {
+    let temp = TempDir::new().unwrap();
+    assert!(markdown_file_count(&temp.path().join("gone")).is_err());
+}
@@ -0,0 +1,88 @@
+// Category 72: Front Matter Freshness Tests (front_matter.rs)
+// Tests for reading front matter and flagging date fields a change left untouched
+
+use crate::front_matter::{self, FrontMatterField};
+use chrono::NaiveDate;
+
+const ARTICLE: &str = "---\ntitle: Install the CLI\nms.date: 01/15/2024\nlast_reviewed: \"2024-01-15\"\nms.topic: how-to\n---\n\n# Install\n\nRun the installer.\n";
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+}
+
+fn date_fields() -> Vec<String> {
+    front_matter::DEFAULT_DATE_FIELDS.iter().map(|f| f.to_string()).collect()
+}
+
+/// Test Case 72.1: Front Matter Fields Parsed
+#[test]
+fn test_fields() {
+    let fields = front_matter::fields(ARTICLE);
+    assert_eq!(fields.len(), 4);
+    assert_eq!(
+        fields[2],
+        FrontMatterField {
+            key: "last_reviewed".to_string(),
+            value: "2024-01-15".to_string(),
+            line: 4,
+        }
+    );
+
+    // No opening or closing delimiter means no front matter
+    assert!(front_matter::fields("# Title\nms.date: 01/15/2024\n").is_empty());
+    assert!(front_matter::fields("---\nms.date: 01/15/2024\n# Title\n").is_empty());
+}
+
+/// Test Case 72.2: Untouched Date Fields Flagged With Suggestions
+#[test]
+fn test_stale_dates_flagged() {
+    let patch = "@@ -10,1 +10,1 @@\n-Run the setup.\n+Run the installer.";
+    let findings =
+        front_matter::check_freshness("docs/install.md", ARTICLE, patch, &date_fields(), today());
+
+    assert_eq!(findings.len(), 2);
+    assert_eq!(findings[0].field, "ms.date");
+    assert_eq!(findings[0].line, 3);
+    assert_eq!(findings[0].suggested_value.as_deref(), Some("10/16/2026"));
+    assert!(findings[0]
+        .suggested_comment
+        .ends_with("```suggestion\nms.date: 10/16/2026\n```"));
+    // The quoting of the original line is kept
+    assert!(findings[1]
+        .suggested_comment
+        .ends_with("```suggestion\nlast_reviewed: \"2026-10-16\"\n```"));
+}
+
+/// Test Case 72.3: Updated or Current Dates Not Flagged
+#[test]
+fn test_updated_dates_not_flagged() {
+    let patch = "@@ -3,1 +3,1 @@\n-ms.date: 01/15/2023\n+ms.date: 01/15/2024\n@@ -10,1 +10,1 @@\n-Run the setup.\n+Run the installer.";
+    let findings =
+        front_matter::check_freshness("docs/install.md", ARTICLE, patch, &date_fields(), today());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].field, "last_reviewed");
+
+    let current = ARTICLE.replace("2024-01-15", "2026-10-16");
+    let findings =
+        front_matter::check_freshness("docs/install.md", &current, patch, &date_fields(), today());
+    assert!(findings.is_empty());
+}
+
+/// Test Case 72.4: Only Configured Fields in Markdown Files Checked
+#[test]
+fn test_scope() {
+    let patch = "@@ -10,1 +10,1 @@\n-Run the setup.\n+Run the installer.";
+    let fields = date_fields();
+    assert!(front_matter::check_freshness("install.yml", ARTICLE, patch, &fields, today()).is_empty());
+    assert!(front_matter::check_freshness("install.md", ARTICLE, "", &fields, today()).is_empty());
+
+    let custom = vec!["MS.DATE".to_string()];
+    let findings = front_matter::check_freshness("install.md", ARTICLE, patch, &custom, today());
+    assert_eq!(findings.len(), 1);
+
+    // Unrecognized formats are still flagged, without a suggestion
+    let odd = ARTICLE.replace("01/15/2024", "last spring");
+    let findings = front_matter::check_freshness("install.md", &odd, patch, &custom, today());
+    assert_eq!(findings[0].suggested_value, None);
+    assert!(!findings[0].suggested_comment.contains("```suggestion"));
+}
@@ -181,3 +181,220 @@ fn test_api_constants() {
     assert!(user_agent.contains("github-review"));
     assert!(api_version.contains("-"));
 }
+
+fn suggestion(replacement: &str) -> crate::models::Suggestion {
+    crate::models::Suggestion {
+        replacement: replacement.to_string(),
+        start_line: 3,
+        end_line: 4,
+    }
+}
+
+/// Test Case 3.15: Suggestion Block Follows the Comment
+#[test]
+fn test_suggestion_body() {
+    let body = crate::github::suggestion_body("Reword this:\n", &suggestion("New text.\n"));
+    assert_eq!(body, "Reword this:\n\n```suggestion\nNew text.\n```");
+
+    let body = crate::github::suggestion_body("", &suggestion("New text."));
+    assert_eq!(body, "```suggestion\nNew text.\n```");
+}
+
+/// Test Case 3.16: Suggestions Containing Code Fences Use a Longer Fence
+#[test]
+fn test_suggestion_body_nested_fence() {
+    let body = crate::github::suggestion_body("", &suggestion("```sh\nls\n```"));
+    assert_eq!(body, "````suggestion\n```sh\nls\n```\n````");
+}
+
+/// Test Case 3.17: Empty and Blank Replacements
+#[test]
+fn test_suggestion_body_deletion() {
+    assert_eq!(crate::github::suggestion_body("", &suggestion("")), "```suggestion\n```");
+    assert_eq!(crate::github::suggestion_body("", &suggestion("\n")), "```suggestion\n\n```");
+}
+
+/// Test Case 3.18: One Query Looks Up Every PR Under Review
+#[test]
+fn test_review_states_query() {
+    let query = crate::github::review_states_query(2);
+    assert!(query.starts_with(
+        "query($o0: String!, $r0: String!, $n0: Int!, $o1: String!, $r1: String!, $n1: Int!)"
+    ));
+    assert!(query.contains("pr0: repository(owner: $o0, name: $r0)"));
+    assert!(query.contains("pr1: repository(owner: $o1, name: $r1)"));
+    assert!(query.contains("pullRequest(number: $n1)"));
+    assert_eq!(query.matches("reviews(states: PENDING)").count(), 2);
+}
+
+/// Test Case 3.19: Batched States Map to REST Terms, With Gaps for Missing PRs
+#[test]
+fn test_parse_review_states() {
+    let data = serde_json::json!({
+        "pr0": { "pullRequest": {
+            "headRefOid": "abc", "state": "OPEN", "locked": false,
+            "reviews": { "totalCount": 1 }
+        }},
+        "pr1": null,
+        "pr2": { "pullRequest": {
+            "headRefOid": "def", "state": "MERGED", "locked": true,
+            "reviews": { "totalCount": 0 }
+        }}
+    });
+    let states = crate::github::parse_review_states(data, 3);
+    assert_eq!(states.len(), 3);
+
+    let open = states[0].as_ref().unwrap();
+    assert_eq!((open.head_sha.as_str(), open.state.as_str()), ("abc", "open"));
+    assert!(open.has_pending_review);
+    assert!(!open.merged);
+
+    assert!(states[1].is_none());
+
+    let merged = states[2].as_ref().unwrap();
+    assert_eq!(merged.state, "closed");
+    assert!(merged.merged && merged.locked);
+    assert!(!merged.has_pending_review);
+}
+
+/// Test Case 3.20: Only Markdown Files Can Be Fetched as GitHub's HTML
+#[tokio::test]
+async fn test_rendered_contents_need_markdown() {
+    let result = crate::github::get_file_contents(
+        "token",
+        "owner",
+        "repo",
+        "src/main.rs",
+        "base",
+        "head",
+        "modified",
+        None,
+        None,
+        crate::github::ContentFormat::Html,
+    )
+    .await;
+    assert!(matches!(result, Err(crate::error::AppError::InvalidInput(_))));
+}
+
+/// Test Case 3.21: Fork Detection Ignores Case of Owner and Repository
+#[test]
+fn test_is_fork() {
+    use crate::github::is_fork;
+
+    assert!(!is_fork("Owner", "Docs", "owner", "docs"));
+    assert!(is_fork("owner", "docs", "contributor", "docs"));
+    assert!(is_fork("owner", "docs", "owner", "docs-fork"));
+}
+
+/// Test Case 3.22: One Query Reads the Timelines of a Batch of PRs
+#[test]
+fn test_attention_query() {
+    let query = crate::github::attention_query(2);
+    assert!(query.contains("pr1: repository(owner: $o1, name: $r1)"));
+    assert_eq!(query.matches("timelineItems(last: 100").count(), 2);
+    assert_eq!(query.matches("reviewThreads(last: 50)").count(), 2);
+}
+
+/// Test Case 3.23: Batched Timelines Decide Whose Turn It Is
+#[test]
+fn test_parse_attention() {
+    let data = serde_json::json!({
+        "pr0": { "pullRequest": {
+            "state": "OPEN", "author": { "login": "author" },
+            "timelineItems": { "nodes": [
+                { "__typename": "PullRequestReview", "author": { "login": "me" },
+                  "submittedAt": "2024-03-01T10:00:00Z" },
+                { "__typename": "PullRequestCommit",
+                  "commit": { "committedDate": "2024-03-02T10:00:00Z" } }
+            ]},
+            "reviewThreads": { "nodes": [] }
+        }},
+        "pr1": { "pullRequest": {
+            "state": "OPEN", "author": { "login": "author" },
+            "timelineItems": { "nodes": [
+                { "__typename": "ReviewRequestedEvent", "createdAt": "2024-03-01T10:00:00Z",
+                  "requestedReviewer": {} }
+            ]},
+            "reviewThreads": { "nodes": [
+                { "comments": { "nodes": [
+                    { "author": { "login": "me" }, "createdAt": "2024-03-01T11:00:00Z" },
+                    { "author": { "login": "author" }, "createdAt": "2024-03-01T12:00:00Z" }
+                ]}}
+            ]}
+        }},
+        "pr2": { "pullRequest": {
+            "state": "MERGED", "author": { "login": "author" },
+            "timelineItems": { "nodes": [
+                { "__typename": "ReviewRequestedEvent", "createdAt": "2024-03-01T10:00:00Z",
+                  "requestedReviewer": { "login": "me" } }
+            ]},
+            "reviewThreads": { "nodes": [] }
+        }},
+        "pr3": null
+    });
+
+    let flags = crate::github::parse_attention(data, 4, "me");
+    // Pushed after my review; the author replied in a thread; merged; not found
+    assert_eq!(flags, vec![Some(true), Some(true), Some(false), None]);
+}
+
+/// Test Case 3.24: One Query Reads the Activity of a Batch of PRs
+#[test]
+fn test_activity_query() {
+    let query = crate::github::activity_query(2);
+    assert!(query.contains("pr1: repository(owner: $o1, name: $r1)"));
+    assert_eq!(query.matches("reviews(first: 100)").count(), 2);
+    assert_eq!(query.matches("comments(last: 100)").count(), 2);
+}
+
+/// Test Case 3.25: Batched Activity Keeps My Recent Reviews and Comments
+#[test]
+fn test_parse_review_activity() {
+    use crate::review_activity::ActivityKind;
+
+    let data = serde_json::json!({
+        "pr0": { "pullRequest": {
+            "number": 7, "title": "Add login",
+            "repository": { "name": "app", "owner": { "login": "octo" } },
+            "reviews": { "nodes": [
+                { "author": { "login": "Me" }, "state": "APPROVED", "url": "r1",
+                  "submittedAt": "2024-05-02T00:00:00Z", "body": "LGTM",
+                  "comments": { "nodes": [
+                      { "path": "docs/a.md", "line": null, "originalLine": 4, "url": "c1",
+                        "createdAt": "2024-05-02T00:00:00Z", "body": "nit" },
+                      { "path": "docs/a.md", "line": 9, "originalLine": 9, "url": "c0",
+                        "createdAt": "2024-04-01T00:00:00Z", "body": "old" }
+                  ]}},
+                { "author": { "login": "me" }, "state": "PENDING", "url": "r2",
+                  "submittedAt": null, "body": "",
+                  "comments": { "nodes": [
+                      { "path": "docs/a.md", "line": 1, "originalLine": 1, "url": "c2",
+                        "createdAt": "2024-05-03T00:00:00Z", "body": "draft" }
+                  ]}},
+                { "author": { "login": "someone" }, "state": "COMMENTED", "url": "r3",
+                  "submittedAt": "2024-05-02T00:00:00Z", "body": "theirs",
+                  "comments": { "nodes": [] }}
+            ]},
+            "comments": { "nodes": [
+                { "author": { "login": "me" }, "url": "i1",
+                  "createdAt": "2024-05-04T00:00:00Z", "body": "thanks" },
+                { "author": null, "url": "i2", "createdAt": "2024-05-04T00:00:00Z", "body": "ghost" }
+            ]}
+        }},
+        "pr1": null
+    });
+
+    let activity = crate::github::parse_review_activity(data, 2, "me", "2024-05-01T00:00:00Z");
+    assert_eq!(activity.len(), 2);
+    assert!(activity[1].is_none());
+
+    let records = activity[0].as_ref().unwrap();
+    let bodies: Vec<&str> = records.iter().map(|r| r.body.as_str()).collect();
+    // Old comments, pending reviews and other people's activity are left out
+    assert_eq!(bodies, ["LGTM", "nit", "thanks"]);
+    assert_eq!(records[0].kind, ActivityKind::Review);
+    assert_eq!(records[0].state.as_deref(), Some("APPROVED"));
+    assert_eq!((records[1].path.as_deref(), records[1].line), (Some("docs/a.md"), Some(4)));
+    assert_eq!((records[2].kind, records[2].path.as_deref()), (ActivityKind::Comment, None));
+    assert_eq!((records[2].owner.as_str(), records[2].pr_number), ("octo", 7));
+}
@@ -0,0 +1,87 @@
+// Category 36: Review Handoff Tests (handoff.rs)
+// Tests for exporting a local review with anchors and importing it elsewhere
+
+use std::collections::HashMap;
+
+use crate::handoff::{
+    anchor_for, build_handoff, import_handoff, parse_handoff, relocate, HANDOFF_FORMAT,
+};
+use super::create_test_storage;
+
+const OLD: &str = "# Title\nintro\n\n## Install\nrun make\nthen test\n";
+const NEW: &str = "# Title\nnew paragraph\nintro\n\n## Install\nrun make\nthen test\n";
+
+/// Test Case 36.1: Anchors capture the line and its neighbours
+#[test]
+fn test_anchor_for() {
+    let anchor = anchor_for(OLD, 5).unwrap();
+    assert_eq!(anchor.line_text, "run make");
+    assert_eq!(anchor.before, vec!["", "## Install"]);
+    assert_eq!(anchor.after, vec!["then test"]);
+    assert!(anchor_for(OLD, 0).is_none());
+    assert!(anchor_for(OLD, 99).is_none());
+}
+
+/// Test Case 36.2: Relocation follows a line that moved
+#[test]
+fn test_relocate_moved_line() {
+    let anchor = anchor_for(OLD, 5).unwrap();
+    assert_eq!(relocate(&anchor, NEW, 5), Some(6));
+    assert_eq!(relocate(&anchor, "nothing here\n", 5), None);
+}
+
+/// Test Case 36.3: Duplicate lines are told apart by their context
+#[test]
+fn test_relocate_duplicates() {
+    let old = "a\nTODO\nb\nc\nTODO\nd\n";
+    let anchor = anchor_for(old, 5).unwrap();
+    let new = "x\na\nTODO\nb\nc\nTODO\nd\n";
+    assert_eq!(relocate(&anchor, new, 5), Some(6));
+}
+
+/// Test Case 36.4: Round trip through export and import preserves reply threads
+#[tokio::test]
+async fn test_handoff_round_trip() {
+    let (source, _source_dir) = create_test_storage();
+    let metadata = source.start_review("o", "r", 3, "old", Some("Summary"), None).unwrap();
+    let parent = source
+        .add_comment("o", "r", 3, "docs/a.md", 5, "RIGHT", "Use cmake?", "old", None)
+        .await
+        .unwrap();
+    source
+        .add_comment("o", "r", 3, "docs/a.md", 5, "RIGHT", "Or ninja", "old", Some(parent.id))
+        .await
+        .unwrap();
+    let comments = source.get_comments("o", "r", 3).unwrap();
+
+    let contents = HashMap::from([(("docs/a.md".to_string(), "old".to_string()), OLD.to_string())]);
+    let document = build_handoff(&metadata, &comments, &contents, Some("alice".into()));
+    let json = serde_json::to_string(&document).unwrap();
+    let parsed = parse_handoff(&json).unwrap();
+    assert_eq!(parsed.format, HANDOFF_FORMAT);
+    assert!(parsed.comments.iter().all(|c| c.anchor.is_some()));
+
+    let (target, _target_dir) = create_test_storage();
+    let head_contents = HashMap::from([("docs/a.md".to_string(), NEW.to_string())]);
+    let result = import_handoff(&target, &parsed, Some("new"), &head_contents)
+        .await
+        .unwrap();
+    assert_eq!(result.imported, 2);
+    assert!(result.unanchored.is_empty());
+    assert_eq!(result.review.commit_id, "new");
+
+    let imported = target.get_comments("o", "r", 3).unwrap();
+    assert!(imported.iter().all(|c| c.line_number == 6 && c.commit_id == "new"));
+    let reply = imported.iter().find(|c| c.body == "Or ninja").unwrap();
+    let parent = imported.iter().find(|c| c.body == "Use cmake?").unwrap();
+    assert_eq!(reply.in_reply_to_id, Some(parent.id));
+}
+
+/// Test Case 36.5: Files that are not handoffs are rejected
+#[test]
+fn test_parse_rejects_other_json() {
+    let json = r#"{"format":"other","version":1,"exported_at":"","exported_by":null,
+        "owner":"o","repo":"r","pr_number":1,"commit_id":"c","body":null,"comments":[]}"#;
+    assert!(parse_handoff(json).is_err());
+    assert!(parse_handoff("not json").is_err());
+}
@@ -0,0 +1,95 @@
+// Category 73: Heading Style Tests (heading_style.rs)
+// Tests for heading case rewriting, depth and level rules, and persisted configuration
+
+use crate::heading_style::{self, HeadingCase, HeadingIssueKind, HeadingStyleConfig};
+use super::create_test_storage;
+
+/// Test Case 73.1: Sentence Case Rewrite
+#[test]
+fn test_sentence_case() {
+    let nouns = vec!["Azure".to_string()];
+    let recase = |title| heading_style::recase(title, HeadingCase::Sentence, &nouns);
+
+    assert_eq!(recase("Install The Command-Line Tool"), "Install the command-line tool");
+    // Acronyms, inner capitals, versions, and configured names keep their spelling
+    assert_eq!(recase("Use The GitHub API On azure"), "Use the GitHub API on Azure");
+    assert_eq!(recase("Upgrade To Python3"), "Upgrade to Python3");
+    // Inline code and link targets are untouched; the first word is capitalized
+    assert_eq!(
+        recase("run `Get-Item` With [Options](Setup.md)"),
+        "Run `Get-Item` with [options](Setup.md)"
+    );
+    assert_eq!(recase("Step 1: Sign In"), "Step 1: Sign in");
+}
+
+/// Test Case 73.2: Title Case Rewrite
+#[test]
+fn test_title_case() {
+    let recase = |title| heading_style::recase(title, HeadingCase::Title, &[]);
+
+    assert_eq!(recase("install the command-line tool"), "Install the Command-Line Tool");
+    assert_eq!(recase("the end of the road"), "The End of the Road");
+    assert_eq!(recase("what to sign in with"), "What to Sign in With");
+    assert_eq!(recase("Configure `npm` for CI"), "Configure `npm` for CI");
+}
+
+/// Test Case 73.3: Depth and Skipped Levels Flagged
+#[test]
+fn test_depth_and_levels() {
+    let config = HeadingStyleConfig {
+        case: HeadingCase::Any,
+        max_depth: 3,
+        ..HeadingStyleConfig::default()
+    };
+    let content = "# Guide\n\n### Details\n\n```sh\n# not a heading\n```\n\n#### Deep dive\n";
+    let findings = heading_style::check(&config, content, None);
+
+    let found: Vec<(u64, HeadingIssueKind)> = findings.iter().map(|f| (f.line, f.kind)).collect();
+    assert_eq!(
+        found,
+        vec![(3, HeadingIssueKind::SkippedLevel), (9, HeadingIssueKind::TooDeep)]
+    );
+
+    let lenient = HeadingStyleConfig {
+        allow_skipped_levels: true,
+        max_depth: 6,
+        ..config
+    };
+    assert!(heading_style::check(&lenient, content, None).is_empty());
+}
+
+/// Test Case 73.4: Only Changed Headings Reported
+#[test]
+fn test_patch_limits_findings() {
+    let content = "---\n# yaml comment\n---\n# Old Title Case\n\n## New Title Case\n";
+    let findings = heading_style::check(&HeadingStyleConfig::default(), content, None);
+    assert_eq!(findings.len(), 2);
+
+    let patch = "@@ -5,0 +6,1 @@\n+## New Title Case";
+    let findings = heading_style::check(&HeadingStyleConfig::default(), content, Some(patch));
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].line, 6);
+    assert_eq!(findings[0].kind, HeadingIssueKind::Case);
+    assert_eq!(findings[0].suggested.as_deref(), Some("New title case"));
+}
+
+/// Test Case 73.5: Config Validated and Persisted
+#[test]
+fn test_config_persistence() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(heading_style::load_config(&storage).unwrap(), HeadingStyleConfig::default());
+
+    let invalid = HeadingStyleConfig {
+        max_depth: 0,
+        ..HeadingStyleConfig::default()
+    };
+    assert!(heading_style::save_config(&storage, &invalid).is_err());
+
+    let config = HeadingStyleConfig {
+        case: HeadingCase::Title,
+        proper_nouns: vec!["Kubernetes".to_string()],
+        ..HeadingStyleConfig::default()
+    };
+    heading_style::save_config(&storage, &config).unwrap();
+    assert_eq!(heading_style::load_config(&storage).unwrap(), config);
+}
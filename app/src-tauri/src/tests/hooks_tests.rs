@@ -0,0 +1,128 @@
+// Category 25: Automation Hooks Tests (hooks.rs)
+// Tests for hook templating, the allow-list, and persisted hook configuration
+
+use crate::allow_list::{self, AllowList, HOOKS_ALLOW_LIST_KEY};
+use crate::hooks::{self, Hook, HookAction, HookContext, HookEvent, HooksConfig};
+use std::collections::BTreeMap;
+use super::create_test_storage;
+
+fn http_hook(url: &str) -> Hook {
+    Hook {
+        name: "dashboard".to_string(),
+        event: HookEvent::ReviewSubmitted,
+        enabled: true,
+        action: HookAction::Http {
+            url: url.to_string(),
+            method: "POST".to_string(),
+            headers: BTreeMap::new(),
+            body: None,
+        },
+    }
+}
+
+/// Test Case 25.1: Template Placeholders Rendered
+#[test]
+fn test_render_placeholders() {
+    let context = HookContext::new(HookEvent::CommentAdded, "octocat", "docs", 42)
+        .with("comment_body", "Looks {{owner}} good");
+
+    assert_eq!(
+        context.render("{{event}} on {{owner}}/{{repo}}#{{pr_number}}"),
+        "comment_added on octocat/docs#42"
+    );
+    // Values are not re-expanded and unknown placeholders are kept
+    assert_eq!(context.render("{{comment_body}}"), "Looks {{owner}} good");
+    assert_eq!(context.render("{{missing}} {{"), "{{missing}} {{");
+
+    let payload: serde_json::Value = serde_json::from_str(&context.render("{{payload}}")).unwrap();
+    assert_eq!(payload["pr_number"], "42");
+}
+
+/// Test Case 25.2: Allow-List Enforced
+#[test]
+fn test_allow_list_enforced() {
+    let mut config = HooksConfig {
+        hooks: vec![http_hook("https://docs.example.com/update")],
+        ..HooksConfig::default()
+    };
+    let mut allowed = AllowList::default();
+    assert!(config.validate(&allowed).is_err());
+
+    allowed.hosts.push("docs.example.com".to_string());
+    assert!(config.validate(&allowed).is_ok());
+
+    // Plain http is only allowed for loopback hosts
+    config.hooks = vec![http_hook("http://docs.example.com/update")];
+    assert!(config.validate(&allowed).is_err());
+
+    let shell = HookAction::Shell {
+        program: "/bin/rm".to_string(),
+        args: vec!["-rf".to_string()],
+    };
+    assert!(hooks::check_allowed(&allowed, &shell).is_err());
+
+    // Bare names resolve through PATH, so even a listed one is refused
+    let bare = HookAction::Shell {
+        program: "rm".to_string(),
+        args: Vec::new(),
+    };
+    allowed.programs.push("rm".to_string());
+    assert!(hooks::check_allowed(&allowed, &bare).is_err());
+}
+
+/// Test Case 25.3: Hooks Config Round-Trip Against the Stored Allow-List
+#[test]
+fn test_hooks_config_round_trip() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(hooks::load_config(&storage).unwrap(), HooksConfig::default());
+
+    let config = HooksConfig {
+        hooks: vec![http_hook("https://docs.example.com/update")],
+        ..HooksConfig::default()
+    };
+    assert_eq!(config.targets(), (Vec::new(), vec!["docs.example.com".to_string()]));
+    assert!(hooks::save_config(&storage, &config).is_err());
+
+    let allowed = AllowList {
+        hosts: vec!["docs.example.com".to_string()],
+        ..AllowList::default()
+    };
+    allow_list::save(&storage, HOOKS_ALLOW_LIST_KEY, &allowed).unwrap();
+    hooks::save_config(&storage, &config).unwrap();
+    assert_eq!(hooks::load_config(&storage).unwrap(), config);
+    assert_eq!(hooks::load_allow_list(&storage).unwrap(), allowed);
+
+    // An allow-list can only hold absolute programs
+    let bare = AllowList {
+        programs: vec!["bash".to_string()],
+        ..AllowList::default()
+    };
+    assert!(allow_list::save(&storage, HOOKS_ALLOW_LIST_KEY, &bare).is_err());
+}
+
+/// Test Case 25.4: Shell Hook Runs Without a Shell
+#[cfg(unix)]
+#[tokio::test]
+async fn test_shell_hook_runs() {
+    let hook = Hook {
+        name: "echo".to_string(),
+        event: HookEvent::ReviewStarted,
+        enabled: true,
+        action: HookAction::Shell {
+            program: "/bin/echo".to_string(),
+            args: vec!["{{owner}}; exit 1".to_string()],
+        },
+    };
+    let context = HookContext::new(HookEvent::ReviewStarted, "octocat", "docs", 1);
+    let config = HooksConfig::default();
+
+    let blocked = hooks::run_hook(&config, &AllowList::default(), &hook, &context).await;
+    assert!(blocked.is_err());
+
+    let allowed = AllowList {
+        programs: vec!["/bin/echo".to_string()],
+        ..AllowList::default()
+    };
+    let outcome = hooks::run_hook(&config, &allowed, &hook, &context).await.unwrap();
+    assert!(outcome.starts_with("/bin/echo exited"));
+}
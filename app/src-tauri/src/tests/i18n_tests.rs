@@ -0,0 +1,38 @@
+// Category 22: Localization Tests (i18n.rs)
+// Tests for translated backend messages and placeholder substitution
+
+use crate::i18n::{fill, translate, Locale, Msg};
+
+/// Test Case 22.1: English Is the Default Locale
+#[test]
+fn test_default_locale_is_english() {
+    assert_eq!(Locale::default(), Locale::En);
+    assert_eq!(translate(Locale::En, Msg::Timeout), "operation timed out");
+}
+
+/// Test Case 22.2: Every Locale Translates Log Headers
+#[test]
+fn test_locales_translate_headers() {
+    assert_eq!(translate(Locale::De, Msg::LogLine), "Zeile");
+    assert_eq!(translate(Locale::Ja, Msg::LogLine), "行");
+    assert_ne!(
+        translate(Locale::En, Msg::LogReviewDeleted),
+        translate(Locale::De, Msg::LogReviewDeleted)
+    );
+}
+
+/// Test Case 22.3: Placeholder Substitution
+#[test]
+fn test_fill_placeholders() {
+    let template = translate(Locale::De, Msg::InvalidInput);
+    assert_eq!(fill(template, &[&"theme"]), "ungültige Eingabe: theme");
+    assert_eq!(fill("{0} of {1}", &[&3, &5]), "3 of 5");
+}
+
+/// Test Case 22.4: Locale Serializes as Lowercase Code
+#[test]
+fn test_locale_serialization() {
+    assert_eq!(serde_json::to_value(Locale::Ja).unwrap(), "ja");
+    let parsed: Locale = serde_json::from_str("\"de\"").unwrap();
+    assert_eq!(parsed, Locale::De);
+}
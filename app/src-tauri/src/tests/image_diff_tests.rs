@@ -0,0 +1,80 @@
+// Category 45: Image Diff Tests (image_diff.rs)
+// Tests for image header sniffing and perceptual change between versions
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+use crate::image_diff;
+
+/// Grayscale PNG whose pixel values come from `pixel(x, y)`.
+fn encode_png(width: u32, height: u32, pixel: impl Fn(u32, u32) -> u8) -> Vec<u8> {
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        let data: Vec<u8> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| pixel(x, y))
+            .collect();
+        writer.write_image_data(&data).unwrap();
+    }
+    out
+}
+
+/// Test Case 45.1: Header Sniffing for Common Formats
+#[test]
+fn test_sniff_formats() {
+    let png = encode_png(12, 7, |_, _| 0);
+    assert_eq!(image_diff::sniff(&png), (Some("png"), Some((12, 7))));
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend_from_slice(&[0x40, 0x01, 0xF0, 0x00]);
+    assert_eq!(image_diff::sniff(&gif), (Some("gif"), Some((320, 240))));
+
+    // SOI, APP0 (length 4), SOF0 with height 480 and width 640
+    let jpeg = [
+        0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0xE0,
+        0x02, 0x80,
+    ];
+    assert_eq!(image_diff::sniff(&jpeg), (Some("jpeg"), Some((640, 480))));
+
+    let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 16"><path/></svg>"#;
+    assert_eq!(image_diff::sniff(svg), (Some("svg"), Some((24, 16))));
+
+    assert_eq!(image_diff::sniff(b"plain text"), (None, None));
+}
+
+/// Test Case 45.2: Identical Images Do Not Change; Different Ones Do
+#[test]
+fn test_compare_png_versions() {
+    let gradient = encode_png(64, 32, |x, _| (x * 4) as u8);
+    let resized = encode_png(128, 64, |x, _| (x * 2) as u8);
+    let inverted = encode_png(64, 32, |x, _| 255 - (x * 4) as u8);
+    let b64 = |bytes: &[u8]| STANDARD.encode(bytes);
+
+    let same = image_diff::compare("img.png", Some(&b64(&gradient)), Some(&b64(&resized)));
+    assert_eq!(same.hash_distance, Some(0));
+    assert_eq!(same.changed_percent, Some(0.0));
+    assert_eq!(same.head.as_ref().and_then(|h| h.width), Some(128));
+    assert_eq!(
+        same.byte_size_delta,
+        resized.len() as i64 - gradient.len() as i64
+    );
+
+    let changed = image_diff::compare("img.png", Some(&b64(&gradient)), Some(&b64(&inverted)));
+    assert_eq!(changed.hash_distance, Some(64));
+    assert_eq!(changed.changed_percent, Some(100.0));
+}
+
+/// Test Case 45.3: Added Images Have No Base and No Hash Comparison
+#[test]
+fn test_compare_added_image() {
+    let png = encode_png(4, 4, |x, y| (x * 60 + y) as u8);
+    let diff = image_diff::compare("new.png", None, Some(&STANDARD.encode(&png)));
+
+    assert!(diff.base.is_none());
+    assert_eq!(diff.byte_size_delta, png.len() as i64);
+    assert_eq!(diff.head.unwrap().format.as_deref(), Some("png"));
+    assert!(diff.hash_distance.is_none());
+}
@@ -0,0 +1,69 @@
+// Category 75: Link Index Tests (link_index.rs)
+// Tests for heading anchors, link extraction, and path resolution in the link index
+
+use crate::link_index::{self, IndexedLink, LinkIndex};
+
+/// Test Case 75.1: Anchors Generated as on GitHub
+#[test]
+fn test_anchors() {
+    let content = "# Install the CLI\n\n## What's new in `v2`?\n\n```md\n# Not a heading\n```\n\n## Install the CLI\n";
+    let slugs: Vec<(String, u64)> = link_index::anchors(content)
+        .into_iter()
+        .map(|a| (a.slug, a.line))
+        .collect();
+    assert_eq!(
+        slugs,
+        vec![
+            ("install-the-cli".to_string(), 1),
+            ("whats-new-in-v2".to_string(), 3),
+            ("install-the-cli-1".to_string(), 9),
+        ]
+    );
+}
+
+/// Test Case 75.2: Relative Paths Resolved
+#[test]
+fn test_resolve() {
+    assert_eq!(link_index::resolve("docs/a/b.md", "../c.md").as_deref(), Some("docs/c.md"));
+    assert_eq!(link_index::resolve("docs/a/b.md", "./d/e.md").as_deref(), Some("docs/a/d/e.md"));
+    assert_eq!(link_index::resolve("docs/a/b.md", "/README.md").as_deref(), Some("README.md"));
+    assert_eq!(link_index::resolve("docs/a/b.md", "").as_deref(), Some("docs/a/b.md"));
+    assert_eq!(link_index::resolve("b.md", "../../x.md"), None);
+}
+
+/// Test Case 75.3: Anchor Links Extracted
+#[test]
+fn test_links() {
+    let content = "See [setup](../setup.md#Install-the-CLI \"Setup\") and [below](#usage).\n\
+                   [ext](https://example.com/a.md#x) [img](pic.png#frag) [plain](other.md)\n\
+                   [ref]: <guide.md#faq>\n";
+    let links = link_index::links("docs/how-to/use.md", content);
+
+    let found: Vec<(&str, &str, u64)> = links
+        .iter()
+        .map(|l| (l.target_path.as_str(), l.anchor.as_str(), l.line))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("docs/setup.md", "install-the-cli", 1),
+            ("docs/how-to/use.md", "usage", 1),
+            ("docs/how-to/guide.md", "faq", 3),
+        ]
+    );
+}
+
+/// Test Case 75.4: Index Lookup by Target and Anchor
+#[test]
+fn test_index_lookup() {
+    let files = vec![
+        ("docs/a.md".to_string(), "[x](b.md#intro)\n[y](b.md#other)\n".to_string()),
+        ("docs/c.md".to_string(), "```\n[z](b.md#intro)\n```\n[w](./b.md#intro)\n".to_string()),
+    ];
+    let index = LinkIndex::build(&files, false);
+    assert_eq!(index.indexed_files, 2);
+
+    let hits: Vec<&IndexedLink> = index.links_to("docs/b.md", "intro").collect();
+    assert_eq!(hits.len(), 2);
+    assert_eq!((hits[1].source_path.as_str(), hits[1].line), ("docs/c.md", 4));
+}
@@ -0,0 +1,41 @@
+// Category 51: Linked Issue Tests (linked_issues.rs)
+// Tests for closing keywords in PR descriptions
+
+use crate::linked_issues::{closing_references, IssueReference};
+
+fn local(number: u64) -> IssueReference {
+    IssueReference { owner: None, repo: None, number }
+}
+
+fn remote(owner: &str, repo: &str, number: u64) -> IssueReference {
+    IssueReference {
+        owner: Some(owner.to_string()),
+        repo: Some(repo.to_string()),
+        number,
+    }
+}
+
+/// Test Case 51.1: Every Closing Keyword Form Is Recognised
+#[test]
+fn test_closing_keywords() {
+    let body = "Fixes #12, closes octo/docs#7 and Resolved: #3.\n\
+                fix #4\n\
+                Resolves https://github.com/octo/site/issues/99";
+
+    assert_eq!(
+        closing_references(body),
+        vec![local(12), remote("octo", "docs", 7), local(3), local(4), remote("octo", "site", 99)]
+    );
+}
+
+/// Test Case 51.2: Plain Mentions, Code, and Duplicates Are Ignored
+#[test]
+fn test_non_closing_mentions_ignored() {
+    let body = "Related to #5, see #6.\n\
+                Prefix fixes#8 is not a keyword use.\n\
+                `fixes #9` in code\n\
+                ```\nfixes #10\n```\n\
+                Fixes #11 and fixes #11 again";
+
+    assert_eq!(closing_references(body), vec![local(11)]);
+}
@@ -0,0 +1,168 @@
+// Category 79: Local Roots Tests (local_roots.rs)
+// Tests for the folders local directory mode is allowed to read
+
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::local_roots::{self, LocalRootsConfig};
+use tempfile::TempDir;
+use super::create_test_storage;
+
+fn roots(dirs: &[&Path]) -> LocalRootsConfig {
+    LocalRootsConfig {
+        roots: dirs.iter().map(|d| d.to_string_lossy().into_owned()).collect(),
+    }
+}
+
+/// Test Case 79.1: Folders Inside a Root Are Allowed
+#[test]
+fn test_folder_inside_root_allowed() {
+    let root = TempDir::new().unwrap();
+    let docs = root.path().join("docs");
+    std::fs::create_dir(&docs).unwrap();
+
+    let config = roots(&[root.path()]);
+    let canonical = config.check(&docs).expect("docs is inside the root");
+    assert_eq!(canonical, std::fs::canonicalize(&docs).unwrap());
+    assert!(config.check(root.path()).is_ok());
+}
+
+/// Test Case 79.2: Folders Outside Every Root Are Refused
+#[test]
+fn test_folder_outside_roots_refused() {
+    let root = TempDir::new().unwrap();
+    let other = TempDir::new().unwrap();
+
+    let err = roots(&[root.path()]).check(other.path()).unwrap_err();
+    assert!(matches!(err, AppError::PathNotAllowed(_)));
+    assert!(matches!(
+        LocalRootsConfig::default().check(root.path()),
+        Err(AppError::PathNotAllowed(_))
+    ));
+}
+
+/// Test Case 79.3: Dot-Dot Segments Cannot Climb Out of a Root
+#[test]
+fn test_dot_dot_escape_refused() {
+    let outer = TempDir::new().unwrap();
+    let root = outer.path().join("root");
+    std::fs::create_dir(&root).unwrap();
+    std::fs::create_dir(outer.path().join("secrets")).unwrap();
+
+    let escape = root.join("..").join("secrets");
+    assert!(matches!(
+        roots(&[&root]).check(&escape),
+        Err(AppError::PathNotAllowed(_))
+    ));
+}
+
+/// Test Case 79.4: Network Paths Are Recognized
+#[test]
+fn test_network_paths() {
+    assert!(local_roots::is_network_path(Path::new(r"\\server\share\docs")));
+    assert!(local_roots::is_network_path(Path::new("//server/share/docs")));
+    assert!(!local_roots::is_network_path(Path::new("/home/me/docs")));
+    assert!(!local_roots::is_network_path(Path::new("docs/guide")));
+
+    let err = roots(&[Path::new("/")]).check(Path::new("//server/share")).unwrap_err();
+    assert!(matches!(err, AppError::PathNotAllowed(_)));
+}
+
+/// Test Case 79.5: Roots Must Be Absolute Local Paths
+#[test]
+fn test_validate_roots() {
+    assert!(roots(&[Path::new("relative/docs")]).validate().is_err());
+    assert!(roots(&[Path::new("//server/share")]).validate().is_err());
+    let root = TempDir::new().unwrap();
+    assert!(roots(&[root.path()]).validate().is_ok());
+}
+
+/// Test Case 79.6: Adding a Root Persists It Once
+#[test]
+fn test_add_root_persists_once() {
+    let (storage, _temp) = create_test_storage();
+    let root = TempDir::new().unwrap();
+    let nested = root.path().join("guides");
+    std::fs::create_dir(&nested).unwrap();
+
+    let config = local_roots::add_root(&storage, root.path()).unwrap();
+    assert_eq!(config.roots.len(), 1);
+    // Already covered by the first root
+    local_roots::add_root(&storage, &nested).unwrap();
+
+    let loaded = local_roots::load_config(&storage).unwrap();
+    assert_eq!(loaded, config);
+    assert!(loaded.check(&nested).is_ok());
+}
+
+/// Test Case 79.7: Files Are Read Only While Still Inside the Folder
+#[test]
+fn test_ensure_within() {
+    let root = TempDir::new().unwrap();
+    let other = TempDir::new().unwrap();
+    let base = std::fs::canonicalize(root.path()).unwrap();
+    let inside = root.path().join("a.md");
+    let outside = other.path().join("b.md");
+    std::fs::write(&inside, "# A").unwrap();
+    std::fs::write(&outside, "# B").unwrap();
+
+    assert!(local_roots::ensure_within(&base, &inside).is_ok());
+    assert!(matches!(
+        local_roots::ensure_within(&base, &outside),
+        Err(AppError::PathNotAllowed(_))
+    ));
+}
+
+/// Test Case 79.8: Symlinks Out of a Root Are Refused
+#[cfg(unix)]
+#[test]
+fn test_symlink_escape_refused() {
+    let root = TempDir::new().unwrap();
+    let other = TempDir::new().unwrap();
+    let link = root.path().join("elsewhere");
+    std::os::unix::fs::symlink(other.path(), &link).unwrap();
+
+    assert!(matches!(
+        roots(&[root.path()]).check(&link),
+        Err(AppError::PathNotAllowed(_))
+    ));
+}
+
+/// Test Case 79.9: Filesystem and Home Roots Are Too Broad
+#[test]
+fn test_broad_roots_refused() {
+    assert!(local_roots::is_too_broad(Path::new("/")));
+    assert!(roots(&[Path::new("/")]).validate().is_err());
+    let root = TempDir::new().unwrap();
+    assert!(!local_roots::is_too_broad(root.path()));
+
+    // A stored filesystem root does not allow anything
+    let docs = root.path().join("docs");
+    std::fs::create_dir(&docs).unwrap();
+    assert!(matches!(
+        roots(&[Path::new("/")]).check(&docs),
+        Err(AppError::PathNotAllowed(_))
+    ));
+}
+
+/// Test Case 79.10: Saving Roots Can Only Remove Them
+#[test]
+fn test_retain_roots_only_removes() {
+    let (storage, _temp) = create_test_storage();
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    local_roots::add_root(&storage, first.path()).unwrap();
+    let added = local_roots::add_root(&storage, second.path()).unwrap();
+
+    let unknown = TempDir::new().unwrap();
+    let mut with_unknown = added.clone();
+    with_unknown.roots.push(unknown.path().to_string_lossy().into_owned());
+    assert!(matches!(
+        local_roots::retain_roots(&storage, &with_unknown),
+        Err(AppError::PathNotAllowed(_))
+    ));
+
+    let kept = LocalRootsConfig { roots: added.roots[1..].to_vec() };
+    assert_eq!(local_roots::retain_roots(&storage, &kept).unwrap(), kept);
+    assert_eq!(local_roots::load_config(&storage).unwrap(), kept);
+}
@@ -0,0 +1,95 @@
+// Category 74: Localization Pairing Tests (localization.rs)
+// Tests for locale folder detection and pairing translations with their source files
+
+use crate::localization;
+use crate::models::PullRequestFile;
+
+fn file(path: &str) -> PullRequestFile {
+    PullRequestFile {
+        path: path.to_string(),
+        status: "modified".to_string(),
+        additions: 1,
+        deletions: 0,
+        patch: None,
+        head_content: None,
+        base_content: None,
+        language: "markdown".to_string(),
+        previous_filename: None,
+        localized_pair: None,
+    }
+}
+
+/// Test Case 74.1: Locale Folders Detected
+#[test]
+fn test_locale_of() {
+    assert_eq!(localization::locale_of("docs/ja/intro.md").as_deref(), Some("ja"));
+    assert_eq!(localization::locale_of("site/content/pt-BR/a.md").as_deref(), Some("pt-BR"));
+    assert_eq!(localization::locale_of("i18n/zh_Hans/guide/b.md").as_deref(), Some("zh_Hans"));
+    // Ordinary folders and file names are not locales
+    assert_eq!(localization::locale_of("ci/ui/readme.md"), None);
+    assert_eq!(localization::locale_of("docs/en.md"), None);
+    assert_eq!(localization::locale_of("docs/english/a.md"), None);
+}
+
+/// Test Case 74.2: Source Path for a Translation
+#[test]
+fn test_source_path() {
+    assert_eq!(
+        localization::source_path("docs/ja/setup/install.md", "en").as_deref(),
+        Some("docs/en/setup/install.md")
+    );
+    assert_eq!(
+        localization::source_path("docs/de-de/install.md", "en-us").as_deref(),
+        Some("docs/en-us/install.md")
+    );
+    // The source itself, in any region, has no source
+    assert_eq!(localization::source_path("docs/en-us/install.md", "en"), None);
+    assert_eq!(localization::source_path("docs/install.md", "en"), None);
+}
+
+/// Test Case 74.3: Files Paired in the File Model
+#[test]
+fn test_pair_files() {
+    let mut files = vec![
+        file("docs/en/install.md"),
+        file("docs/ja/install.md"),
+        file("docs/de/install.md"),
+        file("docs/ja/only-translated.md"),
+        file("README.md"),
+    ];
+    localization::pair_files(&mut files, localization::DEFAULT_SOURCE_LOCALE);
+
+    let pairs: Vec<Option<&str>> = files.iter().map(|f| f.localized_pair.as_deref()).collect();
+    assert_eq!(
+        pairs,
+        vec![
+            Some("docs/ja/install.md"),
+            Some("docs/en/install.md"),
+            Some("docs/en/install.md"),
+            None,
+            None,
+        ]
+    );
+}
+
+/// Test Case 74.4: Translations Without a Source Left Unpaired
+#[test]
+fn test_no_source_no_pair() {
+    let mut files = vec![file("docs/ja/a.md"), file("docs/de/a.md")];
+    localization::pair_files(&mut files, "en");
+    assert!(files.iter().all(|f| f.localized_pair.is_none()));
+
+    let mut files = vec![file("docs/en-us/a.md"), file("docs/fr-fr/a.md")];
+    localization::pair_files(&mut files, "en");
+    assert_eq!(files[1].localized_pair.as_deref(), Some("docs/en-us/a.md"));
+}
+
+/// Test Case 74.5: Removed Files Left Unpaired
+#[test]
+fn test_removed_files_not_paired() {
+    let mut removed = file("docs/ja/a.md");
+    removed.status = "removed".to_string();
+    let mut files = vec![file("docs/en/a.md"), removed];
+    localization::pair_files(&mut files, "en");
+    assert!(files.iter().all(|f| f.localized_pair.is_none()));
+}
@@ -0,0 +1,53 @@
+// Category 47: Markdown Render Cache Tests (markdown_render.rs)
+// Tests for blob-SHA keys and the LRU cache of rendered HTML
+
+use crate::markdown_render::{self, git_blob_sha};
+use super::create_test_storage;
+
+/// Test Case 47.1: Blob SHA Matches Git
+#[test]
+fn test_git_blob_sha_matches_git() {
+    assert_eq!(git_blob_sha(""), "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+    assert_eq!(git_blob_sha("hello\n"), "ce013625030ba8dba906f756967f9e9ca394464a");
+}
+
+/// Test Case 47.2: Render Context Needs Both Owner and Repo
+#[test]
+fn test_render_context() {
+    assert_eq!(markdown_render::render_context(Some("octo"), Some("docs")), "octo/docs");
+    assert_eq!(markdown_render::render_context(Some("octo"), None), "");
+    assert_eq!(markdown_render::render_context(None, None), "");
+}
+
+/// Test Case 47.3: Cached HTML Round Trips per Context
+#[test]
+fn test_rendered_markdown_round_trip() {
+    let (storage, _temp_dir) = create_test_storage();
+    let sha = git_blob_sha("# Title\n");
+
+    assert_eq!(storage.get_rendered_markdown(&sha, "octo/docs").unwrap(), None);
+    storage.put_rendered_markdown(&sha, "octo/docs", "<h1>Title</h1>", 10).unwrap();
+    storage.put_rendered_markdown(&sha, "octo/docs", "<h1>Title!</h1>", 10).unwrap();
+
+    assert_eq!(
+        storage.get_rendered_markdown(&sha, "octo/docs").unwrap().as_deref(),
+        Some("<h1>Title!</h1>")
+    );
+    assert_eq!(storage.get_rendered_markdown(&sha, "").unwrap(), None);
+}
+
+/// Test Case 47.4: Least Recently Used Entries Are Evicted
+#[test]
+fn test_rendered_markdown_lru_eviction() {
+    let (storage, _temp_dir) = create_test_storage();
+
+    storage.put_rendered_markdown("a", "", "A", 2).unwrap();
+    storage.put_rendered_markdown("b", "", "B", 2).unwrap();
+    // Reading `a` makes `b` the least recently used
+    assert!(storage.get_rendered_markdown("a", "").unwrap().is_some());
+    storage.put_rendered_markdown("c", "", "C", 2).unwrap();
+
+    assert!(storage.get_rendered_markdown("a", "").unwrap().is_some());
+    assert_eq!(storage.get_rendered_markdown("b", "").unwrap(), None);
+    assert!(storage.get_rendered_markdown("c", "").unwrap().is_some());
+}
@@ -0,0 +1,109 @@
+// Category 44: Markdown Structure Tests (markdown_structure.rs)
+// Tests for broken tables, unclosed fences, and malformed lists
+
+use crate::markdown_structure::{self, StructureIssueKind};
+
+fn kinds_and_lines(content: &str, patch: Option<&str>) -> Vec<(StructureIssueKind, u64)> {
+    markdown_structure::validate(content, patch)
+        .into_iter()
+        .map(|issue| (issue.kind, issue.line))
+        .collect()
+}
+
+/// Test Case 44.1: Table Rows With the Wrong Column Count
+#[test]
+fn test_table_column_mismatch() {
+    let content = "\
+| Name | Value |
+| ---- | ----- |
+| a | 1 |
+| b | 2 | extra |
+| `a|b` | escaped \\| pipe |
+";
+
+    assert_eq!(
+        kinds_and_lines(content, None),
+        vec![(StructureIssueKind::TableColumnMismatch, 4)]
+    );
+}
+
+/// Test Case 44.2: Delimiter Row Mismatch and Non-Tables
+#[test]
+fn test_table_delimiter_and_non_tables() {
+    let content = "| a | b | c |\n|---|---|\n| 1 | 2 | 3 |\n\nA | B heading\n---\n";
+
+    assert_eq!(
+        kinds_and_lines(content, None),
+        vec![(StructureIssueKind::TableColumnMismatch, 2)]
+    );
+}
+
+/// Test Case 44.3: Unclosed Fence Reported at Its Opening Line
+#[test]
+fn test_unclosed_fence() {
+    let content = "Intro\n\n````rust\nlet x = 1;\n```\n| not | a | table |\n|---|\n";
+
+    assert_eq!(
+        kinds_and_lines(content, None),
+        vec![(StructureIssueKind::UnclosedFence, 3)]
+    );
+    assert!(kinds_and_lines("~~~\ncode\n~~~\n", None).is_empty());
+}
+
+/// Test Case 44.4: Malformed Lists
+#[test]
+fn test_malformed_lists() {
+    let content = "\
+- first
+-second
+* third
+  - nested
+  - nested again
+
+-v is a flag mentioned in prose.
+1. one
+2.two
+";
+
+    assert_eq!(
+        kinds_and_lines(content, None),
+        vec![
+            (StructureIssueKind::ListMarkerMissingSpace, 2),
+            (StructureIssueKind::ListMarkerMismatch, 3),
+            (StructureIssueKind::ListMarkerMissingSpace, 9),
+        ]
+    );
+}
+
+/// Test Case 44.5: Patch Limits Findings to Touched Blocks
+#[test]
+fn test_patch_filter() {
+    let content = "\
+| a | b |
+|---|---|
+| 1 | 2 | 3 |
+
+- one
+* two
+";
+    // Only the list is touched
+    let patch = "@@ -5,1 +5,2 @@\n - one\n+* two";
+
+    assert_eq!(
+        kinds_and_lines(content, Some(patch)),
+        vec![(StructureIssueKind::ListMarkerMismatch, 6)]
+    );
+}
+
+/// Test Case 44.6: Code Blocks Are Extracted With Their Language
+#[test]
+fn test_code_blocks() {
+    let content = "Intro\n\n```Rust ignore\nfn main() {}\n```\n\n~~~\nplain\n~~~\n\n```python\nunclosed\n";
+    let blocks = markdown_structure::code_blocks(content);
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].language, "rust");
+    assert_eq!(blocks[0].fence_line, 3);
+    assert_eq!(blocks[0].lines, vec!["fn main() {}"]);
+    assert_eq!(blocks[1].language, "");
+    assert_eq!(blocks[1].lines, vec!["plain"]);
+}
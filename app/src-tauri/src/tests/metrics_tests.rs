@@ -0,0 +1,170 @@
+// Category 41: Review Turnaround Metrics Tests (metrics.rs)
+// Tests for turnaround records, usage events and the stats computed from them
+
+use crate::metrics::{self, UsageKind};
+use crate::review_storage::{TurnaroundRecord, UsageRecord};
+use chrono::{DateTime, Utc};
+use super::create_test_storage;
+
+fn record(number: u64, requested_at: Option<&str>, submitted_at: &str) -> TurnaroundRecord {
+    TurnaroundRecord {
+        owner: "acme".to_string(),
+        repo: "docs".to_string(),
+        pr_number: number,
+        requested_at: requested_at.map(str::to_string),
+        submitted_at: submitted_at.to_string(),
+    }
+}
+
+/// Test Case 41.1: Stats Use Only Reviews With a Request Time
+#[test]
+fn test_compute_stats() {
+    let records = vec![
+        record(1, Some("2024-06-01T09:00:00+00:00"), "2024-06-01T11:00:00+00:00"),
+        record(2, Some("2024-06-02T09:00:00+00:00"), "2024-06-03T09:00:00+00:00"),
+        record(3, None, "2024-06-03T10:00:00+00:00"),
+        record(4, Some("2024-06-04T09:00:00+00:00"), "2024-06-04T13:00:00+00:00"),
+    ];
+
+    let stats = metrics::compute_stats("quarter", &records);
+
+    assert_eq!(stats.range, "quarter");
+    assert_eq!(stats.reviews, 4);
+    assert_eq!(stats.measured, 3);
+    assert_eq!(stats.average_hours, Some(10.0));
+    assert_eq!(stats.median_hours, Some(4.0));
+    assert_eq!(stats.p90_hours, Some(24.0));
+    assert_eq!(stats.slowest.map(|r| r.pr_number), Some(2));
+}
+
+/// Test Case 41.2: No Measured Reviews Means No Figures
+#[test]
+fn test_compute_stats_empty() {
+    let stats = metrics::compute_stats("week", &[record(1, None, "2024-06-01T09:00:00+00:00")]);
+
+    assert_eq!(stats.reviews, 1);
+    assert_eq!(stats.measured, 0);
+    assert!(stats.average_hours.is_none());
+    assert!(stats.median_hours.is_none());
+    assert!(stats.slowest.is_none());
+}
+
+/// Test Case 41.3: Ranges Are Validated
+#[test]
+fn test_range_start() {
+    let now = DateTime::parse_from_rfc3339("2024-06-30T00:00:00Z").unwrap().with_timezone(&Utc);
+
+    assert_eq!(
+        metrics::range_start("week", now).unwrap().map(|t| t.to_rfc3339()),
+        Some("2024-06-23T00:00:00+00:00".to_string())
+    );
+    assert_eq!(metrics::range_start("all", now).unwrap(), None);
+    assert!(metrics::range_start("fortnight", now).is_err());
+}
+
+/// Test Case 41.4: Turnaround Records Round Trip and Filter by Submission Time
+#[test]
+fn test_turnaround_records_storage() {
+    let (storage, _temp) = create_test_storage();
+
+    storage
+        .record_turnaround("acme", "docs", 1, Some("2024-01-10T09:00:00+00:00"), "2024-01-11T09:00:00+00:00")
+        .unwrap();
+    storage
+        .record_turnaround("acme", "docs", 2, None, "2024-06-01T09:00:00+00:00")
+        .unwrap();
+
+    let all = storage.get_turnaround_records(None).unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].requested_at.as_deref(), Some("2024-01-10T09:00:00+00:00"));
+
+    let recent = storage
+        .get_turnaround_records(Some("2024-03-01T00:00:00+00:00"))
+        .unwrap();
+    assert_eq!(recent, vec![record(2, None, "2024-06-01T09:00:00+00:00")]);
+}
+
+fn usage(kind: UsageKind, occurred_at: &str, duration_ms: Option<u64>) -> UsageRecord {
+    UsageRecord {
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.to_string(),
+        duration_ms,
+    }
+}
+
+/// Test Case 41.5: Usage Metrics Count Events and Rank API Latency
+#[test]
+fn test_compute_usage() {
+    let at = "2024-06-01T09:00:00+00:00";
+    let mut records = vec![
+        usage(UsageKind::ReviewStarted, at, None),
+        usage(UsageKind::CommentAdded, at, None),
+        usage(UsageKind::CommentAdded, at, None),
+        usage(UsageKind::ReviewSubmitted, at, None),
+        usage(UsageKind::SubmissionFailed, at, None),
+    ];
+    records.extend((1..=10).rev().map(|i| usage(UsageKind::ApiRequest, at, Some(i * 100))));
+
+    let usage = metrics::compute_usage("month", true, &records);
+
+    assert_eq!(usage.range, "month");
+    assert!(usage.enabled);
+    assert_eq!(usage.reviews_started, 1);
+    assert_eq!(usage.comments_added, 2);
+    assert_eq!(usage.reviews_submitted, 1);
+    assert_eq!(usage.submission_failures, 1);
+    assert_eq!(usage.api_requests, 10);
+    assert_eq!(usage.api_latency_p50_ms, Some(500.0));
+    assert_eq!(usage.api_latency_p90_ms, Some(900.0));
+    assert_eq!(usage.api_latency_p99_ms, Some(1000.0));
+
+    let empty = metrics::compute_usage("week", false, &[]);
+    assert_eq!(empty.api_requests, 0);
+    assert!(empty.api_latency_p50_ms.is_none());
+}
+
+/// Test Case 41.6: Usage Events Round Trip, Filter and Expire
+#[test]
+fn test_usage_events_storage() {
+    let (storage, _temp) = create_test_storage();
+
+    storage
+        .record_usage_event("comment_added", "2023-01-10T09:00:00+00:00", None)
+        .unwrap();
+    storage
+        .record_usage_event("api_request", "2024-06-01T09:00:00+00:00", Some(250))
+        .unwrap();
+
+    let recent = storage
+        .get_usage_events(Some("2024-03-01T00:00:00+00:00"))
+        .unwrap();
+    assert_eq!(
+        recent,
+        vec![usage(UsageKind::ApiRequest, "2024-06-01T09:00:00+00:00", Some(250))]
+    );
+
+    let now = DateTime::parse_from_rfc3339("2024-06-30T00:00:00Z").unwrap().with_timezone(&Utc);
+    assert_eq!(metrics::purge_old_usage(&storage, now).unwrap(), 1);
+    assert_eq!(storage.get_usage_events(None).unwrap().len(), 1);
+}
+
+/// Test Case 41.7: Batched API Latencies Are Written Together
+#[test]
+fn test_record_usage_events_batch() {
+    let (storage, _temp) = create_test_storage();
+
+    let batch = vec![
+        ("2024-06-01T09:00:00+00:00".to_string(), 120),
+        ("2024-06-01T09:00:01+00:00".to_string(), 340),
+    ];
+    storage.record_usage_events(UsageKind::ApiRequest.as_str(), &batch).unwrap();
+    storage.record_usage_events(UsageKind::ApiRequest.as_str(), &[]).unwrap();
+
+    assert_eq!(
+        storage.get_usage_events(None).unwrap(),
+        vec![
+            usage(UsageKind::ApiRequest, "2024-06-01T09:00:00+00:00", Some(120)),
+            usage(UsageKind::ApiRequest, "2024-06-01T09:00:01+00:00", Some(340)),
+        ]
+    );
+}
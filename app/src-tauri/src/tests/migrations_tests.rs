@@ -0,0 +1,52 @@
+// Category 23: App Data Migration Tests (migrations.rs)
+// Tests for version detection, backups, and marker updates
+
+use crate::migrations::{run_migrations, CURRENT_DATA_VERSION};
+use tempfile::TempDir;
+
+/// Test Case 23.1: Fresh Install Writes Current Version Without Backup
+#[test]
+fn test_fresh_install_no_backup() {
+    let temp = TempDir::new().unwrap();
+
+    let report = run_migrations(temp.path()).unwrap();
+
+    assert_eq!(report.previous_version, CURRENT_DATA_VERSION);
+    assert_eq!(report.current_version, CURRENT_DATA_VERSION);
+    assert!(report.backup_dir.is_none());
+    assert!(temp.path().join("data_version.json").exists());
+}
+
+/// Test Case 23.2: Legacy Layout Is Backed Up Then Marked Current
+#[test]
+fn test_legacy_layout_backed_up() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(temp.path().join("reviews.db"), b"legacy").unwrap();
+    std::fs::create_dir_all(temp.path().join("review_logs")).unwrap();
+    std::fs::write(temp.path().join("review_logs").join("owner-repo-1.log"), "# Review").unwrap();
+
+    let report = run_migrations(temp.path()).unwrap();
+
+    assert_eq!(report.previous_version, 1);
+    assert_eq!(report.current_version, CURRENT_DATA_VERSION);
+    assert!(report.error.is_none());
+    // Version 1 data already has the version 2 layout; only the marker is new
+    assert!(report.applied.is_empty());
+
+    let backup = std::path::PathBuf::from(report.backup_dir.expect("backup dir"));
+    assert!(backup.join("reviews.db").exists());
+    assert!(backup.join("review_logs").join("owner-repo-1.log").exists());
+}
+
+/// Test Case 23.3: Second Run Is a No-Op
+#[test]
+fn test_migrations_idempotent() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(temp.path().join("reviews.db"), b"legacy").unwrap();
+
+    run_migrations(temp.path()).unwrap();
+    let second = run_migrations(temp.path()).unwrap();
+
+    assert!(second.applied.is_empty());
+    assert!(second.backup_dir.is_none());
+}
@@ -1,6 +1,17 @@
 // Backend Test Suite
 // Organized by test case categories from backend-test-cases.md
 
+use crate::review_storage::ReviewStorage;
+use tempfile::TempDir;
+
+/// Helper to create a test storage instance with temp directory
+fn create_test_storage() -> (ReviewStorage, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let storage = ReviewStorage::new(temp_dir.path())
+        .expect("Failed to create storage");
+    (storage, temp_dir)
+}
+
 #[cfg(test)]
 mod error_tests;
 
@@ -15,3 +26,216 @@ mod storage_tests;
 
 #[cfg(test)]
 mod review_storage_tests;
+
+#[cfg(test)]
+mod settings_tests;
+
+#[cfg(test)]
+mod i18n_tests;
+
+#[cfg(test)]
+mod migrations_tests;
+
+#[cfg(test)]
+mod commands_tests;
+
+#[cfg(test)]
+mod hooks_tests;
+
+#[cfg(test)]
+mod plugins_tests;
+
+#[cfg(test)]
+mod secrets_tests;
+
+#[cfg(test)]
+mod content_filter_tests;
+
+#[cfg(test)]
+mod summary_tests;
+
+#[cfg(test)]
+mod pr_template_tests;
+
+#[cfg(test)]
+mod cache_tests;
+
+#[cfg(test)]
+mod diff_tests;
+
+#[cfg(test)]
+mod permalinks_tests;
+
+#[cfg(test)]
+mod quote_tests;
+
+#[cfg(test)]
+mod emoji_tests;
+
+#[cfg(test)]
+mod handoff_tests;
+
+#[cfg(test)]
+mod categories_tests;
+
+#[cfg(test)]
+mod quiet_hours_tests;
+
+#[cfg(test)]
+mod repo_config_tests;
+
+#[cfg(test)]
+mod workload_tests;
+
+#[cfg(test)]
+mod metrics_tests;
+
+#[cfg(test)]
+mod todos_tests;
+
+#[cfg(test)]
+mod acronyms_tests;
+
+#[cfg(test)]
+mod markdown_structure_tests;
+
+#[cfg(test)]
+mod image_diff_tests;
+
+#[cfg(test)]
+mod alt_text_tests;
+
+#[cfg(test)]
+mod markdown_render_tests;
+
+#[cfg(test)]
+mod owner_policy_tests;
+
+#[cfg(test)]
+mod confirmations_tests;
+
+#[cfg(test)]
+mod pr_size_tests;
+
+#[cfg(test)]
+mod linked_issues_tests;
+
+#[cfg(test)]
+mod drift_tests;
+
+#[cfg(test)]
+mod visits_tests;
+
+#[cfg(test)]
+mod attention_tests;
+
+#[cfg(test)]
+mod comment_export_tests;
+
+#[cfg(test)]
+mod quick_comments_tests;
+
+#[cfg(test)]
+mod time_format_tests;
+
+#[cfg(test)]
+mod api_version_tests;
+
+#[cfg(test)]
+mod recorder_tests;
+
+#[cfg(test)]
+mod rate_limit_tests;
+
+#[cfg(test)]
+mod reset_tests;
+
+#[cfg(test)]
+mod oauth_scopes_tests;
+
+#[cfg(test)]
+mod comment_sync_tests;
+
+#[cfg(test)]
+mod submission_report_tests;
+
+#[cfg(test)]
+mod orphans_tests;
+
+#[cfg(test)]
+mod comment_threads_tests;
+
+#[cfg(test)]
+mod comment_search_tests;
+
+#[cfg(test)]
+mod review_activity_tests;
+
+#[cfg(test)]
+mod diagrams_tests;
+
+#[cfg(test)]
+mod snippet_checks_tests;
+
+#[cfg(test)]
+mod spec_validation_tests;
+
+#[cfg(test)]
+mod front_matter_tests;
+
+#[cfg(test)]
+mod heading_style_tests;
+
+#[cfg(test)]
+mod localization_tests;
+
+#[cfg(test)]
+mod link_index_tests;
+
+#[cfg(test)]
+mod anchor_impact_tests;
+
+#[cfg(test)]
+mod folder_counts_tests;
+
+#[cfg(test)]
+mod command_args_tests;
+
+#[cfg(test)]
+mod local_roots_tests;
+
+#[cfg(test)]
+mod local_files_tests;
+
+#[cfg(test)]
+mod second_pass_tests;
+
+#[cfg(test)]
+mod session_tests;
+
+#[cfg(test)]
+mod settings_bundle_tests;
+
+#[cfg(test)]
+mod auth_tests;
+
+#[cfg(test)]
+mod stale_reviews_tests;
+
+#[cfg(test)]
+mod review_snapshot_tests;
+
+#[cfg(test)]
+mod safe_mode_tests;
+
+#[cfg(test)]
+mod pr_cache_tests;
+
+#[cfg(test)]
+mod review_import_tests;
+
+#[cfg(test)]
+mod comment_anchors_tests;
+
+#[cfg(test)]
+mod review_profiles_tests;
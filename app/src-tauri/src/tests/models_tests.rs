@@ -60,6 +60,10 @@ fn test_pr_summary_serialization() {
         state: "open".to_string(),
         merged: false,
         locked: false,
+        size: None,
+        new_since_last_visit: false,
+        needs_my_attention: false,
+        pending_comment_count: 7,
     };
     
     let json = serde_json::to_value(&summary).unwrap();
@@ -68,6 +72,7 @@ fn test_pr_summary_serialization() {
     assert_eq!(json["author"], "octocat");
     assert_eq!(json["has_pending_review"], true);
     assert_eq!(json["file_count"], 5);
+    assert_eq!(json["pending_comment_count"], 7);
     assert_eq!(json["state"], "open");
     assert_eq!(json["merged"], false);
     assert_eq!(json["locked"], false);
@@ -82,6 +87,9 @@ fn test_pr_metadata_serialization() {
         state: "open".to_string(),
         merged: false,
         locked: true,
+        head_sha: "abc123".to_string(),
+        head_owner: None,
+        head_repo: None,
     };
     
     let json = serde_json::to_value(&metadata).unwrap();
@@ -93,7 +101,7 @@ fn test_pr_metadata_serialization() {
 /// Test Case 2.5: PullRequestDetail serializes with files and comments
 #[test]
 fn test_pr_detail_serialization() {
-    use crate::models::{PullRequestDetail, PullRequestFile};
+    use crate::models::{PullRequestDetail, PullRequestFile, ReactionSummary};
     
     let detail = PullRequestDetail {
         number: 456,
@@ -102,6 +110,8 @@ fn test_pr_detail_serialization() {
         author: "developer".to_string(),
         head_sha: "abc123def456".to_string(),
         base_sha: "789xyz000111".to_string(),
+        head_owner: None,
+        head_repo: None,
         files: vec![
             PullRequestFile {
                 path: "src/main.rs".to_string(),
@@ -113,11 +123,25 @@ fn test_pr_detail_serialization() {
                 base_content: Some("old content".to_string()),
                 language: "rust".to_string(),
                 previous_filename: None,
+                localized_pair: None,
             }
         ],
         comments: vec![],
+        threads: vec![],
         my_comments: vec![],
         reviews: vec![],
+        secret_findings: vec![],
+        reactions: ReactionSummary::default(),
+        participants: vec![],
+        author_context: None,
+        linked_issues: vec![],
+        merge_status: None,
+        bookmarks: vec![],
+        last_visited_at: None,
+        new_comment_count: 0,
+        skipped_files: vec![],
+        stale: false,
+        cached_at: None,
     };
     
     let json = serde_json::to_value(&detail).unwrap();
@@ -144,6 +168,7 @@ fn test_pr_file_renamed() {
         base_content: None,
         language: "rust".to_string(),
         previous_filename: Some("src/old_name.rs".to_string()),
+        localized_pair: None,
     };
     
     let json = serde_json::to_value(&file).unwrap();
@@ -161,6 +186,7 @@ fn test_pr_comment_with_line() {
         body: "This needs fixing".to_string(),
         author: "reviewer".to_string(),
         created_at: "2024-01-15T10:00:00Z".to_string(),
+        display_time: "Jan 15, 2024, 10:00 AM UTC".to_string(),
         url: "https://github.com/owner/repo/pull/1#discussion_r12345".to_string(),
         path: Some("src/app.rs".to_string()),
         line: Some(42),
@@ -172,6 +198,8 @@ fn test_pr_comment_with_line() {
         review_id: Some(9999),
         in_reply_to_id: None,
         outdated: Some(false),
+        new_since_last_visit: false,
+        reactions: Default::default(),
     };
     
     let json = serde_json::to_value(&comment).unwrap();
@@ -192,6 +220,7 @@ fn test_pr_comment_file_level() {
         body: "General file feedback".to_string(),
         author: "reviewer".to_string(),
         created_at: "2024-01-15T11:00:00Z".to_string(),
+        display_time: "Jan 15, 2024, 11:00 AM UTC".to_string(),
         url: "https://github.com/owner/repo/pull/1#discussion_r67890".to_string(),
         path: Some("README.md".to_string()),
         line: None,
@@ -203,6 +232,8 @@ fn test_pr_comment_file_level() {
         review_id: None,
         in_reply_to_id: None,
         outdated: None,
+        new_since_last_visit: false,
+        reactions: Default::default(),
     };
     
     let json = serde_json::to_value(&comment).unwrap();
@@ -221,6 +252,7 @@ fn test_pr_review_pending() {
         state: "PENDING".to_string(),
         author: "reviewer".to_string(),
         submitted_at: None,
+        display_time: None,
         body: Some("Draft review".to_string()),
         html_url: Some("https://github.com/owner/repo/pull/1#pullrequestreview-11111".to_string()),
         commit_id: Some("abc123".to_string()),
@@ -245,9 +277,18 @@ fn test_pr_under_review_serialization() {
         title: "Fix hooks".to_string(),
         has_local_review: true,
         has_pending_review: false,
-        viewed_count: 5,
+        viewed_count: 1,
+        hunk_reviewed_files: vec!["docs/intro.md".to_string()],
         total_count: 10,
         local_folder: None,
+        needs_my_attention: Some(true),
+        due_at: None,
+        overdue: false,
+        pending_comment_count: 4,
+        head_sha: Some("abc123".to_string()),
+        state: Some("closed".to_string()),
+        merged: Some(true),
+        locked: Some(false),
     };
     
     let json = serde_json::to_value(&pr).unwrap();
@@ -255,8 +296,13 @@ fn test_pr_under_review_serialization() {
     assert_eq!(json["repo"], "react");
     assert_eq!(json["number"], 123);
     assert_eq!(json["has_local_review"], true);
-    assert_eq!(json["viewed_count"], 5);
+    assert_eq!(json["viewed_count"], 1);
+    assert_eq!(json["hunk_reviewed_files"][0], "docs/intro.md");
     assert_eq!(json["total_count"], 10);
+    assert_eq!(json["needs_my_attention"], true);
+    assert_eq!(json["pending_comment_count"], 4);
+    assert_eq!(json["state"], "closed");
+    assert_eq!(json["merged"], true);
 }
 
 /// Test Case 2.11: PrUnderReview with local folder
@@ -271,9 +317,18 @@ fn test_pr_under_review_local_folder() {
         title: "Local review".to_string(),
         has_local_review: true,
         has_pending_review: false,
-        viewed_count: 3,
+        viewed_count: 0,
+        hunk_reviewed_files: Vec::new(),
         total_count: 7,
         local_folder: Some("C:/Users/me/docs".to_string()),
+        needs_my_attention: None,
+        due_at: None,
+        overdue: false,
+        pending_comment_count: 0,
+        head_sha: None,
+        state: None,
+        merged: None,
+        locked: None,
     };
     
     let json = serde_json::to_value(&pr).unwrap();
@@ -281,3 +336,73 @@ fn test_pr_under_review_local_folder() {
     assert_eq!(json["repo"], "local");
     assert_eq!(json["local_folder"], "C:/Users/me/docs");
 }
+
+/// Test Case 2.12: Participants combine GitHub's list with comment and review counts
+#[test]
+fn test_tally_participants() {
+    use crate::models::tally_participants;
+
+    let participants = vec!["alice".to_string(), "Bob".to_string(), "carol".to_string()];
+    let tally = tally_participants(&participants, &["bob", "bob", "dave"], &["alice", "bob"]);
+
+    let summary: Vec<(&str, u32, u32)> = tally
+        .iter()
+        .map(|p| (p.login.as_str(), p.comments, p.reviews))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("Bob", 2, 1), ("alice", 0, 1), ("dave", 1, 0), ("carol", 0, 0)]
+    );
+}
+
+/// Test Case 2.13: First contribution follows author association and merged count
+#[test]
+fn test_is_first_contribution() {
+    use crate::models::is_first_contribution;
+
+    assert!(is_first_contribution("FIRST_TIME_CONTRIBUTOR", 0));
+    assert!(is_first_contribution("FIRST_TIMER", 3));
+    assert!(is_first_contribution("NONE", 0));
+    assert!(!is_first_contribution("CONTRIBUTOR", 2));
+    assert!(!is_first_contribution("MEMBER", 0));
+    assert!(!is_first_contribution("OWNER", 0));
+}
+
+/// Test Case 2.14: Comment reactions read GitHub's rollup
+#[test]
+fn test_comment_reactions_from_rollup() {
+    use crate::models::CommentReactions;
+
+    let rollup = serde_json::json!({
+        "url": "https://api.github.com/repos/o/r/issues/comments/1/reactions",
+        "total_count": 5,
+        "+1": 3,
+        "-1": 0,
+        "hooray": 1,
+        "eyes": 1
+    });
+    let reactions: CommentReactions = serde_json::from_value(rollup).unwrap();
+    assert_eq!(reactions.thumbs_up, 3);
+    assert_eq!(reactions.hooray, 1);
+    assert_eq!(reactions.eyes, 1);
+    assert_eq!(reactions.heart, 0);
+
+    let json = serde_json::to_value(&reactions).unwrap();
+    assert_eq!(json["thumbs_up"], 3);
+    assert!(json.get("+1").is_none());
+}
+
+/// Test Case 2.15: File review progress percentage
+#[test]
+fn test_file_review_progress() {
+    use crate::models::FileReviewProgress;
+
+    let progress = FileReviewProgress::new("a.md".into(), 2, 3);
+    assert_eq!(progress.percent, 66);
+
+    let progress = FileReviewProgress::new("a.md".into(), 5, 3);
+    assert_eq!((progress.reviewed_hunks, progress.percent), (3, 100));
+
+    let progress = FileReviewProgress::new("a.md".into(), 0, 0);
+    assert_eq!(progress.percent, 0);
+}
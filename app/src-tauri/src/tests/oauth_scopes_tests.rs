@@ -0,0 +1,63 @@
+// Category 62: OAuth Scope Upgrade Tests (oauth_scopes.rs)
+// Tests for spotting requests refused for a missing scope and widening the sign-in
+
+use reqwest::header::{HeaderMap, HeaderValue};
+
+use crate::oauth_scopes::{covers, graphql_missing_scope, missing_scope, record, take_pending, with_scope};
+
+fn headers(accepted: &str, granted: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-accepted-oauth-scopes", HeaderValue::from_str(accepted).unwrap());
+    headers.insert("x-oauth-scopes", HeaderValue::from_str(granted).unwrap());
+    headers
+}
+
+/// Test Case 62.1: Broader Scopes Cover Narrower Ones
+#[test]
+fn test_scope_coverage() {
+    let granted = vec!["repo".to_string(), "write:org".to_string()];
+    assert!(covers(&granted, "repo"));
+    assert!(covers(&granted, "public_repo"));
+    assert!(covers(&granted, "read:org"));
+    assert!(!covers(&granted, "admin:org"));
+    assert!(!covers(&granted, "workflow"));
+}
+
+/// Test Case 62.2: Missing Scope From Response Headers
+#[test]
+fn test_missing_scope_from_headers() {
+    assert_eq!(
+        missing_scope(&headers("admin:org, write:org", "repo")).as_deref(),
+        Some("admin:org")
+    );
+    assert_eq!(missing_scope(&headers("read:org", "repo, admin:org")), None);
+    assert_eq!(missing_scope(&headers("", "repo")), None);
+    assert_eq!(missing_scope(&HeaderMap::new()), None);
+}
+
+/// Test Case 62.3: Missing Scope From a GraphQL Error
+#[test]
+fn test_graphql_missing_scope() {
+    let message = "Your token has not been granted the required scopes to execute this query. \
+                   The 'teams' field requires one of the following scopes: ['read:org'], but your \
+                   token has only been granted the: ['repo'] scopes.";
+    assert_eq!(graphql_missing_scope(message).as_deref(), Some("read:org"));
+    assert_eq!(graphql_missing_scope("Something else went wrong"), None);
+}
+
+/// Test Case 62.4: Upgraded Sign-In Adds Only Uncovered Scopes
+#[test]
+fn test_with_scope() {
+    assert_eq!(with_scope("repo pull_request:write", "read:org"), "repo pull_request:write read:org");
+    assert_eq!(with_scope("repo pull_request:write", "public_repo"), "repo pull_request:write");
+}
+
+/// Test Case 62.5: A Refused Request Is Kept Until Read
+#[test]
+fn test_pending_upgrade() {
+    record("delete pending review", "repo");
+    let pending = take_pending().unwrap();
+    assert_eq!(pending.context, "delete pending review");
+    assert_eq!(pending.scope, "repo");
+    assert!(take_pending().is_none());
+}
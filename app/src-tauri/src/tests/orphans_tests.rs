@@ -0,0 +1,133 @@
+// Category 65: Orphaned Comment Tests (orphans.rs)
+// Tests for finding draft comments on files that are no longer part of the PR
+
+use crate::models::PullRequestFile;
+use crate::orphans::{describe, find_orphans, OrphanAction};
+use crate::review_storage::ReviewComment;
+
+fn comment(id: i64, path: &str, line: u64) -> ReviewComment {
+    ReviewComment {
+        id,
+        owner: "o".to_string(),
+        repo: "r".to_string(),
+        pr_number: 1,
+        file_path: path.to_string(),
+        line_number: line,
+        side: "RIGHT".to_string(),
+        body: "body".to_string(),
+        commit_id: "abc".to_string(),
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: "2024-01-01T00:00:00Z".to_string(),
+        deleted: false,
+        in_reply_to_id: None,
+        category: None,
+        general: false,
+        suggestion: None,
+    }
+}
+
+fn file(path: &str, previous: Option<&str>) -> PullRequestFile {
+    PullRequestFile {
+        path: path.to_string(),
+        status: if previous.is_some() { "renamed" } else { "modified" }.to_string(),
+        additions: 1,
+        deletions: 0,
+        patch: None,
+        head_content: None,
+        base_content: None,
+        language: "rust".to_string(),
+        previous_filename: previous.map(str::to_string),
+        localized_pair: None,
+    }
+}
+
+/// Test Case 65.1: Comments on Files Still in the PR Are Not Orphans
+#[test]
+fn test_no_orphans_when_files_present() {
+    let comments = vec![comment(1, "src/a.rs", 3), comment(2, "src/b.rs", 9)];
+    let files = vec![file("src/a.rs", None), file("src/b.rs", None)];
+    assert!(find_orphans(&comments, &files).is_empty());
+}
+
+/// Test Case 65.2: Comment on a Reverted File Is Reported With Move, Convert and Drop
+#[test]
+fn test_reverted_file_is_orphaned() {
+    let comments = vec![comment(1, "src/a.rs", 3), comment(2, "src/gone.rs", 7)];
+    let files = vec![file("src/a.rs", None)];
+
+    let orphans = find_orphans(&comments, &files);
+    assert_eq!(orphans.len(), 1);
+    assert_eq!(orphans[0].comment_id, 2);
+    assert_eq!(orphans[0].file_path, "src/gone.rs");
+    assert_eq!(orphans[0].line_number, 7);
+    assert_eq!(orphans[0].suggested_path, None);
+    assert_eq!(orphans[0].actions, vec![OrphanAction::Move, OrphanAction::Convert, OrphanAction::Drop]);
+}
+
+/// Test Case 65.3: Renamed File Is Suggested as the Move Target
+#[test]
+fn test_renamed_file_suggested() {
+    let comments = vec![comment(4, "src/old.rs", 12)];
+    let files = vec![file("src/new.rs", Some("src/old.rs"))];
+
+    let orphans = find_orphans(&comments, &files);
+    assert_eq!(orphans.len(), 1);
+    assert_eq!(orphans[0].suggested_path.as_deref(), Some("src/new.rs"));
+}
+
+/// Test Case 65.4: Actions Serialize in Snake Case
+#[test]
+fn test_orphan_serialization() {
+    let orphans = find_orphans(&[comment(5, "x.rs", 1)], &[]);
+    let json = serde_json::to_value(&orphans[0]).unwrap();
+    assert_eq!(json["actions"], serde_json::json!(["move", "convert", "drop"]));
+    assert!(json["suggested_path"].is_null());
+}
+
+/// Test Case 65.5: Description Names Each Removed File Once
+#[test]
+fn test_describe_lists_paths_once() {
+    let comments = vec![comment(1, "b.rs", 1), comment(2, "a.rs", 2), comment(3, "b.rs", 3)];
+    let text = describe(&find_orphans(&comments, &[]));
+    assert!(text.contains('3'));
+    assert!(text.contains("a.rs, b.rs"));
+    assert_eq!(text.matches("b.rs").count(), 1);
+}
+
+/// Test Case 65.6: General Comments Are Never Orphaned
+#[test]
+fn test_general_comments_not_orphaned() {
+    let mut general = comment(6, "src/gone.rs", 2);
+    general.general = true;
+    assert!(find_orphans(&[general], &[file("src/a.rs", None)]).is_empty());
+}
+
+/// Test Case 65.7: Comments on a File the PR Deletes Are Not Orphans
+#[test]
+fn test_removed_file_is_not_orphaned() {
+    let mut removed = file("docs/old.md", None);
+    removed.status = "removed".to_string();
+    let mut left = comment(1, "docs/old.md", 4);
+    left.side = "LEFT".to_string();
+    let file_level = comment(2, "docs/old.md", 0);
+
+    assert!(find_orphans(&[left, file_level], &[removed]).is_empty());
+}
+
+/// Test Case 65.8: Only Thread Roots Are Checked and Replies Go With Them
+#[test]
+fn test_replies_follow_their_root() {
+    let root = comment(1, "src/gone.rs", 3);
+    let mut reply = comment(2, "src/gone.rs", 3);
+    reply.in_reply_to_id = Some(1);
+    let mut nested = comment(3, "src/gone.rs", 3);
+    nested.in_reply_to_id = Some(2);
+    // Replying to a comment already on GitHub, which is not a local draft
+    let mut remote_reply = comment(4, "src/also-gone.rs", 8);
+    remote_reply.in_reply_to_id = Some(900);
+
+    let orphans = find_orphans(&[nested, root, reply, remote_reply], &[file("src/a.rs", None)]);
+    assert_eq!(orphans.len(), 1);
+    assert_eq!(orphans[0].comment_id, 1);
+    assert_eq!(orphans[0].reply_ids, vec![2, 3]);
+}
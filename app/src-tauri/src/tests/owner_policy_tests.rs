@@ -0,0 +1,100 @@
+// Category 48: Owner Policy Tests (owner_policy.rs)
+// Tests for the allow-list and block-list of repository owners
+
+use crate::error::AppError;
+use crate::owner_policy::{self, OwnerPolicy};
+
+fn policy(allowed: &[&str], blocked: &[&str]) -> OwnerPolicy {
+    OwnerPolicy {
+        allowed_owners: allowed.iter().map(|o| o.to_string()).collect(),
+        blocked_owners: blocked.iter().map(|o| o.to_string()).collect(),
+    }
+}
+
+/// Test Case 48.1: Empty Policy Allows Everyone
+#[test]
+fn test_default_policy_allows_all() {
+    let policy = OwnerPolicy::default();
+    assert!(policy.is_allowed("octo-org"));
+    assert!(policy.ensure_allowed("anyone").is_ok());
+}
+
+/// Test Case 48.2: Allow-List Is Case-Insensitive and Exclusive
+#[test]
+fn test_allow_list() {
+    let policy = policy(&["Acme", "acme-docs"], &[]);
+    assert!(policy.is_allowed("acme"));
+    assert!(policy.is_allowed("ACME-DOCS"));
+    assert!(!policy.is_allowed("octo-org"));
+}
+
+/// Test Case 48.3: Block-List Wins Over Allow-List
+#[test]
+fn test_block_list_wins() {
+    let policy = policy(&["acme"], &["acme", "personal"]);
+    assert!(!policy.is_allowed("acme"));
+    assert!(!policy.is_allowed("Personal"));
+
+    let err = policy.ensure_allowed("personal").unwrap_err();
+    assert!(matches!(&err, AppError::OwnerNotAllowed(owner) if owner == "personal"));
+    assert!(err.to_string().contains("personal"));
+}
+
+/// Test Case 48.4: Invalid Owner Names Are Rejected
+#[test]
+fn test_validate_rejects_invalid_names() {
+    assert!(policy(&["acme", "acme-docs"], &["x1"]).validate().is_ok());
+    assert!(policy(&["acme/docs"], &[]).validate().is_err());
+    assert!(policy(&[], &[""]).validate().is_err());
+    assert!(policy(&["-acme"], &[]).validate().is_err());
+}
+
+/// Test Case 48.5: Policy Is Read From Its File
+#[test]
+fn test_load_policy_from_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    assert_eq!(owner_policy::load_policy(temp_dir.path()).unwrap(), OwnerPolicy::default());
+
+    let path = temp_dir.path().join(owner_policy::OWNER_POLICY_FILE);
+    let saved = policy(&["acme"], &["personal"]);
+    std::fs::write(&path, serde_json::to_vec(&saved).unwrap()).unwrap();
+    assert_eq!(owner_policy::load_policy(temp_dir.path()).unwrap(), saved);
+
+    // A broken or invalid file is an error rather than an open policy
+    std::fs::write(&path, "{ not json").unwrap();
+    assert!(owner_policy::load_policy(temp_dir.path()).is_err());
+    std::fs::write(&path, serde_json::to_vec(&policy(&["a b"], &[])).unwrap()).unwrap();
+    assert!(owner_policy::load_policy(temp_dir.path()).is_err());
+}
+
+/// Test Case 48.6: Requests Are Checked Against the Owner They Reach
+#[test]
+fn test_request_owners() {
+    let client = reqwest::Client::new();
+    let owners = |builder: reqwest::RequestBuilder| {
+        owner_policy::request_owners(&builder.build().unwrap())
+    };
+
+    assert_eq!(owners(client.get("https://api.github.com/repos/acme/docs/pulls")), ["acme"]);
+    assert_eq!(owners(client.get("https://api.github.com/orgs/Acme")), ["Acme"]);
+    assert_eq!(owners(client.get("https://api.github.com/users/octocat/repos")), ["octocat"]);
+    assert!(owners(client.get("https://api.github.com/user")).is_empty());
+    assert!(owners(client.get("https://example.com/repos/personal/x")).is_empty());
+
+    let graphql = client.post("https://api.github.com/graphql").json(&serde_json::json!({
+        "query": "query",
+        "variables": { "owner": "acme", "o0": "personal", "repo": "docs", "n0": 1 },
+    }));
+    let mut found = owners(graphql);
+    found.sort();
+    assert_eq!(found, ["acme", "personal"]);
+
+    let policy = policy(&["acme"], &[]);
+    let allowed = client.get("https://api.github.com/repos/ACME/docs").build().unwrap();
+    assert!(policy.ensure_request_allowed(&allowed).is_ok());
+    let blocked = client.get("https://api.github.com/repos/personal/x").build().unwrap();
+    assert!(matches!(
+        policy.ensure_request_allowed(&blocked),
+        Err(AppError::OwnerNotAllowed(owner)) if owner == "personal"
+    ));
+}
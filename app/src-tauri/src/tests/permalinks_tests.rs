@@ -0,0 +1,50 @@
+// Category 33: Permalink Tests (permalinks.rs)
+// Tests for comment anchors, line links and GitHub Enterprise host mapping
+
+use crate::permalinks::{comment_url, line_url, web_base, CommentKind};
+
+const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+/// Test Case 33.1: API bases map to their web hosts
+#[test]
+fn test_web_base() {
+    assert_eq!(web_base("https://api.github.com"), "https://github.com");
+    assert_eq!(web_base("https://ghe.example.com/api/v3/"), "https://ghe.example.com");
+}
+
+/// Test Case 33.2: Each comment kind gets its own anchor
+#[test]
+fn test_comment_anchors() {
+    let base = "https://github.com";
+    assert_eq!(
+        comment_url(base, "o", "r", 7, CommentKind::ReviewComment, 42).unwrap(),
+        "https://github.com/o/r/pull/7#discussion_r42"
+    );
+    assert_eq!(
+        comment_url(base, "o", "r", 7, CommentKind::IssueComment, 42).unwrap(),
+        "https://github.com/o/r/pull/7#issuecomment-42"
+    );
+    assert_eq!(
+        comment_url(base, "o", "r", 7, CommentKind::Review, 42).unwrap(),
+        "https://github.com/o/r/pull/7#pullrequestreview-42"
+    );
+}
+
+/// Test Case 33.3: Line links pin the commit and encode the path
+#[test]
+fn test_line_url() {
+    let url = line_url("https://ghe.example.com", "o", "r", SHA, "docs/my guide.md", 12).unwrap();
+    assert_eq!(
+        url,
+        format!("https://ghe.example.com/o/r/blob/{SHA}/docs/my%20guide.md#L12")
+    );
+}
+
+/// Test Case 33.4: Branch names, line zero and empty paths are rejected
+#[test]
+fn test_line_url_validation() {
+    let base = "https://github.com";
+    assert!(line_url(base, "o", "r", "main", "a.md", 1).is_err());
+    assert!(line_url(base, "o", "r", SHA, "a.md", 0).is_err());
+    assert!(line_url(base, "o", "r", SHA, "/", 1).is_err());
+}
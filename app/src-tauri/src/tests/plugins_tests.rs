@@ -0,0 +1,311 @@
+// Category 26: Analyzer Plugin Tests (plugins.rs)
+// Tests for plugin discovery and mapping findings to draft comments
+
+use crate::command_args::RepoPath;
+use crate::plugins::{PluginFileInput, PluginFinding, PluginHost};
+use tempfile::TempDir;
+use wasm_encoder::{
+    BlockType, CanonicalOption, CodeSection, ComponentBuilder, ComponentExportKind,
+    ComponentValType, ConstExpr, DataSection, ExportKind, ExportSection, Function,
+    FunctionSection, GlobalSection, GlobalType, Instruction, MemorySection, MemoryType, Module,
+    PrimitiveValType, TypeSection, ValType,
+};
+
+/// Test Case 26.1: Empty Plugin Directory
+#[test]
+fn test_empty_plugin_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let host = PluginHost::new(temp_dir.path()).unwrap();
+
+    assert!(temp_dir.path().join("plugins").is_dir());
+    assert!(host.list().unwrap().is_empty());
+
+    let files = vec![PluginFileInput {
+        path: RepoPath("README.md".to_string()),
+        content: "# Title".to_string(),
+        diff: None,
+    }];
+    assert!(host.analyze(&files, None).unwrap().is_empty());
+}
+
+/// Test Case 26.2: Invalid Component Reported, Not Fatal
+#[test]
+fn test_invalid_plugin_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let plugin_dir = temp_dir.path().join("plugins");
+    std::fs::create_dir_all(&plugin_dir).unwrap();
+    std::fs::write(plugin_dir.join("broken.wasm"), b"not wasm").unwrap();
+    std::fs::write(plugin_dir.join("notes.txt"), b"ignored").unwrap();
+
+    let host = PluginHost::new(temp_dir.path()).unwrap();
+    let plugins = host.list().unwrap();
+
+    assert_eq!(plugins.len(), 1);
+    assert_eq!(plugins[0].name, "broken");
+    assert!(plugins[0].error.is_some());
+}
+
+/// Test Case 26.3: Finding Draft Body Includes Suggestion
+#[test]
+fn test_finding_draft_body() {
+    let finding = PluginFinding {
+        plugin: "style-guide".to_string(),
+        path: "docs/intro.md".to_string(),
+        line: Some(3),
+        severity: "warning".to_string(),
+        message: "Use sentence case in headings".to_string(),
+        suggestion: Some("## Getting started".to_string()),
+    };
+
+    let body = finding.draft_body();
+    assert!(body.starts_with("**style-guide** (warning): Use sentence case"));
+    assert!(body.ends_with("```suggestion\n## Getting started\n```"));
+}
+
+/// Test Case 26.4: File-Level Finding Drops Its Suggestion
+#[test]
+fn test_file_level_finding_draft_body() {
+    let finding = PluginFinding {
+        plugin: "style-guide".to_string(),
+        path: "docs/intro.md".to_string(),
+        line: None,
+        severity: "info".to_string(),
+        message: "Add a summary at the top".to_string(),
+        suggestion: Some("Summary".to_string()),
+    };
+
+    let body = finding.draft_body();
+    assert_eq!(body, "**style-guide** (info): Add a summary at the top");
+}
+
+/// Build an analyzer component whose `analyze` runs `body` and then returns one warning
+/// at line 1. The core module keeps its strings and the finding in a data segment and
+/// hands out memory from a bump allocator.
+fn analyzer_component(name: &str, body: &[Instruction]) -> Vec<u8> {
+    let mut module = Module::new();
+    let mut types = TypeSection::new();
+    types.ty().function([ValType::I32; 4], [ValType::I32]);
+    types.ty().function([], [ValType::I32]);
+    types.ty().function([ValType::I32; 7], [ValType::I32]);
+    module.section(&types);
+
+    let mut functions = FunctionSection::new();
+    functions.function(0).function(1).function(2);
+    module.section(&functions);
+
+    let mut memories = MemorySection::new();
+    memories.memory(MemoryType {
+        minimum: 1,
+        maximum: None,
+        memory64: false,
+        shared: false,
+        page_size_log2: None,
+    });
+    module.section(&memories);
+
+    let mut globals = GlobalSection::new();
+    let heap = GlobalType { val_type: ValType::I32, mutable: true, shared: false };
+    globals.global(heap, &ConstExpr::i32_const(1024));
+    module.section(&globals);
+
+    let mut exports = ExportSection::new();
+    exports
+        .export("memory", ExportKind::Memory, 0)
+        .export("realloc", ExportKind::Func, 0)
+        .export("name", ExportKind::Func, 1)
+        .export("analyze", ExportKind::Func, 2);
+    module.section(&exports);
+
+    let mut code = CodeSection::new();
+    let mut realloc = Function::new([]);
+    for instruction in [
+        Instruction::GlobalGet(0),
+        Instruction::GlobalGet(0),
+        Instruction::LocalGet(3),
+        Instruction::I32Add,
+        Instruction::I32Const(7),
+        Instruction::I32Add,
+        Instruction::I32Const(-8),
+        Instruction::I32And,
+        Instruction::GlobalSet(0),
+        Instruction::End,
+    ] {
+        realloc.instruction(&instruction);
+    }
+    code.function(&realloc);
+    let mut name_fn = Function::new([]);
+    name_fn.instruction(&Instruction::I32Const(16)).instruction(&Instruction::End);
+    code.function(&name_fn);
+    let mut analyze = Function::new([]);
+    for instruction in body {
+        analyze.instruction(instruction);
+    }
+    analyze.instruction(&Instruction::I32Const(48)).instruction(&Instruction::End);
+    code.function(&analyze);
+    module.section(&code);
+
+    // 16: name (ptr, len), 32: name bytes, 48: findings (ptr, len), 64: the finding,
+    // 128: its message.
+    let message = b"ran to the end";
+    let words = |values: &[u32]| values.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>();
+    let mut finding = words(&[1, 1, 1, 128, message.len() as u32, 0]);
+    finding.resize(32, 0);
+    let mut data = DataSection::new();
+    data.active(0, &ConstExpr::i32_const(16), words(&[32, name.len() as u32]))
+        .active(0, &ConstExpr::i32_const(32), name.bytes())
+        .active(0, &ConstExpr::i32_const(48), words(&[64, 1]))
+        .active(0, &ConstExpr::i32_const(64), finding)
+        .active(0, &ConstExpr::i32_const(128), message.iter().copied());
+    module.section(&data);
+
+    let mut component = ComponentBuilder::default();
+    let module = component.core_module(None, &module);
+    let instance = component.core_instantiate(None, module, []);
+    let memory = component.core_alias_export(None, instance, "memory", ExportKind::Memory);
+    let realloc = component.core_alias_export(None, instance, "realloc", ExportKind::Func);
+    let name_fn = component.core_alias_export(None, instance, "name", ExportKind::Func);
+    let analyze = component.core_alias_export(None, instance, "analyze", ExportKind::Func);
+
+    let string = ComponentValType::Primitive(PrimitiveValType::String);
+    let (severity, ty) = component.ty(None);
+    ty.defined_type().enum_type(["info", "warning", "error"]);
+    let severity = component.export("severity", ComponentExportKind::Type, severity, None);
+    let (optional_string, ty) = component.ty(None);
+    ty.defined_type().option(string);
+    let (file_input, ty) = component.ty(None);
+    ty.defined_type().record([
+        ("path", string),
+        ("content", string),
+        ("diff", ComponentValType::Type(optional_string)),
+    ]);
+    let file_input = component.export("file-input", ComponentExportKind::Type, file_input, None);
+    let (optional_line, ty) = component.ty(None);
+    ty.defined_type().option(PrimitiveValType::U32);
+    let (finding, ty) = component.ty(None);
+    ty.defined_type().record([
+        ("line", ComponentValType::Type(optional_line)),
+        ("severity", ComponentValType::Type(severity)),
+        ("message", string),
+        ("suggestion", ComponentValType::Type(optional_string)),
+    ]);
+    let finding = component.export("finding", ComponentExportKind::Type, finding, None);
+    let (findings, ty) = component.ty(None);
+    ty.defined_type().list(ComponentValType::Type(finding));
+
+    let (name_ty, ty) = component.ty(None);
+    ty.function().params([] as [(&str, ComponentValType); 0]).result(Some(string));
+    let (analyze_ty, ty) = component.ty(None);
+    ty.function()
+        .params([("file", ComponentValType::Type(file_input))])
+        .result(Some(ComponentValType::Type(findings)));
+
+    let name_fn = component.lift_func(
+        None,
+        name_fn,
+        name_ty,
+        [CanonicalOption::UTF8, CanonicalOption::Memory(memory)],
+    );
+    let analyze = component.lift_func(
+        None,
+        analyze,
+        analyze_ty,
+        [
+            CanonicalOption::UTF8,
+            CanonicalOption::Memory(memory),
+            CanonicalOption::Realloc(realloc),
+        ],
+    );
+    component.export("name", ComponentExportKind::Func, name_fn, None);
+    component.export("analyze", ComponentExportKind::Func, analyze, None);
+    component.finish()
+}
+
+/// Write the fixture plugins and return a host that has loaded them.
+fn host_with_plugins(temp_dir: &TempDir, plugins: &[(&str, Vec<u8>)]) -> PluginHost {
+    let plugin_dir = temp_dir.path().join("plugins");
+    std::fs::create_dir_all(&plugin_dir).unwrap();
+    for (name, wasm) in plugins {
+        std::fs::write(plugin_dir.join(format!("{}.wasm", name)), wasm).unwrap();
+    }
+    PluginHost::new(temp_dir.path()).unwrap()
+}
+
+fn readme() -> Vec<PluginFileInput> {
+    vec![PluginFileInput {
+        path: RepoPath("README.md".to_string()),
+        content: "# Title".to_string(),
+        diff: None,
+    }]
+}
+
+/// Test Case 26.5: Component Findings Reach the Host
+#[test]
+fn test_component_findings() {
+    let temp_dir = TempDir::new().unwrap();
+    let host = host_with_plugins(&temp_dir, &[("finisher", analyzer_component("finisher", &[]))]);
+
+    let plugins = host.list().unwrap();
+    assert_eq!(plugins.len(), 1);
+    assert_eq!(plugins[0].name, "finisher");
+    assert!(plugins[0].error.is_none());
+
+    let findings = host.analyze(&readme(), None).unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].plugin, "finisher");
+    assert_eq!(findings[0].path, "README.md");
+    assert_eq!(findings[0].line, Some(1));
+    assert_eq!(findings[0].severity, "warning");
+    assert_eq!(findings[0].message, "ran to the end");
+    assert!(findings[0].suggestion.is_none());
+}
+
+/// Test Case 26.6: Endless Loop Stopped by the Fuel Budget
+#[test]
+fn test_endless_loop_stopped() {
+    let spin = [
+        Instruction::Loop(BlockType::Empty),
+        Instruction::Br(0),
+        Instruction::End,
+    ];
+    let temp_dir = TempDir::new().unwrap();
+    let host = host_with_plugins(
+        &temp_dir,
+        &[
+            ("finisher", analyzer_component("finisher", &[])),
+            ("spinner", analyzer_component("spinner", &spin)),
+        ],
+    );
+    assert!(host.list().unwrap().iter().all(|plugin| plugin.error.is_none()));
+
+    let findings = host.analyze(&readme(), None).unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].plugin, "finisher");
+}
+
+/// Test Case 26.7: Large Allocation Stopped by the Memory Cap
+#[test]
+fn test_large_allocation_stopped() {
+    // Ask for 128 MiB at once and trap if it is refused; past the cap, nothing comes back.
+    let hog = [
+        Instruction::I32Const(2048),
+        Instruction::MemoryGrow(0),
+        Instruction::I32Const(-1),
+        Instruction::I32Eq,
+        Instruction::If(BlockType::Empty),
+        Instruction::Unreachable,
+        Instruction::End,
+    ];
+    let temp_dir = TempDir::new().unwrap();
+    let host = host_with_plugins(
+        &temp_dir,
+        &[
+            ("finisher", analyzer_component("finisher", &[])),
+            ("hog", analyzer_component("hog", &hog)),
+        ],
+    );
+    assert!(host.list().unwrap().iter().all(|plugin| plugin.error.is_none()));
+
+    let findings = host.analyze(&readme(), None).unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].plugin, "finisher");
+}
@@ -0,0 +1,121 @@
+// Category 88: Offline PR Cache Tests (pr_cache.rs)
+// Tests for keeping the last-fetched details of a PR readable while offline
+
+use crate::error::AppError;
+use crate::models::{PullRequestDetail, ReactionSummary};
+use crate::pr_cache::{self, PrCache};
+use tempfile::TempDir;
+
+fn detail() -> PullRequestDetail {
+    PullRequestDetail {
+        number: 7,
+        title: "Fix typo".to_string(),
+        body: None,
+        author: "octocat".to_string(),
+        head_sha: "abc123".to_string(),
+        base_sha: "def456".to_string(),
+        head_owner: None,
+        head_repo: None,
+        files: vec![],
+        comments: vec![],
+        threads: vec![],
+        my_comments: vec![],
+        reviews: vec![],
+        secret_findings: vec![],
+        reactions: ReactionSummary::default(),
+        participants: vec![],
+        author_context: None,
+        linked_issues: vec![],
+        merge_status: None,
+        bookmarks: vec![],
+        last_visited_at: None,
+        new_comment_count: 0,
+        skipped_files: vec![],
+        stale: false,
+        cached_at: None,
+    }
+}
+
+/// Test Case 88.1: Cached Details Come Back Marked Stale
+#[test]
+fn test_pr_cache_round_trip() {
+    let temp = TempDir::new().unwrap();
+    let cache = PrCache::new(temp.path());
+    assert!(cache.load("octocat", "acme", "docs", 7).is_none());
+
+    cache.save("octocat", "acme", "docs", 7, &detail()).unwrap();
+    let cached = cache.load("octocat", "acme", "docs", 7).unwrap();
+    assert!(cached.stale);
+    assert!(cached.cached_at.is_some());
+    assert_eq!(cached.title, "Fix typo");
+    assert_eq!(cached.head_sha, "abc123");
+    assert!(cache.load("octocat", "acme", "docs", 8).is_none());
+
+    cache.clear().unwrap();
+    assert!(cache.load("octocat", "acme", "docs", 7).is_none());
+}
+
+/// Test Case 88.2: A Damaged Cache File Is Ignored
+#[test]
+fn test_pr_cache_damaged_file() {
+    let temp = TempDir::new().unwrap();
+    let cache = PrCache::new(temp.path());
+    let dir = temp
+        .path()
+        .join(pr_cache::PR_CACHE_DIR)
+        .join("octocat")
+        .join("acme")
+        .join("docs");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("7.json"), "{\"number\": 7").unwrap();
+
+    assert!(cache.load("octocat", "acme", "docs", 7).is_none());
+}
+
+/// Test Case 88.3: Only Failures to Reach GitHub Fall Back to the Cache
+#[test]
+fn test_is_offline_error() {
+    assert!(pr_cache::is_offline_error(&AppError::Timeout));
+    assert!(pr_cache::is_offline_error(&AppError::RateLimited("10:00".into())));
+    assert!(!pr_cache::is_offline_error(&AppError::AuthExpired));
+    assert!(!pr_cache::is_offline_error(&AppError::Api("Not Found".into())));
+}
+
+/// Test Case 88.4: Each Account Reads Only the Details It Cached
+#[test]
+fn test_pr_cache_per_account() {
+    let temp = TempDir::new().unwrap();
+    let cache = PrCache::new(temp.path());
+    cache.save("octocat", "acme", "docs", 7, &detail()).unwrap();
+
+    assert!(cache.load("octocat", "acme", "docs", 7).is_some());
+    assert!(cache.load("hubot", "acme", "docs", 7).is_none());
+}
+
+/// Test Case 88.5: The Cache Is Bounded by Age and Size
+#[test]
+fn test_pr_cache_bounded() {
+    let temp = TempDir::new().unwrap();
+    let cache = PrCache::new(temp.path());
+    for number in 0..=pr_cache::MAX_ENTRIES as u64 {
+        cache.save("octocat", "acme", "docs", number, &detail()).unwrap();
+    }
+    let dir = temp
+        .path()
+        .join(pr_cache::PR_CACHE_DIR)
+        .join("octocat")
+        .join("acme")
+        .join("docs");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), pr_cache::MAX_ENTRIES);
+
+    let later =
+        std::time::SystemTime::now() + pr_cache::MAX_AGE + std::time::Duration::from_secs(60);
+    cache.prune(later).unwrap();
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+    // Details cached longer ago than the limit are not served
+    let mut old = detail();
+    old.cached_at = Some("2020-01-01T00:00:00+00:00".to_string());
+    std::fs::write(dir.join("7.json"), serde_json::to_vec(&old).unwrap()).unwrap();
+    assert!(cache.load("octocat", "acme", "docs", 7).is_none());
+}
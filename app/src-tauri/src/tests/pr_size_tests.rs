@@ -0,0 +1,88 @@
+// Category 50: PR Size Tests (pr_size.rs)
+// Tests for size classes and review time estimates
+
+use crate::pr_size::{self, FileChange, PrSizeConfig, SizeClass};
+use super::create_test_storage;
+
+fn file(path: &str, additions: u64, deletions: u64) -> FileChange {
+    FileChange {
+        path: path.to_string(),
+        additions,
+        deletions,
+    }
+}
+
+/// Test Case 50.1: Classes Follow the Thresholds
+#[test]
+fn test_size_classes() {
+    let config = PrSizeConfig::default();
+    let class = |lines: u64| {
+        pr_size::classify(lines, 0, &[file("src/lib.rs", lines, 0)], 1, &config).class
+    };
+
+    assert_eq!(class(3), SizeClass::Xs);
+    assert_eq!(class(10), SizeClass::Xs);
+    assert_eq!(class(11), SizeClass::S);
+    assert_eq!(class(200), SizeClass::M);
+    assert_eq!(class(900), SizeClass::L);
+    assert_eq!(class(2000), SizeClass::Xl);
+    assert_eq!(class(9000), SizeClass::Xxl);
+    assert_eq!(serde_json::to_value(SizeClass::Xxl).unwrap(), "XXL");
+}
+
+/// Test Case 50.2: Assets Are Not Reviewed Lines and Prose Reads Faster
+#[test]
+fn test_file_kinds_and_estimate() {
+    let config = PrSizeConfig::default();
+    let files = [
+        file("docs/guide.md", 30, 10),
+        file("src/main.rs", 16, 0),
+        file("package-lock.json", 5000, 4000),
+        file("img/diagram.PNG", 0, 0),
+    ];
+    let size = pr_size::classify(5046, 4010, &files, 4, &config);
+
+    assert_eq!(size.reviewed_lines, 56);
+    assert_eq!(size.class, SizeClass::M);
+    // 40 prose lines at 20/min, 16 code lines at 8/min, 4 files at 0.5 min
+    assert_eq!(size.estimated_minutes, 6);
+}
+
+/// Test Case 50.3: Lines in Unlisted Files Count as Code
+#[test]
+fn test_unlisted_files_count_as_code() {
+    let config = PrSizeConfig::default();
+    let size = pr_size::classify(120, 30, &[file("README.md", 20, 0)], 150, &config);
+
+    assert_eq!(size.reviewed_lines, 150);
+    assert_eq!(size.estimated_minutes, 93);
+
+    let empty = pr_size::classify(0, 0, &[], 0, &config);
+    assert_eq!(empty.class, SizeClass::Xs);
+    assert_eq!(empty.estimated_minutes, 1);
+}
+
+/// Test Case 50.4: Config Validation and Round Trip
+#[test]
+fn test_config_round_trip() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(pr_size::load_config(&storage).unwrap(), PrSizeConfig::default());
+
+    let config = PrSizeConfig {
+        thresholds: [5, 20, 100, 400, 1000],
+        ..Default::default()
+    };
+    pr_size::save_config(&storage, &config).unwrap();
+    assert_eq!(pr_size::load_config(&storage).unwrap(), config);
+
+    let unordered = PrSizeConfig {
+        thresholds: [5, 5, 100, 400, 1000],
+        ..Default::default()
+    };
+    assert!(pr_size::save_config(&storage, &unordered).is_err());
+    let stalled = PrSizeConfig {
+        code_lines_per_minute: 0.0,
+        ..Default::default()
+    };
+    assert!(stalled.validate().is_err());
+}
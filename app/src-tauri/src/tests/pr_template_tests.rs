@@ -0,0 +1,45 @@
+// Category 30: PR Template Compliance Tests (pr_template.rs)
+// Tests for comparing PR descriptions against repository PR templates
+
+use crate::pr_template;
+
+const TEMPLATE: &str = "## Summary\n<!-- Describe the change -->\n\n## Testing\nDescribe how you tested.\n\n## Screenshots (optional)\n\n## Checklist\n- [ ] I updated the docs\n- [ ] I ran the link checker\n";
+
+/// Test Case 30.1: Complete Description Passes
+#[test]
+fn test_complete_body() {
+    let body = "## Summary\nFixes typos.\n\n## Testing\nBuilt the site locally.\n\n## Checklist\n- [x] I updated the docs\n- [X] I ran the link checker\n";
+    let check = pr_template::check_body(".github/pull_request_template.md", TEMPLATE, body);
+
+    assert!(check.is_complete, "{check:?}");
+    assert_eq!(check.template_path.as_deref(), Some(".github/pull_request_template.md"));
+}
+
+/// Test Case 30.2: Missing, Empty and Unticked Items Reported
+#[test]
+fn test_incomplete_body() {
+    let body = "## summary\n<!-- Describe the change -->\n\n## Testing\nDescribe how you tested.\n\n- [ ] I updated the docs\n";
+    let check = pr_template::check_body("PULL_REQUEST_TEMPLATE.md", TEMPLATE, body);
+
+    assert!(!check.is_complete);
+    assert_eq!(check.missing_sections, vec!["Checklist"]);
+    assert_eq!(check.empty_sections, vec!["Summary", "Testing"]);
+    assert_eq!(check.missing_checkboxes, vec!["I ran the link checker"]);
+    assert_eq!(check.unchecked_checkboxes, vec!["I updated the docs"]);
+}
+
+/// Test Case 30.3: Closest Template Chosen, None Means Complete
+#[test]
+fn test_multiple_templates() {
+    let templates = vec![
+        ("bug.md".to_string(), "## Repro steps\n\n## Expected\n".to_string()),
+        ("docs.md".to_string(), "## Summary\n".to_string()),
+    ];
+    let check = pr_template::check_against_templates(&templates, "## Summary\nNew page.");
+    assert_eq!(check.template_path.as_deref(), Some("docs.md"));
+    assert!(check.is_complete);
+
+    let none = pr_template::check_against_templates(&[], "anything");
+    assert!(none.is_complete);
+    assert!(none.template_path.is_none());
+}
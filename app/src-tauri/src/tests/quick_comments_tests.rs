@@ -0,0 +1,137 @@
+// Category 56: Quick Comment Tests (quick_comments.rs)
+// Tests for comment templates and their placeholders
+
+use crate::quick_comments::{self, CommentContext, QuickCommentConfig, QuickCommentTemplate};
+use super::create_test_storage;
+
+fn template(id: &str, body: &str, category: Option<&str>) -> QuickCommentTemplate {
+    QuickCommentTemplate {
+        id: id.to_string(),
+        label: id.to_string(),
+        body: body.to_string(),
+        category: category.map(str::to_string),
+    }
+}
+
+/// Test Case 56.1: Placeholders Are Filled From the Comment Context
+#[test]
+fn test_render_placeholders() {
+    let template = template(
+        "ctx",
+        "{{owner}}/{{repo}}#{{pr}}: {{file}} ({{path}}) line {{line}}",
+        None,
+    );
+    let context = CommentContext {
+        owner: "acme",
+        repo: "docs",
+        pr_number: 42,
+        path: "guides/setup.md",
+        line: Some(7),
+    };
+
+    assert_eq!(
+        template.render(&context),
+        "acme/docs#42: setup.md (guides/setup.md) line 7"
+    );
+
+    let file_level = CommentContext { line: None, ..context };
+    assert_eq!(template.render(&file_level), "acme/docs#42: setup.md (guides/setup.md) line ");
+}
+
+/// Test Case 56.2: Default Templates Are Valid and Found by Id
+#[test]
+fn test_default_templates() {
+    let config = QuickCommentConfig::default();
+
+    assert!(config.validate().is_ok());
+    assert_eq!(config.find("typo").unwrap().category.as_deref(), Some("nit"));
+    assert!(config.find("missing").is_err());
+}
+
+/// Test Case 56.3: Invalid Template Lists Are Rejected
+#[test]
+fn test_validate_templates() {
+    let duplicate = QuickCommentConfig {
+        templates: vec![template("a", "One", None), template("a", "Two", None)],
+    };
+    assert!(duplicate.validate().is_err());
+
+    let empty_body = QuickCommentConfig { templates: vec![template("a", "  ", None)] };
+    assert!(empty_body.validate().is_err());
+
+    let bad_category = QuickCommentConfig { templates: vec![template("a", "One", Some("urgent"))] };
+    assert!(bad_category.validate().is_err());
+
+    let empty_id = QuickCommentConfig { templates: vec![template(" ", "One", None)] };
+    assert!(empty_id.validate().is_err());
+
+    let mut same_label = template("b", "Two", None);
+    same_label.label = "A".to_string();
+    let same_label = QuickCommentConfig { templates: vec![template("a", "One", None), same_label] };
+    assert!(same_label.validate().is_err());
+}
+
+/// Test Case 56.4: Quick Comments Are Kept in the Template Library
+#[test]
+fn test_quick_comments_share_template_library() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(quick_comments::load_config(&storage).unwrap(), QuickCommentConfig::default());
+
+    // A library template with the same name becomes the quick comment
+    storage.create_comment_template("tone", "Old text", None).unwrap();
+    let config = QuickCommentConfig {
+        templates: vec![
+            template("tone", "Softer, please.", Some("suggestion")),
+            template("typo", "Typo here.", Some("nit")),
+        ],
+    };
+    quick_comments::save_config(&storage, &config).unwrap();
+    assert_eq!(quick_comments::load_config(&storage).unwrap(), config);
+
+    let library = storage.list_comment_templates().unwrap();
+    let tone = library.iter().find(|t| t.name == "tone").unwrap();
+    assert_eq!(tone.body, "Softer, please.");
+    assert_eq!(tone.quick_key.as_deref(), Some("tone"));
+    assert_eq!(library.len(), 2);
+
+    // Templates that drop off the list stay in the library without a key
+    let config = QuickCommentConfig { templates: vec![template("typo", "Typo here.", None)] };
+    quick_comments::save_config(&storage, &config).unwrap();
+    assert_eq!(quick_comments::load_config(&storage).unwrap(), config);
+    let tone = storage.list_comment_templates().unwrap().into_iter().find(|t| t.name == "tone");
+    assert!(tone.unwrap().quick_key.is_none());
+
+    // An empty list stays empty rather than bringing back the defaults
+    quick_comments::save_config(&storage, &QuickCommentConfig { templates: Vec::new() }).unwrap();
+    assert!(quick_comments::load_config(&storage).unwrap().templates.is_empty());
+}
+
+/// Test Case 56.5: Quick Comments Are Refused on Lines of Removed Files
+#[tokio::test]
+async fn test_quick_comment_on_removed_file() {
+    use crate::command_args::LOCAL_OWNER;
+    use crate::models::{ensure_side_exists, PullRequestFile};
+
+    let removed = PullRequestFile {
+        path: "docs/old.md".to_string(),
+        status: "removed".to_string(),
+        additions: 0,
+        deletions: 4,
+        patch: None,
+        head_content: None,
+        base_content: None,
+        language: "markdown".to_string(),
+        previous_filename: None,
+        localized_pair: None,
+    };
+    let files = [removed];
+    // Quick comments always go on the right side
+    let err = ensure_side_exists(&files, "docs/old.md", "RIGHT", 3).unwrap_err();
+    assert!(err.to_string().contains("docs/old.md"));
+    assert!(ensure_side_exists(&files, "docs/old.md", "RIGHT", 0).is_ok());
+    assert!(ensure_side_exists(&files, "docs/new.md", "RIGHT", 3).is_ok());
+
+    // Local folders and file-level comments never wait for GitHub's file list
+    assert!(crate::ensure_side_exists(LOCAL_OWNER, "local", 7, "a.md", "RIGHT", 3).await.is_ok());
+    assert!(crate::ensure_side_exists("owner", "repo", 7, "a.md", "RIGHT", 0).await.is_ok());
+}
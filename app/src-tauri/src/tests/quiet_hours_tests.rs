@@ -0,0 +1,141 @@
+// Category 38: Quiet Hours Tests (quiet_hours.rs)
+// Tests for the do-not-disturb window used by notifications and reminders
+
+use crate::quiet_hours::{self, QuietHoursConfig};
+use chrono::{DateTime, Utc};
+use super::create_test_storage;
+
+fn berlin() -> QuietHoursConfig {
+    QuietHoursConfig {
+        enabled: true,
+        timezone: Some("Europe/Berlin".to_string()),
+        ..QuietHoursConfig::default()
+    }
+}
+
+fn at(rfc3339: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+}
+
+/// Test Case 38.1: Overnight Window Wraps Past Midnight
+#[test]
+fn test_overnight_window() {
+    let config = berlin();
+
+    // Wednesday 2024-06-05; Berlin is UTC+2 in summer
+    assert!(!config.is_quiet_at(at("2024-06-05T10:00:00Z")).unwrap());
+    assert!(config.is_quiet_at(at("2024-06-05T16:00:00Z")).unwrap());
+    assert!(config.is_quiet_at(at("2024-06-05T23:30:00Z")).unwrap());
+    assert!(config.is_quiet_at(at("2024-06-06T06:59:00Z")).unwrap());
+    assert!(!config.is_quiet_at(at("2024-06-06T07:00:00Z")).unwrap());
+}
+
+/// Test Case 38.2: Quiet Days Are Quiet All Day
+#[test]
+fn test_quiet_days() {
+    let config = berlin();
+
+    // Saturday midday in Berlin
+    assert!(config.is_quiet_at(at("2024-06-08T10:00:00Z")).unwrap());
+
+    let weekdays_only = QuietHoursConfig {
+        quiet_days: vec![],
+        ..berlin()
+    };
+    assert!(!weekdays_only.is_quiet_at(at("2024-06-08T10:00:00Z")).unwrap());
+}
+
+/// Test Case 38.3: Disabled Config Is Never Quiet
+#[test]
+fn test_disabled_never_quiet() {
+    let config = QuietHoursConfig {
+        enabled: false,
+        ..berlin()
+    };
+
+    assert!(!config.is_quiet_at(at("2024-06-08T23:00:00Z")).unwrap());
+    assert!(!config.status_at(at("2024-06-08T23:00:00Z")).unwrap().quiet);
+}
+
+/// Test Case 38.4: Alerts Resume When the Window and Weekend End
+#[test]
+fn test_next_allowed_at() {
+    let config = berlin();
+
+    // Wednesday evening resumes Thursday 09:00 local
+    assert_eq!(
+        config.next_allowed_at(at("2024-06-05T20:15:30Z")).unwrap(),
+        Some(at("2024-06-06T07:00:00Z"))
+    );
+    // Friday evening skips the weekend to Monday 09:00 local
+    assert_eq!(
+        config.next_allowed_at(at("2024-06-07T17:00:00Z")).unwrap(),
+        Some(at("2024-06-10T07:00:00Z"))
+    );
+    // Outside quiet hours the instant itself is allowed
+    let now = at("2024-06-05T10:00:00Z");
+    assert_eq!(config.next_allowed_at(now).unwrap(), Some(now));
+
+    let status = config.status_at(at("2024-06-05T20:00:00Z")).unwrap();
+    assert!(status.quiet);
+    assert_eq!(status.resumes_at.as_deref(), Some("2024-06-06T07:00:00+00:00"));
+}
+
+/// Test Case 38.5: A Week With Every Day Quiet Never Resumes
+#[test]
+fn test_always_quiet_never_resumes() {
+    let config = QuietHoursConfig {
+        quiet_days: quiet_hours::WEEKDAYS.iter().map(|d| d.to_string()).collect(),
+        ..berlin()
+    };
+
+    assert_eq!(config.next_allowed_at(at("2024-06-05T10:00:00Z")).unwrap(), None);
+}
+
+/// Test Case 38.6: Invalid Times, Zones, and Days Are Rejected
+#[test]
+fn test_validation() {
+    assert!(berlin().validate().is_ok());
+    assert!(QuietHoursConfig::default().validate().is_ok());
+
+    let bad_time = QuietHoursConfig {
+        start: "25:00".to_string(),
+        ..berlin()
+    };
+    assert!(bad_time.validate().is_err());
+
+    let bad_zone = QuietHoursConfig {
+        timezone: Some("Mars/Olympus".to_string()),
+        ..berlin()
+    };
+    assert!(bad_zone.validate().is_err());
+
+    let bad_day = QuietHoursConfig {
+        quiet_days: vec!["saturday".to_string()],
+        ..berlin()
+    };
+    assert!(bad_day.validate().is_err());
+}
+
+/// Test Case 38.7: Config Round Trips Through Settings
+#[test]
+fn test_config_round_trip() {
+    let (storage, _temp) = create_test_storage();
+
+    assert_eq!(quiet_hours::load_config(&storage).unwrap(), QuietHoursConfig::default());
+
+    let config = QuietHoursConfig {
+        start: "19:30".to_string(),
+        end: "08:00".to_string(),
+        ..berlin()
+    };
+    quiet_hours::save_config(&storage, &config).unwrap();
+    assert_eq!(quiet_hours::load_config(&storage).unwrap(), config);
+
+    let invalid = QuietHoursConfig {
+        end: "8am".to_string(),
+        ..berlin()
+    };
+    assert!(quiet_hours::save_config(&storage, &invalid).is_err());
+    assert_eq!(quiet_hours::load_config(&storage).unwrap(), config);
+}
@@ -0,0 +1,79 @@
+// Category 34: Quote Reply Tests (quote.rs, review_storage.rs)
+// Tests for markdown quoting of GitHub comments and local drafts
+
+use crate::quote::{build_reply, draft_attribution, general_comment_body, github_attribution, quote_body};
+use super::create_test_storage;
+
+/// Test Case 34.1: Every line is quoted and blank lines keep the block together
+#[test]
+fn test_quote_body_lines() {
+    assert_eq!(quote_body("first\n\nsecond  \n"), "> first\n>\n> second");
+}
+
+/// Test Case 34.2: Nested quotes gain another level
+#[test]
+fn test_quote_nested() {
+    assert_eq!(quote_body("> earlier\nreply"), "> > earlier\n> reply");
+}
+
+/// Test Case 34.3: Suggestion blocks are quoted as plain diffs
+#[test]
+fn test_quote_suggestion() {
+    let quoted = quote_body("Try:\n```suggestion\nnew text\n```");
+    assert!(!quoted.contains("suggestion"));
+    assert!(quoted.contains("> ```diff\n> new text\n> ```"));
+}
+
+/// Test Case 34.4: Replies put the attribution first and leave room to type
+#[test]
+fn test_build_reply() {
+    let reply = build_reply(&github_attribution("octocat"), "Looks good");
+    assert_eq!(reply, "@octocat wrote:\n\n> Looks good\n\n");
+    assert_eq!(draft_attribution("docs/a.md", 4), "On `docs/a.md` line 4:");
+}
+
+/// Test Case 34.5: Local drafts can be looked up by id
+#[tokio::test]
+async fn test_get_local_comment() {
+    let (storage, _temp_dir) = create_test_storage();
+    storage
+        .start_review("o", "r", 1, "abc", None, None)
+        .unwrap();
+    let comment = storage
+        .add_comment("o", "r", 1, "docs/a.md", 3, "RIGHT", "Typo here", "abc", None)
+        .await
+        .unwrap();
+
+    let found = storage.get_comment(comment.id).unwrap().unwrap();
+    assert_eq!(found.body, "Typo here");
+    assert!(storage.get_comment(comment.id + 1).unwrap().is_none());
+}
+
+/// Test Case 34.6: Converted comments quote their file and line and become general
+#[tokio::test]
+async fn test_convert_to_general_comment() {
+    let (storage, _temp_dir) = create_test_storage();
+    storage
+        .start_review("o", "r", 1, "abc", None, None)
+        .unwrap();
+    let comment = storage
+        .add_comment("o", "r", 1, "docs/a.md", 3, "RIGHT", "Typo here", "abc", None)
+        .await
+        .unwrap();
+    assert!(!comment.general);
+
+    let body = general_comment_body(&comment.file_path, comment.line_number, &comment.body);
+    assert_eq!(body, "> On `docs/a.md` line 3:\n\nTypo here");
+    assert_eq!(general_comment_body("docs/b.md", 0, "Split"), "> On `docs/b.md`:\n\nSplit");
+
+    let converted = storage.convert_to_general_comment(comment.id, &body).await.unwrap();
+    assert!(converted.general);
+    assert_eq!(converted.body, body);
+    assert_eq!(converted.file_path, "docs/a.md");
+    assert!(storage.get_comments("o", "r", 1).unwrap()[0].general);
+
+    let err = storage.convert_to_general_comment(comment.id, "again").await.unwrap_err();
+    assert!(err.to_string().contains("is already a general comment"));
+    let err = storage.convert_to_general_comment(comment.id + 1, "missing").await.unwrap_err();
+    assert!(err.to_string().ends_with("not found"));
+}
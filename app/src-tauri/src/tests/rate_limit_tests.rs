@@ -0,0 +1,181 @@
+// Category 60: Rate Limit Tests (rate_limit.rs)
+// Tests for recognizing GitHub rate limits and deciding how long to pause
+
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+
+use crate::rate_limit::{
+    classify, parse_rate_limit_body, parse_status, pause_for, resource_for_path, retry_after,
+    RateLimitKind, RateLimitStatus,
+};
+
+fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in pairs {
+        map.insert(*name, HeaderValue::from_static(value));
+    }
+    map
+}
+
+/// Test Case 60.1: Primary and Secondary Limits Are Told Apart
+#[test]
+fn test_classify() {
+    let none = HeaderMap::new();
+    let secondary = r#"{"message":"You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}"#;
+    let primary = r#"{"message":"API rate limit exceeded for user ID 1."}"#;
+
+    assert_eq!(classify(StatusCode::FORBIDDEN, &none, secondary), Some(RateLimitKind::Secondary));
+    assert_eq!(classify(StatusCode::FORBIDDEN, &none, primary), Some(RateLimitKind::Primary));
+    assert_eq!(
+        classify(StatusCode::FORBIDDEN, &headers(&[("x-ratelimit-remaining", "0")]), "{}"),
+        Some(RateLimitKind::Primary)
+    );
+    assert_eq!(classify(StatusCode::TOO_MANY_REQUESTS, &none, ""), Some(RateLimitKind::Secondary));
+    assert_eq!(classify(StatusCode::FORBIDDEN, &none, r#"{"message":"Resource not accessible"}"#), None);
+    assert_eq!(classify(StatusCode::UNPROCESSABLE_ENTITY, &none, primary), None);
+}
+
+/// Test Case 60.2: Retry-After Accepts Seconds and HTTP Dates
+#[test]
+fn test_retry_after() {
+    let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+
+    assert_eq!(retry_after(&headers(&[("retry-after", "30")]), now), Some(Duration::from_secs(30)));
+    assert_eq!(
+        retry_after(&headers(&[("retry-after", "Mon, 10 Jun 2024 12:01:30 GMT")]), now),
+        Some(Duration::from_secs(90))
+    );
+    assert_eq!(
+        retry_after(&headers(&[("retry-after", "Mon, 10 Jun 2024 11:00:00 GMT")]), now),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(retry_after(&HeaderMap::new(), now), None);
+}
+
+/// Test Case 60.3: Pauses Follow Headers, Then Back Off for Secondary Limits
+#[test]
+fn test_pause_for() {
+    let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+    let none = HeaderMap::new();
+
+    assert_eq!(
+        pause_for(RateLimitKind::Secondary, &headers(&[("retry-after", "5")]), 3, now),
+        Duration::from_secs(5)
+    );
+    assert_eq!(pause_for(RateLimitKind::Secondary, &none, 0, now), Duration::from_secs(60));
+    assert_eq!(pause_for(RateLimitKind::Secondary, &none, 2, now), Duration::from_secs(240));
+    assert_eq!(pause_for(RateLimitKind::Secondary, &none, 10, now), Duration::from_secs(900));
+
+    let reset = (now.timestamp() + 120).to_string();
+    let mut reset_headers = HeaderMap::new();
+    reset_headers.insert("x-ratelimit-reset", HeaderValue::from_str(&reset).unwrap());
+    assert_eq!(pause_for(RateLimitKind::Primary, &reset_headers, 0, now), Duration::from_secs(120));
+    assert_eq!(pause_for(RateLimitKind::Primary, &none, 0, now), Duration::from_secs(60));
+    assert_eq!(
+        pause_for(RateLimitKind::Primary, &headers(&[("retry-after", "0")]), 0, now),
+        Duration::from_secs(1)
+    );
+}
+
+/// Test Case 60.4: Quota Headers Parsed on Any Response
+#[test]
+fn test_parse_status() {
+    let status = parse_status(&headers(&[
+        ("x-ratelimit-limit", "5000"),
+        ("x-ratelimit-remaining", "4321"),
+        ("x-ratelimit-reset", "1700000000"),
+        ("x-ratelimit-resource", "graphql"),
+    ]))
+    .unwrap();
+    assert_eq!(status.resource, "graphql");
+    assert_eq!((status.limit, status.remaining), (5000, 4321));
+    assert!(status.reset_at.starts_with("2023-11-14T22:13:20"));
+    assert!(!status.is_low());
+
+    let core = parse_status(&headers(&[
+        ("x-ratelimit-limit", "60"),
+        ("x-ratelimit-remaining", "5"),
+        ("x-ratelimit-reset", "1700000000"),
+    ]))
+    .unwrap();
+    assert_eq!(core.resource, "core");
+    assert!(core.is_low());
+
+    assert!(parse_status(&headers(&[("x-ratelimit-remaining", "5")])).is_none());
+}
+
+/// Test Case 60.5: Low Quotas Spread Requests Until the Reset
+#[test]
+fn test_delay_before_request() {
+    let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+    let reset_in = |secs: i64| now.timestamp() + secs;
+
+    let plenty = RateLimitStatus::new("core", 5000, 4000, reset_in(600)).unwrap();
+    assert_eq!(plenty.delay_before_request(now), None);
+
+    // 99 calls left for 600 seconds: one every 6 seconds
+    let low = RateLimitStatus::new("core", 5000, 99, reset_in(600)).unwrap();
+    assert_eq!(low.delay_before_request(now), Some(Duration::from_secs(6)));
+
+    // Spacing is capped so a nearly empty quota does not stall the app
+    let nearly_out = RateLimitStatus::new("core", 5000, 1, reset_in(3000)).unwrap();
+    assert_eq!(nearly_out.delay_before_request(now), Some(Duration::from_secs(10)));
+
+    // Used up: wait for a reset that is close, but not for one far off
+    let out_soon = RateLimitStatus::new("core", 5000, 0, reset_in(30)).unwrap();
+    assert_eq!(out_soon.delay_before_request(now), Some(Duration::from_secs(30)));
+    let out_long = RateLimitStatus::new("core", 5000, 0, reset_in(1800)).unwrap();
+    assert_eq!(out_long.delay_before_request(now), None);
+
+    // Once the window is over the old numbers no longer apply
+    let expired = RateLimitStatus::new("core", 5000, 0, reset_in(-5)).unwrap();
+    assert_eq!(expired.delay_before_request(now), None);
+}
+
+/// Test Case 60.6: Rate Limit Endpoint and Request Resources
+#[test]
+fn test_rate_limit_body_and_resources() {
+    let body = serde_json::json!({
+        "resources": {
+            "core": { "limit": 5000, "used": 1, "remaining": 4999, "reset": 1700000000 },
+            "search": { "limit": 30, "used": 0, "remaining": 30, "reset": 1700000060 }
+        },
+        "rate": { "limit": 5000, "remaining": 4999, "reset": 1700000000 }
+    });
+    let mut statuses = parse_rate_limit_body(&body);
+    statuses.sort_by(|a, b| a.resource.cmp(&b.resource));
+    let summary: Vec<(&str, u32)> =
+        statuses.iter().map(|s| (s.resource.as_str(), s.remaining)).collect();
+    assert_eq!(summary, vec![("core", 4999), ("search", 30)]);
+    assert!(parse_rate_limit_body(&serde_json::json!({})).is_empty());
+
+    assert_eq!(resource_for_path("/graphql"), "graphql");
+    assert_eq!(resource_for_path("/search/issues"), "search");
+    assert_eq!(resource_for_path("/search/code"), "code_search");
+    assert_eq!(resource_for_path("/repos/o/r/pulls/1"), "core");
+}
+
+/// Test Case 60.7: Throttled Requests Claim Successive Slots
+#[test]
+fn test_reserve_spaces_concurrent_requests() {
+    let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+    let mut low = RateLimitStatus::new("core", 5000, 99, now.timestamp() + 600).unwrap();
+
+    // Both requests arrive together; the second waits behind the first instead of beside it
+    let first = low.reserve(now).unwrap();
+    let second = low.reserve(now).unwrap();
+    assert_eq!(first, Duration::from_secs(6));
+    assert!(second > first + Duration::from_secs(6));
+    assert_eq!(low.remaining, 97);
+
+    // Once the slots have passed, the next request only waits its own spacing
+    let later = now + chrono::Duration::seconds(60);
+    assert!(low.reserve(later).unwrap() < Duration::from_secs(7));
+
+    let mut plenty = RateLimitStatus::new("core", 5000, 4000, now.timestamp() + 600).unwrap();
+    assert_eq!(plenty.reserve(now), None);
+    assert_eq!(plenty.remaining, 4000);
+}
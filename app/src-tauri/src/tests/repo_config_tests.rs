@@ -0,0 +1,155 @@
+// Category 39: Repository Review Config Tests (repo_config.rs)
+// Tests for parsing .github/doc-review.yml and merging it over local settings
+
+use crate::content_filter::ContentFilterConfig;
+use crate::repo_config::{self, FileFilters, RepoReviewConfig};
+use crate::review_storage::ReviewComment;
+
+const SAMPLE: &str = r#"
+# Review policy for the docs site
+files:
+  include:
+    - "docs/**"
+    - README.md
+  exclude: [docs/generated/**, "*.svg"]
+
+checklist:
+- Links checked
+- "Screenshots updated: light & dark"   # quoted, with a colon
+
+required_categories: [blocking, suggestion, nit]
+banned_phrases:
+  - simply
+  - 'it''s easy'
+"#;
+
+fn comment(id: i64, category: Option<&str>) -> ReviewComment {
+    ReviewComment {
+        id,
+        owner: "owner".to_string(),
+        repo: "repo".to_string(),
+        pr_number: 1,
+        file_path: "docs/guide.md".to_string(),
+        line_number: id as u64,
+        side: "RIGHT".to_string(),
+        body: "Comment".to_string(),
+        commit_id: "abc123".to_string(),
+        created_at: "2024-01-15T10:00:00Z".to_string(),
+        updated_at: "2024-01-15T10:00:00Z".to_string(),
+        deleted: false,
+        in_reply_to_id: None,
+        category: category.map(str::to_string),
+        general: false,
+        suggestion: None,
+    }
+}
+
+/// Test Case 39.1: Full Config Parses
+#[test]
+fn test_parse_sample_config() {
+    let config = repo_config::parse_repo_config(SAMPLE).unwrap();
+
+    assert_eq!(config.files.include, vec!["docs/**", "README.md"]);
+    assert_eq!(config.files.exclude, vec!["docs/generated/**", "*.svg"]);
+    assert_eq!(
+        config.checklist,
+        vec!["Links checked", "Screenshots updated: light & dark"]
+    );
+    assert_eq!(config.required_categories, vec!["blocking", "suggestion", "nit"]);
+    assert_eq!(config.banned_phrases, vec!["simply", "it's easy"]);
+}
+
+/// Test Case 39.2: Empty File Means No Policy
+#[test]
+fn test_parse_empty_config() {
+    let config = repo_config::parse_repo_config("# nothing yet\n").unwrap();
+    assert_eq!(config, RepoReviewConfig::default());
+}
+
+/// Test Case 39.3: Malformed and Invalid Configs Are Rejected
+#[test]
+fn test_parse_rejects_bad_config() {
+    let err = repo_config::parse_repo_config("checklist:\n\t- item\n").unwrap_err();
+    assert!(err.to_string().contains("line 2"), "{err}");
+
+    assert!(repo_config::parse_repo_config("checklist: [a]\n    extra: 1\n").is_err());
+    assert!(repo_config::parse_repo_config("required_categories: [urgent]\n").is_err());
+    assert!(repo_config::parse_repo_config("checklist:\n  nested: map\n").is_err());
+}
+
+/// Test Case 39.4: File Filters Apply Globs
+#[test]
+fn test_file_filters() {
+    let filters = FileFilters {
+        include: vec!["docs/**".to_string(), "README.md".to_string()],
+        exclude: vec!["docs/generated".to_string(), "*.svg".to_string()],
+    };
+
+    assert!(filters.matches("docs/guide.md"));
+    assert!(filters.matches("docs/api/index.md"));
+    assert!(filters.matches("README.md"));
+    assert!(filters.matches("packages/cli/README.md"));
+    assert!(!filters.matches("src/main.rs"));
+    assert!(!filters.matches("docs/generated/api.md"));
+    assert!(!filters.matches("docs/images/arch.svg"));
+
+    assert!(FileFilters::default().matches("anything/at/all.txt"));
+    assert!(repo_config::glob_matches("src/*.rs", "src/lib.rs"));
+    assert!(!repo_config::glob_matches("src/*.rs", "src/tests/mod.rs"));
+    assert!(repo_config::glob_matches("src/**/*.rs", "src/lib.rs"));
+}
+
+/// Test Case 39.5: Repo Policy Merges Over Local Settings
+#[test]
+fn test_merge_over_local() {
+    let local = ContentFilterConfig {
+        banned_phrases: vec!["Simply".to_string(), "obviously".to_string()],
+        ..ContentFilterConfig::default()
+    };
+
+    let without_repo = repo_config::merge(local.clone(), None);
+    assert!(without_repo.source.is_none());
+    assert_eq!(without_repo.content_filter, local);
+
+    let repo = repo_config::parse_repo_config(SAMPLE).unwrap();
+    let merged = repo_config::merge(local, Some(&repo));
+    assert_eq!(merged.source.as_deref(), Some(repo_config::REPO_CONFIG_PATH));
+    assert_eq!(
+        merged.content_filter.banned_phrases,
+        vec!["Simply", "obviously", "it's easy"]
+    );
+    assert_eq!(merged.checklist.len(), 2);
+    assert_eq!(merged.files, repo.files);
+}
+
+/// Test Case 39.6: Comments Without a Required Category Are Reported
+#[test]
+fn test_comments_missing_category() {
+    let comments = vec![
+        comment(1, Some("blocking")),
+        comment(2, None),
+        comment(3, Some("praise")),
+        ReviewComment {
+            deleted: true,
+            ..comment(4, None)
+        },
+    ];
+    let required = vec!["blocking".to_string(), "nit".to_string()];
+
+    let missing: Vec<i64> = repo_config::comments_missing_category(&required, &comments)
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(missing, vec![2, 3]);
+    assert!(repo_config::comments_missing_category(&[], &comments).is_empty());
+}
+
+/// Test Case 39.7: Full YAML Syntax Is Accepted
+#[test]
+fn test_parse_full_yaml() {
+    let text = "checklist:\n  - &links Links checked\n  - >-\n    Screenshots\n    updated\n\
+                banned_phrases: [*links]\n";
+    let config = repo_config::parse_repo_config(text).unwrap();
+    assert_eq!(config.checklist, vec!["Links checked", "Screenshots updated"]);
+    assert_eq!(config.banned_phrases, vec!["Links checked"]);
+}
@@ -0,0 +1,113 @@
+// Category 61: App Data Reset Tests (reset.rs)
+// Tests for granular removal of tokens, caches, local reviews, logs and settings
+
+use crate::confirmations::{ConfirmationStore, DestructiveAction};
+use crate::reset::{reset_app_data, ResetOptions};
+use crate::review_storage::ReviewStorage;
+use std::time::Duration;
+use tempfile::TempDir;
+use super::create_test_storage;
+
+async fn storage_with_data() -> (ReviewStorage, TempDir) {
+    let (storage, temp_dir) = create_test_storage();
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage
+        .add_comment("owner", "repo", 1, "src/lib.rs", 3, "RIGHT", "Nit", "commit1", None)
+        .await
+        .unwrap();
+    storage.record_visit("owner", "repo", 1, "2024-03-01T10:00:00+00:00").unwrap();
+    storage.set_setting("preferences", "{}").unwrap();
+    std::fs::write(temp_dir.path().join("review_logs").join("crash.log"), "boom").unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("recordings")).unwrap();
+    std::fs::write(temp_dir.path().join("recordings").join("session.json"), "{}").unwrap();
+    (storage, temp_dir)
+}
+
+/// Test Case 61.1: Presets and Descriptions
+#[test]
+fn test_reset_presets() {
+    assert!(ResetOptions::default().is_empty());
+    assert!(!ResetOptions::keep_drafts().local_reviews);
+    assert!(ResetOptions::keep_drafts().describe().contains("keeping local reviews"));
+    assert!(ResetOptions::everything().describe().starts_with("Remove all traces"));
+
+    let custom = ResetOptions {
+        logs: true,
+        settings: true,
+        ..ResetOptions::default()
+    };
+    assert_eq!(custom.describe(), "Reset app data: review logs and request recordings, settings");
+
+    let parsed: ResetOptions = serde_json::from_str(r#"{"cached_data":true}"#).unwrap();
+    assert_eq!(
+        parsed,
+        ResetOptions {
+            cached_data: true,
+            ..ResetOptions::default()
+        }
+    );
+}
+
+/// Test Case 61.2: Clearing Caches Keeps Drafts
+#[tokio::test]
+async fn test_reset_cached_data_keeps_drafts() {
+    let (storage, temp) = storage_with_data().await;
+    let options = ResetOptions {
+        cached_data: true,
+        ..ResetOptions::default()
+    };
+    reset_app_data(&storage, temp.path(), &options).await.unwrap();
+
+    assert_eq!(storage.get_last_visit("owner", "repo", 1).unwrap(), None);
+    assert_eq!(storage.get_comments("owner", "repo", 1).unwrap().len(), 1);
+    assert!(storage.get_setting("preferences").unwrap().is_some());
+    assert!(temp.path().join("review_logs").join("crash.log").exists());
+}
+
+/// Test Case 61.3: Local Reviews, Logs and Settings Are Removed When Chosen
+#[tokio::test]
+async fn test_reset_local_data() {
+    let (storage, temp) = storage_with_data().await;
+    let options = ResetOptions {
+        local_reviews: true,
+        logs: true,
+        settings: true,
+        ..ResetOptions::default()
+    };
+    reset_app_data(&storage, temp.path(), &options).await.unwrap();
+
+    assert!(storage.get_review_metadata("owner", "repo", 1).unwrap().is_none());
+    assert!(storage.get_comments("owner", "repo", 1).unwrap().is_empty());
+    assert!(storage.get_setting("preferences").unwrap().is_none());
+    assert!(!temp.path().join("review_logs").join("crash.log").exists());
+    assert!(temp.path().join("review_logs").is_dir());
+    assert!(!temp.path().join("recordings").exists());
+    assert!(storage.get_last_visit("owner", "repo", 1).unwrap().is_some());
+}
+
+/// Test Case 61.4: Empty Options Are Rejected
+#[tokio::test]
+async fn test_reset_requires_a_choice() {
+    let (storage, temp) = storage_with_data().await;
+    assert!(reset_app_data(&storage, temp.path(), &ResetOptions::default())
+        .await
+        .is_err());
+    assert_eq!(storage.get_comments("owner", "repo", 1).unwrap().len(), 1);
+}
+
+/// Test Case 61.5: A Confirmation Covers Only the Options It Was Issued For
+#[test]
+fn test_reset_confirmation_is_bound_to_options() {
+    let store = ConfirmationStore::new(Duration::from_secs(60));
+    let keep = DestructiveAction::ResetAppData {
+        options: ResetOptions::keep_drafts(),
+    };
+    let everything = DestructiveAction::ResetAppData {
+        options: ResetOptions::everything(),
+    };
+
+    let issued = store.issue(keep.clone(), keep.describe(None)).unwrap();
+    assert!(store.redeem(&issued.token, &everything).is_err());
+    let issued = store.issue(keep.clone(), keep.describe(None)).unwrap();
+    assert!(store.redeem(&issued.token, &keep).is_ok());
+}
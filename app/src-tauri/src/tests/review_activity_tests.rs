@@ -0,0 +1,95 @@
+// Category 68: Review Activity Export Tests (review_activity.rs)
+// Tests for exporting my reviews and comments across an org as CSV or JSON
+
+use std::path::Path;
+
+use crate::review_activity::{
+    export_file_name, export_summary, normalize_since, render_export, search_query, sort_records,
+    to_csv, ActivityExportFormat, ActivityKind, ActivityRecord,
+};
+
+fn record(kind: ActivityKind, created_at: &str, body: &str) -> ActivityRecord {
+    ActivityRecord {
+        kind,
+        owner: "octo".to_string(),
+        repo: "app".to_string(),
+        pr_number: 7,
+        pr_title: "Add login".to_string(),
+        state: (kind == ActivityKind::Review).then(|| "APPROVED".to_string()),
+        path: None,
+        line: None,
+        url: "https://github.com/octo/app/pull/7".to_string(),
+        created_at: created_at.to_string(),
+        body: body.to_string(),
+    }
+}
+
+/// Test Case 68.1: Dates and Timestamps Normalize to UTC
+#[test]
+fn test_normalize_since() {
+    assert_eq!(normalize_since("2024-05-01").unwrap(), "2024-05-01T00:00:00Z");
+    assert_eq!(normalize_since("2024-05-01T02:00:00+02:00").unwrap(), "2024-05-01T00:00:00Z");
+    assert!(normalize_since("last month").is_err());
+}
+
+/// Test Case 68.2: Search Covers PRs I Reviewed in the Org
+#[test]
+fn test_search_query() {
+    assert_eq!(
+        search_query("octo", "2024-05-01T00:00:00Z"),
+        "is:pr org:octo reviewed-by:@me updated:>=2024-05-01T00:00:00Z"
+    );
+}
+
+/// Test Case 68.3: CSV Quotes Commas, Quotes and Newlines
+#[test]
+fn test_to_csv() {
+    let mut comment = record(ActivityKind::Comment, "2024-05-02T00:00:00Z", "Use \"semver\",\nplease");
+    comment.path = Some("src/lib.rs".to_string());
+    comment.line = Some(12);
+    let csv = to_csv(&[record(ActivityKind::Review, "2024-05-01T00:00:00Z", "LGTM"), comment]);
+
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("kind,owner,repo,pr_number,pr_title,state,path,line,url,created_at,body")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("review,octo,app,7,Add login,APPROVED,,,https://github.com/octo/app/pull/7,2024-05-01T00:00:00Z,LGTM")
+    );
+    assert!(csv.contains(",src/lib.rs,12,"));
+    assert!(csv.ends_with(",\"Use \"\"semver\"\",\nplease\"\n"));
+}
+
+/// Test Case 68.4: Records Sort Oldest First
+#[test]
+fn test_sort_records() {
+    let mut records = vec![
+        record(ActivityKind::Comment, "2024-05-03T00:00:00Z", "b"),
+        record(ActivityKind::Review, "2024-05-01T00:00:00Z", "a"),
+    ];
+    sort_records(&mut records);
+    assert_eq!(records[0].body, "a");
+}
+
+/// Test Case 68.5: Exports Render in the Chosen Format and Counts Are Reported
+#[test]
+fn test_render_export() {
+    let records = vec![
+        record(ActivityKind::Review, "2024-05-01T00:00:00Z", "LGTM"),
+        record(ActivityKind::Comment, "2024-05-02T00:00:00Z", "nit"),
+    ];
+
+    let csv = render_export(&records, ActivityExportFormat::Csv).unwrap();
+    assert!(csv.starts_with("kind,"));
+    assert_eq!(export_file_name("octo", ActivityExportFormat::Csv), "octo-review-activity.csv");
+
+    let json = render_export(&records, ActivityExportFormat::Json).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed[1]["kind"], "comment");
+
+    let summary = export_summary(&records, Path::new("/tmp/octo-review-activity.json"));
+    assert_eq!((summary.reviews, summary.comments), (1, 1));
+    assert!(summary.path.ends_with("octo-review-activity.json"));
+}
@@ -0,0 +1,185 @@
+// Category 89: Review Import Tests (review_import.rs)
+// Tests for adding comments from a JSON review file or a punch list to a local review
+
+use crate::models::PullRequestFile;
+use crate::review_import::{
+    import_comments, parse_punch_list, parse_review_json, validate_comments, PunchListFormat,
+};
+use super::create_test_storage;
+
+fn file(path: &str) -> PullRequestFile {
+    PullRequestFile {
+        path: path.to_string(),
+        status: "modified".to_string(),
+        additions: 1,
+        deletions: 0,
+        patch: None,
+        head_content: None,
+        base_content: None,
+        language: "markdown".to_string(),
+        previous_filename: None,
+        localized_pair: None,
+    }
+}
+
+/// Test Case 89.1: Exported Reviews and Minimal Script Output Both Parse
+#[test]
+fn test_parse_review_json() {
+    let exported = r#"{
+        "exported_at": "2024-01-01T00:00:00Z",
+        "review": {"owner": "o", "repo": "r", "pr_number": 1},
+        "comments": [{
+            "id": 7, "owner": "o", "repo": "r", "pr_number": 1, "file_path": "docs/a.md",
+            "line_number": 3, "side": "LEFT", "body": "Typo", "commit_id": "abc",
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z",
+            "deleted": false, "in_reply_to_id": null, "category": "nit", "general": false,
+            "suggestion": null
+        }]
+    }"#;
+    let comments = parse_review_json(exported).unwrap();
+    assert_eq!(comments[0].id, Some(7));
+    assert_eq!(comments[0].side, "LEFT");
+    assert_eq!(comments[0].category.as_deref(), Some("nit"));
+
+    let minimal = r#"{"comments": [{"file_path": "docs/a.md", "body": "Needs an example"}]}"#;
+    let comments = parse_review_json(minimal).unwrap();
+    assert_eq!(comments[0].side, "RIGHT");
+    assert_eq!(comments[0].line_number, 0);
+    assert_eq!(comments[0].commit_id, None);
+
+    assert!(parse_review_json(r#"{"review": {}}"#).is_err());
+}
+
+/// Test Case 89.2: Comments on Files Outside the PR Are Rejected Together
+#[test]
+fn test_validate_comments() {
+    let files = vec![file("docs/a.md")];
+    let json = r#"{"comments": [
+        {"file_path": "docs/a.md", "body": "Fine"},
+        {"file_path": "docs/z.md", "body": "Elsewhere"},
+        {"file_path": "docs/b.md", "body": "Also elsewhere"},
+        {"file_path": "docs/z.md", "body": "General", "general": true}
+    ]}"#;
+    let comments = parse_review_json(json).unwrap();
+
+    let err = validate_comments(&comments, &files).unwrap_err().to_string();
+    assert!(err.contains("docs/b.md, docs/z.md"));
+
+    assert!(validate_comments(&comments[..1], &files).is_ok());
+    assert!(validate_comments(&comments[3..], &files).is_ok());
+    assert!(validate_comments(&[], &files).is_err());
+
+    let bad_side = parse_review_json(r#"{"comments": [
+        {"file_path": "docs/a.md", "body": "x", "side": "BOTH"}
+    ]}"#)
+    .unwrap();
+    assert!(validate_comments(&bad_side, &files).is_err());
+}
+
+/// Test Case 89.3: Imported Comments Join the Local Review With Threads Rebuilt
+#[tokio::test]
+async fn test_import_comments() {
+    let (storage, _temp) = create_test_storage();
+    let json = r#"{"comments": [
+        {"id": 2, "file_path": "docs/a.md", "line_number": 4, "body": "Agreed",
+         "in_reply_to_id": 1},
+        {"id": 1, "file_path": "docs/a.md", "line_number": 4, "body": "Reword", "category": "nit"},
+        {"file_path": "docs/a.md", "body": "Overall good", "general": true, "commit_id": "old"}
+    ]}"#;
+    let comments = parse_review_json(json).unwrap();
+
+    let result = import_comments(&storage, "o", "r", 1, "head", &comments).await.unwrap();
+    assert_eq!(result.imported, 3);
+    assert_eq!(result.review.commit_id, "head");
+
+    let stored = storage.get_comments("o", "r", 1).unwrap();
+    let parent = stored.iter().find(|c| c.body == "Reword").unwrap();
+    let reply = stored.iter().find(|c| c.body == "Agreed").unwrap();
+    let general = stored.iter().find(|c| c.body == "Overall good").unwrap();
+    assert_eq!(parent.category.as_deref(), Some("nit"));
+    assert_eq!(parent.commit_id, "head");
+    assert_eq!(reply.in_reply_to_id, Some(parent.id));
+    assert!(general.general);
+    assert_eq!(general.commit_id, "old");
+}
+
+/// Test Case 89.4: CSV Punch Lists With or Without a Header Row
+#[test]
+fn test_parse_csv_punch_list() {
+    let plain = "docs/a.md,12,Typo\r\ndocs/b.md,,\"Say \"\"hi\"\", then\nwave\"\r\n";
+    let comments = parse_punch_list(plain, PunchListFormat::Csv).unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!((comments[0].file_path.as_str(), comments[0].line_number), ("docs/a.md", 12));
+    assert_eq!(comments[1].line_number, 0);
+    assert_eq!(comments[1].body, "Say \"hi\", then\nwave");
+
+    let headed = "\u{feff}Comment,Line,File,Category\n'=SUM(A1),L3,docs/a.md,nit\n\n";
+    let comments = parse_punch_list(headed, PunchListFormat::Csv).unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].file_path, "docs/a.md");
+    assert_eq!(comments[0].line_number, 3);
+    assert_eq!(comments[0].body, "=SUM(A1)");
+    assert_eq!(comments[0].category.as_deref(), Some("nit"));
+
+    assert!(parse_punch_list("docs/a.md,twelve,Typo", PunchListFormat::Csv).is_err());
+    assert!(parse_punch_list("docs/a.md,1,\"open", PunchListFormat::Csv).is_err());
+}
+
+/// Test Case 89.5: Markdown Punch Lists Import Open Items With Their Files
+#[test]
+fn test_parse_markdown_punch_list() {
+    let list = "\
+## Review meeting
+
+- [ ] docs/a.md:12 — Typo in the intro
+- [x] docs/a.md:14 Already fixed
+- [ ] `docs/c d.md:3`: Spaces in the path
+  second line
+
+  after a gap
+
+### `docs/b.md`
+
+- [ ] [L7](https://github.com/o/r/blob/abc/docs/b.md#L7) **nit**: Reword
+- [ ] whole file: Needs an example
+- [ ] ganze Datei: Exported in German
+- [ ] ファイル全体: Exported in Japanese
+";
+    let comments = parse_punch_list(list, PunchListFormat::Markdown).unwrap();
+    let summary: Vec<(&str, u64, &str)> = comments
+        .iter()
+        .map(|c| (c.file_path.as_str(), c.line_number, c.body.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("docs/a.md", 12, "Typo in the intro"),
+            ("docs/c d.md", 3, "Spaces in the path\nsecond line\n\nafter a gap"),
+            ("docs/b.md", 7, "Reword"),
+            ("docs/b.md", 0, "Needs an example"),
+            ("docs/b.md", 0, "Exported in German"),
+            ("docs/b.md", 0, "Exported in Japanese"),
+        ]
+    );
+    assert_eq!(comments[2].category.as_deref(), Some("nit"));
+
+    assert!(parse_punch_list("- [ ] docs/a.md:3", PunchListFormat::Markdown).is_err());
+}
+
+/// Test Case 89.6: Imported Line Comments on the New Side of Removed Files Are Rejected
+#[test]
+fn test_validate_comments_on_removed_file() {
+    let mut removed = file("docs/old.md");
+    removed.status = "removed".to_string();
+    let files = vec![removed];
+    let comments = parse_review_json(r#"{"comments": [
+        {"file_path": "docs/old.md", "line_number": 2, "side": "LEFT", "body": "Base side"},
+        {"file_path": "docs/old.md", "body": "Whole file"},
+        {"file_path": "docs/old.md", "line_number": 2, "body": "New side"}
+    ]}"#)
+    .unwrap();
+
+    assert!(validate_comments(&comments[..2], &files).is_ok());
+    let err = validate_comments(&comments, &files).unwrap_err().to_string();
+    assert!(err.contains("docs/old.md"));
+}
@@ -0,0 +1,120 @@
+// Category 91: Review Profile Tests (review_profiles.rs)
+// Tests for reviewer profiles and switching the one used on a review
+
+use crate::review_profiles::{
+    active_profile, analyzer_enabled, extend_checklist, save_config, set_active_profile,
+    ReviewProfile, ReviewProfileConfig,
+};
+use super::create_test_storage;
+
+fn profile(name: &str, analyzers: &[&str], templates: &[&str]) -> ReviewProfile {
+    ReviewProfile {
+        name: name.to_string(),
+        description: None,
+        analyzers: analyzers.iter().map(|a| a.to_string()).collect(),
+        plugins: vec!["vale".to_string()],
+        checklist: vec!["Read it aloud".to_string()],
+        templates: templates.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+/// Test Case 91.1: Default Profiles Are Valid and Found Ignoring Case
+#[test]
+fn test_default_profiles() {
+    let config = ReviewProfileConfig::default();
+
+    assert!(config.validate().is_ok());
+    let accessibility = config.find("accessibility PASS").unwrap();
+    assert!(accessibility.analyzers.contains(&"alt_text".to_string()));
+    assert!(config.find("legal pass").is_err());
+}
+
+/// Test Case 91.2: Invalid Profile Lists Are Rejected
+#[test]
+fn test_validate_profiles() {
+    let duplicate = ReviewProfileConfig {
+        profiles: vec![profile("Style", &[], &[]), profile(" style ", &[], &[])],
+    };
+    assert!(duplicate.validate().is_err());
+
+    let unnamed = ReviewProfileConfig { profiles: vec![profile("  ", &[], &[])] };
+    assert!(unnamed.validate().is_err());
+
+    let unknown_check = profile("Style", &["spelling"], &[]);
+    assert!(ReviewProfileConfig { profiles: vec![unknown_check] }.validate().is_err());
+
+    let mut empty_item = profile("Style", &["headings"], &[]);
+    empty_item.checklist.push(" ".to_string());
+    assert!(ReviewProfileConfig { profiles: vec![empty_item] }.validate().is_err());
+}
+
+/// Test Case 91.3: The Active Profile Is Chosen Per Review
+#[test]
+fn test_active_profile_per_review() {
+    let (storage, _temp) = create_test_storage();
+    storage.create_comment_template("Alt text", "Please describe this image.", None).unwrap();
+    storage.create_comment_template("Typo", "Typo on line {{line}}.", Some("nit")).unwrap();
+    let config = ReviewProfileConfig {
+        profiles: vec![
+            profile("Accessibility", &["alt_text"], &["alt text", "Contrast"]),
+            profile("Style", &["headings"], &[]),
+        ],
+    };
+    save_config(&storage, &config).unwrap();
+
+    assert!(active_profile(&storage, "owner", "repo", 1).unwrap().is_none());
+    let active = set_active_profile(&storage, "owner", "repo", 1, Some("accessibility"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(active.profile.name, "Accessibility");
+    let names: Vec<&str> = active.templates.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["Alt text"]);
+    assert_eq!(active.missing_templates, vec!["Contrast"]);
+    assert_eq!(active_profile(&storage, "owner", "repo", 1).unwrap(), Some(active));
+
+    // Another PR keeps its own choice, and a profile without templates offers them all
+    let style = set_active_profile(&storage, "owner", "repo", 2, Some("Style"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(style.templates.len(), 2);
+    let first = active_profile(&storage, "owner", "repo", 1).unwrap().unwrap();
+    assert_eq!(first.profile.name, "Accessibility");
+
+    assert!(set_active_profile(&storage, "owner", "repo", 1, Some("Legal")).is_err());
+    assert!(set_active_profile(&storage, "owner", "repo", 1, None).unwrap().is_none());
+    assert!(active_profile(&storage, "owner", "repo", 1).unwrap().is_none());
+
+    // Deleting the profile leaves the PR that used it with none
+    save_config(&storage, &ReviewProfileConfig { profiles: Vec::new() }).unwrap();
+    assert!(active_profile(&storage, "owner", "repo", 2).unwrap().is_none());
+}
+
+/// Test Case 91.4: The Active Profile Picks Checks and Checklist Until the Review Is Cleared
+#[tokio::test]
+async fn test_active_profile_gates_checks() {
+    let (storage, _temp) = create_test_storage();
+    let config = ReviewProfileConfig {
+        profiles: vec![profile("Accessibility", &["alt_text", "headings"], &[])],
+    };
+    save_config(&storage, &config).unwrap();
+
+    // Every check runs while no profile is chosen
+    assert!(analyzer_enabled(&storage, "owner", "repo", 1, "spec").unwrap());
+
+    set_active_profile(&storage, "owner", "repo", 1, Some("Accessibility")).unwrap();
+    assert!(analyzer_enabled(&storage, "owner", "repo", 1, "alt_text").unwrap());
+    assert!(!analyzer_enabled(&storage, "owner", "repo", 1, "spec").unwrap());
+
+    let mut checklist = vec!["Links work".to_string(), "read it ALOUD".to_string()];
+    extend_checklist(&storage, "owner", "repo", 1, &mut checklist).unwrap();
+    assert_eq!(checklist, vec!["Links work", "read it ALOUD"]);
+    let mut checklist = vec!["Links work".to_string()];
+    extend_checklist(&storage, "owner", "repo", 1, &mut checklist).unwrap();
+    assert_eq!(checklist, vec!["Links work", "Read it aloud"]);
+
+    // Clearing the review drops the choice with it
+    storage.start_review("owner", "repo", 1, "abc", None, None).unwrap();
+    storage.clear_review("owner", "repo", 1, None).await.unwrap();
+    assert!(active_profile(&storage, "owner", "repo", 1).unwrap().is_none());
+    assert!(analyzer_enabled(&storage, "owner", "repo", 1, "spec").unwrap());
+}
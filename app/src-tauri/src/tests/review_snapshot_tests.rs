@@ -0,0 +1,55 @@
+// Category 86: Review Snapshot Tests (review_snapshot.rs)
+// Tests for the PR context written into the log of a submitted review
+
+use crate::review_snapshot::{ReviewSnapshot, SnapshotFile};
+
+fn file(path: &str, status: &str, additions: u32, deletions: u32) -> SnapshotFile {
+    SnapshotFile {
+        path: path.to_string(),
+        status: status.to_string(),
+        additions,
+        deletions,
+        previous_filename: None,
+    }
+}
+
+pub fn sample_snapshot() -> ReviewSnapshot {
+    let mut renamed = file("docs/guide.md", "renamed", 2, 1);
+    renamed.previous_filename = Some("docs/intro.md".to_string());
+    ReviewSnapshot {
+        title: "Rework the install guide".to_string(),
+        head_sha: "abc1234def".to_string(),
+        base_sha: "9876fed".to_string(),
+        files: vec![
+            file("docs/new.md", "added", 3, 0),
+            renamed,
+            file("docs/old.md", "removed", 0, 7),
+            file("README.md", "modified", 1, 1),
+        ],
+        captured_at: "2026-10-16T12:00:00Z".to_string(),
+    }
+}
+
+/// Test Case 86.1: Diffstat Totals
+#[test]
+fn test_snapshot_totals() {
+    assert_eq!(sample_snapshot().totals(), (6, 9));
+
+    let empty = ReviewSnapshot { files: Vec::new(), ..sample_snapshot() };
+    assert_eq!(empty.totals(), (0, 0));
+}
+
+/// Test Case 86.2: Log Lines List Every File With Its Status
+#[test]
+fn test_snapshot_log_lines() {
+    let lines = sample_snapshot().log_lines();
+
+    assert!(lines.lines().all(|line| line.starts_with('#')), "{lines}");
+    assert!(lines.contains("Rework the install guide"));
+    assert!(lines.contains("abc1234def"));
+    assert!(lines.contains("9876fed"));
+    assert!(lines.contains("A docs/new.md (+3 -0)"));
+    assert!(lines.contains("R docs/intro.md -> docs/guide.md (+2 -1)"));
+    assert!(lines.contains("D docs/old.md (+0 -7)"));
+    assert!(lines.contains("M README.md (+1 -1)"));
+}
@@ -1,16 +1,7 @@
 // Category 10 & 11: Review Storage Tests (review_storage.rs)
 // Tests for SQLite storage operations and log file generation
 
-use crate::review_storage::ReviewStorage;
-use tempfile::TempDir;
-
-/// Helper to create a test storage instance with temp directory
-fn create_test_storage() -> (ReviewStorage, TempDir) {
-    let temp_dir = TempDir::new().expect("Failed to create temp directory");
-    let storage = ReviewStorage::new(temp_dir.path())
-        .expect("Failed to create storage");
-    (storage, temp_dir)
-}
+use super::create_test_storage;
 
 /// Test Case 10.1: Create New Review
 /// Insert review into database
@@ -449,3 +440,565 @@ async fn test_log_file_index() {
     // Index should increment
     assert!(meta2.log_file_index >= meta1.log_file_index);
 }
+
+/// Test Case 11.11: Cleared Review Moves to Trash
+#[tokio::test]
+async fn test_clear_review_moves_to_trash() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", Some("Body"), None).unwrap();
+    storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "First", "commit1", None).await.unwrap();
+    storage.add_comment("owner", "repo", 1, "b.md", 7, "RIGHT", "Second", "commit1", None).await.unwrap();
+    storage.start_review("owner", "repo", 2, "commit2", None, None).unwrap();
+
+    storage.clear_review("owner", "repo", 1, None).await.unwrap();
+    storage.abandon_review("owner", "repo", 2).await.unwrap();
+
+    assert!(storage.get_comments("owner", "repo", 1).unwrap().is_empty());
+    let trashed = storage.list_trashed_reviews().unwrap();
+    let summary: Vec<(u64, &str, u64)> = trashed
+        .iter()
+        .map(|t| (t.pr_number, t.reason.as_str(), t.comment_count))
+        .collect();
+    assert!(summary.contains(&(1, "cleared", 2)));
+    assert!(summary.contains(&(2, "abandoned", 0)));
+}
+
+/// Test Case 11.12: Restore Trashed Review With Comments
+#[tokio::test]
+async fn test_restore_trashed_review() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", Some("Body"), None).unwrap();
+    let parent = storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "First", "commit1", None).await.unwrap();
+    storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "Reply", "commit1", Some(parent.id)).await.unwrap();
+    storage.clear_review("owner", "repo", 1, None).await.unwrap();
+
+    let restored = storage.restore_trashed_review("owner", "repo", 1).unwrap();
+    assert_eq!(restored.body.as_deref(), Some("Body"));
+    assert!(storage.get_review_metadata("owner", "repo", 1).unwrap().is_some());
+
+    let comments = storage.get_comments("owner", "repo", 1).unwrap();
+    assert_eq!(comments.len(), 2);
+    assert!(comments.iter().any(|c| c.id == parent.id && c.body == "First"));
+    assert!(comments.iter().any(|c| c.in_reply_to_id == Some(parent.id)));
+    assert!(storage.list_trashed_reviews().unwrap().is_empty());
+
+    // Nothing left to restore
+    assert!(storage.restore_trashed_review("owner", "repo", 1).is_err());
+}
+
+/// Test Case 11.13: Restore Refuses to Overwrite a Review in Progress
+#[tokio::test]
+async fn test_restore_refuses_when_review_in_progress() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage.clear_review("owner", "repo", 1, None).await.unwrap();
+    storage.start_review("owner", "repo", 1, "commit2", None, None).unwrap();
+
+    assert!(storage.restore_trashed_review("owner", "repo", 1).is_err());
+    assert_eq!(storage.list_trashed_reviews().unwrap().len(), 1);
+}
+
+/// Test Case 11.14: Purge Removes Reviews Trashed Before the Cutoff
+#[tokio::test]
+async fn test_purge_trash() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "First", "commit1", None).await.unwrap();
+    storage.clear_review("owner", "repo", 1, None).await.unwrap();
+
+    assert_eq!(storage.purge_trash("2000-01-01T00:00:00+00:00").unwrap(), 0);
+    assert_eq!(storage.purge_trash("9999-01-01T00:00:00+00:00").unwrap(), 1);
+    assert!(storage.list_trashed_reviews().unwrap().is_empty());
+    assert!(storage.restore_trashed_review("owner", "repo", 1).is_err());
+}
+
+/// Test Case 11.15: Bookmarks Are Listed per PR and Notes Update in Place
+#[test]
+fn test_bookmarks() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.add_bookmark("owner", "repo", 1, "github", 9001, Some("follow up")).unwrap();
+    storage.add_bookmark("owner", "repo", 1, "local", 4, None).unwrap();
+    storage.add_bookmark("owner", "repo", 2, "github", 9002, None).unwrap();
+    let updated = storage.add_bookmark("owner", "repo", 1, "github", 9001, Some("  done?  ")).unwrap();
+    assert_eq!(updated.note.as_deref(), Some("done?"));
+
+    let bookmarks = storage.get_bookmarks("owner", "repo", 1).unwrap();
+    assert_eq!(bookmarks.len(), 2);
+    assert!(bookmarks.iter().any(|b| b.source == "local" && b.comment_id == 4));
+
+    assert!(storage.add_bookmark("owner", "repo", 1, "slack", 1, None).is_err());
+    assert!(storage.remove_bookmark("owner", "repo", 1, "github", 9001).unwrap());
+    assert!(!storage.remove_bookmark("owner", "repo", 1, "github", 9001).unwrap());
+    assert_eq!(storage.get_bookmarks("owner", "repo", 1).unwrap().len(), 1);
+}
+
+/// Test Case 11.16: Deleting a Local Comment Drops Its Bookmark
+#[tokio::test]
+async fn test_delete_comment_removes_bookmark() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    let comment = storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "Pin me", "commit1", None).await.unwrap();
+    storage.add_bookmark("owner", "repo", 1, "local", comment.id, None).unwrap();
+    storage.add_bookmark("owner", "repo", 1, "github", comment.id, None).unwrap();
+
+    storage.delete_comment(comment.id).await.unwrap();
+
+    let bookmarks = storage.get_bookmarks("owner", "repo", 1).unwrap();
+    assert_eq!(bookmarks.len(), 1);
+    assert_eq!(bookmarks[0].source, "github");
+}
+
+/// Test Case 11.17: Last Visits Are Stored per PR
+#[test]
+fn test_record_visit() {
+    let (storage, _temp) = create_test_storage();
+
+    assert_eq!(storage.get_last_visit("owner", "repo", 1).unwrap(), None);
+    storage.record_visit("owner", "repo", 1, "2024-03-01T10:00:00+00:00").unwrap();
+    storage.record_visit("owner", "repo", 1, "2024-03-02T10:00:00+00:00").unwrap();
+    storage.record_visit("owner", "repo", 2, "2024-03-01T10:00:00+00:00").unwrap();
+    storage.record_visit("owner", "other", 3, "2024-03-01T10:00:00+00:00").unwrap();
+
+    assert_eq!(
+        storage.get_last_visit("owner", "repo", 1).unwrap().as_deref(),
+        Some("2024-03-02T10:00:00+00:00")
+    );
+    let visits = storage.get_last_visits("owner", "repo").unwrap();
+    assert_eq!(visits.len(), 2);
+    assert_eq!(visits.get(&2).map(String::as_str), Some("2024-03-01T10:00:00+00:00"));
+}
+
+/// Test Case 11.18: Due Date Is Stored and Survives the Trash
+#[tokio::test]
+async fn test_review_due_date() {
+    let (storage, _temp) = create_test_storage();
+
+    assert!(storage.set_review_due_date("owner", "repo", 1, Some("2024-06-12T23:59:59+00:00")).is_err());
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    let metadata = storage
+        .set_review_due_date("owner", "repo", 1, Some("2024-06-12T23:59:59+00:00"))
+        .unwrap();
+    assert_eq!(metadata.due_at.as_deref(), Some("2024-06-12T23:59:59+00:00"));
+
+    storage.clear_review("owner", "repo", 1, None).await.unwrap();
+    let restored = storage.restore_trashed_review("owner", "repo", 1).unwrap();
+    assert_eq!(restored.due_at.as_deref(), Some("2024-06-12T23:59:59+00:00"));
+
+    let cleared = storage.set_review_due_date("owner", "repo", 1, None).unwrap();
+    assert_eq!(cleared.due_at, None);
+}
+
+/// Test Case 11.19: Parked Submissions Round-Trip and Replace per PR
+#[test]
+fn test_parked_submissions() {
+    use crate::comment_export::SubmissionMode;
+    use crate::review_storage::ParkedSubmission;
+
+    let (storage, _temp) = create_test_storage();
+    let local = ParkedSubmission {
+        owner: "owner".into(),
+        repo: "repo".into(),
+        pr_number: 1,
+        review_id: None,
+        event: Some("COMMENT".into()),
+        body: None,
+        mode: Some(SubmissionMode::Consolidated),
+        login: "octocat".into(),
+        parked_at: "2024-03-01T10:00:00+00:00".into(),
+    };
+    let pending = ParkedSubmission {
+        pr_number: 2,
+        review_id: Some(77),
+        event: None,
+        body: Some("Looks good".into()),
+        mode: None,
+        login: "hubot".into(),
+        parked_at: "2024-03-01T09:00:00+00:00".into(),
+        ..local.clone()
+    };
+    storage.park_submission(&local).unwrap();
+    storage.park_submission(&pending).unwrap();
+
+    assert_eq!(storage.get_parked_submissions().unwrap(), vec![pending.clone(), local.clone()]);
+
+    let replaced = ParkedSubmission {
+        event: Some("APPROVE".into()),
+        parked_at: "2024-03-02T10:00:00+00:00".into(),
+        ..local.clone()
+    };
+    storage.park_submission(&replaced).unwrap();
+    assert_eq!(storage.get_parked_submissions().unwrap(), vec![pending, replaced]);
+
+    assert!(storage.remove_parked_submission("owner", "repo", 1).unwrap());
+    assert!(!storage.remove_parked_submission("owner", "repo", 1).unwrap());
+    assert_eq!(storage.get_parked_submissions().unwrap().len(), 1);
+}
+
+/// Test Case 11.20: Deleted GitHub Pending Review Is Archived and Restorable
+#[test]
+fn test_archive_pending_review() {
+    use crate::models::PullRequestComment;
+
+    let (storage, _temp) = create_test_storage();
+    let draft = |id: u64, line: Option<u64>, in_reply_to_id: Option<u64>, created_at: &str| {
+        PullRequestComment {
+            id,
+            body: format!("draft {id}"),
+            author: "me".into(),
+            created_at: created_at.into(),
+            display_time: String::new(),
+            url: String::new(),
+            path: Some("src/lib.rs".into()),
+            line,
+            side: Some("LEFT".into()),
+            is_review_comment: true,
+            is_draft: true,
+            state: Some("PENDING".into()),
+            is_mine: true,
+            review_id: Some(9),
+            in_reply_to_id,
+            outdated: None,
+            new_since_last_visit: false,
+            reactions: Default::default(),
+        }
+    };
+    let comments = vec![
+        draft(3_000_000_001, Some(12), None, "2024-05-01T10:00:00Z"),
+        draft(3_000_000_002, None, Some(3_000_000_001), "2024-04-30T10:00:00Z"),
+    ];
+
+    storage
+        .archive_pending_review("owner", "repo", 4, "headsha", &comments)
+        .unwrap();
+    let trashed = storage.list_trashed_reviews().unwrap();
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(trashed[0].reason, "deleted_on_github");
+    assert_eq!(trashed[0].comment_count, 2);
+    assert_eq!(trashed[0].created_at, "2024-04-30T10:00:00Z");
+
+    let restored = storage.restore_trashed_review("owner", "repo", 4).unwrap();
+    assert_eq!(restored.commit_id, "headsha");
+    let local = storage.get_comments("owner", "repo", 4).unwrap();
+    assert_eq!(local.len(), 2);
+    // Restored under fresh local ids, with the reply pointing at its parent's
+    assert!(local.iter().all(|c| c.id < 3_000_000_000));
+    let parent = local.iter().find(|c| c.body == "draft 3000000001").unwrap();
+    let reply = local.iter().find(|c| c.body == "draft 3000000002").unwrap();
+    assert_eq!(reply.line_number, 0);
+    assert_eq!(reply.side, "LEFT");
+    assert_eq!(reply.in_reply_to_id, Some(parent.id));
+    assert_eq!(reply.commit_id, "headsha");
+}
+
+/// Test Case 11.21: Pending Comments Are Counted per PR
+#[tokio::test]
+async fn test_pending_comment_counts() {
+    let (storage, _temp) = create_test_storage();
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage.start_review("owner", "repo", 2, "commit1", None, None).unwrap();
+    storage.start_review("owner", "other", 1, "commit1", None, None).unwrap();
+    storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "One", "commit1", None).await.unwrap();
+    storage.add_comment("owner", "repo", 1, "a.md", 5, "RIGHT", "Two", "commit1", None).await.unwrap();
+    let deleted = storage.add_comment("owner", "repo", 2, "b.md", 1, "RIGHT", "Gone", "commit1", None).await.unwrap();
+    storage.add_comment("owner", "other", 1, "c.md", 1, "RIGHT", "Elsewhere", "commit1", None).await.unwrap();
+    storage.delete_comment(deleted.id).await.unwrap();
+
+    let counts = storage.get_pending_comment_counts("owner", "repo").unwrap();
+    assert_eq!(counts.get(&1), Some(&2));
+    assert_eq!(counts.get(&2), None);
+    assert_eq!(counts.len(), 1);
+}
+
+/// Test Case 11.22: Pending Comments Are Counted for Every Local Review
+#[tokio::test]
+async fn test_all_pending_comment_counts() {
+    let (storage, _temp) = create_test_storage();
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage.start_review("owner", "other", 1, "commit1", None, None).unwrap();
+    storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "One", "commit1", None).await.unwrap();
+    storage.add_comment("owner", "other", 1, "c.md", 1, "RIGHT", "Two", "commit1", None).await.unwrap();
+    storage.add_comment("owner", "other", 1, "c.md", 2, "RIGHT", "Three", "commit1", None).await.unwrap();
+
+    let counts = storage.get_all_pending_comment_counts().unwrap();
+    assert_eq!(counts.get(&("owner".to_string(), "repo".to_string(), 1)), Some(&1));
+    assert_eq!(counts.get(&("owner".to_string(), "other".to_string(), 1)), Some(&2));
+}
+
+/// Test Case 11.23: Local Folders Keep Their Number
+#[test]
+fn test_local_folder_number_is_stable() {
+    let (storage, _temp) = create_test_storage();
+    let first = storage.local_folder_number("/docs/a", "/docs/a", 1234).unwrap();
+    assert_eq!(first, 1234);
+    assert_eq!(storage.local_folder_number("/docs/a", "/docs/a", 999).unwrap(), 1234);
+}
+
+/// Test Case 11.24: Colliding Local Folders Get Different Numbers
+#[test]
+fn test_local_folder_number_collision() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(storage.local_folder_number("/docs/a", "/docs/a", 1234).unwrap(), 1234);
+    assert_eq!(storage.local_folder_number("/docs/b", "/docs/b", 1234).unwrap(), 1235);
+    let last = i32::MAX as u64;
+    assert_eq!(storage.local_folder_number("/docs/c", "/docs/c", last).unwrap(), last);
+    assert_eq!(storage.local_folder_number("/docs/d", "/docs/d", last).unwrap(), 2);
+}
+
+/// Test Case 11.25: Folders Reviewed Before Keep Number 1
+#[test]
+fn test_local_folder_number_legacy() {
+    let (storage, _temp) = create_test_storage();
+    storage
+        .start_review("__local__", "local", 1, "LOCAL-abc", None, Some("/docs/old"))
+        .unwrap();
+    // They recorded the folder as it was entered, not its canonical path
+    assert_eq!(storage.local_folder_number("/home/me/docs/old", "/docs/old", 1234).unwrap(), 1);
+    assert_eq!(storage.local_folder_number("/docs/new", "/docs/new", 1).unwrap(), 2);
+}
+
+/// Test Case 11.26: Suggestions Are Stored With Their Comment
+#[tokio::test]
+async fn test_comment_suggestion() {
+    let (storage, _temp) = create_test_storage();
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    let comment = storage
+        .add_comment("owner", "repo", 1, "a.md", 4, "RIGHT", "Reword", "commit1", None)
+        .await
+        .unwrap();
+    let suggestion = crate::models::Suggestion {
+        replacement: "Better text.".to_string(),
+        start_line: 3,
+        end_line: 4,
+    };
+
+    storage.set_comment_suggestion(comment.id, Some(&suggestion)).await.unwrap();
+    let stored = storage.get_comment(comment.id).unwrap().unwrap();
+    assert_eq!(stored.suggestion, Some(suggestion.clone()));
+
+    let backwards = crate::models::Suggestion { start_line: 5, ..suggestion.clone() };
+    assert!(storage.set_comment_suggestion(comment.id, Some(&backwards)).await.is_err());
+
+    storage.set_comment_suggestion(comment.id, None).await.unwrap();
+    assert_eq!(storage.get_comment(comment.id).unwrap().unwrap().suggestion, None);
+
+    // The base side cannot be changed by a suggestion
+    let removed = storage
+        .add_comment("owner", "repo", 1, "a.md", 4, "LEFT", "Why?", "commit1", None)
+        .await
+        .unwrap();
+    assert!(storage.set_comment_suggestion(removed.id, Some(&suggestion)).await.is_err());
+    assert_eq!(storage.get_comment(removed.id).unwrap().unwrap().suggestion, None);
+}
+
+/// Test Case 11.27: The Commit of My Last Review Is Remembered
+#[test]
+fn test_review_commit() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(storage.get_review_commit("owner", "repo", 1).unwrap(), None);
+
+    storage
+        .record_review_commit("owner", "repo", 1, "aaa", "2024-05-01T10:00:00+00:00")
+        .unwrap();
+    storage
+        .record_review_commit("owner", "repo", 1, "bbb", "2024-05-02T10:00:00+00:00")
+        .unwrap();
+    let commit = storage.get_review_commit("owner", "repo", 1).unwrap().unwrap();
+    assert_eq!(commit.commit_id, "bbb");
+    assert_eq!(commit.submitted_at, "2024-05-02T10:00:00+00:00");
+    assert_eq!(storage.get_review_commit("owner", "repo", 2).unwrap(), None);
+}
+
+/// Test Case 11.28: Hunk Review Progress Per File
+#[test]
+fn test_hunk_review_progress() {
+    let (storage, _temp) = create_test_storage();
+
+    let progress = storage
+        .mark_hunk_reviewed("owner", "repo", 1, "docs/ref.md", "@@ -1,5 +1,7 @@", 4, true)
+        .unwrap();
+    assert_eq!((progress.reviewed_hunks, progress.hunk_count, progress.percent), (1, 4, 25));
+    // Marking the same hunk twice counts it once
+    storage
+        .mark_hunk_reviewed("owner", "repo", 1, "docs/ref.md", "@@ -1,5 +1,7 @@", 4, true)
+        .unwrap();
+    for hunk in ["b", "c", "d"] {
+        storage
+            .mark_hunk_reviewed("owner", "repo", 1, "docs/ref.md", hunk, 4, true)
+            .unwrap();
+    }
+    storage
+        .mark_hunk_reviewed("owner", "repo", 1, "README.md", "a", 3, true)
+        .unwrap();
+
+    let progress = storage.get_hunk_progress("owner", "repo", 1).unwrap();
+    assert_eq!(progress.len(), 2);
+    assert_eq!(progress[0].file_path, "README.md");
+    assert_eq!(progress[0].percent, 33);
+    assert_eq!(progress[1].percent, 100);
+
+    let files = storage.get_all_hunk_reviewed_files().unwrap();
+    assert_eq!(
+        files.get(&("owner".to_string(), "repo".to_string(), 1)),
+        Some(&vec!["docs/ref.md".to_string()])
+    );
+
+    // Clearing a mark, then a changed diff, which drops the earlier marks
+    let progress = storage
+        .mark_hunk_reviewed("owner", "repo", 1, "docs/ref.md", "b", 4, false)
+        .unwrap();
+    assert_eq!(progress.reviewed_hunks, 3);
+    let progress = storage
+        .mark_hunk_reviewed("owner", "repo", 1, "docs/ref.md", "e", 5, true)
+        .unwrap();
+    assert_eq!((progress.reviewed_hunks, progress.hunk_count), (1, 5));
+    assert!(storage.get_all_hunk_reviewed_files().unwrap().is_empty());
+
+    assert!(storage
+        .mark_hunk_reviewed("owner", "repo", 1, "empty.md", "a", 0, true)
+        .is_err());
+}
+
+/// Test Case 11.29: Reviews of Closed PRs Archived to the Trash
+#[tokio::test]
+async fn test_archive_closed_review() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage.add_comment("owner", "repo", 1, "a.md", 3, "RIGHT", "Draft", "commit1", None).await.unwrap();
+    storage.start_review("owner", "repo", 2, "commit2", None, None).unwrap();
+
+    assert!(storage.archive_closed_review("owner", "repo", 1, true).await.unwrap());
+    assert!(storage.archive_closed_review("owner", "repo", 2, false).await.unwrap());
+    assert!(!storage.archive_closed_review("owner", "repo", 3, true).await.unwrap());
+
+    assert!(storage.get_all_review_metadata().unwrap().is_empty());
+    let trashed = storage.list_trashed_reviews().unwrap();
+    let summary: Vec<(u64, &str, u64)> = trashed
+        .iter()
+        .map(|t| (t.pr_number, t.reason.as_str(), t.comment_count))
+        .collect();
+    assert!(summary.contains(&(1, "merged", 1)));
+    assert!(summary.contains(&(2, "closed", 0)));
+
+    storage.restore_trashed_review("owner", "repo", 1).unwrap();
+    assert_eq!(storage.get_comments("owner", "repo", 1).unwrap().len(), 1);
+}
+
+/// Test Case 11.30: Submitted Review Log Keeps the PR Snapshot
+#[tokio::test]
+async fn test_submitted_log_has_snapshot() {
+    let (storage, temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage.add_comment("owner", "repo", 1, "docs/new.md", 2, "RIGHT", "Typo", "commit1", None).await.unwrap();
+    let snapshot = crate::tests::review_snapshot_tests::sample_snapshot();
+    storage.mark_review_submitted("owner", "repo", 1, Some(&snapshot)).await.unwrap();
+
+    assert!(storage.get_review_metadata("owner", "repo", 1).unwrap().is_none());
+    let log = std::fs::read_to_string(temp.path().join("review_logs").join("owner-repo-1.log")).unwrap();
+    assert!(log.contains("abc1234def"), "{log}");
+    assert!(log.contains("A docs/new.md (+3 -0)"), "{log}");
+    // The comments written before submission are still below the header
+    assert!(log.contains("Typo"), "{log}");
+}
+
+/// Test Case 11.31: Comment Templates Are Saved, Edited and Deleted
+#[test]
+fn test_comment_template_crud() {
+    let (storage, _temp) = create_test_storage();
+
+    let typo = storage.create_comment_template(" Typo ", "Typo in {{file}}", Some("nit")).unwrap();
+    assert_eq!(typo.name, "Typo");
+    storage.create_comment_template("Add a test", "Please add a test", None).unwrap();
+    let names: Vec<String> =
+        storage.list_comment_templates().unwrap().into_iter().map(|t| t.name).collect();
+    assert_eq!(names, vec!["Add a test", "Typo"]);
+
+    // Names are unique ignoring case; bodies must be non-empty and categories known
+    let err = storage.create_comment_template("typo", "Other", None).unwrap_err();
+    assert!(err.to_string().ends_with("a comment template named `typo` already exists"));
+    let err = storage.create_comment_template("Empty", "  ", None).unwrap_err();
+    assert!(err.to_string().ends_with("comment template `Empty` has an empty body"));
+    assert!(storage.create_comment_template("Odd", "Body", Some("whatever")).is_err());
+
+    let edited = storage
+        .update_comment_template(typo.id, "Typo", "Spelling: {{line}}", None)
+        .unwrap();
+    assert_eq!(edited.body, "Spelling: {{line}}");
+    assert_eq!(edited.category, None);
+    assert_eq!(storage.get_comment_template(typo.id).unwrap(), Some(edited));
+    let err = storage.update_comment_template(999, "Gone", "Body", None).unwrap_err();
+    assert!(err.to_string().ends_with("comment template 999 not found"));
+
+    assert!(storage.delete_comment_template(typo.id).unwrap());
+    assert!(!storage.delete_comment_template(typo.id).unwrap());
+    assert_eq!(storage.list_comment_templates().unwrap().len(), 1);
+}
+
+/// Test Case 11.32: New Drafts Never Take the Ids of Archived Comments
+#[tokio::test]
+async fn test_archived_ids_are_reserved() {
+    use crate::models::PullRequestComment;
+
+    let (storage, _temp) = create_test_storage();
+    let archived = PullRequestComment {
+        id: 3_000_000_001,
+        body: "archived".into(),
+        author: "me".into(),
+        created_at: "2024-05-01T10:00:00Z".into(),
+        display_time: String::new(),
+        url: String::new(),
+        path: Some("a.md".into()),
+        line: Some(1),
+        side: Some("RIGHT".into()),
+        is_review_comment: true,
+        is_draft: true,
+        state: Some("PENDING".into()),
+        is_mine: true,
+        review_id: Some(9),
+        in_reply_to_id: None,
+        outdated: None,
+        new_since_last_visit: false,
+        reactions: Default::default(),
+    };
+    storage
+        .archive_pending_review("owner", "repo", 4, "headsha", &[archived])
+        .unwrap();
+
+    storage.start_review("owner", "repo", 5, "headsha", None, None).unwrap();
+    let draft = storage
+        .add_comment("owner", "repo", 5, "a.md", 1, "RIGHT", "new", "headsha", None)
+        .await
+        .unwrap();
+    storage.restore_trashed_review("owner", "repo", 4).unwrap();
+    let restored = storage.get_comments("owner", "repo", 4).unwrap();
+    assert_eq!(restored.len(), 1);
+    assert_ne!(restored[0].id, draft.id);
+}
+
+/// Test Case 11.33: Clearing a Review Drops Its Hunk Marks
+#[tokio::test]
+async fn test_clear_review_drops_hunk_marks() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.start_review("owner", "repo", 1, "commit1", None, None).unwrap();
+    storage
+        .mark_hunk_reviewed("owner", "repo", 1, "docs/ref.md", "a", 1, true)
+        .unwrap();
+    storage
+        .mark_hunk_reviewed("owner", "repo", 2, "docs/ref.md", "a", 1, true)
+        .unwrap();
+
+    storage.clear_review("owner", "repo", 1, None).await.unwrap();
+
+    assert!(storage.get_hunk_progress("owner", "repo", 1).unwrap().is_empty());
+    let files = storage.get_all_hunk_reviewed_files().unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(files.contains_key(&("owner".to_string(), "repo".to_string(), 2)));
+}
@@ -0,0 +1,62 @@
+// Category 87: Safe Mode Tests (safe_mode.rs)
+// Tests for crash-loop bookkeeping and the repair actions offered in safe mode
+
+use crate::safe_mode::{self, CrashSummary, StartupState, CRASH_LOOP_THRESHOLD};
+use tempfile::TempDir;
+use super::create_test_storage;
+
+fn crash(message: &str) -> CrashSummary {
+    CrashSummary {
+        message: message.to_string(),
+        location: "src/lib.rs:1:1".to_string(),
+        occurred_at: "2026-01-01T00:00:00+00:00".to_string(),
+    }
+}
+
+/// Test Case 87.1: Only Crashes at Startup Count Towards a Crash Loop
+#[test]
+fn test_crash_loop_detection() {
+    let mut state = StartupState::default();
+    for n in 1..CRASH_LOOP_THRESHOLD {
+        state = state.with_crash(crash(&format!("boom {n}")), true);
+        assert!(!state.is_crash_loop());
+    }
+    state = state.with_crash(crash("later"), false);
+    assert!(!state.is_crash_loop());
+    assert_eq!(state.last_crash.as_ref().map(|c| c.message.as_str()), Some("later"));
+
+    state = state.with_crash(crash("again"), true);
+    assert!(state.is_crash_loop());
+
+    let state = state.started_cleanly();
+    assert_eq!(state.startup_crashes, 0);
+    assert_eq!(state.last_crash, Some(crash("again")));
+}
+
+/// Test Case 87.2: Startup State Round Trips and Survives a Damaged File
+#[test]
+fn test_startup_state_file() {
+    let temp = TempDir::new().unwrap();
+    assert_eq!(safe_mode::read_state(temp.path()), StartupState::default());
+
+    let state = StartupState::default().with_crash(crash("boom"), true);
+    safe_mode::write_state(temp.path(), &state).unwrap();
+    assert_eq!(safe_mode::read_state(temp.path()), state);
+
+    std::fs::write(temp.path().join("startup_state.json"), "{not json").unwrap();
+    assert_eq!(safe_mode::read_state(temp.path()), StartupState::default());
+}
+
+/// Test Case 87.3: Database Check and Cache Purge on a Sound Database
+#[test]
+fn test_repair_actions() {
+    let (storage, _temp) = create_test_storage();
+
+    let check = safe_mode::check_database(&storage).unwrap();
+    assert!(check.ok);
+    assert!(check.problems.is_empty());
+
+    storage.set_setting(crate::session::SESSION_KEY, r#"{"owner":"acme","repo":"docs","pr_number":7}"#).unwrap();
+    safe_mode::purge_cache(&storage).unwrap();
+    assert_eq!(crate::session::load(&storage).unwrap(), None);
+}
@@ -0,0 +1,82 @@
+// Category 81: Second Pass Tests (second_pass.rs)
+// Tests for narrowing a re-review to what changed since my last review
+
+use std::collections::HashSet;
+
+use crate::second_pass::{self, ChangedFile, ReviewedCommit};
+
+fn reviewed(commit_id: &str, submitted_at: &str) -> ReviewedCommit {
+    ReviewedCommit {
+        commit_id: commit_id.to_string(),
+        submitted_at: submitted_at.to_string(),
+    }
+}
+
+fn changed(path: &str, previous_path: Option<&str>) -> ChangedFile {
+    ChangedFile {
+        path: path.to_string(),
+        previous_path: previous_path.map(str::to_string),
+        status: if previous_path.is_some() { "renamed" } else { "modified" }.to_string(),
+        additions: 1,
+        deletions: 1,
+        patch: Some("@@ -1 +1 @@\n-old\n+new".to_string()),
+    }
+}
+
+fn paths(paths: &[&str]) -> HashSet<String> {
+    paths.iter().map(|path| path.to_string()).collect()
+}
+
+/// Test Case 81.1: The Most Recent Review Wins
+#[test]
+fn test_latest_review() {
+    let here = reviewed("aaa", "2024-05-01T10:00:00+00:00");
+    let github = reviewed("bbb", "2024-05-02T08:00:00Z");
+    assert_eq!(second_pass::latest(Some(here.clone()), Some(github.clone())), Some(github.clone()));
+    assert_eq!(second_pass::latest(Some(github.clone()), Some(here.clone())), Some(github));
+    assert_eq!(second_pass::latest(None, Some(here.clone())), Some(here.clone()));
+    assert_eq!(second_pass::latest(None, None), None);
+}
+
+/// Test Case 81.2: Only Files I Commented On Are Kept
+#[test]
+fn test_build_restricts_to_commented_files() {
+    let changes = second_pass::build(
+        Some("aaa".to_string()),
+        "ccc",
+        vec![
+            changed("docs/b.md", None),
+            changed("docs/other.md", None),
+            changed("docs/a.md", None),
+        ],
+        &paths(&["docs/a.md", "docs/b.md", "docs/c.md"]),
+    );
+    let files: Vec<&str> = changes.files.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(files, vec!["docs/a.md", "docs/b.md"]);
+    assert_eq!(changes.unchanged_files, vec!["docs/c.md"]);
+    assert_eq!(changes.reviewed_commit.as_deref(), Some("aaa"));
+    assert_eq!(changes.head_sha, "ccc");
+}
+
+/// Test Case 81.3: Renamed Files Match by Their Old Name
+#[test]
+fn test_build_follows_renames() {
+    let changes = second_pass::build(
+        Some("aaa".to_string()),
+        "ccc",
+        vec![changed("guide/setup.md", Some("docs/setup.md"))],
+        &paths(&["docs/setup.md"]),
+    );
+    assert_eq!(changes.files.len(), 1);
+    assert_eq!(changes.files[0].path, "guide/setup.md");
+    assert!(changes.unchanged_files.is_empty());
+}
+
+/// Test Case 81.4: Nothing Changed Since the Review
+#[test]
+fn test_build_without_changes() {
+    let changes = second_pass::build(None, "ccc", Vec::new(), &paths(&["docs/a.md"]));
+    assert!(changes.files.is_empty());
+    assert_eq!(changes.unchanged_files, vec!["docs/a.md"]);
+    assert_eq!(changes.reviewed_commit, None);
+}
@@ -0,0 +1,80 @@
+// Category 82: Session Restore Tests (session.rs)
+// Tests for saving and restoring where the user was across restarts
+
+use crate::session::{self, DraftComment, SessionState};
+use super::create_test_storage;
+
+fn open_pr() -> SessionState {
+    SessionState {
+        owner: Some("octo".to_string()),
+        repo: Some("docs".to_string()),
+        pr_number: Some(42),
+        selected_file: Some("guide/install.md".to_string()),
+        scroll_anchor_line: Some(1200),
+        draft: Some(DraftComment {
+            file_path: Some("guide/install.md".to_string()),
+            line: Some(1214),
+            in_reply_to_id: None,
+            body: "This step is out of date".to_string(),
+        }),
+        ..SessionState::default()
+    }
+}
+
+/// Test Case 82.1: A Saved Session Is Restored
+#[test]
+fn test_session_round_trip() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(session::load(&storage).unwrap(), None);
+
+    let saved = session::save(&storage, open_pr()).unwrap();
+    assert!(saved.saved_at.is_some());
+
+    let restored = session::load(&storage).unwrap().unwrap();
+    assert_eq!(restored, saved);
+    assert_eq!(restored.scroll_anchor_line, Some(1200));
+    assert_eq!(restored.draft.unwrap().body, "This step is out of date");
+}
+
+/// Test Case 82.2: Saving an Empty Session Clears It
+#[test]
+fn test_empty_session_clears() {
+    let (storage, _temp) = create_test_storage();
+    session::save(&storage, open_pr()).unwrap();
+
+    let cleared = session::save(&storage, SessionState::default()).unwrap();
+    assert_eq!(cleared.saved_at, None);
+    assert_eq!(session::load(&storage).unwrap(), None);
+}
+
+/// Test Case 82.3: Local Folder Sessions Need No Owner
+#[test]
+fn test_local_folder_session() {
+    let (storage, _temp) = create_test_storage();
+    let folder = SessionState {
+        local_folder: Some("/home/me/notes".to_string()),
+        selected_file: Some("todo.md".to_string()),
+        ..SessionState::default()
+    };
+    session::save(&storage, folder).unwrap();
+    let restored = session::load(&storage).unwrap().unwrap();
+    assert_eq!(restored.local_folder.as_deref(), Some("/home/me/notes"));
+}
+
+/// Test Case 82.4: Invalid Sessions Are Rejected
+#[test]
+fn test_invalid_session() {
+    let (storage, _temp) = create_test_storage();
+    let no_repo = SessionState {
+        pr_number: Some(1),
+        ..SessionState::default()
+    };
+    assert!(session::save(&storage, no_repo).is_err());
+
+    let line_zero = SessionState {
+        scroll_anchor_line: Some(0),
+        ..open_pr()
+    };
+    assert!(session::save(&storage, line_zero).is_err());
+    assert_eq!(session::load(&storage).unwrap(), None);
+}
@@ -0,0 +1,153 @@
+// Category 83: Settings Export Tests (settings_bundle.rs)
+// Tests for exporting a reviewer setup and importing it on another machine
+
+use crate::allow_list::{self, AllowList, SNIPPET_CHECKS_ALLOW_LIST_KEY};
+use crate::content_filter;
+use crate::heading_style;
+use crate::quick_comments::{self, QuickCommentConfig, QuickCommentTemplate};
+use crate::settings;
+use crate::settings_bundle::{build_bundle, import_bundle, parse_bundle, SETTINGS_FORMAT};
+use crate::snippet_checks::{self, SnippetCheck, SnippetChecksConfig};
+use super::create_test_storage;
+
+fn check(language: &str, program: &str) -> SnippetCheck {
+    SnippetCheck {
+        language: language.to_string(),
+        program: program.to_string(),
+        args: vec!["{file}".to_string()],
+        extension: "txt".to_string(),
+    }
+}
+
+/// Test Case 83.1: An Export Imports Unchanged on Another Machine
+#[test]
+fn test_export_import_round_trip() {
+    let (source, _source_dir) = create_test_storage();
+    let mut preferences = settings::load_preferences(&source).unwrap();
+    preferences.theme = "light".to_string();
+    settings::save_preferences(&source, &preferences).unwrap();
+    let templates = QuickCommentConfig {
+        templates: vec![QuickCommentTemplate {
+            id: "style".to_string(),
+            label: "Style guide".to_string(),
+            body: "See the style guide.".to_string(),
+            category: Some("nit".to_string()),
+        }],
+    };
+    quick_comments::save_config(&source, &templates).unwrap();
+    source.create_comment_template("Needs test", "Please add a test.", None).unwrap();
+
+    let bundle = build_bundle(&source).unwrap();
+    let json = serde_json::to_string(&bundle).unwrap();
+    assert!(!json.contains("hooks"));
+    assert!(!json.contains("local_roots"));
+
+    let (target, _target_dir) = create_test_storage();
+    let report = import_bundle(&target, parse_bundle(&json).unwrap()).unwrap();
+    assert!(report.imported.contains(&"preferences".to_string()));
+    assert!(report.skipped.is_empty());
+    assert_eq!(settings::load_preferences(&target).unwrap().theme, "light");
+    assert_eq!(quick_comments::load_config(&target).unwrap(), templates);
+    let library = target.list_comment_templates().unwrap();
+    assert!(library.iter().any(|t| t.name == "Needs test" && t.quick_key.is_none()));
+}
+
+/// Test Case 83.2: Sections Missing From the File Are Left Alone
+#[test]
+fn test_partial_import() {
+    let (storage, _temp) = create_test_storage();
+    let json = format!(
+        r#"{{"format": "{SETTINGS_FORMAT}", "version": 1, "exported_at": "2024-01-01T00:00:00Z",
+            "heading_style": {{"max_depth": 3, "proper_nouns": ["Azure"]}},
+            "token": "ghp_ignored"}}"#
+    );
+    let report = import_bundle(&storage, parse_bundle(&json).unwrap()).unwrap();
+    assert_eq!(report.imported, vec!["heading_style".to_string()]);
+
+    let headings = heading_style::load_config(&storage).unwrap();
+    assert_eq!(headings.max_depth, 3);
+    assert_eq!(headings.proper_nouns, vec!["Azure".to_string()]);
+    assert_eq!(
+        quick_comments::load_config(&storage).unwrap(),
+        QuickCommentConfig::default()
+    );
+}
+
+/// Test Case 83.3: Imported Snippet Checks Keep the Local Allow-List
+#[test]
+fn test_snippet_checks_keep_local_allow_list() {
+    let (storage, _temp) = create_test_storage();
+    let local = AllowList {
+        programs: vec!["/usr/bin/rustc".to_string()],
+        ..AllowList::default()
+    };
+    allow_list::save(&storage, SNIPPET_CHECKS_ALLOW_LIST_KEY, &local).unwrap();
+
+    let mut bundle = build_bundle(&storage).unwrap();
+    bundle.preferences = None;
+    bundle.snippet_checks = Some(SnippetChecksConfig {
+        checks: vec![check("rust", "/usr/bin/rustc"), check("sh", "/bin/bash")],
+        timeout_secs: 10,
+    });
+    let report = import_bundle(&storage, bundle).unwrap();
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].contains("bash"));
+
+    let saved = snippet_checks::load_config(&storage).unwrap();
+    assert_eq!(snippet_checks::load_allow_list(&storage).unwrap(), local);
+    assert_eq!(saved.checks, vec![check("rust", "/usr/bin/rustc")]);
+    assert_eq!(saved.timeout_secs, 10);
+}
+
+/// Test Case 83.4: A Bad File Changes Nothing
+#[test]
+fn test_invalid_import_is_rejected() {
+    let (storage, _temp) = create_test_storage();
+    assert!(parse_bundle(r#"{"format": "other", "version": 1, "exported_at": ""}"#).is_err());
+    let newer = format!(r#"{{"format": "{SETTINGS_FORMAT}", "version": 99, "exported_at": ""}}"#);
+    assert!(parse_bundle(&newer).is_err());
+
+    let mut bundle = build_bundle(&storage).unwrap();
+    bundle.preferences.as_mut().unwrap().theme = "light".to_string();
+    bundle.heading_style.as_mut().unwrap().max_depth = 9;
+    assert!(import_bundle(&storage, bundle).is_err());
+    assert_eq!(settings::load_preferences(&storage).unwrap().theme, "dark");
+}
+
+/// Test Case 83.5: A Bad Content Filter Is Rejected Before Anything Is Saved
+#[test]
+fn test_invalid_content_filter_rejected() {
+    let (storage, _temp) = create_test_storage();
+    let mut bundle = build_bundle(&storage).unwrap();
+    bundle.comment_templates = Some(Vec::new());
+    bundle.quick_comments.as_mut().unwrap().templates.truncate(1);
+    bundle.preferences.as_mut().unwrap().theme = "light".to_string();
+    bundle.content_filter.as_mut().unwrap().internal_hosts = vec!["not a host".to_string()];
+
+    assert!(import_bundle(&storage, bundle.clone()).is_err());
+    assert_eq!(settings::load_preferences(&storage).unwrap().theme, "dark");
+    assert!(storage.get_setting(quick_comments::QUICK_COMMENTS_SAVED_KEY).unwrap().is_none());
+
+    bundle.content_filter.as_mut().unwrap().internal_hosts = vec![".corp.example.com".to_string()];
+    let report = import_bundle(&storage, bundle).unwrap();
+    assert!(report.imported.contains(&"content_filter".to_string()));
+    let filter = content_filter::load_config(&storage).unwrap();
+    assert_eq!(filter.internal_hosts, vec![".corp.example.com".to_string()]);
+    assert_eq!(quick_comments::load_config(&storage).unwrap().templates.len(), 1);
+}
+
+/// Test Case 83.6: Imports Keep the Local Usage Metrics Choice
+#[test]
+fn test_import_keeps_usage_metrics() {
+    let (source, _source_dir) = create_test_storage();
+    let mut shared = settings::load_preferences(&source).unwrap();
+    shared.usage_metrics = true;
+    shared.theme = "light".to_string();
+    settings::save_preferences(&source, &shared).unwrap();
+
+    let (target, _target_dir) = create_test_storage();
+    import_bundle(&target, build_bundle(&source).unwrap()).unwrap();
+    let preferences = settings::load_preferences(&target).unwrap();
+    assert_eq!(preferences.theme, "light");
+    assert!(!preferences.usage_metrics);
+}
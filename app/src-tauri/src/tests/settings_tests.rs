@@ -0,0 +1,121 @@
+// Category 21: Settings and Preferences Tests (settings.rs)
+// Tests for typed settings persisted in the review storage settings table
+
+use crate::settings::{self, Preferences};
+use super::create_test_storage;
+
+/// Test Case 21.1: Defaults Returned When Nothing Stored
+#[test]
+fn test_preferences_default_when_missing() {
+    let (storage, _temp) = create_test_storage();
+
+    let prefs = settings::load_preferences(&storage).unwrap();
+    assert_eq!(prefs, Preferences::default());
+    assert_eq!(prefs.default_review_event, "COMMENT");
+}
+
+/// Test Case 21.2: Preferences Round-Trip
+#[test]
+fn test_preferences_round_trip() {
+    let (storage, _temp) = create_test_storage();
+
+    let mut prefs = Preferences {
+        theme: "light".to_string(),
+        ..Default::default()
+    };
+    prefs.diff_view.mode = "unified".to_string();
+    prefs.confirmations.confirm_comment = true;
+    settings::save_preferences(&storage, &prefs).unwrap();
+
+    let loaded = settings::load_preferences(&storage).unwrap();
+    assert_eq!(loaded, prefs);
+}
+
+/// Test Case 21.3: Invalid Preferences Rejected
+#[test]
+fn test_preferences_validation() {
+    let (storage, _temp) = create_test_storage();
+
+    let prefs = Preferences {
+        default_review_event: "MERGE".to_string(),
+        ..Default::default()
+    };
+
+    assert!(settings::save_preferences(&storage, &prefs).is_err());
+    assert_eq!(settings::load_preferences(&storage).unwrap(), Preferences::default());
+
+    let prefs = Preferences {
+        trash_retention_days: 0,
+        ..Default::default()
+    };
+    assert!(settings::save_preferences(&storage, &prefs).is_err());
+
+    let prefs = Preferences {
+        timezone: Some("Nowhere/Special".to_string()),
+        ..Default::default()
+    };
+    assert!(settings::save_preferences(&storage, &prefs).is_err());
+
+    let mut prefs = Preferences::default();
+    prefs.stale_reviews.action = "delete".to_string();
+    assert!(settings::save_preferences(&storage, &prefs).is_err());
+
+    let mut prefs = Preferences::default();
+    prefs.diff_view.markdown_preview = "pandoc".to_string();
+    assert!(settings::save_preferences(&storage, &prefs).is_err());
+}
+
+/// Test Case 21.4: Partial Stored JSON Fills Missing Fields
+#[test]
+fn test_preferences_partial_json() {
+    let (storage, _temp) = create_test_storage();
+
+    storage.set_setting(settings::PREFERENCES_KEY, r#"{"theme":"system"}"#).unwrap();
+
+    let loaded = settings::load_preferences(&storage).unwrap();
+    assert_eq!(loaded.theme, "system");
+    assert_eq!(loaded.diff_view, Default::default());
+}
+
+/// Test Case 21.5: Follow-up Comment Template Rendering
+#[test]
+fn test_followup_template_render() {
+    let followup = settings::ReviewFollowup {
+        post_comment: true,
+        comment_template: "@{{author}}: {{reviewer}} is checking {{short_sha}} ({{head_sha}})".into(),
+    };
+
+    let rendered = followup.render("alice", "bob", "0123456789abcdef");
+    assert_eq!(rendered, "@alice: bob is checking 0123456 (0123456789abcdef)");
+    assert!(Preferences::default().followup.post_comment);
+}
+
+/// Test Case 21.6: Submissions Without an Event Use the Default
+#[test]
+fn test_review_event_default() {
+    let prefs = Preferences {
+        default_review_event: "REQUEST_CHANGES".to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(prefs.review_event(None), "REQUEST_CHANGES");
+    assert_eq!(prefs.review_event(Some(" ")), "REQUEST_CHANGES");
+    assert_eq!(prefs.review_event(Some("approve")), "APPROVE");
+    assert_eq!(Preferences::default().review_event(None), "COMMENT");
+}
+
+/// Test Case 21.7: Verdicts Without a Body Refused Only When the Policy Is On
+#[test]
+fn test_check_review_body() {
+    let prefs = Preferences::default();
+    assert!(prefs.check_review_body("APPROVE", None).is_ok());
+
+    let prefs = Preferences {
+        require_verdict_body: true,
+        ..Default::default()
+    };
+    assert!(prefs.check_review_body("APPROVE", None).is_err());
+    assert!(prefs.check_review_body("REQUEST_CHANGES", Some("  \n")).is_err());
+    assert!(prefs.check_review_body("APPROVE", Some("Looks good")).is_ok());
+    assert!(prefs.check_review_body("COMMENT", None).is_ok());
+}
@@ -0,0 +1,131 @@
+// Category 70: Snippet Check Tests (snippet_checks.rs)
+// Tests for choosing fenced blocks to check, the allow-list, and running checks
+
+use crate::allow_list::{self, AllowList, SNIPPET_CHECKS_ALLOW_LIST_KEY};
+use crate::snippet_checks::{self, SnippetCheck, SnippetChecksConfig};
+use super::create_test_storage;
+
+fn check(language: &str, program: &str, args: &[&str]) -> SnippetCheck {
+    SnippetCheck {
+        language: language.to_string(),
+        program: program.to_string(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+        extension: "txt".to_string(),
+    }
+}
+
+fn config(checks: Vec<SnippetCheck>) -> SnippetChecksConfig {
+    SnippetChecksConfig {
+        checks,
+        ..SnippetChecksConfig::default()
+    }
+}
+
+fn allowing(config: &SnippetChecksConfig) -> AllowList {
+    AllowList {
+        programs: config.programs(),
+        ..AllowList::default()
+    }
+}
+
+const DOC: &str = "# Usage\n\n```rust\nfn main() {}\n```\n\n```text\nplain\n```\n\n```Python\nprint('hi')\n```\n";
+
+/// Test Case 70.1: Only Configured Languages Are Checked
+#[test]
+fn test_blocks_for_configured_languages() {
+    let config = config(vec![
+        check("rust", "/usr/bin/rustc", &["{file}"]),
+        check("python", "/usr/bin/python3", &["-m", "py_compile"]),
+    ]);
+
+    let blocks = snippet_checks::blocks_to_check(&config, DOC, None);
+    let found: Vec<(&str, u64)> = blocks
+        .iter()
+        .map(|b| (b.language.as_str(), b.fence_line))
+        .collect();
+    assert_eq!(found, vec![("rust", 3), ("python", 11)]);
+}
+
+/// Test Case 70.2: Patch Limits Checks to Touched Blocks
+#[test]
+fn test_blocks_limited_to_patch() {
+    let config = config(vec![
+        check("rust", "/usr/bin/rustc", &["{file}"]),
+        check("python", "/usr/bin/python3", &["-m", "py_compile"]),
+    ]);
+
+    let patch = "@@ -12,1 +12,1 @@\n-print 'hi'\n+print('hi')";
+    let blocks = snippet_checks::blocks_to_check(&config, DOC, Some(patch));
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].language, "python");
+
+    // A change outside every fence checks nothing
+    let patch = "@@ -1,1 +1,1 @@\n-# Use\n+# Usage";
+    assert!(snippet_checks::blocks_to_check(&config, DOC, Some(patch)).is_empty());
+}
+
+/// Test Case 70.3: File Placeholder Substituted or Appended
+#[test]
+fn test_render_args() {
+    let args = vec!["--edition".to_string(), "2021".to_string(), "{file}".to_string()];
+    assert_eq!(
+        snippet_checks::render_args(&args, "snippet_1.rs"),
+        vec!["--edition", "2021", "snippet_1.rs"]
+    );
+
+    let args = vec!["-m".to_string(), "py_compile".to_string()];
+    assert_eq!(
+        snippet_checks::render_args(&args, "snippet_1.py"),
+        vec!["-m", "py_compile", "snippet_1.py"]
+    );
+}
+
+/// Test Case 70.4: Config Validated and Persisted
+#[test]
+fn test_config_validation_and_persistence() {
+    let (storage, _temp) = create_test_storage();
+    assert_eq!(
+        snippet_checks::load_config(&storage).unwrap(),
+        SnippetChecksConfig::default()
+    );
+
+    let unlisted = SnippetChecksConfig {
+        checks: vec![check("rust", "/usr/bin/rustc", &[])],
+        ..SnippetChecksConfig::default()
+    };
+    assert!(snippet_checks::save_config(&storage, &unlisted).is_err());
+
+    // Bare program names are refused even when allowed by name
+    let bare = config(vec![check("rust", "rustc", &[])]);
+    assert!(bare.validate(&allowing(&bare)).is_err());
+
+    let valid = config(vec![check("rust", "/usr/bin/rustc", &["--emit=metadata", "{file}"])]);
+    allow_list::save(&storage, SNIPPET_CHECKS_ALLOW_LIST_KEY, &allowing(&valid)).unwrap();
+
+    let mut bad_extension = config(vec![check("rust", "/usr/bin/rustc", &[])]);
+    bad_extension.checks[0].extension = "../rs".to_string();
+    assert!(snippet_checks::save_config(&storage, &bad_extension).is_err());
+
+    snippet_checks::save_config(&storage, &valid).unwrap();
+    assert_eq!(snippet_checks::load_config(&storage).unwrap(), valid);
+}
+
+/// Test Case 70.5: Failing Snippets Reported as Findings
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_checks_reports_failures() {
+    let content = "```sh\nexit 0\n```\n\n```sh\necho broken sample >&2; exit 3\n```\n";
+    let config = config(vec![check("sh", "/bin/sh", &["{file}"])]);
+
+    let findings = snippet_checks::run_checks(&config, &allowing(&config), content, None)
+        .await
+        .unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].line, 5);
+    assert_eq!(findings[0].program, "/bin/sh");
+    assert!(findings[0].output.contains("broken sample"));
+
+    // Programs outside the allow-list never run
+    let blocked = AllowList::default();
+    assert!(snippet_checks::run_checks(&config, &blocked, content, None).await.is_err());
+}
@@ -0,0 +1,141 @@
+// Category 71: Spec Validation Tests (spec_validation.rs)
+// Tests for OpenAPI and JSON Schema checks on changed spec files and their line numbers
+
+use crate::spec_validation::{self, SpecKind};
+
+const PETSTORE: &str = r##"openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets/{petId}:
+    get:
+      operationId: getPet
+      responses:
+        "200":
+          description: A pet
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id]
+      properties:
+        id:
+          type: integer
+"##;
+
+/// Test Case 71.1: Valid OpenAPI Document Apart From Path Parameter
+#[test]
+fn test_missing_path_parameter() {
+    let report = spec_validation::validate("api/openapi.yaml", PETSTORE);
+    assert_eq!(report.kind, Some(SpecKind::OpenApi));
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].pointer, "/paths/~1pets~1{petId}/get");
+    assert_eq!(report.issues[0].line, 7);
+    assert!(report.issues[0].detail.contains("petId"));
+}
+
+/// Test Case 71.2: Broken References and Types Located by Line
+#[test]
+fn test_broken_ref_and_type() {
+    let content = PETSTORE
+        .replace("schemas/Pet\"", "schemas/Dog\"")
+        .replace("type: integer", "type: int");
+    let report = spec_validation::validate("openapi.yml", &content);
+
+    let found: Vec<(u64, &str)> = report
+        .issues
+        .iter()
+        .map(|issue| (issue.line, issue.pointer.as_str()))
+        .collect();
+    assert!(found.contains(&(
+        15,
+        "/paths/~1pets~1{petId}/get/responses/200/content/application~1json/schema/$ref"
+    )));
+    assert!(found.contains(&(23, "/components/schemas/Pet/properties/id/type")));
+}
+
+/// Test Case 71.3: Operations, Parameters, and Responses Checked
+#[test]
+fn test_operation_checks() {
+    let content = r#"{
+  "swagger": "2.0",
+  "info": {"title": "Legacy"},
+  "paths": {
+    "/users/{id}": {
+      "parameters": [{"name": "id", "in": "path", "required": true}],
+      "get": {"operationId": "getUser", "responses": {"ok": {"description": "x"}}},
+      "delete": {"operationId": "getUser", "parameters": [{"name": "q", "in": "cookie"}]}
+    }
+  }
+}"#;
+    let report = spec_validation::validate("swagger.json", content);
+    assert_eq!(report.kind, Some(SpecKind::Swagger));
+
+    let details: Vec<(&str, u64)> = report
+        .issues
+        .iter()
+        .map(|issue| (issue.detail.as_str(), issue.line))
+        .collect();
+    assert!(details.contains(&("missing required `version`", 3)));
+    assert!(details.contains(&("`ok` is not an HTTP status code", 7)));
+    assert!(details.iter().any(|(d, line)| d.starts_with("duplicate operationId") && *line == 8));
+    assert!(details.iter().any(|(d, _)| d.starts_with("`in: cookie`")));
+    assert!(details.contains(&("missing required `responses`", 8)));
+    assert_eq!(report.issues.len(), 5);
+}
+
+/// Test Case 71.4: JSON Schema Detected and Checked
+#[test]
+fn test_json_schema() {
+    let content = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "type": "object",
+  "required": ["name", "email"],
+  "properties": {
+    "name": {"type": "string"},
+    "tags": {"type": "array", "items": {"$ref": "#/$defs/Tag"}}
+  },
+  "$defs": {"Label": {"type": "string"}}
+}"##;
+    let report = spec_validation::validate("config.json", content);
+    assert_eq!(report.kind, Some(SpecKind::JsonSchema));
+
+    let found: Vec<(&str, u64)> = report
+        .issues
+        .iter()
+        .map(|issue| (issue.pointer.as_str(), issue.line))
+        .collect();
+    assert_eq!(found, vec![("/required", 4), ("/properties/tags/items/$ref", 7)]);
+    assert!(report.issues[0].detail.contains("`email`"));
+}
+
+/// Test Case 71.5: Syntax Errors Reported for Any Spec File
+#[test]
+fn test_syntax_errors() {
+    let report = spec_validation::validate("data.json", "{\n  \"a\": 1,\n  \"b\"\n}");
+    assert_eq!(report.kind, None);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].line, 4);
+
+    let report = spec_validation::validate("ci.yaml", "jobs:\n  build:\n    - a\n   b: [\n");
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.issues[0].line >= 3);
+}
+
+/// Test Case 71.6: Other Files Ignored
+#[test]
+fn test_non_spec_files_ignored() {
+    // Plain data parses without issues
+    let report = spec_validation::validate("package.json", r#"{"name": "app", "type": "module"}"#);
+    assert_eq!(report.kind, None);
+    assert!(report.issues.is_empty());
+
+    // Only JSON and YAML files are parsed
+    let report = spec_validation::validate("README.md", "{ not json");
+    assert!(report.issues.is_empty());
+}
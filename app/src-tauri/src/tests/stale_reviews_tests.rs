@@ -0,0 +1,58 @@
+// Category 85: Stale Review Tests (stale_reviews.rs)
+// Tests for picking out local reviews whose pull requests were merged or closed
+
+use std::collections::HashMap;
+
+use crate::models::PrReviewState;
+use crate::review_storage::ReviewMetadata;
+use crate::stale_reviews::select_stale;
+
+fn review(pr_number: u64) -> ReviewMetadata {
+    ReviewMetadata {
+        owner: "owner".to_string(),
+        repo: "repo".to_string(),
+        pr_number,
+        commit_id: "abc123".to_string(),
+        body: None,
+        local_folder: None,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        log_file_index: 0,
+        due_at: None,
+    }
+}
+
+fn state(state: &str, merged: bool) -> Option<PrReviewState> {
+    Some(PrReviewState {
+        head_sha: "def456".to_string(),
+        state: state.to_string(),
+        merged,
+        locked: false,
+        has_pending_review: false,
+    })
+}
+
+/// Test Case 85.1: Merged and Closed PRs Are Stale, Open Ones Are Not
+#[test]
+fn test_select_stale_by_state() {
+    let reviews = vec![review(1), review(2), review(3)];
+    let states = vec![state("open", false), state("closed", true), state("closed", false)];
+    let pending = HashMap::from([(("owner".to_string(), "repo".to_string(), 2), 4)]);
+
+    let stale = select_stale(&reviews, &states, &pending);
+
+    let summary: Vec<(u64, bool, usize)> = stale
+        .iter()
+        .map(|s| (s.pr_number, s.merged, s.pending_comment_count))
+        .collect();
+    assert_eq!(summary, vec![(2, true, 4), (3, false, 0)]);
+}
+
+/// Test Case 85.2: Reviews Without a Known State Are Kept
+#[test]
+fn test_select_stale_unknown_state() {
+    let reviews = vec![review(1), review(2)];
+    // PRs GitHub could not find, or whose owner is blocked by policy, come back as None
+    let states = vec![None, None];
+
+    assert!(select_stale(&reviews, &states, &HashMap::new()).is_empty());
+}
@@ -138,3 +138,43 @@ fn test_unicode_login_handling() {
     let login = "test-user-123";
     assert!(login.chars().all(|c| c.is_alphanumeric() || c == '-'));
 }
+
+/// Test Case 9.11: Account Profiles Added, Switched and Removed
+#[test]
+fn test_profile_index() {
+    use crate::storage::{ProfileIndex, DEFAULT_PROFILE};
+
+    let mut index = ProfileIndex::default();
+    assert_eq!(index.active, DEFAULT_PROFILE);
+
+    index.add("work").unwrap();
+    assert!(index.add("work").is_err());
+    assert!(index.switch_to("personal").is_err());
+    index.switch_to("work").unwrap();
+    assert_eq!(index.active, "work");
+
+    // Removing the active account falls back to the first remaining one
+    index.remove("work").unwrap();
+    assert_eq!(index.active, DEFAULT_PROFILE);
+    assert!(index.remove("work").is_err());
+
+    // Removing the last account leaves an empty default one
+    index.remove(DEFAULT_PROFILE).unwrap();
+    assert_eq!(index, ProfileIndex::default());
+}
+
+/// Test Case 9.12: Profile Names and Their Keyring Entries
+#[test]
+fn test_profile_names() {
+    use crate::storage::{profile_account, validate_profile_name};
+
+    assert!(validate_profile_name("work").is_ok());
+    assert!(validate_profile_name("client_a-2").is_ok());
+    assert!(validate_profile_name("").is_err());
+    assert!(validate_profile_name("a:b").is_err());
+    assert!(validate_profile_name(&"x".repeat(33)).is_err());
+
+    // The default profile keeps the keyring entries of single-account installs
+    assert_eq!(profile_account("github-token", "default"), "github-token");
+    assert_eq!(profile_account("github-token", "work"), "github-token:work");
+}
@@ -0,0 +1,90 @@
+// Category 64: Submission Report Tests (submission_report.rs)
+// Tests for classifying rejected comments and summarizing a submission
+
+use crate::review_storage::ReviewComment;
+use crate::submission_report::{error_code, summarize, unposted, FailedComment, SubmissionReport};
+
+fn comment(id: i64) -> ReviewComment {
+    ReviewComment {
+        id,
+        owner: "o".to_string(),
+        repo: "r".to_string(),
+        pr_number: 1,
+        file_path: "docs/a.md".to_string(),
+        line_number: 3,
+        side: "RIGHT".to_string(),
+        body: "Typo".to_string(),
+        commit_id: "abc1234".to_string(),
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: "2024-01-01T00:00:00Z".to_string(),
+        deleted: false,
+        in_reply_to_id: None,
+        category: None,
+        general: false,
+        suggestion: None,
+    }
+}
+
+/// Test Case 64.1: GitHub Error Text Maps to Stable Codes
+#[test]
+fn test_error_codes() {
+    let cases = [
+        (
+            r#"Status 422: {"errors":[{"field":"pull_request_review_thread.line","message":"could not be resolved"}]}"#,
+            "line_not_in_diff",
+        ),
+        ("Status 422: was submitted too quickly", "submitted_too_quickly"),
+        ("still hitting the GitHub secondary rate limit after 5 pauses", "rate_limited"),
+        ("Forbidden when posting comment to a.md:3", "forbidden"),
+        ("Status 404 Not Found", "not_found"),
+        (r#"Status 422: {"message":"Body can't be blank"}"#, "empty_body"),
+        ("something odd", "unknown"),
+    ];
+    for (message, code) in cases {
+        assert_eq!(error_code(message), code, "{message}");
+        assert_eq!(FailedComment::new(1, message.to_string()).error_code, code);
+    }
+}
+
+/// Test Case 64.2: A Stopped Batch Fails Every Remaining Comment
+#[test]
+fn test_unposted() {
+    let failed = unposted(&[comment(4), comment(5)], "locked", "PR is locked");
+    assert_eq!(failed.len(), 2);
+    assert_eq!(failed[1].comment_id, 5);
+    assert!(failed.iter().all(|f| f.error_code == "locked" && f.message == "PR is locked"));
+}
+
+/// Test Case 64.3: Summary Explains the First Failure
+#[test]
+fn test_summarize() {
+    assert_eq!(summarize(3, 3, &[]), None);
+
+    let failed = vec![FailedComment::with_code(2, "locked", "locked".to_string())];
+    let partial = summarize(2, 3, &failed).unwrap();
+    assert!(partial.starts_with("Submitted 2 of 3 comments. 1 failed."));
+    assert!(partial.contains("PR conversation is locked."));
+
+    let total = summarize(0, 1, &failed).unwrap();
+    assert!(total.starts_with("Failed to submit all 1 comments."));
+}
+
+/// Test Case 64.4: Reports Round-Trip Through JSON
+#[test]
+fn test_report_serialization() {
+    let report = SubmissionReport {
+        owner: "o".to_string(),
+        repo: "r".to_string(),
+        pr_number: 7,
+        posted: 1,
+        failed: vec![FailedComment::with_code(9, "not_found", "gone".to_string())],
+        review_url: Some("https://github.com/o/r/pull/7".to_string()),
+        summary: None,
+        submitted_at: "2024-01-01T00:00:00+00:00".to_string(),
+        orphaned: Vec::new(),
+    };
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["failed"][0]["error_code"], "not_found");
+    let parsed: SubmissionReport = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, report);
+}
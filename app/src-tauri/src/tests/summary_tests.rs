@@ -0,0 +1,75 @@
+// Category 29: PR Summary Tests (summary.rs)
+// Tests for the structural summary used to triage large pull requests
+
+use crate::models::PullRequestFile;
+use crate::summary;
+
+fn file(path: &str, status: &str, additions: u32, deletions: u32, patch: Option<&str>) -> PullRequestFile {
+    PullRequestFile {
+        path: path.to_string(),
+        status: status.to_string(),
+        additions,
+        deletions,
+        patch: patch.map(str::to_string),
+        head_content: None,
+        base_content: None,
+        language: "markdown".to_string(),
+        previous_filename: None,
+        localized_pair: None,
+    }
+}
+
+/// Test Case 29.1: Files Grouped By Directory And Ranked
+#[test]
+fn test_directories_and_largest_changes() {
+    let files = vec![
+        file("docs/guide/intro.md", "modified", 5, 1, None),
+        file("docs/guide/setup.md", "modified", 40, 10, None),
+        file("README.md", "modified", 2, 0, None),
+        file("src/lib.rs", "modified", 1, 1, None),
+    ];
+
+    let result = summary::summarize(&files);
+
+    assert_eq!(result.total_files, 4);
+    assert_eq!(result.total_additions, 48);
+    assert_eq!(result.directories[0].directory, "docs/guide");
+    assert_eq!(result.directories[0].files, 2);
+    assert!(result.directories.iter().any(|d| d.directory == "."));
+    assert_eq!(result.largest_changes[0].path, "docs/guide/setup.md");
+}
+
+/// Test Case 29.2: New, Removed And Renamed Docs
+#[test]
+fn test_doc_lifecycle() {
+    let mut renamed = file("docs/new-name.md", "renamed", 0, 0, None);
+    renamed.previous_filename = Some("docs/old-name.md".to_string());
+    let files = vec![
+        file("docs/added.md", "added", 10, 0, None),
+        file("docs/gone.rst", "removed", 0, 8, None),
+        file("src/added.rs", "added", 3, 0, None),
+        renamed,
+    ];
+
+    let result = summary::summarize(&files);
+
+    assert_eq!(result.added_docs, vec!["docs/added.md"]);
+    assert_eq!(result.removed_docs, vec!["docs/gone.rst"]);
+    assert_eq!(result.renamed_docs[0].from, "docs/old-name.md");
+}
+
+/// Test Case 29.3: TOC Changes And Heading Churn From Patch
+#[test]
+fn test_toc_and_heading_churn() {
+    let patch = "@@ -1,8 +1,9 @@\n # Guide\n-## Install\n+## Installation\n+Run the installer.\n ## Usage\n-Old usage.\n+New usage.\n+More usage.\n ## FAQ";
+    let files = vec![file("docs/guide.md", "modified", 4, 2, Some(patch))];
+
+    let result = summary::summarize(&files);
+
+    assert_eq!(result.toc_changes.len(), 1);
+    assert_eq!(result.toc_changes[0].added, vec!["## Installation"]);
+    assert_eq!(result.toc_changes[0].removed, vec!["## Install"]);
+
+    assert_eq!(result.top_headings[0].heading, "## Usage");
+    assert_eq!(result.top_headings[0].changed_lines, 3);
+}
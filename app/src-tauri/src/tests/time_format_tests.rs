@@ -0,0 +1,49 @@
+// Category 57: Timestamp Formatting Tests (time_format.rs)
+// Tests for showing RFC 3339 timestamps in the user's locale and time zone
+
+use crate::i18n::Locale;
+use crate::time_format::{format_timestamp, parse_zone};
+
+/// Test Case 57.1: Each Locale Uses Its Own Date Order
+#[test]
+fn test_format_per_locale() {
+    let utc = parse_zone(Some("UTC")).unwrap();
+    let timestamp = "2024-06-10T14:05:00Z";
+
+    assert_eq!(format_timestamp(timestamp, Locale::En, utc), "Jun 10, 2024, 2:05 PM UTC");
+    assert_eq!(format_timestamp(timestamp, Locale::Ja, utc), "2024年6月10日 14:05 UTC");
+    assert_eq!(format_timestamp(timestamp, Locale::De, utc), "10.06.2024, 14:05 UTC");
+}
+
+/// Test Case 57.2: Times Convert to the Configured Zone, Including DST
+#[test]
+fn test_format_in_zone() {
+    let berlin = parse_zone(Some("Europe/Berlin")).unwrap();
+
+    assert_eq!(
+        format_timestamp("2024-06-10T23:30:00+00:00", Locale::De, berlin),
+        "11.06.2024, 01:30 CEST"
+    );
+    assert_eq!(
+        format_timestamp("2024-01-10T23:30:00Z", Locale::De, berlin),
+        "11.01.2024, 00:30 CET"
+    );
+}
+
+/// Test Case 57.3: Non-Timestamps Pass Through Unchanged
+#[test]
+fn test_format_passes_through_invalid() {
+    let utc = parse_zone(Some("UTC")).unwrap();
+
+    assert_eq!(format_timestamp("", Locale::En, utc), "");
+    assert_eq!(format_timestamp("yesterday", Locale::Ja, utc), "yesterday");
+}
+
+/// Test Case 57.4: Zone Names Are Validated
+#[test]
+fn test_parse_zone() {
+    assert_eq!(parse_zone(None).unwrap(), None);
+    assert_eq!(parse_zone(Some("  ")).unwrap(), None);
+    assert!(parse_zone(Some("Asia/Tokyo")).unwrap().is_some());
+    assert!(parse_zone(Some("Mars/Olympus")).is_err());
+}
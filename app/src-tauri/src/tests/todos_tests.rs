@@ -0,0 +1,68 @@
+// Category 42: TODO Extraction Tests (todos.rs)
+// Tests for finding placeholder markers added by a pull request
+
+use crate::models::PullRequestFile;
+use crate::todos;
+
+fn file(path: &str, patch: Option<&str>, head_content: Option<&str>) -> PullRequestFile {
+    PullRequestFile {
+        path: path.to_string(),
+        status: "modified".to_string(),
+        additions: 0,
+        deletions: 0,
+        patch: patch.map(str::to_string),
+        head_content: head_content.map(str::to_string),
+        base_content: None,
+        language: "markdown".to_string(),
+        previous_filename: None,
+        localized_pair: None,
+    }
+}
+
+/// Test Case 42.1: Only Added Lines Are Reported, With Head Line Numbers
+#[test]
+fn test_scan_patch_added_lines_only() {
+    let patch = "@@ -10,4 +10,5 @@ Intro\n Context line\n-Old TODO: removed\n+New text, TBD later\n+Plain addition\n TODO already there\n+  FIXME: broken link  ";
+
+    let findings = todos::scan_patch("docs/guide.md", patch);
+
+    let summary: Vec<(u64, &str, &str)> = findings
+        .iter()
+        .map(|f| (f.line, f.marker.as_str(), f.text.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (11, "TBD", "New text, TBD later"),
+            (14, "FIXME", "FIXME: broken link"),
+        ]
+    );
+    assert!(findings[0].suggested_comment.contains("TBD"));
+}
+
+/// Test Case 42.2: Markers Must Be Whole Uppercase Words
+#[test]
+fn test_marker_word_boundaries() {
+    assert!(todos::scan_line("a.md", 1, "Add to the todo list").is_none());
+    assert!(todos::scan_line("a.md", 1, "See TODOS.md").is_none());
+    assert!(todos::scan_line("a.md", 1, "Date: TBD.").is_some());
+    assert_eq!(
+        todos::scan_line("a.md", 1, "TODO and FIXME").map(|f| f.marker),
+        Some("TODO".to_string())
+    );
+}
+
+/// Test Case 42.3: Files Without a Patch Fall Back to Head Content
+#[test]
+fn test_scan_files_fallback() {
+    let files = vec![
+        file("with-patch.md", Some("@@ -0,0 +1,2 @@\n+Intro\n+TODO: write this"), None),
+        file("new.md", None, Some("# Title\n\nFIXME\n")),
+        file("binary.png", None, None),
+    ];
+
+    let findings = todos::scan_files(&files);
+
+    let locations: Vec<(&str, u64)> = findings.iter().map(|f| (f.path.as_str(), f.line)).collect();
+    assert_eq!(locations, vec![("with-patch.md", 2), ("new.md", 3)]);
+}
@@ -0,0 +1,74 @@
+// Category 53: Visit Tracking Tests (visits.rs)
+// Tests for flagging comments posted since the last visit to a PR
+
+use crate::models::PullRequestComment;
+use crate::visits::{is_after, mark_new_comments, normalize_seen_at};
+
+fn comment(id: u64, created_at: &str, is_mine: bool) -> PullRequestComment {
+    PullRequestComment {
+        id,
+        body: "Looks good".to_string(),
+        author: if is_mine { "me" } else { "reviewer" }.to_string(),
+        created_at: created_at.to_string(),
+        display_time: String::new(),
+        url: format!("https://github.com/owner/repo/pull/1#issuecomment-{id}"),
+        path: None,
+        line: None,
+        side: None,
+        is_review_comment: false,
+        is_draft: false,
+        state: None,
+        is_mine,
+        review_id: None,
+        in_reply_to_id: None,
+        outdated: None,
+        new_since_last_visit: false,
+        reactions: Default::default(),
+    }
+}
+
+/// Test Case 53.1: Timestamps Compare as Instants Across Offset Styles
+#[test]
+fn test_is_after_mixed_offsets() {
+    assert!(is_after("2024-03-01T10:00:01Z", "2024-03-01T10:00:00+00:00"));
+    assert!(!is_after("2024-03-01T10:00:00Z", "2024-03-01T10:00:00+00:00"));
+    assert!(!is_after("2024-03-01T10:00:00Z", "2024-03-01T11:30:00+01:00"));
+    assert!(is_after("2024-03-01T10:00:00.500Z", "2024-03-01T10:00:00Z"));
+}
+
+/// Test Case 53.2: Only Others' Comments After the Last Visit Are New
+#[test]
+fn test_mark_new_comments() {
+    let mut comments = vec![
+        comment(1, "2024-03-01T09:00:00Z", false),
+        comment(2, "2024-03-01T11:00:00Z", false),
+        comment(3, "2024-03-01T12:00:00Z", true),
+    ];
+
+    let count = mark_new_comments(&mut comments, Some("2024-03-01T10:00:00+00:00"));
+
+    assert_eq!(count, 1);
+    let new: Vec<u64> = comments.iter().filter(|c| c.new_since_last_visit).map(|c| c.id).collect();
+    assert_eq!(new, vec![2]);
+}
+
+/// Test Case 53.3: Nothing Is New on a First Visit
+#[test]
+fn test_first_visit_has_nothing_new() {
+    let mut comments = vec![comment(1, "2024-03-01T09:00:00Z", false)];
+    comments[0].new_since_last_visit = true;
+
+    assert_eq!(mark_new_comments(&mut comments, None), 0);
+    assert!(!comments[0].new_since_last_visit);
+}
+
+/// Test Case 53.4: Seen-At Timestamps Are Validated and Normalized
+#[test]
+fn test_normalize_seen_at() {
+    assert_eq!(
+        normalize_seen_at(Some("2024-03-01T11:30:00+01:00")).unwrap(),
+        "2024-03-01T10:30:00+00:00"
+    );
+    assert!(normalize_seen_at(Some("yesterday")).is_err());
+    assert!(normalize_seen_at(None).is_ok());
+}
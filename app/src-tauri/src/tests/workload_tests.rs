@@ -0,0 +1,138 @@
+// Category 40: Review Workload Tests (workload.rs)
+// Tests for combining requested reviews with unsent local reviews
+
+use crate::review_storage::{ReviewComment, ReviewMetadata};
+use crate::workload::{self, AwaitingReview};
+use chrono::{DateTime, Utc};
+
+fn now() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2024-06-10T12:00:00Z").unwrap().with_timezone(&Utc)
+}
+
+fn awaiting(owner: &str, number: u64, created_at: &str, changed_files: u64) -> AwaitingReview {
+    AwaitingReview {
+        owner: owner.to_string(),
+        repo: "docs".to_string(),
+        number,
+        title: format!("PR {number}"),
+        author: "author".to_string(),
+        url: format!("https://github.com/{owner}/docs/pull/{number}"),
+        created_at: created_at.to_string(),
+        changed_files,
+        additions: 10,
+        deletions: 2,
+        has_local_review: false,
+    }
+}
+
+fn metadata(owner: &str, number: u64, created_at: &str) -> ReviewMetadata {
+    ReviewMetadata {
+        owner: owner.to_string(),
+        repo: "docs".to_string(),
+        pr_number: number,
+        commit_id: "abc123".to_string(),
+        body: None,
+        local_folder: None,
+        created_at: created_at.to_string(),
+        log_file_index: 0,
+        due_at: None,
+    }
+}
+
+fn comment(id: i64, deleted: bool) -> ReviewComment {
+    ReviewComment {
+        id,
+        owner: "acme".to_string(),
+        repo: "docs".to_string(),
+        pr_number: 1,
+        file_path: "guide.md".to_string(),
+        line_number: 1,
+        side: "RIGHT".to_string(),
+        body: "Comment".to_string(),
+        commit_id: "abc123".to_string(),
+        created_at: "2024-06-10T00:00:00Z".to_string(),
+        updated_at: "2024-06-10T00:00:00Z".to_string(),
+        deleted,
+        in_reply_to_id: None,
+        category: None,
+        general: false,
+        suggestion: None,
+    }
+}
+
+/// Test Case 40.1: Requested Reviews and Unsent Local Reviews Are Combined
+#[test]
+fn test_build_workload() {
+    let requested = vec![
+        awaiting("acme", 2, "2024-06-10T06:00:00Z", 3),
+        awaiting("acme", 1, "2024-06-09T12:00:00Z", 12),
+    ];
+    let local = vec![
+        (metadata("ACME", 1, "2024-06-10T10:00:00Z"), vec![comment(1, false), comment(2, true)]),
+        (metadata("other", 7, "2024-06-10T11:00:00Z"), vec![comment(3, true)]),
+    ];
+
+    let result = workload::build_workload(requested, &local, now());
+
+    let order: Vec<u64> = result.awaiting_review.iter().map(|pr| pr.number).collect();
+    assert_eq!(order, vec![1, 2]);
+    assert!(result.awaiting_review[0].has_local_review);
+    assert!(!result.awaiting_review[1].has_local_review);
+    assert_eq!(result.total_changed_files, 15);
+
+    // The review of other/docs#7 has nothing left to send
+    assert_eq!(result.local_reviews.len(), 1);
+    assert_eq!(result.local_reviews[0].unsent_comments, 1);
+    assert_eq!(result.local_reviews[0].changed_files, Some(12));
+
+    // Ages: 24h, 6h, 2h
+    assert_eq!(result.average_age_hours, Some(32.0 / 3.0));
+    assert!(result.github_error.is_none());
+}
+
+/// Test Case 40.2: Empty Workload Has No Average Age
+#[test]
+fn test_empty_workload() {
+    let result = workload::build_workload(Vec::new(), &[], now());
+
+    assert!(result.awaiting_review.is_empty());
+    assert!(result.local_reviews.is_empty());
+    assert_eq!(result.average_age_hours, None);
+    assert_eq!(result.total_changed_files, 0);
+}
+
+/// Test Case 40.3: Deadlines Sort First and Past Ones Are Overdue
+#[test]
+fn test_workload_deadlines() {
+    let mut overdue = metadata("acme", 3, "2024-06-10T09:00:00Z");
+    overdue.due_at = Some("2024-06-10T08:00:00+00:00".to_string());
+    let mut upcoming = metadata("acme", 4, "2024-06-01T09:00:00Z");
+    upcoming.due_at = Some("2024-06-12T23:59:59+00:00".to_string());
+    let local = vec![
+        (metadata("acme", 5, "2024-05-01T09:00:00Z"), vec![comment(1, false)]),
+        (upcoming, Vec::new()),
+        (overdue, Vec::new()),
+    ];
+
+    let result = workload::build_workload(Vec::new(), &local, now());
+
+    // Reviews with a deadline are listed even without unsent comments
+    let order: Vec<u64> = result.local_reviews.iter().map(|r| r.number).collect();
+    assert_eq!(order, vec![3, 4, 5]);
+    assert!(result.local_reviews[0].overdue);
+    assert!(!result.local_reviews[1].overdue);
+    assert_eq!(result.overdue_count, 1);
+}
+
+/// Test Case 40.4: Due Dates Accept a Day or a Timestamp
+#[test]
+fn test_normalize_due_at() {
+    assert_eq!(workload::normalize_due_at("2024-06-12").unwrap(), "2024-06-12T23:59:59+00:00");
+    assert_eq!(
+        workload::normalize_due_at("2024-06-12T10:00:00+02:00").unwrap(),
+        "2024-06-12T08:00:00+00:00"
+    );
+    assert!(workload::normalize_due_at("next friday").is_err());
+    assert!(workload::is_overdue("2024-06-10T11:59:00+00:00", now()));
+    assert!(!workload::is_overdue("not a date", now()));
+}
@@ -0,0 +1,57 @@
+use std::sync::RwLock;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::error::{AppError, AppResult};
+use crate::i18n::{current_locale, Locale};
+
+static CURRENT_ZONE: RwLock<Option<Tz>> = RwLock::new(None);
+
+/// Zone used for displayed times; `None` follows the system clock.
+pub fn set_zone(zone: Option<Tz>) {
+    if let Ok(mut current) = CURRENT_ZONE.write() {
+        *current = zone;
+    }
+}
+
+pub fn current_zone() -> Option<Tz> {
+    CURRENT_ZONE.read().map(|zone| *zone).unwrap_or_default()
+}
+
+/// An IANA zone such as `Europe/Berlin`. Blank or missing means the system clock.
+pub fn parse_zone(name: Option<&str>) -> AppResult<Option<Tz>> {
+    match name.map(str::trim).filter(|name| !name.is_empty()) {
+        None => Ok(None),
+        Some(name) => name
+            .parse::<Tz>()
+            .map(Some)
+            .map_err(|_| AppError::InvalidInput(format!("unknown time zone `{name}`"))),
+    }
+}
+
+fn pattern(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "%b %-d, %Y, %-I:%M %p %Z",
+        Locale::Ja => "%Y年%-m月%-d日 %H:%M %Z",
+        Locale::De => "%d.%m.%Y, %H:%M %Z",
+    }
+}
+
+/// An RFC 3339 timestamp written the way `locale` writes dates, in `zone`. Text that is
+/// not a timestamp comes back unchanged.
+pub fn format_timestamp(timestamp: &str, locale: Locale, zone: Option<Tz>) -> String {
+    let Ok(instant) = DateTime::parse_from_rfc3339(timestamp.trim()) else {
+        return timestamp.to_string();
+    };
+    let utc = instant.with_timezone(&Utc).naive_utc();
+    match zone {
+        Some(tz) => tz.from_utc_datetime(&utc).format(pattern(locale)).to_string(),
+        None => Local.from_utc_datetime(&utc).format(pattern(locale)).to_string(),
+    }
+}
+
+/// `timestamp` in the user's locale and time zone.
+pub fn display_time(timestamp: &str) -> String {
+    format_timestamp(timestamp, current_locale(), current_zone())
+}
@@ -0,0 +1,69 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::diff::added_lines;
+use crate::i18n::{tf, Msg};
+use crate::models::PullRequestFile;
+
+/// Placeholder markers, matched as whole uppercase words so prose like "todo list" is
+/// left alone.
+const MARKERS: &[&str] = &["TODO", "FIXME", "TBD"];
+
+/// A placeholder left in the changed text, ready to become a review comment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TodoFinding {
+    pub path: String,
+    /// 1-based line in the head version, usable as a RIGHT-side comment line.
+    pub line: u64,
+    pub marker: String,
+    /// The line the marker is on, trimmed.
+    pub text: String,
+    pub suggested_comment: String,
+}
+
+fn marker_regex() -> &'static Regex {
+    static MARKER: OnceLock<Regex> = OnceLock::new();
+    MARKER.get_or_init(|| {
+        Regex::new(&format!(r"\b({})\b", MARKERS.join("|"))).expect("marker pattern is valid")
+    })
+}
+
+/// Scan one line; a line with several markers is reported once, for the first.
+pub fn scan_line(path: &str, line_number: u64, text: &str) -> Option<TodoFinding> {
+    let marker = marker_regex().find(text)?.as_str();
+    Some(TodoFinding {
+        path: path.to_string(),
+        line: line_number,
+        marker: marker.to_string(),
+        text: text.trim().to_string(),
+        suggested_comment: tf(Msg::TodoCandidateComment, &[&marker]),
+    })
+}
+
+/// Markers on the lines a patch adds; placeholders already on the base branch are not
+/// this PR's doing.
+pub fn scan_patch(path: &str, patch: &str) -> Vec<TodoFinding> {
+    added_lines(patch)
+        .into_iter()
+        .filter_map(|(line_number, text)| scan_line(path, line_number, text))
+        .collect()
+}
+
+/// Scan each file's patch, or its whole head content when GitHub omitted the patch
+/// (large or newly added files).
+pub fn scan_files(files: &[PullRequestFile]) -> Vec<TodoFinding> {
+    files
+        .iter()
+        .flat_map(|file| match (&file.patch, &file.head_content) {
+            (Some(patch), _) => scan_patch(&file.path, patch),
+            (None, Some(content)) => content
+                .lines()
+                .enumerate()
+                .filter_map(|(index, line)| scan_line(&file.path, index as u64 + 1, line))
+                .collect(),
+            (None, None) => Vec::new(),
+        })
+        .collect()
+}
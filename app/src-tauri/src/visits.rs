@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+
+use crate::error::{AppError, AppResult};
+use crate::models::{CommentThread, PullRequestComment};
+
+/// Whether `timestamp` is strictly later than `since`. Both are RFC 3339; GitHub writes
+/// `Z` while we store `+00:00`, so they are compared as instants, not strings.
+pub fn is_after(timestamp: &str, since: &str) -> bool {
+    match (
+        DateTime::parse_from_rfc3339(timestamp),
+        DateTime::parse_from_rfc3339(since),
+    ) {
+        (Ok(timestamp), Ok(since)) => timestamp > since,
+        _ => timestamp > since,
+    }
+}
+
+/// Flag comments by others posted after `last_seen` and return how many there are.
+/// Nothing is new on a first visit, when `last_seen` is `None`.
+pub fn mark_new_comments(comments: &mut [PullRequestComment], last_seen: Option<&str>) -> usize {
+    let mut count = 0;
+    for comment in comments {
+        comment.new_since_last_visit = !comment.is_mine
+            && last_seen.is_some_and(|seen| is_after(&comment.created_at, seen));
+        if comment.new_since_last_visit {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// `mark_new_comments` for the copies of the comments held in threads.
+pub fn mark_new_in_threads(threads: &mut [CommentThread], last_seen: Option<&str>) {
+    for thread in threads {
+        mark_new_comments(std::slice::from_mut(&mut thread.root), last_seen);
+        mark_new_comments(&mut thread.replies, last_seen);
+    }
+}
+
+/// The visit time to store: `seen_at` when given (normalized to UTC), otherwise now.
+pub fn normalize_seen_at(seen_at: Option<&str>) -> AppResult<String> {
+    match seen_at {
+        Some(seen_at) => DateTime::parse_from_rfc3339(seen_at)
+            .map(|parsed| parsed.with_timezone(&Utc).to_rfc3339())
+            .map_err(|_| {
+                AppError::InvalidInput(format!("seen_at must be an RFC 3339 timestamp, got {seen_at}"))
+            }),
+        None => Ok(Utc::now().to_rfc3339()),
+    }
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+use crate::review_storage::{ReviewComment, ReviewMetadata};
+
+/// An open pull request where my review has been requested.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AwaitingReview {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub url: String,
+    pub created_at: String,
+    pub changed_files: u64,
+    pub additions: u64,
+    pub deletions: u64,
+    /// Set when a local review of this PR is already in progress.
+    pub has_local_review: bool,
+}
+
+/// A local review (GitHub PR or local folder) with comments that have not been sent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LocalReviewWork {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+    pub local_folder: Option<String>,
+    pub unsent_comments: usize,
+    pub started_at: String,
+    /// Known when the PR is also awaiting my review.
+    pub changed_files: Option<u64>,
+    pub due_at: Option<String>,
+    pub overdue: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReviewWorkload {
+    /// Oldest first.
+    pub awaiting_review: Vec<AwaitingReview>,
+    /// Soonest deadline first, then oldest first.
+    pub local_reviews: Vec<LocalReviewWork>,
+    /// Local reviews past their deadline.
+    pub overdue_count: usize,
+    /// Mean age of every item above, in hours.
+    pub average_age_hours: Option<f64>,
+    /// Files changed across the PRs awaiting review.
+    pub total_changed_files: u64,
+    /// Why the GitHub part is missing, e.g. when offline; local reviews are still listed.
+    pub github_error: Option<String>,
+}
+
+/// A review deadline as RFC 3339 UTC. A bare `YYYY-MM-DD` means the end of that day, UTC.
+pub fn normalize_due_at(value: &str) -> AppResult<String> {
+    let value = value.trim();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let end_of_day = date.and_hms_opt(23, 59, 59).unwrap_or_default();
+        return Ok(end_of_day.and_utc().to_rfc3339());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|due| due.with_timezone(&Utc).to_rfc3339())
+        .map_err(|_| {
+            AppError::InvalidInput(format!(
+                "due date must be YYYY-MM-DD or an RFC 3339 timestamp, got `{value}`"
+            ))
+        })
+}
+
+/// Whether a deadline has passed. Unparseable deadlines never are.
+pub fn is_overdue(due_at: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(due_at).is_ok_and(|due| due.with_timezone(&Utc) < now)
+}
+
+fn age_hours(timestamp: &str, now: DateTime<Utc>) -> Option<f64> {
+    let then = DateTime::parse_from_rfc3339(timestamp).ok()?;
+    Some((now - then.with_timezone(&Utc)).num_minutes().max(0) as f64 / 60.0)
+}
+
+/// Combine requested reviews with local reviews that still have unsent comments or a
+/// deadline.
+pub fn build_workload(
+    mut awaiting_review: Vec<AwaitingReview>,
+    local: &[(ReviewMetadata, Vec<ReviewComment>)],
+    now: DateTime<Utc>,
+) -> ReviewWorkload {
+    for pr in &mut awaiting_review {
+        pr.has_local_review = local.iter().any(|(metadata, _)| {
+            metadata.owner.eq_ignore_ascii_case(&pr.owner)
+                && metadata.repo.eq_ignore_ascii_case(&pr.repo)
+                && metadata.pr_number == pr.number
+        });
+    }
+
+    let mut local_reviews: Vec<LocalReviewWork> = local
+        .iter()
+        .filter_map(|(metadata, comments)| {
+            let unsent_comments = comments.iter().filter(|c| !c.deleted).count();
+            (unsent_comments > 0 || metadata.due_at.is_some()).then(|| LocalReviewWork {
+                owner: metadata.owner.clone(),
+                repo: metadata.repo.clone(),
+                number: metadata.pr_number,
+                local_folder: metadata.local_folder.clone(),
+                unsent_comments,
+                started_at: metadata.created_at.clone(),
+                changed_files: awaiting_review
+                    .iter()
+                    .find(|pr| {
+                        pr.owner.eq_ignore_ascii_case(&metadata.owner)
+                            && pr.repo.eq_ignore_ascii_case(&metadata.repo)
+                            && pr.number == metadata.pr_number
+                    })
+                    .map(|pr| pr.changed_files),
+                due_at: metadata.due_at.clone(),
+                overdue: metadata.due_at.as_deref().is_some_and(|due| is_overdue(due, now)),
+            })
+        })
+        .collect();
+
+    awaiting_review.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    local_reviews.sort_by(|a, b| {
+        // Reviews with a deadline first; stored deadlines are all UTC, so they sort as text
+        a.due_at
+            .is_none()
+            .cmp(&b.due_at.is_none())
+            .then_with(|| a.due_at.cmp(&b.due_at))
+            .then_with(|| a.started_at.cmp(&b.started_at))
+    });
+
+    let ages: Vec<f64> = awaiting_review
+        .iter()
+        .map(|pr| pr.created_at.as_str())
+        .chain(local_reviews.iter().map(|r| r.started_at.as_str()))
+        .filter_map(|timestamp| age_hours(timestamp, now))
+        .collect();
+    let average_age_hours = (!ages.is_empty()).then(|| ages.iter().sum::<f64>() / ages.len() as f64);
+
+    ReviewWorkload {
+        total_changed_files: awaiting_review.iter().map(|pr| pr.changed_files).sum(),
+        overdue_count: local_reviews.iter().filter(|r| r.overdue).count(),
+        awaiting_review,
+        local_reviews,
+        average_age_hours,
+        github_error: None,
+    }
+}
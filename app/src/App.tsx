@@ -3,8 +3,7 @@ import React, { useCallback, useEffect, useLayoutEffect, useMemo, useRef, useSta
 import { useQueries, useQuery, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { open as openDialog } from "@tauri-apps/plugin-dialog";
-import ReactMarkdown from "react-markdown";
+import ReactMarkdown, { type Options as MarkdownOptions } from "react-markdown";
 import remarkGfm from "remark-gfm";
 import remarkFrontmatter from "remark-frontmatter";
 import rehypeRaw from "rehype-raw";
@@ -28,6 +27,16 @@ import type {
   SourceRestoreState,
   LocalComment,
   ReviewMetadata,
+  ApiDeprecation,
+  RateLimitStatus,
+  SubmissionProgress,
+  ResetOptions,
+  SessionState,
+  DraftComment,
+  DatabaseCheck,
+  SafeModeStatus,
+  StaleReview,
+  MarkdownPreview,
 } from "./types";
 import {
   RETRY_CONFIG,
@@ -46,6 +55,8 @@ import {
 import { loadScrollCache, pruneScrollCache } from "./utils/scrollCache";
 import { parseLinePrefix, getImageMimeType, formatFileLabel, formatFileTooltip, formatFilePathWithLeadingEllipsis, isImageFile, isMarkdownFile } from "./utils/helpers";
 import { moveFullEditorDraftToInline, moveInlineDraftToFullEditor } from "./utils/commentDrafts";
+import { requestConfirmation } from "./utils/confirmation";
+import { RESET_EVERYTHING, RESET_KEEP_DRAFTS } from "./utils/resetAppData";
 import { MemoizedAsyncImage, MermaidCode, CommentThreadItem, MediaViewer, ConfirmDialog, CommentList, CommentComposer, CommentStatus, handleCtrlEnter as handleCtrlEnterUtil } from "./components";
 import type { MediaContent } from "./components";
 import { usePaneZoom, useViewedFiles, useMRUList, useLocalStorage, useTocSortedFiles, useFileContents, useCommentFiltering, useMarkdownComponents, useCommentMutations, useFileNavigation, useAuth, createLocalReview } from "./hooks";
@@ -143,6 +154,14 @@ if (typeof window !== "undefined") {
   }
 }
 
+/** " Paused for GitHub rate limit, resuming in 42s" while a submission waits, else "". */
+const formatRateLimitPause = (progress: SubmissionProgress) => {
+  if (!progress.waitTimeMs || !progress.waitReason) return "";
+  const seconds = Math.ceil(progress.waitTimeMs / 1000);
+  const limit = progress.waitReason === "secondary_rate_limit" ? "secondary rate limit" : "rate limit";
+  return ` Paused for GitHub ${limit}, resuming in ${seconds}s`;
+};
+
 const openDevtoolsWindow = () => {
   void invoke("cmd_open_devtools").catch((error) => {
     console.warn("Failed to open devtools", error);
@@ -223,7 +242,7 @@ function App() {
   const [commentContextMenu, setCommentContextMenu] = useState<{ x: number; y: number; comment: PullRequestComment | null } | null>(null);
   const [pendingReviewOverride, setPendingReviewOverride] = useState<PullRequestReview | null>(null);
   // Note: localComments, setLocalComments come from useCommentMutations hook
-  const [submissionProgress, setSubmissionProgress] = useState<{ current: number; total: number; file: string } | null>(null);
+  const [submissionProgress, setSubmissionProgress] = useState<SubmissionProgress | null>(null);
   const commentPanelBodyRef = useRef<HTMLDivElement>(null);
   const commentPanelLastScrollTopRef = useRef<number | null>(null);
   const preserveScrollPositionRef = useRef<number | null>(null);
@@ -232,6 +251,7 @@ function App() {
   const [editingComment, setEditingComment] = useState<PullRequestComment | null>(null);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
   const [showDeleteReviewConfirm, setShowDeleteReviewConfirm] = useState(false);
+  const [pendingReset, setPendingReset] = useState<ResetOptions | null>(null);
   // Note: submitReviewDialogMessage, setSubmitReviewDialogMessage come from useCommentMutations hook
   const [showDiff, setShowDiff] = useState(false);
   const [showSourceMenu, setShowSourceMenu] = useState(false);
@@ -312,12 +332,16 @@ function App() {
     return `...\\${parts[parts.length - 2]}\\${parts[parts.length - 1]}`;
   }, []);
 
-  const enterLocalDirectoryMode = useCallback((directory: string) => {
+  const enterLocalDirectoryMode = useCallback(async (directory: string, number?: number) => {
+    // Each folder has its own pseudo PR number, so reviews of different folders are kept apart
+    const folderNumber = number ?? await invoke<number>("cmd_get_local_folder_number", {
+      args: { directory },
+    });
     setActiveLocalDir(directory);
     addLocalDir(directory);
     setRepoInput(formatLocalDirDisplay(directory));
     setRepoRef({ owner: "__local__", repo: "local" });
-    setSelectedPr(1);
+    setSelectedPr(folderNumber);
     setSelectedFilePath(null);
     setPrSearchFilter("");
     setPrMode("repo");
@@ -330,12 +354,12 @@ function App() {
 
   const handlePickLocalFolder = useCallback(async () => {
     try {
-      const result = await openDialog({ directory: true, multiple: false });
-      const selected = typeof result === "string" ? result : null;
+      // The backend shows the picker, so only a folder the user chose becomes readable
+      const selected = await invoke<string | null>("cmd_add_local_root");
       if (!selected) {
         return;
       }
-      enterLocalDirectoryMode(selected);
+      await enterLocalDirectoryMode(selected);
     } catch (error) {
       console.error("Failed to open folder picker", error);
     }
@@ -703,7 +727,7 @@ function App() {
 
   // Listen for comment submission progress events
   useEffect(() => {
-    const unlisten = listen<{ current: number; total: number; file: string }>(
+    const unlisten = listen<SubmissionProgress>(
       'comment-submit-progress',
       (event) => {
         setSubmissionProgress(event.payload);
@@ -715,6 +739,35 @@ function App() {
     };
   }, []);
 
+  // Surface GitHub API deprecations before they turn into failing requests
+  useEffect(() => {
+    const unlisten = listen<ApiDeprecation>("api-deprecation", (event) => {
+      const { context, endpoint, sunset_at } = event.payload;
+      console.warn(
+        `GitHub API deprecation for ${context} (${endpoint})${sunset_at ? `, sunset ${sunset_at}` : ""}`
+      );
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Requests slow down on their own when a quota runs low; say why in the console
+  useEffect(() => {
+    const unlisten = listen<RateLimitStatus>("rate-limit-low", (event) => {
+      const { resource, remaining, limit, reset_at } = event.payload;
+      console.warn(
+        `GitHub ${resource} rate limit low: ${remaining} of ${limit} left until ${reset_at}; spacing out requests`
+      );
+      void queryClient.invalidateQueries({ queryKey: ["rate-limit"] });
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
   // Authentication managed by useAuth hook
   const {
     isLoading: isAuthLoading,
@@ -723,8 +776,16 @@ function App() {
     avatarUrl,
     startLogin,
     isLoggingIn,
+    deviceCode,
     logout,
     isLoggingOut,
+    accounts,
+    switchAccount,
+    addAccount,
+    removeAccount,
+    isChangingAccount,
+    resetAppData,
+    isResetting,
   } = useAuth({
     isOnline,
     onOffline: markOffline,
@@ -736,6 +797,15 @@ function App() {
     },
   });
 
+  // REST quota as GitHub last reported it, shown in the user menu
+  const rateLimitQuery = useQuery({
+    queryKey: ["rate-limit"],
+    queryFn: () => invoke<RateLimitStatus[]>("cmd_get_rate_limit", { args: { refresh: false } }),
+    enabled: isAuthenticated && isOnline && isUserMenuOpen,
+    staleTime: 0,
+  });
+  const coreRateLimit = rateLimitQuery.data?.find((status) => status.resource === "core") ?? null;
+
   // Handle app wake from sleep/hibernation - refetch all queries
   useEffect(() => {
     let lastVisibilityChange = Date.now();
@@ -787,6 +857,75 @@ function App() {
     },
   });
 
+  // Set when the app started in safe mode after crashing repeatedly at startup
+  const [safeMode, setSafeMode] = useState<SafeModeStatus | null>(null);
+  const [safeModeMessage, setSafeModeMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    // The event can fire before this listener exists, so ask as well
+    void invoke<SafeModeStatus>("cmd_get_safe_mode_status").then((status) => {
+      if (status.active) {
+        setSafeMode(status);
+      }
+    });
+    const unlisten = listen<SafeModeStatus>("safe-mode", (event) => {
+      setSafeMode(event.payload);
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  const handleSafeModeAction = useCallback(async (command: string, done: string) => {
+    try {
+      if (command === "cmd_check_database") {
+        const check = await invoke<DatabaseCheck>(command);
+        setSafeModeMessage(
+          check.ok ? "The database looks fine." : `Database problems: ${check.problems.join("; ")}`,
+        );
+      } else {
+        await invoke(command);
+        setSafeModeMessage(done);
+      }
+    } catch (error) {
+      console.error(`Safe mode action ${command} failed:`, error);
+      setSafeModeMessage(String(error));
+    }
+  }, []);
+
+  // Reviews of merged or closed PRs found by the background check
+  const [staleReviews, setStaleReviews] = useState<StaleReview[]>([]);
+  const [isClearingStaleReviews, setIsClearingStaleReviews] = useState(false);
+
+  useEffect(() => {
+    const unlistenFound = listen<StaleReview[]>("stale-reviews-found", (event) => {
+      setStaleReviews(event.payload);
+    });
+    const unlistenCleared = listen<StaleReview[]>("stale-reviews-cleared", () => {
+      setStaleReviews([]);
+      void queryClient.invalidateQueries({ queryKey: ["prs-under-review"] });
+    });
+
+    return () => {
+      unlistenFound.then(fn => fn());
+      unlistenCleared.then(fn => fn());
+    };
+  }, [queryClient]);
+
+  const handleClearStaleReviews = useCallback(async () => {
+    setIsClearingStaleReviews(true);
+    try {
+      await invoke<StaleReview[]>("cmd_prune_stale_reviews", { args: { dryRun: false } });
+      setStaleReviews([]);
+      await prsUnderReviewQuery.refetch();
+    } catch (error) {
+      console.error("Failed to clear reviews of closed PRs:", error);
+    } finally {
+      setIsClearingStaleReviews(false);
+    }
+  }, [prsUnderReviewQuery]);
+
   // Query all MRU repos for OPEN PRs with pending reviews
   const mruOpenPrsQueries = useQueries({
     queries: repoMRU.slice(0, 10).map(repoString => {
@@ -805,10 +944,12 @@ function App() {
           
           // Fetch open PRs with has_pending_review flag already populated by backend
           const prs = await invoke<PullRequestSummary[]>("cmd_list_pull_requests", {
-            owner,
-            repo,
-            state: "open",
-            currentLogin,
+            args: {
+              owner,
+              repo,
+              state: "open",
+              currentLogin,
+            },
           });
           
           // Filter for PRs with pending reviews and convert to PrUnderReview format
@@ -884,10 +1025,12 @@ function App() {
           
           // Fetch closed PRs with has_pending_review flag already populated by backend
           const prs = await invoke<PullRequestSummary[]>("cmd_list_pull_requests", {
-            owner,
-            repo,
-            state: "closed",
-            currentLogin,
+            args: {
+              owner,
+              repo,
+              state: "closed",
+              currentLogin,
+            },
           });
           
           // Filter for PRs with pending reviews and convert to PrUnderReview format
@@ -947,9 +1090,11 @@ function App() {
     queryFn: async () => {
       try {
         const data = await invoke<PullRequestSummary[]>("cmd_list_pull_requests", {
-          owner: repoRef?.owner,
-          repo: repoRef?.repo,
-          state: showClosedPRs ? "all" : "open",
+          args: {
+            owner: repoRef?.owner,
+            repo: repoRef?.repo,
+            state: showClosedPRs ? "all" : "open",
+          },
         });
         markOnline();
         return data;
@@ -995,7 +1140,9 @@ function App() {
     queryFn: async () => {
       if (activeLocalDir) {
         return await invoke<PullRequestDetail>("cmd_load_local_directory", {
-          directory: activeLocalDir,
+          args: {
+            directory: activeLocalDir,
+          },
         });
       }
       if (isLocalRepo) {
@@ -1006,11 +1153,20 @@ function App() {
       // Always try network first (to detect coming back online)
       try {
         const data = await invoke<PullRequestDetail>("cmd_get_pull_request", {
-          owner: repoRef?.owner,
-          repo: repoRef?.repo,
-          number: selectedPr,
-          currentLogin,
+          args: {
+            owner: repoRef?.owner,
+            repo: repoRef?.repo,
+            number: selectedPr,
+            currentLogin,
+          },
         });
+
+        // GitHub was out of reach and the backend served its copy from the last fetch
+        if (data.stale) {
+          console.log(`📦 Loaded PR #${selectedPr} from the backend cache (${data.cached_at})`);
+          markOffline();
+          return data;
+        }
         
         // Successful network request - mark online
         markOnline();
@@ -1072,6 +1228,22 @@ function App() {
 
   const { refetch: refetchPullDetail } = pullDetailQuery;
   const prDetail = pullDetailQuery.data;
+  const conflictingFiles = useMemo(
+    () => new Set(prDetail?.merge_status?.conflicting_files ?? []),
+    [prDetail],
+  );
+
+  // Mark the PR's comments as seen when leaving it, so the next visit flags only newer ones
+  useEffect(() => {
+    if (!repoRef || !selectedPr || isLocalRepo || isLocalDirectoryMode) return;
+    const { owner, repo } = repoRef;
+    const prNumber = selectedPr;
+    return () => {
+      invoke("cmd_mark_pull_request_seen", { args: { owner, repo, prNumber, seenAt: null } }).catch((err) => {
+        console.error("Failed to mark pull request as seen:", err);
+      });
+    };
+  }, [repoRef, selectedPr, isLocalRepo, isLocalDirectoryMode]);
 
   useEffect(() => {
     if (pullDetailQuery.isError) {
@@ -1108,13 +1280,17 @@ function App() {
       for (const file of prDetail.files) {
         try {
           const [headContent, baseContent] = await invoke<[string | null, string | null]>("cmd_get_file_contents", {
-            owner: repoRef.owner,
-            repo: repoRef.repo,
-            filePath: file.path,
-            baseSha: prDetail.base_sha,
-            headSha: prDetail.head_sha,
-            status: file.status,
-            previousFilename: file.previous_filename ?? null,
+            args: {
+              owner: repoRef.owner,
+              repo: repoRef.repo,
+              filePath: file.path,
+              baseSha: prDetail.base_sha,
+              headSha: prDetail.head_sha,
+              headOwner: prDetail.head_owner ?? null,
+              headRepo: prDetail.head_repo ?? null,
+              status: file.status,
+              previousFilename: file.previous_filename ?? null,
+            },
           });
           
           await offlineCache.cacheFileContent(
@@ -1278,10 +1454,12 @@ function App() {
       console.log(`🔄 Updating local review commit from ${pendingReview.commit_id} to ${prDetail.head_sha}`);
       
       invoke("cmd_local_update_review_commit", {
-        owner: repoRef.owner,
-        repo: repoRef.repo,
-        prNumber: selectedPr,
-        newCommitId: prDetail.head_sha,
+        args: {
+          owner: repoRef.owner,
+          repo: repoRef.repo,
+          prNumber: selectedPr,
+          newCommitId: prDetail.head_sha,
+        },
       })
         .then(() => {
           console.log('✅ Local review commit ID updated');
@@ -1330,9 +1508,11 @@ function App() {
 
       try {
         const localCommentData = await invoke<LocalComment[]>("cmd_local_get_comments", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+          },
         });
 
         if (localCommentData.length > 0) {
@@ -1388,11 +1568,13 @@ function App() {
       const fetchPendingComments = async () => {
         try {
           const pendingComments = await invoke<PullRequestComment[]>("cmd_get_pending_review_comments", {
-            owner: repoRef.owner,
-            repo: repoRef.repo,
-            prNumber: prDetail.number,
-            reviewId: pendingReviewFromServer.id,
-            currentLogin: userLogin ?? null,
+            args: {
+              owner: repoRef.owner,
+              repo: repoRef.repo,
+              prNumber: prDetail.number,
+              reviewId: pendingReviewFromServer.id,
+              currentLogin: userLogin ?? null,
+            },
           });
           setLocalComments(pendingComments);
           // Set the pending review override so reviewAwareComments includes these comments
@@ -1618,12 +1800,16 @@ function App() {
             queryKey: cacheKey,
             queryFn: async () => {
               const [headContent, baseContent] = await invoke<[string | null, string | null]>("cmd_get_file_contents", {
-                owner: repoRef.owner,
-                repo: repoRef.repo,
-                filePath: file.path,
-                baseSha: prDetail.base_sha,
-                headSha: prDetail.head_sha,
-                status: file.status,
+                args: {
+                  owner: repoRef.owner,
+                  repo: repoRef.repo,
+                  filePath: file.path,
+                  baseSha: prDetail.base_sha,
+                  headSha: prDetail.head_sha,
+                  headOwner: prDetail.head_owner ?? null,
+                  headRepo: prDetail.head_repo ?? null,
+                  status: file.status,
+                },
               });
               return { headContent, baseContent };
             },
@@ -1654,9 +1840,11 @@ function App() {
     if (repoRef && prDetail) {
       try {
         const localCommentData = await invoke<LocalComment[]>("cmd_local_get_comments", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+          },
         });
 
         if (localCommentData.length > 0) {
@@ -1724,6 +1912,26 @@ function App() {
     closeUserMenu();
     logout();
   }, [closeUserMenu, logout]);
+
+  const [newAccountName, setNewAccountName] = useState<string | null>(null);
+
+  const handleSwitchAccount = useCallback((name: string) => {
+    closeUserMenu();
+    switchAccount(name);
+  }, [closeUserMenu, switchAccount]);
+
+  const handleAddAccount = useCallback(() => {
+    const name = newAccountName?.trim();
+    if (!name) return;
+    closeUserMenu();
+    setNewAccountName(null);
+    addAccount(name);
+  }, [closeUserMenu, newAccountName, addAccount]);
+
+  const handleRequestReset = useCallback((options: ResetOptions) => {
+    closeUserMenu();
+    setPendingReset(options);
+  }, [closeUserMenu]);
   const pullsErrorMessage = pullsQuery.isError
     ? pullsQuery.error instanceof Error
       ? pullsQuery.error.message
@@ -1737,7 +1945,13 @@ function App() {
   }, [closeUserMenu]);
 
   // File content loading with offline caching
-  const { selectedFile } = useFileContents({
+  const preferencesQuery = useQuery({
+    queryKey: ["preferences"],
+    queryFn: () => invoke<{ diff_view: { markdown_preview: MarkdownPreview } }>("cmd_get_preferences"),
+    staleTime: Infinity,
+  });
+
+  const { selectedFile, renderedHeadHtml } = useFileContents({
     selectedFilePath,
     repoRef,
     prDetail,
@@ -1747,8 +1961,21 @@ function App() {
     markOnline,
     markOffline,
     activeLocalDir,
+    markdownPreview: preferencesQuery.data?.diff_view.markdown_preview ?? "local",
   });
 
+  // GitHub's HTML goes through the same raw + sanitize pipeline as local markdown, so the
+  // preview keeps the app's link handling and never renders unsanitized markup
+  const remarkPreviewPlugins = useMemo<NonNullable<MarkdownOptions["remarkPlugins"]>>(() => {
+    if (renderedHeadHtml === null) {
+      return [remarkGfm, [remarkFrontmatter, { type: 'yaml', marker: '-' }]];
+    }
+    const replaceWithGithubHtml = () => (tree: { children: unknown[] }) => {
+      tree.children = [{ type: "html", value: renderedHeadHtml }];
+    };
+    return [replaceWithGithubHtml];
+  }, [renderedHeadHtml]);
+
   // Memoize markdown preview content to prevent re-rendering on every keystroke
   const memoizedMarkdownContent = useMemo(() => {
     if (!selectedFile || !isMarkdownFile(selectedFile)) {
@@ -2030,7 +2257,15 @@ function App() {
         return;
       }
 
-      const stored = getScrollPosition("sourcePane", selectedFileCacheKey);
+      let stored = getScrollPosition("sourcePane", selectedFileCacheKey);
+      // The previous session's anchor line wins once the restored file's contents are in
+      const restoredAnchor = restoredScrollAnchorRef.current;
+      if (restoredAnchor && restoredAnchor.filePath !== selectedFilePath) {
+        restoredScrollAnchorRef.current = null;
+      } else if (restoredAnchor && selectedFile?.head_content != null) {
+        restoredScrollAnchorRef.current = null;
+        stored = editorInstance.getTopForLineNumber?.(restoredAnchor.line) ?? stored;
+      }
       const target = stored ?? 0;
       const currentTop = editorInstance.getScrollTop?.() ?? 0;
       const shouldEnforceRestore =
@@ -2219,10 +2454,67 @@ function App() {
       : "";
   const formattedRepoTitle = activeLocalDir ? activeLocalDir : formattedRepo;
 
+  // Reopen whatever was open when the app last closed or crashed
+  const sessionRestoredRef = useRef(false);
+  const restoredDraftRef = useRef<DraftComment | null>(null);
+  // Selected once the restored PR's file list has loaded
+  const restoredFileRef = useRef<string | null>(null);
+  // Scrolled to once the restored file's contents are in the editor
+  const restoredScrollAnchorRef = useRef<{ filePath: string; line: number } | null>(null);
+  useEffect(() => {
+    let cancelled = false;
+    const restore = async () => {
+      try {
+        const session = await invoke<SessionState | null>("cmd_get_session");
+        if (cancelled || !session) return;
+        restoredDraftRef.current = session.draft;
+        if (session.local_folder) {
+          await enterLocalDirectoryMode(session.local_folder);
+        } else if (session.owner && session.repo && session.pr_number) {
+          setRepoInput(`${session.owner}/${session.repo}`);
+          setRepoRef({ owner: session.owner, repo: session.repo });
+          setSelectedPr(session.pr_number);
+        }
+        restoredFileRef.current = session.selected_file;
+        if (session.selected_file && session.scroll_anchor_line) {
+          restoredScrollAnchorRef.current = {
+            filePath: session.selected_file,
+            line: session.scroll_anchor_line,
+          };
+        }
+      } catch (error) {
+        console.warn("Failed to restore session", error);
+      } finally {
+        sessionRestoredRef.current = true;
+      }
+    };
+    void restore();
+    return () => {
+      cancelled = true;
+    };
+    // Only on mount
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
   // Load drafts from localStorage on mount
   useEffect(() => {
     if (repoRef && selectedPr) {
       const key = `drafts_${repoRef.owner}_${repoRef.repo}_${selectedPr}`;
+      const restoredDraft = restoredDraftRef.current;
+      if (restoredDraft?.file_path && restoredDraft.body.trim()) {
+        // The session's copy of the draft, in case the crash came before localStorage caught up
+        restoredDraftRef.current = null;
+        try {
+          const drafts = JSON.parse(localStorage.getItem(key) || "{}");
+          const fileDraft = drafts[restoredDraft.file_path] || {};
+          if (!fileDraft.inline?.trim()) {
+            drafts[restoredDraft.file_path] = { ...fileDraft, inline: restoredDraft.body };
+            localStorage.setItem(key, JSON.stringify(drafts));
+          }
+        } catch (e) {
+          console.error('Failed to restore session draft:', e);
+        }
+      }
       const stored = localStorage.getItem(key);
       if (stored) {
         try {
@@ -2280,6 +2572,63 @@ function App() {
     return () => clearTimeout(timeoutId);
   }, [draftsByFile, repoRef, selectedPr]);
 
+  // Save where we are so a crash or restart can come back to it. Scrolling only moves the
+  // anchor line, so it goes through refs rather than re-rendering on every scroll event.
+  const sessionStateRef = useRef<SessionState | null>(null);
+  const scrollAnchorLineRef = useRef<number | null>(null);
+  const sessionSaveTimeoutRef = useRef<number | null>(null);
+  const scheduleSessionSave = useCallback(() => {
+    if (sessionSaveTimeoutRef.current !== null) {
+      window.clearTimeout(sessionSaveTimeoutRef.current);
+    }
+    sessionSaveTimeoutRef.current = window.setTimeout(() => {
+      sessionSaveTimeoutRef.current = null;
+      const state = sessionStateRef.current;
+      if (!state) return;
+      const session: SessionState = { ...state, scroll_anchor_line: scrollAnchorLineRef.current };
+      invoke("cmd_save_session", { args: { session } }).catch((error) => {
+        console.warn("Failed to save session", error);
+      });
+    }, 1000);
+  }, []);
+
+  // First line of the selected file in view, as `scroll_anchor_line` for the next session
+  const recordScrollAnchor = useCallback(
+    (editor: { getVisibleRanges?: () => { startLineNumber: number }[] }) => {
+      if (restoredScrollAnchorRef.current) return;
+      const line = editor.getVisibleRanges?.()[0]?.startLineNumber ?? null;
+      if (line === scrollAnchorLineRef.current) return;
+      scrollAnchorLineRef.current = line;
+      scheduleSessionSave();
+    },
+    [scheduleSessionSave],
+  );
+
+  useEffect(() => {
+    if (!sessionRestoredRef.current || restoredFileRef.current) return;
+    const inline = selectedFilePath ? draftsByFile[selectedFilePath]?.inline : undefined;
+    sessionStateRef.current = {
+      owner: activeLocalDir ? null : repoRef?.owner ?? null,
+      repo: activeLocalDir ? null : repoRef?.repo ?? null,
+      pr_number: activeLocalDir ? null : selectedPr,
+      local_folder: activeLocalDir,
+      selected_file: selectedFilePath,
+      scroll_anchor_line: null,
+      draft: selectedFilePath && inline?.trim()
+        ? { file_path: selectedFilePath, line: null, body: inline }
+        : null,
+    };
+    scheduleSessionSave();
+  }, [activeLocalDir, draftsByFile, repoRef, selectedFilePath, selectedPr, scheduleSessionSave]);
+
+  useEffect(() => {
+    return () => {
+      if (sessionSaveTimeoutRef.current !== null) {
+        window.clearTimeout(sessionSaveTimeoutRef.current);
+      }
+    };
+  }, []);
+
   // Automatically restore inline draft when file with draft is selected
   useEffect(() => {
     if (selectedFilePath && draftsByFile[selectedFilePath]?.inline) {
@@ -2401,8 +2750,13 @@ function App() {
   // Auto-select first file when file list changes
   useEffect(() => {
     if (filteredSortedFiles.length > 0) {
+      const restored = restoredFileRef.current;
+      restoredFileRef.current = null;
       // Use setSelectedFilePath (not navigateToFile) for auto-selection to avoid polluting history
       setSelectedFilePath((current: string | null) => {
+        if (restored && filteredSortedFiles.some((file) => file.path === restored)) {
+          return restored;
+        }
         if (current && filteredSortedFiles.some((file) => file.path === current)) {
           return current;
         }
@@ -2944,13 +3298,14 @@ function App() {
         hasPendingReview = !!myPendingReview;
       }
       
-      const viewedCount = viewed.length;
+      // Files with every hunk reviewed count too; only the backend tracks those
+      const viewedCount = new Set([...viewed, ...(pr.hunk_reviewed_files ?? [])]).size;
       
       // Get cached state/merged if not already on pr
       let state = pr.state;
       let merged = pr.merged;
       let locked = pr.locked;
-      if (state === undefined || merged === undefined) {
+      if (state == null || merged == null) {
         const cachedMetadata = prMetadata[prKey];
         if (cachedMetadata) {
           state = cachedMetadata.state;
@@ -3016,9 +3371,11 @@ function App() {
             queryKey: ["pull-request-metadata", pr.owner, pr.repo, pr.number],
             queryFn: async () =>
               await invoke<PullRequestMetadata>("cmd_get_pull_request_metadata", {
-                owner: pr.owner,
-                repo: pr.repo,
-                number: pr.number,
+                args: {
+                  owner: pr.owner,
+                  repo: pr.repo,
+                  number: pr.number,
+                },
               }),
             staleTime: 60 * 60 * 1000,
           });
@@ -3066,10 +3423,12 @@ function App() {
           queryKey: ["pull-request", pr.owner, pr.repo, pr.number, userLogin],
           queryFn: async () => {
             return await invoke<PullRequestDetail>("cmd_get_pull_request", {
-              owner: pr.owner,
-              repo: pr.repo,
-              number: pr.number,
-              currentLogin: userLogin,
+              args: {
+                owner: pr.owner,
+                repo: pr.repo,
+                number: pr.number,
+                currentLogin: userLogin,
+              },
             });
           },
           staleTime: 60 * 60 * 1000, // Cache for 1 hour
@@ -3087,10 +3446,12 @@ function App() {
           queryKey: ["pull-request", pr.owner, pr.repo, pr.number, userLogin],
           queryFn: async () => {
             return await invoke<PullRequestDetail>("cmd_get_pull_request", {
-              owner: pr.owner,
-              repo: pr.repo,
-              number: pr.number,
-              currentLogin: userLogin,
+              args: {
+                owner: pr.owner,
+                repo: pr.repo,
+                number: pr.number,
+                currentLogin: userLogin,
+              },
             });
           },
         });
@@ -3157,7 +3518,7 @@ function App() {
     const isLocalUnderReview = pr.owner === "__local__" && pr.repo === "local" && !!pr.local_folder;
 
     if (isLocalUnderReview && pr.local_folder) {
-      enterLocalDirectoryMode(pr.local_folder);
+      void enterLocalDirectoryMode(pr.local_folder, pr.number);
       setIsPrCommentsView(false);
       setIsPrCommentComposerOpen(false);
       return;
@@ -3233,6 +3594,10 @@ function App() {
     startLogin();
   }, [startLogin]);
 
+  const handleDeviceLogin = useCallback(() => {
+    startLogin("device");
+  }, [startLogin]);
+
   // Get mutation functions from the hook (state already destructured above)
   const {
     submitCommentMutation: hookSubmitCommentMutation,
@@ -3418,7 +3783,8 @@ function App() {
           prTitle: prDetail?.title ?? undefined,
         },
         {
-          onSuccess: () => {
+          onSuccess: (deleted) => {
+            if (!deleted) return;
             // App-specific: clear UI state
             setPendingReviewOverride(null);
             void refetchPullDetail();
@@ -3473,7 +3839,8 @@ function App() {
       options?: { onSuccess?: () => void; onError?: (error: unknown) => void }
     ) => {
       hookDeleteCommentMutation.mutate(commentId, {
-        onSuccess: async () => {
+        onSuccess: async (deleted) => {
+          if (!deleted) return;
           // App-specific: clear editing state
           setFileCommentDraft("");
           setEditingCommentId(null);
@@ -3501,20 +3868,34 @@ function App() {
             if (repoRef && prDetail) {
               try {
                 const remainingComments = await invoke<PullRequestComment[]>("cmd_local_get_comments", {
-                  owner: repoRef.owner,
-                  repo: repoRef.repo,
-                  prNumber: prDetail.number,
+                  args: {
+                    owner: repoRef.owner,
+                    repo: repoRef.repo,
+                    prNumber: prDetail.number,
+                  },
                 });
                 
                 if (remainingComments.length === 0) {
-                  await invoke("cmd_local_clear_review", {
+                  const confirmationToken = await requestConfirmation({
+                    kind: "clear_local_review",
                     owner: repoRef.owner,
                     repo: repoRef.repo,
-                    prNumber: prDetail.number,
+                    pr_number: prDetail.number,
                   });
-                  
-                  setPendingReviewOverride(null);
-                  setIsInlineCommentOpen(false);
+                  // Keep the empty review when the user declines to clear it
+                  if (confirmationToken) {
+                    await invoke("cmd_local_clear_review", {
+                      args: {
+                        owner: repoRef.owner,
+                        repo: repoRef.repo,
+                        prNumber: prDetail.number,
+                        confirmationToken,
+                      },
+                    });
+
+                    setPendingReviewOverride(null);
+                    setIsInlineCommentOpen(false);
+                  }
                 }
               } catch (error) {
                 console.error("Failed to check remaining comments or delete review:", error);
@@ -3579,9 +3960,11 @@ function App() {
       console.log("Local comments exist, loading review metadata");
       try {
         const metadata = await invoke<ReviewMetadata | null>("cmd_local_get_review_metadata", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+          },
         });
 
         if (metadata) {
@@ -3673,25 +4056,29 @@ function App() {
       console.log("Starting review first before adding comment...");
       // First, start the review to create the review metadata
       await invoke("cmd_local_start_review", {
-        owner: repoRef.owner,
-        repo: repoRef.repo,
-        prNumber: prDetail.number,
-        commitId: prDetail.head_sha,
-        body: null,
+        args: {
+          owner: repoRef.owner,
+          repo: repoRef.repo,
+          prNumber: prDetail.number,
+          commitId: prDetail.head_sha,
+          body: null,
+        },
       });
 
       console.log("Review started, now adding comment...");
       // Now save the comment to local storage
       await invoke("cmd_local_add_comment", {
-        owner: repoRef.owner,
-        repo: repoRef.repo,
-        prNumber: prDetail.number,
-        filePath: selectedFilePath,
-        lineNumber: parsedLine,
-        side: fileCommentSide,
-        body: trimmed,
-        commitId: prDetail.head_sha,
-        inReplyToId: null,
+        args: {
+          owner: repoRef.owner,
+          repo: repoRef.repo,
+          prNumber: prDetail.number,
+          filePath: selectedFilePath,
+          lineNumber: parsedLine,
+          side: fileCommentSide,
+          body: trimmed,
+          commitId: prDetail.head_sha,
+          inReplyToId: null,
+        },
       });
 
       // Clear the form
@@ -3748,11 +4135,13 @@ function App() {
       try {
         console.log("Fetching pending review comments for review:", pendingReviewFromServer.id);
         const pendingComments = await invoke<PullRequestComment[]>("cmd_get_pending_review_comments", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
-          reviewId: pendingReviewFromServer.id,
-          currentLogin: userLogin ?? null,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+            reviewId: pendingReviewFromServer.id,
+            currentLogin: userLogin ?? null,
+          },
         });
         console.log("Fetched pending review comments:", pendingComments);
         setLocalComments(pendingComments);
@@ -3814,11 +4203,23 @@ function App() {
     } else {
       // Local review - clear from database
       try {
-        await invoke("cmd_local_clear_review", {
+        const confirmationToken = await requestConfirmation({
+          kind: "clear_local_review",
           owner: repoRef.owner,
           repo: repoRef.repo,
-          prNumber: prDetail.number,
-          prTitle: prDetail.title || null,
+          pr_number: prDetail.number,
+        });
+        if (!confirmationToken) {
+          return;
+        }
+        await invoke("cmd_local_clear_review", {
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+            prTitle: prDetail.title || null,
+            confirmationToken,
+          },
         });
         setPendingReviewOverride(null);
         setLocalComments([]);
@@ -4047,7 +4448,18 @@ function App() {
           <button onClick={handleLogin} disabled={isLoggingIn}>
             {isLoggingIn ? "Waiting for GitHub…" : "Continue with GitHub"}
           </button>
+          {!isLoggingIn && (
+            <button className="login-actions__secondary" onClick={handleDeviceLogin}>
+              Sign in with a code instead
+            </button>
+          )}
         </div>
+        {deviceCode && (
+          <p className="login-hint">
+            Enter <strong className="login-device-code">{deviceCode.user_code}</strong> at{" "}
+            {deviceCode.verification_uri} to finish signing in.
+          </p>
+        )}
       </div>
     );
   }
@@ -4116,6 +4528,17 @@ function App() {
               </button>
               {isUserMenuOpen && (
                 <div className="user-menu__popover" role="menu">
+                  {coreRateLimit && (
+                    <div
+                      className={`user-menu__rate-limit${
+                        coreRateLimit.remaining <= coreRateLimit.limit / 10 ? " user-menu__rate-limit--low" : ""
+                      }`}
+                      title={`Resets at ${new Date(coreRateLimit.reset_at).toLocaleTimeString()}`}
+                    >
+                      GitHub API: {coreRateLimit.remaining.toLocaleString()} of{" "}
+                      {coreRateLimit.limit.toLocaleString()} requests left
+                    </div>
+                  )}
                   <button
                     type="button"
                     className="user-menu__item"
@@ -4145,6 +4568,63 @@ function App() {
                       Debugging
                     </button>
                   )}
+                  {accounts
+                    .filter((account) => !account.active)
+                    .map((account) => (
+                      <div key={account.name} className="user-menu__account">
+                        <button
+                          type="button"
+                          className="user-menu__item"
+                          onClick={() => handleSwitchAccount(account.name)}
+                          disabled={isChangingAccount}
+                          role="menuitem"
+                        >
+                          Switch to {account.name}
+                          {account.login ? ` (${account.login})` : ""}
+                        </button>
+                        <button
+                          type="button"
+                          className="user-menu__account-remove"
+                          onClick={() => removeAccount(account.name)}
+                          disabled={isChangingAccount}
+                          aria-label={`Remove account ${account.name}`}
+                          title="Remove account"
+                        >
+                          ×
+                        </button>
+                      </div>
+                    ))}
+                  {newAccountName === null ? (
+                    <button
+                      type="button"
+                      className="user-menu__item"
+                      onClick={() => setNewAccountName("")}
+                      disabled={isChangingAccount}
+                      role="menuitem"
+                    >
+                      Add Account…
+                    </button>
+                  ) : (
+                    <form
+                      className="user-menu__account-form"
+                      onSubmit={(event) => {
+                        event.preventDefault();
+                        handleAddAccount();
+                      }}
+                    >
+                      <input
+                        type="text"
+                        value={newAccountName}
+                        onChange={(event) => setNewAccountName(event.target.value)}
+                        placeholder="Account name, e.g. work"
+                        maxLength={32}
+                        autoFocus
+                      />
+                      <button type="submit" disabled={!newAccountName.trim()}>
+                        Sign in
+                      </button>
+                    </form>
+                  )}
                   <button
                     type="button"
                     className="user-menu__item"
@@ -4154,6 +4634,24 @@ function App() {
                   >
                     {isLoggingOut ? "Signing out…" : "Logout"}
                   </button>
+                  <button
+                    type="button"
+                    className="user-menu__item"
+                    onClick={() => handleRequestReset(RESET_KEEP_DRAFTS)}
+                    disabled={isResetting}
+                    role="menuitem"
+                  >
+                    Logout, Keep Drafts
+                  </button>
+                  <button
+                    type="button"
+                    className="user-menu__item"
+                    onClick={() => handleRequestReset(RESET_EVERYTHING)}
+                    disabled={isResetting}
+                    role="menuitem"
+                  >
+                    Remove All App Data…
+                  </button>
                 </div>
               )}
             </div>
@@ -4580,7 +5078,7 @@ function App() {
                                 <>
                                   {/* Render all comments in thread */}
                                   {allCommentsInThread.map((comment: any, index: number) => {
-                                    const formattedTimestamp = new Date(comment.created_at).toLocaleString();
+                                    const formattedTimestamp = comment.display_time || new Date(comment.created_at).toLocaleString();
                                     const isPendingGitHubReviewComment = comment.review_id === pendingReview?.id && pendingReview?.html_url;
                                     const isPendingLocalReviewComment = comment.is_draft && !pendingReview?.html_url;
                                     
@@ -5365,6 +5863,7 @@ function App() {
                     {submissionProgress && (
                       <div className="comment-panel__progress">
                         Submitting comment {submissionProgress.current} of {submissionProgress.total}...
+                        {formatRateLimitPause(submissionProgress)}
                       </div>
                     )}
                     <div className="pr-comments-view__footer-buttons">
@@ -5505,7 +6004,9 @@ function App() {
                                         className="repo-form__mru-item"
                                         onClick={() => {
                                           setShowRepoMRU(false);
-                                          enterLocalDirectoryMode(dir);
+                                          enterLocalDirectoryMode(dir).catch((error) => {
+                                            console.error("Failed to open local folder", error);
+                                          });
                                         }}
                                         title={dir}
                                       >
@@ -5648,6 +6149,60 @@ function App() {
                           />
                         </div>
                       )}
+                      {safeMode && (
+                        <div className="safe-mode-notice">
+                          <span>
+                            Started in safe mode after {safeMode.startup_crashes} crashes at
+                            startup; the saved session, plugins and background checks were skipped.
+                            {safeMode.last_crash && ` Last crash: ${safeMode.last_crash.message}`}
+                          </span>
+                          <button
+                            type="button"
+                            onClick={() => void handleSafeModeAction("cmd_check_database", "")}
+                          >
+                            Check database
+                          </button>
+                          <button
+                            type="button"
+                            onClick={() =>
+                              void handleSafeModeAction("cmd_purge_cache", "Cached data was purged.")
+                            }
+                          >
+                            Purge cache
+                          </button>
+                          <button
+                            type="button"
+                            onClick={() =>
+                              void handleSafeModeAction(
+                                "cmd_leave_safe_mode",
+                                "The app will start normally next time.",
+                              )
+                            }
+                          >
+                            Start normally next time
+                          </button>
+                          {safeModeMessage && <span>{safeModeMessage}</span>}
+                        </div>
+                      )}
+                      {prMode === "under-review" && staleReviews.length > 0 && (
+                        <div className="stale-reviews-notice">
+                          <span>
+                            {staleReviews.length === 1
+                              ? "1 review is for a merged or closed PR."
+                              : `${staleReviews.length} reviews are for merged or closed PRs.`}
+                          </span>
+                          <button
+                            type="button"
+                            onClick={() => void handleClearStaleReviews()}
+                            disabled={isClearingStaleReviews}
+                          >
+                            {isClearingStaleReviews ? "Clearing…" : "Move to trash"}
+                          </button>
+                          <button type="button" onClick={() => setStaleReviews([])}>
+                            Dismiss
+                          </button>
+                        </div>
+                      )}
                       <div className="panel__scroll-content">
                         {prMode === "under-review" ? (
                           // Only show loading if we have no results yet
@@ -5987,12 +6542,23 @@ function App() {
                           </div>
                         ) : (
                           <>
+                            {(prDetail.skipped_files?.length ?? 0) > 0 && (
+                              <div
+                                className="empty-state empty-state--subtle"
+                                title={prDetail.skipped_files
+                                  ?.map((file) => `${file.path}: ${file.reason}`)
+                                  .join("\n")}
+                              >
+                                {prDetail.skipped_files?.length} file(s) could not be loaded
+                              </div>
+                            )}
                             <ul className="file-list file-list--compact" ref={fileListScrollRef}>
                               {visibleFiles.map((file) => {
                                 const displayName = formatFileLabel(file.path, tocFileNameMap);
                                 const tooltip = formatFileTooltip(file);
                                 const commentCount = getFileCommentCount(file.path);
                                 const viewed = isFileViewed(file.path);
+                                const mayConflict = conflictingFiles.has(file.path);
 
                                 return (
                                   <li key={file.path} className="file-list__item">
@@ -6013,7 +6579,26 @@ function App() {
                                       onClick={() => navigateToFile(file.path)}
                                       title={tooltip}
                                     >
-                                      <span className="file-list__name">{displayName}</span>
+                                      <span
+                                        className={`file-list__name${
+                                          file.status === "removed" ? " file-list__name--removed" : ""
+                                        }`}
+                                      >
+                                        {displayName}
+                                      </span>
+                                      {file.status === "removed" && (
+                                        <span className="file-list__removed" title="Deleted by this PR">
+                                          deleted
+                                        </span>
+                                      )}
+                                      {mayConflict && (
+                                        <span
+                                          className="file-list__conflict"
+                                          title="Also changed on the base branch; likely conflicts until the author rebases"
+                                        >
+                                          ⚠
+                                        </span>
+                                      )}
                                       <span className="file-list__badge-wrapper">
                                         {commentCount > 0 ? (
                                           <span
@@ -6087,6 +6672,7 @@ function App() {
                                     {isLocalDirectoryMode
                                       ? `Saving comment ${submissionProgress.current} of ${submissionProgress.total}...`
                                       : `Submitting comment ${submissionProgress.current} of ${submissionProgress.total}...`}
+                                    {formatRateLimitPause(submissionProgress)}
                                   </div>
                                 )}
                                 <div className="pr-comments-view__footer-buttons">
@@ -6335,6 +6921,7 @@ function App() {
                           const modifiedEditor = editor.getModifiedEditor();
                           if (modifiedEditor) {
                             modifiedEditor.onDidScrollChange(() => {
+                              recordScrollAnchor(modifiedEditor);
                               const fileKey = selectedFileCacheKeyRef.current;
                               if (!fileKey) {
                                 return;
@@ -6473,6 +7060,7 @@ function App() {
                           
                           // Scroll synchronization (anchor-based for markdown)
                           editor.onDidScrollChange(() => {
+                            recordScrollAnchor(editor);
                             if (!previewViewerRef.current) return;
                             
                             // Skip if we're currently re-enforcing to avoid loops
@@ -6639,7 +7227,7 @@ function App() {
                                 
                                 // Handle external URLs
                                 if (clickedUrl.startsWith('http://') || clickedUrl.startsWith('https://')) {
-                                  void invoke('cmd_open_url', { url: clickedUrl });
+                                  void invoke('cmd_open_url', { args: { url: clickedUrl } });
                                   return;
                                 }
                                 
@@ -6826,7 +7414,7 @@ function App() {
                         }}
                       >
                         <ReactMarkdown 
-                          remarkPlugins={[remarkGfm, [remarkFrontmatter, { type: 'yaml', marker: '-' }]]}
+                          remarkPlugins={remarkPreviewPlugins}
                           rehypePlugins={[rehypeRaw, rehypeSanitize]}
                           components={{
                             ...markdownComponents,
@@ -6850,7 +7438,7 @@ function App() {
                                 // Check if it's an external URL
                                 if (href.startsWith('http://') || href.startsWith('https://')) {
                                   // Open external links in browser
-                                  void invoke('cmd_open_url', { url: href });
+                                  void invoke('cmd_open_url', { args: { url: href } });
                                 } else if (prDetail && selectedFile) {
                                   // Handle relative file paths within the PR
                                   let resolvedPath = href;
@@ -6979,10 +7567,12 @@ function App() {
                                 onClick={async (_e: React.MouseEvent<HTMLImageElement>) => {
                                   try {
                                     const base64Data = await invoke<string>("cmd_fetch_file_content", {
-                                      owner: repoRef.owner,
-                                      repo: repoRef.repo,
-                                      reference: prDetail.head_sha,
-                                      path: resolvedPath
+                                      args: {
+                                        owner: repoRef.owner,
+                                        repo: repoRef.repo,
+                                        reference: prDetail.head_sha,
+                                        path: resolvedPath,
+                                      },
                                     });
                                     const mimeType = getImageMimeType(resolvedPath);
                                     setMediaViewerContent({ type: 'image', content: `data:${mimeType};base64,${base64Data}` });
@@ -7041,6 +7631,21 @@ function App() {
         </div>
       </section>
 
+      {pendingReset && (
+        <ConfirmDialog
+          title={pendingReset === RESET_EVERYTHING ? "Remove All App Data" : "Logout, Keep Drafts"}
+          message={
+            pendingReset === RESET_EVERYTHING
+              ? "This signs you out and deletes cached data, local reviews and drafts, logs, recordings and settings. It cannot be undone."
+              : "This signs you out and clears cached pull request data. Local reviews and drafts are kept."
+          }
+          confirmText={pendingReset === RESET_EVERYTHING ? "Remove Everything" : "Logout"}
+          isDanger={pendingReset === RESET_EVERYTHING}
+          onClose={() => setPendingReset(null)}
+          onConfirm={() => resetAppData(pendingReset)}
+        />
+      )}
+
       {showDeleteConfirm && (
         <ConfirmDialog
           title="Delete Comment"
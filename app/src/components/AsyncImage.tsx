@@ -60,10 +60,12 @@ function AsyncImage({
     const fetchImage = async () => {
       try {
         const base64Data = await invoke<string>("cmd_fetch_file_content", {
-          owner,
-          repo,
-          reference,
-          path
+          args: {
+            owner,
+            repo,
+            reference,
+            path,
+          },
         });
         
         if (!cancelled) {
@@ -8,6 +8,7 @@
 // =============================================================================
 
 export const AUTH_QUERY_KEY = ["auth-status"] as const;
+export const ACCOUNTS_QUERY_KEY = ["accounts"] as const;
 
 // =============================================================================
 // Retry Configuration
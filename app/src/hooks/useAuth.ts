@@ -5,9 +5,18 @@
 
 import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
-import { useEffect } from "react";
-import type { AuthStatus } from "../types";
-import { AUTH_QUERY_KEY } from "../constants";
+import { listen } from "@tauri-apps/api/event";
+import { useEffect, useState } from "react";
+import type {
+  AccountProfile,
+  AuthStatus,
+  DeviceCodePrompt,
+  LoginMethod,
+  ResetOptions,
+  ResumedSubmission,
+} from "../types";
+import { ACCOUNTS_QUERY_KEY, AUTH_QUERY_KEY } from "../constants";
+import { resetAppData } from "../utils/resetAppData";
 
 export interface UseAuthOptions {
   /** Callback to mark the app as offline */
@@ -37,14 +46,30 @@ export interface UseAuthReturn {
   userLogin: string | null;
   /** Current user's avatar URL */
   avatarUrl: string | null;
-  /** Function to trigger login */
-  startLogin: () => void;
+  /** Function to trigger login; the device flow suits machines that block local listeners */
+  startLogin: (method?: LoginMethod) => void;
   /** Whether login is in progress */
   isLoggingIn: boolean;
+  /** Code to enter on GitHub while a device sign-in is waiting */
+  deviceCode: DeviceCodePrompt | null;
   /** Function to trigger logout */
   logout: () => void;
   /** Whether logout is in progress */
   isLoggingOut: boolean;
+  /** Function to remove the chosen kinds of app data */
+  resetAppData: (options: ResetOptions) => void;
+  /** Whether a reset is in progress */
+  isResetting: boolean;
+  /** Saved GitHub accounts, e.g. work and personal */
+  accounts: AccountProfile[];
+  /** Function to make another saved account the one requests use */
+  switchAccount: (name: string) => void;
+  /** Function to add a named account and sign in to it */
+  addAccount: (name: string, method?: LoginMethod) => void;
+  /** Function to forget a saved account and its token */
+  removeAccount: (name: string) => void;
+  /** Whether an account is being switched, added or removed */
+  isChangingAccount: boolean;
   /** Function to refetch auth status */
   refetch: () => void;
 }
@@ -106,13 +131,112 @@ export function useAuth(options: UseAuthOptions = {}) {
     }
   }, [isOnline, authQuery.data?.is_offline]);
 
+  // GitHub rejected the token (revoked or expired): the backend already signed out
+  useEffect(() => {
+    const unlisten = listen<{ context: string }>("auth-expired", (event) => {
+      console.warn("GitHub session expired during:", event.payload.context);
+      queryClient.setQueryData<AuthStatus>(AUTH_QUERY_KEY, {
+        is_authenticated: false,
+        login: null,
+        avatar_url: null,
+        is_offline: false,
+      });
+      queryClient.removeQueries({ queryKey: ["pull-requests"] });
+      queryClient.removeQueries({ queryKey: ["pull-request"] });
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [queryClient]);
+
+  // The device flow reports the code to enter while the sign-in command is still running
+  const [deviceCode, setDeviceCode] = useState<DeviceCodePrompt | null>(null);
+  useEffect(() => {
+    const unlisten = listen<DeviceCodePrompt>("oauth-device-code", (event) => {
+      setDeviceCode(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const loginMutation = useMutation({
-    mutationFn: async () => {
-      const status = await invoke<AuthStatus>("cmd_start_github_oauth");
+    mutationFn: async (method: LoginMethod = "browser") => {
+      const status = await invoke<AuthStatus>("cmd_start_github_oauth", { args: { method } });
       return status;
     },
-    onSuccess: (status) => {
+    onSettled: () => {
+      setDeviceCode(null);
+    },
+    onSuccess: async (status) => {
       queryClient.setQueryData(AUTH_QUERY_KEY, status);
+      if (!status.is_authenticated) return;
+
+      // Finish submissions that were interrupted when the previous token was revoked
+      try {
+        const resumed = await invoke<ResumedSubmission[]>("cmd_resume_parked_submissions");
+        for (const result of resumed) {
+          if (result.error) {
+            console.error(
+              `Resuming submission for ${result.owner}/${result.repo}#${result.pr_number} failed:`,
+              result.error,
+            );
+          }
+        }
+        if (resumed.length > 0) {
+          queryClient.invalidateQueries({ queryKey: ["pull-request"] });
+        }
+      } catch (error) {
+        console.error("Failed to resume parked submissions:", error);
+      }
+    },
+  });
+
+  const accountsQuery = useQuery({
+    queryKey: ACCOUNTS_QUERY_KEY,
+    queryFn: () => invoke<AccountProfile[]>("cmd_list_accounts"),
+    enabled: authQuery.data?.is_authenticated === true,
+  });
+
+  // Another account's token is used from now on; drop what the previous one loaded
+  const applyAccountStatus = (status: AuthStatus) => {
+    queryClient.setQueryData(AUTH_QUERY_KEY, status);
+    queryClient.removeQueries({ queryKey: ["pull-requests"] });
+    queryClient.removeQueries({ queryKey: ["pull-request"] });
+    queryClient.invalidateQueries({ queryKey: ACCOUNTS_QUERY_KEY });
+  };
+
+  const switchAccountMutation = useMutation({
+    mutationFn: (name: string) =>
+      invoke<AuthStatus>("cmd_switch_account", { args: { name } }),
+    onSuccess: applyAccountStatus,
+    onError: (error) => {
+      console.error("Failed to switch account:", error);
+    },
+  });
+
+  const addAccountMutation = useMutation({
+    mutationFn: ({ name, method = "browser" }: { name: string; method?: LoginMethod }) =>
+      invoke<AuthStatus>("cmd_add_account", { args: { name, method } }),
+    onSettled: () => {
+      setDeviceCode(null);
+    },
+    onSuccess: applyAccountStatus,
+    onError: (error) => {
+      console.error("Failed to add account:", error);
+    },
+  });
+
+  const removeAccountMutation = useMutation({
+    mutationFn: (name: string) =>
+      invoke<AccountProfile[]>("cmd_remove_account", { args: { name } }),
+    onSuccess: (accounts) => {
+      queryClient.setQueryData(ACCOUNTS_QUERY_KEY, accounts);
+      // Removing the active account switches to another one
+      void authQuery.refetch();
+    },
+    onError: (error) => {
+      console.error("Failed to remove account:", error);
     },
   });
 
@@ -133,6 +257,26 @@ export function useAuth(options: UseAuthOptions = {}) {
     },
   });
 
+  const resetMutation = useMutation({
+    mutationFn: resetAppData,
+    onSuccess: (_data, options) => {
+      if (options.token) {
+        queryClient.setQueryData<AuthStatus>(AUTH_QUERY_KEY, {
+          is_authenticated: false,
+          login: null,
+          avatar_url: null,
+          is_offline: false,
+        });
+        onLogoutSuccess?.();
+      }
+      queryClient.removeQueries({ queryKey: ["pull-requests"] });
+      queryClient.removeQueries({ queryKey: ["pull-request"] });
+    },
+    onError: (error) => {
+      console.error("Failed to reset app data:", error);
+    },
+  });
+
   return {
     // Auth state
     authStatus: authQuery.data,
@@ -145,13 +289,29 @@ export function useAuth(options: UseAuthOptions = {}) {
     avatarUrl: authQuery.data?.avatar_url ?? null,
     
     // Login
-    startLogin: () => loginMutation.mutate(),
+    startLogin: (method?: LoginMethod) => loginMutation.mutate(method),
     isLoggingIn: loginMutation.isPending,
+    deviceCode,
     
     // Logout
     logout: () => logoutMutation.mutate(),
     isLoggingOut: logoutMutation.isPending,
+
+    // Reset
+    resetAppData: (options: ResetOptions) => resetMutation.mutate(options),
+    isResetting: resetMutation.isPending,
     
+    // Accounts
+    accounts: accountsQuery.data ?? [],
+    switchAccount: (name: string) => switchAccountMutation.mutate(name),
+    addAccount: (name: string, method?: LoginMethod) =>
+      addAccountMutation.mutate({ name, method }),
+    removeAccount: (name: string) => removeAccountMutation.mutate(name),
+    isChangingAccount:
+      switchAccountMutation.isPending ||
+      addAccountMutation.isPending ||
+      removeAccountMutation.isPending,
+
     // Refetch
     refetch: () => authQuery.refetch(),
   };
@@ -30,8 +30,19 @@ import type {
   PullRequestDetail,
   FileDrafts,
   LocalComment,
+  SubmissionReport,
+  Suggestion,
 } from "../types";
 import * as offlineCache from "../offlineCache";
+
+/** A local review submission that left some comments unposted. */
+class SubmissionReportError extends Error {
+  constructor(readonly report: SubmissionReport) {
+    super(report.summary ?? `${report.failed.length} comment(s) could not be submitted.`);
+  }
+}
+import { requestConfirmation } from "../utils/confirmation";
+import { withScopeUpgrade } from "../utils/scopeUpgrade";
 import { convertLocalComments } from "../utils/helpers";
 
 // =============================================================================
@@ -84,6 +95,8 @@ export interface SubmitCommentParams {
   inReplyTo?: number | null;
   /** Explicit file path (defaults to selectedFilePath) */
   filePath?: string;
+  /** Replacement for a line range, posted as a suggested change */
+  suggestion?: Suggestion | null;
 }
 
 /**
@@ -107,11 +120,15 @@ export interface UseCommentMutationsReturn {
   /** Submit all review comments to GitHub */
   submitReviewMutation: UseMutationResult<void, unknown, void, unknown>;
   /** Delete a review (handles both local and GitHub reviews) */
-  deleteReviewMutation: UseMutationResult<void, unknown, DeleteReviewParams, unknown>;
+  /** Resolves to false when the user declined the delete. */
+  deleteReviewMutation: UseMutationResult<boolean, unknown, DeleteReviewParams, unknown>;
   /** Update an existing comment (local or GitHub) */
   updateCommentMutation: UseMutationResult<void, unknown, { commentId: number; body: string }, unknown>;
   /** Delete a comment (local or GitHub) */
-  deleteCommentMutation: UseMutationResult<void, unknown, number, unknown>;
+  /** Resolves to false when the user declined the delete. */
+  deleteCommentMutation: UseMutationResult<boolean, unknown, number, unknown>;
+  /** Turn a local line comment into a general PR comment */
+  convertToGeneralCommentMutation: UseMutationResult<void, unknown, number, unknown>;
   
   // Local comments state
   localComments: PullRequestComment[];
@@ -208,9 +225,11 @@ export function useCommentMutations(
     }
     try {
       const localCommentData = await invoke<LocalComment[]>("cmd_local_get_comments", {
-        owner: repoRef.owner,
-        repo: repoRef.repo,
-        prNumber: prDetail.number,
+        args: {
+          owner: repoRef.owner,
+          repo: repoRef.repo,
+          prNumber: prDetail.number,
+        },
       });
       
       // Convert to PullRequestComment format
@@ -264,10 +283,12 @@ export function useCommentMutations(
       // PR-level comment (issue comment API)
       if (type === "pr") {
         await invoke("cmd_submit_review_comment", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          number: prDetail.number,
-          body,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            number: prDetail.number,
+            body,
+          },
         });
         return;
       }
@@ -281,6 +302,7 @@ export function useCommentMutations(
         pendingReviewId = null,
         inReplyTo = null,
         filePath: explicitFilePath,
+        suggestion = null,
       } = params;
 
       const targetFilePath = explicitFilePath ?? selectedFilePath;
@@ -291,16 +313,19 @@ export function useCommentMutations(
       // Local folder mode: always save to local review/log storage.
       if (isLocalDirectoryMode) {
         await invoke("cmd_local_add_comment", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
-          filePath: targetFilePath,
-          lineNumber: line,
-          side,
-          body,
-          commitId: prDetail.head_sha,
-          inReplyToId: inReplyTo,
-          localFolder: activeLocalDir ?? null,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+            filePath: targetFilePath,
+            lineNumber: line,
+            side,
+            body,
+            commitId: prDetail.head_sha,
+            inReplyToId: inReplyTo,
+            localFolder: activeLocalDir ?? null,
+            suggestion,
+          },
         });
         return;
       }
@@ -308,15 +333,18 @@ export function useCommentMutations(
       // For review mode (local storage)
       if (mode === "review" || pendingReviewId) {
         await invoke("cmd_local_add_comment", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
-          filePath: targetFilePath,
-          lineNumber: line,
-          side,
-          body,
-          commitId: prDetail.head_sha,
-          inReplyToId: inReplyTo,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+            filePath: targetFilePath,
+            lineNumber: line,
+            side,
+            body,
+            commitId: prDetail.head_sha,
+            inReplyToId: inReplyTo,
+            suggestion,
+          },
         });
       } else {
         // For single comments, use the GitHub API
@@ -327,13 +355,14 @@ export function useCommentMutations(
             number: prDetail.number,
             path: targetFilePath,
             body,
-            commit_id: prDetail.head_sha,
+            commitId: prDetail.head_sha,
             line,
             side: line !== null ? side : null,
-            subject_type: subjectType,
+            subjectType,
             mode,
-            pending_review_id: pendingReviewId,
-            in_reply_to: inReplyTo,
+            pendingReviewId,
+            inReplyTo,
+            suggestion,
           },
         });
       }
@@ -381,11 +410,13 @@ export function useCommentMutations(
       }
 
       await invoke("cmd_local_start_review", {
-        owner: repoRef.owner,
-        repo: repoRef.repo,
-        prNumber: prDetail.number,
-        commitId: prDetail.head_sha,
-        body: null,
+        args: {
+          owner: repoRef.owner,
+          repo: repoRef.repo,
+          prNumber: prDetail.number,
+          commitId: prDetail.head_sha,
+          body: null,
+        },
       });
 
       return createLocalReview({
@@ -423,21 +454,28 @@ export function useCommentMutations(
       
       if (isGithubPendingReview) {
         await invoke("cmd_submit_pending_review", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          number: prDetail.number,
-          reviewId: pendingReview.id,
-          event: "COMMENT",
-          body: null,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            number: prDetail.number,
+            reviewId: pendingReview.id,
+            event: null,
+            body: null,
+          },
         });
       } else {
-        await invoke("cmd_submit_local_review", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
-          body: null,
-          event: "COMMENT",
+        const report = await invoke<SubmissionReport>("cmd_submit_local_review", {
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+            body: null,
+            event: null,
+          },
         });
+        if (report.failed.length > 0 || report.orphaned.length > 0) {
+          throw new SubmissionReportError(report);
+        }
       }
     },
     onSuccess: async () => {
@@ -463,8 +501,9 @@ export function useCommentMutations(
       })();
 
       const normalized = message.toLowerCase();
-      const isLockedConversation =
-        normalized.includes("cannot submit review comments because this pr conversation is locked") ||
+      const isLockedConversation = error instanceof SubmissionReportError
+        ? error.report.failed.some((failure) => failure.error_code === "locked")
+        : normalized.includes("cannot submit review comments because this pr conversation is locked") ||
         (normalized.includes("cannot submit review comments because pr #") && normalized.includes("is locked on github"));
 
       setFileCommentError(null);
@@ -493,23 +532,53 @@ export function useCommentMutations(
 
       if (isLocal) {
         // Delete local review (clears from SQLite and optionally saves to log file)
-        await invoke("cmd_local_clear_review", {
+        const confirmationToken = await requestConfirmation({
+          kind: "clear_local_review",
           owner: repoRef.owner,
           repo: repoRef.repo,
-          prNumber: prDetail.number,
-          prTitle: prTitle ?? prDetail.title ?? null,
+          pr_number: prDetail.number,
+        });
+        if (!confirmationToken) {
+          return false;
+        }
+        await invoke("cmd_local_clear_review", {
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            prNumber: prDetail.number,
+            prTitle: prTitle ?? prDetail.title ?? null,
+            confirmationToken,
+          },
         });
       } else {
         // Delete GitHub pending review
-        await invoke("cmd_delete_review", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          prNumber: prDetail.number,
-          reviewId,
+        return withScopeUpgrade(async () => {
+          const confirmationToken = await requestConfirmation({
+            kind: "delete_review",
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            pr_number: prDetail.number,
+            review_id: reviewId,
+          });
+          if (!confirmationToken) {
+            return false;
+          }
+          await invoke("cmd_delete_review", {
+            args: {
+              owner: repoRef.owner,
+              repo: repoRef.repo,
+              prNumber: prDetail.number,
+              reviewId,
+              confirmationToken,
+            },
+          });
+          return true;
         });
       }
+      return true;
     },
-    onSuccess: async () => {
+    onSuccess: async (deleted) => {
+      if (!deleted) return;
       setLocalComments([]);
       setFileCommentError(null);
       await invalidateAndRefetch();
@@ -530,16 +599,20 @@ export function useCommentMutations(
       
       if (isLocalComment) {
         await invoke("cmd_local_update_comment", {
-          commentId,
-          body,
+          args: {
+            commentId,
+            body,
+          },
         });
       } else {
         if (!repoRef) throw new Error("Repository information not available");
         await invoke("cmd_github_update_comment", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          commentId,
-          body,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            commentId,
+            body,
+          },
         });
       }
     },
@@ -569,18 +642,37 @@ export function useCommentMutations(
       
       if (isLocalComment) {
         await invoke("cmd_local_delete_comment", {
-          commentId,
+          args: {
+            commentId,
+          },
         });
       } else {
         if (!repoRef) throw new Error("Repository information not available");
-        await invoke("cmd_github_delete_comment", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          commentId,
+        return withScopeUpgrade(async () => {
+          const confirmationToken = await requestConfirmation({
+            kind: "delete_github_comment",
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            comment_id: commentId,
+          });
+          if (!confirmationToken) {
+            return false;
+          }
+          await invoke("cmd_github_delete_comment", {
+            args: {
+              owner: repoRef.owner,
+              repo: repoRef.repo,
+              commentId,
+              confirmationToken,
+            },
+          });
+          return true;
         });
       }
+      return true;
     },
-    onSuccess: async () => {
+    onSuccess: async (deleted) => {
+      if (!deleted) return;
       setFileCommentError(null);
       await invalidateAndRefetch();
       
@@ -595,6 +687,24 @@ export function useCommentMutations(
     },
   });
 
+  // ==========================================================================
+  // Convert to General Comment Mutation
+  // ==========================================================================
+
+  const convertToGeneralCommentMutation = useMutation({
+    mutationFn: async (commentId: number) => {
+      await invoke("cmd_convert_to_general_comment", { args: { commentId } });
+    },
+    onSuccess: async () => {
+      setFileCommentError(null);
+      await loadLocalComments();
+    },
+    onError: (error: unknown) => {
+      const message = error instanceof Error ? error.message : "Failed to convert comment.";
+      setFileCommentError(message);
+    },
+  });
+
   // ==========================================================================
   // Return
   // ==========================================================================
@@ -607,6 +717,7 @@ export function useCommentMutations(
     deleteReviewMutation,
     updateCommentMutation,
     deleteCommentMutation,
+    convertToGeneralCommentMutation,
     
     // Local comments
     localComments,
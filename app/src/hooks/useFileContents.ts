@@ -3,16 +3,18 @@
  * 
  * This hook handles:
  * - Looking up file metadata from PR details
- * - Loading file contents from GitHub API
+ * - Loading file contents from GitHub API, or from disk for local folders
  * - Caching content for offline access
  * - Falling back to cache when offline
+ * - Fetching GitHub's rendering of markdown files when that preview is chosen
  */
 
 import { useMemo } from "react";
 import { useQuery } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
-import type { PullRequestDetail, PullRequestFile, RepoRef } from "../types";
+import type { MarkdownPreview, PullRequestDetail, PullRequestFile, RepoRef } from "../types";
 import * as offlineCache from "../offlineCache";
+import { isMarkdownFile } from "../utils/helpers";
 
 export interface UseFileContentsOptions {
   /** The currently selected file path */
@@ -33,6 +35,8 @@ export interface UseFileContentsOptions {
   markOffline: () => void;
   /** Active local directory path (for query key) */
   activeLocalDir: string | null;
+  /** `github` fetches GitHub's HTML for markdown previews; `local` renders in the app */
+  markdownPreview: MarkdownPreview;
 }
 
 export interface FileContents {
@@ -53,13 +57,15 @@ export interface UseFileContentsResult {
   isError: boolean;
   /** Error message if loading failed */
   error: Error | null;
+  /** GitHub's HTML of the head version, when the GitHub preview is chosen and available */
+  renderedHeadHtml: string | null;
 }
 
 /**
  * Hook for loading file contents with offline caching support.
  * 
  * Behavior:
- * 1. In local directory mode, returns file metadata directly (content already embedded)
+ * 1. In local directory mode, reads the selected file from disk (folders load without contents)
  * 2. In PR mode, fetches content from GitHub API with network-first strategy
  * 3. Caches successful fetches for offline access
  * 4. Falls back to cache when network is unavailable
@@ -75,6 +81,7 @@ export function useFileContents(options: UseFileContentsOptions): UseFileContent
     markOnline,
     markOffline,
     activeLocalDir,
+    markdownPreview,
   } = options;
 
   // Look up file metadata from PR files list
@@ -96,17 +103,32 @@ export function useFileContents(options: UseFileContentsOptions): UseFileContent
     ],
     queryFn: async (): Promise<FileContents | null> => {
       if (!selectedFileMetadata || !prDetail || !repoRef || !selectedPr) return null;
+
+      // Local folders list their files without contents; read the selected one from disk
+      if (isLocalDirectoryMode) {
+        const headContent = await invoke<string>("cmd_read_local_file", {
+          args: {
+            baseId: prDetail.head_sha,
+            relPath: selectedFilePath,
+          },
+        });
+        return { headContent, baseContent: null };
+      }
       
       // Always try network first (to detect coming back online)
       try {
         const [headContent, baseContent] = await invoke<[string | null, string | null]>("cmd_get_file_contents", {
-          owner: repoRef.owner,
-          repo: repoRef.repo,
-          filePath: selectedFilePath,
-          baseSha: prDetail.base_sha,
-          headSha: prDetail.head_sha,
-          status: selectedFileMetadata.status,
-          previousFilename: selectedFileMetadata.previous_filename ?? null,
+          args: {
+            owner: repoRef.owner,
+            repo: repoRef.repo,
+            filePath: selectedFilePath,
+            baseSha: prDetail.base_sha,
+            headSha: prDetail.head_sha,
+            headOwner: prDetail.head_owner ?? null,
+            headRepo: prDetail.head_repo ?? null,
+            status: selectedFileMetadata.status,
+            previousFilename: selectedFileMetadata.previous_filename ?? null,
+          },
         });
         
         // Successful network request - mark online
@@ -159,7 +181,7 @@ export function useFileContents(options: UseFileContentsOptions): UseFileContent
         throw error;
       }
     },
-    enabled: Boolean(selectedFileMetadata && prDetail && repoRef && !isLocalDirectoryMode),
+    enabled: Boolean(selectedFileMetadata && prDetail && repoRef),
     staleTime: Infinity, // File contents don't change for a given SHA
     retry: (failureCount, error) => {
       // Don't retry if offline and no cache available
@@ -172,6 +194,43 @@ export function useFileContents(options: UseFileContentsOptions): UseFileContent
     retryDelay: (attemptIndex) => Math.min(1000 * 2 ** attemptIndex, 30000),
   });
 
+  // GitHub's rendering needs a connection; without it the preview renders in the app
+  const wantsRenderedHtml =
+    markdownPreview === "github" &&
+    !isLocalDirectoryMode &&
+    isOnline &&
+    isMarkdownFile(selectedFileMetadata);
+  const renderedContentsQuery = useQuery({
+    queryKey: [
+      "rendered-file-contents",
+      repoRef?.owner,
+      repoRef?.repo,
+      selectedFilePath,
+      prDetail?.base_sha,
+      prDetail?.head_sha,
+    ],
+    queryFn: async (): Promise<string | null> => {
+      if (!selectedFileMetadata || !prDetail || !repoRef) return null;
+      const [headHtml] = await invoke<[string | null, string | null]>("cmd_get_rendered_file_contents", {
+        args: {
+          owner: repoRef.owner,
+          repo: repoRef.repo,
+          filePath: selectedFilePath,
+          baseSha: prDetail.base_sha,
+          headSha: prDetail.head_sha,
+          headOwner: prDetail.head_owner ?? null,
+          headRepo: prDetail.head_repo ?? null,
+          status: selectedFileMetadata.status,
+          previousFilename: selectedFileMetadata.previous_filename ?? null,
+        },
+      });
+      return headHtml;
+    },
+    enabled: wantsRenderedHtml && Boolean(prDetail && repoRef),
+    staleTime: Infinity,
+    retry: false,
+  });
+
   // Merge file metadata with loaded content
   const selectedFile = useMemo(() => {
     if (!selectedFileMetadata) return null;
@@ -190,5 +249,6 @@ export function useFileContents(options: UseFileContentsOptions): UseFileContent
     isLoading: fileContentsQuery.isLoading,
     isError: fileContentsQuery.isError,
     error: fileContentsQuery.error ?? null,
+    renderedHeadHtml: wantsRenderedHtml ? renderedContentsQuery.data ?? null : null,
   };
 }
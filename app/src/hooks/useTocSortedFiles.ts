@@ -103,13 +103,22 @@ export function useTocSortedFiles(options: UseTocSortedFilesOptions): UseTocSort
         return new Map<string, string>();
       }
 
-      // In local directory mode, TOC contents are already embedded in `head_content`.
+      // In local directory mode, TOC files are read from disk like any other file.
       if (isLocalDirectoryMode) {
         const contentMap = new Map<string, string>();
         for (const tocFile of tocFilesMetadata) {
-          const content = tocFile.head_content ?? "";
-          if (content) {
-            contentMap.set(tocFile.path, content);
+          try {
+            const content = await invoke<string>("cmd_read_local_file", {
+              args: {
+                baseId: prDetail.head_sha,
+                relPath: tocFile.path,
+              },
+            });
+            if (content) {
+              contentMap.set(tocFile.path, content);
+            }
+          } catch (error) {
+            console.warn(`Failed to read ${tocFile.path}:`, error);
           }
         }
         return contentMap;
@@ -124,12 +133,16 @@ export function useTocSortedFiles(options: UseTocSortedFilesOptions): UseTocSort
           const [headContent, baseContent] = await invoke<[string | null, string | null]>(
             "cmd_get_file_contents",
             {
-              owner: repoRef.owner,
-              repo: repoRef.repo,
-              filePath: tocFile.path,
-              baseSha: prDetail.base_sha,
-              headSha: prDetail.head_sha,
-              status: tocFile.status,
+              args: {
+                owner: repoRef.owner,
+                repo: repoRef.repo,
+                filePath: tocFile.path,
+                baseSha: prDetail.base_sha,
+                headSha: prDetail.head_sha,
+                headOwner: prDetail.head_owner ?? null,
+                headRepo: prDetail.head_repo ?? null,
+                status: tocFile.status,
+              },
             }
           );
 
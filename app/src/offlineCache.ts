@@ -270,6 +270,18 @@ export async function clearPRCache(owner: string, repo: string, prNumber: number
   });
 }
 
+export async function clearAllCache(): Promise<void> {
+  const db = await openDB();
+  const transaction = db.transaction(['fileContents', 'prDetails'], 'readwrite');
+  transaction.objectStore('fileContents').clear();
+  transaction.objectStore('prDetails').clear();
+
+  return new Promise((resolve, reject) => {
+    transaction.oncomplete = () => resolve();
+    transaction.onerror = () => reject(transaction.error);
+  });
+}
+
 // Run cleanup on startup
 if (typeof window !== 'undefined') {
   cleanExpiredCache().catch((error) => {
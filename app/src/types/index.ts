@@ -15,6 +15,26 @@ export type AuthStatus = {
   is_offline?: boolean;
 };
 
+/** A named GitHub account with its own stored token, from `cmd_list_accounts`. */
+export type AccountProfile = {
+  name: string;
+  /** GitHub login it last signed in as. */
+  login: string | null;
+  signed_in: boolean;
+  /** Requests use this account's token. */
+  active: boolean;
+};
+
+/** `browser` listens locally for GitHub's redirect; `device` has the user enter a code. */
+export type LoginMethod = "browser" | "device";
+
+/** Code to enter at `verification_uri` during a device sign-in (`oauth-device-code`). */
+export type DeviceCodePrompt = {
+  user_code: string;
+  verification_uri: string;
+  expires_in: number;
+};
+
 // =============================================================================
 // Repository Types
 // =============================================================================
@@ -39,12 +59,30 @@ export type PullRequestSummary = {
   state: string;
   merged: boolean;
   locked?: boolean;
+  size?: PullRequestSize | null;
+  /** Updated since the PR was last marked seen; false for PRs never opened. */
+  new_since_last_visit?: boolean;
+  /** My review was re-requested, new commits landed after it, or the author replied to me. */
+  needs_my_attention?: boolean;
+  /** Unsubmitted comments in the local review of this PR. */
+  pending_comment_count?: number;
 };
 
+export type PullRequestSize = {
+  class: "XS" | "S" | "M" | "L" | "XL" | "XXL";
+  reviewed_lines: number;
+  estimated_minutes: number;
+};
+
+/** How markdown previews are rendered: in the app, or by GitHub (`diff_view.markdown_preview`). */
+export type MarkdownPreview = "local" | "github";
+
 export type PullRequestMetadata = {
   state: string;
   merged: boolean;
   locked: boolean;
+  head_owner?: string | null;
+  head_repo?: string | null;
 };
 
 export type PullRequestDetail = {
@@ -54,10 +92,64 @@ export type PullRequestDetail = {
   author: string;
   head_sha: string;
   base_sha: string;
+  /** The fork the changes come from; null for a branch of the same repository. */
+  head_owner?: string | null;
+  head_repo?: string | null;
   files: PullRequestFile[];
   comments: PullRequestComment[];
+  /** Line comments grouped into conversations, oldest first. */
+  threads: PullRequestThread[];
   my_comments: PullRequestComment[];
   reviews: PullRequestReview[];
+  merge_status?: MergeStatus | null;
+  bookmarks?: Bookmark[];
+  last_visited_at?: string | null;
+  new_comment_count?: number;
+  /** Local folder files that could not be loaded. */
+  skipped_files?: SkippedFile[];
+  /** Served from the backend's offline cache because GitHub could not be reached. */
+  stale?: boolean;
+  /** When the cached copy was fetched; null for fresh results. */
+  cached_at?: string | null;
+};
+
+/** A local folder file left out of the review, and why. */
+export type SkippedFile = {
+  path: string;
+  reason: string;
+};
+
+/** A line comment and the replies to it, as grouped by the backend (`CommentThread`). */
+export type PullRequestThread = {
+  /** Id of the root comment. */
+  id: number;
+  path: string | null;
+  line: number | null;
+  side: string | null;
+  root: PullRequestComment;
+  /** Oldest first. */
+  replies: PullRequestComment[];
+  /** `null` when GitHub could not be asked. */
+  resolved: boolean | null;
+  outdated: boolean;
+};
+
+/** A pinned comment. `comment_id` is a GitHub id or a local comment id, per `source`. */
+export type Bookmark = {
+  owner: string;
+  repo: string;
+  pr_number: number;
+  source: "github" | "local";
+  comment_id: number;
+  note: string | null;
+  created_at: string;
+};
+
+export type MergeStatus = {
+  mergeable: "mergeable" | "conflicting" | "unknown";
+  merge_state: string;
+  /** Files that also changed on the base branch; conflicts are almost always among these. */
+  conflicting_files: string[];
 };
 
 // =============================================================================
@@ -76,6 +168,8 @@ export type PullRequestFile = {
   base_content?: string | null;
   language: FileLanguage;
   previous_filename?: string | null;
+  /** Source of a translation, or first translation of a source, when both changed. */
+  localized_pair?: string | null;
 };
 
 // =============================================================================
@@ -87,6 +181,8 @@ export type PullRequestComment = {
   body: string;
   author: string;
   created_at: string;
+  /** `created_at` in the user's locale and time zone. */
+  display_time?: string;
   url: string;
   path?: string | null;
   line?: number | null;
@@ -98,8 +194,26 @@ export type PullRequestComment = {
   review_id?: number | null;
   in_reply_to_id?: number | null;
   outdated?: boolean | null;
+  /** Posted by someone else since the last visit to the PR. */
+  new_since_last_visit?: boolean;
+  /** Empty for review summaries, which the API has no reactions for. */
+  reactions?: CommentReactions;
+};
+
+export type CommentReactions = {
+  thumbs_up: number;
+  thumbs_down: number;
+  laugh: number;
+  hooray: number;
+  confused: number;
+  heart: number;
+  rocket: number;
+  eyes: number;
 };
 
+/** Reaction names as the reaction commands take them. */
+export type ReactionContent = "+1" | "-1" | "laugh" | "hooray" | "confused" | "heart" | "rocket" | "eyes";
+
 export type CommentThread = {
   parent: PullRequestComment;
   replies: PullRequestComment[];
@@ -114,6 +228,8 @@ export type PullRequestReview = {
   state: string;
   author: string;
   submitted_at?: string | null;
+  /** `submitted_at` in the user's locale and time zone. */
+  display_time?: string | null;
   body?: string | null;
   html_url?: string | null;
   commit_id?: string | null;
@@ -127,12 +243,55 @@ export type PrUnderReview = {
   title: string;
   has_local_review: boolean;
   has_pending_review: boolean;
+  /** Files marked viewed, or with every hunk marked reviewed. */
   viewed_count: number;
+  /** Files with every hunk marked reviewed, from the backend. */
+  hunk_reviewed_files?: string[];
   total_count: number;
-  state?: string;
-  merged?: boolean;
-  locked?: boolean;
+  /** Head, state and lock as GitHub reports them now; null for local folders or offline. */
+  head_sha?: string | null;
+  state?: string | null;
+  merged?: boolean | null;
+  locked?: boolean | null;
   local_folder?: string | null;
+  /** Null for local folders or when GitHub could not be reached. */
+  needs_my_attention?: boolean | null;
+  /** Deadline for submitting the local review, RFC 3339 UTC. */
+  due_at?: string | null;
+  overdue?: boolean;
+  /** Unsubmitted comments in the local review. */
+  pending_comment_count?: number;
+};
+
+/** Hunks of a file marked reviewed, from `cmd_mark_hunk_reviewed`. */
+export type FileReviewProgress = {
+  file_path: string;
+  reviewed_hunks: number;
+  hunk_count: number;
+  /** Rounded down; 100 only when every hunk is reviewed. */
+  percent: number;
+};
+
+/** A comment being written when the session was saved. */
+export type DraftComment = {
+  /** Null for a general PR comment. */
+  file_path: string | null;
+  line: number | null;
+  in_reply_to_id?: number | null;
+  body: string;
+};
+
+/** Where the user was, from `cmd_get_session`, for restoring after a crash or restart. */
+export type SessionState = {
+  owner: string | null;
+  repo: string | null;
+  pr_number: number | null;
+  local_folder: string | null;
+  selected_file: string | null;
+  /** First line of the selected file in view. */
+  scroll_anchor_line: number | null;
+  draft: DraftComment | null;
+  saved_at?: string | null;
 };
 
 // =============================================================================
@@ -212,6 +371,17 @@ export type LocalComment = {
   created_at: string;
   updated_at: string;
   in_reply_to_id: number | null;
+  /** Posted in the PR conversation; `file_path`/`line_number` only record where it was written. */
+  general: boolean;
+  suggestion?: Suggestion | null;
+};
+
+/** Replacement text proposed for head lines `start_line`..`end_line`, posted as a suggestion block. */
+export type Suggestion = {
+  /** Empty to suggest deleting the lines. */
+  replacement: string;
+  start_line: number;
+  end_line: number;
 };
 
 export type ReviewMetadata = {
@@ -222,4 +392,393 @@ export type ReviewMetadata = {
   body: string | null;
   created_at: string;
   log_file_index: number;
+  due_at?: string | null;
+};
+
+/**
+ * A destructive backend call that must be confirmed first.
+ * Mirrors `confirmations::DestructiveAction` in the backend.
+ */
+export type DestructiveAction =
+  | { kind: "delete_review"; owner: string; repo: string; pr_number: number; review_id: number }
+  | { kind: "delete_github_comment"; owner: string; repo: string; comment_id: number }
+  | { kind: "clear_local_review"; owner: string; repo: string; pr_number: number }
+  | { kind: "reset_app_data"; options: ResetOptions };
+
+/** Kinds of app data `cmd_reset_app_data` removes. Mirrors `reset::ResetOptions`. */
+export type ResetOptions = {
+  token: boolean;
+  cached_data: boolean;
+  local_reviews: boolean;
+  logs: boolean;
+  settings: boolean;
+};
+
+export type ConfirmationToken = {
+  token: string;
+  action: DestructiveAction;
+  description: string;
+  expires_at: string;
+};
+
+export type TasklistExport = {
+  body: string;
+  item_count: number;
+  /** Set when the task list was posted as a conversation comment. */
+  comment_url: string | null;
+};
+
+/** File format of `cmd_export_review`, which returns the path it wrote, or null when the save dialog was cancelled. */
+export type ReviewExportFormat = "markdown" | "json" | "csv";
+
+/** File format of `cmd_import_comments`: CSV rows or markdown task list items of path, line and body. */
+export type PunchListFormat = "csv" | "markdown";
+
+/** How `cmd_submit_local_review` sends comments: anchored inline, or listed in one review body. */
+export type SubmissionMode = "inline" | "consolidated";
+
+/** A canned comment for `cmd_add_quick_comment`; the body supports `{{path}}`, `{{file}}`, `{{line}}`, etc. */
+export type QuickCommentTemplate = {
+  id: string;
+  label: string;
+  body: string;
+  category?: string | null;
+};
+
+export type QuickCommentConfig = {
+  templates: QuickCommentTemplate[];
+};
+
+/** A saved comment; `cmd_render_comment_template` fills in its placeholders. */
+export type CommentTemplate = {
+  id: number;
+  name: string;
+  body: string;
+  category?: string | null;
+  /** Id bound to a key when the template is also a quick comment. */
+  quick_key?: string | null;
+  created_at: string;
+  updated_at: string;
+};
+
+/** One pass over a review, e.g. accessibility, and the checks, checklist and templates it uses. */
+export type ReviewProfile = {
+  name: string;
+  description?: string | null;
+  /** Built-in checks, e.g. `alt_text` or `snippets`. */
+  analyzers: string[];
+  /** Analyzer plugins, by name. */
+  plugins: string[];
+  checklist: string[];
+  /** Comment template names; empty offers the whole library. */
+  templates: string[];
+};
+
+export type ReviewProfileConfig = {
+  profiles: ReviewProfile[];
+};
+
+/** The profile switched on for a PR with `cmd_set_active_profile`. */
+export type ActiveProfile = {
+  profile: ReviewProfile;
+  templates: CommentTemplate[];
+  missing_templates: string[];
+};
+
+/** A GitHub request quota, from `cmd_get_rate_limit` and the `rate-limit-low` event. */
+export type RateLimitStatus = {
+  /** `core`, `graphql`, `search` and so on. */
+  resource: string;
+  limit: number;
+  remaining: number;
+  reset_at: string;
+};
+
+/** Result of `cmd_get_usage_metrics`; counted only while the `usage_metrics` preference is on. */
+export type UsageMetrics = {
+  range: string;
+  enabled: boolean;
+  reviews_started: number;
+  comments_added: number;
+  reviews_submitted: number;
+  submission_failures: number;
+  api_requests: number;
+  api_latency_p50_ms: number | null;
+  api_latency_p90_ms: number | null;
+  api_latency_p99_ms: number | null;
+};
+
+/** Payload of the `api-deprecation` event and entries of `cmd_get_api_deprecations`. */
+export type ApiDeprecation = {
+  context: string;
+  endpoint: string;
+  deprecated: boolean;
+  deprecated_at: string | null;
+  sunset_at: string | null;
+  link: string | null;
+  /** The API version GitHub served, when it differs from the one requested. */
+  selected_version: string | null;
+  unsupported_version: boolean;
+  first_seen_at: string;
+};
+
+/** State of the GitHub request recorder; `path` is the file to attach to a bug report. */
+export type RecorderStatus = {
+  recording: boolean;
+  path: string | null;
+  exchange_count: number;
+};
+
+/** A draft comment GitHub did not accept; it stays in local storage. */
+export type FailedComment = {
+  comment_id: number;
+  /** Stable reason such as `line_not_in_diff`, `locked` or `auth_expired`. */
+  error_code: string;
+  message: string;
+};
+
+/** Result of `cmd_submit_local_review`, also returned by `cmd_get_last_submission_report`. */
+export type SubmissionReport = {
+  owner: string;
+  repo: string;
+  pr_number: number;
+  posted: number;
+  failed: FailedComment[];
+  review_url: string | null;
+  summary: string | null;
+  submitted_at: string;
+  /** Comments on files that left the PR; when any are listed nothing was submitted. */
+  orphaned: OrphanedComment[];
+};
+
+/** What can be done with a comment whose file is no longer in the PR. */
+export type OrphanAction = "move" | "convert" | "drop";
+
+/** A draft comment on a file the PR no longer touches. */
+export type OrphanedComment = {
+  comment_id: number;
+  file_path: string;
+  line_number: number;
+  /** The PR file this path was renamed to, if any. */
+  suggested_path: string | null;
+  actions: OrphanAction[];
+  /** Draft replies in this comment's thread, which go wherever the comment goes. */
+  reply_ids: number[];
+};
+
+/** A GitHub request refused until the token is granted `scope`. */
+export type ScopeUpgrade = {
+  context: string;
+  scope: string;
+};
+
+/** A submission interrupted by a revoked token, resumed after the next sign-in. */
+export type ParkedSubmission = {
+  owner: string;
+  repo: string;
+  pr_number: number;
+  review_id: number | null;
+  event: string | null;
+  body: string | null;
+  mode: SubmissionMode | null;
+  /** GitHub login the submission was made as; only that account resumes it. */
+  login: string;
+  parked_at: string;
+};
+
+export type ResumedSubmission = {
+  owner: string;
+  repo: string;
+  pr_number: number;
+  error: string | null;
+};
+
+/** Payload of the `comment-submit-progress` event. */
+export type SubmissionProgress = {
+  current: number;
+  total: number;
+  file: string;
+  /** Time left while the submission is paused. */
+  waitTimeMs?: number;
+  /** Set when the pause is for a GitHub rate limit. */
+  waitReason?: "rate_limit" | "secondary_rate_limit";
+};
+
+/** One of my comments found by `cmd_search_my_comments`. */
+export type CommentSearchHit = {
+  owner: string;
+  repo: string;
+  pr_number: number;
+  pr_title: string;
+  comment_id: number;
+  url: string;
+  /** `null` for comments in the PR conversation. */
+  path: string | null;
+  line: number | null;
+  created_at: string;
+  /** Text around the first match, with `…` where it was cut. */
+  snippet: string;
+};
+
+/** File format of `cmd_export_review_activity`, which returns what it wrote, or null when the save dialog was cancelled. */
+export type ActivityExportFormat = "json" | "csv";
+
+/** Result of `cmd_export_review_activity`. */
+export type ActivityExport = {
+  path: string;
+  reviews: number;
+  comments: number;
+};
+
+/** Result of `cmd_import_settings`. */
+export type SettingsImport = {
+  /** Sections that were saved, e.g. `preferences` or `quick_comments`. */
+  imported: string[];
+  /** Parts of the file that were not applied, and why. */
+  skipped: string[];
+};
+
+/** The panic that ended an earlier run. */
+export type CrashSummary = {
+  message: string;
+  location: string;
+  occurred_at: string;
+};
+
+/** Payload of the `safe-mode` event and result of `cmd_get_safe_mode_status`. */
+export type SafeModeStatus = {
+  active: boolean;
+  startup_crashes: number;
+  last_crash: CrashSummary | null;
+};
+
+/** Result of `cmd_check_database`. */
+export type DatabaseCheck = {
+  ok: boolean;
+  problems: string[];
+};
+
+/** A local review whose PR was merged or closed, from `cmd_prune_stale_reviews`. */
+export type StaleReview = {
+  owner: string;
+  repo: string;
+  pr_number: number;
+  merged: boolean;
+  /** Unsubmitted local comments that go to the trash with the review. */
+  pending_comment_count: number;
+};
+
+/** A syntax problem in a mermaid or PlantUML fence, from `cmd_validate_diagrams`. */
+export type DiagramIssue = {
+  language: "mermaid" | "plantuml";
+  /** 1-based line in the markdown content. */
+  line: number;
+  detail: string;
+};
+
+/** A local command run on fenced blocks of one language; `{file}` in `args` is the snippet. */
+export type SnippetCheck = {
+  language: string;
+  /** Absolute path; the backend asks the user in a native dialog before first running it. */
+  program: string;
+  args: string[];
+  extension: string;
+};
+
+export type SnippetChecksConfig = {
+  checks: SnippetCheck[];
+  timeout_secs: number;
+};
+
+/** A fenced block whose check failed, from `cmd_check_snippets`. */
+export type SnippetFinding = {
+  language: string;
+  /** 1-based line of the opening fence. */
+  line: number;
+  program: string;
+  output: string;
+};
+
+export type SpecKind = "open_api" | "swagger" | "json_schema";
+
+/** A problem in a changed spec file, from `cmd_validate_spec`. */
+export type SpecIssue = {
+  line: number;
+  /** JSON pointer to the offending value; empty for syntax errors. */
+  pointer: string;
+  detail: string;
+};
+
+export type SpecReport = {
+  /** `null` when the file is not an OpenAPI or JSON Schema document. */
+  kind: SpecKind | null;
+  issues: SpecIssue[];
+};
+
+/** A date field a markdown change left as it was, from `cmd_check_front_matter_freshness`. */
+export type StaleDateFinding = {
+  path: string;
+  line: number;
+  field: string;
+  value: string;
+  /** Today's date in the format of `value`; `null` when the format is not recognized. */
+  suggested_value: string | null;
+  suggested_comment: string;
+};
+
+export type HeadingStyleConfig = {
+  case: "any" | "sentence" | "title";
+  /** Deepest heading level allowed, 1-6. */
+  max_depth: number;
+  allow_skipped_levels: boolean;
+  /** Names that keep their capitalization in sentence case. */
+  proper_nouns: string[];
+};
+
+/** A heading that breaks the configured style, from `cmd_check_headings`. */
+export type HeadingFinding = {
+  line: number;
+  level: number;
+  heading: string;
+  kind: "case" | "too_deep" | "skipped_level";
+  detail: string;
+  /** The heading rewritten to the configured case, for `case` findings. */
+  suggested: string | null;
+};
+
+/** A translation and its source side by side, from `cmd_get_localized_pair`. */
+export type LocalizedPair = {
+  source_path: string;
+  source_locale: string;
+  /** `null` when the source does not exist at the requested ref. */
+  source_content: string | null;
+  translation_path: string;
+  translation_locale: string;
+  translation_content: string | null;
+};
+
+/** A heading anchor removed by the PR, usually because the heading was renamed. */
+export type ChangedAnchor = {
+  path: string;
+  old_anchor: string;
+  new_anchor: string | null;
+  line: number | null;
+};
+
+/** An in-repo link that still points at a removed anchor. */
+export type BrokenAnchorLink = {
+  source_path: string;
+  line: number;
+  target_path: string;
+  anchor: string;
+  suggested_anchor: string | null;
+};
+
+/** Result of `cmd_get_anchor_impact`. */
+export type AnchorImpactReport = {
+  changed_anchors: ChangedAnchor[];
+  broken_links: BrokenAnchorLink[];
+  indexed_files: number;
+  /** Only part of the repository's markdown was indexed. */
+  truncated: boolean;
 };
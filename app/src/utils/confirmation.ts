@@ -0,0 +1,16 @@
+/**
+ * Confirmation tokens for destructive backend commands.
+ * The backend rejects deletes that do not carry a token issued for that exact action.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { ConfirmationToken, DestructiveAction } from "../types";
+
+/**
+ * Asks the user to confirm `action` in a native dialog shown by the backend and returns
+ * the single-use token to pass to the destructive command, or null when they decline.
+ */
+export async function requestConfirmation(action: DestructiveAction): Promise<string | null> {
+  const confirmation = await invoke<ConfirmationToken | null>("cmd_request_confirmation", { args: { action } });
+  return confirmation?.token ?? null;
+}
@@ -0,0 +1,66 @@
+/**
+ * Granular removal of app data, from signing out while keeping drafts to removing
+ * every trace of the app.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { clearAllCache } from "../offlineCache";
+import type { ResetOptions } from "../types";
+import { requestConfirmation } from "./confirmation";
+
+/** Log out but keep local reviews, drafts, logs and settings. */
+export const RESET_KEEP_DRAFTS: ResetOptions = {
+  token: true,
+  cached_data: true,
+  local_reviews: false,
+  logs: false,
+  settings: false,
+};
+
+/** Remove all traces of the app. */
+export const RESET_EVERYTHING: ResetOptions = {
+  token: true,
+  cached_data: true,
+  local_reviews: true,
+  logs: true,
+  settings: true,
+};
+
+/** localStorage keys holding data fetched from GitHub. */
+const CACHED_KEY_PREFIXES = ["cached-", "mru-open-prs-", "mru-closed-prs-"];
+/** localStorage keys holding unsent drafts and per-PR review state. */
+const DRAFT_KEY_PREFIXES = ["drafts_", "viewed-files"];
+
+function removeKeys(prefixes: string[]) {
+  for (const key of Object.keys(localStorage)) {
+    if (prefixes.some((prefix) => key.startsWith(prefix))) {
+      localStorage.removeItem(key);
+    }
+  }
+}
+
+/**
+ * Removes the chosen data in the backend and its copies in the webview, once the user
+ * confirms the reset in the backend's dialog.
+ */
+export async function resetAppData(options: ResetOptions): Promise<void> {
+  const confirmationToken = await requestConfirmation({ kind: "reset_app_data", options });
+  if (!confirmationToken) {
+    return;
+  }
+  await invoke("cmd_reset_app_data", { args: { options, confirmationToken } });
+
+  if (Object.values(options).every(Boolean)) {
+    localStorage.clear();
+  } else {
+    if (options.token || options.cached_data) {
+      removeKeys(CACHED_KEY_PREFIXES);
+    }
+    if (options.local_reviews) {
+      removeKeys(DRAFT_KEY_PREFIXES);
+    }
+  }
+  if (options.cached_data) {
+    await clearAllCache();
+  }
+}
@@ -0,0 +1,33 @@
+/**
+ * Recovery for GitHub requests refused because the token lacks a scope: offer to sign
+ * in again with the extra scope, then retry the operation once.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
+import type { AuthStatus, ScopeUpgrade } from "../types";
+
+/**
+ * Runs `operation`. If it fails for a missing scope and the user agrees to grant it,
+ * signs in again with that scope and runs `operation` a second time. `operation` must
+ * redo every step, including requesting fresh confirmation tokens.
+ */
+export async function withScopeUpgrade<T>(operation: () => Promise<T>): Promise<T> {
+  try {
+    return await operation();
+  } catch (error) {
+    const upgrade = await invoke<ScopeUpgrade | null>("cmd_take_scope_upgrade");
+    if (!upgrade) throw error;
+
+    const granted = await ask(
+      `GitHub refused to ${upgrade.context} because this app lacks the "${upgrade.scope}" permission. ` +
+        "Sign in again to grant it and retry?",
+      { title: "Additional GitHub Permission Needed", kind: "warning", okLabel: "Grant and Retry" },
+    );
+    if (!granted) throw error;
+
+    const status = await invoke<AuthStatus>("cmd_upgrade_oauth_scope", { args: { scope: upgrade.scope } });
+    if (!status.is_authenticated) throw error;
+    return operation();
+  }
+}